@@ -1,8 +1,9 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Context as _;
 use clap::{Parser, Subcommand};
 use tokio::io::{self, AsyncReadExt};
+use zksync_anchoring::HttpMintlayerClient;
 use zksync_block_reverter::{
     eth_client::{
         clients::{Client, PKSigningClient},
@@ -94,6 +95,14 @@ enum Command {
         /// Flag that allows to roll back already executed blocks. It's ultra dangerous and required only for fixing external nodes.
         #[arg(long)]
         allow_executed_block_reversion: bool,
+        /// Flag that specifies if a compensating Mintlayer anchor should be sent to revoke IPFS data for rolled back
+        /// L1 batches already anchored on Mintlayer. Uses the `ML_RPC_URL`, `ML_RPC_USERNAME` and `ML_RPC_PASSWORD`
+        /// env variables.
+        #[arg(long, requires = "rollback_postgres")]
+        revoke_mintlayer_anchors: bool,
+        /// Only prints the rollback plan (what would be removed from each store) as JSON without modifying any state.
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Clears failed L1 transactions.
@@ -298,8 +307,10 @@ async fn main() -> anyhow::Result<()> {
             rollback_sk_cache,
             rollback_snapshots,
             allow_executed_block_reversion,
+            revoke_mintlayer_anchors,
+            dry_run,
         } => {
-            if !rollback_tree && rollback_postgres {
+            if !rollback_tree && rollback_postgres && !dry_run {
                 println!("You want to roll back Postgres DB without rolling back tree.");
                 println!(
                     "If the tree is not yet rolled back to this L1 batch, then the only way \
@@ -315,14 +326,16 @@ async fn main() -> anyhow::Result<()> {
             }
 
             if allow_executed_block_reversion {
-                println!("You want to roll back already executed blocks. It's impossible to restore them for the main node");
-                println!("Make sure you are doing it ONLY for external node");
-                println!("Are you sure? Print y/n");
+                if !dry_run {
+                    println!("You want to roll back already executed blocks. It's impossible to restore them for the main node");
+                    println!("Make sure you are doing it ONLY for external node");
+                    println!("Are you sure? Print y/n");
 
-                let mut input = [0u8];
-                io::stdin().read_exact(&mut input).await.unwrap();
-                if input[0] != b'y' && input[0] != b'Y' {
-                    std::process::exit(0);
+                    let mut input = [0u8];
+                    io::stdin().read_exact(&mut input).await.unwrap();
+                    if input[0] != b'y' && input[0] != b'Y' {
+                        std::process::exit(0);
+                    }
                 }
                 block_reverter.allow_rolling_back_executed_batches();
             }
@@ -338,6 +351,11 @@ async fn main() -> anyhow::Result<()> {
                             .await?,
                     );
                 }
+                if revoke_mintlayer_anchors {
+                    let mintlayer_client = HttpMintlayerClient::from_env()
+                        .context("HttpMintlayerClient::from_env()")?;
+                    block_reverter.enable_revoking_mintlayer_anchors(Arc::new(mintlayer_client));
+                }
             }
             if rollback_tree {
                 block_reverter.enable_rolling_back_merkle_tree(db_config.merkle_tree.path);
@@ -347,9 +365,16 @@ async fn main() -> anyhow::Result<()> {
                     .enable_rolling_back_state_keeper_cache(db_config.state_keeper_db_path);
             }
 
-            block_reverter
-                .roll_back(L1BatchNumber(l1_batch_number))
-                .await?;
+            if dry_run {
+                let plan = block_reverter
+                    .plan_rollback(L1BatchNumber(l1_batch_number))
+                    .await?;
+                println!("{}", serde_json::to_string_pretty(&plan)?);
+            } else {
+                block_reverter
+                    .roll_back(L1BatchNumber(l1_batch_number))
+                    .await?;
+            }
         }
         Command::ClearFailedL1Transactions => {
             block_reverter.clear_failed_l1_transactions().await?;
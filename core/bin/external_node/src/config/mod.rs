@@ -25,6 +25,7 @@ use zksync_node_api_server::{
     tx_sender::TxSenderConfig,
     web3::{state::InternalApiConfig, Namespace},
 };
+use zksync_node_db_pruner::RetentionPolicy;
 use zksync_protobuf_config::proto;
 use zksync_snapshots_applier::SnapshotsApplierConfig;
 use zksync_types::{
@@ -257,6 +258,19 @@ pub(crate) struct OptionalENConfig {
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     #[serde(default = "OptionalENConfig::default_max_batch_request_size")]
     pub max_batch_request_size: usize,
+    /// Maximum number of calls in a single `zks_simulateBundle` request. Default is 16.
+    #[serde(default = "OptionalENConfig::default_max_simulated_bundle_size")]
+    pub max_simulated_bundle_size: usize,
+    /// Timeout for executing all calls in a `zks_simulateBundle` request. Default is 10 seconds.
+    #[serde(default = "OptionalENConfig::default_simulate_bundle_timeout_ms")]
+    simulate_bundle_timeout_ms: u64,
+    /// Maximum number of transactions in a single `zks_sendBundle` request. Default is 16.
+    #[serde(default = "OptionalENConfig::default_max_submitted_bundle_size")]
+    pub max_submitted_bundle_size: usize,
+    /// Timeout for simulating all transactions in a `zks_sendBundle` request before submission.
+    /// Default is 10 seconds.
+    #[serde(default = "OptionalENConfig::default_submit_bundle_timeout_ms")]
+    submit_bundle_timeout_ms: u64,
     /// Maximum response body size in MiBs. Default is 10 MiB.
     #[serde(default = "OptionalENConfig::default_max_response_body_size_mb")]
     pub max_response_body_size_mb: usize,
@@ -374,6 +388,11 @@ pub(crate) struct OptionalENConfig {
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     #[serde(default = "OptionalENConfig::default_merkle_tree_stalled_writes_timeout_sec")]
     merkle_tree_stalled_writes_timeout_sec: u64,
+    /// Capacity of the LRU cache for internal Merkle tree nodes, measured in the number of nodes. The cache speeds up
+    /// creating Merkle proofs in the tree API (e.g., for `zks_getProof`); it is only used by tree readers, not by
+    /// the tree writer. Setting it to 0 disables the cache.
+    #[serde(default = "OptionalENConfig::default_merkle_tree_node_cache_capacity")]
+    pub merkle_tree_node_cache_capacity: usize,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -423,6 +442,21 @@ pub(crate) struct OptionalENConfig {
 
     #[serde(default)]
     pub snapshots_recovery_object_store: Option<ObjectStoreConfig>,
+    /// If specified, requires manifests of all recovered snapshots to be signed by this address (normally,
+    /// the operator address of the main node).
+    #[serde(default)]
+    pub snapshots_recovery_manifest_signer: Option<Address>,
+    /// If specified, the snapshot is recovered from IPFS instead of the object store. The value is the ID
+    /// of the Mintlayer transaction anchoring the snapshot. Requires `snapshots_recovery_ipfs_gateway_url`,
+    /// `snapshots_recovery_mintlayer_api_url` and `snapshots_recovery_manifest_signer` to be set as well.
+    #[serde(default)]
+    pub snapshots_recovery_ipfs_anchor_tx_id: Option<String>,
+    /// IPFS gateway used to fetch snapshot data during recovery from IPFS.
+    #[serde(default)]
+    pub snapshots_recovery_ipfs_gateway_url: Option<String>,
+    /// Mintlayer API server used to fetch the anchoring transaction during recovery from IPFS.
+    #[serde(default)]
+    pub snapshots_recovery_mintlayer_api_url: Option<String>,
 
     /// Enables pruning of the historical node state (Postgres and Merkle tree). The node will retain
     /// recent state and will continuously remove (prune) old enough parts of the state in the background.
@@ -442,6 +476,72 @@ pub(crate) struct OptionalENConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 7 days.
     #[serde(default = "OptionalENConfig::default_pruning_data_retention_sec")]
     pruning_data_retention_sec: u64,
+    /// If set, events outlive the pruned L1 batches and are removed once they are this old (in seconds).
+    /// Only has effect if greater than `pruning_data_retention_sec`.
+    #[serde(default)]
+    pruning_events_retention_sec: Option<u64>,
+    /// Same as `pruning_events_retention_sec`, but for user L2-to-L1 logs.
+    #[serde(default)]
+    pruning_l2_to_l1_logs_retention_sec: Option<u64>,
+    /// Same as `pruning_events_retention_sec`, but for call traces.
+    #[serde(default)]
+    pruning_call_traces_retention_sec: Option<u64>,
+    /// Same as `pruning_events_retention_sec`, but for transaction input, data and execution info.
+    #[serde(default)]
+    pruning_transaction_data_retention_sec: Option<u64>,
+    /// Data related to these addresses (transactions initiated by or sent to them, emitted events etc.)
+    /// is never pruned.
+    #[serde(default)]
+    pruning_retained_addresses: Vec<Address>,
+    /// If set, the pruner doesn't modify the database and only reports what would be pruned.
+    #[serde(default)]
+    pub pruning_dry_run: bool,
+
+    // Reorg recovery
+    /// Enables automatic recovery from reorgs detected while the node is running. If a reorg is detected,
+    /// the node stops its components, rolls back Postgres, the Merkle tree and the state keeper cache
+    /// to the last correct L1 batch, and resumes syncing without a restart.
+    ///
+    /// If enabled, the limits below also apply to reorgs detected on node start.
+    #[serde(default)]
+    pub reorg_auto_recovery_enabled: bool,
+    /// Maximum number of L1 batches that can be rolled back automatically. If a deeper reorg is detected,
+    /// the node stops with an error and must be reverted manually.
+    #[serde(default = "OptionalENConfig::default_reorg_auto_recovery_max_depth")]
+    pub reorg_auto_recovery_max_depth: u32,
+    /// Allows automatically rolling back L1 batches that were executed on L1. Disabled by default.
+    #[serde(default)]
+    pub reorg_auto_recovery_allow_executed_batches: bool,
+
+    // Verify-only mode
+    /// First L1 batch checked by the batch verifier (the `verifier` component) if no batches are verified yet.
+    /// If not set, verification starts from L1 batch #1.
+    #[serde(default)]
+    pub verifier_first_l1_batch: Option<L1BatchNumber>,
+    /// Mintlayer API server used by the batch verifier to check anchors of L1 batch commitments.
+    /// If not set, anchors are not checked.
+    #[serde(default)]
+    pub verifier_mintlayer_api_url: Option<String>,
+    /// IPFS gateway used by the batch verifier to fetch anchored commit operations. Required if
+    /// `verifier_mintlayer_api_url` is set.
+    #[serde(default)]
+    pub verifier_ipfs_gateway_url: Option<String>,
+
+    // Sync sources
+    /// Object store with the L2 block archive written by the main node (the `block_archiver` component).
+    /// If set, L2 blocks are synced from the archive instead of the main node JSON-RPC API.
+    #[serde(default)]
+    pub sync_archive_object_store: Option<ObjectStoreConfig>,
+    /// IPFS gateway used to sync L2 blocks from the archive pinned to IPFS by the main node. Chunk CIDs
+    /// are resolved using `sync_archive_cid_resolver_urls` and the main node JSON-RPC API.
+    /// Mutually exclusive with `sync_archive_object_store`.
+    #[serde(default)]
+    pub sync_archive_ipfs_gateway_url: Option<String>,
+    /// JSON-RPC URLs of external nodes used to resolve CIDs of the L2 block archive chunks pinned to IPFS
+    /// (external nodes syncing from IPFS record resolved CIDs and serve them via the `en` namespace).
+    /// Resolvers are queried in order before the main node, so that the main node is not a single point of failure.
+    #[serde(default)]
+    pub sync_archive_cid_resolver_urls: Vec<String>,
 }
 
 impl OptionalENConfig {
@@ -485,6 +585,26 @@ impl OptionalENConfig {
                 web3_json_rpc.max_batch_request_size,
                 default_max_batch_request_size
             ),
+            max_simulated_bundle_size: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.max_simulated_bundle_size,
+                default_max_simulated_bundle_size
+            ),
+            simulate_bundle_timeout_ms: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.simulate_bundle_timeout_ms,
+                default_simulate_bundle_timeout_ms
+            ),
+            max_submitted_bundle_size: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.max_submitted_bundle_size,
+                default_max_submitted_bundle_size
+            ),
+            submit_bundle_timeout_ms: load_optional_config_or_default!(
+                general_config.api_config,
+                web3_json_rpc.submit_bundle_timeout_ms,
+                default_submit_bundle_timeout_ms
+            ),
             max_response_body_size_mb: load_optional_config_or_default!(
                 general_config.api_config,
                 web3_json_rpc.max_response_body_size_mb,
@@ -593,6 +713,11 @@ impl OptionalENConfig {
                 merkle_tree.stalled_writes_timeout_sec,
                 default_merkle_tree_stalled_writes_timeout_sec
             ),
+            merkle_tree_node_cache_capacity: load_optional_config_or_default!(
+                general_config.db_config,
+                merkle_tree.node_cache_capacity,
+                default_merkle_tree_node_cache_capacity
+            ),
             database_long_connection_threshold_ms: load_config!(
                 general_config.postgres_config,
                 long_connection_threshold_ms
@@ -626,6 +751,22 @@ impl OptionalENConfig {
                 general_config.snapshot_recovery,
                 object_store
             ),
+            snapshots_recovery_manifest_signer: load_config!(
+                general_config.snapshot_recovery,
+                manifest_signer
+            ),
+            snapshots_recovery_ipfs_anchor_tx_id: load_config!(
+                general_config.snapshot_recovery,
+                ipfs_anchor_tx_id
+            ),
+            snapshots_recovery_ipfs_gateway_url: load_config!(
+                general_config.snapshot_recovery,
+                ipfs_gateway_url
+            ),
+            snapshots_recovery_mintlayer_api_url: load_config!(
+                general_config.snapshot_recovery,
+                mintlayer_api_url
+            ),
             pruning_chunk_size: load_optional_config_or_default!(
                 general_config.pruning,
                 chunk_size,
@@ -641,6 +782,32 @@ impl OptionalENConfig {
                 data_retention_sec,
                 default_pruning_data_retention_sec
             ),
+            pruning_events_retention_sec: load_config!(
+                general_config.pruning,
+                events_retention_sec
+            ),
+            pruning_l2_to_l1_logs_retention_sec: load_config!(
+                general_config.pruning,
+                l2_to_l1_logs_retention_sec
+            ),
+            pruning_call_traces_retention_sec: load_config!(
+                general_config.pruning,
+                call_traces_retention_sec
+            ),
+            pruning_transaction_data_retention_sec: load_config!(
+                general_config.pruning,
+                transaction_data_retention_sec
+            ),
+            pruning_retained_addresses: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.retained_addresses.clone())
+                .unwrap_or_default(),
+            pruning_dry_run: general_config
+                .pruning
+                .as_ref()
+                .map(|a| a.dry_run)
+                .unwrap_or_default(),
             protective_reads_persistence_enabled: general_config
                 .db_config
                 .as_ref()
@@ -664,6 +831,19 @@ impl OptionalENConfig {
             main_node_rate_limit_rps: enconfig
                 .main_node_rate_limit_rps
                 .unwrap_or_else(Self::default_main_node_rate_limit_rps),
+            reorg_auto_recovery_enabled: enconfig.reorg_auto_recovery_enabled.unwrap_or_default(),
+            reorg_auto_recovery_max_depth: enconfig
+                .reorg_auto_recovery_max_depth
+                .unwrap_or_else(Self::default_reorg_auto_recovery_max_depth),
+            reorg_auto_recovery_allow_executed_batches: enconfig
+                .reorg_auto_recovery_allow_executed_batches
+                .unwrap_or_default(),
+            verifier_first_l1_batch: enconfig.verifier_first_l1_batch,
+            verifier_mintlayer_api_url: enconfig.verifier_mintlayer_api_url.clone(),
+            verifier_ipfs_gateway_url: enconfig.verifier_ipfs_gateway_url.clone(),
+            sync_archive_object_store: enconfig.sync_archive_object_store.clone(),
+            sync_archive_ipfs_gateway_url: enconfig.sync_archive_ipfs_gateway_url.clone(),
+            sync_archive_cid_resolver_urls: enconfig.sync_archive_cid_resolver_urls.clone(),
             api_namespaces,
             contracts_diamond_proxy_addr: None,
         })
@@ -748,6 +928,10 @@ impl OptionalENConfig {
         30
     }
 
+    const fn default_merkle_tree_node_cache_capacity() -> usize {
+        16_384
+    }
+
     const fn default_fee_history_limit() -> u64 {
         1_024
     }
@@ -756,6 +940,22 @@ impl OptionalENConfig {
         500 // The default limit is chosen to be reasonably permissive.
     }
 
+    const fn default_max_simulated_bundle_size() -> usize {
+        16
+    }
+
+    const fn default_simulate_bundle_timeout_ms() -> u64 {
+        10_000
+    }
+
+    const fn default_max_submitted_bundle_size() -> usize {
+        16
+    }
+
+    const fn default_submit_bundle_timeout_ms() -> u64 {
+        10_000
+    }
+
     const fn default_max_response_body_size_mb() -> usize {
         10
     }
@@ -804,11 +1004,16 @@ impl OptionalENConfig {
         3_600 * 24 * 7 // 7 days
     }
 
+    const fn default_reorg_auto_recovery_max_depth() -> u32 {
+        10
+    }
+
     fn from_env() -> anyhow::Result<Self> {
         let mut result: OptionalENConfig = envy::prefixed("EN_")
             .from_env()
             .context("could not load external node config")?;
         result.snapshots_recovery_object_store = snapshot_recovery_object_store_config().ok();
+        result.sync_archive_object_store = sync_archive_object_store_config().ok();
         Ok(result)
     }
 
@@ -888,6 +1093,14 @@ impl OptionalENConfig {
         Duration::from_millis(self.mempool_cache_update_interval_ms)
     }
 
+    pub fn simulate_bundle_timeout(&self) -> Duration {
+        Duration::from_millis(self.simulate_bundle_timeout_ms)
+    }
+
+    pub fn submit_bundle_timeout(&self) -> Duration {
+        Duration::from_millis(self.submit_bundle_timeout_ms)
+    }
+
     pub fn pruning_removal_delay(&self) -> Duration {
         Duration::from_secs(self.pruning_removal_delay_sec.get())
     }
@@ -896,6 +1109,22 @@ impl OptionalENConfig {
         Duration::from_secs(self.pruning_data_retention_sec)
     }
 
+    pub fn pruning_retention_policy(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            events: self.pruning_events_retention_sec.map(Duration::from_secs),
+            l2_to_l1_logs: self
+                .pruning_l2_to_l1_logs_retention_sec
+                .map(Duration::from_secs),
+            call_traces: self
+                .pruning_call_traces_retention_sec
+                .map(Duration::from_secs),
+            transaction_data: self
+                .pruning_transaction_data_retention_sec
+                .map(Duration::from_secs),
+            retained_addresses: self.pruning_retained_addresses.clone(),
+        }
+    }
+
     #[cfg(test)]
     fn mock() -> Self {
         // Set all values to their defaults
@@ -1058,6 +1287,10 @@ pub(crate) struct ExperimentalENConfig {
     /// If not set, parallel persistence will be disabled.
     #[serde(default)] // Temporarily use a conservative option (sequential recovery) as default
     pub snapshots_recovery_tree_parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Enables bootstrapping the Merkle tree from a checkpoint stored in the snapshot object store, if the main node
+    /// has exported one for the snapshot L1 batch. Falls back to ordinary tree recovery otherwise.
+    #[serde(default)]
+    pub snapshots_recovery_tree_use_checkpoints: bool,
 
     // Commitment generator
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
@@ -1084,6 +1317,7 @@ impl ExperimentalENConfig {
             snapshots_recovery_drop_storage_key_preimages: false,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
+            snapshots_recovery_tree_use_checkpoints: false,
             commitment_generator_max_parallelism: None,
         }
     }
@@ -1114,6 +1348,10 @@ impl ExperimentalENConfig {
                 general_config.snapshot_recovery,
                 tree.parallel_persistence_buffer
             ),
+            snapshots_recovery_tree_use_checkpoints: general_config
+                .snapshot_recovery
+                .as_ref()
+                .map_or(false, |config| config.tree.use_checkpoints),
             snapshots_recovery_drop_storage_key_preimages: general_config
                 .snapshot_recovery
                 .as_ref()
@@ -1154,6 +1392,14 @@ pub(crate) fn snapshot_recovery_object_store_config() -> anyhow::Result<ObjectSt
         .context("failed loading snapshot object store config from env variables")
 }
 
+/// Configuration of the object store with the L2 block archive. Should be loaded optionally, only if
+/// the archive is used as a sync source.
+fn sync_archive_object_store_config() -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed("EN_SYNC_ARCHIVE_OBJECT_STORE_")
+        .from_env::<ObjectStoreConfig>()
+        .context("failed loading L2 block archive object store config from env variables")
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ApiComponentConfig {
     /// Address of the tree API used by this EN in case it does not have a
@@ -1349,10 +1595,16 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             l2_testnet_paymaster_addr: config.remote.l2_testnet_paymaster_addr,
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
+            max_simulated_bundle_size: config.optional.max_simulated_bundle_size,
+            simulate_bundle_timeout: config.optional.simulate_bundle_timeout(),
+            max_submitted_bundle_size: config.optional.max_submitted_bundle_size,
+            submit_bundle_timeout: config.optional.submit_bundle_timeout(),
             base_token_address: Some(config.remote.base_token_addr),
             filters_disabled: config.optional.filters_disabled,
             dummy_verifier: config.remote.dummy_verifier,
             l1_batch_commit_data_generator_mode: config.remote.l1_batch_commit_data_generator_mode,
+            // External nodes don't store TEE proofs.
+            tee_finalized_tag_enabled: false,
         }
     }
 }
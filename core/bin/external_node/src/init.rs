@@ -1,16 +1,20 @@
 //! EN initialization logic.
 
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use anyhow::Context as _;
+use zksync_anchoring::{HttpIpfsClient, HttpMintlayerClient};
 use zksync_config::ObjectStoreConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_sync::genesis::perform_genesis_if_needed;
 use zksync_object_store::ObjectStoreFactory;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
-use zksync_snapshots_applier::{SnapshotsApplierConfig, SnapshotsApplierTask};
-use zksync_types::{L1BatchNumber, L2ChainId};
+use zksync_snapshots_applier::{
+    IpfsSnapshotSource, SnapshotsApplierConfig, SnapshotsApplierMainNodeClient,
+    SnapshotsApplierTask,
+};
+use zksync_types::{Address, L1BatchNumber, L2ChainId};
 use zksync_web3_decl::client::{DynClient, L2};
 
 #[derive(Debug)]
@@ -18,7 +22,20 @@ pub(crate) struct SnapshotRecoveryConfig {
     /// If not specified, the latest snapshot will be used.
     pub snapshot_l1_batch_override: Option<L1BatchNumber>,
     pub drop_storage_key_preimages: bool,
+    /// If specified, snapshot manifests must be signed by this address.
+    pub manifest_signer: Option<Address>,
     pub object_store_config: Option<ObjectStoreConfig>,
+    /// If specified, snapshot data is taken from IPFS instead of the object store and the main node.
+    /// L1 batch and L2 block details, and tokens are still fetched from the main node.
+    pub ipfs: Option<IpfsSnapshotRecoveryConfig>,
+}
+
+#[derive(Debug)]
+pub(crate) struct IpfsSnapshotRecoveryConfig {
+    /// ID of the Mintlayer transaction anchoring the snapshot.
+    pub anchor_tx_id: String,
+    pub gateway_url: String,
+    pub mintlayer_api_url: String,
 }
 
 #[derive(Debug)]
@@ -91,20 +108,41 @@ pub(crate) async fn ensure_storage_initialized(
             )?;
 
             tracing::warn!("Proceeding with snapshot recovery. This is an experimental feature; use at your own risk");
-            let object_store_config = recovery_config.object_store_config.context(
-                "Snapshot object store must be presented if snapshot recovery is activated",
-            )?;
-            let object_store = ObjectStoreFactory::new(object_store_config)
-                .create_store()
-                .await?;
+            let main_node_client: Box<dyn SnapshotsApplierMainNodeClient> =
+                Box::new(main_node_client.for_component("snapshot_recovery"));
+            let (main_node_client, object_store) = if let Some(ipfs_config) = &recovery_config.ipfs
+            {
+                anyhow::ensure!(
+                    recovery_config.manifest_signer.is_some(),
+                    "Recovering from IPFS requires a snapshot manifest signer"
+                );
+                tracing::info!(
+                    "Recovering from snapshot published to IPFS and anchored in Mintlayer transaction {}",
+                    ipfs_config.anchor_tx_id
+                );
+                let ipfs = HttpIpfsClient::new(ipfs_config.gateway_url.clone());
+                let mintlayer = HttpMintlayerClient::new(ipfs_config.mintlayer_api_url.clone());
+                let source = IpfsSnapshotSource::resolve(
+                    Arc::new(ipfs),
+                    &mintlayer,
+                    &ipfs_config.anchor_tx_id,
+                )
+                .await
+                .context("failed resolving snapshot anchored on Mintlayer")?;
+                (source.wrap_client(main_node_client), source.object_store())
+            } else {
+                let object_store_config = recovery_config.object_store_config.context(
+                    "Snapshot object store must be presented if snapshot recovery is activated",
+                )?;
+                let object_store = ObjectStoreFactory::new(object_store_config)
+                    .create_store()
+                    .await?;
+                (main_node_client, object_store)
+            };
 
             let config = SnapshotsApplierConfig::default();
-            let mut snapshots_applier_task = SnapshotsApplierTask::new(
-                config,
-                pool,
-                Box::new(main_node_client.for_component("snapshot_recovery")),
-                object_store,
-            );
+            let mut snapshots_applier_task =
+                SnapshotsApplierTask::new(config, pool, main_node_client, object_store);
             if let Some(snapshot_l1_batch) = recovery_config.snapshot_l1_batch_override {
                 tracing::info!(
                     "Using a specific snapshot with L1 batch #{snapshot_l1_batch}; this may not work \
@@ -116,6 +154,10 @@ pub(crate) async fn ensure_storage_initialized(
                 tracing::info!("Dropping storage key preimages for snapshot storage logs");
                 snapshots_applier_task.drop_storage_key_preimages();
             }
+            if let Some(signer) = recovery_config.manifest_signer {
+                tracing::info!("Requiring snapshot manifests to be signed by {signer:?}");
+                snapshots_applier_task.require_signed_manifests(signer, l2_chain_id);
+            }
             app_health.insert_component(snapshots_applier_task.health_check())?;
 
             let recovery_started_at = Instant::now();
@@ -8,7 +8,7 @@ use tokio::{
     sync::{oneshot, watch, RwLock},
     task::{self, JoinHandle},
 };
-use zksync_block_reverter::{BlockReverter, NodeRole};
+use zksync_anchoring::{HttpIpfsClient, HttpMintlayerClient};
 use zksync_commitment_generator::{
     validation_task::L1BatchCommitmentModeValidationTask, CommitmentGenerator,
 };
@@ -35,10 +35,12 @@ use zksync_node_consensus as consensus;
 use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
 use zksync_node_fee_model::l1_gas_price::MainNodeFeeParamsFetcher;
 use zksync_node_sync::{
-    batch_status_updater::BatchStatusUpdater, external_io::ExternalIO,
-    tree_data_fetcher::TreeDataFetcher, validate_chain_ids_task::ValidateChainIdsTask, ActionQueue,
-    MainNodeHealthCheck, SyncState,
+    batch_status_updater::BatchStatusUpdater, batch_verifier::BatchVerifier,
+    block_archive::ArchiveSyncSource, external_io::ExternalIO, tree_data_fetcher::TreeDataFetcher,
+    validate_chain_ids_task::ValidateChainIdsTask, ActionQueue, FallbackSyncSource,
+    MainNodeHealthCheck, SyncSource, SyncState,
 };
+use zksync_object_store::ObjectStoreFactory;
 use zksync_reorg_detector::ReorgDetector;
 use zksync_shared_metrics::rustc::RUST_METRICS;
 use zksync_state::{PostgresStorageCaches, RocksdbStorageOptions};
@@ -47,7 +49,7 @@ use zksync_state_keeper::{
     StateKeeperPersistence, TreeWritesPersistence, ZkSyncStateKeeper,
 };
 use zksync_storage::RocksDB;
-use zksync_types::L2ChainId;
+use zksync_types::{url::SensitiveUrl, L2ChainId};
 use zksync_utils::wait_for_tasks::ManagedTasks;
 use zksync_web3_decl::{
     client::{Client, DynClient, L1, L2},
@@ -57,7 +59,8 @@ use zksync_web3_decl::{
 
 use crate::{
     config::ExternalNodeConfig,
-    init::{ensure_storage_initialized, SnapshotRecoveryConfig},
+    init::{ensure_storage_initialized, IpfsSnapshotRecoveryConfig, SnapshotRecoveryConfig},
+    reorg_recovery::ReorgRecovery,
 };
 
 mod config;
@@ -65,6 +68,7 @@ mod init;
 mod metadata;
 mod metrics;
 mod node_builder;
+mod reorg_recovery;
 #[cfg(test)]
 mod tests;
 
@@ -139,6 +143,7 @@ async fn run_tree(
             .merkle_tree_include_indices_and_filters_in_block_cache,
         memtable_capacity: config.optional.merkle_tree_memtable_capacity(),
         stalled_writes_timeout: config.optional.merkle_tree_stalled_writes_timeout(),
+        node_cache_capacity: config.optional.merkle_tree_node_cache_capacity,
         sealed_batches_have_protective_reads: config.optional.protective_reads_persistence_enabled,
         recovery: MetadataCalculatorRecoveryConfig {
             desired_chunk_size: config.experimental.snapshots_recovery_tree_chunk_size,
@@ -168,6 +173,17 @@ async fn run_tree(
             .await
             .context("failed initializing metadata calculator")?
             .with_recovery_pool(recovery_pool);
+    if config.experimental.snapshots_recovery_tree_use_checkpoints {
+        let object_store_config = config
+            .optional
+            .snapshots_recovery_object_store
+            .clone()
+            .context("Snapshot object store must be provided to use Merkle tree checkpoints")?;
+        let object_store = ObjectStoreFactory::new(object_store_config)
+            .create_store()
+            .await?;
+        metadata_calculator = metadata_calculator.with_checkpoint_store(object_store);
+    }
 
     let tree_reader = Arc::new(metadata_calculator.tree_reader());
     app_health.insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))?;
@@ -207,6 +223,55 @@ async fn run_tree(
     Ok(tree_reader)
 }
 
+/// Creates a sync source for L2 blocks if the node is configured to sync from an L2 block archive
+/// rather than from the main node. L2 blocks not available from the archive (e.g., ones newer than the last archived
+/// chunk), or blocks for which the archive fails, are fetched from the main node.
+async fn build_sync_source(
+    config: &ExternalNodeConfig,
+    connection_pool: ConnectionPool<Core>,
+    main_node_client: Box<DynClient<L2>>,
+) -> anyhow::Result<Option<Box<dyn SyncSource>>> {
+    let object_store_config = config.optional.sync_archive_object_store.clone();
+    let ipfs_gateway_url = config.optional.sync_archive_ipfs_gateway_url.clone();
+    Ok(match (object_store_config, ipfs_gateway_url) {
+        (Some(_), Some(_)) => {
+            anyhow::bail!(
+                "At most one L2 block archive (object store or IPFS) may be used as a sync source"
+            );
+        }
+        (Some(object_store_config), None) => {
+            tracing::info!("Syncing L2 blocks from the archive in object store");
+            let object_store = ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?;
+            let archive = ArchiveSyncSource::object_store(object_store);
+            Some(Box::new(FallbackSyncSource::new(archive, main_node_client)))
+        }
+        (None, Some(gateway_url)) => {
+            tracing::info!(
+                "Syncing L2 blocks from the archive pinned to IPFS via gateway {gateway_url}"
+            );
+            let ipfs = Arc::new(HttpIpfsClient::new(gateway_url));
+            let mut resolvers = vec![];
+            for url in &config.optional.sync_archive_cid_resolver_urls {
+                let url: SensitiveUrl = url
+                    .parse()
+                    .with_context(|| format!("invalid CID resolver URL `{url}`"))?;
+                let resolver = Client::http(url)
+                    .context("failed creating JSON-RPC client for CID resolver")?
+                    .for_network(config.required.l2_chain_id.into())
+                    .build();
+                resolvers.push(Box::new(resolver) as Box<DynClient<L2>>);
+            }
+            resolvers.push(main_node_client.clone());
+            let archive =
+                ArchiveSyncSource::ipfs(resolvers, ipfs).with_chunk_index(connection_pool);
+            Some(Box::new(FallbackSyncSource::new(archive, main_node_client)))
+        }
+        (None, None) => None,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_core(
     config: &ExternalNodeConfig,
@@ -258,6 +323,8 @@ async fn run_core(
     )
     .await?;
 
+    let sync_source =
+        build_sync_source(config, connection_pool.clone(), main_node_client.clone()).await?;
     task_handles.push(tokio::spawn({
         let config = config.consensus.clone();
         let secrets =
@@ -292,6 +359,7 @@ async fn run_core(
                     pool,
                     sync_state,
                     main_node_client,
+                    sync_source,
                     action_queue_sender,
                 ));
                 ctx.wait(stop_receiver.wait_for(|stop| *stop)).await??;
@@ -314,6 +382,10 @@ async fn run_core(
                 removal_delay: config.optional.pruning_removal_delay(),
                 pruned_batch_chunk_size: config.optional.pruning_chunk_size,
                 minimum_l1_batch_age,
+                retention: config.optional.pruning_retention_policy(),
+                // External nodes don't anchor batches on Mintlayer.
+                wait_for_mintlayer_anchoring: false,
+                dry_run: config.optional.pruning_dry_run,
             },
             connection_pool.clone(),
         );
@@ -571,6 +643,46 @@ async fn run_api(
     Ok(())
 }
 
+/// Spawns the batch verifier, which is the only component run in the verify-only mode.
+fn run_batch_verifier(
+    config: &ExternalNodeConfig,
+    connection_pool: ConnectionPool<Core>,
+    main_node_client: Box<DynClient<L2>>,
+    eth_client: Box<DynClient<L1>>,
+    task_handles: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    app_health: &AppHealthCheck,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let mut verifier = BatchVerifier::new(
+        main_node_client,
+        eth_client,
+        config.remote.diamond_proxy_addr,
+        connection_pool,
+    )?;
+    if let Some(number) = config.optional.verifier_first_l1_batch {
+        verifier = verifier.with_first_l1_batch(number);
+    }
+    if let Some(mintlayer_api_url) = config.optional.verifier_mintlayer_api_url.clone() {
+        let gateway_url = config
+            .optional
+            .verifier_ipfs_gateway_url
+            .clone()
+            .context("IPFS gateway URL must be specified to check Mintlayer anchors")?;
+        verifier = verifier.with_anchor_checks(
+            Arc::new(HttpMintlayerClient::new(mintlayer_api_url)),
+            Arc::new(HttpIpfsClient::new(gateway_url)),
+        );
+    } else {
+        tracing::info!(
+            "Mintlayer API server URL is not specified; Mintlayer anchors won't be checked"
+        );
+    }
+
+    app_health.insert_component(verifier.health_check())?;
+    task_handles.push(tokio::spawn(verifier.run(stop_receiver)));
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn init_tasks(
     config: &ExternalNodeConfig,
@@ -739,6 +851,9 @@ pub enum Component {
     TreeApi,
     TreeFetcher,
     Core,
+    /// Verify-only mode: checks L1 batches of the main node against L1 commitments (and optionally,
+    /// Mintlayer anchors) without syncing the state. Cannot be combined with other components.
+    Verifier,
 }
 
 impl Component {
@@ -751,6 +866,7 @@ impl Component {
             "tree_api" => Ok(&[Component::TreeApi]),
             "tree_fetcher" => Ok(&[Component::TreeFetcher]),
             "core" => Ok(&[Component::Core]),
+            "verifier" => Ok(&[Component::Verifier]),
             "all" => Ok(&[
                 Component::HttpApi,
                 Component::WsApi,
@@ -776,6 +892,10 @@ impl FromStr for ComponentsToRun {
                 acc.extend(components);
                 Ok::<_, Self::Err>(acc)
             })?;
+        anyhow::ensure!(
+            !components.contains(&Component::Verifier) || components.len() == 1,
+            "`verifier` component cannot be combined with other components"
+        );
         Ok(Self(components))
     }
 }
@@ -890,6 +1010,16 @@ impl NodeEnvironment for () {
     }
 }
 
+/// Outcome of running node components.
+#[derive(Debug)]
+enum NodeRunOutcome {
+    /// The node has stopped because of a stop signal or because one of its components has exited.
+    Stopped,
+    /// A reorg was detected while the node was running, and node components were stopped in order
+    /// to automatically roll back the node state.
+    ReorgDetected,
+}
+
 async fn run_node(
     mut env: impl NodeEnvironment,
     opt: &Cli,
@@ -901,6 +1031,40 @@ async fn run_node(
 ) -> anyhow::Result<()> {
     tracing::warn!("The external node is in the alpha phase, and should be used with caution.");
     tracing::info!("Started the external node");
+    // Signal handling outlives node components, which can be restarted during automatic reorg recovery.
+    let mut sigint_receiver = None;
+    loop {
+        let outcome = run_node_components(
+            &mut env,
+            &mut sigint_receiver,
+            opt,
+            config,
+            connection_pool.clone(),
+            singleton_pool_builder.clone(),
+            main_node_client.clone(),
+            eth_client.clone(),
+        )
+        .await?;
+        match outcome {
+            NodeRunOutcome::Stopped => return Ok(()),
+            NodeRunOutcome::ReorgDetected => {
+                tracing::info!("Restarting node components to recover from the detected reorg");
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_node_components(
+    env: &mut impl NodeEnvironment,
+    sigint_receiver: &mut Option<watch::Receiver<bool>>,
+    opt: &Cli,
+    config: &ExternalNodeConfig,
+    connection_pool: ConnectionPool<Core>,
+    singleton_pool_builder: ConnectionPoolBuilder<Core>,
+    main_node_client: Box<DynClient<L2>>,
+    eth_client: Box<DynClient<L1>>,
+) -> anyhow::Result<NodeRunOutcome> {
     let (stop_sender, mut stop_receiver) = watch::channel(false);
     let stop_sender = Arc::new(stop_sender);
 
@@ -964,7 +1128,35 @@ async fn run_node(
     let mut task_handles = vec![metrics_task, validate_chain_ids_task];
     task_handles.extend(prometheus_task);
 
+    // In the verify-only mode, the node doesn't sync the state, so storage initialization and reorg detection are skipped.
+    let is_verify_only = opt.components.0.contains(&Component::Verifier);
     // Make sure that the node storage is initialized either via genesis or snapshot recovery.
+    let ipfs_recovery_config = config
+        .optional
+        .snapshots_recovery_ipfs_anchor_tx_id
+        .clone()
+        .map(|anchor_tx_id| {
+            // IPFS gateways and the anchoring transaction are untrusted, so the snapshot must be authenticated
+            // by the operator signature.
+            anyhow::ensure!(
+                config.optional.snapshots_recovery_manifest_signer.is_some(),
+                "snapshot manifest signer must be specified to recover from IPFS"
+            );
+            anyhow::Ok(IpfsSnapshotRecoveryConfig {
+                anchor_tx_id,
+                gateway_url: config
+                    .optional
+                    .snapshots_recovery_ipfs_gateway_url
+                    .clone()
+                    .context("IPFS gateway URL must be specified to recover from IPFS")?,
+                mintlayer_api_url: config
+                    .optional
+                    .snapshots_recovery_mintlayer_api_url
+                    .clone()
+                    .context("Mintlayer API server URL must be specified to recover from IPFS")?,
+            })
+        })
+        .transpose()?;
     let recovery_config =
         config
             .optional
@@ -974,83 +1166,120 @@ async fn run_node(
                 drop_storage_key_preimages: config
                     .experimental
                     .snapshots_recovery_drop_storage_key_preimages,
+                manifest_signer: config.optional.snapshots_recovery_manifest_signer,
                 object_store_config: config.optional.snapshots_recovery_object_store.clone(),
+                ipfs: ipfs_recovery_config,
             });
-    ensure_storage_initialized(
-        connection_pool.clone(),
-        main_node_client.clone(),
-        &app_health,
-        config.required.l2_chain_id,
-        recovery_config,
-    )
-    .await?;
-    let sigint_receiver = env.setup_sigint_handler();
+    if !is_verify_only {
+        ensure_storage_initialized(
+            connection_pool.clone(),
+            main_node_client.clone(),
+            &app_health,
+            config.required.l2_chain_id,
+            recovery_config,
+        )
+        .await?;
+    }
+    let sigint_receiver = sigint_receiver
+        .get_or_insert_with(|| {
+            let sigint = env.setup_sigint_handler();
+            let (sigint_sender, sigint_receiver) = watch::channel(false);
+            tokio::spawn(async move {
+                sigint.await.ok();
+                tracing::info!("Stop signal received, shutting down");
+                sigint_sender.send_replace(true);
+            });
+            sigint_receiver
+        })
+        .clone();
     // Spawn reacting to signals in a separate task so that the node is responsive to signals right away
     // (e.g., during the initial reorg detection).
-    tokio::spawn({
+    let stop_forwarding_task = tokio::spawn({
         let stop_sender = stop_sender.clone();
+        let mut sigint_receiver = sigint_receiver.clone();
         async move {
-            sigint_receiver.await.ok();
-            tracing::info!("Stop signal received, shutting down");
-            stop_sender.send_replace(true);
+            if sigint_receiver.wait_for(|&stop| stop).await.is_ok() {
+                stop_sender.send_replace(true);
+            }
         }
     });
 
-    // Revert the storage if needed.
-    let mut reverter = BlockReverter::new(NodeRole::External, connection_pool.clone());
-    // Reverting executed batches is more-or-less safe for external nodes.
-    let reverter = reverter
-        .allow_rolling_back_executed_batches()
-        .enable_rolling_back_postgres()
-        .enable_rolling_back_merkle_tree(config.required.merkle_tree_path.clone())
-        .enable_rolling_back_state_keeper_cache(config.required.state_cache_path.clone());
-
-    let mut reorg_detector = ReorgDetector::new(main_node_client.clone(), connection_pool.clone());
-    // We're checking for the reorg in the beginning because we expect that if reorg is detected during
-    // the node lifecycle, the node will exit the same way as it does with any other critical error,
-    // and would restart. Then, on the 2nd launch reorg would be detected here, then processed and the node
-    // will be able to operate normally afterwards.
-    match reorg_detector.run_once(stop_receiver.clone()).await {
-        Ok(()) if *stop_receiver.borrow() => {
-            tracing::info!("Stop signal received during initial reorg detection; shutting down");
-            healthcheck_handle.stop().await;
-            return Ok(());
-        }
-        Ok(()) => {
-            tracing::info!("Successfully checked no reorg compared to the main node");
-        }
-        Err(zksync_reorg_detector::Error::ReorgDetected(last_correct_l1_batch)) => {
-            tracing::info!("Reverting to l1 batch number {last_correct_l1_batch}");
-            reverter.roll_back(last_correct_l1_batch).await?;
-            tracing::info!("Revert successfully completed");
+    let mut reorg_receiver = None;
+    if is_verify_only {
+        run_batch_verifier(
+            config,
+            connection_pool,
+            main_node_client,
+            eth_client,
+            &mut task_handles,
+            &app_health,
+            stop_receiver.clone(),
+        )?;
+    } else {
+        // Revert the storage if needed.
+        let reorg_recovery = ReorgRecovery::new(config, connection_pool.clone());
+
+        let mut reorg_detector =
+            ReorgDetector::new(main_node_client.clone(), connection_pool.clone());
+        // We're checking for the reorg in the beginning because we expect that if reorg is detected during
+        // the node lifecycle, the node will exit the same way as it does with any other critical error,
+        // and would restart. Then, on the 2nd launch reorg would be detected here, then processed and the node
+        // will be able to operate normally afterwards.
+        match reorg_detector.run_once(stop_receiver.clone()).await {
+            Ok(()) if *stop_receiver.borrow() => {
+                tracing::info!(
+                    "Stop signal received during initial reorg detection; shutting down"
+                );
+                healthcheck_handle.stop().await;
+                return Ok(NodeRunOutcome::Stopped);
+            }
+            Ok(()) => {
+                tracing::info!("Successfully checked no reorg compared to the main node");
+            }
+            Err(zksync_reorg_detector::Error::ReorgDetected(last_correct_l1_batch)) => {
+                reorg_recovery.roll_back(last_correct_l1_batch).await?;
+            }
+            Err(err) => return Err(err).context("reorg_detector.check_consistency()"),
         }
-        Err(err) => return Err(err).context("reorg_detector.check_consistency()"),
-    }
 
-    app_health.insert_component(reorg_detector.health_check().clone())?;
-    task_handles.push(tokio::spawn({
-        let stop = stop_receiver.clone();
-        async move {
-            reorg_detector
-                .run(stop)
-                .await
-                .context("reorg_detector.run()")
-        }
-    }));
+        app_health.insert_component(reorg_detector.health_check().clone())?;
+        let (reorg_sender, receiver) = oneshot::channel();
+        reorg_receiver = Some(receiver);
+        let is_reorg_recovery_automatic = reorg_recovery.is_automatic();
+        task_handles.push(tokio::spawn({
+            let stop = stop_receiver.clone();
+            async move {
+                match reorg_detector.run(stop).await {
+                    Err(zksync_reorg_detector::Error::ReorgDetected(last_correct_l1_batch))
+                        if is_reorg_recovery_automatic =>
+                    {
+                        // The state will be rolled back once all node components are stopped.
+                        tracing::warn!(
+                            "Reorg detected, last correct L1 batch #{last_correct_l1_batch}; stopping node components \
+                             for automatic recovery"
+                        );
+                        reorg_sender.send(()).ok();
+                        Ok(())
+                    }
+                    result => result.context("reorg_detector.run()"),
+                }
+            }
+        }));
 
-    init_tasks(
-        config,
-        connection_pool,
-        singleton_pool_builder,
-        main_node_client,
-        eth_client,
-        &mut task_handles,
-        &app_health,
-        stop_receiver.clone(),
-        &opt.components.0,
-    )
-    .await
-    .context("init_tasks")?;
+        init_tasks(
+            config,
+            connection_pool,
+            singleton_pool_builder,
+            main_node_client,
+            eth_client,
+            &mut task_handles,
+            &app_health,
+            stop_receiver.clone(),
+            &opt.components.0,
+        )
+        .await
+        .context("init_tasks")?;
+    }
 
     env.set_app_health(app_health);
 
@@ -1067,6 +1296,15 @@ async fn run_node(
     // Broadcast the stop signal (in case it wasn't broadcast previously) to all actors and exit.
     stop_sender.send_replace(true);
     shutdown_components(tasks, healthcheck_handle).await?;
+    stop_forwarding_task.abort();
     tracing::info!("Stopped");
-    Ok(())
+
+    let is_reorg_detected = reorg_receiver
+        .as_mut()
+        .is_some_and(|receiver| receiver.try_recv().is_ok());
+    if is_reorg_detected && !*sigint_receiver.borrow() {
+        Ok(NodeRunOutcome::ReorgDetected)
+    } else {
+        Ok(NodeRunOutcome::Stopped)
+    }
 }
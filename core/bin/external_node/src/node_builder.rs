@@ -206,6 +206,13 @@ impl ExternalNodeBuilder {
     }
 
     fn add_consensus_layer(mut self) -> anyhow::Result<Self> {
+        if self.config.optional.sync_archive_object_store.is_some()
+            || self.config.optional.sync_archive_ipfs_gateway_url.is_some()
+        {
+            anyhow::bail!(
+                "Syncing from L2 block archive is not supported by the node framework yet"
+            );
+        }
         let config = self.config.consensus.clone();
         let secrets =
             config::read_consensus_secrets().context("config::read_consensus_secrets()")?;
@@ -220,7 +227,9 @@ impl ExternalNodeBuilder {
                 self.config.optional.pruning_removal_delay(),
                 self.config.optional.pruning_chunk_size,
                 self.config.optional.pruning_data_retention(),
-            );
+            )
+            .with_retention_policy(self.config.optional.pruning_retention_policy())
+            .with_dry_run(self.config.optional.pruning_dry_run);
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -302,6 +311,7 @@ impl ExternalNodeBuilder {
                 .merkle_tree_include_indices_and_filters_in_block_cache,
             memtable_capacity: self.config.optional.merkle_tree_memtable_capacity(),
             stalled_writes_timeout: self.config.optional.merkle_tree_stalled_writes_timeout(),
+            node_cache_capacity: self.config.optional.merkle_tree_node_cache_capacity,
             sealed_batches_have_protective_reads: self
                 .config
                 .optional
@@ -330,6 +340,21 @@ impl ExternalNodeBuilder {
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
 
+        // Import tree checkpoints during snapshot recovery if enabled.
+        if self
+            .config
+            .experimental
+            .snapshots_recovery_tree_use_checkpoints
+        {
+            let store_config = self
+                .config
+                .optional
+                .snapshots_recovery_object_store
+                .clone()
+                .context("Snapshot object store must be provided to use Merkle tree checkpoints")?;
+            layer = layer.with_checkpoint_store_config(store_config);
+        }
+
         // Add tree pruning if needed.
         if self.config.optional.pruning_enabled {
             layer = layer.with_pruning_config(self.config.optional.pruning_removal_delay());
@@ -500,6 +525,9 @@ impl ExternalNodeBuilder {
                         .add_commitment_generator_layer()?
                         .add_batch_status_updater_layer()?;
                 }
+                Component::Verifier => {
+                    anyhow::bail!("Verify-only mode is not supported by the node framework yet");
+                }
             }
         }
 
@@ -0,0 +1,77 @@
+//! Rolling back the node state after a reorg is detected.
+
+use anyhow::Context as _;
+use zksync_block_reverter::{BlockReverter, NodeRole};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L1BatchNumber;
+
+use crate::config::ExternalNodeConfig;
+
+/// Rolls back Postgres, the Merkle tree and the state keeper cache to the last correct L1 batch
+/// as reported by the reorg detector. All node components using these storages must be stopped beforehand.
+#[derive(Debug)]
+pub(crate) struct ReorgRecovery {
+    reverter: BlockReverter,
+    connection_pool: ConnectionPool<Core>,
+    /// Maximum number of L1 batches that can be rolled back. `None` means no limit.
+    max_depth: Option<u32>,
+}
+
+impl ReorgRecovery {
+    pub fn new(config: &ExternalNodeConfig, connection_pool: ConnectionPool<Core>) -> Self {
+        let mut reverter = BlockReverter::new(NodeRole::External, connection_pool.clone());
+        reverter
+            .enable_rolling_back_postgres()
+            .enable_rolling_back_merkle_tree(config.required.merkle_tree_path.clone())
+            .enable_rolling_back_state_keeper_cache(config.required.state_cache_path.clone());
+
+        let optional = &config.optional;
+        let max_depth = if optional.reorg_auto_recovery_enabled {
+            if optional.reorg_auto_recovery_allow_executed_batches {
+                reverter.allow_rolling_back_executed_batches();
+            }
+            Some(optional.reorg_auto_recovery_max_depth)
+        } else {
+            // Reverting executed batches is more-or-less safe for external nodes.
+            reverter.allow_rolling_back_executed_batches();
+            None
+        };
+
+        Self {
+            reverter,
+            connection_pool,
+            max_depth,
+        }
+    }
+
+    pub fn is_automatic(&self) -> bool {
+        self.max_depth.is_some()
+    }
+
+    pub async fn roll_back(&self, last_correct_l1_batch: L1BatchNumber) -> anyhow::Result<()> {
+        if let Some(max_depth) = self.max_depth {
+            let mut storage = self
+                .connection_pool
+                .connection_tagged("reorg_recovery")
+                .await?;
+            let last_l1_batch = storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await?
+                .context("no L1 batches in storage")?;
+            drop(storage);
+
+            let depth = last_l1_batch.0.saturating_sub(last_correct_l1_batch.0);
+            anyhow::ensure!(
+                depth <= max_depth,
+                "Rolling back {depth} L1 batches (#{last_l1_batch} -> #{last_correct_l1_batch}) exceeds \
+                 the automatic reorg recovery limit ({max_depth}); the node must be reverted manually"
+            );
+        }
+
+        tracing::info!("Reverting to l1 batch number {last_correct_l1_batch}");
+        self.reverter.roll_back(last_correct_l1_batch).await?;
+        tracing::info!("Revert successfully completed");
+        Ok(())
+    }
+}
@@ -6,8 +6,8 @@ use zksync_dal::CoreDal;
 use zksync_eth_client::clients::MockEthereum;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_types::{
-    api, ethabi, fee_model::FeeParams, Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId,
-    H256, U64,
+    api, block::L1BatchHeader, ethabi, fee_model::FeeParams, Address, L1BatchNumber, L2BlockNumber,
+    ProtocolVersionId, H256, U64,
 };
 use zksync_web3_decl::{
     client::{MockClient, L1},
@@ -319,3 +319,43 @@ async fn node_reacts_to_stop_signal_during_initial_reorg_detection() {
     env_handles.sigint_sender.send(()).unwrap();
     node_handle.await.unwrap().unwrap();
 }
+
+#[tokio::test]
+async fn automatic_reorg_recovery_respects_max_depth() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    for number in 1..=3 {
+        let header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            number.into(),
+            Default::default(),
+            ProtocolVersionId::latest(),
+        );
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+    drop(storage);
+
+    let mut config = ExternalNodeConfig::mock(&temp_dir, &connection_pool);
+    config.optional.reorg_auto_recovery_enabled = true;
+    config.optional.reorg_auto_recovery_max_depth = 2;
+    let reorg_recovery = ReorgRecovery::new(&config, connection_pool);
+    assert!(reorg_recovery.is_automatic());
+
+    let err = reorg_recovery
+        .roll_back(L1BatchNumber(0))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("exceeds the automatic reorg recovery limit"),
+        "{err}"
+    );
+}
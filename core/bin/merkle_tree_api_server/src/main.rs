@@ -0,0 +1,101 @@
+use std::{net::Ipv4Addr, time::Duration};
+
+use anyhow::Context as _;
+use clap::Parser;
+use tokio::sync::watch;
+use zksync_config::{
+    configs::{api::MerkleTreeApiConfig, ObservabilityConfig},
+    DBConfig,
+};
+use zksync_env_config::FromEnv;
+use zksync_metadata_calculator::{MerkleTreeReplica, MerkleTreeReplicaConfig};
+use zksync_utils::wait_for_tasks::ManagedTasks;
+use zksync_vlog::prometheus::PrometheusExporterConfig;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Standalone Merkle tree API server backed by a read-only tree replica",
+    long_about = None
+)]
+struct Cli {
+    /// Path to store secondary RocksDB instance info. Must be unique for each server instance
+    /// sharing the same Merkle tree.
+    #[arg(long)]
+    secondary_db_path: String,
+    /// Port to bind the tree API server to. If not specified, the port from the Merkle tree API config is used.
+    #[arg(long)]
+    port: Option<u16>,
+    /// Interval between catching up with the primary Merkle tree, in milliseconds.
+    #[arg(long, default_value_t = 1_000)]
+    catch_up_interval_ms: u64,
+    /// Port to expose Prometheus metrics on. If not specified, metrics are not exported.
+    #[arg(long)]
+    prometheus_port: Option<u16>,
+}
+
+impl Cli {
+    async fn run(
+        self,
+        db_config: &DBConfig,
+        api_config: &MerkleTreeApiConfig,
+    ) -> anyhow::Result<()> {
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let replica_config = MerkleTreeReplicaConfig::new(
+            &db_config.merkle_tree,
+            self.secondary_db_path,
+            Duration::from_millis(self.catch_up_interval_ms),
+        );
+        let replica = MerkleTreeReplica::new(replica_config, db_config.merkle_tree.mode)
+            .await
+            .context("failed initializing Merkle tree replica")?;
+
+        let bind_address = (Ipv4Addr::UNSPECIFIED, self.port.unwrap_or(api_config.port)).into();
+        let mut tasks = vec![
+            tokio::spawn(
+                replica
+                    .tree_reader()
+                    .run_api_server(bind_address, stop_receiver.clone()),
+            ),
+            tokio::spawn(replica.run(stop_receiver.clone())),
+        ];
+        if let Some(port) = self.prometheus_port {
+            let exporter_config = PrometheusExporterConfig::pull(port);
+            tasks.push(tokio::spawn(exporter_config.run(stop_receiver)));
+        }
+
+        let mut tasks = ManagedTasks::new(tasks);
+        tokio::select! {
+            () = tasks.wait_single() => {},
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("Stop signal received, shutting down");
+            }
+        }
+        stop_sender.send_replace(true);
+        tasks.complete(Duration::from_secs(5)).await;
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let observability_config =
+        ObservabilityConfig::from_env().context("ObservabilityConfig::from_env()")?;
+    let log_format: zksync_vlog::LogFormat = observability_config
+        .log_format
+        .parse()
+        .context("Invalid log format")?;
+    let mut builder = zksync_vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = observability_config.sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(observability_config.sentry_environment);
+    }
+    let _guard = builder.build();
+
+    let db_config = DBConfig::from_env().context("DBConfig::from_env()")?;
+    let api_config = MerkleTreeApiConfig::from_env().context("MerkleTreeApiConfig::from_env()")?;
+    Cli::parse().run(&db_config, &api_config).await
+}
@@ -7,24 +7,30 @@ use tokio::sync::Semaphore;
 use zksync_config::SnapshotsCreatorConfig;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalResult};
 use zksync_object_store::{ObjectStore, StoredObject};
+use zksync_protobuf::ProtoFmt;
 use zksync_types::{
     snapshots::{
         uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
         SnapshotMetadata, SnapshotStorageLog, SnapshotStorageLogsChunk,
         SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
-    L1BatchNumber, L2BlockNumber,
+    K256PrivateKey, L1BatchNumber, L2BlockNumber, L2ChainId, H256,
 };
 
-use crate::metrics::{FactoryDepsStage, StorageChunkStage, METRICS};
 #[cfg(test)]
 use crate::tests::HandleEvent;
+use crate::{
+    ipfs::SnapshotIpfsPublisher,
+    metrics::{FactoryDepsStage, StorageChunkStage, METRICS},
+};
 
 /// Encapsulates progress of creating a particular storage snapshot.
 #[derive(Debug)]
 struct SnapshotProgress {
     version: SnapshotVersion,
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot; only set for delta snapshots.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
@@ -32,10 +38,16 @@ struct SnapshotProgress {
 }
 
 impl SnapshotProgress {
-    fn new(version: SnapshotVersion, l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             version,
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
@@ -53,6 +65,7 @@ impl SnapshotProgress {
         Self {
             version: snapshot.version,
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
@@ -60,12 +73,24 @@ impl SnapshotProgress {
     }
 }
 
+/// Signer of snapshot manifests.
+#[derive(Debug, Clone)]
+pub(crate) struct ManifestSigner {
+    pub private_key: K256PrivateKey,
+    /// L2 chain the signatures are bound to.
+    pub l2_chain_id: L2ChainId,
+}
+
 /// Creator of a single storage snapshot.
 #[derive(Debug)]
 pub(crate) struct SnapshotCreator {
     pub blob_store: Arc<dyn ObjectStore>,
     pub master_pool: ConnectionPool<Core>,
     pub replica_pool: ConnectionPool<Core>,
+    /// Signer of snapshot manifests. If not set, manifests are not signed.
+    pub manifest_signer: Option<ManifestSigner>,
+    /// Publisher of complete snapshots to IPFS. If not set, snapshots are not published.
+    pub ipfs_publisher: Option<SnapshotIpfsPublisher>,
     #[cfg(test)]
     pub event_listener: Box<dyn HandleEvent>,
 }
@@ -82,6 +107,7 @@ impl SnapshotCreator {
         semaphore: &Semaphore,
         progress: &SnapshotProgress,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         chunk_id: u64,
     ) -> anyhow::Result<()> {
         let chunk_count = progress.chunk_count;
@@ -98,7 +124,7 @@ impl SnapshotCreator {
 
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
-        let (output_filepath, latency) = match progress.version {
+        let (output_filepath, digest, latency) = match progress.version {
             SnapshotVersion::Version0 => {
                 #[allow(deprecated)] // support of version 0 snapshots will be removed eventually
                 let logs = conn
@@ -136,6 +162,29 @@ impl SnapshotCreator {
                 self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
                     .await?
             }
+            SnapshotVersion::Version2 => {
+                let base_l2_block_number =
+                    base_l2_block_number.context("delta snapshot doesn't have a base snapshot")?;
+                let logs = conn
+                    .snapshots_creator_dal()
+                    .get_changed_storage_logs_chunk(
+                        base_l2_block_number,
+                        l2_block_number,
+                        l1_batch_number,
+                        hashed_keys_range,
+                    )
+                    .await
+                    .context("error fetching changed storage logs")?;
+                drop(conn);
+
+                let latency = latency.observe();
+                tracing::info!(
+                    "Loaded chunk {chunk_id} ({} changed logs) from Postgres in {latency:?}",
+                    logs.len()
+                );
+                self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
+                    .await?
+            }
         };
 
         let mut master_conn = self
@@ -144,7 +193,12 @@ impl SnapshotCreator {
             .await?;
         master_conn
             .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &output_filepath)
+            .add_storage_logs_filepath_for_snapshot(
+                l1_batch_number,
+                chunk_id,
+                &output_filepath,
+                digest,
+            )
             .await?;
         #[cfg(test)]
         self.event_listener.on_chunk_saved();
@@ -162,13 +216,15 @@ impl SnapshotCreator {
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         logs: Vec<SnapshotStorageLog<K>>,
-    ) -> anyhow::Result<(String, Duration)>
+    ) -> anyhow::Result<(String, H256, Duration)>
     where
-        for<'a> SnapshotStorageLogsChunk<K>: StoredObject<Key<'a> = SnapshotStorageLogsStorageKey>,
+        for<'a> SnapshotStorageLogsChunk<K>:
+            StoredObject<Key<'a> = SnapshotStorageLogsStorageKey> + ProtoFmt,
     {
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::SaveToGcs].start();
         let storage_logs_chunk = SnapshotStorageLogsChunk { storage_logs: logs };
+        let digest = storage_logs_chunk.digest();
         let key = SnapshotStorageLogsStorageKey {
             l1_batch_number,
             chunk_id,
@@ -183,23 +239,27 @@ impl SnapshotCreator {
             .get_storage_prefix::<SnapshotStorageLogsChunk<K>>();
         let output_filepath = format!("{output_filepath_prefix}/{filename}");
         let latency = latency.observe();
-        Ok((output_filepath, latency))
+        Ok((output_filepath, digest, latency))
     }
 
     async fn process_factory_deps(
         &self,
         l2_block_number: L2BlockNumber,
+        base_l2_block_number: Option<L2BlockNumber>,
         l1_batch_number: L1BatchNumber,
-    ) -> anyhow::Result<String> {
+    ) -> anyhow::Result<(String, H256)> {
         let mut conn = self.connect_to_replica().await?;
 
         tracing::info!("Loading factory deps from Postgres...");
         let latency =
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
-        let factory_deps = conn
-            .snapshots_creator_dal()
-            .get_all_factory_deps(l2_block_number)
-            .await?;
+        let mut dal = conn.snapshots_creator_dal();
+        let factory_deps = if let Some(base_l2_block_number) = base_l2_block_number {
+            dal.get_factory_deps_after(base_l2_block_number, l2_block_number)
+                .await?
+        } else {
+            dal.get_all_factory_deps(l2_block_number).await?
+        };
         drop(conn);
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
@@ -214,6 +274,7 @@ impl SnapshotCreator {
             })
             .collect();
         let factory_deps = SnapshotFactoryDependencies { factory_deps };
+        let digest = factory_deps.digest();
         let filename = self
             .blob_store
             .put(l1_batch_number, &factory_deps)
@@ -229,7 +290,7 @@ impl SnapshotCreator {
             factory_deps.factory_deps.len()
         );
 
-        Ok(output_filepath)
+        Ok((output_filepath, digest))
     }
 
     /// Returns `Ok(None)` if the created snapshot would coincide with `latest_snapshot`.
@@ -254,10 +315,26 @@ impl SnapshotCreator {
                 )
             })?;
 
-        let distinct_storage_logs_keys_count = conn
-            .snapshots_creator_dal()
-            .get_distinct_storage_logs_keys_count(l1_batch_number)
-            .await?;
+        let (base_l1_batch_number, distinct_storage_logs_keys_count) = if snapshot_version
+            .is_delta()
+        {
+            let base_l1_batch_number = Self::select_base_snapshot(l1_batch_number, conn).await?;
+            let (_, base_l2_block_number) =
+                Self::l2_block_range_of_l1_batch(base_l1_batch_number, conn).await?;
+            let (_, l2_block_number) =
+                Self::l2_block_range_of_l1_batch(l1_batch_number, conn).await?;
+            let changed_keys_count = conn
+                .snapshots_creator_dal()
+                .get_changed_storage_logs_keys_count(base_l2_block_number, l2_block_number)
+                .await?;
+            (Some(base_l1_batch_number), changed_keys_count)
+        } else {
+            let keys_count = conn
+                .snapshots_creator_dal()
+                .get_distinct_storage_logs_keys_count(l1_batch_number)
+                .await?;
+            (None, keys_count)
+        };
         let chunk_size = config.storage_logs_chunk_size;
         // We force the minimum number of chunks to avoid situations where only one chunk is created in tests.
         let chunk_count = distinct_storage_logs_keys_count
@@ -271,10 +348,110 @@ impl SnapshotCreator {
         Ok(Some(SnapshotProgress::new(
             snapshot_version,
             l1_batch_number,
+            base_l1_batch_number,
             chunk_count,
         )))
     }
 
+    /// Selects the base for a delta snapshot at `l1_batch_number`, which is the newest complete snapshot
+    /// preceding the L1 batch.
+    async fn select_base_snapshot(
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        let all_snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+        let base_l1_batch_number = all_snapshots
+            .snapshots_l1_batch_numbers
+            .into_iter()
+            .find(|&number| number < l1_batch_number)
+            .with_context(|| {
+                format!(
+                    "Cannot create a delta snapshot for L1 batch #{l1_batch_number}: there are no complete snapshots \
+                     before it. Create a full snapshot first"
+                )
+            })?;
+        let base_snapshot = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(base_l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("Snapshot for L1 batch #{base_l1_batch_number} disappeared")
+            })?;
+        anyhow::ensure!(
+            base_snapshot.version != SnapshotVersion::Version0,
+            "Cannot create a delta snapshot on top of the version 0 snapshot for L1 batch #{base_l1_batch_number}; \
+             delta snapshots require a base snapshot with hashed storage keys"
+        );
+
+        tracing::info!(
+            "Selected snapshot for L1 batch #{base_l1_batch_number} (version {:?}) as the base for delta snapshot",
+            base_snapshot.version
+        );
+        Ok(base_l1_batch_number)
+    }
+
+    async fn l2_block_range_of_l1_batch(
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<(L2BlockNumber, L2BlockNumber)> {
+        conn.blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("No L2 blocks for L1 batch #{l1_batch_number}"))
+    }
+
+    /// Signs the manifest of a complete snapshot if the signing key is configured and the manifest isn't signed with it yet.
+    async fn sign_manifest(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<()> {
+        let Some(signer) = &self.manifest_signer else {
+            return Ok(());
+        };
+
+        let mut master_conn = self
+            .master_pool
+            .connection_tagged("snapshots_creator")
+            .await?;
+        let manifest = master_conn
+            .snapshots_dal()
+            .get_snapshot_manifest(l1_batch_number)
+            .await?;
+        let Some(mut manifest) = manifest else {
+            tracing::warn!(
+                "Snapshot for L1 batch #{l1_batch_number} has no manifest (probably, it was created by an old snapshot creator); \
+                 skipping signing"
+            );
+            return Ok(());
+        };
+        let signer_address = signer.private_key.address();
+        let recovered_signer = manifest.recover_signer(signer.l2_chain_id);
+        if matches!(recovered_signer, Ok(Some(address)) if address == signer_address) {
+            return Ok(());
+        }
+
+        manifest.sign(signer.l2_chain_id, &signer.private_key)?;
+        // `unwrap()` is safe: the signature was just set
+        let signature = manifest.signature.as_ref().unwrap();
+        master_conn
+            .snapshots_dal()
+            .set_snapshot_manifest_signature(l1_batch_number, signature)
+            .await?;
+        tracing::info!(
+            "Signed manifest for snapshot at L1 batch #{l1_batch_number} by {signer_address:?}"
+        );
+        Ok(())
+    }
+
+    /// Signs and publishes a complete snapshot according to the creator configuration.
+    async fn finalize_snapshot(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<()> {
+        self.sign_manifest(l1_batch_number).await?;
+        if let Some(publisher) = &self.ipfs_publisher {
+            publisher
+                .publish(self.blob_store.as_ref(), &self.master_pool, l1_batch_number)
+                .await
+                .context("failed publishing snapshot to IPFS")?;
+        }
+        Ok(())
+    }
+
     /// Returns `Ok(None)` if a snapshot should not be created / resumed.
     async fn load_or_initialize_snapshot_progress(
         &self,
@@ -315,6 +492,9 @@ impl SnapshotCreator {
         match existing_snapshot {
             Some(snapshot) if snapshot.is_complete() => {
                 tracing::info!("Snapshot for the requested L1 batch is complete: {snapshot:?}");
+                // The creator may have been stopped after persisting all chunks, but before signing the manifest
+                // or publishing the snapshot.
+                self.finalize_snapshot(snapshot.l1_batch_number).await?;
                 Ok(None)
             }
             Some(snapshot) if config.l1_batch_number.is_some() => {
@@ -361,23 +541,33 @@ impl SnapshotCreator {
         };
 
         let mut conn = self.connect_to_replica().await?;
-        let (_, last_l2_block_number_in_batch) = conn
-            .blocks_dal()
-            .get_l2_block_range_of_l1_batch(progress.l1_batch_number)
-            .await?
-            .context("No L2 blocks for L1 batch")?;
+        let (_, last_l2_block_number_in_batch) =
+            Self::l2_block_range_of_l1_batch(progress.l1_batch_number, &mut conn).await?;
+        let base_l2_block_number = if let Some(base_l1_batch_number) = progress.base_l1_batch_number
+        {
+            let (_, base_l2_block_number) =
+                Self::l2_block_range_of_l1_batch(base_l1_batch_number, &mut conn).await?;
+            Some(base_l2_block_number)
+        } else {
+            None
+        };
         drop(conn);
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
         tracing::info!(
             "Creating snapshot for storage logs up to L2 block {last_l2_block_number_in_batch}, \
-            L1 batch {}",
-            progress.l1_batch_number
+            L1 batch {} (base L1 batch: {:?})",
+            progress.l1_batch_number,
+            progress.base_l1_batch_number
         );
 
         if progress.is_new_snapshot {
-            let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+            let (factory_deps_output_file, factory_deps_digest) = self
+                .process_factory_deps(
+                    last_l2_block_number_in_batch,
+                    base_l2_block_number,
+                    progress.l1_batch_number,
+                )
                 .await?;
 
             let mut master_conn = self
@@ -389,8 +579,10 @@ impl SnapshotCreator {
                 .add_snapshot(
                     progress.version,
                     progress.l1_batch_number,
+                    progress.base_l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
+                    factory_deps_digest,
                 )
                 .await?;
         }
@@ -408,10 +600,12 @@ impl SnapshotCreator {
                     &semaphore,
                     &progress,
                     last_l2_block_number_in_batch,
+                    base_l2_block_number,
                     chunk_id,
                 )
             });
         futures::future::try_join_all(tasks).await?;
+        self.finalize_snapshot(progress.l1_batch_number).await?;
 
         METRICS
             .snapshot_l1_batch
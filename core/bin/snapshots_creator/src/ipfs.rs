@@ -0,0 +1,166 @@
+//! Publishing snapshots to IPFS and anchoring them on Mintlayer.
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_anchoring::{IpfsClient, MintlayerClient};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_object_store::{Bucket, ObjectStore, StoredObject};
+use zksync_types::{
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotIpfsAnchor, SnapshotIpfsDocument,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey,
+    },
+    L1BatchNumber,
+};
+
+/// Publishes complete snapshots to IPFS and anchors them on Mintlayer.
+///
+/// All snapshot objects are uploaded to IPFS as is (i.e., as gzipped Protobuf messages), and are referenced
+/// by a [`SnapshotIpfsDocument`], which also contains the snapshot manifest. The CID of the document is then deposited
+/// on Mintlayer. Documents for delta snapshots reference the document of their base snapshot, so the base snapshot
+/// must be published first.
+#[derive(Debug)]
+pub(crate) struct SnapshotIpfsPublisher {
+    pub ipfs: Arc<dyn IpfsClient>,
+    pub mintlayer: Arc<dyn MintlayerClient>,
+}
+
+impl SnapshotIpfsPublisher {
+    /// Publishes the specified complete snapshot unless it's already published. Returns the publication info,
+    /// or `None` if the snapshot cannot be published.
+    pub async fn publish(
+        &self,
+        blob_store: &dyn ObjectStore,
+        pool: &ConnectionPool<Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotIpfsAnchor>> {
+        let mut conn = pool.connection_tagged("snapshots_creator").await?;
+        let mut dal = conn.snapshots_dal();
+        if let Some(anchor) = dal.get_snapshot_ipfs_anchor(l1_batch_number).await? {
+            tracing::info!(
+                "Snapshot for L1 batch #{l1_batch_number} is already published to IPFS: {anchor:?}"
+            );
+            return Ok(Some(anchor));
+        }
+
+        let metadata = dal
+            .get_snapshot_metadata(l1_batch_number)
+            .await?
+            .with_context(|| format!("snapshot for L1 batch #{l1_batch_number} disappeared"))?;
+        anyhow::ensure!(
+            metadata.is_complete(),
+            "cannot publish incomplete snapshot for L1 batch #{l1_batch_number}"
+        );
+        let Some(manifest) = dal.get_snapshot_manifest(l1_batch_number).await? else {
+            tracing::warn!(
+                "Snapshot for L1 batch #{l1_batch_number} has no manifest (probably, it was created by an old snapshot creator); \
+                 skipping publishing it to IPFS"
+            );
+            return Ok(None);
+        };
+        let base_document_cid = if let Some(base_l1_batch_number) = metadata.base_l1_batch_number {
+            let Some(base_anchor) = dal.get_snapshot_ipfs_anchor(base_l1_batch_number).await?
+            else {
+                tracing::warn!(
+                    "Base snapshot for L1 batch #{base_l1_batch_number} is not published to IPFS; skipping publishing \
+                     delta snapshot for L1 batch #{l1_batch_number}"
+                );
+                return Ok(None);
+            };
+            Some(base_anchor.document_cid)
+        } else {
+            None
+        };
+        let (_, l2_block_number) = conn
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("no L2 blocks for L1 batch #{l1_batch_number}"))?;
+        drop(conn);
+
+        tracing::info!("Publishing snapshot for L1 batch #{l1_batch_number} to IPFS");
+        let factory_deps_key = SnapshotFactoryDependencies::encode_key(l1_batch_number);
+        let factory_deps_cid = self
+            .upload_object(
+                blob_store,
+                SnapshotFactoryDependencies::BUCKET,
+                factory_deps_key,
+            )
+            .await?;
+        let chunk_count = metadata.storage_logs_filepaths.len() as u64;
+        let mut storage_logs_chunks = Vec::with_capacity(chunk_count as usize);
+        for chunk_id in 0..chunk_count {
+            // Keys and buckets don't depend on the storage log key type, so we use the default one.
+            let key = <SnapshotStorageLogsChunk>::encode_key(SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            });
+            let cid = self
+                .upload_object(blob_store, <SnapshotStorageLogsChunk>::BUCKET, key)
+                .await?;
+            tracing::debug!("Uploaded chunk {chunk_id}/{chunk_count} to IPFS: {cid}");
+            storage_logs_chunks.push(SnapshotStorageLogsChunkMetadata {
+                chunk_id,
+                filepath: cid,
+            });
+        }
+
+        let document = SnapshotIpfsDocument {
+            header: SnapshotHeader {
+                version: metadata.version.into(),
+                l1_batch_number,
+                l2_block_number,
+                base_l1_batch_number: metadata.base_l1_batch_number,
+                storage_logs_chunks,
+                factory_deps_filepath: factory_deps_cid,
+            },
+            manifest,
+            base_document_cid,
+        };
+        let document_bytes =
+            serde_json::to_vec(&document).context("failed serializing snapshot IPFS document")?;
+        let document_name = format!("snapshot_l1_batch_{l1_batch_number}.json");
+        let document_cid = self.ipfs.upload(&document_name, document_bytes).await?;
+        tracing::info!(
+            "Uploaded IPFS document for snapshot at L1 batch #{l1_batch_number}: {document_cid}"
+        );
+
+        let anchor_tx_id = self
+            .mintlayer
+            .deposit_data(document_cid.as_bytes())
+            .await
+            .context("failed anchoring snapshot IPFS document on Mintlayer")?;
+        let anchor = SnapshotIpfsAnchor {
+            document_cid,
+            anchor_tx_id,
+        };
+        pool.connection_tagged("snapshots_creator")
+            .await?
+            .snapshots_dal()
+            .set_snapshot_ipfs_anchor(l1_batch_number, &anchor)
+            .await?;
+        tracing::info!(
+            "Anchored snapshot for L1 batch #{l1_batch_number} on Mintlayer in transaction {}; nodes can recover \
+             from the snapshot using this transaction ID",
+            anchor.anchor_tx_id
+        );
+        Ok(Some(anchor))
+    }
+
+    async fn upload_object(
+        &self,
+        blob_store: &dyn ObjectStore,
+        bucket: Bucket,
+        key: String,
+    ) -> anyhow::Result<String> {
+        let bytes = blob_store
+            .get_raw(bucket, &key)
+            .await
+            .with_context(|| format!("failed fetching `{key}` from object store"))?;
+        self.ipfs
+            .upload(&key, bytes)
+            .await
+            .with_context(|| format!("failed uploading `{key}` to IPFS"))
+    }
+}
@@ -9,10 +9,16 @@
 //! It is assumed that the snapshot creator is run as a singleton process (no more than 1 instance
 //! at a time).
 
+use std::sync::Arc;
+
 use anyhow::Context as _;
 use tokio::{sync::watch, task::JoinHandle};
+use zksync_anchoring::{HttpIpfsClient, HttpMintlayerClient};
 use zksync_config::{
-    configs::{DatabaseSecrets, ObservabilityConfig, PrometheusConfig},
+    configs::{
+        chain::NetworkConfig, wallets::Wallets, DatabaseSecrets, ObservabilityConfig,
+        PrometheusConfig,
+    },
     SnapshotsCreatorConfig,
 };
 use zksync_dal::{ConnectionPool, Core};
@@ -20,9 +26,13 @@ use zksync_env_config::{object_store::SnapshotsObjectStoreConfig, FromEnv};
 use zksync_object_store::ObjectStoreFactory;
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
-use crate::creator::SnapshotCreator;
+use crate::{
+    creator::{ManifestSigner, SnapshotCreator},
+    ipfs::SnapshotIpfsPublisher,
+};
 
 mod creator;
+mod ipfs;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -92,10 +102,45 @@ async fn main() -> anyhow::Result<()> {
         .build()
         .await?;
 
+    let manifest_signer = if creator_config.sign_manifests {
+        let wallets = Wallets::from_env().context("Wallets::from_env()")?;
+        let operator = wallets
+            .eth_sender
+            .context("operator private key is required to sign snapshot manifests")?
+            .operator;
+        let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+        let l2_chain_id = network_config.zksync_network_id;
+        tracing::info!(
+            "Snapshot manifests will be signed by operator {:?} for L2 chain {l2_chain_id:?}",
+            operator.address()
+        );
+        Some(ManifestSigner {
+            private_key: operator.private_key().clone(),
+            l2_chain_id,
+        })
+    } else {
+        None
+    };
+
+    let ipfs_publisher = if creator_config.publish_to_ipfs {
+        let ipfs = HttpIpfsClient::from_env().context("HttpIpfsClient::from_env()")?;
+        let mintlayer =
+            HttpMintlayerClient::from_env().context("HttpMintlayerClient::from_env()")?;
+        tracing::info!("Complete snapshots will be published to IPFS and anchored on Mintlayer");
+        Some(SnapshotIpfsPublisher {
+            ipfs: Arc::new(ipfs),
+            mintlayer: Arc::new(mintlayer),
+        })
+    } else {
+        None
+    };
+
     let creator = SnapshotCreator {
         blob_store,
         master_pool,
         replica_pool,
+        manifest_signer,
+        ipfs_publisher,
         #[cfg(test)]
         event_listener: Box::new(()),
     };
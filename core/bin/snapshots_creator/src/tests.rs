@@ -10,16 +10,18 @@ use std::{
 };
 
 use rand::{thread_rng, Rng};
+use zksync_anchoring::{IpfsClient, MintlayerClient, MockIpfsClient, MockMintlayerClient};
 use zksync_dal::{Connection, CoreDal};
-use zksync_object_store::{MockObjectStore, ObjectStore};
+use zksync_object_store::{MockObjectStore, ObjectStore, StoredObject};
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotIpfsDocument,
+        SnapshotStorageLog, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
-    AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
-    H256,
+    AccountTreeId, Address, K256PrivateKey, L1BatchNumber, L2BlockNumber, L2ChainId,
+    ProtocolVersion, StorageKey, StorageLog, H256,
 };
 
 use super::*;
@@ -29,6 +31,8 @@ const TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
     l1_batch_number: None,
     storage_logs_chunk_size: 1_000_000,
     concurrent_queries_count: 10,
+    sign_manifests: false,
+    publish_to_ipfs: false,
     object_store: None,
 };
 const SEQUENTIAL_TEST_CONFIG: SnapshotsCreatorConfig = SnapshotsCreatorConfig {
@@ -69,6 +73,8 @@ impl SnapshotCreator {
             blob_store,
             master_pool: pool.clone(),
             replica_pool: pool,
+            manifest_signer: None,
+            ipfs_publisher: None,
             event_listener: Box::new(()),
         }
     }
@@ -363,6 +369,139 @@ async fn persisting_snapshot_logs_with_specified_l1_batch() {
     }
 }
 
+#[tokio::test]
+async fn persisting_signed_snapshot_manifest() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let private_key = K256PrivateKey::random();
+    let mut creator = SnapshotCreator::for_tests(object_store.clone(), pool.clone());
+    let l2_chain_id = L2ChainId::default();
+    creator.manifest_signer = Some(ManifestSigner {
+        private_key: private_key.clone(),
+        l2_chain_id,
+    });
+    creator.run(TEST_CONFIG, MIN_CHUNK_COUNT).await.unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let manifest = conn
+        .snapshots_dal()
+        .get_snapshot_manifest(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot manifest");
+    assert_eq!(manifest.l1_batch_number, snapshot_l1_batch_number);
+    assert_eq!(
+        manifest.recover_signer(l2_chain_id).unwrap(),
+        Some(private_key.address())
+    );
+
+    let factory_deps: SnapshotFactoryDependencies =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(manifest.factory_deps_digest, factory_deps.digest());
+    assert_eq!(
+        manifest.storage_logs_chunk_digests.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+    for (chunk_id, &expected_digest) in manifest.storage_logs_chunk_digests.iter().enumerate() {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id: chunk_id as u64,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        assert_eq!(chunk.digest(), expected_digest);
+    }
+}
+
+#[tokio::test]
+async fn publishing_snapshot_to_ipfs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let ipfs = MockIpfsClient::default();
+    let mintlayer = MockMintlayerClient::default();
+    let mut creator = SnapshotCreator::for_tests(object_store.clone(), pool.clone());
+    creator.ipfs_publisher = Some(SnapshotIpfsPublisher {
+        ipfs: Arc::new(ipfs.clone()),
+        mintlayer: Arc::new(mintlayer.clone()),
+    });
+    creator.run(TEST_CONFIG, MIN_CHUNK_COUNT).await.unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let anchor = conn
+        .snapshots_dal()
+        .get_snapshot_ipfs_anchor(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("snapshot is not published");
+    let deposited_data = mintlayer
+        .fetch_deposited_data(&anchor.anchor_tx_id)
+        .await
+        .unwrap();
+    assert_eq!(deposited_data, anchor.document_cid.as_bytes());
+
+    let document = ipfs.fetch(&anchor.document_cid).await.unwrap();
+    let document: SnapshotIpfsDocument = serde_json::from_slice(&document).unwrap();
+    assert_eq!(document.header.l1_batch_number, snapshot_l1_batch_number);
+    assert_eq!(document.base_document_cid, None);
+    let manifest = conn
+        .snapshots_dal()
+        .get_snapshot_manifest(snapshot_l1_batch_number)
+        .await
+        .unwrap();
+    assert_eq!(manifest, Some(document.manifest));
+
+    let factory_deps = ipfs
+        .fetch(&document.header.factory_deps_filepath)
+        .await
+        .unwrap();
+    let factory_deps = SnapshotFactoryDependencies::deserialize(factory_deps).unwrap();
+    let expected_factory_deps: SnapshotFactoryDependencies =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(factory_deps.digest(), expected_factory_deps.digest());
+
+    assert_eq!(
+        document.header.storage_logs_chunks.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+    for chunk in &document.header.storage_logs_chunks {
+        let chunk_bytes = ipfs.fetch(&chunk.filepath).await.unwrap();
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id: chunk.chunk_id,
+        };
+        let expected_bytes = object_store
+            .get_raw(
+                <SnapshotStorageLogsChunk>::BUCKET,
+                &<SnapshotStorageLogsChunk>::encode_key(key),
+            )
+            .await
+            .unwrap();
+        assert_eq!(chunk_bytes, expected_bytes);
+    }
+
+    // Rerunning the creator must not publish the snapshot again.
+    let object_count = ipfs.len();
+    let mut creator = SnapshotCreator::for_tests(object_store, pool);
+    creator.ipfs_publisher = Some(SnapshotIpfsPublisher {
+        ipfs: Arc::new(ipfs.clone()),
+        mintlayer: Arc::new(mintlayer.clone()),
+    });
+    let config = SnapshotsCreatorConfig {
+        l1_batch_number: Some(snapshot_l1_batch_number),
+        ..TEST_CONFIG
+    };
+    creator.run(config, MIN_CHUNK_COUNT).await.unwrap();
+    assert_eq!(ipfs.len(), object_count);
+    assert_eq!(mintlayer.deposits().len(), 1);
+}
+
 async fn assert_storage_logs(
     object_store: &dyn ObjectStore,
     snapshot_l1_batch_number: L1BatchNumber,
@@ -429,6 +568,87 @@ async fn persisting_snapshot_logs_for_v0_snapshot() {
     assert_eq!(actual_logs, expected_outputs.storage_logs);
 }
 
+#[tokio::test]
+async fn persisting_delta_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let delta_config = SnapshotsCreatorConfig {
+        version: SnapshotVersion::Version2.into(),
+        ..TEST_CONFIG
+    };
+    // Delta snapshots cannot be created without a base snapshot.
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(delta_config.clone(), MIN_CHUNK_COUNT)
+        .await
+        .unwrap_err();
+
+    let base_l1_batch_number = L1BatchNumber(4);
+    let base_config = SnapshotsCreatorConfig {
+        l1_batch_number: Some(base_l1_batch_number),
+        ..TEST_CONFIG
+    };
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(base_config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+    SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+        .run(delta_config, MIN_CHUNK_COUNT)
+        .await
+        .unwrap();
+
+    let snapshot_l1_batch_number = L1BatchNumber(8);
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(snapshot_l1_batch_number)
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert!(snapshot_metadata.is_complete());
+    assert_eq!(snapshot_metadata.version, SnapshotVersion::Version2);
+    assert_eq!(
+        snapshot_metadata.base_l1_batch_number,
+        Some(base_l1_batch_number)
+    );
+
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: snapshot_l1_batch_number,
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        actual_logs.extend(chunk.storage_logs);
+    }
+    // All logs in the test are initial writes, so the delta must contain logs for L1 batches 5..=8.
+    let expected_logs: HashSet<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .filter(|log| {
+            log.l1_batch_number_of_initial_write > base_l1_batch_number
+                && log.l1_batch_number_of_initial_write <= snapshot_l1_batch_number
+        })
+        .cloned()
+        .collect();
+    assert_eq!(actual_logs, expected_logs);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(snapshot_l1_batch_number).await.unwrap();
+    assert_eq!(factory_deps.len(), 40);
+    for dep in &factory_deps {
+        assert!(expected_outputs.deps.contains(dep));
+    }
+    let SnapshotFactoryDependencies {
+        factory_deps: base_factory_deps,
+    } = object_store.get(base_l1_batch_number).await.unwrap();
+    assert!(factory_deps
+        .iter()
+        .all(|dep| !base_factory_deps.contains(dep)));
+}
+
 #[tokio::test]
 async fn recovery_workflow() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -16,9 +16,9 @@ use zksync_config::{
         FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig, L1Secrets, ObservabilityConfig,
         PrometheusConfig, ProofDataHandlerConfig, ProtectiveReadsWriterConfig, Secrets,
     },
-    ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DADispatcherConfig, DBConfig,
-    EthConfig, EthWatchConfig, GasAdjusterConfig, GenesisConfig, ObjectStoreConfig, PostgresConfig,
-    SnapshotsCreatorConfig,
+    ApiConfig, BaseTokenAdjusterConfig, BlockArchiverConfig, ContractVerifierConfig,
+    DADispatcherConfig, DBConfig, EthConfig, EthWatchConfig, GasAdjusterConfig, GenesisConfig,
+    ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
 use zksync_core_leftovers::{
     genesis_init, is_genesis_needed,
@@ -135,7 +135,7 @@ fn main() -> anyhow::Result<()> {
     }
 
     let wallets = match opt.wallets_path {
-        None => tmp_config.wallets(),
+        None => tmp_config.wallets()?,
         Some(path) => {
             let yaml =
                 std::fs::read_to_string(&path).with_context(|| path.display().to_string())?;
@@ -274,6 +274,7 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         basic_witness_input_producer_config: BasicWitnessInputProducerConfig::from_env().ok(),
         core_object_store: ObjectStoreConfig::from_env().ok(),
         base_token_adjuster_config: BaseTokenAdjusterConfig::from_env().ok(),
+        block_archiver_config: BlockArchiverConfig::from_env().ok(),
         commitment_generator: None,
         pruning: None,
         snapshot_recovery: None,
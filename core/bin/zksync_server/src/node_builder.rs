@@ -1,6 +1,8 @@
 //! This module provides a "builder" for the main node,
 //! as well as an interface to run the node with the specified components.
 
+use std::time::Duration;
+
 use anyhow::Context;
 use zksync_config::{
     configs::{
@@ -19,10 +21,12 @@ use zksync_node_api_server::{
     tx_sender::{ApiContracts, TxSenderConfig},
     web3::{state::InternalApiConfig, Namespace},
 };
+use zksync_node_db_pruner::RetentionPolicy;
 use zksync_node_framework::{
     implementations::layers::{
         base_token_ratio_persister::BaseTokenRatioPersisterLayer,
         base_token_ratio_provider::BaseTokenRatioProviderLayer,
+        block_archiver::BlockArchiverLayer,
         circuit_breaker_checker::CircuitBreakerCheckerLayer,
         commitment_generator::CommitmentGeneratorLayer,
         consensus::MainNodeConsensusLayer,
@@ -41,13 +45,16 @@ use zksync_node_framework::{
         postgres_metrics::PostgresMetricsLayer,
         prometheus_exporter::PrometheusExporterLayer,
         proof_data_handler::ProofDataHandlerLayer,
+        pruning::PruningLayer,
         query_eth_client::QueryEthClientLayer,
         sigint::SigintHandlerLayer,
+        signing_eth_client::SigningEthClientLayer,
         state_keeper::{
             main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer,
             output_handler::OutputHandlerLayer, RocksdbStorageOptions, StateKeeperLayer,
         },
         tee_verifier_input_producer::TeeVerifierInputProducerLayer,
+        tx_allowlist::TxAllowlistLayer,
         vm_runner::{
             bwip::BasicWitnessInputProducerLayer, protective_reads::ProtectiveReadsWriterLayer,
         },
@@ -133,6 +140,25 @@ impl MainNodeBuilder {
 
     fn add_pk_signing_client_layer(mut self) -> anyhow::Result<Self> {
         let eth_config = try_load_config!(self.configs.eth);
+        if let Some(wallets) = self.wallets.remote_eth_sender.clone() {
+            self.node.add_layer(SigningEthClientLayer::new(
+                eth_config,
+                self.contracts_config.clone(),
+                self.genesis_config.l1_chain_id,
+                wallets,
+            ));
+            return Ok(self);
+        }
+        if let Some(wallets) = self.wallets.keystore_eth_sender.clone() {
+            self.node.add_layer(PKSigningEthClientLayer::from_keystores(
+                eth_config,
+                self.contracts_config.clone(),
+                self.genesis_config.l1_chain_id,
+                wallets,
+            ));
+            return Ok(self);
+        }
+
         let wallets = try_load_config!(self.wallets.eth_sender);
         self.node.add_layer(PKSigningEthClientLayer::new(
             eth_config,
@@ -189,6 +215,17 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_tx_allowlist_layer(mut self) -> anyhow::Result<Self> {
+        let Some(sk_config) = &self.configs.state_keeper_config else {
+            return Ok(self);
+        };
+        if sk_config.tx_allowlist_enabled {
+            let reload_interval = sk_config.tx_allowlist_reload_interval();
+            self.node.add_layer(TxAllowlistLayer::new(reload_interval));
+        }
+        Ok(self)
+    }
+
     fn add_metadata_calculator_layer(mut self, with_tree_api: bool) -> anyhow::Result<Self> {
         let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
         let operations_manager_env_config =
@@ -199,7 +236,11 @@ impl MainNodeBuilder {
             &operations_manager_env_config,
             &state_keeper_env_config,
         );
+        let export_checkpoints = merkle_tree_env_config.export_checkpoints;
         let mut layer = MetadataCalculatorLayer::new(metadata_calculator_config);
+        if export_checkpoints {
+            layer = layer.with_checkpoint_export(Duration::from_secs(60));
+        }
         if with_tree_api {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
@@ -281,7 +322,8 @@ impl MainNodeBuilder {
 
         // On main node we always use master pool sink.
         self.node.add_layer(MasterPoolSinkLayer);
-        self.node.add_layer(TxSenderLayer::new(
+        let tx_allowlist_enabled = sk_config.tx_allowlist_enabled;
+        let tx_sender_layer = TxSenderLayer::new(
             TxSenderConfig::new(
                 &sk_config,
                 &rpc_config,
@@ -293,7 +335,9 @@ impl MainNodeBuilder {
             postgres_storage_caches_config,
             rpc_config.vm_concurrency_limit(),
             ApiContracts::load_from_disk_blocking(), // TODO (BFT-138): Allow to dynamically reload API contracts
-        ));
+        )
+        .with_tx_allowlist(tx_allowlist_enabled);
+        self.node.add_layer(tx_sender_layer);
         Ok(self)
     }
 
@@ -530,6 +574,45 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_block_archiver_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.block_archiver);
+        self.node.add_layer(BlockArchiverLayer::new(config));
+        Ok(self)
+    }
+
+    fn add_pruning_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.pruning);
+        anyhow::ensure!(
+            config.enabled,
+            "DB pruner component is requested, but pruning is disabled in the config"
+        );
+        let removal_delay = config
+            .removal_delay_sec
+            .map_or(Duration::from_secs(60), |delay| {
+                Duration::from_secs(delay.get())
+            });
+        let minimum_l1_batch_age = Duration::from_secs(config.data_retention_sec.unwrap_or(3_600));
+        let retention_period = |sec: Option<u64>| sec.map(Duration::from_secs);
+        let retention = RetentionPolicy {
+            events: retention_period(config.events_retention_sec),
+            l2_to_l1_logs: retention_period(config.l2_to_l1_logs_retention_sec),
+            call_traces: retention_period(config.call_traces_retention_sec),
+            transaction_data: retention_period(config.transaction_data_retention_sec),
+            retained_addresses: config.retained_addresses,
+        };
+
+        let layer = PruningLayer::new(
+            removal_delay,
+            config.chunk_size.unwrap_or(10),
+            minimum_l1_batch_age,
+        )
+        .with_retention_policy(retention)
+        .with_mintlayer_anchoring(config.wait_for_mintlayer_anchoring)
+        .with_dry_run(config.dry_run);
+        self.node.add_layer(layer);
+        Ok(self)
+    }
+
     pub fn build(mut self, mut components: Vec<Component>) -> anyhow::Result<ZkStackService> {
         // Add "base" layers (resources and helper tasks).
         self = self
@@ -541,7 +624,8 @@ impl MainNodeBuilder {
             .add_prometheus_exporter_layer()?
             .add_query_eth_client_layer()?
             .add_sequencer_l1_gas_layer()?
-            .add_l1_batch_commitment_mode_validation_layer()?;
+            .add_l1_batch_commitment_mode_validation_layer()?
+            .add_tx_allowlist_layer()?;
 
         // Sort the components, so that the components they may depend on each other are added in the correct order.
         components.sort_unstable_by_key(|component| match component {
@@ -626,6 +710,12 @@ impl MainNodeBuilder {
                 Component::VmRunnerBwip => {
                     self = self.add_vm_runner_bwip_layer()?;
                 }
+                Component::DbPruner => {
+                    self = self.add_pruning_layer()?;
+                }
+                Component::BlockArchiver => {
+                    self = self.add_block_archiver_layer()?;
+                }
             }
         }
         Ok(self.node.build()?)
@@ -0,0 +1,326 @@
+//! Minimal support for IPFS content IDs (CIDs) and UnixFS file blocks, used to verify data fetched
+//! from untrusted IPFS gateways.
+
+use std::fmt;
+
+use anyhow::Context as _;
+use prost::Message as _;
+use sha2::{Digest as _, Sha256};
+
+/// Multihash code for SHA2-256.
+const SHA2_256_CODE: u64 = 0x12;
+const SHA2_256_LEN: usize = 32;
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Codec of an IPFS block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Codec {
+    /// Raw binary data.
+    Raw,
+    /// `dag-pb` node (e.g., a UnixFS file node).
+    DagPb,
+}
+
+impl Codec {
+    fn code(self) -> u64 {
+        match self {
+            Self::Raw => 0x55,
+            Self::DagPb => 0x70,
+        }
+    }
+
+    fn from_code(code: u64) -> anyhow::Result<Self> {
+        match code {
+            0x55 => Ok(Self::Raw),
+            0x70 => Ok(Self::DagPb),
+            _ => anyhow::bail!("unsupported CID codec {code:#x}"),
+        }
+    }
+}
+
+/// Parsed content ID. Only SHA2-256 multihashes are supported, which is the default for IPFS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Cid {
+    codec: Codec,
+    digest: [u8; SHA2_256_LEN],
+}
+
+impl Cid {
+    /// Parses a CID from its string form: either a base58btc CIDv0 (`Qm...`), or a base32 CIDv1 (`b...`).
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        if s.starts_with("Qm") {
+            let bytes = decode_base58(s).context("invalid base58 encoding")?;
+            return Self::from_bytes(&bytes);
+        }
+        let encoded = s
+            .strip_prefix('b')
+            .context("unsupported CID encoding; only CIDv0 and base32 CIDv1 are supported")?;
+        let bytes = decode_base32(encoded).context("invalid base32 encoding")?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parses a CID from its binary form (e.g., as used in `dag-pb` links).
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        // CIDv0 is a bare SHA2-256 multihash of a `dag-pb` node.
+        if bytes.len() == SHA2_256_LEN + 2 && bytes[0] == SHA2_256_CODE as u8 {
+            return Ok(Self {
+                codec: Codec::DagPb,
+                digest: parse_multihash(bytes)?,
+            });
+        }
+
+        let mut reader = bytes;
+        let version = read_varint(&mut reader)?;
+        anyhow::ensure!(version == 1, "unsupported CID version {version}");
+        let codec = Codec::from_code(read_varint(&mut reader)?)?;
+        Ok(Self {
+            codec,
+            digest: parse_multihash(reader)?,
+        })
+    }
+
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Checks that the provided block corresponds to this CID.
+    pub fn verify(&self, block: &[u8]) -> anyhow::Result<()> {
+        let digest: [u8; SHA2_256_LEN] = Sha256::digest(block).into();
+        anyhow::ensure!(
+            digest == self.digest,
+            "block content doesn't match CID {self}"
+        );
+        Ok(())
+    }
+}
+
+/// Formats the CID as a base32 CIDv1, which is accepted by all gateways.
+impl fmt::Display for Cid {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = vec![];
+        write_varint(&mut bytes, 1);
+        write_varint(&mut bytes, self.codec.code());
+        write_varint(&mut bytes, SHA2_256_CODE);
+        write_varint(&mut bytes, SHA2_256_LEN as u64);
+        bytes.extend_from_slice(&self.digest);
+        write!(formatter, "b{}", encode_base32(&bytes))
+    }
+}
+
+/// Node of a UnixFS file DAG.
+#[derive(Debug)]
+pub(crate) struct UnixFsNode {
+    /// File data stored in this node. Precedes data of the linked nodes.
+    pub data: Vec<u8>,
+    /// Links to child nodes in the file order.
+    pub links: Vec<Cid>,
+}
+
+impl UnixFsNode {
+    /// UnixFS node types corresponding to file data.
+    const RAW_TYPE: i32 = 0;
+    const FILE_TYPE: i32 = 2;
+
+    /// Decodes a `dag-pb` block. Returns an error if the block is not a UnixFS file node.
+    pub fn decode(block: &[u8]) -> anyhow::Result<Self> {
+        let node = PbNode::decode(block).context("malformed dag-pb node")?;
+        let unixfs = node.data.context("dag-pb node has no UnixFS data")?;
+        let unixfs = UnixFsData::decode(unixfs.as_slice()).context("malformed UnixFS data")?;
+        anyhow::ensure!(
+            matches!(unixfs.r#type, Self::RAW_TYPE | Self::FILE_TYPE),
+            "UnixFS node has unsupported type {}; only files are supported",
+            unixfs.r#type
+        );
+
+        let links = node
+            .links
+            .iter()
+            .map(|link| {
+                let hash = link.hash.as_deref().context("dag-pb link has no hash")?;
+                Cid::from_bytes(hash)
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            data: unixfs.data.unwrap_or_default(),
+            links,
+        })
+    }
+}
+
+/// `PBNode` message from the `dag-pb` spec.
+#[derive(Clone, PartialEq, prost::Message)]
+struct PbNode {
+    #[prost(message, repeated, tag = "2")]
+    links: Vec<PbLink>,
+    #[prost(bytes = "vec", optional, tag = "1")]
+    data: Option<Vec<u8>>,
+}
+
+/// `PBLink` message from the `dag-pb` spec.
+#[derive(Clone, PartialEq, prost::Message)]
+struct PbLink {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    hash: Option<Vec<u8>>,
+    #[prost(string, optional, tag = "2")]
+    name: Option<String>,
+    #[prost(uint64, optional, tag = "3")]
+    tsize: Option<u64>,
+}
+
+/// `Data` message from the UnixFS spec (only fields relevant for files).
+#[derive(Clone, PartialEq, prost::Message)]
+struct UnixFsData {
+    #[prost(int32, required, tag = "1")]
+    r#type: i32,
+    #[prost(bytes = "vec", optional, tag = "2")]
+    data: Option<Vec<u8>>,
+    #[prost(uint64, optional, tag = "3")]
+    filesize: Option<u64>,
+    #[prost(uint64, repeated, packed = "false", tag = "4")]
+    blocksizes: Vec<u64>,
+}
+
+fn parse_multihash(mut bytes: &[u8]) -> anyhow::Result<[u8; SHA2_256_LEN]> {
+    let code = read_varint(&mut bytes)?;
+    anyhow::ensure!(
+        code == SHA2_256_CODE,
+        "unsupported multihash code {code:#x}; only SHA2-256 is supported"
+    );
+    let len = read_varint(&mut bytes)?;
+    anyhow::ensure!(
+        len == SHA2_256_LEN as u64 && bytes.len() == SHA2_256_LEN,
+        "invalid SHA2-256 multihash length"
+    );
+    Ok(bytes.try_into().unwrap())
+}
+
+fn read_varint(bytes: &mut &[u8]) -> anyhow::Result<u64> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().context("unexpected end of varint")?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    anyhow::bail!("varint overflow")
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn decode_base58(s: &str) -> anyhow::Result<Vec<u8>> {
+    // Big-endian base-256 digits of the decoded number.
+    let mut digits: Vec<u8> = vec![];
+    for ch in s.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&c| c == ch)
+            .with_context(|| format!("invalid base58 character {:?}", char::from(ch)))?
+            as u32;
+        for digit in digits.iter_mut().rev() {
+            carry += u32::from(*digit) * 58;
+            *digit = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            digits.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let leading_zeros = s.bytes().take_while(|&ch| ch == BASE58_ALPHABET[0]).count();
+    let mut bytes = vec![0; leading_zeros];
+    bytes.extend(digits);
+    Ok(bytes)
+}
+
+fn decode_base32(s: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(s.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for ch in s.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&c| c == ch)
+            .with_context(|| format!("invalid base32 character {:?}", char::from(ch)))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0_u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(char::from(
+                BASE32_ALPHABET[(buffer >> bits) as usize & 0x1f],
+            ));
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        output.push(char::from(
+            BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 0x1f],
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `dag-pb` block produced by `ipfs add` for a file with `hello\n` contents.
+    const HELLO_BLOCK: &[u8] = b"\x0a\x0c\x08\x02\x12\x06hello\n\x18\x06";
+    const HELLO_CID_V0: &str = "QmZULkCELmmk5XNfCgTnCyFgAVxBRBXyDHGGMVoLFLiXEN";
+    const HELLO_RAW_CID_V1: &str = "bafkreicysg23kiwv34eg2d7qweipxwosdo2py4ldv42nbauguluen5v6am";
+
+    #[test]
+    fn parsing_and_verifying_cid_v0() {
+        let cid = Cid::parse(HELLO_CID_V0).unwrap();
+        assert_eq!(cid.codec(), Codec::DagPb);
+        cid.verify(HELLO_BLOCK).unwrap();
+        cid.verify(b"hello\n").unwrap_err();
+
+        let node = UnixFsNode::decode(HELLO_BLOCK).unwrap();
+        assert_eq!(node.data, b"hello\n");
+        assert!(node.links.is_empty());
+
+        let cid_v1 = cid.to_string();
+        assert!(cid_v1.starts_with("bafy"), "{cid_v1}");
+        assert_eq!(Cid::parse(&cid_v1).unwrap(), cid);
+    }
+
+    #[test]
+    fn parsing_and_verifying_raw_cid_v1() {
+        let cid = Cid::parse(HELLO_RAW_CID_V1).unwrap();
+        assert_eq!(cid.codec(), Codec::Raw);
+        cid.verify(b"hello\n").unwrap();
+        cid.verify(b"hello").unwrap_err();
+        assert_eq!(cid.to_string(), HELLO_RAW_CID_V1);
+    }
+
+    #[test]
+    fn rejecting_invalid_cids() {
+        Cid::parse("mock0123").unwrap_err();
+        Cid::parse("Qm0").unwrap_err();
+        Cid::parse(&HELLO_CID_V0[..40]).unwrap_err();
+        Cid::parse("bafkreicysg23").unwrap_err();
+    }
+}
@@ -0,0 +1,213 @@
+//! IPFS clients.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    io::Cursor,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use s3::{creds::Credentials, Bucket, Region};
+use zksync_basic_types::web3::keccak256;
+
+use crate::cid::{Cid, Codec, UnixFsNode};
+
+/// Client pinning data to IPFS and fetching it back.
+#[async_trait]
+pub trait IpfsClient: 'static + fmt::Debug + Send + Sync {
+    /// Uploads (pins) data to IPFS and returns its content ID (CID). `name` is a human-readable name of the uploaded
+    /// object; it doesn't influence the returned CID.
+    async fn upload(&self, name: &str, data: Vec<u8>) -> anyhow::Result<String>;
+
+    /// Fetches data with the specified CID.
+    async fn fetch(&self, cid: &str) -> anyhow::Result<Vec<u8>>;
+}
+
+/// HTTP-based [`IpfsClient`]. Uploads data via the 4EVERLAND S3-compatible API and fetches it via an IPFS gateway.
+///
+/// Data is fetched block by block using the [trustless gateway](https://specs.ipfs.tech/http-gateways/trustless-gateway/)
+/// API, and each block is checked against its CID. Only files (i.e., raw blocks and UnixFS file DAGs)
+/// with SHA2-256 CIDs are supported.
+#[derive(Debug)]
+pub struct HttpIpfsClient {
+    client: reqwest::Client,
+    gateway_url: String,
+    bucket: Option<Box<Bucket>>,
+}
+
+impl HttpIpfsClient {
+    const DEFAULT_GATEWAY_URL: &'static str = "https://4everland.io";
+    const FOUREVERLAND_ENDPOINT: &'static str = "https://endpoint.4everland.co";
+    /// Maximum number of blocks in a fetched file. With the default 256 KiB chunks, this corresponds to 16 GiB of data.
+    const MAX_BLOCK_COUNT: usize = 1 << 16;
+
+    /// Creates a read-only client fetching data from the specified IPFS gateway (e.g., `https://ipfs.io`).
+    pub fn new(gateway_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            gateway_url: gateway_url.trim_end_matches('/').to_owned(),
+            bucket: None,
+        }
+    }
+
+    /// Creates a client able to upload data using the 4EVERLAND bucket specified by the `4EVERLAND_API_KEY`,
+    /// `4EVERLAND_SECRET_KEY` and `4EVERLAND_BUCKET_NAME` env variables (the same ones as used for anchoring
+    /// L1 batch data). The gateway can be overridden using the `IPFS_GATEWAY_URL` env variable.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let api_key = std::env::var("4EVERLAND_API_KEY").context("4EVERLAND_API_KEY not set")?;
+        let secret_key =
+            std::env::var("4EVERLAND_SECRET_KEY").context("4EVERLAND_SECRET_KEY not set")?;
+        let bucket_name =
+            std::env::var("4EVERLAND_BUCKET_NAME").context("4EVERLAND_BUCKET_NAME not set")?;
+        let gateway_url = std::env::var("IPFS_GATEWAY_URL")
+            .unwrap_or_else(|_| Self::DEFAULT_GATEWAY_URL.to_owned());
+
+        let credentials = Credentials::new(Some(&api_key), Some(&secret_key), None, None, None)
+            .context("invalid 4EVERLAND credentials")?;
+        let region = Region::Custom {
+            region: "us-east-1".into(),
+            endpoint: Self::FOUREVERLAND_ENDPOINT.into(),
+        };
+        let bucket = Bucket::new(&bucket_name, region, credentials)
+            .context("failed creating 4EVERLAND bucket client")?;
+        Ok(Self {
+            bucket: Some(bucket),
+            ..Self::new(gateway_url)
+        })
+    }
+
+    async fn fetch_block(&self, cid: &Cid) -> anyhow::Result<Vec<u8>> {
+        let url = format!("{}/ipfs/{cid}?format=raw", self.gateway_url);
+        let response = self
+            .client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "application/vnd.ipld.raw")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("failed fetching `{url}`"))?;
+        let block = response
+            .bytes()
+            .await
+            .with_context(|| format!("failed reading response for `{url}`"))?;
+        cid.verify(&block)
+            .with_context(|| format!("gateway returned invalid data for `{url}`"))?;
+        Ok(block.into())
+    }
+}
+
+#[async_trait]
+impl IpfsClient for HttpIpfsClient {
+    async fn upload(&self, name: &str, data: Vec<u8>) -> anyhow::Result<String> {
+        let bucket = self
+            .bucket
+            .as_ref()
+            .context("IPFS client is read-only; 4EVERLAND credentials are not configured")?;
+        let response = bucket
+            .put_object_stream(&mut Cursor::new(data), name)
+            .await
+            .with_context(|| format!("failed uploading `{name}` to 4EVERLAND"))?;
+        anyhow::ensure!(
+            response.status_code() == 200,
+            "uploading `{name}` to 4EVERLAND failed with status {}",
+            response.status_code()
+        );
+
+        // 4EVERLAND returns the CID of the uploaded object in its metadata.
+        let (head, _) = bucket
+            .head_object(name)
+            .await
+            .with_context(|| format!("failed getting metadata for `{name}` from 4EVERLAND"))?;
+        head.metadata
+            .and_then(|mut metadata| metadata.remove("ipfs-hash"))
+            .with_context(|| format!("missing IPFS hash in metadata for `{name}`"))
+    }
+
+    async fn fetch(&self, cid: &str) -> anyhow::Result<Vec<u8>> {
+        let root_cid = Cid::parse(cid).with_context(|| format!("invalid CID `{cid}`"))?;
+        let mut data = vec![];
+        // Blocks are traversed depth-first, which corresponds to the file order.
+        let mut pending_cids = vec![root_cid];
+        let mut block_count = 0;
+        while let Some(block_cid) = pending_cids.pop() {
+            block_count += 1;
+            anyhow::ensure!(
+                block_count <= Self::MAX_BLOCK_COUNT,
+                "object `{cid}` has more than {} blocks",
+                Self::MAX_BLOCK_COUNT
+            );
+
+            let block = self.fetch_block(&block_cid).await?;
+            match block_cid.codec() {
+                Codec::Raw => data.extend_from_slice(&block),
+                Codec::DagPb => {
+                    let node = UnixFsNode::decode(&block)
+                        .with_context(|| format!("invalid block {block_cid} for object `{cid}`"))?;
+                    data.extend_from_slice(&node.data);
+                    pending_cids.extend(node.links.into_iter().rev());
+                }
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// In-memory [`IpfsClient`] implementation. CIDs are derived from the keccak256 hash of the uploaded data.
+#[derive(Debug, Clone, Default)]
+pub struct MockIpfsClient {
+    objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl MockIpfsClient {
+    /// Inserts an object with the specified CID, replacing the existing object if any.
+    pub fn insert(&self, cid: String, data: Vec<u8>) {
+        self.objects.lock().unwrap().insert(cid, data);
+    }
+
+    /// Returns the number of uploaded objects.
+    pub fn len(&self) -> usize {
+        self.objects.lock().unwrap().len()
+    }
+
+    /// Checks whether no objects are uploaded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl IpfsClient for MockIpfsClient {
+    async fn upload(&self, _name: &str, data: Vec<u8>) -> anyhow::Result<String> {
+        let cid = format!("mock{}", hex::encode(keccak256(&data)));
+        self.insert(cid.clone(), data);
+        Ok(cid)
+    }
+
+    async fn fetch(&self, cid: &str) -> anyhow::Result<Vec<u8>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(cid)
+            .cloned()
+            .with_context(|| format!("object `{cid}` is not present in IPFS"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn mock_ipfs_client_basics() {
+        let client = MockIpfsClient::default();
+        let cid = client.upload("test", b"test".to_vec()).await.unwrap();
+        assert_eq!(client.fetch(&cid).await.unwrap(), b"test");
+        let same_cid = client.upload("other", b"test".to_vec()).await.unwrap();
+        assert_eq!(same_cid, cid);
+        assert_eq!(client.len(), 1);
+
+        client.fetch("missing").await.unwrap_err();
+    }
+}
@@ -0,0 +1,18 @@
+//! Clients for anchoring data on IPFS and Mintlayer.
+//!
+//! Data is pinned to IPFS via the [4EVERLAND](https://www.4everland.org/) S3-compatible API and is read back
+//! via an IPFS gateway; fetched data is verified against its content ID, so the gateway doesn't need to be trusted.
+//! Content IDs (CIDs) of the pinned data are anchored on Mintlayer as data deposits;
+//! anchored data can be read back via the Mintlayer API server.
+//!
+//! Both clients are defined as traits so that they can be mocked in tests; see [`MockIpfsClient`]
+//! and [`MockMintlayerClient`].
+
+pub use self::{
+    ipfs::{HttpIpfsClient, IpfsClient, MockIpfsClient},
+    mintlayer::{HttpMintlayerClient, MintlayerClient, MockMintlayerClient},
+};
+
+mod cid;
+mod ipfs;
+mod mintlayer;
@@ -0,0 +1,224 @@
+//! Mintlayer clients.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use base64::Engine as _;
+use zksync_basic_types::web3::keccak256;
+
+/// Client anchoring data on Mintlayer and reading anchored data back.
+#[async_trait]
+pub trait MintlayerClient: 'static + fmt::Debug + Send + Sync {
+    /// Deposits data on Mintlayer and returns the ID of the depositing transaction.
+    async fn deposit_data(&self, data: &[u8]) -> anyhow::Result<String>;
+
+    /// Fetches data deposited by the specified transaction.
+    async fn fetch_deposited_data(&self, tx_id: &str) -> anyhow::Result<Vec<u8>>;
+}
+
+/// HTTP-based [`MintlayerClient`]. Deposits data via the Mintlayer wallet RPC and reads deposited data
+/// via the Mintlayer API server.
+pub struct HttpMintlayerClient {
+    client: reqwest::Client,
+    wallet_rpc_url: Option<String>,
+    wallet_rpc_auth: Option<String>,
+    wallet_mnemonic: Option<String>,
+    api_server_url: Option<String>,
+}
+
+impl fmt::Debug for HttpMintlayerClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Credentials and the mnemonic are intentionally omitted.
+        formatter
+            .debug_struct("HttpMintlayerClient")
+            .field("wallet_rpc_url", &self.wallet_rpc_url)
+            .field("api_server_url", &self.api_server_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HttpMintlayerClient {
+    /// Creates a read-only client using the specified Mintlayer API server (e.g., `https://api-server.mintlayer.org/api/v2`).
+    pub fn new(api_server_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            wallet_rpc_url: None,
+            wallet_rpc_auth: None,
+            wallet_mnemonic: None,
+            api_server_url: Some(api_server_url.trim_end_matches('/').to_owned()),
+        }
+    }
+
+    /// Creates a client depositing data via the wallet RPC specified by the `ML_RPC_URL`, `ML_RPC_USERNAME`
+    /// and `ML_RPC_PASSWORD` env variables (the same ones as used for anchoring L1 batch data). The wallet
+    /// is expected to be created and opened; see [`Self::prepare_wallet()`]. The optional `ML_MNEMONIC` env variable
+    /// specifies the mnemonic used to create the wallet.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let wallet_rpc_url = std::env::var("ML_RPC_URL").context("ML_RPC_URL not set")?;
+        let username = std::env::var("ML_RPC_USERNAME");
+        let password = std::env::var("ML_RPC_PASSWORD");
+        let wallet_rpc_auth = match (username, password) {
+            (Ok(username), Ok(password)) => {
+                let credentials = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{username}:{password}"));
+                Some(format!("Basic {credentials}"))
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            wallet_rpc_url: Some(wallet_rpc_url),
+            wallet_rpc_auth,
+            wallet_mnemonic: std::env::var("ML_MNEMONIC").ok(),
+            api_server_url: None,
+        })
+    }
+
+    /// Creates (if necessary) and opens the wallet used to deposit data, and ensures that it has an address.
+    /// Errors creating the wallet are logged and ignored since the wallet may already exist.
+    pub async fn prepare_wallet(&self) -> anyhow::Result<()> {
+        const WALLET_PATH: &str = "/home/mintlayer/wallet.dat";
+
+        let mut create_params = serde_json::json!({
+            "path": WALLET_PATH,
+            "store_seed_phrase": true,
+        });
+        if let Some(mnemonic) = &self.wallet_mnemonic {
+            create_params["mnemonic"] = mnemonic.clone().into();
+        }
+        if let Err(err) = self.call_wallet_rpc("wallet_create", create_params).await {
+            tracing::info!("Failed creating Mintlayer wallet (it may already exist): {err:#}");
+        }
+        if let Err(err) = self
+            .call_wallet_rpc("wallet_open", serde_json::json!({ "path": WALLET_PATH }))
+            .await
+        {
+            tracing::info!("Failed opening Mintlayer wallet (it may already be open): {err:#}");
+        }
+        self.call_wallet_rpc("address_new", serde_json::json!({ "account": 0 }))
+            .await
+            .context("failed creating address in Mintlayer wallet")?;
+        Ok(())
+    }
+
+    async fn call_wallet_rpc(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let url = self
+            .wallet_rpc_url
+            .as_deref()
+            .context("Mintlayer wallet RPC is not configured")?;
+        let payload = serde_json::json!({
+            "method": method,
+            "params": params,
+            "jsonrpc": "2.0",
+            "id": 1,
+        });
+        let mut request = self.client.post(url).json(&payload);
+        if let Some(auth) = &self.wallet_rpc_auth {
+            request = request.header(reqwest::header::AUTHORIZATION, auth);
+        }
+
+        let mut response: serde_json::Value = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("failed sending `{method}` request to Mintlayer wallet"))?
+            .json()
+            .await
+            .context("failed parsing Mintlayer wallet response")?;
+        if let Some(err) = response.get("error") {
+            anyhow::bail!("Mintlayer wallet returned error for `{method}`: {err}");
+        }
+        Ok(response["result"].take())
+    }
+}
+
+#[async_trait]
+impl MintlayerClient for HttpMintlayerClient {
+    async fn deposit_data(&self, data: &[u8]) -> anyhow::Result<String> {
+        let params = serde_json::json!({
+            "data": hex::encode(data),
+            "account": 0,
+            "options": {},
+        });
+        let result = self.call_wallet_rpc("address_deposit_data", params).await?;
+        let tx_id = result
+            .as_str()
+            .with_context(|| format!("no transaction ID in Mintlayer wallet response: {result}"))?;
+        Ok(tx_id.to_owned())
+    }
+
+    async fn fetch_deposited_data(&self, tx_id: &str) -> anyhow::Result<Vec<u8>> {
+        let api_server_url = self
+            .api_server_url
+            .as_deref()
+            .context("Mintlayer API server is not configured")?;
+        let url = format!("{api_server_url}/transaction/{tx_id}");
+        let response: serde_json::Value = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .with_context(|| format!("failed fetching `{url}`"))?
+            .json()
+            .await
+            .with_context(|| format!("failed parsing response for `{url}`"))?;
+
+        let outputs = response
+            .get("outputs")
+            .and_then(|outputs| outputs.as_array())
+            .with_context(|| format!("Mintlayer transaction {tx_id} has no outputs"))?;
+        let data = outputs
+            .iter()
+            .find(|output| output.get("type").and_then(|ty| ty.as_str()) == Some("DataDeposit"))
+            .and_then(|output| output.get("data")?.as_str())
+            .with_context(|| format!("Mintlayer transaction {tx_id} has no data deposit"))?;
+        hex::decode(data.trim_start_matches("0x")).with_context(|| {
+            format!("data deposit in Mintlayer transaction {tx_id} is not hex-encoded")
+        })
+    }
+}
+
+/// In-memory [`MintlayerClient`] implementation. Transaction IDs are derived from the keccak256 hash of the deposited data.
+#[derive(Debug, Clone, Default)]
+pub struct MockMintlayerClient {
+    deposits: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl MockMintlayerClient {
+    /// Returns all deposits made via this client.
+    pub fn deposits(&self) -> HashMap<String, Vec<u8>> {
+        self.deposits.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl MintlayerClient for MockMintlayerClient {
+    async fn deposit_data(&self, data: &[u8]) -> anyhow::Result<String> {
+        let tx_id = hex::encode(keccak256(data));
+        self.deposits
+            .lock()
+            .unwrap()
+            .insert(tx_id.clone(), data.to_vec());
+        Ok(tx_id)
+    }
+
+    async fn fetch_deposited_data(&self, tx_id: &str) -> anyhow::Result<Vec<u8>> {
+        self.deposits
+            .lock()
+            .unwrap()
+            .get(tx_id)
+            .cloned()
+            .with_context(|| format!("Mintlayer transaction {tx_id} not found"))
+    }
+}
@@ -193,6 +193,15 @@ pub struct Web3JsonRpcConfig {
     pub fee_history_limit: Option<u64>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     pub max_batch_request_size: Option<usize>,
+    /// Maximum number of calls in a single `zks_simulateBundle` request. Default is 16.
+    pub max_simulated_bundle_size: Option<usize>,
+    /// Timeout for executing all calls in a `zks_simulateBundle` request (in ms). Default is 10 seconds.
+    pub simulate_bundle_timeout_ms: Option<u64>,
+    /// Maximum number of transactions in a single `zks_sendBundle` request. Default is 16.
+    pub max_submitted_bundle_size: Option<usize>,
+    /// Timeout for simulating all transactions in a `zks_sendBundle` request before submission (in ms).
+    /// Default is 10 seconds.
+    pub submit_bundle_timeout_ms: Option<u64>,
     /// Maximum response body size in MiBs. Default is 10 MiB.
     pub max_response_body_size_mb: Option<usize>,
     /// Method-specific overrides in MiBs for the maximum response body size.
@@ -220,6 +229,10 @@ pub struct Web3JsonRpcConfig {
     /// (hundreds or thousands RPS).
     #[serde(default)]
     pub extended_api_tracing: bool,
+    /// Enables the `tee_finalized` block tag, which resolves to the last L2 block in the latest L1 batch
+    /// such that it and all preceding L1 batches have TEE proofs. Skipped L1 batches are not considered proven.
+    #[serde(default)]
+    pub tee_finalized_tag_enabled: bool,
 }
 
 impl Web3JsonRpcConfig {
@@ -251,6 +264,10 @@ impl Web3JsonRpcConfig {
             latest_values_cache_size_mb: Default::default(),
             fee_history_limit: Default::default(),
             max_batch_request_size: Default::default(),
+            max_simulated_bundle_size: Default::default(),
+            simulate_bundle_timeout_ms: Default::default(),
+            max_submitted_bundle_size: Default::default(),
+            submit_bundle_timeout_ms: Default::default(),
             max_response_body_size_mb: Default::default(),
             max_response_body_size_overrides_mb: MaxResponseSizeOverrides::empty(),
             websocket_requests_per_minute_limit: Default::default(),
@@ -260,6 +277,7 @@ impl Web3JsonRpcConfig {
             whitelisted_tokens_for_aa: Default::default(),
             api_namespaces: None,
             extended_api_tracing: false,
+            tee_finalized_tag_enabled: false,
         }
     }
 
@@ -326,6 +344,22 @@ impl Web3JsonRpcConfig {
         self.max_batch_request_size.unwrap_or(500)
     }
 
+    pub fn max_simulated_bundle_size(&self) -> usize {
+        self.max_simulated_bundle_size.unwrap_or(16)
+    }
+
+    pub fn simulate_bundle_timeout(&self) -> Duration {
+        Duration::from_millis(self.simulate_bundle_timeout_ms.unwrap_or(10_000))
+    }
+
+    pub fn max_submitted_bundle_size(&self) -> usize {
+        self.max_submitted_bundle_size.unwrap_or(16)
+    }
+
+    pub fn submit_bundle_timeout(&self) -> Duration {
+        Duration::from_millis(self.submit_bundle_timeout_ms.unwrap_or(10_000))
+    }
+
     pub fn max_response_body_size(&self) -> MaxResponseSize {
         let scale = NonZeroUsize::new(super::BYTES_IN_MEGABYTE).unwrap();
         MaxResponseSize {
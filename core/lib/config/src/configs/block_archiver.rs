@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Configuration for the L2 block archiver, which archives L2 blocks to the object store so that
+/// external nodes can sync from the archive instead of the main node API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BlockArchiverConfig {
+    /// How often to check whether a new chunk of L2 blocks can be archived.
+    #[serde(default = "BlockArchiverConfig::default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Whether to additionally pin archived chunks to IPFS. IPFS uploads use the same 4EVERLAND credentials
+    /// (`4EVERLAND_*` env variables) as L1 batch data anchoring.
+    #[serde(default)]
+    pub upload_to_ipfs: bool,
+}
+
+impl Default for BlockArchiverConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: Self::default_poll_interval_ms(),
+            upload_to_ipfs: false,
+        }
+    }
+}
+
+impl BlockArchiverConfig {
+    const fn default_poll_interval_ms() -> u64 {
+        10_000
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
@@ -131,6 +131,26 @@ pub struct StateKeeperConfig {
     #[serde(default = "StateKeeperConfig::default_protective_reads_persistence_enabled")]
     pub protective_reads_persistence_enabled: bool,
 
+    /// Maximum estimated size (in bytes) of the L1 batch payload anchored to IPFS / Mintlayer.
+    /// If not set, the anchoring payload size doesn't influence L1 batch sealing.
+    #[serde(default)]
+    pub max_anchoring_payload_bytes: Option<u64>,
+    /// Maximum number of L2 blocks in an L1 batch. If not set, the number of L2 blocks is not limited.
+    #[serde(default)]
+    pub max_l2_blocks_per_batch: Option<u32>,
+    /// Whether the sequencer only accepts L2 transactions permitted by the allowlist stored in Postgres
+    /// (the `tx_allowlist` table). Contract deployments performed during execution (e.g., via factory contracts)
+    /// are restricted to addresses with the deployer permission; other internal calls are not restricted.
+    #[serde(default)]
+    pub tx_allowlist_enabled: bool,
+    /// Interval (in ms) between reloading the transaction allowlist from Postgres.
+    #[serde(default = "StateKeeperConfig::default_tx_allowlist_reload_interval_ms")]
+    pub tx_allowlist_reload_interval_ms: u64,
+    /// Names of the custom seal criteria (as reported in metrics) enabled for the conditional sealer.
+    /// Criteria must be registered with the node; configuring an unknown name is an error.
+    #[serde(default)]
+    pub custom_seal_criteria: Vec<String>,
+
     // Base system contract hashes, required only for generating genesis config.
     // #PLA-811
     #[deprecated(note = "Use GenesisConfig::bootloader_hash instead")]
@@ -147,6 +167,14 @@ impl StateKeeperConfig {
         true
     }
 
+    pub fn default_tx_allowlist_reload_interval_ms() -> u64 {
+        10_000
+    }
+
+    pub fn tx_allowlist_reload_interval(&self) -> Duration {
+        Duration::from_millis(self.tx_allowlist_reload_interval_ms)
+    }
+
     /// Creates a config object suitable for use in unit tests.
     /// Values mostly repeat the values used in the localhost environment.
     pub fn for_tests() -> Self {
@@ -179,6 +207,11 @@ impl StateKeeperConfig {
             save_call_traces: true,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            max_anchoring_payload_bytes: None,
+            max_l2_blocks_per_batch: None,
+            tx_allowlist_enabled: false,
+            tx_allowlist_reload_interval_ms: Self::default_tx_allowlist_reload_interval_ms(),
+            custom_seal_criteria: vec![],
             bootloader_hash: None,
             default_aa_hash: None,
             l1_batch_commit_data_generator_mode: L1BatchCommitmentMode::Rollup,
@@ -229,6 +262,15 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Maximum number of pending transactions per initiator account kept in the mempool.
+    /// If not set, the number of transactions per account is only bounded by `capacity`.
+    pub max_pending_txs_per_account: Option<usize>,
+    /// Time after which transactions stuck behind a nonce gap are evicted from the mempool (in seconds).
+    /// If not set, such transactions are only evicted when the mempool is full.
+    pub nonce_gap_ttl_sec: Option<u64>,
+    /// Whether to evict transactions with the lowest fees when the mempool is full.
+    #[serde(default)]
+    pub evict_low_fee_txs: bool,
 }
 
 impl MempoolConfig {
@@ -236,6 +278,10 @@ impl MempoolConfig {
         Duration::from_millis(self.sync_interval_ms)
     }
 
+    pub fn nonce_gap_ttl(&self) -> Option<Duration> {
+        self.nonce_gap_ttl_sec.map(Duration::from_secs)
+    }
+
     pub fn stuck_tx_timeout(&self) -> Duration {
         Duration::from_secs(self.stuck_tx_timeout)
     }
@@ -12,6 +12,10 @@ pub struct ValidatorPublicKey(pub String);
 #[derive(Debug, Clone)]
 pub struct ValidatorSecretKey(pub Secret<String>);
 
+/// `zksync_consensus_crypto::TextFmt` representation of `zksync_consensus_roles::attester::PublicKey`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AttesterPublicKey(pub String);
+
 /// `zksync_consensus_crypto::TextFmt` representation of `zksync_consensus_roles::node::PublicKey`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NodePublicKey(pub String);
@@ -41,6 +45,15 @@ pub struct WeightedValidator {
     pub weight: u64,
 }
 
+/// Copy-paste of `zksync_consensus_roles::attester::WeightedAttester`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedAttester {
+    /// Attester key
+    pub key: AttesterPublicKey,
+    /// Attester weight inside the Committee.
+    pub weight: u64,
+}
+
 /// Copy-paste of `zksync_concurrency::net::Host`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Host(pub String);
@@ -49,6 +62,19 @@ pub struct Host(pub String);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ProtocolVersion(pub u32);
 
+/// Leader selection mode of the validator committee.
+/// Represents `zksync_consensus_roles::validator::LeaderSelectionMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeaderSelectionMode {
+    /// `GenesisSpec::leader` leads all views.
+    #[default]
+    Sticky,
+    /// Validators lead views in turns.
+    RoundRobin,
+    /// View leaders are selected pseudo-randomly, proportionally to the validator weights.
+    Weighted,
+}
+
 /// Consensus genesis specification.
 /// It is a digest of the `validator::Genesis`,
 /// which allows to initialize genesis (if not present)
@@ -61,9 +87,17 @@ pub struct GenesisSpec {
     pub protocol_version: ProtocolVersion,
     /// The validator committee. Represents `zksync_consensus_roles::validator::Committee`.
     pub validators: Vec<WeightedValidator>,
-    /// Leader of the committee. Represents
-    /// `zksync_consensus_roles::validator::LeaderSelectionMode::Sticky`.
+    /// The attester committee. Represents `zksync_consensus_roles::attester::Committee`.
+    /// If empty, genesis doesn't define an attester committee. The committee is only recorded in the genesis;
+    /// L1 batches are not attested.
+    pub attesters: Vec<WeightedAttester>,
+    /// Leader of the committee in the sticky leader selection mode. Must be a member of the committee.
     pub leader: ValidatorPublicKey,
+    /// Leader selection mode. With rotating modes, views led by external node validators
+    /// can only repropose blocks already proposed by the main node, since only the main node
+    /// produces new blocks; other such views time out. Thus, rotating modes don't provide failover
+    /// of the main node.
+    pub leader_selection: LeaderSelectionMode,
 }
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -111,6 +145,12 @@ pub struct ConsensusConfig {
 
     /// Rate limiting configuration for the p2p RPCs.
     pub rpc: Option<RpcConfig>,
+
+    /// EXTERNAL NODE ONLY: if set, the node only accepts L2 blocks with consensus certificates.
+    /// L2 blocks preceding consensus genesis (which don't have certificates) are not fetched
+    /// from the main node; the node must be initialized at or after the first block of genesis
+    /// (e.g., via snapshot recovery).
+    pub require_certificates: bool,
 }
 
 impl ConsensusConfig {
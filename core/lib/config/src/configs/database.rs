@@ -46,6 +46,15 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[serde(default = "MerkleTreeConfig::default_max_l1_batches_per_iter")]
     pub max_l1_batches_per_iter: usize,
+    /// Capacity of the LRU cache for internal Merkle tree nodes, measured in the number of nodes. The cache speeds up
+    /// creating Merkle proofs in the tree API; it is only used by tree readers, not by the tree writer.
+    /// Setting it to 0 disables the cache. The default value is 16,384.
+    #[serde(default)]
+    pub node_cache_capacity: Option<usize>,
+    /// Whether to export tree checkpoints for L1 batches with storage snapshots to the object store. Checkpoints
+    /// allow external nodes to bootstrap the Merkle tree without recomputing it from snapshot storage logs.
+    #[serde(default)]
+    pub export_checkpoints: bool,
 }
 
 impl Default for MerkleTreeConfig {
@@ -58,6 +67,8 @@ impl Default for MerkleTreeConfig {
             memtable_capacity_mb: Self::default_memtable_capacity_mb(),
             stalled_writes_timeout_sec: Self::default_stalled_writes_timeout_sec(),
             max_l1_batches_per_iter: Self::default_max_l1_batches_per_iter(),
+            node_cache_capacity: None,
+            export_checkpoints: false,
         }
     }
 }
@@ -87,6 +98,11 @@ impl MerkleTreeConfig {
         20
     }
 
+    /// Returns the capacity of the LRU cache for internal Merkle tree nodes.
+    pub fn node_cache_capacity(&self) -> usize {
+        self.node_cache_capacity.unwrap_or(16_384)
+    }
+
     /// Returns the size of block cache size for Merkle tree in bytes.
     pub fn block_cache_size(&self) -> usize {
         self.block_cache_size_mb * super::BYTES_IN_MEGABYTE
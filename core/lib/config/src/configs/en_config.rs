@@ -2,9 +2,11 @@ use std::num::NonZeroUsize;
 
 use serde::Deserialize;
 use zksync_basic_types::{
-    commitment::L1BatchCommitmentMode, url::SensitiveUrl, L1ChainId, L2ChainId,
+    commitment::L1BatchCommitmentMode, url::SensitiveUrl, L1BatchNumber, L1ChainId, L2ChainId,
 };
 
+use crate::ObjectStoreConfig;
+
 /// Temporary config for initializing external node, will be completely replaced by consensus config later
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ENConfig {
@@ -16,4 +18,32 @@ pub struct ENConfig {
     // Main node configuration
     pub main_node_url: SensitiveUrl,
     pub main_node_rate_limit_rps: Option<NonZeroUsize>,
+
+    // Reorg recovery
+    /// Enables automatic rollback of the node state if a reorg is detected while the node is running.
+    pub reorg_auto_recovery_enabled: Option<bool>,
+    /// Maximum number of L1 batches that can be rolled back automatically.
+    pub reorg_auto_recovery_max_depth: Option<u32>,
+    /// Allows automatically rolling back L1 batches that were executed on L1.
+    pub reorg_auto_recovery_allow_executed_batches: Option<bool>,
+
+    // Verify-only mode
+    /// First L1 batch checked by the batch verifier if no batches are verified yet.
+    pub verifier_first_l1_batch: Option<L1BatchNumber>,
+    /// Mintlayer API server used by the batch verifier to check anchors of L1 batch commitments.
+    /// If not set, anchors are not checked.
+    pub verifier_mintlayer_api_url: Option<String>,
+    /// IPFS gateway used by the batch verifier to fetch anchored commit operations.
+    pub verifier_ipfs_gateway_url: Option<String>,
+
+    // Sync sources
+    /// Object store with the L2 block archive written by the main node. If set, L2 blocks are synced
+    /// from the archive instead of the main node JSON-RPC API.
+    pub sync_archive_object_store: Option<ObjectStoreConfig>,
+    /// IPFS gateway used to sync L2 blocks from the archive pinned to IPFS by the main node.
+    /// Mutually exclusive with `sync_archive_object_store`.
+    pub sync_archive_ipfs_gateway_url: Option<String>,
+    /// JSON-RPC URLs of external nodes used to resolve CIDs of the L2 block archive chunks pinned to IPFS.
+    /// Resolvers are queried in order; the main node is always queried last.
+    pub sync_archive_cid_resolver_urls: Vec<String>,
 }
@@ -1,6 +1,7 @@
 use crate::{
     configs::{
         base_token_adjuster::BaseTokenAdjusterConfig,
+        block_archiver::BlockArchiverConfig,
         chain::{CircuitBreakerConfig, MempoolConfig, OperationsManagerConfig, StateKeeperConfig},
         da_dispatcher::DADispatcherConfig,
         fri_prover_group::FriProverGroupConfig,
@@ -46,4 +47,5 @@ pub struct GeneralConfig {
     pub pruning: Option<PruningConfig>,
     pub core_object_store: Option<ObjectStoreConfig>,
     pub base_token_adjuster: Option<BaseTokenAdjusterConfig>,
+    pub block_archiver: Option<BlockArchiverConfig>,
 }
@@ -1,12 +1,24 @@
 use std::time::Duration;
 
 use serde::Deserialize;
+use zksync_basic_types::H256;
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ProofDataHandlerConfig {
     pub http_port: u16,
     pub proof_generation_timeout_in_secs: u16,
     pub tee_support: bool,
+    /// MRENCLAVE values of SGX enclaves allowed to register attestations and submit TEE proofs.
+    #[serde(default)]
+    pub tee_allowed_mr_enclaves: Vec<H256>,
+    /// MRSIGNER values of SGX enclaves allowed to register attestations and submit TEE proofs.
+    /// An enclave is accepted if either its MRENCLAVE or its MRSIGNER is allowed.
+    #[serde(default)]
+    pub tee_allowed_mr_signers: Vec<H256>,
+    /// Path to the PEM-encoded root CA certificate that PCK certificate chains in SGX quotes
+    /// must lead to. If not set, the Intel SGX Root CA embedded into the proof data handler is used.
+    #[serde(default)]
+    pub tee_sgx_root_ca_path: Option<String>,
 }
 
 impl ProofDataHandlerConfig {
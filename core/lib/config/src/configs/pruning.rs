@@ -1,6 +1,7 @@
 use std::num::NonZeroU64;
 
 use serde::Deserialize;
+use zksync_basic_types::Address;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct PruningConfig {
@@ -16,4 +17,24 @@ pub struct PruningConfig {
     /// the retention period greater than that implicitly imposed by other criteria (e.g., 7 or 30 days).
     /// If set to 0, L1 batches will not be retained based on their timestamp. The default value is 1 hour.
     pub data_retention_sec: Option<u64>,
+    /// If set, events outlive the pruned L1 batches and are removed once they are this old (in seconds).
+    /// Only has effect if greater than `data_retention_sec`.
+    pub events_retention_sec: Option<u64>,
+    /// Same as `events_retention_sec`, but for user L2-to-L1 logs.
+    pub l2_to_l1_logs_retention_sec: Option<u64>,
+    /// Same as `events_retention_sec`, but for call traces.
+    pub call_traces_retention_sec: Option<u64>,
+    /// Same as `events_retention_sec`, but for transaction input, data and execution info.
+    pub transaction_data_retention_sec: Option<u64>,
+    /// Data related to these addresses is never pruned: transactions initiated by or sent to these addresses,
+    /// their call traces, events emitted by these addresses and L2-to-L1 logs sent by them.
+    #[serde(default)]
+    pub retained_addresses: Vec<Address>,
+    /// If set, L1 batches are only pruned after they are anchored on Mintlayer. Only makes sense on nodes
+    /// that anchor batches on Mintlayer themselves.
+    #[serde(default)]
+    pub wait_for_mintlayer_anchoring: bool,
+    /// If set, the pruner doesn't modify the database; instead, it periodically reports what would be pruned.
+    #[serde(default)]
+    pub dry_run: bool,
 }
@@ -1,7 +1,7 @@
 use std::num::NonZeroUsize;
 
 use serde::Deserialize;
-use zksync_basic_types::L1BatchNumber;
+use zksync_basic_types::{Address, L1BatchNumber};
 
 use crate::ObjectStoreConfig;
 
@@ -18,6 +18,10 @@ pub struct TreeRecoveryConfig {
     ///
     /// If not set, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// Whether to bootstrap the tree from a checkpoint exported by the main node (if one is available for the snapshot
+    /// L1 batch) instead of recovering it from snapshot storage logs.
+    #[serde(default)]
+    pub use_checkpoints: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Default)]
@@ -42,6 +46,21 @@ pub struct SnapshotRecoveryConfig {
     /// This is a temporary flag that will eventually be removed together with version 0 snapshot support.
     #[serde(default)]
     pub drop_storage_key_preimages: bool,
+    /// If specified, requires manifests of all recovered snapshots to be signed by this address (normally,
+    /// the operator address of the main node). Otherwise, manifests are checked if they are provided by the main node,
+    /// but their signatures are not required.
+    #[serde(default)]
+    pub manifest_signer: Option<Address>,
+    /// If specified, the snapshot is recovered from IPFS rather than from the object store. The value is the ID
+    /// of the Mintlayer transaction anchoring the snapshot (it's logged by the snapshot creator on publication).
+    #[serde(default)]
+    pub ipfs_anchor_tx_id: Option<String>,
+    /// IPFS gateway used to fetch snapshot data if `ipfs_anchor_tx_id` is specified, e.g. `https://ipfs.io`.
+    #[serde(default)]
+    pub ipfs_gateway_url: Option<String>,
+    /// Mintlayer API server used to fetch the anchoring transaction if `ipfs_anchor_tx_id` is specified.
+    #[serde(default)]
+    pub mintlayer_api_url: Option<String>,
     pub tree: TreeRecoveryConfig,
     pub postgres: PostgresRecoveryConfig,
     pub object_store: Option<ObjectStoreConfig>,
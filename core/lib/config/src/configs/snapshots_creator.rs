@@ -5,7 +5,8 @@ use crate::ObjectStoreConfig;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct SnapshotsCreatorConfig {
-    /// Version of snapshots to create.
+    /// Version of snapshots to create. Version 2 corresponds to delta snapshots, which are based on the newest
+    /// complete snapshot preceding the snapshot L1 batch and only contain changes since it.
     // Raw integer version is used because `SnapshotVersion` is defined in `zksync_types` crate.
     #[serde(default)]
     pub version: u16,
@@ -20,6 +21,14 @@ pub struct SnapshotsCreatorConfig {
     pub storage_logs_chunk_size: u64,
     #[serde(default = "SnapshotsCreatorConfig::concurrent_queries_count")]
     pub concurrent_queries_count: u32,
+    /// Whether to sign snapshot manifests with the operator key. If set, the operator private key must be provided
+    /// via the `ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY` env variable.
+    #[serde(default)]
+    pub sign_manifests: bool,
+    /// Whether to publish complete snapshots to IPFS and anchor them on Mintlayer. If set, 4EVERLAND and Mintlayer
+    /// wallet RPC credentials must be provided via the same env variables as used for anchoring L1 batch data.
+    #[serde(default)]
+    pub publish_to_ipfs: bool,
     pub object_store: Option<ObjectStoreConfig>,
 }
 
@@ -1,4 +1,8 @@
-use zksync_basic_types::{Address, H160, H256};
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use secrecy::Secret;
+use zksync_basic_types::{url::SensitiveUrl, Address, H160, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -64,6 +68,59 @@ pub struct EthSender {
     pub blob_operator: Option<Wallet>,
 }
 
+/// Source of the password for an encrypted keystore.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeystorePassword {
+    /// Name of the env variable containing the password.
+    Env(String),
+    /// Path to the file containing the password. Trailing whitespace (e.g., a newline) is ignored.
+    File(PathBuf),
+}
+
+impl KeystorePassword {
+    pub fn read(&self) -> anyhow::Result<Secret<String>> {
+        let password = match self {
+            Self::Env(name) => std::env::var(name)
+                .with_context(|| format!("failed reading keystore password from env `{name}`"))?,
+            Self::File(path) => {
+                let mut password = std::fs::read_to_string(path).with_context(|| {
+                    format!("failed reading keystore password from {}", path.display())
+                })?;
+                password.truncate(password.trim_end().len());
+                password
+            }
+        };
+        Ok(Secret::new(password))
+    }
+}
+
+/// Wallet with the private key stored in an encrypted JSON keystore (Web3 secret storage).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeystoreWallet {
+    /// Expected wallet address. If set, it is checked against the decrypted private key.
+    pub address: Option<Address>,
+    /// Path to the keystore file.
+    pub path: PathBuf,
+    pub password: KeystorePassword,
+}
+
+/// Operator accounts whose keys are stored in encrypted keystores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeystoreEthSender {
+    pub operator: KeystoreWallet,
+    pub blob_operator: Option<KeystoreWallet>,
+}
+
+/// Operator accounts whose keys are held by a remote signing service (e.g., Web3Signer)
+/// instead of being stored in the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteEthSender {
+    /// URL of the signing service implementing `eth_signTransaction`.
+    pub signer_url: SensitiveUrl,
+    pub operator: AddressWallet,
+    pub blob_operator: Option<AddressWallet>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct StateKeeper {
     pub fee_account: AddressWallet,
@@ -72,6 +129,10 @@ pub struct StateKeeper {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Wallets {
     pub eth_sender: Option<EthSender>,
+    /// If set, takes precedence over `eth_sender` for signing L1 transactions.
+    pub keystore_eth_sender: Option<KeystoreEthSender>,
+    /// If set, takes precedence over `eth_sender` and `keystore_eth_sender` for signing L1 transactions.
+    pub remote_eth_sender: Option<RemoteEthSender>,
     pub state_keeper: Option<StateKeeper>,
 }
 
@@ -84,6 +145,8 @@ impl Wallets {
                     Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
                 ),
             }),
+            keystore_eth_sender: None,
+            remote_eth_sender: None,
             state_keeper: Some(StateKeeper {
                 fee_account: AddressWallet::from_address(H160::repeat_byte(0x3)),
             }),
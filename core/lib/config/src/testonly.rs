@@ -80,6 +80,10 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             latest_values_cache_size_mb: self.sample(rng),
             fee_history_limit: self.sample(rng),
             max_batch_request_size: self.sample(rng),
+            max_simulated_bundle_size: self.sample(rng),
+            simulate_bundle_timeout_ms: self.sample(rng),
+            max_submitted_bundle_size: self.sample(rng),
+            submit_bundle_timeout_ms: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
             max_response_body_size_overrides_mb: [
                 (
@@ -101,6 +105,7 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             api_namespaces: self
                 .sample_opt(|| self.sample_range(rng).map(|_| self.sample(rng)).collect()),
             extended_api_tracing: self.sample(rng),
+            tee_finalized_tag_enabled: self.sample(rng),
         }
     }
 }
@@ -180,6 +185,11 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             save_call_traces: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
             protective_reads_persistence_enabled: self.sample(rng),
+            max_anchoring_payload_bytes: self.sample(rng),
+            max_l2_blocks_per_batch: self.sample(rng),
+            tx_allowlist_enabled: self.sample(rng),
+            tx_allowlist_reload_interval_ms: self.sample(rng),
+            custom_seal_criteria: self.sample_range(rng).map(|_| self.sample(rng)).collect(),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
             bootloader_hash: None,
@@ -217,6 +227,9 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            max_pending_txs_per_account: self.sample(rng),
+            nonce_gap_ttl_sec: self.sample(rng),
+            evict_low_fee_txs: self.sample(rng),
         }
     }
 }
@@ -276,6 +289,8 @@ impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
             memtable_capacity_mb: self.sample(rng),
             stalled_writes_timeout_sec: self.sample(rng),
             max_l1_batches_per_iter: self.sample(rng),
+            node_cache_capacity: self.sample(rng),
+            export_checkpoints: self.sample(rng),
         }
     }
 }
@@ -637,6 +652,9 @@ impl Distribution<configs::ProofDataHandlerConfig> for EncodeDist {
             http_port: self.sample(rng),
             proof_generation_timeout_in_secs: self.sample(rng),
             tee_support: self.sample(rng),
+            tee_allowed_mr_enclaves: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            tee_allowed_mr_signers: self.sample_range(rng).map(|_| rng.gen()).collect(),
+            tee_sgx_root_ca_path: self.sample(rng),
         }
     }
 }
@@ -648,6 +666,8 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
             version: if rng.gen() { 0 } else { 1 },
             storage_logs_chunk_size: self.sample(rng),
             concurrent_queries_count: self.sample(rng),
+            sign_manifests: self.sample(rng),
+            publish_to_ipfs: self.sample(rng),
             object_store: self.sample(rng),
         }
     }
@@ -725,14 +745,32 @@ impl Distribution<configs::consensus::WeightedValidator> for EncodeDist {
     }
 }
 
+impl Distribution<configs::consensus::WeightedAttester> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::consensus::WeightedAttester {
+        use configs::consensus::{AttesterPublicKey, WeightedAttester};
+        WeightedAttester {
+            key: AttesterPublicKey(self.sample(rng)),
+            weight: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::consensus::GenesisSpec> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::consensus::GenesisSpec {
-        use configs::consensus::{GenesisSpec, ProtocolVersion, ValidatorPublicKey};
+        use configs::consensus::{
+            GenesisSpec, LeaderSelectionMode, ProtocolVersion, ValidatorPublicKey,
+        };
         GenesisSpec {
             chain_id: L2ChainId::default(),
             protocol_version: ProtocolVersion(self.sample(rng)),
             validators: self.sample_collect(rng),
             leader: ValidatorPublicKey(self.sample(rng)),
+            attesters: self.sample_collect(rng),
+            leader_selection: match rng.gen_range(0..3) {
+                0 => LeaderSelectionMode::Sticky,
+                1 => LeaderSelectionMode::RoundRobin,
+                _ => LeaderSelectionMode::Weighted,
+            },
         }
     }
 }
@@ -755,6 +793,7 @@ impl Distribution<configs::consensus::ConsensusConfig> for EncodeDist {
                 .collect(),
             genesis_spec: self.sample(rng),
             rpc: self.sample(rng),
+            require_certificates: self.sample(rng),
         }
     }
 }
@@ -837,11 +876,49 @@ impl Distribution<configs::wallets::EthSender> for EncodeDist {
     }
 }
 
+impl Distribution<configs::wallets::KeystoreWallet> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::KeystoreWallet {
+        configs::wallets::KeystoreWallet {
+            address: self.sample_opt(|| rng.gen()),
+            path: format!("/keystores/{}.json", rng.gen::<u16>()).into(),
+            password: match rng.gen_range(0..2) {
+                0 => configs::wallets::KeystorePassword::Env(self.sample(rng)),
+                _ => configs::wallets::KeystorePassword::File(
+                    format!("/secrets/{}", rng.gen::<u16>()).into(),
+                ),
+            },
+        }
+    }
+}
+
+impl Distribution<configs::wallets::KeystoreEthSender> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::KeystoreEthSender {
+        configs::wallets::KeystoreEthSender {
+            operator: self.sample(rng),
+            blob_operator: self.sample_opt(|| self.sample(rng)),
+        }
+    }
+}
+
+impl Distribution<configs::wallets::RemoteEthSender> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::RemoteEthSender {
+        configs::wallets::RemoteEthSender {
+            signer_url: format!("http://localhost:{}/", rng.gen::<u16>())
+                .parse()
+                .unwrap(),
+            operator: self.sample(rng),
+            blob_operator: self.sample_opt(|| self.sample(rng)),
+        }
+    }
+}
+
 impl Distribution<configs::wallets::Wallets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::wallets::Wallets {
         configs::wallets::Wallets {
             state_keeper: self.sample_opt(|| self.sample(rng)),
             eth_sender: self.sample_opt(|| self.sample(rng)),
+            keystore_eth_sender: self.sample_opt(|| self.sample(rng)),
+            remote_eth_sender: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
@@ -857,6 +934,24 @@ impl Distribution<configs::en_config::ENConfig> for EncodeDist {
                 _ => L1BatchCommitmentMode::Validium,
             },
             main_node_rate_limit_rps: self.sample_opt(|| rng.gen()),
+            reorg_auto_recovery_enabled: self.sample(rng),
+            reorg_auto_recovery_max_depth: self.sample(rng),
+            reorg_auto_recovery_allow_executed_batches: self.sample(rng),
+            verifier_first_l1_batch: self.sample_opt(|| L1BatchNumber(rng.gen())),
+            verifier_mintlayer_api_url: self.sample(rng),
+            verifier_ipfs_gateway_url: self.sample(rng),
+            sync_archive_object_store: self.sample(rng),
+            sync_archive_ipfs_gateway_url: self.sample(rng),
+            sync_archive_cid_resolver_urls: self.sample_collect(rng),
+        }
+    }
+}
+
+impl Distribution<configs::block_archiver::BlockArchiverConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::block_archiver::BlockArchiverConfig {
+        configs::block_archiver::BlockArchiverConfig {
+            poll_interval_ms: self.sample(rng),
+            upload_to_ipfs: self.sample(rng),
         }
     }
 }
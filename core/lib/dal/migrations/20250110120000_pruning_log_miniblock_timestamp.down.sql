@@ -0,0 +1 @@
+ALTER TABLE pruning_log DROP COLUMN IF EXISTS pruned_miniblock_timestamp;
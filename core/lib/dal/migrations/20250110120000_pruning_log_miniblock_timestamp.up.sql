@@ -0,0 +1 @@
+ALTER TABLE pruning_log ADD COLUMN IF NOT EXISTS pruned_miniblock_timestamp BIGINT;
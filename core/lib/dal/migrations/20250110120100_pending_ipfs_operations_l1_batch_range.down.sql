@@ -0,0 +1,3 @@
+ALTER TABLE pending_ipfs_operations
+    DROP COLUMN IF EXISTS l1_batch_number_from,
+    DROP COLUMN IF EXISTS l1_batch_number_to;
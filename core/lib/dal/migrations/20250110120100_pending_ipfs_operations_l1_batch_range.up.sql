@@ -0,0 +1,3 @@
+ALTER TABLE pending_ipfs_operations
+    ADD COLUMN IF NOT EXISTS l1_batch_number_from BIGINT,
+    ADD COLUMN IF NOT EXISTS l1_batch_number_to BIGINT;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS tx_allowlist
+(
+    address    BYTEA     NOT NULL,
+    permission TEXT      NOT NULL CHECK (permission IN ('sender', 'contract', 'deployer')),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (address, permission)
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS private_transactions;
+DROP TABLE IF EXISTS tx_bundles;
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS tx_bundles
+(
+    id              BIGSERIAL PRIMARY KEY,
+    target_l2_block BIGINT    NOT NULL,
+    created_at      TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Transactions that must not be exposed via pending transaction filters and subscriptions.
+-- Not linked to `transactions` via a foreign key since pending transactions may be replaced, which changes their hash.
+CREATE TABLE IF NOT EXISTS private_transactions
+(
+    tx_hash         BYTEA     PRIMARY KEY,
+    bundle_id       BIGINT    REFERENCES tx_bundles (id) ON DELETE CASCADE,
+    index_in_bundle INT,
+    created_at      TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS private_transactions_bundle_id_idx
+    ON private_transactions (bundle_id) WHERE bundle_id IS NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE snapshots DROP COLUMN base_l1_batch_number;
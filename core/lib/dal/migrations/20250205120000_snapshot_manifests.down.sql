@@ -0,0 +1,4 @@
+ALTER TABLE snapshots
+    DROP COLUMN factory_deps_digest,
+    DROP COLUMN storage_logs_digests,
+    DROP COLUMN manifest_signature;
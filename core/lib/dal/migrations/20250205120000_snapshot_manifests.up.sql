@@ -0,0 +1,4 @@
+ALTER TABLE snapshots
+    ADD COLUMN factory_deps_digest BYTEA,
+    ADD COLUMN storage_logs_digests BYTEA[],
+    ADD COLUMN manifest_signature BYTEA;
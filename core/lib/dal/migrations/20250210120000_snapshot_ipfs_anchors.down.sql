@@ -0,0 +1,3 @@
+ALTER TABLE snapshots
+    DROP COLUMN ipfs_document_cid,
+    DROP COLUMN mintlayer_anchor_tx_id;
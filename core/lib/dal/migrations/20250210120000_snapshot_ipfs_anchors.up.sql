@@ -0,0 +1,3 @@
+ALTER TABLE snapshots
+    ADD COLUMN ipfs_document_cid TEXT,
+    ADD COLUMN mintlayer_anchor_tx_id TEXT;
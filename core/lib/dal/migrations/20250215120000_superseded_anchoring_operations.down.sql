@@ -0,0 +1,8 @@
+-- Postgres doesn't support removing values from enums, so we only remove records using the value.
+DELETE FROM pending_ipfs_operations
+WHERE
+    status::TEXT = 'superseded';
+
+DELETE FROM pending_mintlayer_batches
+WHERE
+    status::TEXT = 'superseded';
@@ -0,0 +1 @@
+ALTER TYPE operation_status ADD VALUE IF NOT EXISTS 'superseded';
@@ -0,0 +1,11 @@
+-- Headers of L1 batches checked by an external node running in the verify-only mode.
+CREATE TABLE IF NOT EXISTS verified_l1_batches (
+    l1_batch_number BIGINT PRIMARY KEY,
+    root_hash BYTEA NOT NULL,
+    commitment BYTEA NOT NULL,
+    commit_tx_hash BYTEA NOT NULL,
+    ipfs_hash TEXT,
+    mintlayer_tx_id TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE l2_block_archive_chunks;
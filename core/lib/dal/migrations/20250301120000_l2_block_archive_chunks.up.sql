@@ -0,0 +1,9 @@
+-- Chunks of L2 blocks archived by the main node to the object store (and optionally, IPFS).
+CREATE TABLE IF NOT EXISTS l2_block_archive_chunks (
+    chunk_index BIGINT PRIMARY KEY,
+    first_l2_block BIGINT NOT NULL,
+    last_l2_block BIGINT NOT NULL,
+    last_l2_block_hash BYTEA NOT NULL,
+    ipfs_cid TEXT,
+    created_at TIMESTAMP NOT NULL
+);
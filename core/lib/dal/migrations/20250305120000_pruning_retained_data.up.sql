@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS pruning_retained_data (
+    data_class TEXT PRIMARY KEY,
+    next_miniblock BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
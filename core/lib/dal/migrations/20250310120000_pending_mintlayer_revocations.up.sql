@@ -0,0 +1,9 @@
+-- Compensating Mintlayer anchors issued by the block reverter. Revocations are recorded in the same DB transaction
+-- as the rollback and are sent to Mintlayer after it is committed.
+CREATE TABLE IF NOT EXISTS pending_mintlayer_revocations (
+    id BIGSERIAL PRIMARY KEY,
+    payload JSONB NOT NULL,
+    mintlayer_tx_id TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP
+);
@@ -0,0 +1,213 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{api::en, L2BlockNumber, H256};
+
+use crate::Core;
+
+#[derive(Debug)]
+struct StorageL2BlockArchiveChunk {
+    chunk_index: i64,
+    first_l2_block: i64,
+    last_l2_block: i64,
+    last_l2_block_hash: Vec<u8>,
+    ipfs_cid: Option<String>,
+}
+
+impl From<StorageL2BlockArchiveChunk> for en::L2BlockArchiveChunkInfo {
+    fn from(row: StorageL2BlockArchiveChunk) -> Self {
+        Self {
+            index: row.chunk_index as u32,
+            first_l2_block: L2BlockNumber(row.first_l2_block as u32),
+            last_l2_block: L2BlockNumber(row.last_l2_block as u32),
+            last_l2_block_hash: H256::from_slice(&row.last_l2_block_hash),
+            ipfs_cid: row.ipfs_cid,
+        }
+    }
+}
+
+/// DAL for the index of L2 block chunks archived by the main node.
+#[derive(Debug)]
+pub struct BlockArchiveDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BlockArchiveDal<'_, '_> {
+    pub async fn insert_chunk(&mut self, chunk: &en::L2BlockArchiveChunkInfo) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l2_block_archive_chunks (
+                    chunk_index,
+                    first_l2_block,
+                    last_l2_block,
+                    last_l2_block_hash,
+                    ipfs_cid,
+                    created_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, NOW())
+            "#,
+            i64::from(chunk.index),
+            i64::from(chunk.first_l2_block.0),
+            i64::from(chunk.last_l2_block.0),
+            chunk.last_l2_block_hash.as_bytes(),
+            chunk.ipfs_cid.as_deref()
+        )
+        .instrument("insert_l2_block_archive_chunk")
+        .with_arg("chunk", chunk)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Inserts a chunk or replaces the existing chunk with the same index (e.g., when an external node records
+    /// chunks resolved from other nodes, and a previously recorded chunk was rolled back).
+    pub async fn upsert_chunk(&mut self, chunk: &en::L2BlockArchiveChunkInfo) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l2_block_archive_chunks (
+                    chunk_index,
+                    first_l2_block,
+                    last_l2_block,
+                    last_l2_block_hash,
+                    ipfs_cid,
+                    created_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (chunk_index) DO
+            UPDATE
+            SET
+                first_l2_block = excluded.first_l2_block,
+                last_l2_block = excluded.last_l2_block,
+                last_l2_block_hash = excluded.last_l2_block_hash,
+                ipfs_cid = excluded.ipfs_cid,
+                created_at = excluded.created_at
+            "#,
+            i64::from(chunk.index),
+            i64::from(chunk.first_l2_block.0),
+            i64::from(chunk.last_l2_block.0),
+            chunk.last_l2_block_hash.as_bytes(),
+            chunk.ipfs_cid.as_deref()
+        )
+        .instrument("upsert_l2_block_archive_chunk")
+        .with_arg("chunk", chunk)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_chunk(
+        &mut self,
+        index: u32,
+    ) -> DalResult<Option<en::L2BlockArchiveChunkInfo>> {
+        let row = sqlx::query_as!(
+            StorageL2BlockArchiveChunk,
+            r#"
+            SELECT
+                chunk_index,
+                first_l2_block,
+                last_l2_block,
+                last_l2_block_hash,
+                ipfs_cid
+            FROM
+                l2_block_archive_chunks
+            WHERE
+                chunk_index = $1
+            "#,
+            i64::from(index)
+        )
+        .instrument("get_l2_block_archive_chunk")
+        .with_arg("index", &index)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    pub async fn get_last_chunk(&mut self) -> DalResult<Option<en::L2BlockArchiveChunkInfo>> {
+        let row = sqlx::query_as!(
+            StorageL2BlockArchiveChunk,
+            r#"
+            SELECT
+                chunk_index,
+                first_l2_block,
+                last_l2_block,
+                last_l2_block_hash,
+                ipfs_cid
+            FROM
+                l2_block_archive_chunks
+            ORDER BY
+                chunk_index DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_last_l2_block_archive_chunk")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    /// Deletes chunks starting from the specified index (e.g., after the L2 blocks in them were rolled back).
+    /// Returns the number of deleted chunks.
+    pub async fn delete_chunks_starting_from(&mut self, index: u32) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM l2_block_archive_chunks
+            WHERE
+                chunk_index >= $1
+            "#,
+            i64::from(index)
+        )
+        .instrument("delete_l2_block_archive_chunks")
+        .with_arg("index", &index)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn mock_chunk(index: u32) -> en::L2BlockArchiveChunkInfo {
+        en::L2BlockArchiveChunkInfo {
+            index,
+            first_l2_block: L2BlockNumber(index * 10),
+            last_l2_block: L2BlockNumber(index * 10 + 9),
+            last_l2_block_hash: H256::repeat_byte(index as u8),
+            ipfs_cid: (index % 2 == 0).then(|| format!("cid{index}")),
+        }
+    }
+
+    #[tokio::test]
+    async fn manipulating_archive_chunks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.block_archive_dal();
+        assert_eq!(dal.get_last_chunk().await.unwrap(), None);
+
+        for index in 0..3 {
+            dal.insert_chunk(&mock_chunk(index)).await.unwrap();
+        }
+        assert_eq!(dal.get_last_chunk().await.unwrap(), Some(mock_chunk(2)));
+        assert_eq!(dal.get_chunk(1).await.unwrap(), Some(mock_chunk(1)));
+        assert_eq!(dal.get_chunk(3).await.unwrap(), None);
+
+        let mut replaced_chunk = mock_chunk(1);
+        replaced_chunk.last_l2_block_hash = H256::zero();
+        dal.upsert_chunk(&replaced_chunk).await.unwrap();
+        assert_eq!(dal.get_chunk(1).await.unwrap(), Some(replaced_chunk));
+        dal.upsert_chunk(&mock_chunk(1)).await.unwrap();
+        dal.upsert_chunk(&mock_chunk(3)).await.unwrap();
+        assert_eq!(dal.get_last_chunk().await.unwrap(), Some(mock_chunk(3)));
+        dal.delete_chunks_starting_from(3).await.unwrap();
+
+        let deleted_count = dal.delete_chunks_starting_from(1).await.unwrap();
+        assert_eq!(deleted_count, 2);
+        assert_eq!(dal.get_last_chunk().await.unwrap(), Some(mock_chunk(0)));
+        assert_eq!(dal.get_chunk(1).await.unwrap(), None);
+    }
+}
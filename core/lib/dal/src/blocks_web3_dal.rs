@@ -307,6 +307,36 @@ impl BlocksWeb3Dal<'_, '_> {
                     ) AS number
                     ";
                 ),
+                // The last L1 batch in the contiguous run of TEE proofs starting from the first
+                // tracked batch. A skipped or missing proof ends the run.
+                api::BlockId::Number(api::BlockNumber::TeeFinalized) => (
+                    "
+                    SELECT COALESCE(
+                        (
+                            SELECT MAX(number) FROM miniblocks
+                            WHERE l1_batch_number = (
+                                SELECT MIN(proofs.l1_batch_number)
+                                FROM tee_proof_generation_details AS proofs
+                                WHERE
+                                    proofs.status = 'generated'
+                                    AND NOT EXISTS (
+                                        SELECT 1 FROM tee_proof_generation_details AS next_proofs
+                                        WHERE
+                                            next_proofs.l1_batch_number = proofs.l1_batch_number + 1
+                                            AND next_proofs.status = 'generated'
+                                    )
+                                    AND NOT EXISTS (
+                                        SELECT 1 FROM tee_proof_generation_details AS prev_proofs
+                                        WHERE
+                                            prev_proofs.l1_batch_number < proofs.l1_batch_number
+                                            AND prev_proofs.status <> 'generated'
+                                    )
+                            )
+                        ),
+                        0
+                    ) AS number
+                    ";
+                ),
             }
         );
 
@@ -736,6 +766,7 @@ mod tests {
         aggregated_operations::AggregatedActionType,
         block::{L2BlockHasher, L2BlockHeader},
         fee::TransactionExecutionMetrics,
+        tee_types::TeeType,
         Address, L2BlockNumber, ProtocolVersion, ProtocolVersionId,
     };
 
@@ -976,6 +1007,101 @@ mod tests {
         assert_eq!(resolved_l2_block_number, Some(l2_block_header.number));
     }
 
+    #[tokio::test]
+    async fn resolving_tee_finalized_block_id() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in 0..=5 {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+            let l1_batch_header = create_l1_batch_header(number);
+            conn.blocks_dal()
+                .insert_mock_l1_batch(&l1_batch_header)
+                .await
+                .unwrap();
+            conn.blocks_dal()
+                .mark_l2_blocks_as_executed_in_l1_batch(l1_batch_header.number)
+                .await
+                .unwrap();
+        }
+        // TEE proofs are not tracked for batch #4.
+        for number in [1, 2, 3, 5] {
+            let l1_batch_number = L1BatchNumber(number);
+            conn.tee_verifier_input_producer_dal()
+                .create_tee_verifier_input_producer_job(l1_batch_number)
+                .await
+                .unwrap();
+            conn.tee_proof_generation_dal()
+                .insert_tee_proof_generation_job(l1_batch_number)
+                .await
+                .unwrap();
+        }
+
+        let tee_finalized = api::BlockId::Number(api::BlockNumber::TeeFinalized);
+        let resolved_l2_block_number = conn
+            .blocks_web3_dal()
+            .resolve_block_id(tee_finalized)
+            .await
+            .unwrap();
+        assert_eq!(resolved_l2_block_number, Some(L2BlockNumber(0)));
+
+        let pubkey = [1; 33];
+        conn.tee_proof_generation_dal()
+            .save_attestation(&pubkey, b"attestation")
+            .await
+            .unwrap();
+        for number in [1, 3, 5] {
+            conn.tee_proof_generation_dal()
+                .save_proof_artifacts_metadata(
+                    L1BatchNumber(number),
+                    b"signature",
+                    &pubkey,
+                    b"proof",
+                    TeeType::Sgx,
+                )
+                .await
+                .unwrap();
+        }
+        sqlx::query(
+            "UPDATE tee_proof_generation_details SET status = 'skipped' WHERE l1_batch_number = 2",
+        )
+        .execute(conn.conn())
+        .await
+        .unwrap();
+
+        // The skipped batch #2 is not proven, so it ends the run of proven batches.
+        let resolved_l2_block_number = conn
+            .blocks_web3_dal()
+            .resolve_block_id(tee_finalized)
+            .await
+            .unwrap();
+        assert_eq!(resolved_l2_block_number, Some(L2BlockNumber(1)));
+
+        conn.tee_proof_generation_dal()
+            .save_proof_artifacts_metadata(
+                L1BatchNumber(2),
+                b"signature",
+                &pubkey,
+                b"proof",
+                TeeType::Sgx,
+            )
+            .await
+            .unwrap();
+        // Batch #4 has no TEE proof, so the run of proven batches ends at batch #3.
+        let resolved_l2_block_number = conn
+            .blocks_web3_dal()
+            .resolve_block_id(tee_finalized)
+            .await
+            .unwrap();
+        assert_eq!(resolved_l2_block_number, Some(L2BlockNumber(3)));
+    }
+
     #[tokio::test]
     async fn resolving_block_by_hash() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
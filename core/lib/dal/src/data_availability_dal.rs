@@ -3,17 +3,17 @@ use zksync_db_connection::{
     error::DalResult,
     instrument::{InstrumentExt, Instrumented},
 };
-use zksync_types::{pubdata_da::DataAvailabilityBlob, L1BatchNumber};
+use zksync_types::{api::en, pubdata_da::DataAvailabilityBlob, L1BatchNumber};
 
+pub use crate::models::storage_data_availability::{
+    OperationStatus, OperationType, PendingIpfsOperation, PendingMintlayerBatch,
+    PendingMintlayerRevocation, RolledBackAnchoring, RolledBackIpfsOperation,
+};
 use crate::{
     models::storage_data_availability::{L1BatchDA, StorageDABlob},
     Core,
 };
 
-pub use crate::models::storage_data_availability::{
-    OperationStatus, OperationType, PendingIpfsOperation, PendingMintlayerBatch,
-};
-
 const MAX_RETRY_ATTEMPTS: i32 = 10;
 const MAX_BATCH_SIZE: i64 = 100;
 
@@ -229,7 +229,7 @@ impl DataAvailabilityDal<'_, '_> {
                 id, operation_type, data, attempts, 
                 last_attempt as "last_attempt", created_at,
                 status::text as "status!", ipfs_hash,
-                requires_mintlayer
+                requires_mintlayer, l1_batch_number_from, l1_batch_number_to
             FROM pending_ipfs_operations
             WHERE status::text = 'pending'
             OR (status::text = 'failed' AND attempts < $1)
@@ -260,10 +260,15 @@ impl DataAvailabilityDal<'_, '_> {
                         "in_progress" => OperationStatus::InProgress,
                         "completed" => OperationStatus::Completed,
                         "failed" => OperationStatus::Failed("".to_string()),
+                        "superseded" => OperationStatus::Superseded,
                         _ => panic!("Invalid operation_type"),
                     },
                     ipfs_hash: row.ipfs_hash,
                     requires_mintlayer: row.requires_mintlayer,
+                    l1_batch_range: row
+                        .l1_batch_number_from
+                        .zip(row.l1_batch_number_to)
+                        .map(|(from, to)| L1BatchNumber(from as u32)..=L1BatchNumber(to as u32)),
                 })
             })
             .collect()
@@ -303,6 +308,7 @@ impl DataAvailabilityDal<'_, '_> {
                         "in_progress" => OperationStatus::InProgress,
                         "completed" => OperationStatus::Completed,
                         "failed" => OperationStatus::Failed("".to_string()),
+                        "superseded" => OperationStatus::Superseded,
                         _ => panic!("Invalid operation_status"),
                     },
                     tx_hash: row.tx_hash,
@@ -338,11 +344,14 @@ impl DataAvailabilityDal<'_, '_> {
         sqlx::query!(
             r#"
             INSERT INTO pending_mintlayer_batches (
-                id, ipfs_hashes, status, attempts, last_attempt, created_at
-            ) VALUES ($1, $2, $3::text::operation_status, $4, $5, $6)
+                id, ipfs_hashes, status, attempts, last_attempt, created_at, tx_hash
+            ) VALUES ($1, $2, $3::text::operation_status, $4, $5, $6, $7)
             ON CONFLICT (id) DO UPDATE SET 
                 ipfs_hashes = $2,
-                status = $3::text::operation_status
+                status = $3::text::operation_status,
+                attempts = $4,
+                last_attempt = $5,
+                tx_hash = $7
             "#,
             batch.id,
             &batch.ipfs_hashes,
@@ -350,6 +359,7 @@ impl DataAvailabilityDal<'_, '_> {
             batch.attempts as i32,
             batch.last_attempt,
             batch.created_at,
+            batch.tx_hash,
         )
         .instrument("update_mintlayer_batch")
         .with_arg("id", &batch.id)
@@ -358,6 +368,314 @@ impl DataAvailabilityDal<'_, '_> {
         .with_arg("attempts", &batch.attempts)
         .with_arg("last_attempt", &batch.last_attempt)
         .with_arg("created_at", &batch.created_at)
+        .with_arg("tx_hash", &batch.tx_hash)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the last L1 batch which was committed in an operation anchored on Mintlayer, i.e. the operation
+    /// was uploaded to IPFS and the Mintlayer batch referencing its IPFS hash was completed.
+    pub async fn get_last_mintlayer_anchored_l1_batch(self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(op.l1_batch_number_to) AS "last_anchored_l1_batch"
+            FROM
+                pending_ipfs_operations op
+                JOIN pending_mintlayer_batches ml ON op.ipfs_hash = ANY (ml.ipfs_hashes)
+            WHERE
+                op.operation_type = 'commit'
+                AND op.status::TEXT <> 'superseded'
+                AND ml.status::text = 'completed'
+            "#
+        )
+        .instrument("get_last_mintlayer_anchored_l1_batch")
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row
+            .last_anchored_l1_batch
+            .map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns the Mintlayer anchor for the commit operation including the specified L1 batch, or `None`
+    /// if the operation is not anchored yet.
+    pub async fn get_l1_batch_anchor(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<en::L1BatchAnchor>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                op.ipfs_hash AS "ipfs_hash!",
+                ml.tx_hash AS "tx_hash!"
+            FROM
+                pending_ipfs_operations op
+                JOIN pending_mintlayer_batches ml ON op.ipfs_hash = ANY (ml.ipfs_hashes)
+            WHERE
+                op.operation_type = 'commit'
+                AND op.status::TEXT <> 'superseded'
+                AND op.l1_batch_number_from <= $1
+                AND op.l1_batch_number_to >= $1
+                AND ml.status::TEXT = 'completed'
+                AND ml.tx_hash IS NOT NULL
+            ORDER BY
+                op.created_at DESC
+            LIMIT
+                1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l1_batch_anchor")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| en::L1BatchAnchor {
+            ipfs_hash: row.ipfs_hash,
+            mintlayer_tx_id: row.tx_hash,
+        }))
+    }
+
+    /// Returns anchoring records covering L1 batches after the specified one that are not superseded yet.
+    pub async fn get_anchoring_records_after(
+        &mut self,
+        last_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<RolledBackAnchoring> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                op.id,
+                op.operation_type,
+                op.ipfs_hash,
+                op.l1_batch_number_from AS "l1_batch_number_from!",
+                op.l1_batch_number_to AS "l1_batch_number_to!",
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        pending_mintlayer_batches ml
+                    WHERE
+                        op.ipfs_hash = ANY (ml.ipfs_hashes)
+                        AND ml.status::TEXT = 'completed'
+                ) AS "anchored_on_mintlayer!"
+            FROM
+                pending_ipfs_operations op
+            WHERE
+                op.l1_batch_number_to > $1
+                AND op.status::TEXT <> 'superseded'
+            ORDER BY
+                op.l1_batch_number_from,
+                op.created_at
+            "#,
+            i64::from(last_retained_l1_batch_number.0)
+        )
+        .instrument("get_anchoring_records_after#ipfs_operations")
+        .with_arg(
+            "last_retained_l1_batch_number",
+            &last_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await?;
+
+        let ipfs_operations: Vec<_> = rows
+            .into_iter()
+            .map(|row| RolledBackIpfsOperation {
+                id: row.id,
+                operation_type: OperationType::from_str(&row.operation_type)
+                    .expect("Invalid operation_type"),
+                l1_batch_range: L1BatchNumber(row.l1_batch_number_from as u32)
+                    ..=L1BatchNumber(row.l1_batch_number_to as u32),
+                ipfs_hash: row.ipfs_hash,
+                anchored_on_mintlayer: row.anchored_on_mintlayer,
+            })
+            .collect();
+        let ipfs_hashes: Vec<_> = ipfs_operations
+            .iter()
+            .filter_map(|op| op.ipfs_hash.clone())
+            .collect();
+
+        let pending_mintlayer_batches = sqlx::query!(
+            r#"
+            SELECT
+                id
+            FROM
+                pending_mintlayer_batches
+            WHERE
+                ipfs_hashes && $1::TEXT[]
+                AND status::TEXT NOT IN ('completed', 'superseded')
+            ORDER BY
+                created_at
+            "#,
+            &ipfs_hashes
+        )
+        .instrument("get_anchoring_records_after#mintlayer_batches")
+        .with_arg(
+            "last_retained_l1_batch_number",
+            &last_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| row.id)
+        .collect();
+
+        Ok(RolledBackAnchoring {
+            ipfs_operations,
+            pending_mintlayer_batches,
+        })
+    }
+
+    /// Marks the specified anchoring records as superseded. Hashes of superseded IPFS operations are removed
+    /// from pending Mintlayer batches; batches left without hashes are superseded as well.
+    ///
+    /// Should be called in a DB transaction together with rolling back the corresponding L1 batches.
+    pub async fn supersede_anchoring_records(
+        &mut self,
+        records: &RolledBackAnchoring,
+    ) -> DalResult<()> {
+        let operation_ids: Vec<_> = records.ipfs_operations.iter().map(|op| op.id).collect();
+        let ipfs_hashes: Vec<_> = records
+            .ipfs_operations
+            .iter()
+            .filter_map(|op| op.ipfs_hash.clone())
+            .collect();
+
+        sqlx::query!(
+            r#"
+            UPDATE pending_ipfs_operations
+            SET
+                status = 'superseded'
+            WHERE
+                id = ANY ($1::UUID[])
+            "#,
+            &operation_ids
+        )
+        .instrument("supersede_anchoring_records#ipfs_operations")
+        .with_arg("operation_ids.len", &operation_ids.len())
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE pending_mintlayer_batches
+            SET
+                ipfs_hashes = ARRAY(
+                    SELECT
+                        hash
+                    FROM
+                        UNNEST(ipfs_hashes) AS hash
+                    WHERE
+                        NOT (hash = ANY ($2::TEXT[]))
+                )
+            WHERE
+                id = ANY ($1::UUID[])
+            "#,
+            &records.pending_mintlayer_batches,
+            &ipfs_hashes
+        )
+        .instrument("supersede_anchoring_records#remove_ipfs_hashes")
+        .with_arg(
+            "pending_mintlayer_batches",
+            &records.pending_mintlayer_batches,
+        )
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE pending_mintlayer_batches
+            SET
+                status = 'superseded'
+            WHERE
+                id = ANY ($1::UUID[])
+                AND CARDINALITY(ipfs_hashes) = 0
+            "#,
+            &records.pending_mintlayer_batches
+        )
+        .instrument("supersede_anchoring_records#mintlayer_batches")
+        .with_arg(
+            "pending_mintlayer_batches",
+            &records.pending_mintlayer_batches,
+        )
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records a compensating Mintlayer anchor to be sent. Should be called in the same DB transaction
+    /// as superseding the revoked records, so that the revocation isn't lost if sending it fails.
+    pub async fn insert_pending_mintlayer_revocation(
+        &mut self,
+        payload: &serde_json::Value,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            pending_mintlayer_revocations (payload, created_at)
+            VALUES
+            ($1, NOW())
+            "#,
+            payload
+        )
+        .instrument("insert_pending_mintlayer_revocation")
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns compensating Mintlayer anchors that were not sent yet, in the order they were recorded.
+    pub async fn get_pending_mintlayer_revocations(
+        &mut self,
+    ) -> DalResult<Vec<PendingMintlayerRevocation>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                payload
+            FROM
+                pending_mintlayer_revocations
+            WHERE
+                mintlayer_tx_id IS NULL
+            ORDER BY
+                id
+            "#
+        )
+        .instrument("get_pending_mintlayer_revocations")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PendingMintlayerRevocation {
+                id: row.id,
+                payload: row.payload,
+            })
+            .collect())
+    }
+
+    /// Marks a compensating Mintlayer anchor as sent in the specified Mintlayer transaction.
+    pub async fn mark_mintlayer_revocation_sent(
+        &mut self,
+        id: i64,
+        mintlayer_tx_id: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE pending_mintlayer_revocations
+            SET
+                mintlayer_tx_id = $2,
+                sent_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            id,
+            mintlayer_tx_id
+        )
+        .instrument("mark_mintlayer_revocation_sent")
+        .with_arg("id", &id)
+        .with_arg("mintlayer_tx_id", &mintlayer_tx_id)
         .execute(self.storage)
         .await?;
         Ok(())
@@ -406,8 +724,8 @@ impl DataAvailabilityDal<'_, '_> {
             INSERT INTO pending_ipfs_operations (
                 id, operation_type, data, attempts,
                 last_attempt, created_at, status, ipfs_hash,
-                requires_mintlayer
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7::text::operation_status, $8, $9) 
+                requires_mintlayer, l1_batch_number_from, l1_batch_number_to
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7::text::operation_status, $8, $9, $10, $11) 
             "#,
             op.id,
             op.operation_type.to_string(),
@@ -417,7 +735,13 @@ impl DataAvailabilityDal<'_, '_> {
             op.created_at,
             op.status.to_string(),
             op.ipfs_hash,
-            op.requires_mintlayer
+            op.requires_mintlayer,
+            op.l1_batch_range
+                .as_ref()
+                .map(|range| i64::from(range.start().0)),
+            op.l1_batch_range
+                .as_ref()
+                .map(|range| i64::from(range.end().0)),
         )
         .instrument("save_pending_operation")
         .with_arg("id", &op.id)
@@ -429,6 +753,7 @@ impl DataAvailabilityDal<'_, '_> {
         .with_arg("status", &op.status)
         .with_arg("ipfs hash", &op.ipfs_hash)
         .with_arg("requires mintlayer", &op.requires_mintlayer)
+        .with_arg("l1 batch range", &op.l1_batch_range)
         .execute(self.storage)
         .await?;
         Ok(())
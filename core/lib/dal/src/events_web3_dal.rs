@@ -82,7 +82,7 @@ impl EventsWeb3Dal<'_, '_> {
             SELECT miniblocks.hash as "block_hash", miniblocks.l1_batch_number as "l1_batch_number",
                 miniblocks.timestamp as block_timestamp, events_select.*
             FROM events_select
-            INNER JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
+            LEFT JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
             ORDER BY miniblock_number ASC, event_index_in_block ASC
             "#,
             where_sql, arg_index
@@ -227,7 +227,7 @@ impl EventsWeb3Dal<'_, '_> {
                 miniblocks.timestamp AS "block_timestamp"
             FROM
                 events_select
-                INNER JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
+                LEFT JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
             ORDER BY
                 miniblock_number ASC,
                 event_index_in_block ASC
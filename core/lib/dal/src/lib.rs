@@ -12,10 +12,11 @@ pub use zksync_db_connection::{
 };
 
 use crate::{
-    base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
-    consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
-    data_availability_dal::DataAvailabilityDal, eth_sender_dal::EthSenderDal,
-    events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
+    base_token_dal::BaseTokenDal, block_archive_dal::BlockArchiveDal, blocks_dal::BlocksDal,
+    blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
+    contract_verification_dal::ContractVerificationDal, data_availability_dal::DataAvailabilityDal,
+    eth_sender_dal::EthSenderDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
+    factory_deps_dal::FactoryDepsDal, private_txs_dal::PrivateTxsDal,
     proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
     snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
@@ -24,10 +25,12 @@ use crate::{
     sync_dal::SyncDal, system_dal::SystemDal, tee_proof_generation_dal::TeeProofGenerationDal,
     tee_verifier_input_producer_dal::TeeVerifierInputProducerDal, tokens_dal::TokensDal,
     tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
+    transactions_web3_dal::TransactionsWeb3Dal, tx_allowlist_dal::TxAllowlistDal,
+    verified_l1_batches_dal::VerifiedL1BatchesDal, vm_runner_dal::VmRunnerDal,
 };
 
 pub mod base_token_dal;
+pub mod block_archive_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
 pub mod consensus;
@@ -41,6 +44,7 @@ pub mod factory_deps_dal;
 pub mod helpers;
 pub mod metrics;
 mod models;
+pub mod private_txs_dal;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
@@ -59,6 +63,8 @@ pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
 pub mod transactions_web3_dal;
+pub mod tx_allowlist_dal;
+pub mod verified_l1_batches_dal;
 pub mod vm_runner_dal;
 
 #[cfg(test)]
@@ -132,6 +138,14 @@ where
     fn vm_runner_dal(&mut self) -> VmRunnerDal<'_, 'a>;
 
     fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a>;
+
+    fn tx_allowlist_dal(&mut self) -> TxAllowlistDal<'_, 'a>;
+
+    fn private_txs_dal(&mut self) -> PrivateTxsDal<'_, 'a>;
+
+    fn verified_l1_batches_dal(&mut self) -> VerifiedL1BatchesDal<'_, 'a>;
+
+    fn block_archive_dal(&mut self) -> BlockArchiveDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -258,4 +272,20 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a> {
         BaseTokenDal { storage: self }
     }
+
+    fn tx_allowlist_dal(&mut self) -> TxAllowlistDal<'_, 'a> {
+        TxAllowlistDal { storage: self }
+    }
+
+    fn private_txs_dal(&mut self) -> PrivateTxsDal<'_, 'a> {
+        PrivateTxsDal { storage: self }
+    }
+
+    fn verified_l1_batches_dal(&mut self) -> VerifiedL1BatchesDal<'_, 'a> {
+        VerifiedL1BatchesDal { storage: self }
+    }
+
+    fn block_archive_dal(&mut self) -> BlockArchiveDal<'_, 'a> {
+        BlockArchiveDal { storage: self }
+    }
 }
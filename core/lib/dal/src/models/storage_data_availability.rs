@@ -1,7 +1,7 @@
-use chrono::NaiveDateTime;
-use chrono::{DateTime, Utc};
+use std::{fmt::Display, ops};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
 use uuid::Uuid;
 use zksync_types::{pubdata_da::DataAvailabilityBlob, L1BatchNumber};
 
@@ -11,6 +11,8 @@ pub enum OperationStatus {
     InProgress,
     Completed,
     Failed(String),
+    /// The operation concerns L1 batches that were rolled back, and must not be processed further.
+    Superseded,
 }
 
 impl Display for OperationStatus {
@@ -20,6 +22,7 @@ impl Display for OperationStatus {
             OperationStatus::InProgress => "in_progress",
             OperationStatus::Completed => "completed",
             OperationStatus::Failed(_) => "failed",
+            OperationStatus::Superseded => "superseded",
         };
 
         write!(f, "{}", status)
@@ -65,6 +68,9 @@ pub struct PendingIpfsOperation {
     pub status: OperationStatus,
     pub ipfs_hash: Option<String>,
     pub requires_mintlayer: bool,
+    /// Range of L1 batches covered by the aggregated operation. `None` for operations
+    /// persisted before the range was tracked.
+    pub l1_batch_range: Option<ops::RangeInclusive<L1BatchNumber>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +98,49 @@ impl PendingMintlayerBatch {
     }
 }
 
+/// IPFS operation covering L1 batches that are rolled back.
+#[derive(Debug, Clone, Serialize)]
+pub struct RolledBackIpfsOperation {
+    pub id: Uuid,
+    pub operation_type: OperationType,
+    pub l1_batch_range: ops::RangeInclusive<L1BatchNumber>,
+    pub ipfs_hash: Option<String>,
+    /// Whether the operation is already anchored by a completed Mintlayer batch.
+    pub anchored_on_mintlayer: bool,
+}
+
+/// Anchoring records referencing L1 batches that are rolled back.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RolledBackAnchoring {
+    pub ipfs_operations: Vec<RolledBackIpfsOperation>,
+    /// IDs of Mintlayer batches that are not anchored yet and reference rolled back operations.
+    pub pending_mintlayer_batches: Vec<Uuid>,
+}
+
+impl RolledBackAnchoring {
+    pub fn is_empty(&self) -> bool {
+        self.ipfs_operations.is_empty() && self.pending_mintlayer_batches.is_empty()
+    }
+
+    /// Returns IPFS hashes of rolled back operations already anchored on Mintlayer. Such anchors cannot be undone;
+    /// they can only be revoked by a compensating anchor.
+    pub fn anchored_ipfs_hashes(&self) -> Vec<String> {
+        self.ipfs_operations
+            .iter()
+            .filter(|op| op.anchored_on_mintlayer)
+            .filter_map(|op| op.ipfs_hash.clone())
+            .collect()
+    }
+}
+
+/// Compensating Mintlayer anchor recorded by the block reverter that wasn't sent yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingMintlayerRevocation {
+    pub id: i64,
+    /// JSON payload to be deposited on Mintlayer.
+    pub payload: serde_json::Value,
+}
+
 /// Represents a blob in the data availability layer.
 #[derive(Debug, Clone)]
 pub(crate) struct StorageDABlob {
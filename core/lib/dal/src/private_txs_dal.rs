@@ -0,0 +1,433 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{L2BlockNumber, Transaction, H256};
+
+use crate::{models::storage_transaction::StorageTransaction, Core};
+
+/// Pending bundle of transactions that must be included atomically and in order.
+#[derive(Debug, Clone)]
+pub struct TxBundle {
+    pub id: u64,
+    /// Last L2 block the bundle may be included in; afterwards, the bundle is dropped.
+    pub target_l2_block: L2BlockNumber,
+    pub transactions: Vec<Transaction>,
+}
+
+/// DAL for private transactions and transaction bundles. Transactions themselves are stored in the `transactions`
+/// table as usual; this DAL only manages their additional metadata.
+#[derive(Debug)]
+pub struct PrivateTxsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl PrivateTxsDal<'_, '_> {
+    /// Marks the transaction as private, so that it's not exposed via pending transaction filters and subscriptions.
+    pub async fn insert_private_tx(&mut self, tx_hash: H256) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                private_transactions (tx_hash, created_at)
+            VALUES
+                ($1, NOW())
+            ON CONFLICT (tx_hash) DO NOTHING
+            "#,
+            tx_hash.as_bytes()
+        )
+        .instrument("insert_private_tx")
+        .with_arg("tx_hash", &tx_hash)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Creates a bundle from the specified transactions, which must be already inserted into the `transactions` table.
+    /// Bundled transactions are private and are not picked up by the mempool. Returns the ID of the created bundle.
+    ///
+    /// This method should be called in the same DB transaction as the one inserting bundled transactions;
+    /// otherwise, bundled transactions may be picked up by the mempool as ordinary ones.
+    pub async fn insert_bundle(
+        &mut self,
+        tx_hashes: &[H256],
+        target_l2_block: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let bundle_id = sqlx::query!(
+            r#"
+            INSERT INTO
+                tx_bundles (target_l2_block, created_at)
+            VALUES
+                ($1, NOW())
+            RETURNING
+                id
+            "#,
+            i64::from(target_l2_block.0)
+        )
+        .instrument("insert_bundle")
+        .with_arg("target_l2_block", &target_l2_block)
+        .fetch_one(self.storage)
+        .await?
+        .id;
+
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                private_transactions (tx_hash, bundle_id, index_in_bundle, created_at)
+            SELECT
+                u.tx_hash,
+                $2,
+                (u.index_in_bundle - 1)::INT,
+                NOW()
+            FROM
+                UNNEST($1::bytea[]) WITH ORDINALITY AS u (tx_hash, index_in_bundle)
+            ON CONFLICT (tx_hash) DO
+            UPDATE
+            SET
+                bundle_id = excluded.bundle_id,
+                index_in_bundle = excluded.index_in_bundle
+            "#,
+            &tx_hashes as &[&[u8]],
+            bundle_id
+        )
+        .instrument("insert_bundle#insert_txs")
+        .with_arg("bundle_id", &bundle_id)
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .execute(self.storage)
+        .await?;
+
+        Ok(bundle_id as u64)
+    }
+
+    /// Returns bundles that can be included into the L2 block with the specified number, ordered by their IDs.
+    /// Bundles with any of the transactions already executed, rejected or missing are not returned.
+    pub async fn get_pending_bundles(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<TxBundle>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_bundles.id AS bundle_id,
+                tx_bundles.target_l2_block,
+                transactions.*
+            FROM
+                tx_bundles
+                INNER JOIN private_transactions ON private_transactions.bundle_id = tx_bundles.id
+                INNER JOIN transactions ON transactions.hash = private_transactions.tx_hash
+            WHERE
+                tx_bundles.target_l2_block >= $1
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        private_transactions AS bundled
+                        LEFT JOIN transactions AS bundled_txs ON bundled_txs.hash = bundled.tx_hash
+                    WHERE
+                        bundled.bundle_id = tx_bundles.id
+                        AND (
+                            bundled_txs.hash IS NULL
+                            OR bundled_txs.miniblock_number IS NOT NULL
+                            OR bundled_txs.error IS NOT NULL
+                        )
+                )
+            ORDER BY
+                tx_bundles.id,
+                private_transactions.index_in_bundle
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_pending_bundles")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut bundles = Vec::<TxBundle>::new();
+        for row in rows {
+            let tx = StorageTransaction {
+                priority_op_id: row.priority_op_id,
+                hash: row.hash,
+                is_priority: row.is_priority,
+                full_fee: row.full_fee,
+                layer_2_tip_fee: row.layer_2_tip_fee,
+                initiator_address: row.initiator_address,
+                nonce: row.nonce,
+                signature: row.signature,
+                gas_limit: row.gas_limit,
+                max_fee_per_gas: row.max_fee_per_gas,
+                max_priority_fee_per_gas: row.max_priority_fee_per_gas,
+                gas_per_storage_limit: row.gas_per_storage_limit,
+                gas_per_pubdata_limit: row.gas_per_pubdata_limit,
+                input: row.input,
+                tx_format: row.tx_format,
+                data: row.data,
+                received_at: row.received_at,
+                in_mempool: row.in_mempool,
+                l1_batch_number: row.l1_batch_number,
+                l1_batch_tx_index: row.l1_batch_tx_index,
+                miniblock_number: row.miniblock_number,
+                index_in_block: row.index_in_block,
+                error: row.error,
+                effective_gas_price: row.effective_gas_price,
+                contract_address: row.contract_address,
+                value: row.value,
+                paymaster: row.paymaster,
+                paymaster_input: row.paymaster_input,
+                refunded_gas: row.refunded_gas,
+                execution_info: row.execution_info,
+                l1_tx_mint: row.l1_tx_mint,
+                l1_tx_refund_recipient: row.l1_tx_refund_recipient,
+                upgrade_id: row.upgrade_id,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                l1_block_number: row.l1_block_number,
+            };
+
+            let bundle_id = row.bundle_id as u64;
+            match bundles.last_mut() {
+                Some(bundle) if bundle.id == bundle_id => bundle.transactions.push(tx.into()),
+                _ => bundles.push(TxBundle {
+                    id: bundle_id,
+                    target_l2_block: L2BlockNumber(row.target_l2_block as u32),
+                    transactions: vec![tx.into()],
+                }),
+            }
+        }
+        Ok(bundles)
+    }
+
+    /// Marks all pending transactions in the bundle as rejected with the specified error.
+    pub async fn reject_bundle(&mut self, bundle_id: u64, error: &str) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = $2,
+                updated_at = NOW()
+            FROM
+                private_transactions
+            WHERE
+                transactions.hash = private_transactions.tx_hash
+                AND private_transactions.bundle_id = $1
+                AND transactions.miniblock_number IS NULL
+                AND transactions.error IS NULL
+            "#,
+            bundle_id as i64,
+            error
+        )
+        .instrument("reject_bundle")
+        .with_arg("bundle_id", &bundle_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks pending transactions in bundles that cannot be included into the L2 block with the specified number
+    /// (and all following L2 blocks) as rejected with the specified error. Returns the number of affected transactions.
+    ///
+    /// Expired bundles without included transactions are removed. Their transactions stay private, but are no longer
+    /// bundled. Bundles with included transactions are kept so that they are re-executed atomically if the including
+    /// L2 block is reverted; they are removed once the including L2 block is pruned.
+    pub async fn drop_expired_bundles(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        error: &str,
+    ) -> DalResult<usize> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = $2,
+                updated_at = NOW()
+            FROM
+                private_transactions
+                INNER JOIN tx_bundles ON tx_bundles.id = private_transactions.bundle_id
+            WHERE
+                transactions.hash = private_transactions.tx_hash
+                AND tx_bundles.target_l2_block < $1
+                AND transactions.miniblock_number IS NULL
+                AND transactions.error IS NULL
+            "#,
+            i64::from(l2_block_number.0),
+            error
+        )
+        .instrument("drop_expired_bundles")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE private_transactions
+            SET
+                bundle_id = NULL,
+                index_in_bundle = NULL
+            FROM
+                tx_bundles
+            WHERE
+                private_transactions.bundle_id = tx_bundles.id
+                AND tx_bundles.target_l2_block < $1
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        private_transactions AS bundled
+                        INNER JOIN transactions ON transactions.hash = bundled.tx_hash
+                    WHERE
+                        bundled.bundle_id = tx_bundles.id
+                        AND transactions.miniblock_number IS NOT NULL
+                )
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("drop_expired_bundles#detach_txs")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM tx_bundles
+            WHERE
+                target_l2_block < $1
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        private_transactions
+                    WHERE
+                        private_transactions.bundle_id = tx_bundles.id
+                )
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("drop_expired_bundles#delete_bundles")
+        .with_arg("l2_block_number", &l2_block_number)
+        .execute(self.storage)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{fee::TransactionExecutionMetrics, l2::L2Tx};
+
+    use super::*;
+    use crate::{tests::mock_l2_transaction, ConnectionPool, CoreDal};
+
+    async fn insert_txs(storage: &mut Connection<'_, Core>, count: usize) -> Vec<L2Tx> {
+        let txs: Vec<_> = (0..count).map(|_| mock_l2_transaction()).collect();
+        for tx in &txs {
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+        txs
+    }
+
+    #[tokio::test]
+    async fn managing_tx_bundles() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        let txs = insert_txs(&mut storage, 3).await;
+        let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+
+        let bundle_id = storage
+            .private_txs_dal()
+            .insert_bundle(&tx_hashes[..2], L2BlockNumber(5))
+            .await
+            .unwrap();
+        let other_bundle_id = storage
+            .private_txs_dal()
+            .insert_bundle(&tx_hashes[2..], L2BlockNumber(10))
+            .await
+            .unwrap();
+        assert!(other_bundle_id > bundle_id);
+
+        let bundles = storage
+            .private_txs_dal()
+            .get_pending_bundles(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[0].id, bundle_id);
+        assert_eq!(bundles[0].target_l2_block, L2BlockNumber(5));
+        let bundled_hashes: Vec<_> = bundles[0]
+            .transactions
+            .iter()
+            .map(Transaction::hash)
+            .collect();
+        assert_eq!(bundled_hashes, tx_hashes[..2]);
+        assert_eq!(bundles[1].id, other_bundle_id);
+
+        let dropped_count = storage
+            .private_txs_dal()
+            .drop_expired_bundles(L2BlockNumber(6), "dropped")
+            .await
+            .unwrap();
+        assert_eq!(dropped_count, 2);
+        let bundles = storage
+            .private_txs_dal()
+            .get_pending_bundles(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].id, other_bundle_id);
+
+        storage
+            .private_txs_dal()
+            .reject_bundle(other_bundle_id, "rejected")
+            .await
+            .unwrap();
+        let bundles = storage
+            .private_txs_dal()
+            .get_pending_bundles(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert!(bundles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn private_txs_are_hidden_from_pending_txs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        let txs = insert_txs(&mut storage, 3).await;
+        storage
+            .private_txs_dal()
+            .insert_private_tx(txs[0].hash())
+            .await
+            .unwrap();
+        storage
+            .private_txs_dal()
+            .insert_bundle(&[txs[1].hash()], L2BlockNumber(5))
+            .await
+            .unwrap();
+
+        let pending_hashes = storage
+            .transactions_web3_dal()
+            .get_pending_txs_hashes_after(Default::default(), None)
+            .await
+            .unwrap();
+        let pending_hashes: Vec<_> = pending_hashes.into_iter().map(|(_, hash)| hash).collect();
+        assert_eq!(pending_hashes, [txs[2].hash()]);
+        let mempool_hashes = storage
+            .transactions_web3_dal()
+            .get_mempool_tx_hashes(None, 100)
+            .await
+            .unwrap();
+        assert_eq!(mempool_hashes, [txs[2].hash()]);
+
+        // Bundled transactions must not be picked up by the mempool.
+        let mempool_txs = storage
+            .transactions_dal()
+            .sync_mempool(&[], &[], 0, 0, 100)
+            .await
+            .unwrap();
+        let mut mempool_hashes: Vec<_> = mempool_txs.iter().map(Transaction::hash).collect();
+        mempool_hashes.sort_unstable();
+        let mut expected_hashes = vec![txs[0].hash(), txs[2].hash()];
+        expected_hashes.sort_unstable();
+        assert_eq!(mempool_hashes, expected_hashes);
+    }
+}
@@ -1,7 +1,10 @@
-use std::ops;
+use std::{
+    collections::{HashMap, HashSet},
+    ops,
+};
 
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber};
 
 use crate::Core;
 
@@ -33,6 +36,46 @@ pub struct HardPruningStats {
     pub deleted_l2_to_l1_logs: u64,
 }
 
+/// Class of data that can outlive the L2 blocks it belongs to during hard pruning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetainedDataClass {
+    Events,
+    L2ToL1Logs,
+    CallTraces,
+    /// Transaction input, data and execution info. Transactions themselves are never removed.
+    TransactionData,
+}
+
+impl RetainedDataClass {
+    pub const ALL: [Self; 4] = [
+        Self::Events,
+        Self::L2ToL1Logs,
+        Self::CallTraces,
+        Self::TransactionData,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Events => "events",
+            Self::L2ToL1Logs => "l2_to_l1_logs",
+            Self::CallTraces => "call_traces",
+            Self::TransactionData => "transaction_data",
+        }
+    }
+}
+
+/// Options for hard pruning.
+#[derive(Debug, Clone, Default)]
+pub struct HardPruningOptions {
+    /// Data classes that are not removed together with pruned L2 blocks. Such data can be removed later
+    /// using [`PruningDal::prune_retained_data()`].
+    pub retained_classes: HashSet<RetainedDataClass>,
+    /// Data related to these addresses is never pruned. This covers transactions initiated by or sent to
+    /// these addresses (together with their call traces), events emitted by these addresses or in transactions
+    /// initiated by them, and L2-to-L1 logs sent by these addresses.
+    pub retained_addresses: Vec<Address>,
+}
+
 #[derive(Debug, sqlx::Type)]
 #[sqlx(type_name = "prune_type")]
 enum PruneType {
@@ -139,6 +182,21 @@ impl PruningDal<'_, '_> {
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
+    ) -> DalResult<HardPruningStats> {
+        self.hard_prune_batches_range_with_options(
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+            &HardPruningOptions::default(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::hard_prune_batches_range()`], but allows retaining some of the pruned data.
+    pub async fn hard_prune_batches_range_with_options(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+        options: &HardPruningOptions,
     ) -> DalResult<HardPruningStats> {
         let row = sqlx::query!(
             r#"
@@ -157,57 +215,442 @@ impl PruningDal<'_, '_> {
         .fetch_one(self.storage)
         .await?;
 
+        // Must be queried before L2 blocks are deleted; used to determine retention cutoffs for retained data.
+        let last_l2_block_timestamp = self.get_l2_block_timestamp(last_l2_block_to_prune).await?;
+
         // We don't have any L2 blocks available when recovering from a snapshot
         let stats = if let Some(first_l2_block_to_prune) = row.first_miniblock_to_prune {
             let first_l2_block_to_prune = L2BlockNumber(first_l2_block_to_prune as u32);
-
-            let deleted_events = self
-                .delete_events(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            let deleted_l2_to_l1_logs = self
-                .delete_l2_to_l1_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            let deleted_call_traces = self
-                .delete_call_traces(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
-            self.clear_transaction_fields(first_l2_block_to_prune..=last_l2_block_to_prune)
-                .await?;
+            let mut pruned_data = HashMap::new();
+            for class in RetainedDataClass::ALL {
+                if !options.retained_classes.contains(&class) {
+                    let pruned_count = self
+                        .prune_retained_data(
+                            class,
+                            first_l2_block_to_prune..=last_l2_block_to_prune,
+                            &options.retained_addresses,
+                        )
+                        .await?;
+                    pruned_data.insert(class, pruned_count);
+                }
+            }
 
             let deleted_storage_logs = self
                 .prune_storage_logs(first_l2_block_to_prune..=last_l2_block_to_prune)
                 .await?;
+            self.delete_private_txs(first_l2_block_to_prune..=last_l2_block_to_prune)
+                .await?;
             let deleted_l1_batches = self.delete_l1_batches(last_l1_batch_to_prune).await?;
             let deleted_l2_blocks = self.delete_l2_blocks(last_l2_block_to_prune).await?;
 
+            let pruned_count = |class| pruned_data.get(&class).copied().unwrap_or(0);
             HardPruningStats {
                 deleted_l1_batches,
                 deleted_l2_blocks,
-                deleted_events,
-                deleted_l2_to_l1_logs,
-                deleted_call_traces,
+                deleted_events: pruned_count(RetainedDataClass::Events),
+                deleted_l2_to_l1_logs: pruned_count(RetainedDataClass::L2ToL1Logs),
+                deleted_call_traces: pruned_count(RetainedDataClass::CallTraces),
                 deleted_storage_logs,
             }
         } else {
             HardPruningStats::default()
         };
 
-        self.insert_hard_pruning_log(last_l1_batch_to_prune, last_l2_block_to_prune)
-            .await?;
+        self.insert_hard_pruning_log(
+            last_l1_batch_to_prune,
+            last_l2_block_to_prune,
+            last_l2_block_timestamp,
+        )
+        .await?;
         Ok(stats)
     }
 
+    /// Prunes data of the specified class in the specified range of L2 blocks, except for data related
+    /// to `retained_addresses`. Returns the number of affected rows. Unlike other pruning methods,
+    /// this one may be called for already hard-pruned L2 blocks; it is used to remove the data retained
+    /// by [`Self::hard_prune_batches_range_with_options()`] once its retention period expires.
+    pub async fn prune_retained_data(
+        &mut self,
+        class: RetainedDataClass,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
+    ) -> DalResult<u64> {
+        match class {
+            RetainedDataClass::Events => {
+                self.delete_events(l2_blocks_to_prune, retained_addresses)
+                    .await
+            }
+            RetainedDataClass::L2ToL1Logs => {
+                self.delete_l2_to_l1_logs(l2_blocks_to_prune, retained_addresses)
+                    .await
+            }
+            RetainedDataClass::CallTraces => {
+                self.delete_call_traces(l2_blocks_to_prune, retained_addresses)
+                    .await
+            }
+            RetainedDataClass::TransactionData => {
+                self.clear_transaction_fields(l2_blocks_to_prune, retained_addresses)
+                    .await
+            }
+        }
+    }
+
+    /// Counts data of the specified class that would be affected by [`Self::prune_retained_data()`] with the same args.
+    /// Doesn't modify the database.
+    pub async fn count_retained_data(
+        &mut self,
+        class: RetainedDataClass,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
+    ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
+        let start = i64::from(l2_blocks.start().0);
+        let end = i64::from(l2_blocks.end().0);
+        let count = match class {
+            RetainedDataClass::Events => {
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT
+                        COUNT(*) AS "count!"
+                    FROM
+                        events
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND address <> ALL ($3)
+                        AND tx_initiator_address <> ALL ($3)
+                    "#,
+                    start,
+                    end,
+                    &retained_addresses as &[&[u8]]
+                )
+                .instrument("count_retained_data#events")
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .fetch_one(self.storage)
+                .await?
+            }
+            RetainedDataClass::L2ToL1Logs => {
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT
+                        COUNT(*) AS "count!"
+                    FROM
+                        l2_to_l1_logs
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND sender <> ALL ($3)
+                    "#,
+                    start,
+                    end,
+                    &retained_addresses as &[&[u8]]
+                )
+                .instrument("count_retained_data#l2_to_l1_logs")
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .fetch_one(self.storage)
+                .await?
+            }
+            RetainedDataClass::CallTraces => {
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT
+                        COUNT(*) AS "count!"
+                    FROM
+                        call_traces
+                    WHERE
+                        tx_hash IN (
+                            SELECT
+                                hash
+                            FROM
+                                transactions
+                            WHERE
+                                miniblock_number BETWEEN $1 AND $2
+                                AND initiator_address <> ALL ($3)
+                                AND (
+                                    contract_address IS NULL
+                                    OR contract_address <> ALL ($3)
+                                )
+                        )
+                    "#,
+                    start,
+                    end,
+                    &retained_addresses as &[&[u8]]
+                )
+                .instrument("count_retained_data#call_traces")
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .fetch_one(self.storage)
+                .await?
+            }
+            RetainedDataClass::TransactionData => {
+                sqlx::query_scalar!(
+                    r#"
+                    SELECT
+                        COUNT(*) AS "count!"
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                        AND upgrade_id IS NULL
+                        AND initiator_address <> ALL ($3)
+                        AND (
+                            contract_address IS NULL
+                            OR contract_address <> ALL ($3)
+                        )
+                    "#,
+                    start,
+                    end,
+                    &retained_addresses as &[&[u8]]
+                )
+                .instrument("count_retained_data#transaction_data")
+                .with_arg("l2_blocks", &l2_blocks)
+                .report_latency()
+                .fetch_one(self.storage)
+                .await?
+            }
+        };
+        Ok(count as u64)
+    }
+
+    /// Estimates the outcome of [`Self::hard_prune_batches_range_with_options()`] with the same args using `COUNT` queries.
+    /// Unlike the pruning method, doesn't modify the database.
+    pub async fn estimate_hard_pruning(
+        &mut self,
+        last_l1_batch_to_prune: L1BatchNumber,
+        last_l2_block_to_prune: L2BlockNumber,
+        options: &HardPruningOptions,
+    ) -> DalResult<HardPruningStats> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT
+                        MIN(number)
+                    FROM
+                        miniblocks
+                    WHERE
+                        l1_batch_number <= $1
+                ) AS first_miniblock_to_prune,
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        l1_batches
+                    WHERE
+                        number <= $1
+                ) AS "l1_batch_count!",
+                (
+                    SELECT
+                        COUNT(*)
+                    FROM
+                        miniblocks
+                    WHERE
+                        number <= $2
+                ) AS "l2_block_count!"
+            "#,
+            i64::from(last_l1_batch_to_prune.0),
+            i64::from(last_l2_block_to_prune.0)
+        )
+        .instrument("estimate_hard_pruning")
+        .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
+        .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+
+        let Some(first_l2_block_to_prune) = row.first_miniblock_to_prune else {
+            return Ok(HardPruningStats::default());
+        };
+        let l2_blocks_to_prune =
+            L2BlockNumber(first_l2_block_to_prune as u32)..=last_l2_block_to_prune;
+        let mut pruned_data = HashMap::new();
+        for class in RetainedDataClass::ALL {
+            if !options.retained_classes.contains(&class) {
+                let count = self
+                    .count_retained_data(
+                        class,
+                        l2_blocks_to_prune.clone(),
+                        &options.retained_addresses,
+                    )
+                    .await?;
+                pruned_data.insert(class, count);
+            }
+        }
+        let deleted_storage_logs = self.count_prunable_storage_logs(l2_blocks_to_prune).await?;
+
+        let pruned_count = |class| pruned_data.get(&class).copied().unwrap_or(0);
+        Ok(HardPruningStats {
+            deleted_l1_batches: row.l1_batch_count as u64,
+            deleted_l2_blocks: row.l2_block_count as u64,
+            deleted_events: pruned_count(RetainedDataClass::Events),
+            deleted_l2_to_l1_logs: pruned_count(RetainedDataClass::L2ToL1Logs),
+            deleted_call_traces: pruned_count(RetainedDataClass::CallTraces),
+            deleted_storage_logs,
+        })
+    }
+
+    /// Counts storage logs that would be removed by [`Self::prune_storage_logs()`].
+    async fn count_prunable_storage_logs(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<u64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            WITH
+                new_logs AS MATERIALIZED (
+                    SELECT DISTINCT
+                        ON (hashed_key) hashed_key,
+                        miniblock_number,
+                        operation_number
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                    ORDER BY
+                        hashed_key,
+                        miniblock_number DESC,
+                        operation_number DESC
+                )
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                storage_logs
+                INNER JOIN new_logs ON storage_logs.hashed_key = new_logs.hashed_key
+            WHERE
+                storage_logs.miniblock_number <= $2
+                AND (storage_logs.miniblock_number, storage_logs.operation_number) < (new_logs.miniblock_number, new_logs.operation_number)
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("estimate_hard_pruning#count_prunable_storage_logs")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(count as u64)
+    }
+
+    /// Returns the first L2 block that may contain retained data of the specified class not yet removed
+    /// by [`Self::prune_retained_data()`] after its retention period has expired.
+    pub async fn get_next_l2_block_with_retained_data(
+        &mut self,
+        class: RetainedDataClass,
+    ) -> DalResult<L2BlockNumber> {
+        let next_l2_block = sqlx::query_scalar!(
+            r#"
+            SELECT
+                next_miniblock
+            FROM
+                pruning_retained_data
+            WHERE
+                data_class = $1
+            "#,
+            class.as_str()
+        )
+        .instrument("get_next_l2_block_with_retained_data")
+        .with_arg("class", &class)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(next_l2_block.map_or(L2BlockNumber(0), |number| L2BlockNumber(number as u32)))
+    }
+
+    /// Persists the first L2 block that may contain not yet removed retained data of the specified class.
+    pub async fn set_next_l2_block_with_retained_data(
+        &mut self,
+        class: RetainedDataClass,
+        next_l2_block: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                pruning_retained_data (data_class, next_miniblock, updated_at)
+            VALUES
+                ($1, $2, NOW())
+            ON CONFLICT (data_class) DO
+            UPDATE
+            SET
+                next_miniblock = $2,
+                updated_at = NOW()
+            "#,
+            class.as_str(),
+            i64::from(next_l2_block.0)
+        )
+        .instrument("set_next_l2_block_with_retained_data")
+        .with_arg("class", &class)
+        .with_arg("next_l2_block", &next_l2_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the last hard-pruned L2 block with the timestamp not exceeding `max_timestamp`. Only considers L2 blocks
+    /// for which the timestamp was recorded during pruning.
+    pub async fn get_last_hard_pruned_l2_block_before(
+        &mut self,
+        max_timestamp: u64,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(pruned_miniblock) AS "pruned_miniblock"
+            FROM
+                pruning_log
+            WHERE
+            TYPE = 'Hard'
+            AND pruned_miniblock_timestamp <= $1
+            "#,
+            max_timestamp as i64
+        )
+        .instrument("get_last_hard_pruned_l2_block_before")
+        .with_arg("max_timestamp", &max_timestamp)
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row
+            .pruned_miniblock
+            .map(|number| L2BlockNumber(number as u32)))
+    }
+
+    async fn get_l2_block_timestamp(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                timestamp
+            FROM
+                miniblocks
+            WHERE
+                number = $1
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("hard_prune_batches_range#get_l2_block_timestamp")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.timestamp as u64))
+    }
+
     async fn delete_events(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
     ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
         let execution_result = sqlx::query!(
             r#"
             DELETE FROM events
             WHERE
                 miniblock_number BETWEEN $1 AND $2
+                AND address <> ALL ($3)
+                AND tx_initiator_address <> ALL ($3)
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]]
         )
         .instrument("hard_prune_batches_range#delete_events")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -220,15 +663,19 @@ impl PruningDal<'_, '_> {
     async fn delete_l2_to_l1_logs(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
     ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
         let execution_result = sqlx::query!(
             r#"
             DELETE FROM l2_to_l1_logs
             WHERE
                 miniblock_number BETWEEN $1 AND $2
+                AND sender <> ALL ($3)
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]]
         )
         .instrument("hard_prune_batches_range#delete_l2_to_l1_logs")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -244,7 +691,9 @@ impl PruningDal<'_, '_> {
     async fn delete_call_traces(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
     ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
         let execution_result = sqlx::query!(
             r#"
             DELETE FROM call_traces
@@ -256,10 +705,16 @@ impl PruningDal<'_, '_> {
                         transactions
                     WHERE
                         miniblock_number BETWEEN $1 AND $2
+                        AND initiator_address <> ALL ($3)
+                        AND (
+                            contract_address IS NULL
+                            OR contract_address <> ALL ($3)
+                        )
                 )
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]]
         )
         .instrument("hard_prune_batches_range#delete_call_traces")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -280,7 +735,9 @@ impl PruningDal<'_, '_> {
     async fn clear_transaction_fields(
         &mut self,
         l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+        retained_addresses: &[Address],
     ) -> DalResult<u64> {
+        let retained_addresses: Vec<_> = retained_addresses.iter().map(Address::as_bytes).collect();
         let execution_result = sqlx::query!(
             r#"
             UPDATE transactions
@@ -292,9 +749,15 @@ impl PruningDal<'_, '_> {
             WHERE
                 miniblock_number BETWEEN $1 AND $2
                 AND upgrade_id IS NULL
+                AND initiator_address <> ALL ($3)
+                AND (
+                    contract_address IS NULL
+                    OR contract_address <> ALL ($3)
+                )
             "#,
             i64::from(l2_blocks_to_prune.start().0),
-            i64::from(l2_blocks_to_prune.end().0)
+            i64::from(l2_blocks_to_prune.end().0),
+            &retained_addresses as &[&[u8]]
         )
         .instrument("hard_prune_batches_range#clear_transaction_fields")
         .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
@@ -304,6 +767,63 @@ impl PruningDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    /// Removes private transaction metadata for transactions included into the pruned L2 blocks or no longer present
+    /// in the `transactions` table (e.g., removed from the mempool), together with the emptied bundles.
+    async fn delete_private_txs(
+        &mut self,
+        l2_blocks_to_prune: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM private_transactions
+            WHERE
+                tx_hash IN (
+                    SELECT
+                        hash
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                )
+                OR NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transactions
+                    WHERE
+                        transactions.hash = private_transactions.tx_hash
+                )
+            "#,
+            i64::from(l2_blocks_to_prune.start().0),
+            i64::from(l2_blocks_to_prune.end().0)
+        )
+        .instrument("hard_prune_batches_range#delete_private_txs")
+        .with_arg("l2_blocks_to_prune", &l2_blocks_to_prune)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM tx_bundles
+            WHERE
+                NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        private_transactions
+                    WHERE
+                        private_transactions.bundle_id = tx_bundles.id
+                )
+            "#
+        )
+        .instrument("hard_prune_batches_range#delete_tx_bundles")
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     /// Removes storage logs overwritten by the specified new logs.
     async fn prune_storage_logs(
         &mut self,
@@ -388,6 +908,7 @@ impl PruningDal<'_, '_> {
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
         last_l2_block_to_prune: L2BlockNumber,
+        last_l2_block_timestamp: Option<u64>,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -395,20 +916,23 @@ impl PruningDal<'_, '_> {
                 pruning_log (
                     pruned_l1_batch,
                     pruned_miniblock,
+                    pruned_miniblock_timestamp,
                     TYPE,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, $3, NOW(), NOW())
+                ($1, $2, $3, $4, NOW(), NOW())
             "#,
             i64::from(last_l1_batch_to_prune.0),
             i64::from(last_l2_block_to_prune.0),
+            last_l2_block_timestamp.map(|timestamp| timestamp as i64),
             PruneType::Hard as PruneType
         )
         .instrument("hard_prune_batches_range#insert_pruning_log")
         .with_arg("last_l1_batch_to_prune", &last_l1_batch_to_prune)
         .with_arg("last_l2_block_to_prune", &last_l2_block_to_prune)
+        .with_arg("last_l2_block_timestamp", &last_l2_block_timestamp)
         .report_latency()
         .execute(self.storage)
         .await?;
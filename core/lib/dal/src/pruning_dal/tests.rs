@@ -1,4 +1,4 @@
-use std::ops;
+use std::{collections::HashSet, ops};
 
 use zksync_db_connection::connection::Connection;
 use zksync_types::{
@@ -443,6 +443,56 @@ async fn l1_batches_can_be_hard_pruned() {
     );
 }
 
+#[tokio::test]
+async fn retained_data_is_pruned_separately() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_realistic_l1_batches(&mut conn, 10).await;
+
+    let retained_address = Address::repeat_byte(1);
+    let options = HardPruningOptions {
+        retained_classes: HashSet::from([RetainedDataClass::Events]),
+        retained_addresses: vec![retained_address],
+    };
+    let stats = conn
+        .pruning_dal()
+        .hard_prune_batches_range_with_options(L1BatchNumber(4), L2BlockNumber(9), &options)
+        .await
+        .unwrap();
+    assert_eq!(stats.deleted_l1_batches, 5);
+    assert_eq!(stats.deleted_l2_blocks, 10);
+    assert_eq!(stats.deleted_events, 0);
+    assert_eq!(stats.deleted_l2_to_l1_logs, 50);
+
+    // Block timestamps are equal to block numbers.
+    let pruning_dal = &mut conn.pruning_dal();
+    let last_pruned_block = pruning_dal.get_last_hard_pruned_l2_block_before(8).await;
+    assert_eq!(last_pruned_block.unwrap(), None);
+    let last_pruned_block = pruning_dal.get_last_hard_pruned_l2_block_before(9).await;
+    assert_eq!(last_pruned_block.unwrap(), Some(L2BlockNumber(9)));
+
+    let deleted_events = pruning_dal
+        .prune_retained_data(
+            RetainedDataClass::Events,
+            L2BlockNumber(0)..=L2BlockNumber(9),
+            &options.retained_addresses,
+        )
+        .await
+        .unwrap();
+    // Each L2 block has 5 events, one of which is emitted by the retained address.
+    assert_eq!(deleted_events, 40);
+
+    let deleted_events = pruning_dal
+        .prune_retained_data(
+            RetainedDataClass::Events,
+            L2BlockNumber(0)..=L2BlockNumber(9),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(deleted_events, 10);
+}
+
 #[tokio::test]
 async fn transactions_are_handled_correctly_after_pruning() {
     let pool = ConnectionPool::<Core>::test_pool().await;
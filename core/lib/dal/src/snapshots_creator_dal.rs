@@ -107,6 +107,106 @@ impl SnapshotsCreatorDal<'_, '_> {
         Ok(storage_logs)
     }
 
+    /// Returns an upper bound on the number of distinct storage keys written to in L2 blocks
+    /// `(base_l2_block_number..=l2_block_number)`.
+    pub async fn get_changed_storage_logs_keys_count(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(DISTINCT hashed_key) AS "count!"
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number > $1
+                AND miniblock_number <= $2
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_changed_storage_logs_keys_count")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .report_latency()
+        .expect_slow_query()
+        .fetch_one(self.storage)
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
+    /// Constructs a delta `storage_logs` chunk, i.e. the state AFTER processing `[0..l1_batch_number]` batches
+    /// restricted to keys written to after `base_l2_block_number`. `l2_block_number` MUST be the last L2 block
+    /// of the `l1_batch_number` batch.
+    pub async fn get_changed_storage_logs_chunk(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys_range: std::ops::RangeInclusive<H256>,
+    ) -> DalResult<Vec<SnapshotStorageLog>> {
+        // Phantom writes are filtered out in the same way as in `get_storage_logs_chunk()`.
+        let storage_logs = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.hashed_key AS "hashed_key!",
+                storage_logs.value AS "value!",
+                storage_logs.miniblock_number AS "miniblock_number!",
+                initial_writes.l1_batch_number AS "l1_batch_number!",
+                initial_writes.index
+            FROM
+                (
+                    SELECT
+                        hashed_key,
+                        MAX(ARRAY[miniblock_number, operation_number]::INT[]) AS op
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $5
+                        AND miniblock_number <= $1
+                        AND hashed_key >= $3
+                        AND hashed_key <= $4
+                    GROUP BY
+                        hashed_key
+                    ORDER BY
+                        hashed_key
+                ) AS keys
+                INNER JOIN storage_logs ON keys.hashed_key = storage_logs.hashed_key
+                AND storage_logs.miniblock_number = keys.op[1]
+                AND storage_logs.operation_number = keys.op[2]
+                INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key
+            WHERE
+                initial_writes.l1_batch_number <= $2
+            "#,
+            i64::from(l2_block_number.0),
+            i64::from(l1_batch_number.0),
+            hashed_keys_range.start().as_bytes(),
+            hashed_keys_range.end().as_bytes(),
+            i64::from(base_l2_block_number.0)
+        )
+        .instrument("get_changed_storage_logs_chunk")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("min_hashed_key", &hashed_keys_range.start())
+        .with_arg("max_hashed_key", &hashed_keys_range.end())
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?
+        .iter()
+        .map(|row| SnapshotStorageLog {
+            key: H256::from_slice(&row.hashed_key),
+            value: H256::from_slice(&row.value),
+            l1_batch_number_of_initial_write: L1BatchNumber(row.l1_batch_number as u32),
+            enumeration_index: row.index as u64,
+        })
+        .collect();
+        Ok(storage_logs)
+    }
+
     /// Same as [`Self::get_storage_logs_chunk()`], but returns full keys.
     #[deprecated(
         note = "will fail if called on a node restored from a v1 snapshot; use `get_storage_logs_chunk()` instead"
@@ -204,6 +304,40 @@ impl SnapshotsCreatorDal<'_, '_> {
             .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
             .collect())
     }
+
+    /// Returns factory dependencies added in L2 blocks `(base_l2_block_number..=l2_block_number)`.
+    pub async fn get_factory_deps_after(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<(H256, Vec<u8>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash,
+                bytecode
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number > $1
+                AND miniblock_number <= $2
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+        )
+        .instrument("get_factory_deps_after")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -377,4 +511,85 @@ mod tests {
         assert_eq!(logs[0].value, real_write.value);
         assert_eq!(logs[0].l1_batch_number_of_initial_write, L1BatchNumber(2));
     }
+
+    #[tokio::test]
+    async fn getting_changed_storage_log_chunks() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let logs: Vec<_> = (0..10)
+            .map(|i| {
+                let key = StorageKey::new(AccountTreeId::default(), H256::from_low_u64_be(i));
+                StorageLog::new_write_log(key, H256::repeat_byte(1))
+            })
+            .collect();
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await
+            .unwrap();
+        let written_keys: Vec<_> = logs.iter().map(|log| log.key.hashed_key()).collect();
+        conn.storage_logs_dedup_dal()
+            .insert_initial_writes(L1BatchNumber(1), &written_keys)
+            .await
+            .unwrap();
+
+        let new_key = StorageKey::new(AccountTreeId::default(), H256::from_low_u64_be(100));
+        let new_logs = [
+            StorageLog::new_write_log(logs[3].key, H256::repeat_byte(2)),
+            StorageLog::new_write_log(new_key, H256::repeat_byte(3)),
+        ];
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(2), &new_logs)
+            .await
+            .unwrap();
+        conn.storage_logs_dedup_dal()
+            .insert_initial_writes(L1BatchNumber(2), &[new_key.hashed_key()])
+            .await
+            .unwrap();
+
+        let changed_count = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_keys_count(L2BlockNumber(1), L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(changed_count, 2);
+
+        let mut changed_logs = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_chunk(
+                L2BlockNumber(1),
+                L2BlockNumber(2),
+                L1BatchNumber(2),
+                H256::zero()..=H256::repeat_byte(0xff),
+            )
+            .await
+            .unwrap();
+        changed_logs.sort_unstable_by_key(|log| log.l1_batch_number_of_initial_write);
+        assert_eq!(changed_logs.len(), 2);
+        assert_eq!(changed_logs[0].key, logs[3].key.hashed_key());
+        assert_eq!(changed_logs[0].value, H256::repeat_byte(2));
+        assert_eq!(
+            changed_logs[0].l1_batch_number_of_initial_write,
+            L1BatchNumber(1)
+        );
+        assert_eq!(changed_logs[1].key, new_key.hashed_key());
+        assert_eq!(changed_logs[1].value, H256::repeat_byte(3));
+        assert_eq!(
+            changed_logs[1].l1_batch_number_of_initial_write,
+            L1BatchNumber(2)
+        );
+
+        // There are no changes after the last L2 block.
+        let changed_logs = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_chunk(
+                L2BlockNumber(2),
+                L2BlockNumber(2),
+                L1BatchNumber(2),
+                H256::zero()..=H256::repeat_byte(0xff),
+            )
+            .await
+            .unwrap();
+        assert_eq!(changed_logs, []);
+    }
 }
@@ -4,8 +4,10 @@ use zksync_db_connection::{
     instrument::InstrumentExt,
 };
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotMetadata, SnapshotVersion},
-    L1BatchNumber,
+    snapshots::{
+        AllSnapshots, SnapshotIpfsAnchor, SnapshotManifest, SnapshotMetadata, SnapshotVersion,
+    },
+    L1BatchNumber, PackedEthSignature, H256,
 };
 
 use crate::Core;
@@ -14,6 +16,7 @@ use crate::Core;
 struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
     factory_deps_filepath: String,
 }
@@ -28,6 +31,9 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
         Ok(Self {
             version,
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
@@ -38,6 +44,51 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
     }
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct StorageSnapshotManifest {
+    version: i32,
+    l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
+    factory_deps_digest: Option<Vec<u8>>,
+    storage_logs_digests: Option<Vec<Vec<u8>>>,
+    manifest_signature: Option<Vec<u8>>,
+}
+
+impl StorageSnapshotManifest {
+    /// Returns `Ok(None)` if the snapshot doesn't have digests for all its objects (e.g., if it was created
+    /// before manifests were introduced).
+    fn into_manifest(self) -> Result<Option<SnapshotManifest>, sqlx::Error> {
+        let Some(factory_deps_digest) = self.factory_deps_digest else {
+            return Ok(None);
+        };
+        let Some(storage_logs_digests) = self.storage_logs_digests else {
+            return Ok(None);
+        };
+        if storage_logs_digests.iter().any(|digest| digest.len() != 32) {
+            return Ok(None);
+        }
+
+        let signature = self
+            .manifest_signature
+            .map(|bytes| PackedEthSignature::deserialize_packed(&bytes))
+            .transpose()
+            .decode_column("manifest_signature")?;
+        Ok(Some(SnapshotManifest {
+            version: u16::try_from(self.version).decode_column("version")?,
+            l1_batch_number: L1BatchNumber(self.l1_batch_number as u32),
+            base_l1_batch_number: self
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
+            factory_deps_digest: H256::from_slice(&factory_deps_digest),
+            storage_logs_chunk_digests: storage_logs_digests
+                .iter()
+                .map(|digest| H256::from_slice(digest))
+                .collect(),
+            signature,
+        }))
+    }
+}
+
 #[derive(Debug)]
 pub struct SnapshotsDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Core>,
@@ -48,8 +99,10 @@ impl SnapshotsDal<'_, '_> {
         &mut self,
         version: SnapshotVersion,
         l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
         storage_logs_chunk_count: u64,
         factory_deps_filepaths: &str,
+        factory_deps_digest: H256,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -57,22 +110,38 @@ impl SnapshotsDal<'_, '_> {
                 snapshots (
                     VERSION,
                     l1_batch_number,
+                    base_l1_batch_number,
                     storage_logs_filepaths,
+                    storage_logs_digests,
                     factory_deps_filepath,
+                    factory_deps_digest,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
+                (
+                    $1,
+                    $2,
+                    $3,
+                    ARRAY_FILL(''::TEXT, ARRAY[$4::INTEGER]),
+                    ARRAY_FILL(''::BYTEA, ARRAY[$4::INTEGER]),
+                    $5,
+                    $6,
+                    NOW(),
+                    NOW()
+                )
             "#,
             version as i32,
             l1_batch_number.0 as i32,
+            base_l1_batch_number.map(|number| i64::from(number.0)),
             storage_logs_chunk_count as i32,
             factory_deps_filepaths,
+            factory_deps_digest.as_bytes(),
         )
         .instrument("add_snapshot")
         .with_arg("version", &version)
         .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
         .report_latency()
         .execute(self.storage)
         .await?;
@@ -84,12 +153,14 @@ impl SnapshotsDal<'_, '_> {
         l1_batch_number: L1BatchNumber,
         chunk_id: u64,
         storage_logs_filepath: &str,
+        storage_logs_digest: H256,
     ) -> DalResult<()> {
         sqlx::query!(
             r#"
             UPDATE snapshots
             SET
                 storage_logs_filepaths[$2] = $3,
+                storage_logs_digests[$2] = $4,
                 updated_at = NOW()
             WHERE
                 l1_batch_number = $1
@@ -97,6 +168,7 @@ impl SnapshotsDal<'_, '_> {
             l1_batch_number.0 as i32,
             chunk_id as i32 + 1,
             storage_logs_filepath,
+            storage_logs_digest.as_bytes(),
         )
         .instrument("add_storage_logs_filepath_for_snapshot")
         .with_arg("l1_batch_number", &l1_batch_number)
@@ -142,6 +214,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             FROM
@@ -169,6 +242,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             FROM
@@ -186,6 +260,193 @@ impl SnapshotsDal<'_, '_> {
         .await
     }
 
+    /// Returns the integrity manifest for a complete snapshot. Returns `None` if the snapshot doesn't exist,
+    /// is incomplete, or has no digests (i.e., was created before manifests were introduced).
+    pub async fn get_snapshot_manifest(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<SnapshotManifest>> {
+        let manifest = sqlx::query_as!(
+            StorageSnapshotManifest,
+            r#"
+            SELECT
+                VERSION,
+                l1_batch_number,
+                base_l1_batch_number,
+                factory_deps_digest,
+                storage_logs_digests,
+                manifest_signature
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number = $1
+                AND NOT (''::TEXT = ANY (storage_logs_filepaths))
+            "#,
+            l1_batch_number.0 as i32
+        )
+        .try_map(StorageSnapshotManifest::into_manifest)
+        .instrument("get_snapshot_manifest")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(manifest.flatten())
+    }
+
+    /// Sets the operator signature for the manifest of the specified snapshot.
+    pub async fn set_snapshot_manifest_signature(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        signature: &PackedEthSignature,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE snapshots
+            SET
+                manifest_signature = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i32,
+            signature.serialize_packed().as_slice()
+        )
+        .instrument("set_snapshot_manifest_signature")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns information about the snapshot publication to IPFS, or `None` if the snapshot doesn't exist
+    /// or wasn't published.
+    pub async fn get_snapshot_ipfs_anchor(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<SnapshotIpfsAnchor>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                ipfs_document_cid AS "ipfs_document_cid!",
+                mintlayer_anchor_tx_id AS "mintlayer_anchor_tx_id!"
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number = $1
+                AND ipfs_document_cid IS NOT NULL
+                AND mintlayer_anchor_tx_id IS NOT NULL
+            "#,
+            l1_batch_number.0 as i32
+        )
+        .instrument("get_snapshot_ipfs_anchor")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| SnapshotIpfsAnchor {
+            document_cid: row.ipfs_document_cid,
+            anchor_tx_id: row.mintlayer_anchor_tx_id,
+        }))
+    }
+
+    /// Records that the specified snapshot is published to IPFS and anchored on Mintlayer.
+    pub async fn set_snapshot_ipfs_anchor(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        anchor: &SnapshotIpfsAnchor,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE snapshots
+            SET
+                ipfs_document_cid = $2,
+                mintlayer_anchor_tx_id = $3,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i32,
+            &anchor.document_cid,
+            &anchor.anchor_tx_id
+        )
+        .instrument("set_snapshot_ipfs_anchor")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("anchor", anchor)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns IPFS anchors for all snapshots after the specified L1 batch number, ordered by the L1 batch number.
+    pub async fn get_snapshot_ipfs_anchors_after(
+        &mut self,
+        last_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotIpfsAnchor>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                ipfs_document_cid AS "ipfs_document_cid!",
+                mintlayer_anchor_tx_id AS "mintlayer_anchor_tx_id!"
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number > $1
+                AND ipfs_document_cid IS NOT NULL
+                AND mintlayer_anchor_tx_id IS NOT NULL
+            ORDER BY
+                l1_batch_number
+            "#,
+            i64::from(last_retained_l1_batch_number.0)
+        )
+        .instrument("get_snapshot_ipfs_anchors_after")
+        .with_arg(
+            "last_retained_l1_batch_number",
+            &last_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SnapshotIpfsAnchor {
+                document_cid: row.ipfs_document_cid,
+                anchor_tx_id: row.mintlayer_anchor_tx_id,
+            })
+            .collect())
+    }
+
+    /// Returns metadata for all snapshots after the specified L1 batch number, ordered by the L1 batch number.
+    pub async fn get_snapshots_after(
+        &mut self,
+        last_retained_l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<SnapshotMetadata>> {
+        sqlx::query_as!(
+            StorageSnapshotMetadata,
+            r#"
+            SELECT
+                VERSION,
+                l1_batch_number,
+                base_l1_batch_number,
+                factory_deps_filepath,
+                storage_logs_filepaths
+            FROM
+                snapshots
+            WHERE
+                l1_batch_number > $1
+            ORDER BY
+                l1_batch_number
+            "#,
+            i64::from(last_retained_l1_batch_number.0)
+        )
+        .try_map(SnapshotMetadata::try_from)
+        .instrument("get_snapshots_after")
+        .with_arg(
+            "last_retained_l1_batch_number",
+            &last_retained_l1_batch_number,
+        )
+        .fetch_all(self.storage)
+        .await
+    }
+
     /// Deletes all snapshots after the specified L1 batch number and returns their metadata.
     pub async fn delete_snapshots_after(
         &mut self,
@@ -200,6 +461,7 @@ impl SnapshotsDal<'_, '_> {
             RETURNING
                 VERSION,
                 l1_batch_number,
+                base_l1_batch_number,
                 factory_deps_filepath,
                 storage_logs_filepaths
             "#,
@@ -218,9 +480,10 @@ impl SnapshotsDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{snapshots::SnapshotVersion, L1BatchNumber};
+    use zksync_types::{K256PrivateKey, L2ChainId};
 
-    use crate::{ConnectionPool, Core, CoreDal};
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
 
     #[tokio::test]
     async fn adding_snapshot() {
@@ -231,8 +494,10 @@ mod tests {
         dal.add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             2,
             "gs:///bucket/factory_deps.bin",
+            H256::zero(),
         )
         .await
         .expect("Failed to add snapshot");
@@ -248,6 +513,7 @@ mod tests {
                 l1_batch_number,
                 i,
                 "gs:///bucket/chunk.bin",
+                H256::zero(),
             )
             .await
             .unwrap();
@@ -276,8 +542,10 @@ mod tests {
         dal.add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             2,
             "gs:///bucket/factory_deps.bin",
+            H256::zero(),
         )
         .await
         .unwrap();
@@ -287,6 +555,7 @@ mod tests {
                 l1_batch_number,
                 i,
                 "gs:///bucket/chunk.bin",
+                H256::zero(),
             )
             .await
             .unwrap();
@@ -339,16 +608,23 @@ mod tests {
         dal.add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             2,
             "gs:///bucket/factory_deps.bin",
+            H256::zero(),
         )
         .await
         .expect("Failed to add snapshot");
 
         let storage_log_filepaths = ["gs:///bucket/test_file1.bin", "gs:///bucket/test_file2.bin"];
-        dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, 1, storage_log_filepaths[1])
-            .await
-            .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            1,
+            storage_log_filepaths[1],
+            H256::zero(),
+        )
+        .await
+        .unwrap();
 
         let files = dal
             .get_snapshot_metadata(l1_batch_number)
@@ -361,9 +637,14 @@ mod tests {
             [None, Some("gs:///bucket/test_file2.bin".to_string())]
         );
 
-        dal.add_storage_logs_filepath_for_snapshot(l1_batch_number, 0, storage_log_filepaths[0])
-            .await
-            .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            0,
+            storage_log_filepaths[0],
+            H256::zero(),
+        )
+        .await
+        .unwrap();
 
         let files = dal
             .get_snapshot_metadata(l1_batch_number)
@@ -379,4 +660,107 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn getting_snapshot_manifest() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let l1_batch_number = L1BatchNumber(100);
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            l1_batch_number,
+            None,
+            2,
+            "gs:///bucket/factory_deps.bin",
+            H256::repeat_byte(0xff),
+        )
+        .await
+        .unwrap();
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            1,
+            "gs:///bucket/chunk1.bin",
+            H256::repeat_byte(1),
+        )
+        .await
+        .unwrap();
+
+        // Manifests are not returned for incomplete snapshots.
+        let manifest = dal.get_snapshot_manifest(l1_batch_number).await.unwrap();
+        assert!(manifest.is_none(), "{manifest:?}");
+
+        dal.add_storage_logs_filepath_for_snapshot(
+            l1_batch_number,
+            0,
+            "gs:///bucket/chunk0.bin",
+            H256::zero(),
+        )
+        .await
+        .unwrap();
+        let mut manifest = dal
+            .get_snapshot_manifest(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no manifest");
+        assert_eq!(
+            manifest,
+            SnapshotManifest {
+                version: SnapshotVersion::Version1.into(),
+                l1_batch_number,
+                base_l1_batch_number: None,
+                factory_deps_digest: H256::repeat_byte(0xff),
+                storage_logs_chunk_digests: vec![H256::zero(), H256::repeat_byte(1)],
+                signature: None,
+            }
+        );
+
+        let private_key = K256PrivateKey::random();
+        let l2_chain_id = L2ChainId::default();
+        manifest.sign(l2_chain_id, &private_key).unwrap();
+        dal.set_snapshot_manifest_signature(l1_batch_number, manifest.signature.as_ref().unwrap())
+            .await
+            .unwrap();
+        let signed_manifest = dal
+            .get_snapshot_manifest(l1_batch_number)
+            .await
+            .unwrap()
+            .expect("no manifest");
+        assert_eq!(signed_manifest, manifest);
+        assert_eq!(
+            signed_manifest.recover_signer(l2_chain_id).unwrap(),
+            Some(private_key.address())
+        );
+    }
+
+    #[tokio::test]
+    async fn setting_snapshot_ipfs_anchor() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.snapshots_dal();
+        let l1_batch_number = L1BatchNumber(100);
+        dal.add_snapshot(
+            SnapshotVersion::Version1,
+            l1_batch_number,
+            None,
+            1,
+            "gs:///bucket/factory_deps.bin",
+            H256::zero(),
+        )
+        .await
+        .unwrap();
+
+        let anchor = dal.get_snapshot_ipfs_anchor(l1_batch_number).await.unwrap();
+        assert_eq!(anchor, None);
+
+        let anchor = SnapshotIpfsAnchor {
+            document_cid: "bafkreidocument".to_owned(),
+            anchor_tx_id: "0123abcd".to_owned(),
+        };
+        dal.set_snapshot_ipfs_anchor(l1_batch_number, &anchor)
+            .await
+            .unwrap();
+        let persisted_anchor = dal.get_snapshot_ipfs_anchor(l1_batch_number).await.unwrap();
+        assert_eq!(persisted_anchor, Some(anchor));
+    }
 }
@@ -151,6 +151,41 @@ impl StorageLogsDal<'_, '_> {
         copy.send(buffer.as_bytes()).await
     }
 
+    /// Updates values of storage logs previously inserted using [`Self::insert_storage_logs_from_snapshot()`].
+    /// Returns the number of updated logs.
+    pub async fn update_storage_logs_from_snapshot(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+        snapshot_storage_logs: &[SnapshotStorageLog],
+    ) -> DalResult<usize> {
+        let (hashed_keys, values): (Vec<_>, Vec<_>) = snapshot_storage_logs
+            .iter()
+            .map(|log| (log.key.as_bytes(), log.value.as_bytes()))
+            .unzip();
+        let result = sqlx::query!(
+            r#"
+            UPDATE storage_logs
+            SET
+                value = data.value,
+                updated_at = NOW()
+            FROM
+                UNNEST($2::bytea[], $3::bytea[]) AS data (hashed_key, value)
+            WHERE
+                storage_logs.miniblock_number = $1
+                AND storage_logs.hashed_key = data.hashed_key
+            "#,
+            i64::from(l2_block_number.0),
+            &hashed_keys as &[&[u8]],
+            &values as &[&[u8]]
+        )
+        .instrument("update_storage_logs_from_snapshot")
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("storage_logs.len", &snapshot_storage_logs.len())
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() as usize)
+    }
+
     pub async fn append_storage_logs(
         &mut self,
         block_number: L2BlockNumber,
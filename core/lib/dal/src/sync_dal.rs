@@ -100,6 +100,30 @@ impl SyncDal<'_, '_> {
         };
         Ok(Some(block.into_api(transactions)))
     }
+
+    /// Returns L2 blocks in the specified range together with their transactions. Blocks missing from the storage
+    /// are skipped.
+    pub async fn sync_blocks_with_transactions(
+        &mut self,
+        numbers: std::ops::Range<L2BlockNumber>,
+    ) -> DalResult<Vec<en::SyncBlock>> {
+        let _latency = MethodLatency::new("sync_dal_sync_blocks_with_transactions");
+        let blocks = self.sync_blocks_inner(numbers.clone()).await?;
+        let mut transactions = self
+            .storage
+            .transactions_web3_dal()
+            .get_raw_l2_blocks_transactions(numbers)
+            .await?;
+        let mut blocks: Vec<_> = blocks
+            .into_iter()
+            .map(|block| {
+                let block_transactions = transactions.remove(&block.number).unwrap_or_default();
+                block.into_api(Some(block_transactions))
+            })
+            .collect();
+        blocks.sort_unstable_by_key(|block| block.number);
+        Ok(blocks)
+    }
 }
 
 #[cfg(test)]
@@ -249,6 +273,20 @@ mod tests {
         assert_eq!(block.l1_batch_number, L1BatchNumber(1));
         assert!(block.last_in_batch);
         assert_eq!(block.operator_address, miniblock_header.fee_account_address);
+
+        let blocks = conn
+            .sync_dal()
+            .sync_blocks_with_transactions(L2BlockNumber(0)..L2BlockNumber(4))
+            .await
+            .unwrap();
+        let block_numbers: Vec<_> = blocks.iter().map(|block| block.number.0).collect();
+        assert_eq!(block_numbers, [0, 1, 2]);
+        assert_eq!(blocks[0].transactions.as_ref().unwrap(), &[]);
+        assert_eq!(
+            blocks[1].transactions.as_ref().unwrap(),
+            &[Transaction::from(tx)]
+        );
+        assert!(blocks[2].last_in_batch);
     }
 
     #[tokio::test]
@@ -7,7 +7,7 @@ use zksync_db_connection::{
     instrument::{InstrumentExt, Instrumented},
     utils::pg_interval_from_duration,
 };
-use zksync_types::{tee_types::TeeType, L1BatchNumber};
+use zksync_types::{api, tee_types::TeeType, L1BatchNumber};
 
 use crate::Core;
 
@@ -202,4 +202,91 @@ impl TeeProofGenerationDal<'_, '_> {
 
         Ok(())
     }
+
+    pub async fn get_attestation(&mut self, pubkey: &[u8]) -> DalResult<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                attestation
+            FROM
+                tee_attestations
+            WHERE
+                pubkey = $1
+            "#,
+            pubkey
+        )
+        .instrument("get_attestation")
+        .with_arg("pubkey", &pubkey)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.and_then(|row| row.attestation))
+    }
+
+    /// Returns TEE proofs for the specified L1 batch together with attestations of the TEEs
+    /// that produced them.
+    pub async fn get_tee_proofs(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<api::TeeProof>> {
+        let query = sqlx::query!(
+            r#"
+            SELECT
+                proofs.l1_batch_number,
+                proofs.status,
+                proofs.tee_type,
+                proofs.pubkey,
+                proofs.signature,
+                proofs.proof,
+                proofs.updated_at,
+                attestations.attestation
+            FROM
+                tee_proof_generation_details AS proofs
+                LEFT JOIN tee_attestations AS attestations ON proofs.pubkey = attestations.pubkey
+            WHERE
+                proofs.l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        );
+        let instrumentation =
+            Instrumented::new("get_tee_proofs").with_arg("l1_batch_number", &l1_batch_number);
+        let rows = instrumentation
+            .clone()
+            .with(query)
+            .fetch_all(self.storage)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let status: api::TeeProofStatus = row.status.parse().map_err(|err| {
+                    instrumentation.constraint_error(anyhow::anyhow!(
+                        "invalid TEE proof generation status `{}`: {err}",
+                        row.status
+                    ))
+                })?;
+                let tee_type: Option<TeeType> = row
+                    .tee_type
+                    .map(|tee_type| {
+                        tee_type.parse().map_err(|err| {
+                            instrumentation.constraint_error(anyhow::anyhow!(
+                                "invalid TEE type `{tee_type}`: {err}"
+                            ))
+                        })
+                    })
+                    .transpose()?;
+                let proved_at =
+                    (status == api::TeeProofStatus::Generated).then(|| row.updated_at.and_utc());
+                Ok(api::TeeProof {
+                    l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+                    status,
+                    tee_type,
+                    pubkey: row.pubkey.map(Into::into),
+                    signature: row.signature.map(Into::into),
+                    proof: row.proof.map(Into::into),
+                    attestation: row.attestation.map(Into::into),
+                    proved_at,
+                })
+            })
+            .collect()
+    }
 }
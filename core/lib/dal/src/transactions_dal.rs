@@ -1691,8 +1691,31 @@ impl TransactionsDal<'_, '_> {
         Ok(rows.len())
     }
 
+    /// Removes transactions evicted from the mempool. Transactions that were already included into an L2 block
+    /// are not affected.
+    pub async fn remove_evicted_txs(&mut self, tx_hashes: &[H256]) -> DalResult<usize> {
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                in_mempool = TRUE
+                AND miniblock_number IS NULL
+                AND hash = ANY ($1)
+            "#,
+            &tx_hashes as &[&[u8]]
+        )
+        .instrument("remove_evicted_txs")
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .execute(self.storage)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
     /// Fetches new updates for mempool. Returns new transactions and current nonces for related accounts;
-    /// the latter are only used to bootstrap mempool for given account.
+    /// the latter are only used to bootstrap mempool for given account. Bundled transactions are never returned;
+    /// they are executed by the state keeper separately.
     pub async fn sync_mempool(
         &mut self,
         stashed_accounts: &[Address],
@@ -1764,6 +1787,15 @@ impl TransactionsDal<'_, '_> {
                                     )
                                 )
                                 AND tx_format != $4
+                                AND NOT EXISTS (
+                                    SELECT
+                                        1
+                                    FROM
+                                        private_transactions
+                                    WHERE
+                                        private_transactions.tx_hash = transactions.hash
+                                        AND private_transactions.bundle_id IS NOT NULL
+                                )
                             ORDER BY
                                 is_priority DESC,
                                 priority_op_id,
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use anyhow::Context as _;
 use sqlx::types::chrono::NaiveDateTime;
@@ -324,6 +324,7 @@ impl TransactionsWeb3Dal<'_, '_> {
     }
 
     /// Returns hashes of txs which were received after `from_timestamp` and the time of receiving the last tx.
+    /// Private transactions are not returned.
     pub async fn get_pending_txs_hashes_after(
         &mut self,
         from_timestamp: NaiveDateTime,
@@ -338,6 +339,14 @@ impl TransactionsWeb3Dal<'_, '_> {
                 transactions
             WHERE
                 received_at > $1
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        private_transactions
+                    WHERE
+                        private_transactions.tx_hash = transactions.hash
+                )
             ORDER BY
                 received_at ASC
             LIMIT
@@ -359,6 +368,119 @@ impl TransactionsWeb3Dal<'_, '_> {
         Ok(hashes)
     }
 
+    /// Returns hashes of L2 transactions that are not yet included into an L2 block (i.e., are in the mempool),
+    /// optionally filtered by the initiator address. Transactions are ordered by initiator and nonce.
+    /// Private transactions are not returned.
+    pub async fn get_mempool_tx_hashes(
+        &mut self,
+        initiator_address: Option<Address>,
+        limit: usize,
+    ) -> DalResult<Vec<H256>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash
+            FROM
+                transactions
+            WHERE
+                miniblock_number IS NULL
+                AND is_priority = FALSE
+                AND error IS NULL
+                AND (
+                    $1::bytea IS NULL
+                    OR initiator_address = $1
+                )
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        private_transactions
+                    WHERE
+                        private_transactions.tx_hash = transactions.hash
+                )
+            ORDER BY
+                nonce,
+                initiator_address
+            LIMIT
+                $2
+            "#,
+            initiator_address.as_ref().map(Address::as_bytes),
+            limit as i64
+        )
+        .instrument("get_mempool_tx_hashes")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| H256::from_slice(&row.hash))
+            .collect())
+    }
+
+    /// Returns ranges of contiguous nonces of public mempool transactions for each initiator account.
+    /// Unlike [`Self::get_mempool_tx_hashes()`], this method is not limited by the number of transactions.
+    pub async fn get_mempool_nonce_ranges(
+        &mut self,
+    ) -> DalResult<HashMap<Address, Vec<Range<u64>>>> {
+        let rows = sqlx::query!(
+            r#"
+            WITH
+                mempool AS (
+                    SELECT
+                        initiator_address,
+                        nonce,
+                        nonce - ROW_NUMBER() OVER (
+                            PARTITION BY
+                                initiator_address
+                            ORDER BY
+                                nonce
+                        ) AS run
+                    FROM
+                        transactions
+                    WHERE
+                        miniblock_number IS NULL
+                        AND is_priority = FALSE
+                        AND error IS NULL
+                        AND NOT EXISTS (
+                            SELECT
+                                1
+                            FROM
+                                private_transactions
+                            WHERE
+                                private_transactions.tx_hash = transactions.hash
+                        )
+                )
+            SELECT
+                initiator_address AS "initiator_address!",
+                MIN(nonce) AS "first_nonce!",
+                COUNT(*) AS "tx_count!"
+            FROM
+                mempool
+            GROUP BY
+                initiator_address,
+                run
+            "#
+        )
+        .instrument("get_mempool_nonce_ranges")
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut ranges = HashMap::<_, Vec<_>>::new();
+        for row in rows {
+            let first_nonce = row.first_nonce as u64;
+            ranges
+                .entry(Address::from_slice(&row.initiator_address))
+                .or_default()
+                .push(first_nonce..first_nonce + row.tx_count as u64);
+        }
+        for account_ranges in ranges.values_mut() {
+            account_ranges.sort_unstable_by_key(|range| range.start);
+        }
+        Ok(ranges)
+    }
+
     /// `committed_next_nonce` should equal the nonce for `initiator_address` in the storage.
     pub async fn next_nonce_by_initiator_account(
         &mut self,
@@ -652,6 +774,58 @@ mod tests {
         assert_eq!(raw_txs[0].hash(), tx_hash);
     }
 
+    #[tokio::test]
+    async fn getting_mempool_tx_hashes() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let executed_tx = mock_l2_transaction();
+        prepare_transactions(&mut conn, vec![executed_tx]).await;
+
+        let initiator = Address::repeat_byte(1);
+        let mut initiator_tx_hashes = vec![];
+        for nonce in [2, 0] {
+            let mut tx = mock_l2_transaction();
+            tx.common_data.nonce = Nonce(nonce);
+            tx.common_data.initiator_address = initiator;
+            initiator_tx_hashes.push(tx.hash());
+            conn.transactions_dal()
+                .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+        initiator_tx_hashes.reverse(); // Hashes must be ordered by nonce
+        let other_tx = mock_l2_transaction();
+        conn.transactions_dal()
+            .insert_transaction_l2(&other_tx, TransactionExecutionMetrics::default())
+            .await
+            .unwrap();
+
+        let all_hashes = conn
+            .transactions_web3_dal()
+            .get_mempool_tx_hashes(None, 100)
+            .await
+            .unwrap();
+        assert_eq!(all_hashes.len(), 3);
+        assert!(all_hashes.contains(&other_tx.hash()));
+
+        let hashes = conn
+            .transactions_web3_dal()
+            .get_mempool_tx_hashes(Some(initiator), 100)
+            .await
+            .unwrap();
+        assert_eq!(hashes, initiator_tx_hashes);
+        let hashes = conn
+            .transactions_web3_dal()
+            .get_mempool_tx_hashes(Some(initiator), 1)
+            .await
+            .unwrap();
+        assert_eq!(hashes, initiator_tx_hashes[..1]);
+    }
+
     #[tokio::test]
     async fn getting_next_nonce_by_initiator_account() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
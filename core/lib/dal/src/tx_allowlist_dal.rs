@@ -0,0 +1,183 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::Address;
+
+use crate::Core;
+
+/// Permission granted to an address in the transaction allowlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxAllowlistPermission {
+    /// The address may send transactions to any contract.
+    Sender,
+    /// Any address may send transactions to this contract.
+    Contract,
+    /// The address may deploy contracts.
+    Deployer,
+}
+
+impl TxAllowlistPermission {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sender => "sender",
+            Self::Contract => "contract",
+            Self::Deployer => "deployer",
+        }
+    }
+
+    fn from_str(s: &str) -> Self {
+        match s {
+            "sender" => Self::Sender,
+            "contract" => Self::Contract,
+            "deployer" => Self::Deployer,
+            // Guaranteed by the `CHECK` constraint on the table.
+            _ => panic!("unknown tx allowlist permission: {s}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TxAllowlistDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl TxAllowlistDal<'_, '_> {
+    /// Returns all allowlist entries.
+    pub async fn get_entries(&mut self) -> DalResult<Vec<(Address, TxAllowlistPermission)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                permission
+            FROM
+                tx_allowlist
+            "#
+        )
+        .instrument("get_tx_allowlist_entries")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    Address::from_slice(&row.address),
+                    TxAllowlistPermission::from_str(&row.permission),
+                )
+            })
+            .collect())
+    }
+
+    /// Grants the `permission` to the `address`. Does nothing if the permission is already granted.
+    pub async fn add_entry(
+        &mut self,
+        address: Address,
+        permission: TxAllowlistPermission,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                tx_allowlist (address, permission, created_at)
+            VALUES
+                ($1, $2, NOW())
+            ON CONFLICT DO NOTHING
+            "#,
+            address.as_bytes(),
+            permission.as_str()
+        )
+        .instrument("add_tx_allowlist_entry")
+        .with_arg("address", &address)
+        .with_arg("permission", &permission)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Revokes the `permission` from the `address`. Returns `false` if the permission wasn't granted.
+    pub async fn remove_entry(
+        &mut self,
+        address: Address,
+        permission: TxAllowlistPermission,
+    ) -> DalResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM tx_allowlist
+            WHERE
+                address = $1
+                AND permission = $2
+            "#,
+            address.as_bytes(),
+            permission.as_str()
+        )
+        .instrument("remove_tx_allowlist_entry")
+        .with_arg("address", &address)
+        .with_arg("permission", &permission)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn manipulating_tx_allowlist() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        assert!(conn
+            .tx_allowlist_dal()
+            .get_entries()
+            .await
+            .unwrap()
+            .is_empty());
+
+        let sender = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        conn.tx_allowlist_dal()
+            .add_entry(sender, TxAllowlistPermission::Sender)
+            .await
+            .unwrap();
+        conn.tx_allowlist_dal()
+            .add_entry(sender, TxAllowlistPermission::Deployer)
+            .await
+            .unwrap();
+        conn.tx_allowlist_dal()
+            .add_entry(contract, TxAllowlistPermission::Contract)
+            .await
+            .unwrap();
+        // Adding an existing entry is a no-op.
+        conn.tx_allowlist_dal()
+            .add_entry(contract, TxAllowlistPermission::Contract)
+            .await
+            .unwrap();
+
+        let mut entries = conn.tx_allowlist_dal().get_entries().await.unwrap();
+        entries.sort_unstable_by_key(|(address, permission)| (*address, permission.as_str()));
+        assert_eq!(
+            entries,
+            [
+                (sender, TxAllowlistPermission::Deployer),
+                (sender, TxAllowlistPermission::Sender),
+                (contract, TxAllowlistPermission::Contract),
+            ]
+        );
+
+        let removed = conn
+            .tx_allowlist_dal()
+            .remove_entry(sender, TxAllowlistPermission::Deployer)
+            .await
+            .unwrap();
+        assert!(removed);
+        let removed = conn
+            .tx_allowlist_dal()
+            .remove_entry(contract, TxAllowlistPermission::Deployer)
+            .await
+            .unwrap();
+        assert!(!removed);
+        assert_eq!(
+            conn.tx_allowlist_dal().get_entries().await.unwrap().len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{api::en, L1BatchNumber, H256};
+
+use crate::Core;
+
+/// Header of an L1 batch checked by the verify-only external node against the L1 commitment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedL1Batch {
+    pub number: L1BatchNumber,
+    pub root_hash: H256,
+    pub commitment: H256,
+    /// Hash of the L1 transaction committing the batch.
+    pub commit_tx_hash: H256,
+    /// Mintlayer anchor of the batch commitment; `None` if the anchor is not checked yet.
+    pub anchor: Option<en::L1BatchAnchor>,
+}
+
+#[derive(Debug)]
+struct StorageVerifiedL1Batch {
+    l1_batch_number: i64,
+    root_hash: Vec<u8>,
+    commitment: Vec<u8>,
+    commit_tx_hash: Vec<u8>,
+    ipfs_hash: Option<String>,
+    mintlayer_tx_id: Option<String>,
+}
+
+impl From<StorageVerifiedL1Batch> for VerifiedL1Batch {
+    fn from(row: StorageVerifiedL1Batch) -> Self {
+        Self {
+            number: L1BatchNumber(row.l1_batch_number as u32),
+            root_hash: H256::from_slice(&row.root_hash),
+            commitment: H256::from_slice(&row.commitment),
+            commit_tx_hash: H256::from_slice(&row.commit_tx_hash),
+            anchor: row
+                .ipfs_hash
+                .zip(row.mintlayer_tx_id)
+                .map(|(ipfs_hash, mintlayer_tx_id)| en::L1BatchAnchor {
+                    ipfs_hash,
+                    mintlayer_tx_id,
+                }),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VerifiedL1BatchesDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl VerifiedL1BatchesDal<'_, '_> {
+    /// Inserts a verified L1 batch header. The anchor of the batch is ignored; use [`Self::set_anchor()`] to set it.
+    pub async fn insert_verified_l1_batch(&mut self, batch: &VerifiedL1Batch) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                verified_l1_batches (
+                    l1_batch_number,
+                    root_hash,
+                    commitment,
+                    commit_tx_hash,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, $4, NOW(), NOW())
+            "#,
+            i64::from(batch.number.0),
+            batch.root_hash.as_bytes(),
+            batch.commitment.as_bytes(),
+            batch.commit_tx_hash.as_bytes()
+        )
+        .instrument("insert_verified_l1_batch")
+        .with_arg("batch", batch)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_verified_l1_batch(
+        &mut self,
+        number: L1BatchNumber,
+    ) -> DalResult<Option<VerifiedL1Batch>> {
+        let row = sqlx::query_as!(
+            StorageVerifiedL1Batch,
+            r#"
+            SELECT
+                l1_batch_number,
+                root_hash,
+                commitment,
+                commit_tx_hash,
+                ipfs_hash,
+                mintlayer_tx_id
+            FROM
+                verified_l1_batches
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(number.0)
+        )
+        .instrument("get_verified_l1_batch")
+        .with_arg("number", &number)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    pub async fn get_first_verified_l1_batch_number(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MIN(l1_batch_number) AS "number"
+            FROM
+                verified_l1_batches
+            "#
+        )
+        .instrument("get_first_verified_l1_batch_number")
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    pub async fn get_last_verified_l1_batch(&mut self) -> DalResult<Option<VerifiedL1Batch>> {
+        let row = sqlx::query_as!(
+            StorageVerifiedL1Batch,
+            r#"
+            SELECT
+                l1_batch_number,
+                root_hash,
+                commitment,
+                commit_tx_hash,
+                ipfs_hash,
+                mintlayer_tx_id
+            FROM
+                verified_l1_batches
+            ORDER BY
+                l1_batch_number DESC
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_last_verified_l1_batch")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    /// Returns the earliest verified L1 batch which doesn't have its Mintlayer anchor checked.
+    pub async fn get_first_unanchored_l1_batch(&mut self) -> DalResult<Option<VerifiedL1Batch>> {
+        let row = sqlx::query_as!(
+            StorageVerifiedL1Batch,
+            r#"
+            SELECT
+                l1_batch_number,
+                root_hash,
+                commitment,
+                commit_tx_hash,
+                ipfs_hash,
+                mintlayer_tx_id
+            FROM
+                verified_l1_batches
+            WHERE
+                mintlayer_tx_id IS NULL
+            ORDER BY
+                l1_batch_number
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_first_unanchored_l1_batch")
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    /// Sets the checked Mintlayer anchor for a verified L1 batch.
+    pub async fn set_anchor(
+        &mut self,
+        number: L1BatchNumber,
+        anchor: &en::L1BatchAnchor,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE verified_l1_batches
+            SET
+                ipfs_hash = $2,
+                mintlayer_tx_id = $3,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(number.0),
+            &anchor.ipfs_hash,
+            &anchor.mintlayer_tx_id
+        )
+        .instrument("set_verified_l1_batch_anchor")
+        .with_arg("number", &number)
+        .with_arg("anchor", anchor)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Deletes verified L1 batch headers after the specified batch. Returns the number of deleted headers.
+    pub async fn delete_verified_l1_batches_after(
+        &mut self,
+        last_retained_number: L1BatchNumber,
+    ) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM verified_l1_batches
+            WHERE
+                l1_batch_number > $1
+            "#,
+            i64::from(last_retained_number.0)
+        )
+        .instrument("delete_verified_l1_batches_after")
+        .with_arg("last_retained_number", &last_retained_number)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn mock_verified_batch(number: u32) -> VerifiedL1Batch {
+        VerifiedL1Batch {
+            number: L1BatchNumber(number),
+            root_hash: H256::repeat_byte(number as u8),
+            commitment: H256::repeat_byte(0x80 | number as u8),
+            commit_tx_hash: H256::from_low_u64_be(number.into()),
+            anchor: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn manipulating_verified_l1_batches() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.verified_l1_batches_dal();
+        assert_eq!(dal.get_last_verified_l1_batch().await.unwrap(), None);
+        assert_eq!(
+            dal.get_first_verified_l1_batch_number().await.unwrap(),
+            None
+        );
+
+        for number in 1..=3 {
+            dal.insert_verified_l1_batch(&mock_verified_batch(number))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            dal.get_first_verified_l1_batch_number().await.unwrap(),
+            Some(L1BatchNumber(1))
+        );
+        assert_eq!(
+            dal.get_last_verified_l1_batch().await.unwrap(),
+            Some(mock_verified_batch(3))
+        );
+        assert_eq!(
+            dal.get_first_unanchored_l1_batch().await.unwrap(),
+            Some(mock_verified_batch(1))
+        );
+
+        let anchor = en::L1BatchAnchor {
+            ipfs_hash: "cid".to_owned(),
+            mintlayer_tx_id: "tx".to_owned(),
+        };
+        dal.set_anchor(L1BatchNumber(1), &anchor).await.unwrap();
+        let batch = dal
+            .get_verified_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.anchor, Some(anchor));
+        assert_eq!(
+            dal.get_first_unanchored_l1_batch().await.unwrap(),
+            Some(mock_verified_batch(2))
+        );
+
+        let deleted_count = dal
+            .delete_verified_l1_batches_after(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(deleted_count, 2);
+        assert_eq!(
+            dal.get_last_verified_l1_batch()
+                .await
+                .unwrap()
+                .unwrap()
+                .number,
+            L1BatchNumber(1)
+        );
+        assert_eq!(dal.get_first_unanchored_l1_batch().await.unwrap(), None);
+    }
+}
@@ -82,6 +82,10 @@ mod tests {
                 latest_values_cache_size_mb: Some(256),
                 fee_history_limit: Some(100),
                 max_batch_request_size: Some(200),
+                max_simulated_bundle_size: Some(8),
+                simulate_bundle_timeout_ms: Some(5000),
+                max_submitted_bundle_size: Some(4),
+                submit_bundle_timeout_ms: Some(3000),
                 max_response_body_size_mb: Some(10),
                 max_response_body_size_overrides_mb: [
                     ("eth_call", NonZeroUsize::new(1).unwrap()),
@@ -100,6 +104,7 @@ mod tests {
                 ],
                 api_namespaces: Some(vec!["debug".to_string()]),
                 extended_api_tracing: true,
+                tee_finalized_tag_enabled: true,
             },
             prometheus: PrometheusConfig {
                 listener_port: 3312,
@@ -133,6 +138,7 @@ mod tests {
             API_WEB3_JSON_RPC_REQUEST_TIMEOUT=10
             API_WEB3_JSON_RPC_API_NAMESPACES=debug
             API_WEB3_JSON_RPC_EXTENDED_API_TRACING=true
+            API_WEB3_JSON_RPC_TEE_FINALIZED_TAG_ENABLED=true
             API_WEB3_JSON_RPC_ACCOUNT_PKS="0x0000000000000000000000000000000000000000000000000000000000000001,0x0000000000000000000000000000000000000000000000000000000000000002"
             API_WEB3_JSON_RPC_WHITELISTED_TOKENS_FOR_AA="0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002"
             API_WEB3_JSON_RPC_ESTIMATE_GAS_SCALE_FACTOR=1.0
@@ -144,6 +150,10 @@ mod tests {
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
+            API_WEB3_JSON_RPC_MAX_SIMULATED_BUNDLE_SIZE=8
+            API_WEB3_JSON_RPC_SIMULATE_BUNDLE_TIMEOUT_MS=5000
+            API_WEB3_JSON_RPC_MAX_SUBMITTED_BUNDLE_SIZE=4
+            API_WEB3_JSON_RPC_SUBMIT_BUNDLE_TIMEOUT_MS=3000
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
@@ -0,0 +1,35 @@
+use zksync_config::BlockArchiverConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for BlockArchiverConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("block_archiver", "BLOCK_ARCHIVER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env_block_archiver() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            BLOCK_ARCHIVER_POLL_INTERVAL_MS=5000
+            BLOCK_ARCHIVER_UPLOAD_TO_IPFS=true
+        "#;
+        lock.set_env(config);
+        let actual = BlockArchiverConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            BlockArchiverConfig {
+                poll_interval_ms: 5_000,
+                upload_to_ipfs: true,
+            }
+        );
+    }
+}
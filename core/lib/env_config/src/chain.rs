@@ -105,6 +105,11 @@ mod tests {
             l1_batch_commit_data_generator_mode,
             max_circuits_per_batch: 24100,
             protective_reads_persistence_enabled: true,
+            max_anchoring_payload_bytes: Some(500_000),
+            max_l2_blocks_per_batch: Some(100),
+            tx_allowlist_enabled: true,
+            tx_allowlist_reload_interval_ms: 5_000,
+            custom_seal_criteria: vec!["custom_criterion".to_owned()],
         }
     }
 
@@ -134,6 +139,11 @@ mod tests {
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
+            CHAIN_STATE_KEEPER_MAX_ANCHORING_PAYLOAD_BYTES="500000"
+            CHAIN_STATE_KEEPER_MAX_L2_BLOCKS_PER_BATCH="100"
+            CHAIN_STATE_KEEPER_TX_ALLOWLIST_RELOAD_INTERVAL_MS="5000"
+            CHAIN_STATE_KEEPER_CUSTOM_SEAL_CRITERIA="custom_criterion"
+            CHAIN_STATE_KEEPER_TX_ALLOWLIST_ENABLED="true"
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
             CHAIN_STATE_KEEPER_L1_BATCH_COMMIT_DATA_GENERATOR_MODE="{l1_batch_commit_data_generator_mode}"
@@ -169,6 +179,9 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            max_pending_txs_per_account: Some(64),
+            nonce_gap_ttl_sec: Some(600),
+            evict_low_fee_txs: true,
         }
     }
 
@@ -182,6 +195,9 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_NONCE_GAP_TTL_SEC="600"
+            CHAIN_MEMPOOL_EVICT_LOW_FEE_TXS="true"
         "#;
         lock.set_env(config);
 
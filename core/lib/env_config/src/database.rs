@@ -101,6 +101,8 @@ mod tests {
             DATABASE_MERKLE_TREE_MEMTABLE_CAPACITY_MB=512
             DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC=60
             DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER=50
+            DATABASE_MERKLE_TREE_NODE_CACHE_CAPACITY=1000
+            DATABASE_MERKLE_TREE_EXPORT_CHECKPOINTS=true
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_BLOCK_CACHE_CAPACITY_MB=64
             DATABASE_EXPERIMENTAL_STATE_KEEPER_DB_MAX_OPEN_FILES=100
         "#;
@@ -114,6 +116,8 @@ mod tests {
         assert_eq!(db_config.merkle_tree.max_l1_batches_per_iter, 50);
         assert_eq!(db_config.merkle_tree.memtable_capacity_mb, 512);
         assert_eq!(db_config.merkle_tree.stalled_writes_timeout_sec, 60);
+        assert_eq!(db_config.merkle_tree.node_cache_capacity(), 1_000);
+        assert!(db_config.merkle_tree.export_checkpoints);
         assert_eq!(
             db_config
                 .experimental
@@ -141,6 +145,8 @@ mod tests {
             "DATABASE_MERKLE_TREE_MEMTABLE_CAPACITY_MB",
             "DATABASE_MERKLE_TREE_STALLED_WRITES_TIMEOUT_SEC",
             "DATABASE_MERKLE_TREE_MAX_L1_BATCHES_PER_ITER",
+            "DATABASE_MERKLE_TREE_NODE_CACHE_CAPACITY",
+            "DATABASE_MERKLE_TREE_EXPORT_CHECKPOINTS",
         ]);
 
         let db_config = DBConfig::from_env().unwrap();
@@ -152,6 +158,8 @@ mod tests {
         assert_eq!(db_config.merkle_tree.block_cache_size_mb, 128);
         assert_eq!(db_config.merkle_tree.memtable_capacity_mb, 256);
         assert_eq!(db_config.merkle_tree.stalled_writes_timeout_sec, 30);
+        assert_eq!(db_config.merkle_tree.node_cache_capacity(), 16_384);
+        assert!(!db_config.merkle_tree.export_checkpoints);
         assert_eq!(
             db_config
                 .experimental
@@ -22,6 +22,7 @@ mod snapshots_creator;
 mod utils;
 
 mod base_token_adjuster;
+mod block_archiver;
 mod da_dispatcher;
 mod genesis;
 #[cfg(test)]
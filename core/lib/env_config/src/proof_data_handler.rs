@@ -10,6 +10,8 @@ impl FromEnv for ProofDataHandlerConfig {
 
 #[cfg(test)]
 mod tests {
+    use zksync_basic_types::H256;
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -20,6 +22,9 @@ mod tests {
             http_port: 3320,
             proof_generation_timeout_in_secs: 18000,
             tee_support: true,
+            tee_allowed_mr_enclaves: vec![H256::repeat_byte(0x11), H256::repeat_byte(0x22)],
+            tee_allowed_mr_signers: vec![H256::repeat_byte(0x33)],
+            tee_sgx_root_ca_path: Some("/etc/sgx/root_ca.pem".to_owned()),
         }
     }
 
@@ -29,6 +34,9 @@ mod tests {
             PROOF_DATA_HANDLER_PROOF_GENERATION_TIMEOUT_IN_SECS="18000"
             PROOF_DATA_HANDLER_HTTP_PORT="3320"
             PROOF_DATA_HANDLER_TEE_SUPPORT="true"
+            PROOF_DATA_HANDLER_TEE_ALLOWED_MR_ENCLAVES="0x1111111111111111111111111111111111111111111111111111111111111111,0x2222222222222222222222222222222222222222222222222222222222222222"
+            PROOF_DATA_HANDLER_TEE_ALLOWED_MR_SIGNERS="0x3333333333333333333333333333333333333333333333333333333333333333"
+            PROOF_DATA_HANDLER_TEE_SGX_ROOT_CA_PATH="/etc/sgx/root_ca.pem"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -2,7 +2,10 @@ use std::str::FromStr;
 
 use anyhow::Context;
 use zksync_basic_types::{Address, H256};
-use zksync_config::configs::wallets::{AddressWallet, EthSender, StateKeeper, Wallet, Wallets};
+use zksync_config::configs::wallets::{
+    AddressWallet, EthSender, KeystoreEthSender, KeystorePassword, KeystoreWallet, RemoteEthSender,
+    StateKeeper, Wallet, Wallets,
+};
 
 use crate::FromEnv;
 
@@ -32,6 +35,46 @@ impl FromEnv for Wallets {
             None
         };
 
+        let keystore_eth_sender = std::env::var("ETH_SENDER_SENDER_OPERATOR_KEYSTORE_PATH")
+            .ok()
+            .map(|operator_path| {
+                // The password is read from the file if it's specified, or from the env otherwise.
+                let password = match std::env::var("ETH_SENDER_SENDER_KEYSTORE_PASSWORD_FILE") {
+                    Ok(path) => KeystorePassword::File(path.into()),
+                    Err(_) => KeystorePassword::Env("ETH_SENDER_SENDER_KEYSTORE_PASSWORD".into()),
+                };
+                let keystore_wallet = |path: String| KeystoreWallet {
+                    address: None,
+                    path: path.into(),
+                    password: password.clone(),
+                };
+                KeystoreEthSender {
+                    operator: keystore_wallet(operator_path),
+                    blob_operator: std::env::var("ETH_SENDER_SENDER_OPERATOR_BLOBS_KEYSTORE_PATH")
+                        .ok()
+                        .map(keystore_wallet),
+                }
+            });
+
+        let remote_eth_sender = std::env::var("ETH_SENDER_SENDER_REMOTE_SIGNER_URL")
+            .ok()
+            .map(|signer_url| {
+                let operator = std::env::var("ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR")
+                    .context("Operator address is required for remote signer")?;
+                let blob_operator = std::env::var("ETH_SENDER_SENDER_OPERATOR_BLOBS_ETH_ADDR")
+                    .ok()
+                    .map(|addr| Address::from_str(&addr).context("Malformed blob operator address"))
+                    .transpose()?;
+                anyhow::Ok(RemoteEthSender {
+                    signer_url: signer_url.parse().context("Malformed remote signer URL")?,
+                    operator: AddressWallet::from_address(
+                        Address::from_str(&operator).context("Malformed operator address")?,
+                    ),
+                    blob_operator: blob_operator.map(AddressWallet::from_address),
+                })
+            })
+            .transpose()?;
+
         let fee_account = std::env::var("CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR").ok();
         let state_keeper = if let Some(fee_account) = fee_account {
             let fee_account = AddressWallet::from_address(Address::from_str(&fee_account)?);
@@ -42,6 +85,8 @@ impl FromEnv for Wallets {
 
         Ok(Self {
             eth_sender,
+            keystore_eth_sender,
+            remote_eth_sender,
             state_keeper,
         })
     }
@@ -2,9 +2,10 @@ use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
 use zksync_contracts::hyperchain_contract;
-use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, TransactionParameters};
+use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, RemoteSigner, TransactionParameters};
 use zksync_types::{
-    ethabi, web3, Address, K256PrivateKey, L1ChainId, EIP_4844_TX_TYPE, H160, U256,
+    ethabi, url::SensitiveUrl, web3, Address, K256PrivateKey, L1ChainId, EIP_4844_TX_TYPE, H160,
+    U256,
 };
 use zksync_web3_decl::client::{DynClient, L1};
 
@@ -25,8 +26,23 @@ impl PKSigningClient {
         l1_chain_id: L1ChainId,
         query_client: Box<DynClient<L1>>,
     ) -> Self {
-        let operator_address = operator_private_key.address();
-        let signer = PrivateKeySigner::new(operator_private_key);
+        Self::from_signer(
+            PrivateKeySigner::new(operator_private_key),
+            diamond_proxy_addr,
+            default_priority_fee_per_gas,
+            l1_chain_id,
+            query_client,
+        )
+    }
+
+    pub fn from_signer(
+        signer: PrivateKeySigner,
+        diamond_proxy_addr: Address,
+        default_priority_fee_per_gas: u64,
+        l1_chain_id: L1ChainId,
+        query_client: Box<DynClient<L1>>,
+    ) -> Self {
+        let operator_address = signer.address();
         tracing::info!("Operator address: {operator_address:?}");
         SigningClient::new(
             query_client,
@@ -40,6 +56,34 @@ impl PKSigningClient {
     }
 }
 
+/// HTTP-based Ethereum client, delegating transaction signing to a remote signing service.
+pub type RemoteSigningClient = SigningClient<RemoteSigner>;
+
+impl RemoteSigningClient {
+    pub fn new_raw(
+        signer_url: SensitiveUrl,
+        operator_address: Address,
+        diamond_proxy_addr: Address,
+        default_priority_fee_per_gas: u64,
+        l1_chain_id: L1ChainId,
+        query_client: Box<DynClient<L1>>,
+    ) -> Self {
+        tracing::info!(
+            "Operator address: {operator_address:?} (signed remotely at {signer_url:?})"
+        );
+        let signer = RemoteSigner::new(signer_url, operator_address);
+        SigningClient::new(
+            query_client,
+            hyperchain_contract(),
+            operator_address,
+            signer,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas.into(),
+            l1_chain_id,
+        )
+    }
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -6,6 +6,6 @@ mod mock;
 pub use zksync_web3_decl::client::{Client, DynClient, L1};
 
 pub use self::{
-    http::{PKSigningClient, SigningClient},
+    http::{PKSigningClient, RemoteSigningClient, SigningClient},
     mock::{MockEthereum, MockEthereumBuilder},
 };
@@ -0,0 +1,65 @@
+//! Loading signers from encrypted JSON keystores (Web3 secret storage).
+
+use std::path::Path;
+
+use zksync_types::{K256PrivateKey, H256};
+
+use crate::PrivateKeySigner;
+
+/// Errors that can occur when loading a signer from a keystore.
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error("failed decrypting keystore: {0}")]
+    Decryption(#[from] eth_keystore::KeystoreError),
+    #[error("keystore contains an invalid private key: {0}")]
+    InvalidKey(String),
+}
+
+impl PrivateKeySigner {
+    /// Decrypts the private key from the keystore at `path` and creates a signer for it.
+    pub fn from_keystore(
+        path: impl AsRef<Path>,
+        password: impl AsRef<[u8]>,
+    ) -> Result<Self, KeystoreError> {
+        let key_bytes = eth_keystore::decrypt_key(path, password)?;
+        if key_bytes.len() != 32 {
+            return Err(KeystoreError::InvalidKey(format!(
+                "expected 32 bytes, got {}",
+                key_bytes.len()
+            )));
+        }
+        let private_key = K256PrivateKey::from_bytes(H256::from_slice(&key_bytes))
+            .map_err(|err| KeystoreError::InvalidKey(err.to_string()))?;
+        Ok(Self::new(private_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loading_signer_from_keystore() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let private_key = H256::repeat_byte(0x42);
+        eth_keystore::encrypt_key(
+            dir.path(),
+            &mut rand::thread_rng(),
+            private_key.as_bytes(),
+            "password",
+            Some("operator.json"),
+        )
+        .unwrap();
+        let path = dir.path().join("operator.json");
+
+        let signer = PrivateKeySigner::from_keystore(&path, "password").unwrap();
+        let expected_address = K256PrivateKey::from_bytes(private_key).unwrap().address();
+        assert_eq!(signer.address(), expected_address);
+
+        let err = PrivateKeySigner::from_keystore(&path, "wrong").unwrap_err();
+        assert!(
+            matches!(err, KeystoreError::Decryption(_)),
+            "unexpected error: {err}"
+        );
+    }
+}
@@ -1,16 +1,23 @@
 use async_trait::async_trait;
 use zksync_types::{Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature};
 
-pub use crate::{pk_signer::PrivateKeySigner, raw_ethereum_tx::TransactionParameters};
+pub use crate::{
+    keystore::KeystoreError, pk_signer::PrivateKeySigner, raw_ethereum_tx::TransactionParameters,
+    remote_signer::RemoteSigner,
+};
 
+mod keystore;
 mod pk_signer;
 mod raw_ethereum_tx;
+mod remote_signer;
 
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum SignerError {
     #[error("Signing failed: {0}")]
     SigningFailed(String),
+    #[error("Operation is not supported by the signer: {0}")]
+    Unsupported(&'static str),
 }
 
 #[async_trait]
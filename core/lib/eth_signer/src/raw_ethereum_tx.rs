@@ -9,11 +9,11 @@
 //! In the case where it will be possible to use only the web3 library without copy-paste, the changes will be small and simple
 //! Link to @Deniallugo's PR to web3: https://github.com/tomusdrw/rust-web3/pull/630
 
-use rlp::RlpStream;
+use rlp::{Rlp, RlpStream};
 use zksync_types::{
     ethabi::Address,
     web3::{keccak256, AccessList, Signature, SignedTransaction},
-    K256PrivateKey, H256, U256, U64,
+    K256PrivateKey, PackedEthSignature, H256, U256, U64,
 };
 
 const LEGACY_TX_ID: u64 = 0;
@@ -70,7 +70,34 @@ pub struct Transaction {
     pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
+impl From<TransactionParameters> for Transaction {
+    fn from(params: TransactionParameters) -> Self {
+        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+        Self {
+            to: params.to,
+            nonce: params.nonce,
+            gas: params.gas,
+            gas_price: params.max_fee_per_gas,
+            value: params.value,
+            data: params.data,
+            transaction_type: params.transaction_type,
+            access_list: params.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: params.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: params.max_fee_per_blob_gas,
+            blob_versioned_hashes: params.blob_versioned_hashes,
+        }
+    }
+}
+
 impl Transaction {
+    fn is_legacy(&self) -> bool {
+        matches!(
+            self.transaction_type.map(|t| t.as_u64()),
+            Some(LEGACY_TX_ID) | None
+        )
+    }
+
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
         stream.append(&self.gas_price);
@@ -242,10 +269,7 @@ impl Transaction {
 
     /// Sign and return a raw signed transaction.
     pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
-            self.transaction_type.map(|t| t.as_u64()),
-            Some(LEGACY_TX_ID) | None
-        );
+        let adjust_v_value = self.is_legacy();
 
         let encoded = self.encode(chain_id, None);
         let message_hash = H256(keccak256(encoded.as_ref()));
@@ -268,4 +292,64 @@ impl Transaction {
             transaction_hash,
         }
     }
+
+    /// Checks that `raw_transaction` is exactly this transaction signed for `chain_id`
+    /// and returns the address of the signer.
+    ///
+    /// Used to validate transactions signed by an external party, which must not be trusted
+    /// to sign what was requested.
+    pub fn recover_signer(&self, chain_id: u64, raw_transaction: &[u8]) -> Result<Address, String> {
+        let is_legacy = self.is_legacy();
+        let payload = if is_legacy {
+            raw_transaction
+        } else {
+            raw_transaction.get(1..).ok_or("transaction is empty")?
+        };
+
+        let rlp = Rlp::new(payload);
+        let item_count = rlp.item_count().map_err(|err| err.to_string())?;
+        if item_count < 3 {
+            return Err(format!("transaction has too few RLP items: {item_count}"));
+        }
+        let decode_err = |err: rlp::DecoderError| format!("malformed signature: {err}");
+        let v: u64 = rlp.val_at(item_count - 3).map_err(decode_err)?;
+        let r: U256 = rlp.val_at(item_count - 2).map_err(decode_err)?;
+        let s: U256 = rlp.val_at(item_count - 1).map_err(decode_err)?;
+        let to_h256 = |value: U256| {
+            let mut bytes = [0_u8; 32];
+            value.to_big_endian(&mut bytes);
+            H256(bytes)
+        };
+        let signature = Signature {
+            v,
+            r: to_h256(r),
+            s: to_h256(s),
+        };
+
+        let expected = self.encode(chain_id, Some(&signature));
+        if expected != raw_transaction {
+            return Err("signed transaction doesn't match the requested one".to_owned());
+        }
+
+        let recovery_id = if is_legacy {
+            let (recovery_id, signed_chain_id) =
+                PackedEthSignature::unpack_v(v).map_err(|err| err.to_string())?;
+            if signed_chain_id != Some(chain_id) {
+                return Err(format!(
+                    "transaction is signed for chain {signed_chain_id:?}, expected {chain_id}"
+                ));
+            }
+            recovery_id
+        } else {
+            match v {
+                0 | 1 => v as u8,
+                _ => return Err(format!("invalid `v` value for typed transaction: {v}")),
+            }
+        };
+
+        let message_hash = H256(keccak256(&self.encode(chain_id, None)));
+        PackedEthSignature::from_rsv(&signature.r, &signature.s, recovery_id)
+            .signature_recover_signer(&message_hash)
+            .map_err(|err| err.to_string())
+    }
 }
@@ -0,0 +1,389 @@
+//! Signer delegating to a remote signing service (e.g., Web3Signer or a KMS / HSM-backed proxy)
+//! speaking the `eth_signTransaction` JSON-RPC protocol.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use zksync_types::{
+    url::SensitiveUrl,
+    web3::{AccessList, Bytes},
+    Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature, H256, U256, U64,
+};
+
+use crate::{
+    raw_ethereum_tx::{Transaction, TransactionParameters},
+    EthereumSigner, SignerError,
+};
+
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Transaction object accepted by `eth_signTransaction`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SignTransactionRequest {
+    pub from: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    pub gas: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    pub value: U256,
+    pub data: Bytes,
+    pub nonce: U256,
+    pub chain_id: U64,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<U64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+}
+
+impl SignTransactionRequest {
+    fn new(from: Address, params: &TransactionParameters) -> Self {
+        let is_legacy = params.transaction_type.map_or(true, |ty| ty.as_u64() <= 1);
+        // Same as for `PrivateKeySigner`, `max_fee_per_gas` is used as the gas price for legacy
+        // and access list transactions.
+        let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = if is_legacy {
+            (Some(params.max_fee_per_gas), None, None)
+        } else {
+            (
+                None,
+                Some(params.max_fee_per_gas),
+                Some(params.max_priority_fee_per_gas),
+            )
+        };
+
+        Self {
+            from,
+            to: params.to,
+            gas: params.gas,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            value: params.value,
+            data: params.data.clone().into(),
+            nonce: params.nonce,
+            chain_id: params.chain_id.into(),
+            transaction_type: params.transaction_type,
+            access_list: params.access_list.clone(),
+            max_fee_per_blob_gas: params.max_fee_per_blob_gas,
+            blob_versioned_hashes: params.blob_versioned_hashes.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a, P> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: P,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// Signer that keeps no key material locally and delegates signing to a remote service.
+///
+/// Transactions are signed via `eth_signTransaction`; all transaction types supported by
+/// [`TransactionParameters`] (including EIP-4844 blob transactions) are forwarded as is.
+/// Signed transactions returned by the service are decoded and checked to correspond
+/// to the requested transaction and the configured signer address before being returned.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: SensitiveUrl,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the `address` account managed by the service at `url`.
+    pub fn new(url: SensitiveUrl, address: Address) -> Self {
+        Self::with_timeout(url, address, DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Same as [`Self::new()`], but with a custom timeout for requests to the signing service.
+    pub fn with_timeout(url: SensitiveUrl, address: Address, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("failed creating HTTP client");
+        Self {
+            client,
+            url,
+            address,
+        }
+    }
+
+    async fn call<P: Serialize, T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<T, SignerError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        };
+        let response = self
+            .client
+            .post(self.url.expose_url().clone())
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| {
+                SignerError::SigningFailed(format!("`{method}` request failed: {err}"))
+            })?;
+        let response: JsonRpcResponse<T> = response.json().await.map_err(|err| {
+            SignerError::SigningFailed(format!("malformed `{method}` response: {err}"))
+        })?;
+
+        match response {
+            JsonRpcResponse {
+                error: Some(JsonRpcError { code, message }),
+                ..
+            } => Err(SignerError::SigningFailed(format!(
+                "`{method}` returned error {code}: {message}"
+            ))),
+            JsonRpcResponse {
+                result: Some(result),
+                ..
+            } => Ok(result),
+            _ => Err(SignerError::SigningFailed(format!(
+                "`{method}` response has neither result nor error"
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    /// EIP-712 signing is not used for L1 transactions and is not supported by this signer.
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        _domain: &Eip712Domain,
+        _typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        Err(SignerError::Unsupported("signing typed data"))
+    }
+
+    /// Signs the transaction using `eth_signTransaction` and returns the RLP-encoded signed transaction.
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let request = SignTransactionRequest::new(self.address, &raw_tx);
+        let signed: Bytes = self.call("eth_signTransaction", [request]).await?;
+
+        let chain_id = raw_tx.chain_id;
+        let signer = Transaction::from(raw_tx)
+            .recover_signer(chain_id, &signed.0)
+            .map_err(|err| {
+                SignerError::SigningFailed(format!(
+                    "remote signer returned invalid transaction: {err}"
+                ))
+            })?;
+        if signer != self.address {
+            return Err(SignerError::SigningFailed(format!(
+                "transaction is signed by {signer:?}, expected {:?}",
+                self.address
+            )));
+        }
+        Ok(signed.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::{extract::State, routing::post, Json, Router};
+    use serde_json::json;
+    use zksync_types::{K256PrivateKey, H160};
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    impl From<SignTransactionRequest> for TransactionParameters {
+        fn from(request: SignTransactionRequest) -> Self {
+            Self {
+                nonce: request.nonce,
+                to: request.to,
+                gas: request.gas,
+                gas_price: request.gas_price,
+                value: request.value,
+                data: request.data.0,
+                chain_id: request.chain_id.as_u64(),
+                transaction_type: request.transaction_type,
+                access_list: request.access_list,
+                max_fee_per_gas: request.max_fee_per_gas.or(request.gas_price).unwrap(),
+                max_priority_fee_per_gas: request.max_priority_fee_per_gas.unwrap_or_default(),
+                max_fee_per_blob_gas: request.max_fee_per_blob_gas,
+                blob_versioned_hashes: request.blob_versioned_hashes,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum MockBehavior {
+        Honest,
+        IgnoreSender,
+        TamperNonce,
+    }
+
+    /// Mock signing service holding a single key.
+    async fn spawn_mock_signer(signer: PrivateKeySigner, behavior: MockBehavior) -> SensitiveUrl {
+        async fn handle(
+            State((signer, behavior)): State<(PrivateKeySigner, MockBehavior)>,
+            Json(request): Json<serde_json::Value>,
+        ) -> Json<serde_json::Value> {
+            assert_eq!(request["method"], "eth_signTransaction");
+            let [tx]: [SignTransactionRequest; 1] =
+                serde_json::from_value(request["params"].clone()).unwrap();
+            let is_known_sender = tx.from == signer.get_address().await.unwrap();
+            if matches!(behavior, MockBehavior::Honest) && !is_known_sender {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": { "code": -32000, "message": "unknown account" },
+                }));
+            }
+
+            let mut tx = TransactionParameters::from(tx);
+            if matches!(behavior, MockBehavior::TamperNonce) {
+                tx.nonce += U256::one();
+            }
+            let signed = signer.sign_transaction(tx).await.unwrap();
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": Bytes(signed),
+            }))
+        }
+
+        let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/", post(handle))
+            .with_state((signer, behavior));
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{local_addr}/").parse().unwrap()
+    }
+
+    fn test_params(transaction_type: u64) -> TransactionParameters {
+        let mut params = TransactionParameters {
+            nonce: 3.into(),
+            to: Some(H160::repeat_byte(0x11)),
+            gas: 100_000.into(),
+            gas_price: None,
+            value: 1.into(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type: Some(transaction_type.into()),
+            access_list: None,
+            max_fee_per_gas: 20.into(),
+            max_priority_fee_per_gas: 2.into(),
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
+        };
+        if transaction_type == 3 {
+            params.max_fee_per_blob_gas = Some(5.into());
+            params.blob_versioned_hashes = Some(vec![H256::repeat_byte(1), H256::repeat_byte(2)]);
+        }
+        params
+    }
+
+    #[tokio::test]
+    async fn signing_transactions_remotely() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x42)).unwrap();
+        let local_signer = PrivateKeySigner::new(private_key.clone());
+        let url = spawn_mock_signer(local_signer.clone(), MockBehavior::Honest).await;
+        let signer = RemoteSigner::new(url, private_key.address());
+        assert_eq!(signer.get_address().await.unwrap(), private_key.address());
+
+        for transaction_type in 0..=3 {
+            let params = test_params(transaction_type);
+            let signed = signer.sign_transaction(params.clone()).await.unwrap();
+            let expected = local_signer.sign_transaction(params).await.unwrap();
+            assert_eq!(signed, expected, "transaction_type={transaction_type}");
+        }
+    }
+
+    #[tokio::test]
+    async fn transactions_signed_by_unexpected_key_are_rejected() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x42)).unwrap();
+        let url = spawn_mock_signer(
+            PrivateKeySigner::new(private_key),
+            MockBehavior::IgnoreSender,
+        )
+        .await;
+        let signer = RemoteSigner::new(url, Address::repeat_byte(0x01));
+
+        for transaction_type in [0, 3] {
+            let err = signer
+                .sign_transaction(test_params(transaction_type))
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("is signed by"),
+                "transaction_type={transaction_type}: {err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn tampered_transactions_are_rejected() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x42)).unwrap();
+        let url = spawn_mock_signer(
+            PrivateKeySigner::new(private_key.clone()),
+            MockBehavior::TamperNonce,
+        )
+        .await;
+        let signer = RemoteSigner::new(url, private_key.address());
+
+        for transaction_type in [0, 2, 3] {
+            let err = signer
+                .sign_transaction(test_params(transaction_type))
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("doesn't match"),
+                "transaction_type={transaction_type}: {err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_signer_errors_are_propagated() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(0x42)).unwrap();
+        let url = spawn_mock_signer(PrivateKeySigner::new(private_key), MockBehavior::Honest).await;
+        let signer = RemoteSigner::new(url, Address::repeat_byte(0x01));
+
+        let err = signer.sign_transaction(test_params(2)).await.unwrap_err();
+        assert!(err.to_string().contains("unknown account"), "{err}");
+    }
+}
@@ -1,7 +1,11 @@
-use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
+use std::{
+    collections::{hash_map, BTreeSet, HashMap, HashSet},
+    time::Duration,
+};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
+    helpers::unix_timestamp_ms, l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce,
+    PriorityOpId, Transaction, H256,
 };
 
 use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
@@ -10,6 +14,9 @@ use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// Hashes of individual transactions evicted from the mempool (e.g., because their initiator
+    /// exceeded the per-account limit). Unlike purged accounts, other transactions of the initiator are retained.
+    pub evicted_transactions: Vec<H256>,
 }
 
 #[derive(Debug)]
@@ -17,6 +24,34 @@ pub struct MempoolStats {
     pub l1_transaction_count: usize,
     pub l2_transaction_count: u64,
     pub l2_priority_queue_size: usize,
+    /// Total number of L2 transactions evicted because their initiator exceeded the per-account limit.
+    pub account_limit_evictions: u64,
+    /// Total number of L2 transactions evicted because they were stuck behind a nonce gap for too long.
+    pub nonce_gap_evictions: u64,
+    /// Total number of L2 transactions evicted because of low fees while the mempool was full.
+    pub low_fee_evictions: u64,
+}
+
+/// Limits and eviction policies applied by [`MempoolStore`] in addition to its capacity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MempoolLimits {
+    /// Maximum number of pending L2 transactions per initiator account. If an account exceeds this limit,
+    /// its transactions with the highest nonces are evicted.
+    pub max_pending_txs_per_account: Option<usize>,
+    /// Maximum time an account can have its transactions stuck behind a nonce gap. Once exceeded,
+    /// all transactions of the account are purged, regardless of the mempool size.
+    pub nonce_gap_ttl: Option<Duration>,
+    /// Whether to evict transactions with the lowest fees if the mempool stays full after accounts
+    /// with nonce gaps are purged.
+    pub evict_low_fee_txs: bool,
+}
+
+/// Cumulative eviction counters reported in [`MempoolStats`].
+#[derive(Debug, Default)]
+struct EvictionCounters {
+    account_limit: u64,
+    nonce_gap: u64,
+    low_fee: u64,
 }
 
 #[derive(Debug)]
@@ -30,9 +65,13 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    /// Transactions evicted since the last `get_mempool_info()` call
+    evicted_transactions: Vec<H256>,
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    limits: MempoolLimits,
+    evictions: EvictionCounters,
 }
 
 impl MempoolStore {
@@ -43,11 +82,20 @@ impl MempoolStore {
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
+            evicted_transactions: vec![],
             size: 0,
             capacity,
+            limits: MempoolLimits::default(),
+            evictions: EvictionCounters::default(),
         }
     }
 
+    /// Sets limits and eviction policies for this mempool.
+    pub fn with_limits(mut self, limits: MempoolLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -102,15 +150,14 @@ impl MempoolStore {
     ) {
         let account = transaction.initiator_account();
 
-        let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
+        let account_txs = match self.l2_transactions_per_account.entry(account) {
+            hash_map::Entry::Occupied(txs) => txs.into_mut(),
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
-                entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
+                entry.insert(AccountTransactions::new(account_nonce))
             }
         };
+        let metadata = account_txs.insert(transaction);
         if let Some(score) = metadata.previous_score {
             self.l2_priority_queue.remove(&score);
         }
@@ -120,6 +167,22 @@ impl MempoolStore {
         if metadata.is_new {
             self.size += 1;
         }
+
+        if let Some(max_pending_txs) = self.limits.max_pending_txs_per_account {
+            // Never evict the transaction with the current account nonce; otherwise, the account would get stuck.
+            while account_txs.len() > max_pending_txs.max(1) {
+                let Some(evicted_tx) = account_txs.evict_last() else {
+                    break;
+                };
+                tracing::debug!(
+                    "evicting L2 transaction {:?} of {account:?}: account exceeds the limit of {max_pending_txs} pending transactions",
+                    evicted_tx.hash()
+                );
+                self.evicted_transactions.push(evicted_tx.hash());
+                self.evictions.account_limit += 1;
+                self.size -= 1;
+            }
+        }
     }
 
     /// Returns `true` if there is a transaction in the mempool satisfying the filter.
@@ -209,6 +272,7 @@ impl MempoolStore {
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
             purged_accounts: self.gc(),
+            evicted_transactions: std::mem::take(&mut self.evicted_transactions),
         }
     }
 
@@ -217,10 +281,60 @@ impl MempoolStore {
             l1_transaction_count: self.l1_transactions.len(),
             l2_transaction_count: self.size,
             l2_priority_queue_size: self.l2_priority_queue.len(),
+            account_limit_evictions: self.evictions.account_limit,
+            nonce_gap_evictions: self.evictions.nonce_gap,
+            low_fee_evictions: self.evictions.low_fee,
         }
     }
 
     fn gc(&mut self) -> Vec<Address> {
+        let mut purged_accounts = self.purge_expired_nonce_gaps();
+        purged_accounts.extend(self.purge_accounts_with_nonce_gaps());
+        if self.limits.evict_low_fee_txs {
+            purged_accounts.extend(self.evict_low_fee_accounts());
+        }
+        purged_accounts
+    }
+
+    /// Purges accounts that have their transactions stuck behind a nonce gap for longer than the configured TTL.
+    fn purge_expired_nonce_gaps(&mut self) -> Vec<Address> {
+        let Some(nonce_gap_ttl) = self.limits.nonce_gap_ttl else {
+            return vec![];
+        };
+        let ttl_ms = u64::try_from(nonce_gap_ttl.as_millis()).unwrap_or(u64::MAX);
+        let now_ms = unix_timestamp_ms();
+        let ready_accounts: HashSet<_> = self
+            .l2_priority_queue
+            .iter()
+            .map(|pointer| pointer.account)
+            .collect();
+
+        let expired_accounts: Vec<_> = self
+            .l2_transactions_per_account
+            .iter()
+            .filter(|(address, txs)| {
+                !ready_accounts.contains(address)
+                    && txs
+                        .oldest_received_timestamp_ms()
+                        .is_some_and(|received_at_ms| {
+                            now_ms.saturating_sub(received_at_ms) > ttl_ms
+                        })
+            })
+            .map(|(address, _)| *address)
+            .collect();
+        for address in &expired_accounts {
+            let removed = self.l2_transactions_per_account.remove(address).unwrap();
+            tracing::debug!(
+                "purging {} L2 transactions of {address:?} stuck behind a nonce gap",
+                removed.len()
+            );
+            self.evictions.nonce_gap += removed.len() as u64;
+            self.size -= removed.len() as u64;
+        }
+        expired_accounts
+    }
+
+    fn purge_accounts_with_nonce_gaps(&mut self) -> Vec<Address> {
         if self.size >= self.capacity {
             let index: HashSet<_> = self
                 .l2_priority_queue
@@ -240,4 +354,43 @@ impl MempoolStore {
         }
         vec![]
     }
+
+    /// Evicts accounts which next transactions have the lowest fees until the mempool is below its capacity.
+    /// Should be called after accounts with nonce gaps are purged, so that all accounts are present in the priority queue.
+    fn evict_low_fee_accounts(&mut self) -> Vec<Address> {
+        if self.size < self.capacity {
+            return vec![];
+        }
+
+        let mut candidates: Vec<_> = self.l2_priority_queue.iter().cloned().collect();
+        // Evict accounts with the lowest fees first; for the same fee, evict the most recent transactions first.
+        candidates.sort_by(|a, b| {
+            a.fee_data
+                .max_fee_per_gas
+                .cmp(&b.fee_data.max_fee_per_gas)
+                .then_with(|| b.received_at_ms.cmp(&a.received_at_ms))
+        });
+
+        let mut evicted_accounts = vec![];
+        for score in candidates {
+            if self.size < self.capacity {
+                break;
+            }
+            self.l2_priority_queue.remove(&score);
+            let removed = self
+                .l2_transactions_per_account
+                .remove(&score.account)
+                .expect("mempool: dangling pointer in priority queue");
+            tracing::debug!(
+                "evicting {} L2 transactions of {:?} with max fee per gas {} due to mempool pressure",
+                removed.len(),
+                score.account,
+                score.fee_data.max_fee_per_gas
+            );
+            self.evictions.low_fee += removed.len() as u64;
+            self.size -= removed.len() as u64;
+            evicted_accounts.push(score.account);
+        }
+        evicted_accounts
+    }
 }
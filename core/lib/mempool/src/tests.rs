@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     iter::FromIterator,
+    time::Duration,
 };
 
 use zksync_types::{
@@ -12,7 +13,10 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::{MempoolLimits, MempoolStore},
+    types::L2TxFilter,
+};
 
 #[test]
 fn basic_flow() {
@@ -365,6 +369,149 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn per_account_limit() {
+    let limits = MempoolLimits {
+        max_pending_txs_per_account: Some(2),
+        ..MempoolLimits::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_limits(limits);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let evicted_tx = gen_l2_tx(account0, Nonce(3));
+    let evicted_tx_hash = evicted_tx.hash();
+    mempool.insert(
+        vec![
+            gen_l2_tx(account0, Nonce(1)),
+            gen_l2_tx(account0, Nonce(2)),
+            evicted_tx,
+            gen_l2_tx(account1, Nonce(0)),
+        ],
+        HashMap::new(),
+    );
+    // The transaction with the current nonce must not be evicted even if it arrives last.
+    mempool.insert(vec![gen_l2_tx(account0, Nonce(0))], HashMap::new());
+
+    let stats = mempool.stats();
+    assert_eq!(stats.l2_transaction_count, 3);
+    assert_eq!(stats.account_limit_evictions, 2);
+    let info = mempool.get_mempool_info();
+    assert_eq!(info.evicted_transactions.len(), 2);
+    assert!(info.evicted_transactions.contains(&evicted_tx_hash));
+    assert!(info.purged_accounts.is_empty());
+
+    let mut remaining_txs = HashSet::new();
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        remaining_txs.insert(view(Some(tx)));
+    }
+    assert_eq!(
+        remaining_txs,
+        HashSet::from_iter([(account0, 0), (account0, 1), (account1, 0)])
+    );
+    assert!(mempool.get_mempool_info().evicted_transactions.is_empty());
+}
+
+#[test]
+fn nonce_gap_ttl() {
+    let limits = MempoolLimits {
+        nonce_gap_ttl: Some(Duration::from_secs(60)),
+        ..MempoolLimits::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_limits(limits);
+    let stuck_account = Address::random();
+    let recent_account = Address::random();
+    let ready_account = Address::random();
+    let long_ago = unix_timestamp_ms() - 120_000;
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_timestamp(stuck_account, Nonce(1), long_ago),
+            gen_l2_tx_with_timestamp(stuck_account, Nonce(2), long_ago),
+            gen_l2_tx(recent_account, Nonce(1)),
+            gen_l2_tx_with_timestamp(ready_account, Nonce(0), long_ago),
+        ],
+        HashMap::new(),
+    );
+
+    // Only the account stuck behind a nonce gap for too long is purged, although the mempool is far from full.
+    let info = mempool.get_mempool_info();
+    assert_eq!(info.purged_accounts, vec![stuck_account]);
+    let stats = mempool.stats();
+    assert_eq!(stats.l2_transaction_count, 2);
+    assert_eq!(stats.nonce_gap_evictions, 2);
+
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (ready_account, 0)
+    );
+    mempool.insert(vec![gen_l2_tx(recent_account, Nonce(0))], HashMap::new());
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (recent_account, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (recent_account, 1)
+    );
+}
+
+#[test]
+fn low_fee_eviction() {
+    let limits = MempoolLimits {
+        evict_low_fee_txs: true,
+        ..MempoolLimits::default()
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 4).with_limits(limits);
+    let cheap_account = Address::random();
+    let average_account = Address::random();
+    let expensive_account = Address::random();
+    let gapped_account = Address::random();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(cheap_account, Nonce(0), 1),
+            gen_l2_tx_with_fee(cheap_account, Nonce(1), 1),
+            gen_l2_tx_with_fee(average_account, Nonce(0), 10),
+            gen_l2_tx_with_fee(expensive_account, Nonce(0), 100),
+            gen_l2_tx_with_fee(gapped_account, Nonce(1), 1_000),
+        ],
+        HashMap::new(),
+    );
+
+    // Purging the gapped account is not enough to get below the capacity, so the cheapest account is evicted as well.
+    let info = mempool.get_mempool_info();
+    assert_eq!(
+        HashSet::<_>::from_iter(info.purged_accounts),
+        HashSet::<_>::from_iter([gapped_account, cheap_account]),
+    );
+    let stats = mempool.stats();
+    assert_eq!(stats.l2_transaction_count, 2);
+    assert_eq!(stats.low_fee_evictions, 2);
+
+    let mut remaining_accounts = HashSet::new();
+    while let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) {
+        remaining_accounts.insert(tx.initiator_account());
+    }
+    assert_eq!(
+        remaining_accounts,
+        HashSet::from_iter([average_account, expensive_account])
+    );
+}
+
+#[test]
+fn low_fee_eviction_is_disabled_by_default() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 2);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account0, Nonce(0), 1),
+            gen_l2_tx_with_fee(account1, Nonce(0), 100),
+        ],
+        HashMap::new(),
+    );
+    assert!(mempool.get_mempool_info().purged_accounts.is_empty());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -384,6 +531,15 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_fee(address: Address, nonce: Nonce, max_fee_per_gas: u64) -> Transaction {
+    let mut tx = gen_l2_tx(address, nonce);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => data.fee.max_fee_per_gas = max_fee_per_gas.into(),
+        _ => unreachable!(),
+    }
+    tx
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
@@ -70,6 +70,23 @@ impl AccountTransactions {
             .map(Self::score_for_transaction)
     }
 
+    /// Removes the transaction with the highest nonce, unless it's the next transaction to be executed.
+    pub fn evict_last(&mut self) -> Option<L2Tx> {
+        let max_nonce = *self.transactions.keys().max()?;
+        if max_nonce <= self.nonce {
+            return None;
+        }
+        self.transactions.remove(&max_nonce)
+    }
+
+    /// Returns the earliest receipt timestamp among the account transactions.
+    pub fn oldest_received_timestamp_ms(&self) -> Option<u64> {
+        self.transactions
+            .values()
+            .map(|tx| tx.received_timestamp_ms)
+            .min()
+    }
+
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
@@ -1,5 +1,8 @@
 //! Tying the Merkle tree implementation to the problem domain.
 
+use std::path::Path;
+
+use anyhow::Context as _;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use zksync_crypto::hasher::blake2::Blake2Hasher;
 use zksync_prover_interface::inputs::{StorageLogMetadata, WitnessInputMerklePaths};
@@ -142,7 +145,7 @@ impl ZkSyncTree {
     /// Returns a readonly handle to the tree. The handle **does not** see uncommitted changes to the tree,
     /// only ones flushed to RocksDB.
     pub fn reader(&self) -> ZkSyncTreeReader {
-        let db = self.tree.db.inner().clone();
+        let db = self.tree.db.inner().cached_reader();
         ZkSyncTreeReader(MerkleTree::new_unchecked(db))
     }
 
@@ -380,6 +383,17 @@ impl ZkSyncTree {
     }
 }
 
+/// Information about a tree checkpoint created by [`ZkSyncTreeReader::create_checkpoint()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeCheckpointInfo {
+    /// L1 batch number of the latest tree version in the checkpoint.
+    pub l1_batch_number: L1BatchNumber,
+    /// Root hash of the tree after the L1 batch.
+    pub root_hash: ValueHash,
+    /// Number of leaves in the tree after the L1 batch.
+    pub leaf_count: u64,
+}
+
 /// Readonly handle to a [`ZkSyncTree`].
 #[derive(Debug)]
 pub struct ZkSyncTreeReader(MerkleTree<RocksDBWrapper>);
@@ -392,13 +406,14 @@ impl Clone for ZkSyncTreeReader {
 }
 
 impl ZkSyncTreeReader {
-    /// Creates a tree reader based on the provided database.
+    /// Creates a tree reader based on the provided database. The reader uses the internal node cache
+    /// of the database, if it is enabled.
     ///
     /// # Errors
     ///
     /// Errors if sanity checks fail.
     pub fn new(db: RocksDBWrapper) -> anyhow::Result<Self> {
-        MerkleTree::new(db).map(Self)
+        MerkleTree::new(db.cached_reader()).map(Self)
     }
 
     /// Returns a reference to the database this.
@@ -456,4 +471,43 @@ impl ZkSyncTreeReader {
         let version = l1_batch_number.0.into();
         self.0.verify_consistency(version, true)
     }
+
+    /// Creates a self-contained checkpoint of the tree at the specified `path`, which must not exist.
+    /// The checkpoint is truncated so that its latest version corresponds to `l1_batch_number`; it can be opened
+    /// as an ordinary tree RocksDB instance in order to bootstrap another tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree doesn't contain the requested L1 batch, or on RocksDB I/O errors.
+    pub fn create_checkpoint(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        path: &Path,
+    ) -> anyhow::Result<TreeCheckpointInfo> {
+        let (root_hash, leaf_count) = self
+            .root_info(l1_batch_number)
+            .with_context(|| format!("tree doesn't contain L1 batch #{l1_batch_number}"))?;
+        self.db().create_checkpoint(path).with_context(|| {
+            format!("failed creating RocksDB checkpoint at `{}`", path.display())
+        })?;
+
+        let db = RocksDBWrapper::new(path).context("failed opening RocksDB checkpoint")?;
+        let mut checkpoint = ZkSyncTree::new_lightweight(db)?;
+        if checkpoint.next_l1_batch_number() > l1_batch_number + 1 {
+            checkpoint.roll_back_logs(l1_batch_number)?;
+            checkpoint.save()?;
+        }
+        // The tree may have been reverted after reading the root info; check that the checkpoint is still valid.
+        let checkpoint_root_hash = checkpoint.root_hash();
+        anyhow::ensure!(
+            checkpoint.next_l1_batch_number() == l1_batch_number + 1 && checkpoint_root_hash == root_hash,
+            "tree was modified while creating checkpoint for L1 batch #{l1_batch_number}: expected root hash {root_hash:?}, \
+             got {checkpoint_root_hash:?}"
+        );
+        Ok(TreeCheckpointInfo {
+            l1_batch_number,
+            root_hash,
+            leaf_count,
+        })
+    }
 }
@@ -0,0 +1,171 @@
+//! LRU cache for internal tree nodes.
+
+use std::{
+    fmt,
+    num::NonZeroUsize,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use lru::LruCache;
+
+use crate::types::{InternalNode, NodeKey};
+
+struct CacheInner {
+    nodes: LruCache<NodeKey, InternalNode>,
+    /// Upper bound on versions of the cached nodes. Allows to skip scanning the cache on invalidation
+    /// in the common case when a new tree version is being written.
+    max_version: Option<u64>,
+    /// Incremented on each invalidation. Nodes loaded from the DB before an invalidation may be outdated,
+    /// so they are not inserted into the cache (see [`InternalNodeLruCache::insert()`]).
+    generation: u64,
+}
+
+impl CacheInner {
+    fn invalidate_from(&mut self, first_version: u64) {
+        self.generation += 1;
+        if self
+            .max_version
+            .map_or(true, |version| version < first_version)
+        {
+            return;
+        }
+
+        let stale_keys: Vec<_> = self
+            .nodes
+            .iter()
+            .filter_map(|(key, _)| (key.version >= first_version).then_some(*key))
+            .collect();
+        for key in &stale_keys {
+            self.nodes.pop(key);
+        }
+        self.max_version = first_version.checked_sub(1);
+    }
+}
+
+/// LRU cache for hot internal nodes (e.g., nodes close to the tree root), which are loaded over and over
+/// when creating Merkle proofs.
+///
+/// Nodes are never modified once persisted, so the cache only needs to be invalidated when tree versions
+/// are overwritten (i.e., after the tree is reverted or during recovery). Pruned nodes can remain in the cache,
+/// but they are unreachable since the corresponding tree roots are removed; they will be eventually evicted.
+pub(crate) struct InternalNodeLruCache {
+    inner: Mutex<CacheInner>,
+}
+
+impl fmt::Debug for InternalNodeLruCache {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.lock();
+        formatter
+            .debug_struct("InternalNodeLruCache")
+            .field("len", &inner.nodes.len())
+            .field("capacity", &inner.nodes.cap())
+            .field("max_version", &inner.max_version)
+            .finish()
+    }
+}
+
+impl InternalNodeLruCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            inner: Mutex::new(CacheInner {
+                nodes: LruCache::new(capacity),
+                max_version: None,
+                generation: 0,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheInner> {
+        // The cache cannot be left in an inconsistent state, so it's safe to ignore poisoning.
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[cfg(test)]
+    pub fn node_count(&self) -> usize {
+        self.lock().nodes.len()
+    }
+
+    pub fn get(&self, key: &NodeKey) -> Option<InternalNode> {
+        self.lock().nodes.get(key).cloned()
+    }
+
+    /// Returns the current cache generation. Must be obtained before loading nodes from the DB
+    /// and then passed to [`Self::insert()`].
+    pub fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    /// Inserts a node loaded from the DB. The node is skipped if the cache was invalidated after `generation`
+    /// was obtained, since the node may be outdated in this case.
+    pub fn insert(&self, key: NodeKey, node: InternalNode, generation: u64) {
+        let mut inner = self.lock();
+        if inner.generation != generation {
+            return;
+        }
+        inner.max_version = Some(
+            inner
+                .max_version
+                .map_or(key.version, |v| v.max(key.version)),
+        );
+        inner.nodes.put(key, node);
+    }
+
+    /// Removes all cached nodes with the version greater or equal to `first_version`.
+    pub fn invalidate_from(&self, first_version: u64) {
+        self.lock().invalidate_from(first_version);
+    }
+
+    /// Same as [`Self::invalidate_from()`], but additionally holds the cache lock while `write_nodes`
+    /// persists the overwritten nodes. This ensures that concurrent readers cannot observe outdated cached nodes,
+    /// or re-populate the cache with the outdated nodes loaded from the DB.
+    pub fn invalidate_from_while<R>(
+        &self,
+        first_version: u64,
+        write_nodes: impl FnOnce() -> R,
+    ) -> R {
+        let mut inner = self.lock();
+        inner.invalidate_from(first_version);
+        write_nodes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Key, Nibbles};
+
+    #[test]
+    fn cache_invalidation() {
+        let cache = InternalNodeLruCache::new(NonZeroUsize::new(10).unwrap());
+        let keys: Vec<_> = (0..5)
+            .map(|version| Nibbles::new(&Key::from(123), 1).with_version(version))
+            .collect();
+        for &key in &keys {
+            cache.insert(key, InternalNode::default(), cache.generation());
+        }
+        assert!(cache.get(&keys[4]).is_some());
+
+        cache.invalidate_from(10);
+        assert!(keys.iter().all(|key| cache.get(key).is_some()));
+
+        cache.invalidate_from(3);
+        assert!(cache.get(&keys[2]).is_some());
+        assert!(cache.get(&keys[3]).is_none());
+        assert!(cache.get(&keys[4]).is_none());
+        assert_eq!(cache.lock().max_version, Some(2));
+    }
+
+    #[test]
+    fn outdated_nodes_are_not_inserted_after_invalidation() {
+        let cache = InternalNodeLruCache::new(NonZeroUsize::new(10).unwrap());
+        let key = Nibbles::new(&Key::from(123), 1).with_version(3);
+        let generation = cache.generation();
+        let write_output = cache.invalidate_from_while(3, || 42);
+        assert_eq!(write_output, 42);
+
+        cache.insert(key, InternalNode::default(), generation);
+        assert!(cache.get(&key).is_none());
+        cache.insert(key, InternalNode::default(), cache.generation());
+        assert!(cache.get(&key).is_some());
+    }
+}
@@ -19,6 +19,7 @@ use crate::{
     },
 };
 
+mod cache;
 mod database;
 mod parallel;
 mod patch;
@@ -1,6 +1,6 @@
 //! RocksDB implementation of [`Database`].
 
-use std::{any::Any, cell::RefCell, path::Path, sync::Arc};
+use std::{any::Any, cell::RefCell, num::NonZeroUsize, path::Path, sync::Arc};
 
 use anyhow::Context as _;
 use rayon::prelude::*;
@@ -16,6 +16,7 @@ use crate::{
     errors::{DeserializeError, ErrorContext},
     metrics::ApplyPatchStats,
     storage::{
+        cache::InternalNodeLruCache,
         database::{PruneDatabase, PrunePatchSet},
         Database, NodeKeys, PatchSet,
     },
@@ -72,6 +73,10 @@ pub struct RocksDBWrapper {
     // struct (as opposed to `thread_local!` vars).
     profiled_operation: Arc<ThreadLocal<LocalProfiledOperation>>,
     multi_get_chunk_size: usize,
+    // Shared among clones, so that cache invalidation by the tree writer is visible to readers.
+    node_cache: Option<Arc<InternalNodeLruCache>>,
+    // Whether nodes are read through `node_cache`. Only set for readers; the tree writer only invalidates the cache.
+    reads_node_cache: bool,
 }
 
 impl RocksDBWrapper {
@@ -106,6 +111,31 @@ impl RocksDBWrapper {
         self.multi_get_chunk_size = chunk_size;
     }
 
+    /// Creates an LRU cache for internal tree nodes with the specified capacity (measured in the number of nodes).
+    /// Setting capacity to 0 disables the cache. The cache is shared among all clones of this wrapper
+    /// created after this call, but it is only used for reads by wrappers returned from [`Self::cached_reader()`];
+    /// this wrapper itself (e.g., used by the tree writer) only invalidates the cache when tree versions are truncated.
+    ///
+    /// The cache is most useful for readers creating Merkle proofs, since nodes close to the tree root
+    /// are loaded for each proof. Each cached node takes order of 1 KiB of RAM.
+    pub fn set_node_cache_capacity(&mut self, capacity: usize) {
+        self.node_cache = NonZeroUsize::new(capacity)
+            .map(|capacity| Arc::new(InternalNodeLruCache::new(capacity)));
+    }
+
+    /// Returns a clone of this wrapper that reads internal nodes through the node cache (if the cache
+    /// is enabled using [`Self::set_node_cache_capacity()`]). Should only be used for tree readers.
+    pub fn cached_reader(&self) -> Self {
+        Self {
+            reads_node_cache: true,
+            ..self.clone()
+        }
+    }
+
+    fn node_cache_for_reads(&self) -> Option<&InternalNodeLruCache> {
+        self.node_cache.as_deref().filter(|_| self.reads_node_cache)
+    }
+
     fn raw_node(&self, key: &[u8]) -> Option<Vec<u8>> {
         self.db
             .get_cf(MerkleTreeColumnFamily::Tree, key)
@@ -157,6 +187,67 @@ impl RocksDBWrapper {
         })
     }
 
+    fn tree_nodes_uncached(&self, keys: &NodeKeys) -> Vec<Option<Node>> {
+        let raw_nodes = self.raw_nodes(keys).into_iter().zip(keys);
+
+        let nodes = raw_nodes.map(|(maybe_node, (key, is_leaf))| {
+            maybe_node
+                .map(|raw_node| Self::deserialize_node(&raw_node, key, *is_leaf))
+                .transpose()
+        });
+        nodes
+            .collect::<Result<_, _>>()
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Creates a RocksDB checkpoint of the tree at the specified `path`. See [`RocksDB::create_checkpoint()`]
+    /// for details.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.db.create_checkpoint(path)
+    }
+
+    /// Synchronizes the wrapper with the primary RocksDB instance, provided that the wrapped DB is opened
+    /// in the secondary mode (see [`RocksDB::with_options_as_secondary()`]).
+    ///
+    /// If the primary instance has reverted tree versions since the last sync, cached internal nodes
+    /// for these versions are invalidated. Since reads running concurrently with the sync may observe
+    /// an inconsistent state or re-populate the cache with outdated nodes, the caller must ensure
+    /// that no reads are in flight.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB errors, e.g. if the wrapped DB is not a secondary instance.
+    pub fn try_catch_up_with_primary(&self) -> anyhow::Result<()> {
+        let prev_version_count = self.manifest().map_or(0, |manifest| manifest.version_count);
+        let prev_latest_root = prev_version_count
+            .checked_sub(1)
+            .and_then(|version| self.raw_node(&NodeKey::empty(version).to_db_key()));
+
+        self.db
+            .try_catch_up_with_primary()
+            .context("failed catching up with primary RocksDB instance")?;
+
+        let Some(cache) = &self.node_cache else {
+            return Ok(());
+        };
+        let version_count = self.manifest().map_or(0, |manifest| manifest.version_count);
+        if version_count < prev_version_count {
+            cache.invalidate_from(version_count);
+        } else if let Some(prev_latest_root) = prev_latest_root {
+            // The tree may have been reverted and then extended past the previously observed version.
+            // We don't know which versions were overwritten in this case, so the entire cache is invalidated.
+            let latest_root_key = NodeKey::empty(prev_version_count - 1).to_db_key();
+            if self.raw_node(&latest_root_key).as_ref() != Some(&prev_latest_root) {
+                cache.invalidate_from(0);
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -169,6 +260,8 @@ impl From<RocksDB<MerkleTreeColumnFamily>> for RocksDBWrapper {
             db,
             profiled_operation: Arc::new(ThreadLocal::new()),
             multi_get_chunk_size: usize::MAX,
+            node_cache: None,
+            reads_node_cache: false,
         }
     }
 }
@@ -197,23 +290,59 @@ impl Database for RocksDBWrapper {
         key: &NodeKey,
         is_leaf: bool,
     ) -> Result<Option<Node>, DeserializeError> {
+        let cache = self.node_cache_for_reads().filter(|_| !is_leaf);
+        if let Some(node) = cache.and_then(|cache| cache.get(key)) {
+            return Ok(Some(Node::Internal(node)));
+        }
+        let cache_generation = cache.map(InternalNodeLruCache::generation);
+
         let Some(raw_node) = self.raw_node(&key.to_db_key()) else {
             return Ok(None);
         };
-        Self::deserialize_node(&raw_node, key, is_leaf).map(Some)
+        let node = Self::deserialize_node(&raw_node, key, is_leaf)?;
+        if let (Some(cache), Some(generation), Node::Internal(node)) =
+            (cache, cache_generation, &node)
+        {
+            cache.insert(*key, node.clone(), generation);
+        }
+        Ok(Some(node))
     }
 
     fn tree_nodes(&self, keys: &NodeKeys) -> Vec<Option<Node>> {
-        let raw_nodes = self.raw_nodes(keys).into_iter().zip(keys);
+        let Some(cache) = self.node_cache_for_reads() else {
+            return self.tree_nodes_uncached(keys);
+        };
 
-        let nodes = raw_nodes.map(|(maybe_node, (key, is_leaf))| {
-            maybe_node
-                .map(|raw_node| Self::deserialize_node(&raw_node, key, *is_leaf))
-                .transpose()
-        });
+        let mut nodes: Vec<_> = keys
+            .iter()
+            .map(|(key, is_leaf)| {
+                let cached = (!is_leaf).then(|| cache.get(key)).flatten();
+                cached.map(Node::Internal)
+            })
+            .collect();
+        let (missing_indices, missing_keys): (Vec<_>, Vec<_>) = keys
+            .iter()
+            .zip(&nodes)
+            .enumerate()
+            .filter_map(|(i, (key, node))| node.is_none().then_some((i, *key)))
+            .unzip();
+        if missing_keys.is_empty() {
+            return nodes;
+        }
+
+        let cache_generation = cache.generation();
+        let loaded_nodes = self.tree_nodes_uncached(&missing_keys);
+        for ((i, (key, _)), node) in missing_indices
+            .into_iter()
+            .zip(&missing_keys)
+            .zip(loaded_nodes)
+        {
+            if let Some(Node::Internal(node)) = &node {
+                cache.insert(*key, node.clone(), cache_generation);
+            }
+            nodes[i] = node;
+        }
         nodes
-            .collect::<Result<_, _>>()
-            .unwrap_or_else(|err| panic!("{err}"))
     }
 
     fn start_profiling(&self, operation: ProfiledTreeOperation) -> Box<dyn Any> {
@@ -246,6 +375,14 @@ impl Database for RocksDBWrapper {
         // ^ 128 looks somewhat reasonable as node capacity
 
         let mut metrics = ApplyPatchStats::new(patch.copied_hashes_count());
+        // All versions in the patch are (re)written from scratch, and versions after the manifest version count
+        // are truncated, so cached nodes for them may be outdated.
+        let first_invalidated_version = patch
+            .patches_by_version
+            .keys()
+            .copied()
+            .chain([patch.manifest.version_count])
+            .min();
 
         patch.manifest.serialize(&mut node_bytes);
         write_batch.put_cf(tree_cf, Self::MANIFEST_KEY, &node_bytes);
@@ -287,9 +424,17 @@ impl Database for RocksDBWrapper {
             write_batch.put_cf(stale_keys_cf, &replaced_key.to_db_key(), &[]);
         }
 
-        self.db
-            .write(write_batch)
-            .context("Failed writing a batch to RocksDB")?;
+        let write = || {
+            self.db
+                .write(write_batch)
+                .context("Failed writing a batch to RocksDB")
+        };
+        if let (Some(cache), Some(version)) = (&self.node_cache, first_invalidated_version) {
+            // Invalidation must be atomic with the write; otherwise, readers may cache outdated nodes.
+            cache.invalidate_from_while(version, write)?;
+        } else {
+            write()?;
+        }
         metrics.report();
         Ok(())
     }
@@ -343,7 +488,11 @@ mod tests {
     use tempfile::TempDir;
 
     use super::*;
-    use crate::storage::tests::{create_patch, generate_nodes};
+    use crate::{
+        storage::tests::{create_patch, generate_nodes},
+        types::{Key, TreeEntry, ValueHash},
+        MerkleTree,
+    };
 
     #[test]
     fn garbage_is_removed_on_db_reverts() {
@@ -389,6 +538,52 @@ mod tests {
         assert_contains_exactly_keys(&db, &expected_keys);
     }
 
+    #[test]
+    fn node_cache_is_invalidated_on_reverts() {
+        let dir = TempDir::new().expect("failed creating temporary dir for RocksDB");
+        let mut db = RocksDBWrapper::new(dir.path()).unwrap();
+        db.set_node_cache_capacity(1_000);
+        let reader = MerkleTree::new_unchecked(db.cached_reader());
+        let mut tree = MerkleTree::new(db.clone()).unwrap();
+
+        let keys: Vec<_> = (0_u64..100)
+            .map(|i| Key::from(i) << 248 | Key::from(i))
+            .collect();
+        let entries = |value_offset: u64| -> Vec<_> {
+            keys.iter()
+                .zip(1..)
+                .map(|(&key, i)| {
+                    TreeEntry::new(key, i, ValueHash::from_low_u64_be(i + value_offset))
+                })
+                .collect()
+        };
+        tree.extend(entries(0)).unwrap();
+        // The writer doesn't populate the cache.
+        for entry in tree.entries_with_proofs(0, &keys).unwrap() {
+            entry
+                .verify(&tree.hasher, tree.root_hash(0).unwrap())
+                .unwrap();
+        }
+        assert_eq!(db.node_cache.as_deref().unwrap().node_count(), 0);
+
+        let root_hash = reader.root_hash(0).unwrap();
+        // Second iteration uses cached internal nodes.
+        for _ in 0..2 {
+            for entry in reader.entries_with_proofs(0, &keys).unwrap() {
+                entry.verify(&reader.hasher, root_hash).unwrap();
+            }
+        }
+        assert!(db.node_cache.as_deref().unwrap().node_count() > 0);
+
+        tree.truncate_recent_versions(0).unwrap();
+        tree.extend(entries(1_000)).unwrap();
+        let new_root_hash = reader.root_hash(0).unwrap();
+        assert_ne!(new_root_hash, root_hash);
+        for entry in reader.entries_with_proofs(0, &keys).unwrap() {
+            entry.verify(&reader.hasher, new_root_hash).unwrap();
+        }
+    }
+
     fn assert_contains_exactly_keys(db: &RocksDBWrapper, expected_keys: &HashSet<NodeKey>) {
         let cf = MerkleTreeColumnFamily::Tree;
         let actual_keys: HashSet<_> = db
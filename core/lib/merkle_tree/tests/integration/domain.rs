@@ -270,6 +270,44 @@ fn revert_blocks() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(3));
 }
 
+#[test]
+fn creating_checkpoint() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let db = RocksDB::new(&temp_dir.path().join("db")).unwrap();
+    let logs = gen_storage_logs();
+    let mut tree = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    let mut root_hashes = vec![];
+    for chunk in logs.chunks(20) {
+        tree.process_l1_batch(chunk).unwrap();
+        root_hashes.push(tree.root_hash());
+    }
+    tree.save().unwrap();
+
+    let checkpoint_path = temp_dir.path().join("checkpoint");
+    let info = tree
+        .reader()
+        .create_checkpoint(L1BatchNumber(2), &checkpoint_path)
+        .unwrap();
+    assert_eq!(info.l1_batch_number, L1BatchNumber(2));
+    assert_eq!(info.root_hash, root_hashes[2]);
+    let (_, expected_leaf_count) = tree.reader().root_info(L1BatchNumber(2)).unwrap();
+    assert_eq!(info.leaf_count, expected_leaf_count);
+    // The original tree must not be affected.
+    assert_eq!(tree.root_hash(), *root_hashes.last().unwrap());
+
+    let db = RocksDB::new(&checkpoint_path).unwrap();
+    let checkpoint = ZkSyncTree::new_lightweight(db.into()).unwrap();
+    assert_eq!(checkpoint.next_l1_batch_number(), L1BatchNumber(3));
+    assert_eq!(checkpoint.root_hash(), root_hashes[2]);
+    checkpoint.verify_consistency(L1BatchNumber(2)).unwrap();
+
+    let err = tree
+        .reader()
+        .create_checkpoint(L1BatchNumber(100), &temp_dir.path().join("other"))
+        .unwrap_err();
+    assert!(err.to_string().contains("L1 batch #100"), "{err}");
+}
+
 #[test]
 fn reset_tree() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
@@ -8,4 +8,4 @@ pub mod validator;
 pub use call_tracer::CallTracer;
 pub use multivm_dispatcher::TracerDispatcher;
 pub use prestate_tracer::PrestateTracer;
-pub use storage_invocation::StorageInvocations;
+pub use storage_invocation::{StorageAccessTracer, StorageInvocations};
@@ -1,15 +1,22 @@
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
 
 use once_cell::sync::OnceCell;
 use zksync_state::{StoragePtr, WriteStorage};
 use zksync_types::{
-    get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey, StorageValue,
-    H160, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    get_code_key, get_nonce_key, web3::keccak256, AccountTreeId, Address, StorageKey,
+    StorageLogWithPreviousValue, StorageValue, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
 use zksync_utils::{address_to_h256, h256_to_u256};
 
+use crate::glue::tracers::IntoOldVmTracer;
+
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
@@ -45,14 +52,19 @@ impl fmt::Display for Account {
     }
 }
 
-type State = HashMap<Address, Account>;
+pub type State = HashMap<Address, Account>;
 
+/// Tracer producing Geth-style prestate traces. The trace only covers storage accessed by the traced transaction,
+/// even if other transactions were executed in the same VM before it.
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
     pub pre: State,
     pub post: State,
     pub config: PrestateTracerConfig,
+    /// Outside the diff mode, only the first element (the state before the transaction) is populated.
     pub result: Arc<OnceCell<(State, State)>>,
+    /// VM timestamp at the start of the traced transaction.
+    start_timestamp: u32,
 }
 
 impl PrestateTracer {
@@ -62,7 +74,46 @@ impl PrestateTracer {
             post: Default::default(),
             config: PrestateTracerConfig { diff_mode },
             result,
+            start_timestamp: 0,
+        }
+    }
+
+    /// Computes the trace from storage logs produced by the traced transaction. The first log for each key
+    /// records the key value before the transaction; other values are unchanged by the transaction,
+    /// so they are read from the current `storage`.
+    fn process_storage_logs<S: WriteStorage>(
+        &mut self,
+        storage_logs: impl Iterator<Item = StorageLogWithPreviousValue>,
+        storage: &StoragePtr<S>,
+    ) {
+        let mut initial_values = HashMap::new();
+        let mut touched_slots = HashMap::<_, HashSet<_>>::new();
+        let mut written_slots = HashMap::<_, HashSet<_>>::new();
+        for storage_log in storage_logs {
+            let key = storage_log.log.key;
+            initial_values
+                .entry(key)
+                .or_insert(storage_log.previous_value);
+            touched_slots.entry(*key.account()).or_default().insert(key);
+            if storage_log.log.is_write() {
+                written_slots.entry(*key.account()).or_default().insert(key);
+            }
         }
+
+        let storage = &mut *storage.borrow_mut();
+        if self.config.diff_mode {
+            self.pre = read_accounts(storage, &written_slots, &initial_values);
+            self.post = read_accounts(storage, &written_slots, &HashMap::new());
+            // Only retain accounts that were actually modified.
+            let post = &self.post;
+            self.pre
+                .retain(|address, account| post.get(address) != Some(account));
+        } else {
+            self.pre = read_accounts(storage, &touched_slots, &initial_values);
+        }
+        self.result
+            .set((self.pre.clone(), self.post.clone()))
+            .unwrap();
     }
 }
 
@@ -71,42 +122,39 @@ pub struct PrestateTracerConfig {
     diff_mode: bool,
 }
 
-pub fn process_modified_storage_keys<S>(
-    prestate: State,
-    storage: &StoragePtr<S>,
-) -> HashMap<H160, Account>
-where
-    S: WriteStorage,
-{
-    let cloned_storage = &storage.clone();
-    let mut initial_storage_ref = cloned_storage.as_ref().borrow_mut();
-
-    initial_storage_ref
-        .modified_storage_keys()
-        .clone()
+impl IntoOldVmTracer for PrestateTracer {}
+
+/// Reads the state of accounts together with the specified storage slots. Values in `initial_values`
+/// take precedence over the values in `storage`.
+fn read_accounts<S: WriteStorage>(
+    storage: &mut S,
+    slots: &HashMap<AccountTreeId, HashSet<StorageKey>>,
+    initial_values: &HashMap<StorageKey, StorageValue>,
+) -> State {
+    let mut read_value = |key: &StorageKey| {
+        initial_values
+            .get(key)
+            .copied()
+            .unwrap_or_else(|| storage.read_value(key))
+    };
+    slots
         .iter()
-        .filter(|k| !prestate.contains_key(k.0.account().address()))
-        .map(|k| {
-            (
-                *(k.0.account().address()),
-                Account {
-                    balance: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_balance_key(k.0.account())),
-                    )),
-                    code: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_code_key(k.0.account().address())),
-                    )),
-                    nonce: Some(h256_to_u256(
-                        initial_storage_ref.read_value(&get_nonce_key(k.0.account().address())),
-                    )),
-                    storage: Some(get_storage_if_present(
-                        k.0.account(),
-                        initial_storage_ref.modified_storage_keys(),
-                    )),
-                },
-            )
+        .map(|(account, slots)| {
+            let address = *account.address();
+            let account = Account {
+                balance: Some(h256_to_u256(read_value(&get_balance_key(account)))),
+                code: Some(h256_to_u256(read_value(&get_code_key(&address)))),
+                nonce: Some(h256_to_u256(read_value(&get_nonce_key(&address)))),
+                storage: Some(
+                    slots
+                        .iter()
+                        .map(|key| (*key.key(), read_value(key)))
+                        .collect(),
+                ),
+            };
+            (address, account)
         })
-        .collect::<State>()
+        .collect()
 }
 
 fn get_balance_key(account: &AccountTreeId) -> StorageKey {
@@ -115,54 +163,3 @@ fn get_balance_key(account: &AccountTreeId) -> StorageKey {
     let balance_key: H256 = keccak256(&bytes).into();
     StorageKey::new(AccountTreeId::new(L2_BASE_TOKEN_ADDRESS), balance_key)
 }
-
-fn get_storage_if_present(
-    account: &AccountTreeId,
-    modified_storage_keys: &HashMap<StorageKey, StorageValue>,
-) -> HashMap<H256, H256> {
-    //check if there is a Storage Key struct with an account field that matches the account and return the key as the key and the Storage Value as the value
-    modified_storage_keys
-        .iter()
-        .filter(|(k, _)| k.account() == account)
-        .map(|(k, v)| (*k.key(), *v))
-        .collect()
-}
-
-fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
-    pre.retain(|k, v| {
-        if let Some(post_v) = post.get(k) {
-            if v != post_v {
-                return true;
-            }
-        }
-        false
-    });
-    result.set((pre, post)).unwrap();
-}
-
-fn get_account_data<T: StorageAccess>(
-    account_key: &StorageKey,
-    state: &T,
-    storage: &HashMap<StorageKey, StorageValue>,
-) -> (Address, Account) {
-    let address = *(account_key.account().address());
-    let balance = state.read_from_storage(&get_balance_key(account_key.account()));
-    let code = state.read_from_storage(&get_code_key(account_key.account().address()));
-    let nonce = state.read_from_storage(&get_nonce_key(account_key.account().address()));
-    let storage = get_storage_if_present(account_key.account(), storage);
-
-    (
-        address,
-        Account {
-            balance: Some(balance),
-            code: Some(code),
-            nonce: Some(nonce),
-            storage: Some(storage),
-        },
-    )
-}
-
-// Define a trait that abstracts storage access
-trait StorageAccess {
-    fn read_from_storage(&self, key: &StorageKey) -> U256;
-}
@@ -0,0 +1,32 @@
+use zk_evm_1_4_0::aux_structures::Timestamp;
+use zksync_state::WriteStorage;
+use zksync_types::StorageLogWithPreviousValue;
+
+use super::PrestateTracer;
+use crate::{
+    glue::GlueInto,
+    interface::dyn_tracers::vm_1_4_0::DynTracer,
+    vm_boojum_integration::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for PrestateTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.start_timestamp = state.local_state.timestamp;
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: crate::interface::tracer::VmExecutionStopReason,
+    ) {
+        let storage_logs = state
+            .storage
+            .storage_log_queries_after_timestamp(Timestamp(self.start_timestamp))
+            .iter()
+            .map(|log| -> StorageLogWithPreviousValue { (**log).glue_into() });
+        self.process_storage_logs(storage_logs, &state.storage.storage.inner().get_ptr());
+    }
+}
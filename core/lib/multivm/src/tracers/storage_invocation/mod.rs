@@ -1,3 +1,9 @@
+use std::{collections::HashSet, sync::Arc};
+
+use once_cell::sync::OnceCell;
+use zksync_state::WriteStorage;
+use zksync_types::StorageKey;
+
 use crate::{glue::tracers::IntoOldVmTracer, tracers::old_tracers::OldTracers};
 
 pub mod vm_1_4_1;
@@ -26,3 +32,44 @@ impl IntoOldVmTracer for StorageInvocations {
         OldTracers::StorageInvocations(self.limit)
     }
 }
+
+/// Tracer collecting storage slots read or written during VM execution. Slots already accessed by the VM
+/// before the execution (e.g., by previous transactions in the same batch) are not reported.
+#[derive(Debug, Clone)]
+pub struct StorageAccessTracer {
+    accessed_before: HashSet<StorageKey>,
+    result: Arc<OnceCell<HashSet<StorageKey>>>,
+}
+
+impl StorageAccessTracer {
+    pub fn new(result: Arc<OnceCell<HashSet<StorageKey>>>) -> Self {
+        Self {
+            accessed_before: HashSet::new(),
+            result,
+        }
+    }
+
+    fn accessed_keys<S: WriteStorage>(storage: &S) -> impl Iterator<Item = &StorageKey> + '_ {
+        storage
+            .read_storage_keys()
+            .keys()
+            .chain(storage.modified_storage_keys().keys())
+    }
+
+    fn initialize<S: WriteStorage>(&mut self, storage: &S) {
+        self.accessed_before = Self::accessed_keys(storage).copied().collect();
+    }
+
+    fn store_result<S: WriteStorage>(&self, storage: &S) {
+        let accessed_keys = Self::accessed_keys(storage)
+            .filter(|key| !self.accessed_before.contains(key))
+            .copied()
+            .collect();
+        // The tracer may be used for several VM executions; only the first one is reported.
+        self.result.set(accessed_keys).ok();
+    }
+}
+
+/// Legacy VMs don't support this tracer, so the result is never set for them; callers must treat this
+/// as an unsupported VM version rather than an empty access list.
+impl IntoOldVmTracer for StorageAccessTracer {}
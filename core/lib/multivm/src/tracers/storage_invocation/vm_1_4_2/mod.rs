@@ -2,11 +2,11 @@ use zksync_state::WriteStorage;
 
 use crate::{
     interface::{
-        tracer::{TracerExecutionStatus, TracerExecutionStopReason},
+        tracer::{TracerExecutionStatus, TracerExecutionStopReason, VmExecutionStopReason},
         traits::tracers::dyn_tracers::vm_1_4_1::DynTracer,
         Halt,
     },
-    tracers::storage_invocation::StorageInvocations,
+    tracers::storage_invocation::{StorageAccessTracer, StorageInvocations},
     vm_1_4_2::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
 };
 
@@ -33,3 +33,20 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StorageInvocations {
         TracerExecutionStatus::Continue
     }
 }
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StorageAccessTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StorageAccessTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.initialize(&*state.storage.storage.get_ptr().borrow());
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result(&*state.storage.storage.get_ptr().borrow());
+    }
+}
@@ -1,8 +1,8 @@
 use zksync_state::WriteStorage;
 
 use crate::{
-    interface::dyn_tracers::vm_1_3_3::DynTracer,
-    tracers::storage_invocation::StorageInvocations,
+    interface::{dyn_tracers::vm_1_3_3::DynTracer, tracer::VmExecutionStopReason},
+    tracers::storage_invocation::{StorageAccessTracer, StorageInvocations},
     vm_virtual_blocks::{
         BootloaderState, ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory,
         VmTracer, ZkSyncVmState,
@@ -33,3 +33,24 @@ impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for StorageInvoc
 }
 
 impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StorageInvocations {}
+
+impl<H: HistoryMode> ExecutionEndTracer<H> for StorageAccessTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StorageAccessTracer {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for StorageAccessTracer {
+    fn initialize_tracer(&mut self, state: &mut ZkSyncVmState<S, H>) {
+        self.initialize(&*state.storage.storage.get_ptr().borrow());
+    }
+
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result(&*state.storage.storage.get_ptr().borrow());
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StorageAccessTracer {}
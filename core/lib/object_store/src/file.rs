@@ -43,6 +43,8 @@ impl FileBackedObjectStore {
             Bucket::ProofsFri,
             Bucket::StorageSnapshot,
             Bucket::TeeVerifierInput,
+            Bucket::MerkleTreeCheckpoints,
+            Bucket::L2BlockArchive,
         ] {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path).await?;
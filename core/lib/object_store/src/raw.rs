@@ -20,6 +20,8 @@ pub enum Bucket {
     StorageSnapshot,
     DataAvailability,
     TeeVerifierInput,
+    MerkleTreeCheckpoints,
+    L2BlockArchive,
 }
 
 impl Bucket {
@@ -39,6 +41,8 @@ impl Bucket {
             Self::StorageSnapshot => "storage_logs_snapshots",
             Self::DataAvailability => "data_availability",
             Self::TeeVerifierInput => "tee_verifier_inputs",
+            Self::MerkleTreeCheckpoints => "merkle_tree_checkpoints",
+            Self::L2BlockArchive => "l2_block_archive",
         }
     }
 }
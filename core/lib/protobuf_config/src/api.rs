@@ -133,6 +133,18 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("max_batch_request_size")?,
+            max_simulated_bundle_size: self
+                .max_simulated_bundle_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_simulated_bundle_size")?,
+            simulate_bundle_timeout_ms: self.simulate_bundle_timeout_ms,
+            max_submitted_bundle_size: self
+                .max_submitted_bundle_size
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_submitted_bundle_size")?,
+            submit_bundle_timeout_ms: self.submit_bundle_timeout_ms,
             max_response_body_size_mb: self
                 .max_response_body_size_mb
                 .map(|x| x.try_into())
@@ -159,6 +171,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .collect::<Result<Vec<_>, _>>()
                 .context("account_pks")?,
             extended_api_tracing: self.extended_api_tracing.unwrap_or_default(),
+            tee_finalized_tag_enabled: self.tee_finalized_tag_enabled.unwrap_or_default(),
             api_namespaces,
         })
     }
@@ -204,6 +217,14 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into().unwrap()),
             fee_history_limit: this.fee_history_limit,
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
+            max_simulated_bundle_size: this
+                .max_simulated_bundle_size
+                .map(|x| x.try_into().unwrap()),
+            simulate_bundle_timeout_ms: this.simulate_bundle_timeout_ms,
+            max_submitted_bundle_size: this
+                .max_submitted_bundle_size
+                .map(|x| x.try_into().unwrap()),
+            submit_bundle_timeout_ms: this.submit_bundle_timeout_ms,
             max_response_body_size_mb: this
                 .max_response_body_size_mb
                 .map(|x| x.try_into().unwrap()),
@@ -229,6 +250,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|k| format!("{:?}", k))
                 .collect(),
             extended_api_tracing: Some(this.extended_api_tracing),
+            tee_finalized_tag_enabled: Some(this.tee_finalized_tag_enabled),
             api_namespaces: this.api_namespaces.clone().unwrap_or_default(),
         }
     }
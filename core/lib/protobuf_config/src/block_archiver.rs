@@ -0,0 +1,23 @@
+use zksync_config::configs::block_archiver::BlockArchiverConfig;
+use zksync_protobuf::ProtoRepr;
+
+use crate::proto::block_archiver as proto;
+
+impl ProtoRepr for proto::BlockArchiver {
+    type Type = BlockArchiverConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let defaults = BlockArchiverConfig::default();
+        Ok(Self::Type {
+            poll_interval_ms: self.poll_interval_ms.unwrap_or(defaults.poll_interval_ms),
+            upload_to_ipfs: self.upload_to_ipfs.unwrap_or(defaults.upload_to_ipfs),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            poll_interval_ms: Some(this.poll_interval_ms),
+            upload_to_ipfs: Some(this.upload_to_ipfs),
+        }
+    }
+}
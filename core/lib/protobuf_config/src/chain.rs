@@ -82,6 +82,13 @@ impl ProtoRepr for proto::StateKeeper {
                 &self.protective_reads_persistence_enabled,
             )
             .context("protective_reads_persistence_enabled")?,
+            max_anchoring_payload_bytes: self.max_anchoring_payload_bytes,
+            max_l2_blocks_per_batch: self.max_l2_blocks_per_batch,
+            tx_allowlist_enabled: self.tx_allowlist_enabled.unwrap_or_default(),
+            tx_allowlist_reload_interval_ms: self
+                .tx_allowlist_reload_interval_ms
+                .unwrap_or_else(Self::Type::default_tx_allowlist_reload_interval_ms),
+            custom_seal_criteria: self.custom_seal_criteria.clone(),
 
             // We need these values only for instantiating configs from environmental variables, so it's not
             // needed during the initialization from files
@@ -120,6 +127,11 @@ impl ProtoRepr for proto::StateKeeper {
             save_call_traces: Some(this.save_call_traces),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
             protective_reads_persistence_enabled: Some(this.protective_reads_persistence_enabled),
+            max_anchoring_payload_bytes: this.max_anchoring_payload_bytes,
+            max_l2_blocks_per_batch: this.max_l2_blocks_per_batch,
+            tx_allowlist_enabled: Some(this.tx_allowlist_enabled),
+            tx_allowlist_reload_interval_ms: Some(this.tx_allowlist_reload_interval_ms),
+            custom_seal_criteria: this.custom_seal_criteria.clone(),
         }
     }
 }
@@ -151,6 +163,13 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: *required(&self.stuck_tx_timeout).context("stuck_tx_timeout")?,
             remove_stuck_txs: *required(&self.remove_stuck_txs).context("remove_stuck_txs")?,
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            max_pending_txs_per_account: self
+                .max_pending_txs_per_account
+                .map(|x| x.try_into())
+                .transpose()
+                .context("max_pending_txs_per_account")?,
+            nonce_gap_ttl_sec: self.nonce_gap_ttl_sec,
+            evict_low_fee_txs: self.evict_low_fee_txs.unwrap_or(false),
         })
     }
 
@@ -162,6 +181,11 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: Some(this.stuck_tx_timeout),
             remove_stuck_txs: Some(this.remove_stuck_txs),
             delay_interval: Some(this.delay_interval),
+            max_pending_txs_per_account: this
+                .max_pending_txs_per_account
+                .map(|x| x.try_into().unwrap()),
+            nonce_gap_ttl_sec: this.nonce_gap_ttl_sec,
+            evict_low_fee_txs: Some(this.evict_low_fee_txs),
         }
     }
 }
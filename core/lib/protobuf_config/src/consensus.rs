@@ -1,8 +1,8 @@
 use anyhow::Context as _;
 use zksync_basic_types::L2ChainId;
 use zksync_config::configs::consensus::{
-    ConsensusConfig, GenesisSpec, Host, NodePublicKey, ProtocolVersion, RpcConfig,
-    ValidatorPublicKey, WeightedValidator,
+    AttesterPublicKey, ConsensusConfig, GenesisSpec, Host, LeaderSelectionMode, NodePublicKey,
+    ProtocolVersion, RpcConfig, ValidatorPublicKey, WeightedAttester, WeightedValidator,
 };
 use zksync_protobuf::{read_optional, repr::ProtoRepr, required, ProtoFmt};
 
@@ -24,6 +24,40 @@ impl ProtoRepr for proto::WeightedValidator {
     }
 }
 
+impl ProtoRepr for proto::WeightedAttester {
+    type Type = WeightedAttester;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            key: AttesterPublicKey(required(&self.key).context("key")?.clone()),
+            weight: *required(&self.weight).context("weight")?,
+        })
+    }
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            key: Some(this.key.0.clone()),
+            weight: Some(this.weight),
+        }
+    }
+}
+
+impl proto::LeaderSelectionMode {
+    fn new(mode: &LeaderSelectionMode) -> Self {
+        match mode {
+            LeaderSelectionMode::Sticky => Self::Sticky,
+            LeaderSelectionMode::RoundRobin => Self::RoundRobin,
+            LeaderSelectionMode::Weighted => Self::Weighted,
+        }
+    }
+
+    fn parse(&self) -> LeaderSelectionMode {
+        match self {
+            Self::Sticky => LeaderSelectionMode::Sticky,
+            Self::RoundRobin => LeaderSelectionMode::RoundRobin,
+            Self::Weighted => LeaderSelectionMode::Weighted,
+        }
+    }
+}
+
 impl ProtoRepr for proto::GenesisSpec {
     type Type = GenesisSpec;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -42,6 +76,19 @@ impl ProtoRepr for proto::GenesisSpec {
                 .collect::<Result<_, _>>()
                 .context("validators")?,
             leader: ValidatorPublicKey(required(&self.leader).context("leader")?.clone()),
+            attesters: self
+                .attesters
+                .iter()
+                .enumerate()
+                .map(|(i, x)| x.read().context(i))
+                .collect::<Result<_, _>>()
+                .context("attesters")?,
+            leader_selection: match self.leader_selection {
+                Some(mode) => proto::LeaderSelectionMode::try_from(mode)
+                    .context("leader_selection")?
+                    .parse(),
+                None => LeaderSelectionMode::default(),
+            },
         })
     }
     fn build(this: &Self::Type) -> Self {
@@ -50,6 +97,8 @@ impl ProtoRepr for proto::GenesisSpec {
             protocol_version: Some(this.protocol_version.0),
             validators: this.validators.iter().map(ProtoRepr::build).collect(),
             leader: Some(this.leader.0.clone()),
+            attesters: this.attesters.iter().map(ProtoRepr::build).collect(),
+            leader_selection: Some(proto::LeaderSelectionMode::new(&this.leader_selection).into()),
         }
     }
 }
@@ -100,6 +149,7 @@ impl ProtoRepr for proto::Config {
                 .collect::<Result<_, _>>()?,
             genesis_spec: read_optional_repr(&self.genesis_spec).context("genesis_spec")?,
             rpc: read_optional_repr(&self.rpc_config).context("rpc_config")?,
+            require_certificates: self.require_certificates.unwrap_or(false),
         })
     }
 
@@ -126,6 +176,7 @@ impl ProtoRepr for proto::Config {
                 .collect(),
             genesis_spec: this.genesis_spec.as_ref().map(ProtoRepr::build),
             rpc_config: this.rpc.as_ref().map(ProtoRepr::build),
+            require_certificates: Some(this.require_certificates),
         }
     }
 }
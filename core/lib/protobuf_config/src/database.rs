@@ -48,6 +48,12 @@ impl ProtoRepr for proto::MerkleTree {
             max_l1_batches_per_iter: required(&self.max_l1_batches_per_iter)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_l1_batches_per_iter")?,
+            node_cache_capacity: self
+                .node_cache_capacity
+                .map(|x| x.try_into())
+                .transpose()
+                .context("node_cache_capacity")?,
+            export_checkpoints: self.export_checkpoints.unwrap_or_default(),
         })
     }
 
@@ -60,6 +66,8 @@ impl ProtoRepr for proto::MerkleTree {
             memtable_capacity_mb: Some(this.memtable_capacity_mb.try_into().unwrap()),
             stalled_writes_timeout_sec: Some(this.stalled_writes_timeout_sec),
             max_l1_batches_per_iter: Some(this.max_l1_batches_per_iter.try_into().unwrap()),
+            node_cache_capacity: this.node_cache_capacity.map(|x| x.try_into().unwrap()),
+            export_checkpoints: Some(this.export_checkpoints),
         }
     }
 }
@@ -1,11 +1,11 @@
 use std::{num::NonZeroUsize, str::FromStr};
 
 use anyhow::Context;
-use zksync_basic_types::{url::SensitiveUrl, L1ChainId, L2ChainId};
+use zksync_basic_types::{url::SensitiveUrl, L1BatchNumber, L1ChainId, L2ChainId};
 use zksync_config::configs::en_config::ENConfig;
 use zksync_protobuf::{required, ProtoRepr};
 
-use crate::proto::en as proto;
+use crate::{proto::en as proto, read_optional_repr};
 
 impl ProtoRepr for proto::ExternalNode {
     type Type = ENConfig;
@@ -30,6 +30,17 @@ impl ProtoRepr for proto::ExternalNode {
             main_node_rate_limit_rps: self
                 .main_node_rate_limit_rps
                 .and_then(|a| NonZeroUsize::new(a as usize)),
+            reorg_auto_recovery_enabled: self.reorg_auto_recovery_enabled,
+            reorg_auto_recovery_max_depth: self.reorg_auto_recovery_max_depth,
+            reorg_auto_recovery_allow_executed_batches: self
+                .reorg_auto_recovery_allow_executed_batches,
+            verifier_first_l1_batch: self.verifier_first_l1_batch.map(L1BatchNumber),
+            verifier_mintlayer_api_url: self.verifier_mintlayer_api_url.clone(),
+            verifier_ipfs_gateway_url: self.verifier_ipfs_gateway_url.clone(),
+            sync_archive_object_store: read_optional_repr(&self.sync_archive_object_store)
+                .context("sync_archive_object_store")?,
+            sync_archive_ipfs_gateway_url: self.sync_archive_ipfs_gateway_url.clone(),
+            sync_archive_cid_resolver_urls: self.sync_archive_cid_resolver_urls.clone(),
         })
     }
 
@@ -45,6 +56,19 @@ impl ProtoRepr for proto::ExternalNode {
                 .into(),
             ),
             main_node_rate_limit_rps: this.main_node_rate_limit_rps.map(|a| a.get() as u64),
+            reorg_auto_recovery_enabled: this.reorg_auto_recovery_enabled,
+            reorg_auto_recovery_max_depth: this.reorg_auto_recovery_max_depth,
+            reorg_auto_recovery_allow_executed_batches: this
+                .reorg_auto_recovery_allow_executed_batches,
+            verifier_first_l1_batch: this.verifier_first_l1_batch.map(|number| number.0),
+            verifier_mintlayer_api_url: this.verifier_mintlayer_api_url.clone(),
+            verifier_ipfs_gateway_url: this.verifier_ipfs_gateway_url.clone(),
+            sync_archive_object_store: this
+                .sync_archive_object_store
+                .as_ref()
+                .map(ProtoRepr::build),
+            sync_archive_ipfs_gateway_url: this.sync_archive_ipfs_gateway_url.clone(),
+            sync_archive_cid_resolver_urls: this.sync_archive_cid_resolver_urls.clone(),
         }
     }
 }
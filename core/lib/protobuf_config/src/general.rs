@@ -49,6 +49,7 @@ impl ProtoRepr for proto::GeneralConfig {
                 .context("core_object_store")?,
             base_token_adjuster: read_optional_repr(&self.base_token_adjuster)
                 .context("base_token_adjuster")?,
+            block_archiver: read_optional_repr(&self.block_archiver).context("block_archiver")?,
             commitment_generator: read_optional_repr(&self.commitment_generator)
                 .context("commitment_generator")?,
             pruning: read_optional_repr(&self.pruning).context("pruning")?,
@@ -99,6 +100,7 @@ impl ProtoRepr for proto::GeneralConfig {
             pruning: this.pruning.as_ref().map(ProtoRepr::build),
             core_object_store: this.core_object_store.as_ref().map(ProtoRepr::build),
             base_token_adjuster: this.base_token_adjuster.as_ref().map(ProtoRepr::build),
+            block_archiver: this.block_archiver.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -6,6 +6,7 @@
 
 mod api;
 mod base_token_adjuster;
+mod block_archiver;
 mod chain;
 mod circuit_breaker;
 mod commitment_generator;
@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{repr::ProtoRepr, required};
 
-use crate::proto::prover as proto;
+use crate::{parse_h256, proto::prover as proto};
 
 impl ProtoRepr for proto::ProofDataHandler {
     type Type = configs::ProofDataHandlerConfig;
@@ -17,6 +17,21 @@ impl ProtoRepr for proto::ProofDataHandler {
             tee_support: required(&self.tee_support)
                 .copied()
                 .context("tee_support")?,
+            tee_allowed_mr_enclaves: self
+                .tee_allowed_mr_enclaves
+                .iter()
+                .enumerate()
+                .map(|(i, x)| parse_h256(x).context(i))
+                .collect::<Result<_, _>>()
+                .context("tee_allowed_mr_enclaves")?,
+            tee_allowed_mr_signers: self
+                .tee_allowed_mr_signers
+                .iter()
+                .enumerate()
+                .map(|(i, x)| parse_h256(x).context(i))
+                .collect::<Result<_, _>>()
+                .context("tee_allowed_mr_signers")?,
+            tee_sgx_root_ca_path: self.tee_sgx_root_ca_path.clone(),
         })
     }
 
@@ -25,6 +40,17 @@ impl ProtoRepr for proto::ProofDataHandler {
             http_port: Some(this.http_port.into()),
             proof_generation_timeout_in_secs: Some(this.proof_generation_timeout_in_secs.into()),
             tee_support: Some(this.tee_support),
+            tee_allowed_mr_enclaves: this
+                .tee_allowed_mr_enclaves
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect(),
+            tee_allowed_mr_signers: this
+                .tee_allowed_mr_signers
+                .iter()
+                .map(|x| format!("{:?}", x))
+                .collect(),
+            tee_sgx_root_ca_path: this.tee_sgx_root_ca_path.clone(),
         }
     }
 }
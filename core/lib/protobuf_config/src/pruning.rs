@@ -1,9 +1,10 @@
 use std::num::NonZeroU64;
 
+use anyhow::Context as _;
 use zksync_config::configs::PruningConfig;
 use zksync_protobuf::ProtoRepr;
 
-use crate::proto::pruning as proto;
+use crate::{parse_h160, proto::pruning as proto};
 
 impl ProtoRepr for proto::Pruning {
     type Type = PruningConfig;
@@ -14,6 +15,19 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: self.chunk_size,
             removal_delay_sec: self.removal_delay_sec.and_then(NonZeroU64::new),
             data_retention_sec: self.data_retention_sec,
+            events_retention_sec: self.events_retention_sec,
+            l2_to_l1_logs_retention_sec: self.l2_to_l1_logs_retention_sec,
+            call_traces_retention_sec: self.call_traces_retention_sec,
+            transaction_data_retention_sec: self.transaction_data_retention_sec,
+            retained_addresses: self
+                .retained_addresses
+                .iter()
+                .enumerate()
+                .map(|(i, address)| parse_h160(address).context(i))
+                .collect::<Result<_, _>>()
+                .context("retained_addresses")?,
+            wait_for_mintlayer_anchoring: self.wait_for_mintlayer_anchoring.unwrap_or_default(),
+            dry_run: self.dry_run.unwrap_or_default(),
         })
     }
 
@@ -23,6 +37,17 @@ impl ProtoRepr for proto::Pruning {
             chunk_size: this.chunk_size,
             removal_delay_sec: this.removal_delay_sec.map(|a| a.get()),
             data_retention_sec: this.data_retention_sec,
+            events_retention_sec: this.events_retention_sec,
+            l2_to_l1_logs_retention_sec: this.l2_to_l1_logs_retention_sec,
+            call_traces_retention_sec: this.call_traces_retention_sec,
+            transaction_data_retention_sec: this.transaction_data_retention_sec,
+            retained_addresses: this
+                .retained_addresses
+                .iter()
+                .map(|address| format!("{address:?}"))
+                .collect(),
+            wait_for_mintlayer_anchoring: Some(this.wait_for_mintlayer_anchoring),
+            dry_run: Some(this.dry_run),
         }
     }
 }
@@ -8,7 +8,7 @@ use zksync_config::configs::{
 };
 use zksync_protobuf::ProtoRepr;
 
-use crate::{proto::snapshot_recovery as proto, read_optional_repr};
+use crate::{parse_h160, proto::snapshot_recovery as proto, read_optional_repr};
 
 impl ProtoRepr for proto::Postgres {
     type Type = PostgresRecoveryConfig;
@@ -48,6 +48,7 @@ impl ProtoRepr for proto::SnapshotRecovery {
                 TreeRecoveryConfig {
                     chunk_size,
                     parallel_persistence_buffer,
+                    use_checkpoints: tree.use_checkpoints.unwrap_or_default(),
                 }
             })
             .unwrap_or_default();
@@ -65,6 +66,15 @@ impl ProtoRepr for proto::SnapshotRecovery {
                 .as_ref()
                 .and_then(|experimental| experimental.drop_storage_key_preimages)
                 .unwrap_or_default(),
+            manifest_signer: self
+                .manifest_signer
+                .as_ref()
+                .map(|signer| parse_h160(signer))
+                .transpose()
+                .context("manifest_signer")?,
+            ipfs_anchor_tx_id: self.ipfs_anchor_tx_id.clone(),
+            ipfs_gateway_url: self.ipfs_gateway_url.clone(),
+            mintlayer_api_url: self.mintlayer_api_url.clone(),
         })
     }
 
@@ -75,6 +85,7 @@ impl ProtoRepr for proto::SnapshotRecovery {
             (
                 Some(proto::Tree {
                     chunk_size: this.tree.chunk_size,
+                    use_checkpoints: Some(this.tree.use_checkpoints),
                 }),
                 Some(crate::proto::experimental::SnapshotRecovery {
                     tree_recovery_parallel_persistence_buffer: this
@@ -97,6 +108,10 @@ impl ProtoRepr for proto::SnapshotRecovery {
             experimental,
             l1_batch: this.l1_batch.map(|a| a.0),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
+            manifest_signer: this.manifest_signer.map(|signer| format!("{signer:?}")),
+            ipfs_anchor_tx_id: this.ipfs_anchor_tx_id.clone(),
+            ipfs_gateway_url: this.ipfs_gateway_url.clone(),
+            mintlayer_api_url: this.mintlayer_api_url.clone(),
         }
     }
 }
@@ -24,6 +24,8 @@ impl ProtoRepr for proto::SnapshotsCreator {
                 .context("storage_logs_chunk_size")?,
             concurrent_queries_count: *required(&self.concurrent_queries_count)
                 .context("concurrent_queries_count")?,
+            sign_manifests: self.sign_manifests.unwrap_or_default(),
+            publish_to_ipfs: self.publish_to_ipfs.unwrap_or_default(),
             object_store,
         })
     }
@@ -34,6 +36,8 @@ impl ProtoRepr for proto::SnapshotsCreator {
             l1_batch_number: this.l1_batch_number.map(|num| num.0),
             storage_logs_chunk_size: Some(this.storage_logs_chunk_size),
             concurrent_queries_count: Some(this.concurrent_queries_count),
+            sign_manifests: Some(this.sign_manifests),
+            publish_to_ipfs: Some(this.publish_to_ipfs),
             object_store: this.object_store.as_ref().map(ProtoRepr::build),
         }
     }
@@ -42,6 +42,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::wallets::Wallets>>(rng);
     test_encode_all_formats::<ReprConv<proto::genesis::Genesis>>(rng);
     test_encode_all_formats::<ReprConv<proto::en::ExternalNode>>(rng);
+    test_encode_all_formats::<ReprConv<proto::block_archiver::BlockArchiver>>(rng);
 }
 
 #[test]
@@ -1,16 +1,77 @@
 use anyhow::Context;
 use zksync_config::configs::{
     self,
-    wallets::{AddressWallet, EthSender, StateKeeper, Wallet},
+    wallets::{
+        AddressWallet, EthSender, KeystoreEthSender, KeystorePassword, KeystoreWallet,
+        RemoteEthSender, StateKeeper, Wallet,
+    },
 };
-use zksync_protobuf::{required, ProtoRepr};
+use zksync_protobuf::{
+    repr::{read_required_repr, ProtoRepr},
+    required,
+};
+
+use crate::{parse_h160, parse_h256, proto::wallets as proto, read_optional_repr};
+
+impl ProtoRepr for proto::KeystoreWallet {
+    type Type = KeystoreWallet;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let password = match (&self.password_env, &self.password_file) {
+            (Some(env), None) => KeystorePassword::Env(env.clone()),
+            (None, Some(path)) => KeystorePassword::File(path.into()),
+            _ => anyhow::bail!("exactly one of `password_env` and `password_file` must be set"),
+        };
+        Ok(Self::Type {
+            address: self
+                .address
+                .as_deref()
+                .map(parse_h160)
+                .transpose()
+                .context("address")?,
+            path: required(&self.path).context("path")?.into(),
+            password,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        let (password_env, password_file) = match &this.password {
+            KeystorePassword::Env(env) => (Some(env.clone()), None),
+            KeystorePassword::File(path) => (None, Some(path.to_string_lossy().into_owned())),
+        };
+        Self {
+            address: this.address.map(|address| format!("{address:?}")),
+            path: Some(this.path.to_string_lossy().into_owned()),
+            password_env,
+            password_file,
+        }
+    }
+}
+
+impl ProtoRepr for proto::Keystores {
+    type Type = KeystoreEthSender;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            operator: read_required_repr(&self.operator).context("operator")?,
+            blob_operator: read_optional_repr(&self.blob_operator).context("blob_operator")?,
+        })
+    }
 
-use crate::{parse_h160, parse_h256, proto::wallets as proto};
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            operator: Some(ProtoRepr::build(&this.operator)),
+            blob_operator: this.blob_operator.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
 
 impl ProtoRepr for proto::Wallets {
     type Type = configs::wallets::Wallets;
     fn read(&self) -> anyhow::Result<Self::Type> {
-        let eth_sender = if self.operator.is_some() && self.blob_operator.is_some() {
+        // Operator wallets may only specify addresses if keys are stored elsewhere (e.g., in `keystores`).
+        let has_private_keys = [&self.operator, &self.blob_operator]
+            .into_iter()
+            .all(|wallet| wallet.as_ref().is_some_and(|w| w.private_key.is_some()));
+        let eth_sender = if has_private_keys {
             let blob_operator = if let Some(blob_operator) = &self.blob_operator {
                 Some(Wallet::from_private_key_bytes(
                     parse_h256(required(&blob_operator.private_key).context("blob operator")?)?,
@@ -41,6 +102,32 @@ impl ProtoRepr for proto::Wallets {
             None
         };
 
+        let keystore_eth_sender = read_optional_repr(&self.keystores).context("keystores")?;
+        let remote_eth_sender = self
+            .remote_signer
+            .as_ref()
+            .map(|signer| {
+                let read_address = |wallet: &proto::AddressWallet| {
+                    parse_h160(required(&wallet.address).context("address")?)
+                        .map(AddressWallet::from_address)
+                };
+                anyhow::Ok(RemoteEthSender {
+                    signer_url: required(&signer.url)
+                        .and_then(|url| Ok(url.parse()?))
+                        .context("url")?,
+                    operator: read_address(required(&signer.operator).context("operator")?)
+                        .context("operator")?,
+                    blob_operator: signer
+                        .blob_operator
+                        .as_ref()
+                        .map(read_address)
+                        .transpose()
+                        .context("blob_operator")?,
+                })
+            })
+            .transpose()
+            .context("remote_signer")?;
+
         let state_keeper = if let Some(fee_account) = &self.fee_account {
             let address = parse_h160(
                 required(&fee_account.address).context("fee_account.address requireed")?,
@@ -55,6 +142,8 @@ impl ProtoRepr for proto::Wallets {
 
         Ok(Self::Type {
             eth_sender,
+            keystore_eth_sender,
+            remote_eth_sender,
             state_keeper,
         })
     }
@@ -87,16 +176,28 @@ impl ProtoRepr for proto::Wallets {
             (None, None)
         };
 
+        let build_address = |wallet: &AddressWallet| proto::AddressWallet {
+            address: Some(format!("{:?}", wallet.address())),
+        };
+        let remote_signer = this
+            .remote_eth_sender
+            .as_ref()
+            .map(|sender| proto::RemoteSigner {
+                url: Some(sender.signer_url.expose_str().to_owned()),
+                operator: Some(build_address(&sender.operator)),
+                blob_operator: sender.blob_operator.as_ref().map(build_address),
+            });
+
         let fee_account = this
             .state_keeper
             .as_ref()
-            .map(|state_keeper| proto::AddressWallet {
-                address: Some(format!("{:?}", state_keeper.fee_account.address())),
-            });
+            .map(|state_keeper| build_address(&state_keeper.fee_account));
         Self {
             blob_operator,
             operator,
             fee_account,
+            remote_signer,
+            keystores: this.keystore_eth_sender.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -0,0 +1,286 @@
+//! Recovery from snapshots published to IPFS and anchored on Mintlayer.
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_anchoring::{IpfsClient, MintlayerClient};
+use zksync_object_store::{Bucket, ObjectStore, ObjectStoreError, StoredObject};
+use zksync_types::{
+    api,
+    snapshots::{
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotIpfsDocument, SnapshotManifest,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+    },
+    tokens::TokenInfo,
+    L1BatchNumber, L2BlockNumber,
+};
+use zksync_web3_decl::error::EnrichedClientResult;
+
+use crate::SnapshotsApplierMainNodeClient;
+
+/// Source of snapshot data published to IPFS by the snapshot creator. Resolved from the ID of a Mintlayer transaction
+/// anchoring the CID of the [`SnapshotIpfsDocument`] for the snapshot.
+///
+/// The source provides an [`ObjectStore`] serving snapshot objects from IPFS, and a wrapper for the main node client
+/// that returns snapshot headers and manifests from IPFS documents. Objects fetched from IPFS are checked against
+/// the snapshot manifests by the applier as usual; to protect against a malicious IPFS gateway substituting
+/// the documents themselves, require manifests to be signed
+/// (see [`SnapshotsApplierTask::require_signed_manifests()`](crate::SnapshotsApplierTask::require_signed_manifests())).
+///
+/// L1 batch and L2 block details, and tokens are not published to IPFS, so they are still fetched from the main node;
+/// i.e., the main node must be reachable during recovery.
+#[derive(Debug)]
+pub struct IpfsSnapshotSource {
+    ipfs: Arc<dyn IpfsClient>,
+    /// Documents for the snapshot chain, from the anchored snapshot to the full snapshot at the chain start.
+    documents: Vec<SnapshotIpfsDocument>,
+}
+
+impl IpfsSnapshotSource {
+    /// Resolves the snapshot chain anchored by the specified Mintlayer transaction.
+    pub async fn resolve(
+        ipfs: Arc<dyn IpfsClient>,
+        mintlayer: &dyn MintlayerClient,
+        anchor_tx_id: &str,
+    ) -> anyhow::Result<Self> {
+        let deposited_data = mintlayer
+            .fetch_deposited_data(anchor_tx_id)
+            .await
+            .with_context(|| {
+                format!("failed fetching data anchored in Mintlayer transaction {anchor_tx_id}")
+            })?;
+        let deposited_data = String::from_utf8(deposited_data).with_context(|| {
+            format!("data anchored in Mintlayer transaction {anchor_tx_id} is not a UTF-8 string")
+        })?;
+
+        // Anchored data may contain several comma-separated CIDs (e.g., if it's shared with L1 batch data).
+        let mut anchored_document = None;
+        for cid in deposited_data.split(',').map(str::trim) {
+            match Self::fetch_document(ipfs.as_ref(), cid).await {
+                Ok(document) => {
+                    anchored_document = Some(document);
+                    break;
+                }
+                Err(err) => {
+                    tracing::info!("IPFS object {cid} is not a snapshot document: {err:#}");
+                }
+            }
+        }
+        let mut document = anchored_document.with_context(|| {
+            format!("Mintlayer transaction {anchor_tx_id} doesn't anchor a snapshot document")
+        })?;
+        tracing::info!(
+            "Resolved snapshot for L1 batch #{} from Mintlayer transaction {anchor_tx_id}",
+            document.header.l1_batch_number
+        );
+
+        let mut documents = vec![];
+        while let Some(base_l1_batch_number) = document.header.base_l1_batch_number {
+            let l1_batch_number = document.header.l1_batch_number;
+            let base_cid = document.base_document_cid.clone().with_context(|| {
+                format!("IPFS document for delta snapshot at L1 batch #{l1_batch_number} doesn't reference its base snapshot")
+            })?;
+            let base_document = Self::fetch_document(ipfs.as_ref(), &base_cid).await?;
+            anyhow::ensure!(
+                base_document.header.l1_batch_number == base_l1_batch_number,
+                "IPFS document {base_cid} referenced as the base for snapshot at L1 batch #{l1_batch_number} \
+                 is for L1 batch #{}, while #{base_l1_batch_number} is expected",
+                base_document.header.l1_batch_number
+            );
+            documents.push(document);
+            document = base_document;
+        }
+        documents.push(document);
+
+        Ok(Self { ipfs, documents })
+    }
+
+    async fn fetch_document(
+        ipfs: &dyn IpfsClient,
+        cid: &str,
+    ) -> anyhow::Result<SnapshotIpfsDocument> {
+        let bytes = ipfs
+            .fetch(cid)
+            .await
+            .with_context(|| format!("failed fetching IPFS object {cid}"))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed parsing IPFS object {cid} as a snapshot document"))
+    }
+
+    /// Returns L1 batch number of the anchored snapshot.
+    pub fn l1_batch_number(&self) -> L1BatchNumber {
+        self.documents[0].header.l1_batch_number
+    }
+
+    /// Returns an object store serving objects for all snapshots in the chain.
+    pub fn object_store(&self) -> Arc<dyn ObjectStore> {
+        let mut cids = HashMap::new();
+        for document in &self.documents {
+            let l1_batch_number = document.header.l1_batch_number;
+            cids.insert(
+                SnapshotFactoryDependencies::encode_key(l1_batch_number),
+                document.header.factory_deps_filepath.clone(),
+            );
+            for chunk in &document.header.storage_logs_chunks {
+                let key = SnapshotStorageLogsStorageKey {
+                    l1_batch_number,
+                    chunk_id: chunk.chunk_id,
+                };
+                // Keys don't depend on the storage log key type, so we use the default one.
+                cids.insert(
+                    <SnapshotStorageLogsChunk>::encode_key(key),
+                    chunk.filepath.clone(),
+                );
+            }
+        }
+        Arc::new(IpfsObjectStore {
+            ipfs: self.ipfs.clone(),
+            cids,
+        })
+    }
+
+    /// Wraps the main node client so that snapshot headers and manifests are taken from IPFS documents.
+    /// Other requests (L1 batch and L2 block details, and tokens) are still sent to the main node.
+    pub fn wrap_client(
+        &self,
+        inner: Box<dyn SnapshotsApplierMainNodeClient>,
+    ) -> Box<dyn SnapshotsApplierMainNodeClient> {
+        Box::new(IpfsSnapshotsClient {
+            inner,
+            documents: self.documents.clone(),
+        })
+    }
+}
+
+/// Read-only [`ObjectStore`] serving snapshot objects from IPFS.
+#[derive(Debug)]
+struct IpfsObjectStore {
+    ipfs: Arc<dyn IpfsClient>,
+    /// Object store keys mapped to IPFS CIDs.
+    cids: HashMap<String, String>,
+}
+
+#[async_trait]
+impl ObjectStore for IpfsObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let cid = (bucket == Bucket::StorageSnapshot)
+            .then(|| self.cids.get(key))
+            .flatten()
+            .ok_or_else(|| {
+                let message = format!("key `{key}` in bucket {bucket} is not in the IPFS snapshot");
+                ObjectStoreError::KeyNotFound(message.into())
+            })?;
+        self.ipfs
+            .fetch(cid)
+            .await
+            .map_err(|err| ObjectStoreError::Other {
+                is_transient: true,
+                source: err.into(),
+            })
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        _value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        let message =
+            format!("cannot put `{key}` to bucket {bucket}: IPFS snapshot store is read-only");
+        Err(ObjectStoreError::Other {
+            is_transient: false,
+            source: message.into(),
+        })
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        let message =
+            format!("cannot remove `{key}` from bucket {bucket}: IPFS snapshot store is read-only");
+        Err(ObjectStoreError::Other {
+            is_transient: false,
+            source: message.into(),
+        })
+    }
+
+    fn storage_prefix_raw(&self, _bucket: Bucket) -> String {
+        "ipfs".to_owned()
+    }
+}
+
+/// Main node client returning snapshot information from IPFS documents.
+struct IpfsSnapshotsClient {
+    inner: Box<dyn SnapshotsApplierMainNodeClient>,
+    documents: Vec<SnapshotIpfsDocument>,
+}
+
+impl fmt::Debug for IpfsSnapshotsClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let l1_batch_numbers: Vec<_> = self
+            .documents
+            .iter()
+            .map(|document| document.header.l1_batch_number)
+            .collect();
+        formatter
+            .debug_struct("IpfsSnapshotsClient")
+            .field("inner", &self.inner)
+            .field("l1_batch_numbers", &l1_batch_numbers)
+            .finish()
+    }
+}
+
+impl IpfsSnapshotsClient {
+    fn document(&self, l1_batch_number: L1BatchNumber) -> Option<&SnapshotIpfsDocument> {
+        self.documents
+            .iter()
+            .find(|document| document.header.l1_batch_number == l1_batch_number)
+    }
+}
+
+#[async_trait]
+impl SnapshotsApplierMainNodeClient for IpfsSnapshotsClient {
+    async fn fetch_l1_batch_details(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<api::L1BatchDetails>> {
+        self.inner.fetch_l1_batch_details(number).await
+    }
+
+    async fn fetch_l2_block_details(
+        &self,
+        number: L2BlockNumber,
+    ) -> EnrichedClientResult<Option<api::BlockDetails>> {
+        self.inner.fetch_l2_block_details(number).await
+    }
+
+    async fn fetch_newest_snapshot_l1_batch_number(
+        &self,
+    ) -> EnrichedClientResult<Option<L1BatchNumber>> {
+        Ok(Some(self.documents[0].header.l1_batch_number))
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotHeader>> {
+        Ok(self
+            .document(l1_batch_number)
+            .map(|document| document.header.clone()))
+    }
+
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+        Ok(self
+            .document(l1_batch_number)
+            .map(|document| document.manifest.clone()))
+    }
+
+    async fn fetch_tokens(
+        &self,
+        at_l2_block: L2BlockNumber,
+    ) -> EnrichedClientResult<Vec<TokenInfo>> {
+        self.inner.fetch_tokens(at_l2_block).await
+    }
+}
@@ -14,22 +14,25 @@ use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_types::{
     api,
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        SnapshotFactoryDependencies, SnapshotHeader, SnapshotManifest, SnapshotRecoveryStatus,
+        SnapshotStorageLog, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
     tokens::TokenInfo,
-    L1BatchNumber, L2BlockNumber, StorageKey, H256,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, StorageKey, H256,
 };
 use zksync_utils::bytecode::hash_bytecode;
 use zksync_web3_decl::{
     client::{DynClient, L2},
     error::{ClientRpcContext, EnrichedClientError, EnrichedClientResult},
-    jsonrpsee::core::client,
+    jsonrpsee::{core::client, types::error::METHOD_NOT_FOUND_CODE},
     namespaces::{EnNamespaceClient, SnapshotsNamespaceClient, ZksNamespaceClient},
 };
 
+pub use self::ipfs::IpfsSnapshotSource;
 use self::metrics::{InitialStage, StorageLogsChunksStage, METRICS};
 
+mod ipfs;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -86,6 +89,20 @@ impl SnapshotsApplierError {
             Self::Fatal(anyhow::Error::from(err).context(context))
         }
     }
+
+    /// Checks an object digest against the snapshot manifest. A mismatch is treated as a retryable error
+    /// since it may be caused by a transient object store issue; since objects are checked one by one,
+    /// the retry will only refetch objects that were not applied yet.
+    fn check_digest(expected: H256, actual: H256, object: fmt::Arguments<'_>) -> Result<(), Self> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(Self::Retryable(anyhow::anyhow!(
+                "{object} fetched from object store have digest {actual:?}, which differs from {expected:?} \
+                 specified in the snapshot manifest"
+            )))
+        }
+    }
 }
 
 impl From<DalError> for SnapshotsApplierError {
@@ -134,6 +151,12 @@ pub trait SnapshotsApplierMainNodeClient: fmt::Debug + Send + Sync {
         l1_batch_number: L1BatchNumber,
     ) -> EnrichedClientResult<Option<SnapshotHeader>>;
 
+    /// Returns `Ok(None)` if the main node doesn't provide a manifest for the snapshot.
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>>;
+
     async fn fetch_tokens(
         &self,
         at_l2_block: L2BlockNumber,
@@ -182,6 +205,29 @@ impl SnapshotsApplierMainNodeClient for Box<DynClient<L2>> {
             .await
     }
 
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+        let result = self
+            .get_snapshot_manifest(l1_batch_number)
+            .rpc_context("get_snapshot_manifest")
+            .with_arg("number", &l1_batch_number)
+            .await;
+        match result {
+            // Old main nodes don't support snapshot manifests.
+            Err(err)
+                if matches!(
+                    err.as_ref(),
+                    client::Error::Call(err) if err.code() == METHOD_NOT_FOUND_CODE
+                ) =>
+            {
+                Ok(None)
+            }
+            _ => result,
+        }
+    }
+
     async fn fetch_tokens(
         &self,
         at_l2_block: L2BlockNumber,
@@ -247,10 +293,19 @@ pub struct SnapshotApplierTaskStats {
     pub done_work: bool,
 }
 
+/// Expected signer of snapshot manifests.
+#[derive(Debug, Clone, Copy)]
+struct ManifestSigner {
+    address: Address,
+    /// L2 chain manifest signatures are bound to.
+    l2_chain_id: L2ChainId,
+}
+
 #[derive(Debug)]
 pub struct SnapshotsApplierTask {
     snapshot_l1_batch: Option<L1BatchNumber>,
     drop_storage_key_preimages: bool,
+    manifest_signer: Option<ManifestSigner>,
     config: SnapshotsApplierConfig,
     health_updater: HealthUpdater,
     connection_pool: ConnectionPool<Core>,
@@ -268,6 +323,7 @@ impl SnapshotsApplierTask {
         Self {
             snapshot_l1_batch: None,
             drop_storage_key_preimages: false,
+            manifest_signer: None,
             config,
             health_updater: ReactiveHealthCheck::new("snapshot_recovery").1,
             connection_pool,
@@ -326,6 +382,16 @@ impl SnapshotsApplierTask {
         self.drop_storage_key_preimages = true;
     }
 
+    /// Requires manifests of all recovered snapshots to be signed by the specified address (normally, the operator
+    /// of the main node). If not set, unsigned manifests are accepted, and snapshots without manifests are recovered
+    /// without checking their objects. Signatures are checked for the specified L2 chain.
+    pub fn require_signed_manifests(&mut self, signer: Address, l2_chain_id: L2ChainId) {
+        self.manifest_signer = Some(ManifestSigner {
+            address: signer,
+            l2_chain_id,
+        });
+    }
+
     /// Returns the health check for snapshot recovery.
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
@@ -382,13 +448,159 @@ impl SnapshotsApplierTask {
     }
 }
 
+/// Snapshot in a [`SnapshotChain`].
+#[derive(Debug, Clone)]
+struct ChainedSnapshot {
+    version: SnapshotVersion,
+    l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot; only set for delta snapshots.
+    base_l1_batch_number: Option<L1BatchNumber>,
+    chunk_count: usize,
+    /// Integrity manifest used to check snapshot objects. `None` if the main node doesn't provide a manifest
+    /// for the snapshot.
+    manifest: Option<SnapshotManifest>,
+}
+
+impl ChainedSnapshot {
+    fn check_manifest(
+        &self,
+        manifest: &SnapshotManifest,
+        expected_signer: Option<ManifestSigner>,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = self.l1_batch_number;
+        anyhow::ensure!(
+            manifest.version == u16::from(self.version)
+                && manifest.l1_batch_number == l1_batch_number
+                && manifest.base_l1_batch_number == self.base_l1_batch_number,
+            "manifest for snapshot at L1 batch #{l1_batch_number} doesn't match the snapshot header: {manifest:?}"
+        );
+        anyhow::ensure!(
+            manifest.storage_logs_chunk_digests.len() == self.chunk_count,
+            "manifest for snapshot at L1 batch #{l1_batch_number} has {} storage log chunk digests, while the snapshot has {} chunks",
+            manifest.storage_logs_chunk_digests.len(),
+            self.chunk_count
+        );
+
+        if let Some(expected_signer) = expected_signer {
+            let ManifestSigner {
+                address: expected_signer,
+                l2_chain_id,
+            } = expected_signer;
+            let signer = manifest.recover_signer(l2_chain_id)?.with_context(|| {
+                format!("manifest for snapshot at L1 batch #{l1_batch_number} is not signed")
+            })?;
+            anyhow::ensure!(
+                signer == expected_signer,
+                "manifest for snapshot at L1 batch #{l1_batch_number} is signed by {signer:?}, while {expected_signer:?} is expected"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Chain of snapshots to recover from: a full snapshot followed by zero or more delta snapshots.
+///
+/// Snapshots in the chain are applied one after another. Storage log chunks of all snapshots are tracked
+/// in a single [`SnapshotRecoveryStatus`] in the chain order. The resulting storage state is checked against
+/// the root hash of the L1 batch of the last snapshot (also recorded in the recovery status) when the Merkle tree
+/// is recovered.
+#[derive(Debug, Clone)]
+struct SnapshotChain {
+    /// Snapshots in the order of application; the first snapshot is always full.
+    snapshots: Vec<ChainedSnapshot>,
+}
+
+impl SnapshotChain {
+    /// Fetches the chain ending with the specified snapshot from the main node, together with snapshot manifests.
+    /// If `manifest_signer` is specified, all snapshots in the chain are required to have manifests signed by it.
+    async fn fetch(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        snapshot: &SnapshotHeader,
+        manifest_signer: Option<ManifestSigner>,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let mut snapshots = vec![];
+        let mut snapshot = snapshot.clone();
+        loop {
+            let version = SnapshotRecoveryStrategy::check_snapshot_version(snapshot.version)?;
+            let l1_batch_number = snapshot.l1_batch_number;
+            let base_l1_batch_number = if version.is_delta() {
+                let base_l1_batch_number = snapshot.base_l1_batch_number.with_context(|| {
+                    format!("delta snapshot for L1 batch #{l1_batch_number} doesn't specify its base snapshot")
+                })?;
+                if base_l1_batch_number >= l1_batch_number {
+                    let err = anyhow::anyhow!(
+                        "delta snapshot for L1 batch #{l1_batch_number} has invalid base L1 batch #{base_l1_batch_number}"
+                    );
+                    return Err(err.into());
+                }
+                Some(base_l1_batch_number)
+            } else {
+                None
+            };
+            let mut chained_snapshot = ChainedSnapshot {
+                version,
+                l1_batch_number,
+                base_l1_batch_number,
+                chunk_count: snapshot.storage_logs_chunks.len(),
+                manifest: None,
+            };
+            let manifest = main_node_client
+                .fetch_snapshot_manifest(l1_batch_number)
+                .await?;
+            if let Some(manifest) = manifest {
+                chained_snapshot.check_manifest(&manifest, manifest_signer)?;
+                chained_snapshot.manifest = Some(manifest);
+            } else if manifest_signer.is_some() {
+                let err = anyhow::anyhow!(
+                    "main node doesn't provide a manifest for snapshot at L1 batch #{l1_batch_number}, \
+                     but signed manifests are required"
+                );
+                return Err(err.into());
+            } else {
+                tracing::warn!(
+                    "Main node doesn't provide a manifest for snapshot at L1 batch #{l1_batch_number}; \
+                     snapshot objects will not be checked"
+                );
+            }
+            snapshots.push(chained_snapshot);
+
+            let Some(base_l1_batch_number) = base_l1_batch_number else {
+                snapshots.reverse();
+                return Ok(Self { snapshots });
+            };
+            snapshot = main_node_client
+                .fetch_snapshot(base_l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "base snapshot for L1 batch #{base_l1_batch_number} (referenced by the delta snapshot \
+                         for L1 batch #{l1_batch_number}) is not present on main node"
+                    )
+                })?;
+            tracing::info!(
+                "Delta snapshot for L1 batch #{l1_batch_number} is based on the snapshot for L1 batch #{base_l1_batch_number} \
+                 with version {version}, storage logs are divided into {chunk_count} chunk(s)",
+                version = snapshot.version,
+                chunk_count = snapshot.storage_logs_chunks.len()
+            );
+        }
+    }
+
+    fn total_chunk_count(&self) -> usize {
+        self.snapshots
+            .iter()
+            .map(|snapshot| snapshot.chunk_count)
+            .sum()
+    }
+}
+
 /// Strategy determining how snapshot recovery should proceed.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum SnapshotRecoveryStrategy {
     /// Snapshot recovery should proceed from scratch with the specified params.
-    New(SnapshotVersion),
+    New(SnapshotChain),
     /// Snapshot recovery should continue with the specified params.
-    Resumed(SnapshotVersion),
+    Resumed(SnapshotChain),
     /// Snapshot recovery has already been completed.
     Completed,
 }
@@ -398,6 +610,7 @@ impl SnapshotRecoveryStrategy {
         storage: &mut Connection<'_, Core>,
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
+        manifest_signer: Option<ManifestSigner>,
     ) -> Result<(Self, SnapshotRecoveryStatus), SnapshotsApplierError> {
         let latency =
             METRICS.initial_stage_duration[&InitialStage::FetchMetadataFromMainNode].start();
@@ -421,11 +634,21 @@ impl SnapshotRecoveryStrategy {
                 })?;
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
-            let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
+            let snapshot_chain =
+                SnapshotChain::fetch(main_node_client, &snapshot_header, manifest_signer).await?;
+            let chunk_count = applied_snapshot_status.storage_logs_chunks_processed.len();
+            if snapshot_chain.total_chunk_count() != chunk_count {
+                let err = anyhow::anyhow!(
+                    "snapshot chain for L1 batch #{l1_batch_number} returned by main node has {} storage log chunks, \
+                     while recovery was started with {chunk_count} chunks",
+                    snapshot_chain.total_chunk_count()
+                );
+                return Err(SnapshotsApplierError::Fatal(err));
+            }
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((Self::Resumed(snapshot_chain), applied_snapshot_status))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -435,8 +658,12 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
-                Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
+            let (recovery_status, snapshot_chain) = Self::create_fresh_recovery_status(
+                main_node_client,
+                snapshot_l1_batch,
+                manifest_signer,
+            )
+            .await?;
 
             let storage_logs_count = storage
                 .storage_logs_dal()
@@ -453,14 +680,15 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(snapshot_chain), recovery_status))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+        manifest_signer: Option<ManifestSigner>,
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotChain), SnapshotsApplierError> {
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -480,7 +708,8 @@ impl SnapshotRecoveryStrategy {
             version = snapshot.version,
             chunk_count = snapshot.storage_logs_chunks.len()
         );
-        let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+        let snapshot_chain =
+            SnapshotChain::fetch(main_node_client, &snapshot, manifest_signer).await?;
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -516,9 +745,9 @@ impl SnapshotRecoveryStrategy {
             l2_block_timestamp: l2_block.base.timestamp,
             l2_block_hash,
             protocol_version,
-            storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
+            storage_logs_chunks_processed: vec![false; snapshot_chain.total_chunk_count()],
         };
-        Ok((status, snapshot_version))
+        Ok((status, snapshot_chain))
     }
 
     fn check_snapshot_version(raw_version: u16) -> anyhow::Result<SnapshotVersion> {
@@ -528,9 +757,16 @@ impl SnapshotRecoveryStrategy {
             )
         })?;
         anyhow::ensure!(
-            matches!(version, SnapshotVersion::Version0 | SnapshotVersion::Version1),
+            matches!(
+                version,
+                SnapshotVersion::Version0 | SnapshotVersion::Version1 | SnapshotVersion::Version2
+            ),
             "Cannot recover from a snapshot with version {version:?}; the only supported versions are {:?}",
-            [SnapshotVersion::Version0, SnapshotVersion::Version1]
+            [
+                SnapshotVersion::Version0,
+                SnapshotVersion::Version1,
+                SnapshotVersion::Version2
+            ]
         );
         Ok(version)
     }
@@ -544,21 +780,39 @@ enum StorageLogs {
 }
 
 impl StorageLogs {
+    /// Loads a chunk from the object store. If `expected_digest` is specified, checks that the loaded chunk matches it.
     async fn load(
         blob_store: &dyn ObjectStore,
         key: SnapshotStorageLogsStorageKey,
         version: SnapshotVersion,
-    ) -> Result<Self, ObjectStoreError> {
-        match version {
+        expected_digest: Option<H256>,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let map_err = |err| {
+            let context = format!("cannot fetch storage logs {key:?} from object store");
+            SnapshotsApplierError::object_store(err, context)
+        };
+        let (logs, digest) = match version {
             SnapshotVersion::Version0 => {
-                let logs: SnapshotStorageLogsChunk<StorageKey> = blob_store.get(key).await?;
-                Ok(Self::V0(logs.storage_logs))
+                let chunk: SnapshotStorageLogsChunk<StorageKey> =
+                    blob_store.get(key).await.map_err(map_err)?;
+                let digest = expected_digest.map(|_| chunk.digest());
+                (Self::V0(chunk.storage_logs), digest)
             }
-            SnapshotVersion::Version1 => {
-                let logs: SnapshotStorageLogsChunk = blob_store.get(key).await?;
-                Ok(Self::V1(logs.storage_logs))
+            SnapshotVersion::Version1 | SnapshotVersion::Version2 => {
+                let chunk: SnapshotStorageLogsChunk = blob_store.get(key).await.map_err(map_err)?;
+                let digest = expected_digest.map(|_| chunk.digest());
+                (Self::V1(chunk.storage_logs), digest)
             }
+        };
+
+        if let (Some(expected_digest), Some(digest)) = (expected_digest, digest) {
+            SnapshotsApplierError::check_digest(
+                expected_digest,
+                digest,
+                format_args!("storage logs {key:?}"),
+            )?;
         }
+        Ok(logs)
     }
 
     fn len(&self) -> usize {
@@ -626,7 +880,7 @@ struct SnapshotsApplier<'a> {
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
-    snapshot_version: SnapshotVersion,
+    snapshot_chain: SnapshotChain,
     max_concurrency: usize,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
@@ -655,13 +909,14 @@ impl<'a> SnapshotsApplier<'a> {
             &mut storage_transaction,
             main_node_client,
             task.snapshot_l1_batch,
+            task.manifest_signer,
         )
         .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
-        let (created_from_scratch, snapshot_version) = match strategy {
+        let (created_from_scratch, snapshot_chain) = match &strategy {
             SnapshotRecoveryStrategy::Completed => return Ok((strategy, applied_snapshot_status)),
-            SnapshotRecoveryStrategy::New(version) => (true, version),
-            SnapshotRecoveryStrategy::Resumed(version) => (false, version),
+            SnapshotRecoveryStrategy::New(chain) => (true, chain.clone()),
+            SnapshotRecoveryStrategy::Resumed(chain) => (false, chain.clone()),
         };
 
         let mut this = Self {
@@ -670,7 +925,7 @@ impl<'a> SnapshotsApplier<'a> {
             blob_store: task.blob_store.as_ref(),
             applied_snapshot_status,
             health_updater,
-            snapshot_version,
+            snapshot_chain,
             max_concurrency: task.config.max_concurrency.get(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
@@ -756,35 +1011,47 @@ impl<'a> SnapshotsApplier<'a> {
     ) -> Result<(), SnapshotsApplierError> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
-        tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
-        let factory_deps: SnapshotFactoryDependencies =
-            self.blob_store.get(l1_batch_number).await.map_err(|err| {
-                let context = format!(
-                    "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
-                );
-                SnapshotsApplierError::object_store(err, context)
-            })?;
-        tracing::debug!(
-            "Fetched {} factory dependencies from object store",
-            factory_deps.factory_deps.len()
-        );
+        // Delta snapshots only contain factory deps added after their base snapshot, so we need to apply the entire chain.
+        for snapshot in &self.snapshot_chain.snapshots {
+            let l1_batch_number = snapshot.l1_batch_number;
+            tracing::debug!(
+                "Fetching factory dependencies for L1 batch #{l1_batch_number} from object store"
+            );
+            let factory_deps: SnapshotFactoryDependencies =
+                self.blob_store.get(l1_batch_number).await.map_err(|err| {
+                    let context = format!(
+                        "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            tracing::debug!(
+                "Fetched {} factory dependencies from object store",
+                factory_deps.factory_deps.len()
+            );
+            if let Some(manifest) = &snapshot.manifest {
+                SnapshotsApplierError::check_digest(
+                    manifest.factory_deps_digest,
+                    factory_deps.digest(),
+                    format_args!("factory deps for L1 batch #{l1_batch_number}"),
+                )?;
+            }
 
-        // we cannot insert all factory deps because of field size limit triggered by UNNEST
-        // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
-        // there were around 100 thousand contracts on mainnet, where this issue first manifested
-        for chunk in factory_deps.factory_deps.chunks(1000) {
-            let chunk_deps_hashmap: HashMap<H256, Vec<u8>> = chunk
-                .iter()
-                .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0.clone()))
-                .collect();
-            storage
-                .factory_deps_dal()
-                .insert_factory_deps(
-                    self.applied_snapshot_status.l2_block_number,
-                    &chunk_deps_hashmap,
-                )
-                .await?;
+            // we cannot insert all factory deps because of field size limit triggered by UNNEST
+            // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
+            // there were around 100 thousand contracts on mainnet, where this issue first manifested
+            for chunk in factory_deps.factory_deps.chunks(1000) {
+                let chunk_deps_hashmap: HashMap<H256, Vec<u8>> = chunk
+                    .iter()
+                    .map(|dep| (hash_bytecode(&dep.bytecode.0), dep.bytecode.0.clone()))
+                    .collect();
+                storage
+                    .factory_deps_dal()
+                    .insert_factory_deps(
+                        self.applied_snapshot_status.l2_block_number,
+                        &chunk_deps_hashmap,
+                    )
+                    .await?;
+            }
         }
 
         let latency = latency.observe();
@@ -834,11 +1101,48 @@ impl<'a> SnapshotsApplier<'a> {
         Ok(())
     }
 
-    #[tracing::instrument(level = "debug", err, skip(self, semaphore))]
+    /// Applies a storage logs chunk from a delta snapshot. Logs for keys initially written after the base snapshot
+    /// are inserted; other logs update values of the already recovered logs.
+    async fn apply_changed_storage_logs_chunk(
+        &self,
+        base_l1_batch_number: L1BatchNumber,
+        storage_logs: Vec<SnapshotStorageLog>,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), SnapshotsApplierError> {
+        let l2_block_number = self.applied_snapshot_status.l2_block_number;
+        let (new_logs, updated_logs): (Vec<_>, Vec<_>) = storage_logs
+            .into_iter()
+            .partition(|log| log.l1_batch_number_of_initial_write > base_l1_batch_number);
+
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs_from_snapshot(l2_block_number, &new_logs)
+            .await?;
+        self.insert_initial_writes_chunk(&new_logs, storage).await?;
+
+        let updated_count = storage
+            .storage_logs_dal()
+            .update_storage_logs_from_snapshot(l2_block_number, &updated_logs)
+            .await?;
+        if updated_count != updated_logs.len() {
+            let err = anyhow::anyhow!(
+                "delta snapshot chunk updates {} storage logs, but only {updated_count} of them were recovered previously; \
+                 the snapshot chain may be corrupted",
+                updated_logs.len()
+            );
+            return Err(SnapshotsApplierError::Fatal(err));
+        }
+        Ok(())
+    }
+
+    /// `chunk_id` is the chunk index in the recovery status, and `snapshot_chunk_id` is the chunk index in `snapshot`.
+    #[tracing::instrument(level = "debug", err, skip(self, semaphore, snapshot))]
     async fn recover_storage_logs_single_chunk(
         &self,
         semaphore: &Semaphore,
+        snapshot: &ChainedSnapshot,
         chunk_id: u64,
+        snapshot_chunk_id: u64,
     ) -> Result<(), SnapshotsApplierError> {
         // `unwrap()` is safe: the semaphore is never closed
         let _permit = semaphore.acquire().await.unwrap();
@@ -848,17 +1152,20 @@ impl<'a> SnapshotsApplier<'a> {
             METRICS.storage_logs_chunks_duration[&StorageLogsChunksStage::LoadFromGcs].start();
 
         let storage_key = SnapshotStorageLogsStorageKey {
-            chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
+            chunk_id: snapshot_chunk_id,
+            l1_batch_number: snapshot.l1_batch_number,
         };
-        let mut storage_logs =
-            StorageLogs::load(self.blob_store, storage_key, self.snapshot_version)
-                .await
-                .map_err(|err| {
-                    let context =
-                        format!("cannot fetch storage logs {storage_key:?} from object store");
-                    SnapshotsApplierError::object_store(err, context)
-                })?;
+        let expected_digest = snapshot
+            .manifest
+            .as_ref()
+            .map(|manifest| manifest.storage_logs_chunk_digests[snapshot_chunk_id as usize]);
+        let mut storage_logs = StorageLogs::load(
+            self.blob_store,
+            storage_key,
+            snapshot.version,
+            expected_digest,
+        )
+        .await?;
 
         storage_logs.validate(&self.applied_snapshot_status)?;
         if self.drop_storage_key_preimages {
@@ -881,11 +1188,21 @@ impl<'a> SnapshotsApplier<'a> {
 
         tracing::info!("Loading {} storage logs into Postgres", storage_logs.len());
 
-        self.insert_storage_logs_chunk(&storage_logs, &mut storage_transaction)
-            .await?;
-        let storage_logs = storage_logs.without_preimages();
-        self.insert_initial_writes_chunk(&storage_logs, &mut storage_transaction)
+        if let Some(base_l1_batch_number) = snapshot.base_l1_batch_number {
+            let storage_logs = storage_logs.without_preimages();
+            self.apply_changed_storage_logs_chunk(
+                base_l1_batch_number,
+                storage_logs,
+                &mut storage_transaction,
+            )
             .await?;
+        } else {
+            self.insert_storage_logs_chunk(&storage_logs, &mut storage_transaction)
+                .await?;
+            let storage_logs = storage_logs.without_preimages();
+            self.insert_initial_writes_chunk(&storage_logs, &mut storage_transaction)
+                .await?;
+        }
 
         storage_transaction
             .snapshot_recovery_dal()
@@ -908,16 +1225,23 @@ impl<'a> SnapshotsApplier<'a> {
         );
         let semaphore = Semaphore::new(effective_concurrency);
 
-        let tasks = self
-            .applied_snapshot_status
-            .storage_logs_chunks_processed
-            .iter()
-            .enumerate()
-            .filter(|(_, is_processed)| !**is_processed)
-            .map(|(chunk_id, _)| {
-                self.recover_storage_logs_single_chunk(&semaphore, chunk_id as u64)
-            });
-        futures::future::try_join_all(tasks).await?;
+        // Each delta snapshot must be applied after all chunks of the preceding snapshots are applied.
+        let chunks_processed = &self.applied_snapshot_status.storage_logs_chunks_processed;
+        let mut chunk_id_offset = 0;
+        for snapshot in &self.snapshot_chain.snapshots {
+            let tasks = (0..snapshot.chunk_count)
+                .filter(|&snapshot_chunk_id| !chunks_processed[chunk_id_offset + snapshot_chunk_id])
+                .map(|snapshot_chunk_id| {
+                    self.recover_storage_logs_single_chunk(
+                        &semaphore,
+                        snapshot,
+                        (chunk_id_offset + snapshot_chunk_id) as u64,
+                        snapshot_chunk_id as u64,
+                    )
+                });
+            futures::future::try_join_all(tasks).await?;
+            chunk_id_offset += snapshot.chunk_count;
+        }
 
         let mut storage = self
             .connection_pool
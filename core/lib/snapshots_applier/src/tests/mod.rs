@@ -8,17 +8,21 @@ use std::{
 use assert_matches::assert_matches;
 use test_casing::test_casing;
 use tokio::sync::Barrier;
+use zksync_anchoring::{IpfsClient, MintlayerClient, MockIpfsClient, MockMintlayerClient};
 use zksync_health_check::CheckHealth;
-use zksync_object_store::MockObjectStore;
+use zksync_object_store::{Bucket, MockObjectStore, StoredObject};
 use zksync_types::{
     api::{BlockDetails, L1BatchDetails},
     block::L1BatchHeader,
-    get_code_key, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    get_code_key,
+    snapshots::{SnapshotIpfsDocument, SnapshotStorageLogsChunkMetadata},
+    K256PrivateKey, L1BatchNumber, L2ChainId, ProtocolVersion, ProtocolVersionId,
 };
 
 use self::utils::{
     mock_l2_block_header, mock_recovery_status, mock_snapshot_header, mock_tokens, prepare_clients,
-    random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
+    prepare_clients_for_chain, random_storage_logs, MockMainNodeClient, MockSnapshot,
+    ObjectStoreWithErrors,
 };
 use super::*;
 use crate::tests::utils::HangingObjectStore;
@@ -212,6 +216,356 @@ async fn applier_recovers_v0_snapshot(drop_storage_key_preimages: bool) {
     }
 }
 
+fn mock_snapshot_chain(status: &SnapshotRecoveryStatus) -> Vec<MockSnapshot> {
+    let base_logs = random_storage_logs::<H256>(L1BatchNumber(100), 200);
+    let new_logs = |l1_batch_number, first_index: u64, count| {
+        let mut logs = random_storage_logs::<H256>(l1_batch_number, count);
+        for log in &mut logs {
+            log.enumeration_index += first_index - 1;
+        }
+        logs
+    };
+    let updated_logs = |logs: &[SnapshotStorageLog], step| {
+        let logs = logs.iter().step_by(step).map(|log| SnapshotStorageLog {
+            value: H256::random(),
+            ..log.clone()
+        });
+        logs.collect::<Vec<_>>()
+    };
+
+    // The first delta updates some of the existing logs and adds new ones.
+    let first_delta_new_logs = new_logs(L1BatchNumber(110), 201, 10);
+    let mut first_delta_logs = updated_logs(&base_logs, 5);
+    first_delta_logs.extend_from_slice(&first_delta_new_logs);
+    // The second delta overrides some of the logs updated or added in the first delta.
+    let mut second_delta_logs = updated_logs(&base_logs, 10);
+    second_delta_logs.extend(updated_logs(&first_delta_new_logs, 3));
+    second_delta_logs.extend(new_logs(status.l1_batch_number, 211, 5));
+
+    vec![
+        MockSnapshot {
+            l1_batch_number: L1BatchNumber(100),
+            chunk_count: 2,
+            storage_logs: base_logs,
+        },
+        MockSnapshot {
+            l1_batch_number: L1BatchNumber(110),
+            chunk_count: 3,
+            storage_logs: first_delta_logs,
+        },
+        MockSnapshot {
+            l1_batch_number: status.l1_batch_number,
+            chunk_count: 1,
+            storage_logs: second_delta_logs,
+        },
+    ]
+}
+
+#[tokio::test]
+async fn applier_recovers_delta_snapshot_chain() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut expected_status = mock_recovery_status();
+    let chain = mock_snapshot_chain(&expected_status);
+    let (object_store, client) = prepare_clients_for_chain(&expected_status, &chain).await;
+    // Chunks of all snapshots in the chain are tracked in the recovery status.
+    let chunk_count = chain
+        .iter()
+        .map(|snapshot| snapshot.chunk_count as usize)
+        .sum();
+    expected_status.storage_logs_chunks_processed = vec![true; chunk_count];
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    let stats = task.run().await.unwrap();
+    assert!(stats.done_work);
+
+    let mut expected_logs = HashMap::new();
+    for snapshot in &chain {
+        expected_logs.extend(snapshot.storage_logs.iter().map(|log| (log.key, log)));
+    }
+    assert_eq!(expected_logs.len(), 215);
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status.unwrap(), expected_status);
+
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, expected_status.l2_block_number);
+    }
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    assert_eq!(all_initial_writes.len(), expected_logs.len());
+    for initial_write in all_initial_writes {
+        let expected_log = expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            expected_log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, expected_log.enumeration_index);
+    }
+
+    for snapshot in &chain {
+        let factory_dep = snapshot.factory_dep();
+        let persisted_dep = storage
+            .factory_deps_dal()
+            .get_sealed_factory_dep(hash_bytecode(&factory_dep))
+            .await
+            .unwrap();
+        assert_eq!(persisted_dep, Some(factory_dep));
+    }
+}
+
+#[tokio::test]
+async fn applier_errors_with_missing_base_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let chain = mock_snapshot_chain(&expected_status);
+    let (object_store, mut client) = prepare_clients_for_chain(&expected_status, &chain).await;
+    client.fetch_snapshot_responses.remove(&L1BatchNumber(100));
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    let err = task.run().await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("is not present on main node"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn applier_checks_signed_snapshot_manifests() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let chain = mock_snapshot_chain(&expected_status);
+    let (object_store, mut client) = prepare_clients_for_chain(&expected_status, &chain).await;
+    let operator_key = K256PrivateKey::random();
+    let l2_chain_id = L2ChainId::default();
+    for manifest in client.fetch_snapshot_manifest_responses.values_mut() {
+        manifest.sign(l2_chain_id, &operator_key).unwrap();
+    }
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    task.require_signed_manifests(operator_key.address(), l2_chain_id);
+    let stats = task.run().await.unwrap();
+    assert!(stats.done_work);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum InvalidSignature {
+    Missing,
+    OtherKey,
+    OtherChain,
+}
+
+const INVALID_SIGNATURES: [InvalidSignature; 3] = [
+    InvalidSignature::Missing,
+    InvalidSignature::OtherKey,
+    InvalidSignature::OtherChain,
+];
+
+#[test_casing(3, INVALID_SIGNATURES)]
+#[tokio::test]
+async fn applier_errors_on_invalid_manifest_signature(kind: InvalidSignature) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let chain = mock_snapshot_chain(&expected_status);
+    let (object_store, mut client) = prepare_clients_for_chain(&expected_status, &chain).await;
+    let operator_key = K256PrivateKey::random();
+    let l2_chain_id = L2ChainId::default();
+    let signing = match kind {
+        InvalidSignature::Missing => None,
+        InvalidSignature::OtherKey => Some((K256PrivateKey::random(), l2_chain_id)),
+        InvalidSignature::OtherChain => Some((operator_key.clone(), L2ChainId::from(271))),
+    };
+    if let Some((key, chain_id)) = signing {
+        for manifest in client.fetch_snapshot_manifest_responses.values_mut() {
+            manifest.sign(chain_id, &key).unwrap();
+        }
+    }
+
+    let mut task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    task.require_signed_manifests(operator_key.address(), l2_chain_id);
+    let err = task.run().await.unwrap_err();
+    let expected_message = match kind {
+        InvalidSignature::Missing => "is not signed",
+        InvalidSignature::OtherKey | InvalidSignature::OtherChain => "is signed by",
+    };
+    assert!(format!("{err:#}").contains(expected_message), "{err:#}");
+}
+
+#[tokio::test]
+async fn applier_errors_on_snapshot_chunk_digest_mismatch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let chain = mock_snapshot_chain(&expected_status);
+    let (object_store, client) = prepare_clients_for_chain(&expected_status, &chain).await;
+    // Replace a chunk of the base snapshot with a chunk not matching the manifest.
+    let chunk_key = SnapshotStorageLogsStorageKey {
+        l1_batch_number: L1BatchNumber(100),
+        chunk_id: 1,
+    };
+    let corrupted_chunk = SnapshotStorageLogsChunk {
+        storage_logs: random_storage_logs::<H256>(L1BatchNumber(100), 10),
+    };
+    object_store.put(chunk_key, &corrupted_chunk).await.unwrap();
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool,
+        Box::new(client),
+        object_store,
+    );
+    let err = task.run().await.unwrap_err();
+    assert!(format!("{err:#}").contains("differs from"), "{err:#}");
+}
+
+/// Publishes snapshots prepared by [`prepare_clients_for_chain()`] to IPFS in the same way as the snapshot creator does,
+/// and returns the ID of the Mintlayer transaction anchoring the last snapshot.
+async fn publish_snapshot_chain(
+    object_store: &dyn ObjectStore,
+    client: &MockMainNodeClient,
+    chain: &[MockSnapshot],
+    ipfs: &MockIpfsClient,
+    mintlayer: &MockMintlayerClient,
+) -> String {
+    let mut base_document_cid = None;
+    for snapshot in chain {
+        let l1_batch_number = snapshot.l1_batch_number;
+        let upload = |key: String| async move {
+            let bytes = object_store
+                .get_raw(Bucket::StorageSnapshot, &key)
+                .await
+                .unwrap();
+            ipfs.upload(&key, bytes).await.unwrap()
+        };
+
+        let mut header = client
+            .fetch_snapshot(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        header.factory_deps_filepath =
+            upload(SnapshotFactoryDependencies::encode_key(l1_batch_number)).await;
+        header.storage_logs_chunks = vec![];
+        for chunk_id in 0..snapshot.chunk_count {
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            let filepath = upload(<SnapshotStorageLogsChunk>::encode_key(key)).await;
+            header
+                .storage_logs_chunks
+                .push(SnapshotStorageLogsChunkMetadata { chunk_id, filepath });
+        }
+        let manifest = client
+            .fetch_snapshot_manifest(l1_batch_number)
+            .await
+            .unwrap()
+            .unwrap();
+        let document = SnapshotIpfsDocument {
+            header,
+            manifest,
+            base_document_cid: base_document_cid.take(),
+        };
+        let document = serde_json::to_vec(&document).unwrap();
+        base_document_cid = Some(ipfs.upload("document", document).await.unwrap());
+    }
+
+    // Add an unrelated CID to check that the anchored document is correctly identified.
+    let unrelated_cid = ipfs.upload("unrelated", b"test".to_vec()).await.unwrap();
+    let anchored_data = format!("{unrelated_cid},{}", base_document_cid.unwrap());
+    mintlayer
+        .deposit_data(anchored_data.as_bytes())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn applier_recovers_snapshot_chain_from_ipfs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut expected_status = mock_recovery_status();
+    let chain = mock_snapshot_chain(&expected_status);
+    let (object_store, mut client) = prepare_clients_for_chain(&expected_status, &chain).await;
+    let chunk_count = chain
+        .iter()
+        .map(|snapshot| snapshot.chunk_count as usize)
+        .sum();
+    expected_status.storage_logs_chunks_processed = vec![true; chunk_count];
+
+    let ipfs = MockIpfsClient::default();
+    let mintlayer = MockMintlayerClient::default();
+    let anchor_tx_id =
+        publish_snapshot_chain(object_store.as_ref(), &client, &chain, &ipfs, &mintlayer).await;
+
+    // Snapshot information must be taken from IPFS rather than from the main node.
+    client.fetch_newest_snapshot_response = None;
+    client.fetch_snapshot_responses.clear();
+    client.fetch_snapshot_manifest_responses.clear();
+
+    let source = IpfsSnapshotSource::resolve(Arc::new(ipfs), &mintlayer, &anchor_tx_id)
+        .await
+        .unwrap();
+    assert_eq!(source.l1_batch_number(), expected_status.l1_batch_number);
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        source.wrap_client(Box::new(client)),
+        source.object_store(),
+    );
+    let stats = task.run().await.unwrap();
+    assert!(stats.done_work);
+
+    let mut storage = pool.connection().await.unwrap();
+    let status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(status.unwrap(), expected_status);
+
+    let mut expected_logs = HashMap::new();
+    for snapshot in &chain {
+        expected_logs.extend(snapshot.storage_logs.iter().map(|log| (log.key, log)));
+    }
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+}
+
 #[tokio::test]
 async fn applier_recovers_explicitly_specified_snapshot() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -393,6 +747,14 @@ async fn health_status_immediately_after_task_start() {
             future::pending().await
         }
 
+        async fn fetch_snapshot_manifest(
+            &self,
+            _l1_batch_number: L1BatchNumber,
+        ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+            self.0.wait().await;
+            future::pending().await
+        }
+
         async fn fetch_tokens(
             &self,
             _at_l2_block: L2BlockNumber,
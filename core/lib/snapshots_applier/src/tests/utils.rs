@@ -13,9 +13,10 @@ use zksync_types::{
     api,
     block::L2BlockHeader,
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader,
-        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
-        SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotHeader, SnapshotManifest, SnapshotRecoveryStatus, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey,
+        SnapshotVersion,
     },
     tokens::{TokenInfo, TokenMetadata},
     web3::Bytes,
@@ -53,6 +54,9 @@ pub(super) struct MockMainNodeClient {
     pub fetch_l1_batch_responses: HashMap<L1BatchNumber, api::L1BatchDetails>,
     pub fetch_l2_block_responses: HashMap<L2BlockNumber, api::BlockDetails>,
     pub fetch_newest_snapshot_response: Option<SnapshotHeader>,
+    /// Snapshots other than the newest one (e.g., bases of the newest delta snapshot).
+    pub fetch_snapshot_responses: HashMap<L1BatchNumber, SnapshotHeader>,
+    pub fetch_snapshot_manifest_responses: HashMap<L1BatchNumber, SnapshotManifest>,
     pub tokens_response: Vec<TokenInfo>,
     pub tokens_response_error: Arc<RwLock<Option<EnrichedClientError>>>,
 }
@@ -98,10 +102,24 @@ impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> EnrichedClientResult<Option<SnapshotHeader>> {
-        Ok(self
+        let newest_snapshot = self
             .fetch_newest_snapshot_response
             .clone()
-            .filter(|response| response.l1_batch_number == l1_batch_number))
+            .filter(|response| response.l1_batch_number == l1_batch_number);
+        Ok(
+            newest_snapshot
+                .or_else(|| self.fetch_snapshot_responses.get(&l1_batch_number).cloned()),
+        )
+    }
+
+    async fn fetch_snapshot_manifest(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<SnapshotManifest>> {
+        Ok(self
+            .fetch_snapshot_manifest_responses
+            .get(&l1_batch_number)
+            .cloned())
     }
 
     async fn fetch_tokens(
@@ -283,6 +301,7 @@ pub(super) fn mock_snapshot_header(
         version,
         l1_batch_number: status.l1_batch_number,
         l2_block_number: status.l2_block_number,
+        base_l1_batch_number: None,
         storage_logs_chunks: (0..status.storage_logs_chunks_processed.len() as u64)
             .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
                 chunk_id,
@@ -349,6 +368,117 @@ where
     (object_store, client)
 }
 
+/// Snapshot in a chain prepared by [`prepare_clients_for_chain()`].
+#[derive(Debug)]
+pub(super) struct MockSnapshot {
+    pub l1_batch_number: L1BatchNumber,
+    pub chunk_count: u64,
+    pub storage_logs: Vec<SnapshotStorageLog>,
+}
+
+impl MockSnapshot {
+    pub fn factory_dep(&self) -> Vec<u8> {
+        vec![self.l1_batch_number.0 as u8; 32]
+    }
+}
+
+/// Prepares clients for recovery from a chain of snapshots, the first of which is full and the following ones
+/// are deltas. The last snapshot in the chain must correspond to `status`. Unlike [`prepare_clients()`],
+/// storage logs are chunked by hashed keys in the same way as in the snapshot creator, and the main node client
+/// returns unsigned manifests for all snapshots.
+pub(super) async fn prepare_clients_for_chain(
+    status: &SnapshotRecoveryStatus,
+    chain: &[MockSnapshot],
+) -> (Arc<dyn ObjectStore>, MockMainNodeClient) {
+    let object_store = MockObjectStore::arc();
+    let mut client = MockMainNodeClient::default();
+
+    let mut base_l1_batch_number = None;
+    for snapshot in chain {
+        let factory_deps = SnapshotFactoryDependencies {
+            factory_deps: vec![SnapshotFactoryDependency {
+                bytecode: Bytes::from(snapshot.factory_dep()),
+            }],
+        };
+        object_store
+            .put(snapshot.l1_batch_number, &factory_deps)
+            .await
+            .unwrap();
+
+        let mut storage_logs_chunk_digests = vec![];
+        for chunk_id in 0..snapshot.chunk_count {
+            let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, snapshot.chunk_count);
+            let storage_logs = snapshot
+                .storage_logs
+                .iter()
+                .filter(|log| hashed_keys_range.contains(&log.key))
+                .cloned()
+                .collect();
+            let chunk_key = SnapshotStorageLogsStorageKey {
+                l1_batch_number: snapshot.l1_batch_number,
+                chunk_id,
+            };
+            let chunk = SnapshotStorageLogsChunk { storage_logs };
+            storage_logs_chunk_digests.push(chunk.digest());
+            object_store.put(chunk_key, &chunk).await.unwrap();
+        }
+
+        let version = if base_l1_batch_number.is_some() {
+            SnapshotVersion::Version2
+        } else {
+            SnapshotVersion::Version1
+        };
+        let header = SnapshotHeader {
+            version: version.into(),
+            l1_batch_number: snapshot.l1_batch_number,
+            l2_block_number: L2BlockNumber(snapshot.l1_batch_number.0),
+            base_l1_batch_number,
+            storage_logs_chunks: (0..snapshot.chunk_count)
+                .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
+                    chunk_id,
+                    filepath: format!("file{chunk_id}"),
+                })
+                .collect(),
+            factory_deps_filepath: "some_filepath".to_string(),
+        };
+        let manifest = SnapshotManifest {
+            version: version.into(),
+            l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number,
+            factory_deps_digest: factory_deps.digest(),
+            storage_logs_chunk_digests,
+            signature: None,
+        };
+        client
+            .fetch_snapshot_responses
+            .insert(snapshot.l1_batch_number, header);
+        client
+            .fetch_snapshot_manifest_responses
+            .insert(snapshot.l1_batch_number, manifest);
+        base_l1_batch_number = Some(snapshot.l1_batch_number);
+    }
+
+    let mut newest_snapshot = client
+        .fetch_snapshot_responses
+        .remove(&status.l1_batch_number)
+        .expect("last snapshot in the chain must correspond to recovery status");
+    newest_snapshot.l2_block_number = status.l2_block_number;
+    client.fetch_newest_snapshot_response = Some(newest_snapshot);
+    client.fetch_l1_batch_responses.insert(
+        status.l1_batch_number,
+        l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
+    );
+    client.fetch_l2_block_responses.insert(
+        status.l2_block_number,
+        l2_block_details(
+            status.l2_block_number,
+            status.l1_batch_number,
+            status.l2_block_hash,
+        ),
+    );
+    (object_store, client)
+}
+
 /// Object store wrapper that hangs up after processing the specified number of requests.
 /// Used to emulate the snapshot applier being restarted since, if it's configured to have concurrency 1,
 /// the applier will request an object from the store strictly after fully processing all previously requested objects.
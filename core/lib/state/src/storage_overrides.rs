@@ -0,0 +1,196 @@
+use std::collections::{HashMap, HashSet};
+
+use zksync_types::{
+    api::state_override::{OverrideState, StateOverride},
+    get_code_key, get_known_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, StorageKey, StorageValue, H256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+use crate::ReadStorage;
+
+/// [`ReadStorage`] implementation applying Geth-style state overrides on top of the wrapped storage.
+/// Used in the API server to execute calls as if the state was different.
+#[derive(Debug)]
+pub struct StorageWithOverrides<S> {
+    storage_handle: S,
+    overridden_slots: HashMap<StorageKey, H256>,
+    overridden_factory_deps: HashMap<H256, Vec<u8>>,
+    /// Accounts with the entire storage replaced; slots not in `overridden_slots` are read as zero.
+    overridden_accounts: HashSet<AccountTreeId>,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    /// Wraps the provided storage, applying `state_override` on top of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a code override is not a valid EraVM bytecode. Overrides are validated during deserialization,
+    /// so this shouldn't happen for overrides received via API.
+    pub fn new(storage: S, state_override: &StateOverride) -> Self {
+        let mut this = Self {
+            storage_handle: storage,
+            overridden_slots: HashMap::new(),
+            overridden_factory_deps: HashMap::new(),
+            overridden_accounts: HashSet::new(),
+        };
+        for (address, account) in state_override.iter() {
+            if let Some(balance) = account.balance {
+                let balance_key = storage_key_for_eth_balance(address);
+                this.overridden_slots
+                    .insert(balance_key, u256_to_h256(balance));
+            }
+
+            if let Some(nonce) = account.nonce {
+                let nonce_key = get_nonce_key(address);
+                let full_nonce = this.read_value(&nonce_key);
+                let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+                let new_full_nonce = nonces_to_full_nonce(nonce, deployment_nonce);
+                this.overridden_slots
+                    .insert(nonce_key, u256_to_h256(new_full_nonce));
+            }
+
+            if let Some(code) = &account.code {
+                let code_key = get_code_key(address);
+                if code.0.is_empty() {
+                    this.overridden_slots.insert(code_key, H256::zero());
+                } else {
+                    let code_hash = hash_bytecode(&code.0);
+                    this.overridden_slots.insert(code_key, code_hash);
+                    this.overridden_slots
+                        .insert(get_known_code_key(&code_hash), H256::from_low_u64_be(1));
+                    this.overridden_factory_deps
+                        .insert(code_hash, code.0.clone());
+                }
+            }
+
+            let account_id = AccountTreeId::new(*address);
+            match &account.state {
+                Some(OverrideState::State(state)) => {
+                    this.overridden_accounts.insert(account_id);
+                    for (&slot, &value) in state {
+                        this.overridden_slots
+                            .insert(StorageKey::new(account_id, slot), value);
+                    }
+                }
+                Some(OverrideState::StateDiff(state_diff)) => {
+                    for (&slot, &value) in state_diff {
+                        this.overridden_slots
+                            .insert(StorageKey::new(account_id, slot), value);
+                    }
+                }
+                None => { /* do nothing */ }
+            }
+        }
+        this
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(value) = self.overridden_slots.get(key) {
+            return *value;
+        }
+        if self.overridden_accounts.contains(key.account()) {
+            return H256::zero();
+        }
+        self.storage_handle.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.storage_handle.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        if let Some(bytecode) = self.overridden_factory_deps.get(&hash) {
+            return Some(bytecode.clone());
+        }
+        self.storage_handle.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.storage_handle.get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{api::state_override::OverrideAccount, web3::Bytes, Address, U256};
+
+    use super::*;
+    use crate::InMemoryStorage;
+
+    #[test]
+    fn overriding_account_state() {
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let slot_key = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(1));
+        let other_slot_key = StorageKey::new(AccountTreeId::new(address), H256::repeat_byte(2));
+        let foreign_slot_key =
+            StorageKey::new(AccountTreeId::new(other_address), H256::repeat_byte(1));
+        let nonce_key = get_nonce_key(&address);
+        // Deployment nonce 1, transaction nonce 5.
+        let full_nonce = nonces_to_full_nonce(5.into(), 1.into());
+
+        let mut storage = InMemoryStorage::default();
+        for (key, value) in [
+            (slot_key, H256::repeat_byte(0xaa)),
+            (other_slot_key, H256::repeat_byte(0xbb)),
+            (foreign_slot_key, H256::repeat_byte(0xcc)),
+            (nonce_key, u256_to_h256(full_nonce)),
+        ] {
+            storage.set_value(key, value);
+        }
+
+        let code = vec![1_u8; 32];
+        let code_hash = hash_bytecode(&code);
+        let state_override = StateOverride::new(HashMap::from([
+            (
+                address,
+                OverrideAccount {
+                    balance: Some(U256::from(100)),
+                    nonce: Some(U256::from(10)),
+                    code: Some(Bytes(code.clone())),
+                    state: Some(OverrideState::State(HashMap::from([(
+                        H256::repeat_byte(1),
+                        H256::repeat_byte(0xff),
+                    )]))),
+                },
+            ),
+            (
+                other_address,
+                OverrideAccount {
+                    state: Some(OverrideState::StateDiff(HashMap::from([(
+                        H256::repeat_byte(3),
+                        H256::repeat_byte(0xdd),
+                    )]))),
+                    ..OverrideAccount::default()
+                },
+            ),
+        ]));
+        let mut storage = StorageWithOverrides::new(storage, &state_override);
+
+        assert_eq!(
+            storage.read_value(&storage_key_for_eth_balance(&address)),
+            u256_to_h256(100.into())
+        );
+        let (tx_nonce, deployment_nonce) =
+            decompose_full_nonce(h256_to_u256(storage.read_value(&nonce_key)));
+        assert_eq!((tx_nonce, deployment_nonce), (10.into(), 1.into()));
+        assert_eq!(storage.read_value(&get_code_key(&address)), code_hash);
+        assert!(storage.is_bytecode_known(&code_hash));
+        assert_eq!(storage.load_factory_dep(code_hash), Some(code));
+
+        // `state` replaces the entire storage of the account.
+        assert_eq!(storage.read_value(&slot_key), H256::repeat_byte(0xff));
+        assert_eq!(storage.read_value(&other_slot_key), H256::zero());
+        // `stateDiff` only overrides the specified slots.
+        assert_eq!(
+            storage.read_value(&foreign_slot_key),
+            H256::repeat_byte(0xcc)
+        );
+        let diff_key = StorageKey::new(AccountTreeId::new(other_address), H256::repeat_byte(3));
+        assert_eq!(storage.read_value(&diff_key), H256::repeat_byte(0xdd));
+    }
+}
@@ -312,6 +312,15 @@ impl Default for RocksDBOptions {
     }
 }
 
+/// Mode in which a [`RocksDB`] instance is opened.
+#[derive(Debug, Clone, Copy)]
+enum OpenMode<'a> {
+    Primary,
+    /// Secondary instance with the specified path for info logs.
+    Secondary(&'a Path),
+    ReadOnly,
+}
+
 /// Thin wrapper around a RocksDB instance.
 ///
 /// The wrapper is cheaply cloneable; internally, it wraps a DB instance in an [`Arc`].
@@ -340,12 +349,46 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
+        Self::open(path, OpenMode::Primary, options)
+    }
+
+    /// Opens an existing RocksDB instance at `path` in the read-only mode. The instance observes the state
+    /// of the DB at the moment it was opened; it can be opened while the DB is used by another (primary) instance.
+    /// Any writes to a read-only instance fail.
+    pub fn with_options_read_only(
+        path: &Path,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
+        Self::open(path, OpenMode::ReadOnly, options)
+    }
+
+    /// Opens a RocksDB instance at `path` in the secondary mode. A secondary instance is read-only;
+    /// it can run alongside the primary instance (potentially, in another process) and can be synchronized
+    /// with it using [`Self::try_catch_up_with_primary()`].
+    ///
+    /// `secondary_path` is used to store info logs of the secondary instance; it must differ from `path`
+    /// and from secondary paths of other secondary instances. [`RocksDBOptions::max_open_files`] is ignored,
+    /// since secondary instances require keeping all files open.
+    pub fn with_options_as_secondary(
+        path: &Path,
+        secondary_path: &Path,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
+        Self::open(path, OpenMode::Secondary(secondary_path), options)
+    }
+
+    fn open(
+        path: &Path,
+        mode: OpenMode<'_>,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
         let mut db_options = Self::rocksdb_options(None, None);
-        let max_open_files = if let Some(non_zero) = options.max_open_files {
-            i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
-        } else {
-            -1
+        let max_open_files = match options.max_open_files {
+            Some(non_zero) if !matches!(mode, OpenMode::Secondary(_)) => {
+                i32::try_from(non_zero.get()).unwrap_or(i32::MAX)
+            }
+            _ => -1,
         };
         db_options.set_max_open_files(max_open_files);
         let existing_cfs = DB::list_cf(&db_options, path).unwrap_or_else(|err| {
@@ -402,7 +445,13 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
-        let db = DB::open_cf_descriptors(&db_options, path, cfs)?;
+        let db = match mode {
+            OpenMode::Primary => DB::open_cf_descriptors(&db_options, path, cfs)?,
+            OpenMode::Secondary(secondary_path) => {
+                DB::open_cf_descriptors_as_secondary(&db_options, path, secondary_path, cfs)?
+            }
+            OpenMode::ReadOnly => DB::open_cf_descriptors_read_only(&db_options, path, cfs, false)?,
+        };
         let inner = Arc::new(RocksDBInner {
             db,
             db_name: CF::DB_NAME,
@@ -412,13 +461,31 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         });
         RocksdbSizeMetrics::register(CF::DB_NAME, Arc::downgrade(&inner));
 
-        tracing::info!(
-            "Initialized RocksDB `{}` at `{}` with {options:?}",
-            CF::DB_NAME,
-            path.display()
-        );
-
-        inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        match mode {
+            OpenMode::Primary => {
+                tracing::info!(
+                    "Initialized RocksDB `{}` at `{}` with {options:?}",
+                    CF::DB_NAME,
+                    path.display()
+                );
+                inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+            }
+            OpenMode::Secondary(secondary_path) => {
+                tracing::info!(
+                    "Initialized secondary RocksDB `{}` at `{}` (primary at `{}`) with {options:?}",
+                    CF::DB_NAME,
+                    secondary_path.display(),
+                    path.display()
+                );
+            }
+            OpenMode::ReadOnly => {
+                tracing::info!(
+                    "Initialized read-only RocksDB `{}` at `{}` with {options:?}",
+                    CF::DB_NAME,
+                    path.display()
+                );
+            }
+        }
         Ok(Self {
             inner,
             sync_writes: false,
@@ -468,6 +535,21 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         }
     }
 
+    /// Creates a [checkpoint] of this instance at the specified `path`, which must not exist. A checkpoint is a consistent
+    /// point-in-time copy of the DB that can be opened as an ordinary RocksDB instance. SST files are hard-linked
+    /// if `path` is on the same filesystem as the DB, so creating a checkpoint is cheap.
+    ///
+    /// [checkpoint]: https://github.com/facebook/rocksdb/wiki/Checkpoints
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        rocksdb::checkpoint::Checkpoint::new(&self.inner.db)?.create_checkpoint(path)
+    }
+
+    /// Synchronizes a secondary instance with the primary one, making all changes persisted by the primary
+    /// instance visible. Returns an error if this instance is not opened in the secondary mode.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), rocksdb::Error> {
+        self.inner.db.try_catch_up_with_primary()
+    }
+
     pub fn estimated_number_of_entries(&self, cf: CF) -> u64 {
         const ERROR_MSG: &str = "failed to get estimated number of entries";
 
@@ -1007,6 +1089,101 @@ mod tests {
         assert_eq!(value.unwrap(), b"value");
     }
 
+    #[test]
+    fn secondary_instance_catches_up_with_primary() {
+        let temp_dir = TempDir::new().unwrap();
+        let secondary_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let secondary = RocksDB::<NewColumnFamilies>::with_options_as_secondary(
+            temp_dir.path(),
+            secondary_dir.path(),
+            RocksDBOptions::default(),
+        )
+        .unwrap();
+        let value = secondary.get_cf(NewColumnFamilies::Other, b"test").unwrap();
+        assert_eq!(value.unwrap(), b"value");
+
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test2", b"value2");
+        db.write(batch).unwrap();
+        assert!(secondary
+            .get_cf(NewColumnFamilies::Other, b"test2")
+            .unwrap()
+            .is_none());
+
+        secondary.try_catch_up_with_primary().unwrap();
+        let value = secondary
+            .get_cf(NewColumnFamilies::Other, b"test2")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value2");
+    }
+
+    #[test]
+    fn read_only_instance() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let read_only = RocksDB::<NewColumnFamilies>::with_options_read_only(
+            temp_dir.path(),
+            RocksDBOptions::default(),
+        )
+        .unwrap();
+        let value = read_only.get_cf(NewColumnFamilies::Other, b"test").unwrap();
+        assert_eq!(value.unwrap(), b"value");
+
+        let mut batch = read_only.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test2", b"value2");
+        read_only.write(batch).unwrap_err();
+        drop(read_only);
+
+        // A read-only instance cannot be opened for a non-existing DB.
+        let missing_path = temp_dir.path().join("missing");
+        RocksDB::<NewColumnFamilies>::with_options_read_only(
+            &missing_path,
+            RocksDBOptions::default(),
+        )
+        .unwrap_err();
+        assert!(!missing_path.exists());
+    }
+
+    #[test]
+    fn creating_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(&temp_dir.path().join("db"))
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let checkpoint_path = temp_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test2", b"value2");
+        db.write(batch).unwrap();
+
+        let checkpoint = RocksDB::<NewColumnFamilies>::new(&checkpoint_path).unwrap();
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Other, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Other, b"test2")
+            .unwrap();
+        assert!(value.is_none());
+    }
+
     #[test]
     fn write_batch_can_be_restored_from_bytes() {
         let temp_dir = TempDir::new().unwrap();
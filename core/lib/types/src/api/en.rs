@@ -46,3 +46,31 @@ pub struct SyncBlock {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusGenesis(pub serde_json::Value);
+
+/// Anchor of an L1 batch commitment on Mintlayer: the commit operation including the batch is pinned to IPFS,
+/// and its content ID (CID) is deposited on Mintlayer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchAnchor {
+    /// CID of the commit operation data on IPFS.
+    pub ipfs_hash: String,
+    /// ID of the Mintlayer transaction depositing the CID.
+    pub mintlayer_tx_id: String,
+}
+
+/// Information about a chunk of L2 blocks archived by the main node (see `L2BlockArchiveChunk` in the `node_sync` crate).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L2BlockArchiveChunkInfo {
+    /// Zero-based index of the chunk. A chunk with index `i` contains L2 blocks with numbers in
+    /// `i * chunk_size..(i + 1) * chunk_size`.
+    pub index: u32,
+    /// First L2 block in the chunk. May differ from `index * chunk_size` for the first archived chunk.
+    pub first_l2_block: L2BlockNumber,
+    /// Last L2 block in the chunk.
+    pub last_l2_block: L2BlockNumber,
+    /// Hash of the last L2 block in the chunk.
+    pub last_l2_block_hash: H256,
+    /// CID of the chunk on IPFS, if the chunk was pinned to IPFS.
+    pub ipfs_cid: Option<String>,
+}
@@ -1,7 +1,12 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use strum::Display;
+use strum::{Display, EnumString};
 use zksync_basic_types::{
     web3::{AccessList, Bytes, Index},
     L1BatchNumber, H160, H2048, H256, H64, U256, U64,
@@ -13,11 +18,13 @@ pub use crate::transaction_request::{
 };
 use crate::{
     protocol_version::L1VerifierConfig,
+    tee_types::TeeType,
     vm_trace::{Call, CallType},
-    Address, L2BlockNumber, ProtocolVersionId,
+    Address, L2BlockNumber, Nonce, ProtocolVersionId,
 };
 
 pub mod en;
+pub mod state_override;
 
 /// Block Number
 #[derive(Copy, Clone, Debug, PartialEq, Display)]
@@ -34,6 +41,10 @@ pub enum BlockNumber {
     Earliest,
     /// Latest block (may be the block that is currently open).
     Pending,
+    /// Last block in an L1 batch such that it and all preceding L1 batches tracked by TEE proof
+    /// generation have TEE proofs; skipped L1 batches are not considered proven. Only supported
+    /// if enabled in the node configuration.
+    TeeFinalized,
     /// Block by number from canon chain
     Number(U64),
 }
@@ -57,6 +68,7 @@ impl Serialize for BlockNumber {
             BlockNumber::L1Committed => serializer.serialize_str("l1_committed"),
             BlockNumber::Earliest => serializer.serialize_str("earliest"),
             BlockNumber::Pending => serializer.serialize_str("pending"),
+            BlockNumber::TeeFinalized => serializer.serialize_str("tee_finalized"),
         }
     }
 }
@@ -80,6 +92,7 @@ impl<'de> Deserialize<'de> for BlockNumber {
                     "l1_committed" => BlockNumber::L1Committed,
                     "earliest" => BlockNumber::Earliest,
                     "pending" => BlockNumber::Pending,
+                    "tee_finalized" => BlockNumber::TeeFinalized,
                     num => {
                         let number =
                             U64::deserialize(de::value::BorrowedStrDeserializer::new(num))?;
@@ -624,6 +637,47 @@ pub struct DebugCall {
     pub calls: Vec<DebugCall>,
 }
 
+/// Account state returned by `prestateTracer`. Empty fields are omitted, same as in Geth.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Output of `prestateTracer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// Returned in the diff mode.
+    Diff {
+        pre: HashMap<Address, PrestateAccount>,
+        post: HashMap<Address, PrestateAccount>,
+    },
+    /// Prestate of all accounts touched during execution.
+    Prestate(HashMap<Address, PrestateAccount>),
+}
+
+/// Result of `debug_traceCall` / `debug_traceTransaction`; its shape depends on the requested tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DebugTraceResult {
+    CallTrace(DebugCall),
+    Prestate(PrestateTrace),
+}
+
+impl From<DebugCall> for DebugTraceResult {
+    fn from(call: DebugCall) -> Self {
+        Self::CallTrace(call)
+    }
+}
+
 impl From<Call> for DebugCall {
     fn from(value: Call) -> Self {
         let calls = value.calls.into_iter().map(DebugCall::from).collect();
@@ -729,12 +783,18 @@ impl ProtocolVersion {
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
     CallTracer,
+    PrestateTracer,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
+    #[serde(default)]
     pub only_top_call: bool,
+    /// Used by `prestateTracer` only: if set, the tracer returns `{ pre, post }` states of the modified accounts
+    /// instead of the prestate of all touched accounts.
+    #[serde(default)]
+    pub diff_mode: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -743,6 +803,9 @@ pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
     pub tracer_config: CallTracerConfig,
+    /// State overrides applied before tracing; only used by `debug_traceCall`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<state_override::StateOverride>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -809,6 +872,51 @@ pub struct Proof {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// Request for storage proofs at a specific L1 batch; used in `zks_getProofs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofRequest {
+    pub address: Address,
+    pub keys: Vec<H256>,
+    pub l1_batch_number: L1BatchNumber,
+}
+
+/// Status of TEE proof generation for an L1 batch.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TeeProofStatus {
+    ReadyToBeProven,
+    PickedByProver,
+    Generated,
+    Skipped,
+}
+
+/// TEE proof for an L1 batch; returned by `zks_getL1BatchTeeProofs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeeProof {
+    pub l1_batch_number: L1BatchNumber,
+    pub status: TeeProofStatus,
+    pub tee_type: Option<TeeType>,
+    pub pubkey: Option<Bytes>,
+    pub signature: Option<Bytes>,
+    pub proof: Option<Bytes>,
+    /// Attestation quote of the TEE that produced the proof.
+    pub attestation: Option<Bytes>,
+    pub proved_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionDetailedResult {
@@ -825,6 +933,66 @@ pub struct ApiStorageLog {
     pub written_value: U256,
 }
 
+/// Result of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    pub access_list: AccessList,
+    pub gas_used: U256,
+    /// Revert reason if the transaction has failed; the access list is still returned in this case.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Options for `zks_simulateBundle`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSimulationOptions {
+    /// Whether to return a call trace for each call in the bundle.
+    #[serde(default)]
+    pub trace_calls: bool,
+    /// State overrides applied before executing the first call in the bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<state_override::StateOverride>,
+}
+
+/// Options for a transaction bundle submitted via `zks_sendBundle`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSubmissionOptions {
+    /// Last L2 block the bundle may be included in. If the bundle isn't included by this block, it's dropped.
+    /// If not specified, a node-specific number of L2 blocks after the latest sealed one is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_block: Option<L2BlockNumber>,
+}
+
+/// Bundle accepted by `zks_sendBundle`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmittedBundle {
+    /// Hashes of the bundled transactions in the execution order.
+    pub tx_hashes: Vec<H256>,
+    /// Last L2 block the bundle may be included in.
+    pub target_block: L2BlockNumber,
+}
+
+/// Outcome of a single call in a bundle simulated by `zks_simulateBundle`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// 1 if the call has succeeded, 0 if it was reverted or halted.
+    pub status: U64,
+    /// Output of a successful call, or the encoded revert data.
+    pub return_data: Bytes,
+    pub gas_used: U256,
+    pub logs: Vec<Log>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Call trace; only present if requested via [`BundleSimulationOptions::trace_calls`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DebugCall>,
+}
+
 /// Raw transaction execution data.
 /// Data is taken from `TransactionExecutionMetrics`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -833,6 +1001,201 @@ pub struct TransactionExecutionInfo {
     pub execution_info: Value,
 }
 
+/// Pending transactions grouped by the initiator address and nonce, as returned by `txpool_content`.
+///
+/// Transactions are `pending` if they can be executed right away (i.e., there are no gaps between their nonce
+/// and the committed account nonce), and `queued` otherwise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContent<T = Transaction> {
+    pub pending: BTreeMap<Address, BTreeMap<u64, T>>,
+    pub queued: BTreeMap<Address, BTreeMap<u64, T>>,
+    /// Non-standard field set if the number of returned transactions is limited by the node,
+    /// and some transactions in the mempool are omitted. Transactions with lower nonces are returned first.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub truncated: bool,
+}
+
+fn is_false(&flag: &bool) -> bool {
+    !flag
+}
+
+impl<T> Default for TxpoolContent<T> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::new(),
+            truncated: false,
+        }
+    }
+}
+
+impl TxpoolContent {
+    /// Groups pending `transactions` using committed nonces of their initiators.
+    /// Transactions without the initiator address are skipped.
+    pub fn new(
+        transactions: impl IntoIterator<Item = Transaction>,
+        committed_nonces: &HashMap<Address, Nonce>,
+    ) -> Self {
+        let mut txs_by_initiator = BTreeMap::<_, BTreeMap<_, _>>::new();
+        for tx in transactions {
+            if let Some(initiator) = tx.from {
+                txs_by_initiator
+                    .entry(initiator)
+                    .or_default()
+                    .insert(tx.nonce.as_u64(), tx);
+            }
+        }
+
+        let mut content = Self::default();
+        for (initiator, txs) in txs_by_initiator {
+            let mut next_nonce = committed_nonces
+                .get(&initiator)
+                .map_or(0, |nonce| u64::from(nonce.0));
+            let (mut pending, mut queued) = (BTreeMap::new(), BTreeMap::new());
+            for (nonce, tx) in txs {
+                if nonce == next_nonce {
+                    pending.insert(nonce, tx);
+                    next_nonce += 1;
+                } else {
+                    queued.insert(nonce, tx);
+                }
+            }
+            if !pending.is_empty() {
+                content.pending.insert(initiator, pending);
+            }
+            if !queued.is_empty() {
+                content.queued.insert(initiator, queued);
+            }
+        }
+        content
+    }
+
+    /// Converts this content into the textual summary returned by `txpool_inspect`.
+    pub fn inspect(self) -> TxpoolInspect {
+        self.map(|tx| tx.txpool_summary())
+    }
+}
+
+impl<T> TxpoolContent<T> {
+    pub fn map<U>(self, mut map_fn: impl FnMut(T) -> U) -> TxpoolContent<U> {
+        let mut map_group = |group: BTreeMap<Address, BTreeMap<u64, T>>| {
+            group
+                .into_iter()
+                .map(|(initiator, txs)| {
+                    let txs = txs.into_iter().map(|(nonce, tx)| (nonce, map_fn(tx)));
+                    (initiator, txs.collect())
+                })
+                .collect()
+        };
+        TxpoolContent {
+            pending: map_group(self.pending),
+            queued: map_group(self.queued),
+            truncated: self.truncated,
+        }
+    }
+
+    pub fn status(&self) -> TxpoolStatus {
+        let count = |group: &BTreeMap<Address, BTreeMap<u64, T>>| {
+            group.values().map(BTreeMap::len).sum::<usize>()
+        };
+        TxpoolStatus {
+            pending: U64::from(count(&self.pending)),
+            queued: U64::from(count(&self.queued)),
+        }
+    }
+
+    /// Extracts transactions initiated by the specified account.
+    pub fn into_account(mut self, initiator: Address) -> TxpoolContentFrom<T> {
+        TxpoolContentFrom {
+            pending: self.pending.remove(&initiator).unwrap_or_default(),
+            queued: self.queued.remove(&initiator).unwrap_or_default(),
+            truncated: self.truncated,
+        }
+    }
+}
+
+/// Pending transactions of a single account keyed by nonce, as returned by `txpool_contentFrom`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContentFrom<T = Transaction> {
+    pub pending: BTreeMap<u64, T>,
+    pub queued: BTreeMap<u64, T>,
+    /// Non-standard field; see [`TxpoolContent::truncated`].
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub truncated: bool,
+}
+
+impl<T> TxpoolContentFrom<T> {
+    /// Converts this content back to [`TxpoolContent`] for the specified initiator.
+    pub fn into_content(self, initiator: Address) -> TxpoolContent<T> {
+        let mut content = TxpoolContent {
+            truncated: self.truncated,
+            ..TxpoolContent::default()
+        };
+        if !self.pending.is_empty() {
+            content.pending.insert(initiator, self.pending);
+        }
+        if !self.queued.is_empty() {
+            content.queued.insert(initiator, self.queued);
+        }
+        content
+    }
+}
+
+/// Textual summary of pending transactions returned by `txpool_inspect`.
+pub type TxpoolInspect = TxpoolContent<String>;
+
+/// Number of pending transactions returned by `txpool_status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    pub pending: U64,
+    pub queued: U64,
+}
+
+impl TxpoolStatus {
+    /// Computes the status from ranges of contiguous nonces of mempool transactions for each initiator,
+    /// classifying transactions in the same way as [`TxpoolContent::new()`].
+    pub fn from_nonce_ranges(
+        nonce_ranges: &HashMap<Address, Vec<Range<u64>>>,
+        committed_nonces: &HashMap<Address, Nonce>,
+    ) -> Self {
+        let (mut pending, mut queued) = (0, 0);
+        for (initiator, ranges) in nonce_ranges {
+            let committed_nonce = committed_nonces
+                .get(initiator)
+                .map_or(0, |nonce| u64::from(nonce.0));
+            for range in ranges {
+                let count = range.end - range.start;
+                if range.contains(&committed_nonce) {
+                    let pending_count = range.end - committed_nonce;
+                    pending += pending_count;
+                    queued += count - pending_count;
+                } else {
+                    queued += count;
+                }
+            }
+        }
+        Self {
+            pending: pending.into(),
+            queued: queued.into(),
+        }
+    }
+}
+
+impl Transaction {
+    /// Returns a Geth-compatible summary of the transaction used in `txpool_inspect`.
+    pub fn txpool_summary(&self) -> String {
+        let gas_price = self.gas_price.or(self.max_fee_per_gas).unwrap_or_default();
+        let recipient = match self.to {
+            Some(to) => format!("{to:?}"),
+            None => "contract creation".to_owned(),
+        };
+        format!(
+            "{recipient}: {} wei + {} gas × {gas_price} wei",
+            self.value, self.gas
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -866,4 +1229,119 @@ mod tests {
         serde_json::from_str::<OldProtocolVersion>(&serde_json::to_string(&new_version).unwrap())
             .unwrap();
     }
+
+    #[test]
+    fn grouping_txpool_content() {
+        let account = Address::repeat_byte(1);
+        let other_account = Address::repeat_byte(2);
+        let transactions = [(account, 3), (account, 4), (account, 6), (other_account, 1)]
+            .into_iter()
+            .map(|(from, nonce)| Transaction {
+                from: Some(from),
+                to: Some(Address::repeat_byte(0xff)),
+                nonce: nonce.into(),
+                value: 1.into(),
+                gas: 21_000.into(),
+                gas_price: Some(100.into()),
+                ..Transaction::default()
+            });
+        let nonces = HashMap::from([(account, Nonce(3))]);
+        let content = TxpoolContent::new(transactions, &nonces);
+
+        let pending_nonces: Vec<_> = content.pending[&account].keys().copied().collect();
+        assert_eq!(pending_nonces, [3, 4]);
+        let queued_nonces: Vec<_> = content.queued[&account].keys().copied().collect();
+        assert_eq!(queued_nonces, [6]);
+        // The committed nonce for `other_account` is 0, so its transaction is queued.
+        assert!(!content.pending.contains_key(&other_account));
+        assert!(content.queued[&other_account].contains_key(&1));
+        assert_eq!(
+            content.status(),
+            TxpoolStatus {
+                pending: 2.into(),
+                queued: 2.into(),
+            }
+        );
+
+        let nonce_ranges =
+            HashMap::from([(account, vec![3..5, 6..7]), (other_account, vec![1..2])]);
+        assert_eq!(
+            TxpoolStatus::from_nonce_ranges(&nonce_ranges, &nonces),
+            content.status()
+        );
+        // A range may start below the committed nonce if the mempool contains stale transactions.
+        let nonce_ranges = HashMap::from([(account, vec![2..5])]);
+        assert_eq!(
+            TxpoolStatus::from_nonce_ranges(&nonce_ranges, &nonces),
+            TxpoolStatus {
+                pending: 2.into(),
+                queued: 1.into(),
+            }
+        );
+
+        let account_content = content.clone().into_account(account);
+        assert_eq!(account_content.pending.len(), 2);
+        assert_eq!(account_content.queued.len(), 1);
+
+        let inspect_json = serde_json::to_value(content.inspect()).unwrap();
+        assert_eq!(
+            inspect_json["pending"][format!("{account:?}")]["3"],
+            format!(
+                "{:?}: 1 wei + 21000 gas × 100 wei",
+                Address::repeat_byte(0xff)
+            )
+        );
+    }
+
+    #[test]
+    fn prestate_tracer_serialization() {
+        let config: TracerConfig = serde_json::from_value(serde_json::json!({
+            "tracer": "prestateTracer",
+            "tracerConfig": { "diffMode": true },
+        }))
+        .unwrap();
+        assert!(matches!(config.tracer, SupportedTracers::PrestateTracer));
+        assert!(config.tracer_config.diff_mode);
+        assert!(!config.tracer_config.only_top_call);
+
+        let address = Address::repeat_byte(1);
+        let pre = PrestateAccount {
+            balance: Some(100.into()),
+            nonce: Some(1),
+            ..PrestateAccount::default()
+        };
+        let post = PrestateAccount {
+            balance: Some(50.into()),
+            nonce: Some(2),
+            storage: BTreeMap::from([(H256::zero(), H256::repeat_byte(1))]),
+            ..PrestateAccount::default()
+        };
+        let trace = DebugTraceResult::Prestate(PrestateTrace::Diff {
+            pre: HashMap::from([(address, pre.clone())]),
+            post: HashMap::from([(address, post)]),
+        });
+        let trace_json = serde_json::to_value(&trace).unwrap();
+        let address_key = format!("{address:?}");
+        assert_eq!(
+            trace_json["pre"][&address_key],
+            serde_json::json!({ "balance": "0x64", "nonce": 1 })
+        );
+        assert_eq!(
+            trace_json["post"][&address_key]["storage"][format!("{:?}", H256::zero())],
+            format!("{:?}", H256::repeat_byte(1))
+        );
+        assert_eq!(
+            serde_json::from_value::<DebugTraceResult>(trace_json).unwrap(),
+            trace
+        );
+
+        let trace =
+            DebugTraceResult::Prestate(PrestateTrace::Prestate(HashMap::from([(address, pre)])));
+        let trace_json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(trace_json[&address_key]["nonce"], 1);
+        assert_eq!(
+            serde_json::from_value::<DebugTraceResult>(trace_json).unwrap(),
+            trace
+        );
+    }
 }
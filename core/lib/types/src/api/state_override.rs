@@ -0,0 +1,150 @@
+//! Geth-compatible state overrides for `eth_call`, `eth_estimateGas` and `debug_traceCall`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, H256, U256};
+use zksync_utils::bytecode::validate_bytecode;
+
+use crate::Address;
+
+/// Collection of overridden accounts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateOverride(HashMap<Address, OverrideAccount>);
+
+impl StateOverride {
+    pub fn new(state: HashMap<Address, OverrideAccount>) -> Self {
+        Self(state)
+    }
+
+    /// Gets the override for the specified account, if any.
+    pub fn get(&self, address: &Address) -> Option<&OverrideAccount> {
+        self.0.get(address)
+    }
+
+    /// Iterates over all overridden accounts.
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &OverrideAccount)> + '_ {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Overrides for a single account. Empty `code` resets the account to an EOA.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "RawOverrideAccount")]
+pub struct OverrideAccount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account; the deployment nonce is left intact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    /// EraVM bytecode of the account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub state: Option<OverrideState>,
+}
+
+/// Storage overrides for an account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverrideState {
+    /// Replaces the entire account storage; slots not mentioned are considered to be zero.
+    State(HashMap<H256, H256>),
+    /// Overrides the specified slots, leaving the remaining storage intact.
+    StateDiff(HashMap<H256, H256>),
+}
+
+/// Wire representation of [`OverrideAccount`] used to validate it during deserialization.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOverrideAccount {
+    #[serde(default)]
+    balance: Option<U256>,
+    #[serde(default)]
+    nonce: Option<U256>,
+    #[serde(default)]
+    code: Option<Bytes>,
+    #[serde(default)]
+    state: Option<HashMap<H256, H256>>,
+    #[serde(default)]
+    state_diff: Option<HashMap<H256, H256>>,
+}
+
+impl TryFrom<RawOverrideAccount> for OverrideAccount {
+    type Error = String;
+
+    fn try_from(raw: RawOverrideAccount) -> Result<Self, Self::Error> {
+        if let Some(code) = &raw.code {
+            if !code.0.is_empty() {
+                validate_bytecode(&code.0)
+                    .map_err(|err| format!("invalid code override: {err}"))?;
+            }
+        }
+        let state = match (raw.state, raw.state_diff) {
+            (Some(_), Some(_)) => {
+                return Err("`state` and `stateDiff` cannot be overridden simultaneously".into());
+            }
+            (Some(state), None) => Some(OverrideState::State(state)),
+            (None, Some(state_diff)) => Some(OverrideState::StateDiff(state_diff)),
+            (None, None) => None,
+        };
+        Ok(Self {
+            balance: raw.balance,
+            nonce: raw.nonce,
+            code: raw.code,
+            state,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializing_state_override() {
+        let address = Address::repeat_byte(1);
+        let json = serde_json::json!({
+            format!("{address:?}"): {
+                "balance": "0x100",
+                "nonce": "0x2",
+                "stateDiff": {
+                    format!("{:?}", H256::zero()): format!("{:?}", H256::repeat_byte(0xff)),
+                },
+            },
+        });
+        let state_override: StateOverride = serde_json::from_value(json.clone()).unwrap();
+        let account = state_override.get(&address).unwrap();
+        assert_eq!(account.balance, Some(0x100.into()));
+        assert_eq!(account.nonce, Some(2.into()));
+        assert_eq!(account.code, None);
+        assert_eq!(
+            account.state,
+            Some(OverrideState::StateDiff(HashMap::from([(
+                H256::zero(),
+                H256::repeat_byte(0xff)
+            )])))
+        );
+        assert_eq!(serde_json::to_value(&state_override).unwrap(), json);
+    }
+
+    #[test]
+    fn invalid_state_overrides_are_rejected() {
+        let account = serde_json::json!({ "state": {}, "stateDiff": {} });
+        let err = serde_json::from_value::<OverrideAccount>(account).unwrap_err();
+        assert!(err.to_string().contains("simultaneously"), "{err}");
+
+        // Even number of 32-byte words is not a valid EraVM bytecode.
+        let account = serde_json::json!({ "code": format!("0x{}", "00".repeat(64)) });
+        let err = serde_json::from_value::<OverrideAccount>(account).unwrap_err();
+        assert!(err.to_string().contains("invalid code override"), "{err}");
+
+        let account = serde_json::json!({ "code": "0x" });
+        let account: OverrideAccount = serde_json::from_value(account).unwrap();
+        assert_eq!(account.code, Some(Bytes(vec![])));
+    }
+}
@@ -101,6 +101,28 @@ impl L1BatchMetadata {
             rollup_last_leaf_index: self.rollup_last_leaf_index,
         }
     }
+
+    /// Recomputes the L1 batch commitment from the tree data, meta parameters and the auxiliary output hash.
+    /// Unlike [`Self::commitment`], the result doesn't rely on the precomputed pass-through data and meta parameters hashes.
+    pub fn recompute_commitment(&self) -> H256 {
+        let pass_through_data = L1BatchPassThroughData {
+            shared_states: vec![
+                RootState {
+                    last_leaf_index: self.rollup_last_leaf_index,
+                    root_hash: self.root_hash,
+                },
+                // zkPorter state is always empty; see `L1BatchCommitment::new()`.
+                RootState {
+                    last_leaf_index: 0,
+                    root_hash: H256::zero(),
+                },
+            ],
+        };
+        let mut bytes = pass_through_data.hash().as_bytes().to_vec();
+        bytes.extend_from_slice(self.block_meta_params.hash().as_bytes());
+        bytes.extend_from_slice(self.aux_data_hash.as_bytes());
+        H256(keccak256(&bytes))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
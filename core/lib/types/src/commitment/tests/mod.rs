@@ -17,7 +17,7 @@ fn run_test(test_name: &str) {
     let contents = read_to_string(format!("src/commitment/tests/{test_name}.json")).unwrap();
     let commitment_test: CommitmentTest = serde_json::from_str(&contents).unwrap();
 
-    let commitment = L1BatchCommitment::new(commitment_test.input);
+    let commitment = L1BatchCommitment::new(commitment_test.input.clone());
 
     assert_eq!(
         commitment.pass_through_data,
@@ -29,6 +29,26 @@ fn run_test(test_name: &str) {
         commitment_test.auxiliary_output
     );
     assert_eq!(commitment.hash(), commitment_test.hashes);
+
+    let metadata = L1BatchMetadata {
+        root_hash: commitment_test.input.common().rollup_root_hash,
+        rollup_last_leaf_index: commitment_test.input.common().rollup_last_leaf_index,
+        initial_writes_compressed: None,
+        repeated_writes_compressed: None,
+        commitment: commitment_test.hashes.commitment,
+        l2_l1_merkle_root: commitment.l2_l1_logs_merkle_root(),
+        block_meta_params: commitment.meta_parameters(),
+        aux_data_hash: commitment_test.hashes.aux_output,
+        meta_parameters_hash: commitment_test.hashes.meta_parameters,
+        pass_through_data_hash: commitment_test.hashes.pass_through_data,
+        events_queue_commitment: None,
+        bootloader_initial_content_commitment: None,
+        state_diffs_compressed: vec![],
+    };
+    assert_eq!(
+        metadata.recompute_commitment(),
+        commitment_test.hashes.commitment
+    );
 }
 
 #[test]
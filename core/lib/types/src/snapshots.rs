@@ -3,11 +3,14 @@ use std::ops;
 use anyhow::Context;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::{Deserialize, Serialize};
-use zksync_basic_types::{AccountTreeId, L1BatchNumber, L2BlockNumber, H256};
+use zksync_basic_types::{
+    web3::keccak256, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, L2ChainId, H256,
+};
+use zksync_crypto_primitives::{K256PrivateKey, PackedEthSignature};
 use zksync_protobuf::{required, ProtoFmt};
 use zksync_utils::u256_to_h256;
 
-use crate::{utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256};
+use crate::{ethabi::Token, utils, web3::Bytes, ProtocolVersionId, StorageKey, StorageValue, U256};
 
 /// Information about all snapshots persisted by the node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +31,17 @@ pub enum SnapshotVersion {
     /// Snapshot version made compatible with L1 recovery. Differs from `Version0` by including
     /// hashed keys in storage logs instead of `(address, key)` pairs.
     Version1 = 1,
+    /// Delta snapshot. Uses the same storage logs format as `Version1`, but only contains storage logs
+    /// and factory dependencies changed since the base snapshot (which can be a delta snapshot itself).
+    /// Recovering from a delta snapshot requires applying the entire chain of snapshots starting from a full one.
+    Version2 = 2,
+}
+
+impl SnapshotVersion {
+    /// Checks whether snapshots with this version only contain changes relative to a base snapshot.
+    pub fn is_delta(self) -> bool {
+        matches!(self, Self::Version2)
+    }
 }
 
 /// Storage snapshot metadata. Used in DAL to fetch certain snapshot data.
@@ -36,6 +50,8 @@ pub struct SnapshotMetadata {
     pub version: SnapshotVersion,
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot. Only set for delta snapshots.
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
@@ -61,6 +77,9 @@ pub struct SnapshotHeader {
     pub l1_batch_number: L1BatchNumber,
     #[serde(rename = "miniblockNumber")] // legacy naming
     pub l2_block_number: L2BlockNumber,
+    /// L1 batch of the base snapshot. Only set for delta snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
@@ -74,6 +93,105 @@ pub struct SnapshotStorageLogsChunkMetadata {
     pub filepath: String,
 }
 
+/// Integrity manifest of a snapshot. Contains digests of all snapshot objects, which allows checking objects
+/// fetched from an untrusted object store one by one. Digests are computed by [`SnapshotStorageLogsChunk::digest()`]
+/// and [`SnapshotFactoryDependencies::digest()`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    pub version: u16,
+    pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot. Only set for delta snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
+    pub factory_deps_digest: H256,
+    /// Ordered by chunk IDs.
+    pub storage_logs_chunk_digests: Vec<H256>,
+    /// Signature of the operator over [`Self::signed_hash()`]. Not set if the manifest is unsigned.
+    /// The signature is bound to a specific L2 chain, so a manifest signed for one chain cannot be replayed
+    /// on another chain with the same operator key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PackedEthSignature>,
+}
+
+impl SnapshotManifest {
+    /// Domain tag prepended to the signed data to separate manifest signatures from other signatures
+    /// made with the operator key.
+    const DOMAIN_TAG: &'static str = "zksync-snapshot-manifest";
+
+    /// Returns the hash signed by the operator. The hash covers the domain tag, the L2 chain ID, and all manifest
+    /// fields except for the signature.
+    pub fn signed_hash(&self, l2_chain_id: L2ChainId) -> H256 {
+        let tokens = [
+            Token::String(Self::DOMAIN_TAG.to_owned()),
+            Token::Uint(l2_chain_id.as_u64().into()),
+            Token::Uint(self.version.into()),
+            Token::Uint(self.l1_batch_number.0.into()),
+            Token::Bool(self.base_l1_batch_number.is_some()),
+            Token::Uint(
+                self.base_l1_batch_number
+                    .map_or(0, |number| number.0)
+                    .into(),
+            ),
+            Token::FixedBytes(self.factory_deps_digest.as_bytes().to_vec()),
+            Token::Array(
+                self.storage_logs_chunk_digests
+                    .iter()
+                    .map(|digest| Token::FixedBytes(digest.as_bytes().to_vec()))
+                    .collect(),
+            ),
+        ];
+        H256(keccak256(&crate::ethabi::encode(&tokens)))
+    }
+
+    /// Signs this manifest for the specified L2 chain with the provided key, overwriting the existing signature if any.
+    pub fn sign(
+        &mut self,
+        l2_chain_id: L2ChainId,
+        private_key: &K256PrivateKey,
+    ) -> anyhow::Result<()> {
+        let signature = PackedEthSignature::sign_raw(private_key, &self.signed_hash(l2_chain_id))
+            .context("failed signing snapshot manifest")?;
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    /// Recovers the address of the manifest signer for the specified L2 chain. Returns `Ok(None)` if the manifest
+    /// is unsigned.
+    pub fn recover_signer(&self, l2_chain_id: L2ChainId) -> anyhow::Result<Option<Address>> {
+        let Some(signature) = &self.signature else {
+            return Ok(None);
+        };
+        let signer = signature
+            .signature_recover_signer(&self.signed_hash(l2_chain_id))
+            .context("malformed snapshot manifest signature")?;
+        Ok(Some(signer))
+    }
+}
+
+/// Snapshot document published to IPFS. The CID of the document is anchored on Mintlayer, which allows recovering
+/// from a snapshot knowing only the anchoring transaction ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotIpfsDocument {
+    /// Snapshot header. Unlike headers returned by the JSON-RPC API, file paths for storage log chunks
+    /// and factory dependencies are IPFS CIDs of the corresponding (gzipped) objects.
+    pub header: SnapshotHeader,
+    pub manifest: SnapshotManifest,
+    /// CID of the IPFS document for the base snapshot. Only set for delta snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_document_cid: Option<String>,
+}
+
+/// Information about a snapshot published to IPFS and anchored on Mintlayer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotIpfsAnchor {
+    /// CID of the [`SnapshotIpfsDocument`] for the snapshot.
+    pub document_cid: String,
+    /// ID of the Mintlayer transaction depositing the document CID.
+    pub anchor_tx_id: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotStorageLogsStorageKey {
@@ -86,6 +204,17 @@ pub struct SnapshotStorageLogsChunk<K = H256> {
     pub storage_logs: Vec<SnapshotStorageLog<K>>,
 }
 
+impl<K> SnapshotStorageLogsChunk<K>
+where
+    Self: ProtoFmt,
+{
+    /// Computes the digest of this chunk as included into [`SnapshotManifest`]. The digest is the keccak256 hash
+    /// of the (uncompressed) Protobuf encoding of the chunk, so it doesn't depend on the compression settings.
+    pub fn digest(&self) -> H256 {
+        H256(keccak256(&zksync_protobuf::encode(self)))
+    }
+}
+
 /// Storage log record in a storage snapshot.
 ///
 /// Version 0 and version 1 snapshots differ in the key type; version 0 uses full [`StorageKey`]s (i.e., storage key preimages),
@@ -114,6 +243,14 @@ pub struct SnapshotFactoryDependencies {
     pub factory_deps: Vec<SnapshotFactoryDependency>,
 }
 
+impl SnapshotFactoryDependencies {
+    /// Computes the digest of factory dependencies as included into [`SnapshotManifest`].
+    /// Similar to [`SnapshotStorageLogsChunk::digest()`], this is the keccak256 hash of the uncompressed Protobuf encoding.
+    pub fn digest(&self) -> H256 {
+        H256(keccak256(&zksync_protobuf::encode(self)))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SnapshotFactoryDependency {
     pub bytecode: Bytes,
@@ -371,4 +508,48 @@ mod tests {
             assert!(max_chunk_size - min_chunk_size < U256::from(chunks_count));
         }
     }
+
+    #[test]
+    fn signing_snapshot_manifest() {
+        let mut manifest = SnapshotManifest {
+            version: SnapshotVersion::Version1.into(),
+            l1_batch_number: L1BatchNumber(42),
+            base_l1_batch_number: None,
+            factory_deps_digest: H256::repeat_byte(1),
+            storage_logs_chunk_digests: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+            signature: None,
+        };
+        let chain_id = L2ChainId::from(270);
+        assert_eq!(manifest.recover_signer(chain_id).unwrap(), None);
+
+        let private_key = K256PrivateKey::random();
+        manifest.sign(chain_id, &private_key).unwrap();
+        assert_eq!(
+            manifest.recover_signer(chain_id).unwrap(),
+            Some(private_key.address())
+        );
+
+        let serialized = serde_json::to_value(&manifest).unwrap();
+        let deserialized: SnapshotManifest = serde_json::from_value(serialized).unwrap();
+        assert_eq!(deserialized, manifest);
+
+        // The signature must not be valid for another chain.
+        assert_ne!(
+            manifest.recover_signer(L2ChainId::from(271)).unwrap(),
+            Some(private_key.address())
+        );
+
+        // Changing any signed field must invalidate the signature.
+        manifest.storage_logs_chunk_digests.swap(0, 1);
+        assert_ne!(
+            manifest.recover_signer(chain_id).unwrap(),
+            Some(private_key.address())
+        );
+        manifest.storage_logs_chunk_digests.swap(0, 1);
+        manifest.base_l1_batch_number = Some(L1BatchNumber(0));
+        assert_ne!(
+            manifest.recover_signer(chain_id).unwrap(),
+            Some(private_key.address())
+        );
+    }
 }
@@ -35,10 +35,20 @@ pub enum Web3Error {
     TooManyTopics,
     #[error("Filter not found")]
     FilterNotFound,
+    #[error("Too many proof requests; the maximum is {0}")]
+    TooManyProofRequests(usize),
+    #[error("Too many storage keys in proof requests; the maximum is {0}")]
+    TooManyProofKeys(usize),
     #[error("Query returned more than {0} results. Try with this block range [{1:#x}, {2:#x}].")]
     LogsLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("Tracer `{0}` is not supported by this method")]
+    UnsupportedTracer(&'static str),
+    #[error("Block tag `{0}` is not supported by this node")]
+    UnsupportedBlockTag(&'static str),
+    #[error("Method is not supported for blocks executed by legacy VM versions")]
+    UnsupportedVmVersion,
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
@@ -2,7 +2,9 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_config::{configs::EcosystemContracts, GenesisConfig};
-use zksync_types::{api::en, tokens::TokenInfo, Address, L2BlockNumber};
+use zksync_types::{
+    api::en, commitment::L1BatchMetadata, tokens::TokenInfo, Address, L1BatchNumber, L2BlockNumber,
+};
 
 use crate::client::{ForNetwork, L2};
 
@@ -41,4 +43,34 @@ pub trait EnNamespace {
 
     #[method(name = "getEcosystemContracts")]
     async fn get_ecosystem_contracts(&self) -> RpcResult<EcosystemContracts>;
+
+    /// Returns the Mintlayer anchor of the commit operation including the specified L1 batch, or `null`
+    /// if the operation is not anchored yet.
+    ///
+    /// This method is used by EN in the verify-only mode in order to check anchored L1 batch commitments.
+    #[method(name = "l1BatchAnchor")]
+    async fn l1_batch_anchor(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<en::L1BatchAnchor>>;
+
+    /// Returns metadata (root hash, commitment and its components) for the specified L1 batch, or `null`
+    /// if the batch doesn't exist or its metadata is not computed yet.
+    ///
+    /// This method is used by EN in the verify-only mode in order to recompute L1 batch commitments.
+    #[method(name = "l1BatchMetadata")]
+    async fn l1_batch_metadata(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchMetadata>>;
+
+    /// Returns information about an archived chunk of L2 blocks with the specified index, or about the latest
+    /// archived chunk if the index is not specified. Returns `null` if there is no such chunk.
+    ///
+    /// This method is used by EN syncing from the L2 block archive pinned to IPFS in order to resolve chunk CIDs.
+    #[method(name = "l2BlockArchiveChunk")]
+    async fn l2_block_archive_chunk(
+        &self,
+        chunk_index: Option<u32>,
+    ) -> RpcResult<Option<en::L2BlockArchiveChunkInfo>>;
 }
@@ -2,7 +2,7 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotHeader},
+    snapshots::{AllSnapshots, SnapshotHeader, SnapshotManifest},
     L1BatchNumber,
 };
 
@@ -25,4 +25,10 @@ pub trait SnapshotsNamespace {
         &self,
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<SnapshotHeader>>;
+
+    #[method(name = "getSnapshotManifest")]
+    async fn get_snapshot_manifest(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<SnapshotManifest>>;
 }
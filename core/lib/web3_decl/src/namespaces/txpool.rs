@@ -0,0 +1,32 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::{
+    api::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus},
+    Address,
+};
+
+use crate::client::{ForNetwork, L2};
+
+/// Geth-compatible namespace allowing to inspect transactions that are not yet included into L2 blocks.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "txpool", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "txpool", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+pub trait TxpoolNamespace {
+    #[method(name = "status")]
+    async fn status(&self) -> RpcResult<TxpoolStatus>;
+
+    #[method(name = "content")]
+    async fn content(&self) -> RpcResult<TxpoolContent>;
+
+    #[method(name = "contentFrom")]
+    async fn content_from(&self, address: Address) -> RpcResult<TxpoolContentFrom>;
+
+    #[method(name = "inspect")]
+    async fn inspect(&self) -> RpcResult<TxpoolInspect>;
+}
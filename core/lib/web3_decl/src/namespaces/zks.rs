@@ -5,8 +5,9 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetailedResult, TransactionDetails,
+        BlockDetails, BlockId, BridgeAddresses, BundleSimulationOptions, BundleSubmissionOptions,
+        L1BatchDetails, L2ToL1LogProof, Proof, ProofRequest, ProtocolVersion, SimulatedCallResult,
+        SubmittedBundle, TeeProof, TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -100,6 +101,10 @@ pub trait ZksNamespace {
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    /// Returns TEE proofs for the specified L1 batch, including proofs that are not generated yet.
+    #[method(name = "getL1BatchTeeProofs")]
+    async fn get_l1_batch_tee_proofs(&self, batch: L1BatchNumber) -> RpcResult<Vec<TeeProof>>;
+
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
@@ -123,6 +128,11 @@ pub trait ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<Proof>>;
 
+    /// Batched version of `getProof` that can request proofs for multiple L1 batches at once.
+    /// Proofs are returned in the same order as requests.
+    #[method(name = "getProofs")]
+    async fn get_proofs(&self, requests: Vec<ProofRequest>) -> RpcResult<Vec<Option<Proof>>>;
+
     #[method(name = "getBatchFeeInput")]
     async fn get_batch_fee_input(&self) -> RpcResult<PubdataIndependentBatchFeeModelInput>;
 
@@ -131,4 +141,31 @@ pub trait ZksNamespace {
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    /// Executes an ordered list of calls on top of the specified block (`pending` by default), so that each call
+    /// observes state changes made by the preceding ones. Returns a result for each executed call; execution
+    /// stops after the first halted call. If the simulation times out, results of the completed calls are returned,
+    /// followed by a failed result for the interrupted call with the timeout error as its revert reason.
+    #[method(name = "simulateBundle")]
+    async fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+        options: Option<BundleSimulationOptions>,
+    ) -> RpcResult<Vec<SimulatedCallResult>>;
+
+    /// Submits a transaction that is executed as usual, but is not exposed via pending transaction filters
+    /// and subscriptions.
+    #[method(name = "sendPrivateTransaction")]
+    async fn send_private_transaction(&self, tx_bytes: Bytes) -> RpcResult<H256>;
+
+    /// Submits an ordered list of transactions that must be included atomically and in order into a single L2 block.
+    /// The bundle is dropped if it cannot be included by the target block, or if any of its transactions fails.
+    /// Bundled transactions are private, i.e., not exposed via pending transaction filters and subscriptions.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(
+        &self,
+        txs: Vec<Bytes>,
+        options: Option<BundleSubmissionOptions>,
+    ) -> RpcResult<SubmittedBundle>;
 }
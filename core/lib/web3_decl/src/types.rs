@@ -355,6 +355,7 @@ mod tests {
         let test_vector = &[
             (r#""committed""#, BlockNumber::Committed),
             (r#""finalized""#, BlockNumber::Finalized),
+            (r#""tee_finalized""#, BlockNumber::TeeFinalized),
             (r#""pending""#, BlockNumber::Pending),
             (r#""latest""#, BlockNumber::Latest),
             (r#""earliest""#, BlockNumber::Earliest),
@@ -94,6 +94,11 @@ pub enum Component {
     BaseTokenRatioPersister,
     /// VM runner-based component that saves VM execution data for basic witness generation.
     VmRunnerBwip,
+    /// Component pruning old data from Postgres.
+    DbPruner,
+    /// Component archiving L2 blocks to the object store (and optionally IPFS), so that external nodes
+    /// can sync from the archive.
+    BlockArchiver,
 }
 
 #[derive(Debug)]
@@ -138,6 +143,8 @@ impl FromStr for Components {
                 Ok(Components(vec![Component::BaseTokenRatioPersister]))
             }
             "vm_runner_bwip" => Ok(Components(vec![Component::VmRunnerBwip])),
+            "db_pruner" => Ok(Components(vec![Component::DbPruner])),
+            "block_archiver" => Ok(Components(vec![Component::BlockArchiver])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
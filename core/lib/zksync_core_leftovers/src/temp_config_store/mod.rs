@@ -17,10 +17,11 @@ use zksync_config::{
         GeneralConfig, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
         ProtectiveReadsWriterConfig, PruningConfig, SnapshotRecoveryConfig,
     },
-    ApiConfig, BaseTokenAdjusterConfig, ContractVerifierConfig, DADispatcherConfig, DBConfig,
-    EthConfig, EthWatchConfig, GasAdjusterConfig, ObjectStoreConfig, PostgresConfig,
-    SnapshotsCreatorConfig,
+    ApiConfig, BaseTokenAdjusterConfig, BlockArchiverConfig, ContractVerifierConfig,
+    DADispatcherConfig, DBConfig, EthConfig, EthWatchConfig, GasAdjusterConfig, ObjectStoreConfig,
+    PostgresConfig, SnapshotsCreatorConfig,
 };
+use zksync_env_config::FromEnv;
 use zksync_protobuf::repr::ProtoRepr;
 
 pub fn decode_yaml_repr<T: ProtoRepr>(yaml: &str) -> anyhow::Result<T::Type> {
@@ -70,6 +71,7 @@ pub struct TempConfigStore {
     pub basic_witness_input_producer_config: Option<BasicWitnessInputProducerConfig>,
     pub core_object_store: Option<ObjectStoreConfig>,
     pub base_token_adjuster_config: Option<BaseTokenAdjusterConfig>,
+    pub block_archiver_config: Option<BlockArchiverConfig>,
     pub commitment_generator: Option<CommitmentGeneratorConfig>,
     pub pruning: Option<PruningConfig>,
     pub snapshot_recovery: Option<SnapshotRecoveryConfig>,
@@ -103,6 +105,7 @@ impl TempConfigStore {
             basic_witness_input_producer_config: self.basic_witness_input_producer_config.clone(),
             core_object_store: self.core_object_store.clone(),
             base_token_adjuster: self.base_token_adjuster_config.clone(),
+            block_archiver: self.block_archiver_config.clone(),
             commitment_generator: self.commitment_generator.clone(),
             snapshot_recovery: self.snapshot_recovery.clone(),
             pruning: self.pruning.clone(),
@@ -110,7 +113,7 @@ impl TempConfigStore {
     }
 
     #[allow(deprecated)]
-    pub fn wallets(&self) -> Wallets {
+    pub fn wallets(&self) -> anyhow::Result<Wallets> {
         let eth_sender = self.eth_sender_config.as_ref().and_then(|config| {
             let sender = config.sender.as_ref()?;
             let operator_private_key = sender.private_key().ok()??;
@@ -133,9 +136,52 @@ impl TempConfigStore {
                         .expect("Must be presented in env variables"),
                 ),
             });
-        Wallets {
+        // Keystore and remote signers are not a part of the sender config and are only configured via env variables.
+        let env_wallets = Wallets::from_env().context("Wallets::from_env()")?;
+        Ok(Wallets {
             eth_sender,
+            keystore_eth_sender: env_wallets.keystore_eth_sender,
+            remote_eth_sender: env_wallets.remote_eth_sender,
             state_keeper,
-        }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_basic_types::Address;
+
+    use super::*;
+
+    #[test]
+    fn loading_remote_and_keystore_signers_from_env() {
+        let operator = Address::repeat_byte(0x11);
+        std::env::set_var(
+            "ETH_SENDER_SENDER_REMOTE_SIGNER_URL",
+            "http://127.0.0.1:9000/",
+        );
+        std::env::set_var(
+            "ETH_SENDER_SENDER_OPERATOR_COMMIT_ETH_ADDR",
+            format!("{operator:?}"),
+        );
+        std::env::set_var(
+            "ETH_SENDER_SENDER_OPERATOR_KEYSTORE_PATH",
+            "/etc/keystore/operator.json",
+        );
+
+        let wallets = TempConfigStore::default().wallets().unwrap();
+        let remote_eth_sender = wallets.remote_eth_sender.expect("no remote signer");
+        assert_eq!(
+            remote_eth_sender.signer_url.expose_str(),
+            "http://127.0.0.1:9000/"
+        );
+        assert_eq!(remote_eth_sender.operator.address(), operator);
+        assert_eq!(remote_eth_sender.blob_operator, None);
+        let keystore_eth_sender = wallets.keystore_eth_sender.expect("no keystore signer");
+        assert_eq!(
+            keystore_eth_sender.operator.path,
+            PathBuf::from("/etc/keystore/operator.json")
+        );
+        assert_eq!(keystore_eth_sender.blob_operator, None);
     }
 }
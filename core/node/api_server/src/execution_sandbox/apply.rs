@@ -17,13 +17,15 @@ use zksync_multivm::{
     vm_latest::{constants::BATCH_COMPUTATIONAL_GAS_LIMIT, HistoryDisabled},
     VmInstance,
 };
-use zksync_state::{PostgresStorage, ReadStorage, StoragePtr, StorageView, WriteStorage};
+use zksync_state::{
+    PostgresStorage, ReadStorage, StoragePtr, StorageView, StorageWithOverrides, WriteStorage,
+};
 use zksync_system_constants::{
     SYSTEM_CONTEXT_ADDRESS, SYSTEM_CONTEXT_CURRENT_L2_BLOCK_INFO_POSITION,
     SYSTEM_CONTEXT_CURRENT_TX_ROLLING_HASH_POSITION, ZKPORTER_IS_AVAILABLE,
 };
 use zksync_types::{
-    api,
+    api::{self, state_override::StateOverride},
     block::{pack_block_info, unpack_block_info, L2BlockHasher},
     fee_model::BatchFeeInput,
     get_nonce_key,
@@ -38,7 +40,8 @@ use super::{
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
 
-type BoxedVm<'a> = Box<VmInstance<StorageView<PostgresStorage<'a>>, HistoryDisabled>>;
+type SandboxStorage<'a> = StorageView<StorageWithOverrides<PostgresStorage<'a>>>;
+type BoxedVm<'a> = Box<VmInstance<SandboxStorage<'a>, HistoryDisabled>>;
 
 #[derive(Debug)]
 struct Sandbox<'a> {
@@ -46,7 +49,7 @@ struct Sandbox<'a> {
     l1_batch_env: L1BatchEnv,
     execution_args: &'a TxExecutionArgs,
     l2_block_info_to_reset: Option<StoredL2BlockInfo>,
-    storage_view: StorageView<PostgresStorage<'a>>,
+    storage_view: SandboxStorage<'a>,
 }
 
 impl<'a> Sandbox<'a> {
@@ -55,6 +58,7 @@ impl<'a> Sandbox<'a> {
         shared_args: TxSharedArgs,
         execution_args: &'a TxExecutionArgs,
         block_args: BlockArgs,
+        state_override: &StateOverride,
     ) -> anyhow::Result<Sandbox<'a>> {
         let resolve_started_at = Instant::now();
         let resolved_block_info = block_args
@@ -73,12 +77,8 @@ impl<'a> Sandbox<'a> {
                 .schedule_values_update(resolved_block_info.state_l2_block_number);
         }
 
-        let (next_l2_block_info, l2_block_info_to_reset) = Self::load_l2_block_info(
-            &mut connection,
-            block_args.is_pending_l2_block(),
-            &resolved_block_info,
-        )
-        .await?;
+        let (next_l2_block_info, l2_block_info_to_reset) =
+            Self::load_l2_block_info(&mut connection, &resolved_block_info).await?;
 
         let storage = PostgresStorage::new_async(
             Handle::current(),
@@ -90,6 +90,7 @@ impl<'a> Sandbox<'a> {
         .context("cannot create `PostgresStorage`")?
         .with_caches(shared_args.caches.clone());
 
+        let storage = StorageWithOverrides::new(storage, state_override);
         let storage_view = StorageView::new(storage);
         let (system_env, l1_batch_env) = Self::prepare_env(
             shared_args,
@@ -109,7 +110,6 @@ impl<'a> Sandbox<'a> {
 
     async fn load_l2_block_info(
         connection: &mut Connection<'_, Core>,
-        is_pending_block: bool,
         resolved_block_info: &ResolvedBlockInfo,
     ) -> anyhow::Result<(L2BlockEnv, Option<StoredL2BlockInfo>)> {
         let mut l2_block_info_to_reset = None;
@@ -121,10 +121,11 @@ impl<'a> Sandbox<'a> {
         .await
         .context("failed reading L2 block info")?;
 
-        let next_l2_block_info = if is_pending_block {
+        let next_l2_block_timestamp = resolved_block_info.next_l2_block_timestamp;
+        let next_l2_block_info = if let Some(timestamp) = next_l2_block_timestamp {
             L2BlockEnv {
                 number: current_l2_block_info.l2_block_number + 1,
-                timestamp: resolved_block_info.l1_batch_timestamp,
+                timestamp,
                 prev_block_hash: current_l2_block_info.l2_block_hash,
                 // For simplicity, we assume each L2 block create one virtual block.
                 // This may be wrong only during transition period.
@@ -259,7 +260,7 @@ impl<'a> Sandbox<'a> {
         mut self,
         tx: &Transaction,
         adjust_pubdata_price: bool,
-    ) -> (BoxedVm<'a>, StoragePtr<StorageView<PostgresStorage<'a>>>) {
+    ) -> (BoxedVm<'a>, StoragePtr<SandboxStorage<'a>>) {
         self.setup_storage_view(tx);
         let protocol_version = self.system_env.version;
         if adjust_pubdata_price {
@@ -295,8 +296,9 @@ pub(super) fn apply_vm_in_sandbox<T>(
     connection_pool: &ConnectionPool<Core>,
     tx: Transaction,
     block_args: BlockArgs,
+    state_override: Option<StateOverride>,
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<SandboxStorage<'_>, HistoryDisabled>,
         Transaction,
         ProtocolVersionId,
     ) -> T,
@@ -319,6 +321,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
         shared_args,
         execution_args,
         block_args,
+        &state_override.unwrap_or_default(),
     ))?;
     let protocol_version = sandbox.system_env.version;
     let (mut vm, storage_view) = sandbox.into_vm(&tx, adjust_pubdata_price);
@@ -407,12 +410,15 @@ impl StoredL2BlockInfo {
 
 #[derive(Debug)]
 pub(crate) struct ResolvedBlockInfo {
-    state_l2_block_number: L2BlockNumber,
-    state_l2_block_hash: H256,
-    vm_l1_batch_number: L1BatchNumber,
-    l1_batch_timestamp: u64,
+    pub(super) state_l2_block_number: L2BlockNumber,
+    pub(super) state_l2_block_hash: H256,
+    pub(super) vm_l1_batch_number: L1BatchNumber,
+    pub(super) l1_batch_timestamp: u64,
+    /// Timestamp of a new L2 block started on top of the state L2 block. If `None`, the transaction
+    /// is executed in the context of the state L2 block itself.
+    pub(super) next_l2_block_timestamp: Option<u64>,
     pub(crate) protocol_version: ProtocolVersionId,
-    historical_fee_input: Option<BatchFeeInput>,
+    pub(super) historical_fee_input: Option<BatchFeeInput>,
 }
 
 impl BlockArgs {
@@ -437,6 +443,7 @@ impl BlockArgs {
         connection: &mut Connection<'_, Core>,
     ) -> anyhow::Result<ResolvedBlockInfo> {
         let (state_l2_block_number, vm_l1_batch_number, l1_batch_timestamp);
+        let (next_l2_block_timestamp, env_l2_block_header);
 
         let l2_block_header = if self.is_pending_l2_block() {
             vm_l1_batch_number = connection
@@ -453,7 +460,38 @@ impl BlockArgs {
             state_l2_block_number = sealed_l2_block_header.number;
             // Timestamp of the next L1 batch must be greater than the timestamp of the last L2 block.
             l1_batch_timestamp = seconds_since_epoch().max(sealed_l2_block_header.timestamp + 1);
+            next_l2_block_timestamp = Some(l1_batch_timestamp);
+            env_l2_block_header = None;
             sealed_l2_block_header
+        } else if self.in_next_l2_block {
+            // The environment (L1 batch, timestamps, fee input and protocol version) is taken from the next L2 block,
+            // while the state is taken from the end of the resolved block.
+            let next_l2_block_number = self.resolved_block_number + 1;
+            let next_l2_block_header = connection
+                .blocks_dal()
+                .get_l2_block_header(next_l2_block_number)
+                .await?
+                .context("next L2 block is not in storage")?;
+            let l1_batch = connection
+                .storage_web3_dal()
+                .resolve_l1_batch_number_of_l2_block(next_l2_block_number)
+                .await
+                .context("failed resolving L1 batch for next L2 block")?;
+            vm_l1_batch_number = l1_batch.expected_l1_batch();
+            l1_batch_timestamp = connection
+                .blocks_web3_dal()
+                .get_expected_l1_batch_timestamp(&l1_batch)
+                .await?
+                .context("missing timestamp for L1 batch of next L2 block")?;
+            state_l2_block_number = self.resolved_block_number;
+            next_l2_block_timestamp = Some(next_l2_block_header.timestamp);
+            env_l2_block_header = Some(next_l2_block_header);
+
+            connection
+                .blocks_dal()
+                .get_l2_block_header(self.resolved_block_number)
+                .await?
+                .context("resolved L2 block disappeared from storage")?
         } else {
             vm_l1_batch_number = connection
                 .storage_web3_dal()
@@ -465,6 +503,8 @@ impl BlockArgs {
                 .l1_batch_timestamp_s
                 .context("L1 batch timestamp is `None` for non-pending block args")?;
             state_l2_block_number = self.resolved_block_number;
+            next_l2_block_timestamp = None;
+            env_l2_block_header = None;
 
             connection
                 .blocks_dal()
@@ -473,7 +513,9 @@ impl BlockArgs {
                 .context("resolved L2 block disappeared from storage")?
         };
 
-        let historical_fee_input = if !self.is_estimate_like() {
+        let historical_fee_input = if let Some(header) = &env_l2_block_header {
+            Some(header.batch_fee_input)
+        } else if !self.is_estimate_like() {
             let l2_block_header = connection
                 .blocks_dal()
                 .get_l2_block_header(self.resolved_block_number)
@@ -486,7 +528,9 @@ impl BlockArgs {
 
         // Blocks without version specified are considered to be of `Version9`.
         // TODO: remove `unwrap_or` when protocol version ID will be assigned for each block.
-        let protocol_version = l2_block_header
+        let protocol_version = env_l2_block_header
+            .as_ref()
+            .unwrap_or(&l2_block_header)
             .protocol_version
             .unwrap_or(ProtocolVersionId::last_potentially_undefined());
 
@@ -495,6 +539,7 @@ impl BlockArgs {
             state_l2_block_hash: l2_block_header.hash,
             vm_l1_batch_number,
             l1_batch_timestamp,
+            next_l2_block_timestamp,
             protocol_version,
             historical_fee_input,
         })
@@ -1,16 +1,25 @@
 //! Implementation of "executing" methods, e.g. `eth_call`.
 
+use std::{
+    iter,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use anyhow::Context as _;
+use once_cell::sync::OnceCell;
+use tokio::sync::mpsc;
 use tracing::{span, Level};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_multivm::{
-    interface::{TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
+    interface::{ExecutionResult, TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
     tracers::StorageInvocations,
     MultiVMTracer,
 };
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, transaction_request::CallOverrides,
-    ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    api::state_override::StateOverride, fee::TransactionExecutionMetrics, l2::L2Tx,
+    transaction_request::CallOverrides, vm_trace::Call, ExecuteTransactionCommon, Nonce,
+    PackedEthSignature, Transaction, U256,
 };
 
 use super::{
@@ -38,6 +47,12 @@ impl TxExecutionArgs {
         }
     }
 
+    /// Arguments to re-execute transactions that were already included into an L2 block (e.g., to trace them).
+    /// Transactions are executed with the base fee of their L2 block; nonces are not enforced.
+    pub fn for_replay(base_fee: u64, vm_execution_cache_misses_limit: Option<usize>) -> Self {
+        Self::for_eth_call(Some(base_fee), vm_execution_cache_misses_limit)
+    }
+
     fn for_eth_call(
         enforced_base_fee: Option<u64>,
         vm_execution_cache_misses_limit: Option<usize>,
@@ -86,6 +101,36 @@ pub(crate) struct TransactionExecutionOutput {
     pub are_published_bytecodes_ok: bool,
 }
 
+/// Output of a single transaction executed as a part of a bundle.
+#[derive(Debug, Clone)]
+pub(crate) struct BundleTxOutput {
+    /// Output of the VM.
+    pub vm: VmExecutionResultAndLogs,
+    /// Execution metrics.
+    pub metrics: TransactionExecutionMetrics,
+    /// Calls made by the transaction; only collected if requested.
+    pub call_traces: Option<Vec<Call>>,
+}
+
+/// Output of executing a bundle of transactions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BundleExecutionOutput {
+    /// Outputs for executed transactions. Execution stops after the first halted transaction,
+    /// so this may contain fewer entries than the bundle.
+    pub txs: Vec<BundleTxOutput>,
+    /// Set if execution was interrupted because of a timeout.
+    pub timed_out: bool,
+}
+
+impl BundleExecutionOutput {
+    /// Records the output of a transaction. Returns `false` if the bundle cannot be executed further.
+    fn push(&mut self, tx_output: BundleTxOutput) -> bool {
+        let is_halted = matches!(tx_output.vm.result, ExecutionResult::Halt { .. });
+        self.txs.push(tx_output);
+        !is_halted
+    }
+}
+
 /// Executor of transactions.
 #[derive(Debug)]
 pub(crate) enum TransactionExecutor {
@@ -111,6 +156,7 @@ impl TransactionExecutor {
         connection_pool: ConnectionPool<Core>,
         tx: Transaction,
         block_args: BlockArgs,
+        state_override: Option<StateOverride>,
         custom_tracers: Vec<ApiTracer>,
     ) -> anyhow::Result<TransactionExecutionOutput> {
         if let Self::Mock(mock_executor) = self {
@@ -129,6 +175,7 @@ impl TransactionExecutor {
                 &connection_pool,
                 tx,
                 block_args,
+                state_override,
                 |vm, tx, _| {
                     let storage_invocation_tracer =
                         StorageInvocations::new(execution_args.missed_storage_invocation_limit);
@@ -159,6 +206,74 @@ impl TransactionExecutor {
         })
     }
 
+    /// Re-executes a transaction that was already included into an L2 block. `preceding_txs` (i.e., transactions
+    /// preceding the replayed one in its L2 block) are executed beforehand in the same VM, so that the replayed transaction
+    /// observes their state changes. `custom_tracers` are only applied to the replayed transaction.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all, fields(preceding_txs = preceding_txs.len()))]
+    pub async fn replay_tx_in_sandbox(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        execution_args: TxExecutionArgs,
+        connection_pool: ConnectionPool<Core>,
+        preceding_txs: Vec<Transaction>,
+        tx: Transaction,
+        block_args: BlockArgs,
+        custom_tracers: Vec<ApiTracer>,
+    ) -> anyhow::Result<VmExecutionResultAndLogs> {
+        if let Self::Mock(mock_executor) = self {
+            return Ok(mock_executor.execute_tx(&tx, &block_args)?.vm);
+        }
+
+        let mut txs = preceding_txs.into_iter().chain([tx]);
+        let first_tx = txs.next().unwrap(); // `unwrap()` is safe: `txs` contain at least the replayed transaction
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "replay_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                None,
+                |vm, first_tx, _| {
+                    let mut txs: Vec<_> = iter::once(first_tx).chain(txs).collect();
+                    let tx = txs.pop().unwrap(); // `unwrap()` is safe: `txs` are non-empty
+                    for preceding_tx in txs {
+                        let storage_invocation_tracer =
+                            StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                        vm.inspect_transaction_with_bytecode_compression(
+                            vec![storage_invocation_tracer.into_tracer_pointer()].into(),
+                            preceding_tx,
+                            true,
+                        );
+                    }
+
+                    let storage_invocation_tracer =
+                        StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                    let custom_tracers: Vec<_> = custom_tracers
+                        .into_iter()
+                        .map(|tracer| tracer.into_boxed())
+                        .chain([storage_invocation_tracer.into_tracer_pointer()])
+                        .collect();
+                    let (_, vm_result) = vm.inspect_transaction_with_bytecode_compression(
+                        custom_tracers.into(),
+                        tx,
+                        true,
+                    );
+                    vm_result
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("transaction replay panicked")?
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn execute_tx_eth_call(
         &self,
@@ -168,6 +283,7 @@ impl TransactionExecutor {
         call_overrides: CallOverrides,
         mut tx: L2Tx,
         block_args: BlockArgs,
+        state_override: Option<StateOverride>,
         vm_execution_cache_misses_limit: Option<usize>,
         custom_tracers: Vec<ApiTracer>,
     ) -> anyhow::Result<VmExecutionResultAndLogs> {
@@ -189,9 +305,142 @@ impl TransactionExecutor {
                 connection_pool,
                 tx.into(),
                 block_args,
+                state_override,
                 custom_tracers,
             )
             .await?;
         Ok(output.vm)
     }
+
+    /// Executes an ordered bundle of calls on top of the same VM instance, so that each call observes
+    /// state changes made by the preceding ones. Execution is stopped after a halted call, or once `timeout` elapses;
+    /// in the latter case, outputs of the calls completed before the timeout are returned.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip_all, fields(bundle_size = txs.len()))]
+    pub async fn execute_bundle_eth_call(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        call_overrides: CallOverrides,
+        txs: Vec<L2Tx>,
+        block_args: BlockArgs,
+        state_override: Option<StateOverride>,
+        vm_execution_cache_misses_limit: Option<usize>,
+        trace_calls: bool,
+        timeout: Duration,
+    ) -> anyhow::Result<BundleExecutionOutput> {
+        let deadline = Instant::now() + timeout;
+        let bundle_size = txs.len();
+        let mut txs = txs.into_iter().map(|mut tx| {
+            if tx.common_data.signature.is_empty() {
+                tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+            }
+            Transaction::from(tx)
+        });
+        let Some(first_tx) = txs.next() else {
+            return Ok(BundleExecutionOutput::default());
+        };
+
+        if let Self::Mock(mock_executor) = self {
+            let mut output = BundleExecutionOutput::default();
+            for tx in iter::once(first_tx).chain(txs) {
+                let TransactionExecutionOutput { vm, metrics, .. } =
+                    mock_executor.execute_tx(&tx, &block_args)?;
+                let call_traces = trace_calls.then(Vec::new);
+                if !output.push(BundleTxOutput {
+                    vm,
+                    metrics,
+                    call_traces,
+                }) {
+                    break;
+                }
+            }
+            return Ok(output);
+        }
+
+        let execution_args = TxExecutionArgs::for_eth_call(
+            call_overrides.enforced_base_fee,
+            vm_execution_cache_misses_limit,
+        );
+        // Outputs are streamed from the blocking task, so that the timeout can be applied to each call.
+        let (output_sender, mut output_receiver) = mpsc::unbounded_channel();
+        let execution_task = tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "execute_bundle_in_sandbox").entered();
+            let result = apply::apply_vm_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx,
+                block_args,
+                state_override,
+                |vm, first_tx, _| {
+                    for tx in iter::once(first_tx).chain(txs) {
+                        // The receiver is dropped if the bundle has timed out.
+                        if output_sender.is_closed() {
+                            break;
+                        }
+
+                        let total_factory_deps = tx.execute.factory_deps.len() as u16;
+                        let storage_invocation_tracer =
+                            StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                        let call_tracer_result = Arc::new(OnceCell::default());
+                        let mut tracers = vec![storage_invocation_tracer.into_tracer_pointer()];
+                        if trace_calls {
+                            tracers.push(
+                                ApiTracer::CallTracer(call_tracer_result.clone()).into_boxed(),
+                            );
+                        }
+                        let (_, vm_result) = vm.inspect_transaction_with_bytecode_compression(
+                            tracers.into(),
+                            tx,
+                            true,
+                        );
+                        let call_traces = trace_calls
+                            .then(|| call_tracer_result.get().cloned().unwrap_or_default());
+                        let metrics = vm_metrics::collect_tx_execution_metrics(
+                            total_factory_deps,
+                            &vm_result,
+                        );
+                        let is_halted = matches!(vm_result.result, ExecutionResult::Halt { .. });
+                        let tx_output = BundleTxOutput {
+                            vm: vm_result,
+                            metrics,
+                            call_traces,
+                        };
+                        if output_sender.send(tx_output).is_err() || is_halted {
+                            break;
+                        }
+                    }
+                },
+            );
+            span.exit();
+            result
+        });
+
+        let mut output = BundleExecutionOutput::default();
+        loop {
+            let remaining_time = deadline.saturating_duration_since(Instant::now());
+            match tokio::time::timeout(remaining_time, output_receiver.recv()).await {
+                Ok(Some(tx_output)) => {
+                    if !output.push(tx_output) {
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(_) => {
+                    // The VM cannot be interrupted in the middle of a call, so the blocking task finishes
+                    // the current call (holding the VM permit) and then exits since the receiver is dropped.
+                    output.timed_out = output.txs.len() < bundle_size;
+                    return Ok(output);
+                }
+            }
+        }
+        execution_task
+            .await
+            .context("bundle execution panicked")??;
+        Ok(output)
+    }
 }
@@ -15,7 +15,7 @@ use zksync_types::{
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{TransactionExecutor, TxExecutionArgs},
+    execute::{BundleExecutionOutput, BundleTxOutput, TransactionExecutor, TxExecutionArgs},
     tracers::ApiTracer,
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
@@ -345,6 +345,7 @@ pub(crate) struct BlockArgs {
     block_id: api::BlockId,
     resolved_block_number: L2BlockNumber,
     l1_batch_timestamp_s: Option<u64>,
+    in_next_l2_block: bool,
 }
 
 impl BlockArgs {
@@ -354,6 +355,7 @@ impl BlockArgs {
             block_id,
             resolved_block_number,
             l1_batch_timestamp_s: None,
+            in_next_l2_block: false,
         })
     }
 
@@ -400,9 +402,18 @@ impl BlockArgs {
             block_id,
             resolved_block_number,
             l1_batch_timestamp_s: Some(l1_batch_timestamp),
+            in_next_l2_block: false,
         })
     }
 
+    /// Makes the VM execute transactions on top of the state at the end of the resolved block, but in a new L2 block
+    /// with the environment (L1 batch, timestamp, fee input and protocol version) of the stored next L2 block.
+    /// This is used to replay transactions from the next block.
+    pub(crate) fn in_next_l2_block(mut self) -> Self {
+        self.in_next_l2_block = true;
+        self
+    }
+
     pub fn resolved_block_number(&self) -> L2BlockNumber {
         self.resolved_block_number
     }
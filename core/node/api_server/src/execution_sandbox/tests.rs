@@ -4,6 +4,7 @@ use assert_matches::assert_matches;
 use zksync_dal::ConnectionPool;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
+use zksync_types::block::L2BlockHeader;
 
 use super::*;
 use crate::{execution_sandbox::apply::apply_vm_in_sandbox, tx_sender::ApiContracts};
@@ -162,6 +163,62 @@ async fn creating_block_args_after_snapshot_recovery() {
     }
 }
 
+#[tokio::test]
+async fn resolving_block_info_in_next_l2_block() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    let first_l2_block = create_l2_block(1);
+    storage
+        .blocks_dal()
+        .insert_l2_block(&first_l2_block)
+        .await
+        .unwrap();
+    let second_l2_block = L2BlockHeader {
+        timestamp: 5,
+        batch_fee_input: BatchFeeInput::l1_pegged(200, 200),
+        ..create_l2_block(2)
+    };
+    storage
+        .blocks_dal()
+        .insert_l2_block(&second_l2_block)
+        .await
+        .unwrap();
+
+    let start_info = BlockStartInfo::new(&mut storage, Duration::MAX)
+        .await
+        .unwrap();
+    let block_id = api::BlockId::Number(1.into());
+    let block_args = BlockArgs::new(&mut storage, block_id, &start_info)
+        .await
+        .unwrap();
+    let block_info = block_args.resolve_block_info(&mut storage).await.unwrap();
+    assert_eq!(block_info.state_l2_block_number, L2BlockNumber(1));
+    assert_eq!(block_info.next_l2_block_timestamp, None);
+    assert_eq!(
+        block_info.historical_fee_input,
+        Some(first_l2_block.batch_fee_input)
+    );
+
+    let block_info = block_args
+        .in_next_l2_block()
+        .resolve_block_info(&mut storage)
+        .await
+        .unwrap();
+    assert_eq!(block_info.state_l2_block_number, L2BlockNumber(1));
+    assert_eq!(block_info.state_l2_block_hash, first_l2_block.hash);
+    assert_eq!(block_info.vm_l1_batch_number, L1BatchNumber(1));
+    assert_eq!(block_info.l1_batch_timestamp, first_l2_block.timestamp);
+    assert_eq!(block_info.next_l2_block_timestamp, Some(5));
+    assert_eq!(
+        block_info.historical_fee_input,
+        Some(second_l2_block.batch_fee_input)
+    );
+    test_instantiating_vm(pool.clone(), block_args.in_next_l2_block()).await;
+}
+
 #[tokio::test]
 async fn instantiating_vm() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -195,6 +252,7 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
             &pool,
             transaction.clone(),
             block_args,
+            None,
             |_, received_tx, _| {
                 assert_eq!(received_tx, transaction);
             },
@@ -1,16 +1,23 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use once_cell::sync::OnceCell;
 use zksync_multivm::{
-    tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer,
+    tracers::{prestate_tracer, CallTracer, PrestateTracer, StorageAccessTracer},
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
 };
 use zksync_state::WriteStorage;
-use zksync_types::vm_trace::Call;
+use zksync_types::{vm_trace::Call, StorageKey};
 
 /// Custom tracers supported by our API
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: Arc<OnceCell<(prestate_tracer::State, prestate_tracer::State)>>,
+    },
+    StorageAccessTracer(Arc<OnceCell<HashSet<StorageKey>>>),
 }
 
 impl ApiTracer {
@@ -22,6 +29,12 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::StorageAccessTracer(result) => {
+                StorageAccessTracer::new(result).into_tracer_pointer()
+            }
         }
     }
 }
@@ -1,9 +1,15 @@
 use std::collections::hash_map::{Entry, HashMap};
 
 use tokio::sync::Mutex;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal, DalResult,
+};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{fee::TransactionExecutionMetrics, l2::L2Tx, Address, Nonce, H256};
+use zksync_types::{
+    fee::TransactionExecutionMetrics,
+    l2::{error::TxCheckError::TxDuplication, L2Tx},
+    Address, L2BlockNumber, Nonce, H256,
+};
 
 use super::{tx_sink::TxSink, SubmitTxError};
 use crate::web3::metrics::API_METRICS;
@@ -22,14 +28,12 @@ impl MasterPoolSink {
             inflight_requests: Mutex::new(HashMap::new()),
         }
     }
-}
 
-#[async_trait::async_trait]
-impl TxSink for MasterPoolSink {
-    async fn submit_tx(
+    async fn insert_tx(
         &self,
         tx: &L2Tx,
         execution_metrics: TransactionExecutionMetrics,
+        is_private: bool,
     ) -> Result<L2TxSubmissionResult, SubmitTxError> {
         let address_and_nonce = (tx.initiator_account(), tx.nonce());
 
@@ -51,18 +55,14 @@ impl TxSink for MasterPoolSink {
         };
         drop(lock);
 
-        let result = match self.master_pool.connection_tagged("api").await {
-            Ok(mut connection) => connection
-                .transactions_dal()
-                .insert_transaction_l2(tx, execution_metrics)
-                .await
-                .map(|submission_res_handle| {
-                    APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
-                    submission_res_handle
-                })
-                .map_err(|err| err.generalize().into()),
-            Err(err) => Err(err.generalize().into()),
-        };
+        let result = self
+            .insert_tx_into_db(tx, execution_metrics, is_private)
+            .await
+            .map(|submission_res_handle| {
+                APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
+                submission_res_handle
+            })
+            .map_err(|err| err.generalize().into());
 
         self.inflight_requests
             .lock()
@@ -72,4 +72,108 @@ impl TxSink for MasterPoolSink {
 
         result
     }
+
+    async fn insert_tx_into_db(
+        &self,
+        tx: &L2Tx,
+        execution_metrics: TransactionExecutionMetrics,
+        is_private: bool,
+    ) -> DalResult<L2TxSubmissionResult> {
+        let mut connection = self.master_pool.connection_tagged("api").await?;
+        if !is_private {
+            return connection
+                .transactions_dal()
+                .insert_transaction_l2(tx, execution_metrics)
+                .await;
+        }
+
+        // The transaction must be marked as private atomically with its insertion, so that it's never exposed.
+        let mut transaction = connection.start_transaction().await?;
+        let result = transaction
+            .transactions_dal()
+            .insert_transaction_l2(tx, execution_metrics)
+            .await?;
+        if matches!(
+            result,
+            L2TxSubmissionResult::Added | L2TxSubmissionResult::Replaced
+        ) {
+            transaction
+                .private_txs_dal()
+                .insert_private_tx(tx.hash())
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(result)
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSink for MasterPoolSink {
+    async fn submit_tx(
+        &self,
+        tx: &L2Tx,
+        execution_metrics: TransactionExecutionMetrics,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        self.insert_tx(tx, execution_metrics, false).await
+    }
+
+    async fn submit_private_tx(
+        &self,
+        tx: &L2Tx,
+        execution_metrics: TransactionExecutionMetrics,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        self.insert_tx(tx, execution_metrics, true).await
+    }
+
+    async fn submit_bundle(
+        &self,
+        txs: &[(L2Tx, TransactionExecutionMetrics)],
+        target_l2_block: L2BlockNumber,
+    ) -> Result<(), SubmitTxError> {
+        let mut connection = self
+            .master_pool
+            .connection_tagged("api")
+            .await
+            .map_err(|err| SubmitTxError::Internal(err.generalize()))?;
+        let mut transaction = connection
+            .start_transaction()
+            .await
+            .map_err(|err| SubmitTxError::Internal(err.generalize()))?;
+
+        for (i, (tx, execution_metrics)) in txs.iter().enumerate() {
+            let result = transaction
+                .transactions_dal()
+                .insert_transaction_l2(tx, *execution_metrics)
+                .await
+                .map_err(|err| SubmitTxError::Internal(err.generalize()))?;
+            // If an error is returned, the DB transaction is dropped without committing, so the bundle is not persisted.
+            match result {
+                L2TxSubmissionResult::Added | L2TxSubmissionResult::Replaced => { /* OK */ }
+                L2TxSubmissionResult::Duplicate => {
+                    return Err(SubmitTxError::IncorrectTx(TxDuplication(tx.hash())));
+                }
+                L2TxSubmissionResult::AlreadyExecuted => {
+                    return Err(SubmitTxError::BundleTxFailed(
+                        i,
+                        "transaction with the same nonce is already executed".to_owned(),
+                    ));
+                }
+                L2TxSubmissionResult::InsertionInProgress | L2TxSubmissionResult::Proxied => {
+                    return Err(SubmitTxError::InsertionInProgress);
+                }
+            }
+        }
+
+        let tx_hashes: Vec<_> = txs.iter().map(|(tx, _)| tx.hash()).collect();
+        transaction
+            .private_txs_dal()
+            .insert_bundle(&tx_hashes, target_l2_block)
+            .await
+            .map_err(|err| SubmitTxError::Internal(err.generalize()))?;
+        transaction
+            .commit()
+            .await
+            .map_err(|err| SubmitTxError::Internal(err.generalize()))?;
+        Ok(())
+    }
 }
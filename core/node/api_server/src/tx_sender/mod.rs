@@ -1,8 +1,13 @@
 //! Helper module to submit transactions into the ZKsync Network.
 
-use std::{sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
+use once_cell::sync::OnceCell;
 use tokio::sync::RwLock;
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
 use zksync_contracts::BaseSystemContracts;
@@ -10,7 +15,7 @@ use zksync_dal::{
     transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
 };
 use zksync_multivm::{
-    interface::VmExecutionResultAndLogs,
+    interface::{ExecutionResult, VmExecutionResultAndLogs},
     utils::{
         adjust_pubdata_price_for_tx, derive_base_fee_and_gas_per_pubdata, derive_overhead,
         get_eth_call_gas_limit, get_max_batch_gas_limit,
@@ -20,28 +25,31 @@ use zksync_multivm::{
 use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
 use zksync_state::PostgresStorageCaches;
 use zksync_state_keeper::{
+    allowlist::TxAllowlistCache,
     seal_criteria::{ConditionalSealer, NoopSealer, SealData},
     SequencerSealer,
 };
 use zksync_types::{
+    api::state_override::StateOverride,
     fee::{Fee, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
     get_code_key, get_intrinsic_constants,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
-    AccountTreeId, Address, ExecuteTransactionCommon, L2ChainId, Nonce, PackedEthSignature,
-    ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
-    MAX_NEW_FACTORY_DEPS, U256,
+    AccountTreeId, Address, ExecuteTransactionCommon, L2BlockNumber, L2ChainId, Nonce,
+    PackedEthSignature, ProtocolVersionId, StorageKey, Transaction, VmVersion, H160, H256,
+    MAX_L2_TX_GAS_LIMIT, MAX_NEW_FACTORY_DEPS, U256,
 };
-use zksync_utils::h256_to_u256;
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256};
 
 pub(super) use self::result::SubmitTxError;
 use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        ApiTracer, BlockArgs, BundleExecutionOutput, SubmitTxStage, TransactionExecutor,
+        TxExecutionArgs, TxSharedArgs, VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit,
+        SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
@@ -211,6 +219,8 @@ pub struct TxSenderBuilder {
     sealer: Option<Arc<dyn ConditionalSealer>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Allowlist of senders / contracts for permissioned deployments.
+    tx_allowlist: Option<TxAllowlistCache>,
 }
 
 impl TxSenderBuilder {
@@ -225,6 +235,7 @@ impl TxSenderBuilder {
             tx_sink,
             sealer: None,
             whitelisted_tokens_for_aa_cache: None,
+            tx_allowlist: None,
         }
     }
 
@@ -238,6 +249,12 @@ impl TxSenderBuilder {
         self
     }
 
+    /// Enables allowlist mode: only transactions permitted by the allowlist will be accepted.
+    pub fn with_tx_allowlist(mut self, tx_allowlist: TxAllowlistCache) -> Self {
+        self.tx_allowlist = Some(tx_allowlist);
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -261,6 +278,7 @@ impl TxSenderBuilder {
             vm_concurrency_limiter,
             storage_caches,
             whitelisted_tokens_for_aa_cache,
+            tx_allowlist: self.tx_allowlist,
             sealer,
             executor: TransactionExecutor::Real,
         }))
@@ -318,6 +336,8 @@ pub struct TxSenderInner {
     storage_caches: PostgresStorageCaches,
     // Cache for white-listed tokens.
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
+    /// Allowlist of senders / contracts for permissioned deployments.
+    tx_allowlist: Option<TxAllowlistCache>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Arc<dyn ConditionalSealer>,
     pub(super) executor: TransactionExecutor,
@@ -357,6 +377,24 @@ impl TxSender {
     pub async fn submit_tx(
         &self,
         tx: L2Tx,
+    ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
+        self.submit_tx_inner(tx, false).await
+    }
+
+    /// Same as [`Self::submit_tx()`], but the transaction is not exposed via pending transaction filters
+    /// and subscriptions.
+    #[tracing::instrument(level = "debug", skip_all, fields(tx.hash = ?tx.hash()))]
+    pub async fn submit_private_tx(
+        &self,
+        tx: L2Tx,
+    ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
+        self.submit_tx_inner(tx, true).await
+    }
+
+    async fn submit_tx_inner(
+        &self,
+        tx: L2Tx,
+        is_private: bool,
     ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
         let tx_hash = tx.hash();
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
@@ -385,6 +423,7 @@ impl TxSender {
                 self.0.replica_connection_pool.clone(),
                 tx.clone().into(),
                 block_args,
+                None,
                 vec![],
             )
             .await?;
@@ -417,15 +456,24 @@ impl TxSender {
         if !execution_output.are_published_bytecodes_ok {
             return Err(SubmitTxError::FailedToPublishCompressedBytecodes);
         }
+        // Deployments via factory contracts or smart accounts are only visible after execution.
+        if let Some(tx_allowlist) = &self.0.tx_allowlist {
+            tx_allowlist
+                .get()
+                .check_deployments(&execution_output.vm.logs.events)?;
+        }
 
         let mut stage_latency =
             SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DbInsert);
         self.ensure_tx_executable(&tx.clone().into(), &execution_output.metrics, true)?;
-        let submission_res_handle = self
-            .0
-            .tx_sink
-            .submit_tx(&tx, execution_output.metrics)
-            .await?;
+        let tx_sink = &self.0.tx_sink;
+        let submission_res_handle = if is_private {
+            tx_sink
+                .submit_private_tx(&tx, execution_output.metrics)
+                .await?
+        } else {
+            tx_sink.submit_tx(&tx, execution_output.metrics).await?
+        };
 
         match submission_res_handle {
             L2TxSubmissionResult::AlreadyExecuted => {
@@ -458,6 +506,73 @@ impl TxSender {
         }
     }
 
+    /// Submits a bundle of transactions that will be included atomically and in order into a single L2 block
+    /// no later than `target_l2_block`. Each transaction is validated separately; then, the entire bundle
+    /// is simulated on top of the pending state, and all transactions in it must succeed.
+    #[tracing::instrument(level = "debug", skip_all, fields(txs.len = txs.len()))]
+    pub async fn submit_bundle(
+        &self,
+        txs: Vec<L2Tx>,
+        target_l2_block: L2BlockNumber,
+        simulation_timeout: Duration,
+    ) -> Result<(), SubmitTxError> {
+        if txs.is_empty() {
+            return Err(SubmitTxError::EmptyBundle);
+        }
+
+        let mut connection = self.acquire_replica_connection().await?;
+        let protocol_version = connection.blocks_dal().pending_protocol_version().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+        for tx in &txs {
+            self.validate_tx(tx, protocol_version).await?;
+        }
+
+        let output = self
+            .simulate_bundle(
+                block_args,
+                CallOverrides {
+                    enforced_base_fee: None,
+                },
+                txs.clone(),
+                None,
+                false,
+                simulation_timeout,
+            )
+            .await?;
+        if output.timed_out {
+            return Err(SubmitTxError::BundleTimeout(output.txs.len()));
+        }
+        let mut txs_with_metrics = Vec::with_capacity(txs.len());
+        for (i, (tx, output)) in txs.into_iter().zip(output.txs).enumerate() {
+            match output.vm.result {
+                ExecutionResult::Success { .. } => { /* OK */ }
+                ExecutionResult::Revert { output } => {
+                    return Err(SubmitTxError::BundleTxFailed(
+                        i,
+                        output.to_user_friendly_string(),
+                    ));
+                }
+                ExecutionResult::Halt { reason } => {
+                    return Err(SubmitTxError::BundleTxFailed(i, reason.to_string()));
+                }
+            }
+            self.ensure_tx_executable(&tx.clone().into(), &output.metrics, true)
+                .map_err(|err| match err {
+                    SubmitTxError::Unexecutable(message) => {
+                        SubmitTxError::BundleTxFailed(i, message)
+                    }
+                    err => err,
+                })?;
+            txs_with_metrics.push((tx, output.metrics));
+        }
+
+        self.0
+            .tx_sink
+            .submit_bundle(&txs_with_metrics, target_l2_block)
+            .await
+    }
+
     /// **Important.** For the main node, this method acquires a DB connection inside `get_batch_fee_input()`.
     /// Thus, you shouldn't call it if you're holding a DB connection already.
     async fn shared_args(&self) -> anyhow::Result<TxSharedArgs> {
@@ -486,6 +601,12 @@ impl TxSender {
         tx: &L2Tx,
         protocol_version: ProtocolVersionId,
     ) -> Result<(), SubmitTxError> {
+        if let Some(tx_allowlist) = &self.0.tx_allowlist {
+            tx_allowlist
+                .get()
+                .check(tx.initiator_account(), tx.recipient_account())?;
+        }
+
         // This check is intended to ensure that the gas-related values will be safe to convert to u64 in the future computations.
         let max_gas = U256::from(u64::MAX);
         if tx.common_data.fee.gas_limit > max_gas
@@ -656,6 +777,7 @@ impl TxSender {
         block_args: BlockArgs,
         base_fee: u64,
         vm_version: VmVersion,
+        state_override: Option<StateOverride>,
     ) -> anyhow::Result<(VmExecutionResultAndLogs, TransactionExecutionMetrics)> {
         let gas_limit_with_overhead = tx_gas_limit
             + derive_overhead(
@@ -703,6 +825,7 @@ impl TxSender {
                 self.0.replica_connection_pool.clone(),
                 tx.clone(),
                 block_args,
+                state_override,
                 vec![],
             )
             .await?;
@@ -733,6 +856,7 @@ impl TxSender {
         mut tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u64,
+        state_override: Option<StateOverride>,
     ) -> Result<Fee, SubmitTxError> {
         let estimation_started_at = Instant::now();
 
@@ -771,24 +895,37 @@ impl TxSender {
         }
 
         let hashed_key = get_code_key(&tx.initiator_account());
+        let initiator_override = state_override
+            .as_ref()
+            .and_then(|state_override| state_override.get(&tx.initiator_account()));
         // If the default account does not have enough funds for transferring `tx.value`, without taking into account the fee,
         // there is no sense to estimate the fee.
-        let account_code_hash = self
-            .acquire_replica_connection()
-            .await?
-            .storage_web3_dal()
-            .get_value(&hashed_key)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed getting code hash for account {:?}",
-                    tx.initiator_account()
-                )
-            })?;
+        let account_code_hash =
+            if let Some(code) = initiator_override.and_then(|acc| acc.code.as_ref()) {
+                if code.0.is_empty() {
+                    H256::zero()
+                } else {
+                    hash_bytecode(&code.0)
+                }
+            } else {
+                self.acquire_replica_connection()
+                    .await?
+                    .storage_web3_dal()
+                    .get_value(&hashed_key)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "failed getting code hash for account {:?}",
+                            tx.initiator_account()
+                        )
+                    })?
+            };
+        let initiator_balance = match initiator_override.and_then(|acc| acc.balance) {
+            Some(balance) => balance,
+            None => self.get_balance(&tx.initiator_account()).await?,
+        };
 
-        if !tx.is_l1()
-            && account_code_hash == H256::zero()
-            && tx.execute.value > self.get_balance(&tx.initiator_account()).await?
+        if !tx.is_l1() && account_code_hash == H256::zero() && tx.execute.value > initiator_balance
         {
             tracing::info!(
                 "fee estimation failed on validation step.
@@ -836,6 +973,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.clone(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -871,6 +1009,7 @@ impl TxSender {
                     block_args,
                     base_fee,
                     protocol_version.into(),
+                    state_override.clone(),
                 )
                 .await
                 .context("estimate_gas step failed")?;
@@ -903,6 +1042,7 @@ impl TxSender {
                 block_args,
                 base_fee,
                 protocol_version.into(),
+                state_override,
             )
             .await
             .context("final estimate_gas step failed")?;
@@ -973,6 +1113,7 @@ impl TxSender {
         block_args: BlockArgs,
         call_overrides: CallOverrides,
         tx: L2Tx,
+        state_override: Option<StateOverride>,
     ) -> Result<Vec<u8>, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
@@ -987,6 +1128,7 @@ impl TxSender {
                 call_overrides,
                 tx,
                 block_args,
+                state_override,
                 vm_execution_cache_misses_limit,
                 vec![],
             )
@@ -994,15 +1136,79 @@ impl TxSender {
             .into_api_call_result()
     }
 
-    pub async fn gas_price(&self) -> anyhow::Result<u64> {
-        let mut connection = self.acquire_replica_connection().await?;
-        let protocol_version = connection
-            .blocks_dal()
-            .pending_protocol_version()
-            .await
-            .context("failed obtaining pending protocol version")?;
-        drop(connection);
+    /// Executes `tx` in the `eth_call` mode, collecting storage slots accessed during execution.
+    /// Accessed slots are `None` if the VM version used to execute the transaction doesn't support collecting them
+    /// (i.e., for legacy VMs not supporting custom tracers).
+    pub(super) async fn create_access_list(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        tx: L2Tx,
+    ) -> Result<(VmExecutionResultAndLogs, Option<HashSet<StorageKey>>), SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let accessed_keys = Arc::new(OnceCell::default());
+        let custom_tracers = vec![ApiTracer::StorageAccessTracer(accessed_keys.clone())];
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let result = self
+            .0
+            .executor
+            .execute_tx_eth_call(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                call_overrides,
+                tx,
+                block_args,
+                None,
+                vm_execution_cache_misses_limit,
+                custom_tracers,
+            )
+            .await?;
+        // The tracer always sets the result after VM execution; legacy VMs ignore it.
+        let accessed_keys = accessed_keys.get().cloned();
+        Ok((result, accessed_keys))
+    }
+
+    /// Executes `txs` one after another on top of the state at `block_args`. Unlike [`Self::eth_call()`],
+    /// reverted and halted calls are not converted to errors; instead, their results are returned to the caller.
+    /// If the bundle times out, outputs of the calls completed before the timeout are returned.
+    pub(super) async fn simulate_bundle(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        txs: Vec<L2Tx>,
+        state_override: Option<StateOverride>,
+        trace_calls: bool,
+        timeout: Duration,
+    ) -> Result<BundleExecutionOutput, SubmitTxError> {
+        // The entire bundle is executed under a single permit, so it cannot exceed the VM concurrency limit.
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let output = self
+            .0
+            .executor
+            .execute_bundle_eth_call(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                call_overrides,
+                txs,
+                block_args,
+                state_override,
+                vm_execution_cache_misses_limit,
+                trace_calls,
+                timeout,
+            )
+            .await?;
+        Ok(output)
+    }
 
+    pub async fn gas_price(&self) -> anyhow::Result<u64> {
+        let protocol_version = self.pending_protocol_version().await?;
         let (base_fee, _) = derive_base_fee_and_gas_per_pubdata(
             self.scaled_batch_fee_input().await?,
             protocol_version.into(),
@@ -1010,6 +1216,34 @@ impl TxSender {
         Ok(base_fee)
     }
 
+    /// Returns the suggested priority fee. The bootloader charges the base fee derived from the batch fee input
+    /// at the time of execution, so the priority fee is the headroom between the scaled gas price returned by
+    /// [`Self::gas_price()`] and the base fee for the current (unscaled) batch fee input.
+    pub async fn max_priority_fee_per_gas(&self) -> anyhow::Result<u64> {
+        let protocol_version = self.pending_protocol_version().await?;
+        let (scaled_base_fee, _) = derive_base_fee_and_gas_per_pubdata(
+            self.scaled_batch_fee_input().await?,
+            protocol_version.into(),
+        );
+        let (base_fee, _) = derive_base_fee_and_gas_per_pubdata(
+            self.0
+                .batch_fee_input_provider
+                .get_batch_fee_input()
+                .await?,
+            protocol_version.into(),
+        );
+        Ok(scaled_base_fee.saturating_sub(base_fee))
+    }
+
+    async fn pending_protocol_version(&self) -> anyhow::Result<ProtocolVersionId> {
+        let mut connection = self.acquire_replica_connection().await?;
+        connection
+            .blocks_dal()
+            .pending_protocol_version()
+            .await
+            .context("failed obtaining pending protocol version")
+    }
+
     fn ensure_tx_executable(
         &self,
         transaction: &Transaction,
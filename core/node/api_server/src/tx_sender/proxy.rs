@@ -16,7 +16,7 @@ use zksync_types::{api, fee::TransactionExecutionMetrics, l2::L2Tx, Address, Non
 use zksync_web3_decl::{
     client::{DynClient, L2},
     error::{ClientRpcContext, EnrichedClientResult, Web3Error},
-    namespaces::EthNamespaceClient,
+    namespaces::{EthNamespaceClient, TxpoolNamespaceClient},
 };
 
 use super::{tx_sink::TxSink, SubmitTxError};
@@ -378,6 +378,29 @@ impl TxSink for TxProxy {
         }
         Ok(None)
     }
+
+    async fn lookup_txpool_content(
+        &self,
+        initiator_address: Option<Address>,
+    ) -> Result<Option<api::TxpoolContent>, Web3Error> {
+        // EN doesn't have a mempool, so we request pending transactions from the main node.
+        let content = if let Some(initiator_address) = initiator_address {
+            self.client
+                .content_from(initiator_address)
+                .rpc_context("txpool_contentFrom")
+                .with_arg("initiator_address", &initiator_address)
+                .await?
+                .into_content(initiator_address)
+        } else {
+            self.client.content().rpc_context("txpool_content").await?
+        };
+        Ok(Some(content))
+    }
+
+    async fn lookup_txpool_status(&self) -> Result<Option<api::TxpoolStatus>, Web3Error> {
+        let status = self.client.status().rpc_context("txpool_status").await?;
+        Ok(Some(status))
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use thiserror::Error;
 use zksync_multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
+use zksync_state_keeper::allowlist::TxAllowlistError;
 use zksync_types::{l2::error::TxCheckError, U256};
 use zksync_web3_decl::error::EnrichedClientError;
 
@@ -72,6 +73,20 @@ pub enum SubmitTxError {
     ProxyError(#[from] EnrichedClientError),
     #[error("not enough gas to publish compressed bytecodes")]
     FailedToPublishCompressedBytecodes,
+    #[error("too many calls in the bundle. {0} provided, while only {1} allowed")]
+    TooManyBundleCalls(usize, usize),
+    #[error("bundle simulation timed out after {0} calls")]
+    BundleTimeout(usize),
+    #[error("transaction is not permitted: {0}")]
+    NotAllowlisted(#[from] TxAllowlistError),
+    #[error("private transactions and bundles are not supported by this node")]
+    PrivateTxsNotSupported,
+    #[error("bundle is empty")]
+    EmptyBundle,
+    #[error("bundle transaction #{0} failed: {1}")]
+    BundleTxFailed(usize, String),
+    #[error("bundle target L2 block #{0} is already sealed")]
+    BundleTargetBlockSealed(u32),
     /// Catch-all internal error (e.g., database error) that should not be exposed to the caller.
     #[error("internal error")]
     Internal(#[from] anyhow::Error),
@@ -107,6 +122,13 @@ impl SubmitTxError {
             Self::IntrinsicGas => "intrinsic-gas",
             Self::ProxyError(_) => "proxy-error",
             Self::FailedToPublishCompressedBytecodes => "failed-to-publish-compressed-bytecodes",
+            Self::TooManyBundleCalls(_, _) => "too-many-bundle-calls",
+            Self::BundleTimeout(_) => "bundle-timeout",
+            Self::NotAllowlisted(_) => "not-allowlisted",
+            Self::PrivateTxsNotSupported => "private-txs-not-supported",
+            Self::EmptyBundle => "empty-bundle",
+            Self::BundleTxFailed(_, _) => "bundle-tx-failed",
+            Self::BundleTargetBlockSealed(_) => "bundle-target-block-sealed",
             Self::Internal(_) => "internal",
         }
     }
@@ -1,12 +1,17 @@
 //! Tests for the transaction sender.
 
 use assert_matches::assert_matches;
-use zksync_multivm::interface::ExecutionResult;
+use zksync_dal::tx_allowlist_dal::TxAllowlistPermission;
+use zksync_multivm::interface::{ExecutionResult, VmExecutionLogs};
 use zksync_node_fee_model::MockBatchFeeParamsProvider;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
-use zksync_types::{get_nonce_key, L1BatchNumber, L2BlockNumber, StorageLog};
-use zksync_utils::u256_to_h256;
+use zksync_state_keeper::allowlist::TxAllowlistError;
+use zksync_types::{
+    event::DEPLOY_EVENT_SIGNATURE, get_nonce_key, L1BatchNumber, L2BlockNumber, StorageLog,
+    VmEvent, CONTRACT_DEPLOYER_ADDRESS,
+};
+use zksync_utils::{address_to_h256, u256_to_h256};
 
 use super::*;
 use crate::{
@@ -155,3 +160,130 @@ async fn submitting_tx_requires_one_connection() {
         .unwrap()
         .expect("transaction is not persisted");
 }
+
+#[tokio::test]
+async fn submitting_tx_in_allowlist_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let fee_input = MockBatchFeeParamsProvider::default()
+        .get_batch_fee_input_scaled(1.0, 1.0)
+        .await
+        .unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let tx = create_l2_transaction(base_fee, gas_per_pubdata);
+    let balance_key = storage_key_for_eth_balance(&tx.initiator_account());
+    let storage_log = StorageLog::new_write_log(balance_key, u256_to_h256(U256::one() << 64));
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(0), &[storage_log])
+        .await
+        .unwrap();
+
+    let mut tx_executor = MockTransactionExecutor::default();
+    tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+    let (mut tx_sender, _) =
+        create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor.into()).await;
+    let tx_allowlist = TxAllowlistCache::new(&pool).await.unwrap();
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_allowlist = Some(tx_allowlist);
+
+    let err = tx_sender.submit_tx(tx.clone()).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::NotAllowlisted(TxAllowlistError::NotAllowlisted { sender, .. })
+            if sender == tx.initiator_account()
+    );
+
+    storage
+        .tx_allowlist_dal()
+        .add_entry(tx.initiator_account(), TxAllowlistPermission::Sender)
+        .await
+        .unwrap();
+    let tx_allowlist = TxAllowlistCache::new(&pool).await.unwrap();
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_allowlist = Some(tx_allowlist);
+
+    let submission_result = tx_sender.submit_tx(tx).await.unwrap();
+    assert_matches!(submission_result.0, L2TxSubmissionResult::Added);
+}
+
+#[tokio::test]
+async fn rejecting_factory_deployment_in_allowlist_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let fee_input = MockBatchFeeParamsProvider::default()
+        .get_batch_fee_input_scaled(1.0, 1.0)
+        .await
+        .unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let tx = create_l2_transaction(base_fee, gas_per_pubdata);
+    let balance_key = storage_key_for_eth_balance(&tx.initiator_account());
+    let storage_log = StorageLog::new_write_log(balance_key, u256_to_h256(U256::one() << 64));
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(L2BlockNumber(0), &[storage_log])
+        .await
+        .unwrap();
+    // The transaction calls an allowlisted factory contract, which deploys a new contract.
+    let factory = tx.recipient_account();
+    storage
+        .tx_allowlist_dal()
+        .add_entry(factory, TxAllowlistPermission::Contract)
+        .await
+        .unwrap();
+
+    let deploy_event = VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: CONTRACT_DEPLOYER_ADDRESS,
+        indexed_topics: vec![
+            *DEPLOY_EVENT_SIGNATURE,
+            address_to_h256(&factory),
+            H256::repeat_byte(0xbc),
+            address_to_h256(&Address::repeat_byte(0xcc)),
+        ],
+        value: vec![],
+    };
+    let mut tx_executor = MockTransactionExecutor::default();
+    tx_executor.set_tx_responses_with_logs(move |_, _| VmExecutionResultAndLogs {
+        result: ExecutionResult::Success { output: vec![] },
+        logs: VmExecutionLogs {
+            events: vec![deploy_event.clone()],
+            ..VmExecutionLogs::default()
+        },
+        statistics: Default::default(),
+        refunds: Default::default(),
+    });
+    let (mut tx_sender, _) =
+        create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor.into()).await;
+    let tx_allowlist = TxAllowlistCache::new(&pool).await.unwrap();
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_allowlist = Some(tx_allowlist);
+
+    let err = tx_sender.submit_tx(tx.clone()).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::NotAllowlisted(TxAllowlistError::DeployerNotAllowed(address))
+            if address == factory
+    );
+
+    // Granting the deployer permission to the factory permits the transaction.
+    storage
+        .tx_allowlist_dal()
+        .add_entry(factory, TxAllowlistPermission::Deployer)
+        .await
+        .unwrap();
+    let tx_allowlist = TxAllowlistCache::new(&pool).await.unwrap();
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_allowlist = Some(tx_allowlist);
+
+    let submission_result = tx_sender.submit_tx(tx).await.unwrap();
+    assert_matches!(submission_result.0, L2TxSubmissionResult::Added);
+}
@@ -1,9 +1,9 @@
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, Core};
 use zksync_types::{
-    api::{Transaction, TransactionDetails, TransactionId},
+    api::{Transaction, TransactionDetails, TransactionId, TxpoolContent, TxpoolStatus},
     fee::TransactionExecutionMetrics,
     l2::L2Tx,
-    Address, Nonce, H256,
+    Address, L2BlockNumber, Nonce, H256,
 };
 use zksync_web3_decl::error::Web3Error;
 
@@ -30,6 +30,28 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
         execution_metrics: TransactionExecutionMetrics,
     ) -> Result<L2TxSubmissionResult, SubmitTxError>;
 
+    /// Ensures that a private transaction is propagated to the mempool. Private transactions are executed as usual,
+    /// but are not exposed via pending transaction filters and subscriptions.
+    /// By default, returns [`SubmitTxError::PrivateTxsNotSupported`].
+    async fn submit_private_tx(
+        &self,
+        _tx: &L2Tx,
+        _execution_metrics: TransactionExecutionMetrics,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        Err(SubmitTxError::PrivateTxsNotSupported)
+    }
+
+    /// Ensures that a bundle of transactions is propagated to the sequencer, which will include it atomically
+    /// into a single L2 block no later than `target_l2_block`. Each transaction is accompanied by its execution metrics
+    /// obtained by simulating the bundle. By default, returns [`SubmitTxError::PrivateTxsNotSupported`].
+    async fn submit_bundle(
+        &self,
+        _txs: &[(L2Tx, TransactionExecutionMetrics)],
+        _target_l2_block: L2BlockNumber,
+    ) -> Result<(), SubmitTxError> {
+        Err(SubmitTxError::PrivateTxsNotSupported)
+    }
+
     /// Attempts to look up the pending nonce for the account in the sink-specific storage.
     /// By default, returns `Ok(None)`.
     async fn lookup_pending_nonce(
@@ -59,4 +81,19 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
     ) -> Result<Option<TransactionDetails>, Web3Error> {
         Ok(None)
     }
+
+    /// Attempts to look up pending transactions (optionally, only ones initiated by the specified account)
+    /// in the sink-specific storage. By default, returns `Ok(None)`, in which case transactions are loaded from Postgres.
+    async fn lookup_txpool_content(
+        &self,
+        _initiator_address: Option<Address>,
+    ) -> Result<Option<TxpoolContent>, Web3Error> {
+        Ok(None)
+    }
+
+    /// Attempts to look up the number of pending transactions in the sink-specific storage. By default,
+    /// returns `Ok(None)`, in which case the status is computed using Postgres.
+    async fn lookup_txpool_status(&self) -> Result<Option<TxpoolStatus>, Web3Error> {
+        Ok(None)
+    }
 }
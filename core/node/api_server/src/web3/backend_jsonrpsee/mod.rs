@@ -38,7 +38,12 @@ impl MethodTracer {
             | Web3Error::PrunedL1Batch(_)
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
+            | Web3Error::TooManyProofRequests(_)
+            | Web3Error::TooManyProofKeys(_)
             | Web3Error::InvalidFilterBlockHash
+            | Web3Error::UnsupportedTracer(_)
+            | Web3Error::UnsupportedBlockTag(_)
+            | Web3Error::UnsupportedVmVersion
             | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
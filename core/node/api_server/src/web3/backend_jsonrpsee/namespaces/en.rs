@@ -1,5 +1,7 @@
 use zksync_config::{configs::EcosystemContracts, GenesisConfig};
-use zksync_types::{api::en, tokens::TokenInfo, Address, L2BlockNumber};
+use zksync_types::{
+    api::en, commitment::L1BatchMetadata, tokens::TokenInfo, Address, L1BatchNumber, L2BlockNumber,
+};
 use zksync_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
     namespaces::EnNamespaceServer,
@@ -48,4 +50,31 @@ impl EnNamespaceServer for EnNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn l1_batch_anchor(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<en::L1BatchAnchor>> {
+        self.l1_batch_anchor_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn l1_batch_metadata(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchMetadata>> {
+        self.l1_batch_metadata_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn l2_block_archive_chunk(
+        &self,
+        chunk_index: Option<u32>,
+    ) -> RpcResult<Option<en::L2BlockArchiveChunkInfo>> {
+        self.l2_block_archive_chunk_impl(chunk_index)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotHeader},
+    snapshots::{AllSnapshots, SnapshotHeader, SnapshotManifest},
     L1BatchNumber,
 };
 use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::SnapshotsNamespaceServer};
@@ -23,4 +23,13 @@ impl SnapshotsNamespaceServer for SnapshotsNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_snapshot_manifest(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<SnapshotManifest>> {
+        self.get_snapshot_manifest_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
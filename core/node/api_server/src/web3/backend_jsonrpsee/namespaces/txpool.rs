@@ -0,0 +1,37 @@
+use zksync_types::{
+    api::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus},
+    Address,
+};
+use zksync_web3_decl::{
+    jsonrpsee::core::{async_trait, RpcResult},
+    namespaces::TxpoolNamespaceServer,
+};
+
+use crate::web3::namespaces::TxpoolNamespace;
+
+#[async_trait]
+impl TxpoolNamespaceServer for TxpoolNamespace {
+    async fn status(&self) -> RpcResult<TxpoolStatus> {
+        self.status_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn content(&self) -> RpcResult<TxpoolContent> {
+        self.content_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn content_from(&self, address: Address) -> RpcResult<TxpoolContentFrom> {
+        self.content_from_impl(address)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn inspect(&self) -> RpcResult<TxpoolInspect> {
+        self.inspect_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
@@ -3,8 +3,10 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use zksync_types::{
     api::{
-        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log, Proof,
-        ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        ApiStorageLog, BlockDetails, BlockId, BridgeAddresses, BundleSimulationOptions,
+        BundleSubmissionOptions, L1BatchDetails, L2ToL1LogProof, Log, Proof, ProofRequest,
+        ProtocolVersion, SimulatedCallResult, SubmittedBundle, TeeProof, TransactionDetailedResult,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -136,6 +138,15 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_l1_batch_tee_proofs(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> RpcResult<Vec<TeeProof>> {
+        self.get_l1_batch_tee_proofs_impl(batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
@@ -180,6 +191,12 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_proofs(&self, requests: Vec<ProofRequest>) -> RpcResult<Vec<Option<Proof>>> {
+        self.get_proofs_for_batches_impl(requests)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_base_token_l1_address(&self) -> RpcResult<Address> {
         self.get_base_token_l1_address_impl()
             .map_err(|err| self.current_method().map_err(err))
@@ -215,4 +232,31 @@ impl ZksNamespaceServer for ZksNamespace {
             })
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn simulate_bundle(
+        &self,
+        requests: Vec<CallRequest>,
+        block: Option<BlockId>,
+        options: Option<BundleSimulationOptions>,
+    ) -> RpcResult<Vec<SimulatedCallResult>> {
+        self.simulate_bundle_impl(requests, block, options)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn send_private_transaction(&self, tx_bytes: Bytes) -> RpcResult<H256> {
+        self.send_private_transaction_impl(tx_bytes)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn send_bundle(
+        &self,
+        txs: Vec<Bytes>,
+        options: Option<BundleSubmissionOptions>,
+    ) -> RpcResult<SubmittedBundle> {
+        self.send_bundle_impl(txs, options)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
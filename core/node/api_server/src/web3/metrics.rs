@@ -105,6 +105,7 @@ enum BlockIdLabel {
     L1Committed,
     Earliest,
     Pending,
+    TeeFinalized,
     Number,
 }
 
@@ -143,6 +144,7 @@ impl From<&MethodMetadata> for MethodLabels {
             api::BlockId::Number(api::BlockNumber::L1Committed) => BlockIdLabel::L1Committed,
             api::BlockId::Number(api::BlockNumber::Earliest) => BlockIdLabel::Earliest,
             api::BlockId::Number(api::BlockNumber::Pending) => BlockIdLabel::Pending,
+            api::BlockId::Number(api::BlockNumber::TeeFinalized) => BlockIdLabel::TeeFinalized,
         });
         let block_diff = meta.block_diff.map(|block_diff| match block_diff {
             0..=2 => BlockDiffLabel::Exact(block_diff),
@@ -168,8 +170,12 @@ enum Web3ErrorKind {
     Proxy,
     TooManyTopics,
     FilterNotFound,
+    TooManyProofRequests,
     LogsLimitExceeded,
     InvalidFilterBlockHash,
+    UnsupportedTracer,
+    UnsupportedBlockTag,
+    UnsupportedVmVersion,
     TreeApiUnavailable,
     Internal,
 }
@@ -184,8 +190,14 @@ impl Web3ErrorKind {
             Web3Error::SerializationError(_) => Self::TransactionSerialization,
             Web3Error::TooManyTopics => Self::TooManyTopics,
             Web3Error::FilterNotFound => Self::FilterNotFound,
+            Web3Error::TooManyProofRequests(_) | Web3Error::TooManyProofKeys(_) => {
+                Self::TooManyProofRequests
+            }
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::UnsupportedTracer(_) => Self::UnsupportedTracer,
+            Web3Error::UnsupportedBlockTag(_) => Self::UnsupportedBlockTag,
+            Web3Error::UnsupportedVmVersion => Self::UnsupportedVmVersion,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
@@ -24,8 +24,8 @@ use zksync_web3_decl::{
     },
     namespaces::{
         DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, UnstableNamespaceServer, Web3NamespaceServer,
-        ZksNamespaceServer,
+        NetNamespaceServer, SnapshotsNamespaceServer, TxpoolNamespaceServer,
+        UnstableNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
@@ -39,7 +39,7 @@ use self::{
     metrics::API_METRICS,
     namespaces::{
         DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
-        UnstableNamespace, Web3Namespace, ZksNamespace,
+        TxpoolNamespace, UnstableNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
     state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
@@ -100,6 +100,7 @@ pub enum Namespace {
     Pubsub,
     Snapshots,
     Unstable,
+    Txpool,
 }
 
 impl Namespace {
@@ -412,6 +413,10 @@ impl ApiServer {
             rpc.merge(SnapshotsNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge snapshots namespace")?;
         }
+        if namespaces.contains(&Namespace::Txpool) {
+            rpc.merge(TxpoolNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge txpool namespace")?;
+        }
         if namespaces.contains(&Namespace::Unstable) {
             rpc.merge(UnstableNamespace::new(rpc_state).into_rpc())
                 .context("cannot merge unstable namespace")?;
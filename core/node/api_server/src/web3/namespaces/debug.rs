@@ -1,25 +1,31 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Context as _;
 use once_cell::sync::OnceCell;
 use zksync_dal::{CoreDal, DalError};
 use zksync_multivm::{
-    interface::ExecutionResult, vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+    interface::ExecutionResult, tracers::prestate_tracer,
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugTraceResult, PrestateAccount, PrestateTrace,
+        ResultDebugCall, SupportedTracers, TracerConfig,
+    },
     debug_flat_call::{flatten_debug_calls, DebugCallFlat},
     fee_model::BatchFeeInput,
     l2::L2Tx,
     transaction_request::CallRequest,
+    utils::decompose_full_nonce,
     vm_trace::Call,
-    AccountTreeId, H256,
+    AccountTreeId, Address, H256,
 };
+use zksync_utils::{bytecode::hash_bytecode, u256_to_h256};
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
-    execution_sandbox::{ApiTracer, TxSharedArgs},
+    execution_sandbox::{ApiTracer, TxExecutionArgs, TxSharedArgs},
     tx_sender::{ApiContracts, TxSenderConfig},
     web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
 };
@@ -69,6 +75,14 @@ impl DebugNamespace {
             // See `EthNamespace::get_block_impl()` for an explanation why this check is needed.
             return Ok(vec![]);
         }
+        // Block traces are served from the stored call traces; re-executing whole blocks is not supported.
+        if let Some(TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            ..
+        }) = &options
+        {
+            return Err(Web3Error::UnsupportedTracer("prestateTracer"));
+        }
 
         let only_top_call = options
             .map(|options| options.tracer_config.only_top_call)
@@ -110,7 +124,18 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
+    ) -> Result<Option<DebugTraceResult>, Web3Error> {
+        if let Some(TracerConfig {
+            tracer: SupportedTracers::PrestateTracer,
+            tracer_config,
+            ..
+        }) = &options
+        {
+            return self
+                .trace_transaction_prestate(tx_hash, tracer_config.diff_mode)
+                .await;
+        }
+
         let only_top_call = options
             .map(|options| options.tracer_config.only_top_call)
             .unwrap_or(false);
@@ -125,22 +150,122 @@ impl DebugNamespace {
             if only_top_call {
                 result.calls = vec![];
             }
-            result
+            result.into()
         }))
     }
 
+    /// Re-executes an already processed transaction with `prestateTracer`. Unlike call traces, prestate traces
+    /// are not persisted, so the transaction is executed on top of the state at the end of the preceding L2 block,
+    /// after replaying the preceding transactions in the same L2 block. The VM environment (L1 batch, timestamp,
+    /// fee input and protocol version) is taken from the stored L2 block containing the transaction.
+    async fn trace_transaction_prestate(
+        &self,
+        tx_hash: H256,
+        diff_mode: bool,
+    ) -> Result<Option<DebugTraceResult>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let api_tx = connection
+            .transactions_web3_dal()
+            .get_transaction_by_hash(tx_hash, self.sender_config().chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(block_number) = api_tx.and_then(|tx| tx.block_number) else {
+            return Ok(None);
+        };
+        let block_number = RpcState::u64_to_block_number(block_number);
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+        // Transactions in the genesis block cannot be re-executed since there is no state preceding them.
+        let Some(prev_block_number) = block_number.0.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        let mut txs = connection
+            .transactions_web3_dal()
+            .get_raw_l2_block_transactions(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(tx_index) = txs.iter().position(|tx| tx.hash() == tx_hash) else {
+            // Transaction data is pruned.
+            return Ok(None);
+        };
+        txs.truncate(tx_index + 1);
+        let tx = txs.pop().unwrap(); // `unwrap()` is safe: `txs` contain at least the traced transaction
+        let Some(block_header) = connection
+            .blocks_dal()
+            .get_l2_block_header(block_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let block_args = self
+            .state
+            .resolve_block_args(
+                &mut connection,
+                BlockId::Number(BlockNumber::Number(prev_block_number.into())),
+            )
+            .await?
+            .in_next_l2_block();
+        drop(connection);
+
+        let shared_args = self.shared_args().await;
+        let vm_permit = self
+            .state
+            .tx_sender
+            .vm_concurrency_limiter()
+            .acquire()
+            .await;
+        let vm_permit = vm_permit.context("cannot acquire VM permit")?;
+
+        let prestate_result = Arc::new(OnceCell::default());
+        let custom_tracers = vec![ApiTracer::PrestateTracer {
+            diff_mode,
+            result: prestate_result.clone(),
+        }];
+        let execution_args = TxExecutionArgs::for_replay(
+            block_header.base_fee_per_gas,
+            self.sender_config().vm_execution_cache_misses_limit,
+        );
+        let factory_deps = tx.execute.factory_deps.clone();
+        let executor = &self.state.tx_sender.0.executor;
+        executor
+            .replay_tx_in_sandbox(
+                vm_permit,
+                shared_args,
+                execution_args,
+                self.state.connection_pool.clone(),
+                txs,
+                tx,
+                block_args,
+                custom_tracers,
+            )
+            .await?;
+
+        let trace = self
+            .prestate_trace(diff_mode, prestate_result, &factory_deps)
+            .await?;
+        Ok(Some(DebugTraceResult::Prestate(trace)))
+    }
+
     pub async fn debug_trace_call_impl(
         &self,
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTraceResult, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let (tracer, tracer_config, state_override) = options
+            .map(|options| {
+                (
+                    options.tracer,
+                    options.tracer_config,
+                    options.state_overrides,
+                )
+            })
+            .unwrap_or((SupportedTracers::CallTracer, Default::default(), None));
 
         let mut connection = self.state.acquire_connection().await?;
         let block_args = self
@@ -180,11 +305,16 @@ impl DebugNamespace {
 
         // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+        let prestate_result = Arc::new(OnceCell::default());
+        let custom_tracers = match tracer {
+            SupportedTracers::CallTracer if tracer_config.only_top_call => vec![],
+            SupportedTracers::CallTracer => vec![ApiTracer::CallTracer(call_tracer_result.clone())],
+            SupportedTracers::PrestateTracer => vec![ApiTracer::PrestateTracer {
+                diff_mode: tracer_config.diff_mode,
+                result: prestate_result.clone(),
+            }],
         };
+        let factory_deps = tx.execute.factory_deps.clone();
 
         let executor = &self.state.tx_sender.0.executor;
         let result = executor
@@ -195,6 +325,7 @@ impl DebugNamespace {
                 call_overrides,
                 tx.clone(),
                 block_args,
+                state_override,
                 self.sender_config().vm_execution_cache_misses_limit,
                 custom_tracers,
             )
@@ -210,6 +341,12 @@ impl DebugNamespace {
                 ))
             }
         };
+        if let SupportedTracers::PrestateTracer = tracer {
+            let trace = self
+                .prestate_trace(tracer_config.diff_mode, prestate_result, &factory_deps)
+                .await?;
+            return Ok(DebugTraceResult::Prestate(trace));
+        }
 
         // We had only one copy of Arc this arc is already dropped it's safe to unwrap
         let trace = Arc::try_unwrap(call_tracer_result)
@@ -225,7 +362,79 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(call.into())
+        Ok(DebugCall::from(call).into())
+    }
+
+    /// Converts the output of [`PrestateTracer`](zksync_multivm::tracers::PrestateTracer) to the Geth format.
+    /// In particular, bytecode hashes stored by the VM are resolved to bytecodes, using `factory_deps`
+    /// of the traced transaction for contracts deployed by it.
+    async fn prestate_trace(
+        &self,
+        diff_mode: bool,
+        tracer_result: Arc<OnceCell<(prestate_tracer::State, prestate_tracer::State)>>,
+        factory_deps: &[Vec<u8>],
+    ) -> Result<PrestateTrace, Web3Error> {
+        // The tracer is dropped after execution, so we hold the only reference
+        let (pre, post) = Arc::try_unwrap(tracer_result)
+            .unwrap()
+            .take()
+            .unwrap_or_default();
+
+        let mut bytecodes: HashMap<H256, Vec<u8>> = factory_deps
+            .iter()
+            .map(|bytecode| (hash_bytecode(bytecode), bytecode.clone()))
+            .collect();
+        let mut connection = self.state.acquire_connection().await?;
+        for account in pre.values().chain(post.values()) {
+            let Some(code_hash) = account.code.filter(|hash| !hash.is_zero()) else {
+                continue;
+            };
+            let code_hash = u256_to_h256(code_hash);
+            if bytecodes.contains_key(&code_hash) {
+                continue;
+            }
+            let bytecode = connection
+                .storage_web3_dal()
+                .get_factory_dep(code_hash)
+                .await
+                .map_err(DalError::generalize)?;
+            if let Some((bytecode, _)) = bytecode {
+                bytecodes.insert(code_hash, bytecode);
+            }
+        }
+        drop(connection);
+
+        let convert_accounts =
+            |state: prestate_tracer::State| -> HashMap<Address, PrestateAccount> {
+                state
+                    .into_iter()
+                    .map(|(address, account)| {
+                        let code = account
+                            .code
+                            .and_then(|hash| bytecodes.get(&u256_to_h256(hash)))
+                            .map(|bytecode| bytecode.clone().into());
+                        let account = PrestateAccount {
+                            balance: account.balance,
+                            code,
+                            nonce: account
+                                .nonce
+                                .map(|full_nonce| decompose_full_nonce(full_nonce).0.as_u64()),
+                            storage: account.storage.unwrap_or_default().into_iter().collect(),
+                        };
+                        (address, account)
+                    })
+                    .collect()
+            };
+
+        Ok(if diff_mode {
+            PrestateTrace::Diff {
+                pre: convert_accounts(pre),
+                post: convert_accounts(post),
+            }
+        } else {
+            // Outside the diff mode, the tracer reports the state of all touched accounts before the execution as `pre`.
+            PrestateTrace::Prestate(convert_accounts(pre))
+        })
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
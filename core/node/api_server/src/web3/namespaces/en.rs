@@ -2,8 +2,8 @@ use anyhow::Context as _;
 use zksync_config::{configs::EcosystemContracts, GenesisConfig};
 use zksync_dal::{CoreDal, DalError};
 use zksync_types::{
-    api::en, protocol_version::ProtocolSemanticVersion, tokens::TokenInfo, Address, L1BatchNumber,
-    L2BlockNumber,
+    api::en, commitment::L1BatchMetadata, protocol_version::ProtocolSemanticVersion,
+    tokens::TokenInfo, Address, L1BatchNumber, L2BlockNumber,
 };
 use zksync_web3_decl::error::Web3Error;
 
@@ -53,6 +53,55 @@ impl EnNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn l1_batch_anchor_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<en::L1BatchAnchor>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .data_availability_dal()
+            .get_l1_batch_anchor(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn l1_batch_metadata_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<L1BatchMetadata>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let batch = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(batch.map(|batch| batch.metadata))
+    }
+
+    pub async fn l2_block_archive_chunk_impl(
+        &self,
+        chunk_index: Option<u32>,
+    ) -> Result<Option<en::L2BlockArchiveChunkInfo>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let mut dal = storage.block_archive_dal();
+        let chunk = match chunk_index {
+            Some(index) => dal.get_chunk(index).await,
+            None => dal.get_last_chunk().await,
+        };
+        let Some(chunk) = chunk.map_err(DalError::generalize)? else {
+            return Ok(None);
+        };
+
+        // Chunks may be indexed by external nodes; don't serve chunks with rolled back L2 blocks.
+        let header = storage
+            .blocks_dal()
+            .get_l2_block_header(chunk.last_l2_block)
+            .await
+            .map_err(DalError::generalize)?;
+        let is_consistent = header.is_some_and(|header| header.hash == chunk.last_l2_block_hash);
+        Ok(is_consistent.then_some(chunk))
+    }
+
     pub async fn sync_tokens_impl(
         &self,
         block_number: Option<L2BlockNumber>,
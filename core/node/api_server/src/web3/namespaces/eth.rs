@@ -1,15 +1,18 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
+use zksync_multivm::interface::ExecutionResult;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        state_override::StateOverride, AccessListResult, BlockId, BlockNumber, GetLogsFilter,
+        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
     utils::decompose_full_nonce,
-    web3::{self, Bytes, FeeHistory, SyncInfo, SyncState},
+    web3::{self, AccessListItem, Bytes, FeeHistory, SyncInfo, SyncState},
     AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
 use zksync_utils::u256_to_h256;
@@ -55,6 +58,7 @@ impl EthNamespace {
         &self,
         mut request: CallRequest,
         block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
@@ -88,7 +92,7 @@ impl EthNamespace {
         let call_result: Vec<u8> = self
             .state
             .tx_sender
-            .eth_call(block_args, call_overrides, tx)
+            .eth_call(block_args, call_overrides, tx, state_override)
             .await?;
         Ok(call_result.into())
     }
@@ -97,6 +101,7 @@ impl EthNamespace {
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
@@ -138,7 +143,12 @@ impl EthNamespace {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_in_wei(
+                tx.into(),
+                scale_factor,
+                acceptable_overestimation as u64,
+                state_override,
+            )
             .await?;
         Ok(fee.gas_limit)
     }
@@ -148,6 +158,82 @@ impl EthNamespace {
         Ok(gas_price.into())
     }
 
+    pub async fn max_priority_fee_per_gas_impl(&self) -> Result<U256, Web3Error> {
+        let priority_fee = self.state.tx_sender.max_priority_fee_per_gas().await?;
+        Ok(priority_fee.into())
+    }
+
+    pub async fn create_access_list_impl(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<AccessListResult, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        if request.gas.is_none() {
+            request.gas = Some(
+                self.state
+                    .tx_sender
+                    .get_default_eth_call_gas(block_args)
+                    .await
+                    .map_err(Web3Error::InternalError)?
+                    .into(),
+            )
+        }
+        let call_overrides = request.get_call_overrides()?;
+        let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+
+        let (result, accessed_keys) = self
+            .state
+            .tx_sender
+            .create_access_list(block_args, call_overrides, tx)
+            .await?;
+        let accessed_keys = accessed_keys.ok_or(Web3Error::UnsupportedVmVersion)?;
+        let error = match result.result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(output.to_user_friendly_string()),
+            ExecutionResult::Halt { reason } => Some(reason.to_string()),
+        };
+
+        // Slots of system contracts (e.g., nonces and base token balances) are accessed by the bootloader
+        // for any transaction, so they are not included into the access list.
+        let mut slots_by_address = BTreeMap::<_, BTreeSet<_>>::new();
+        for key in accessed_keys {
+            let address = *key.address();
+            if !is_system_contract(address) {
+                slots_by_address
+                    .entry(address)
+                    .or_default()
+                    .insert(*key.key());
+            }
+        }
+        let access_list = slots_by_address
+            .into_iter()
+            .map(|(address, slots)| AccessListItem {
+                address,
+                storage_keys: slots.into_iter().collect(),
+            })
+            .collect();
+        Ok(AccessListResult {
+            access_list,
+            gas_used: result.statistics.gas_used.into(),
+            error,
+        })
+    }
+
     pub async fn get_balance_impl(
         &self,
         address: Address,
@@ -869,3 +955,8 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+/// Checks whether the address belongs to the kernel space (addresses below 2^16) reserved for system contracts.
+fn is_system_contract(address: Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
@@ -1,7 +1,7 @@
 use anyhow::Context as _;
 use zksync_dal::{CoreDal, DalError};
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotHeader, SnapshotStorageLogsChunkMetadata},
+    snapshots::{AllSnapshots, SnapshotHeader, SnapshotManifest, SnapshotStorageLogsChunkMetadata},
     L1BatchNumber,
 };
 use zksync_web3_decl::error::Web3Error;
@@ -74,8 +74,21 @@ impl SnapshotsNamespace {
             version: snapshot_metadata.version.into(),
             l1_batch_number: snapshot_metadata.l1_batch_number,
             l2_block_number,
+            base_l1_batch_number: snapshot_metadata.base_l1_batch_number,
             storage_logs_chunks: chunks,
             factory_deps_filepath: snapshot_metadata.factory_deps_filepath,
         }))
     }
+
+    pub async fn get_snapshot_manifest_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<SnapshotManifest>, Web3Error> {
+        let mut storage_processor = self.state.acquire_connection().await?;
+        Ok(storage_processor
+            .snapshots_dal()
+            .get_snapshot_manifest(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
 }
@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use zksync_dal::{CoreDal, DalError};
+use zksync_types::{
+    api::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus},
+    Address,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, RpcState};
+
+#[derive(Debug)]
+pub(crate) struct TxpoolNamespace {
+    state: RpcState,
+}
+
+impl TxpoolNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn status_impl(&self) -> Result<TxpoolStatus, Web3Error> {
+        // External nodes don't have a mempool, so the request is proxied to the main node.
+        if let Some(status) = self.state.tx_sink().lookup_txpool_status().await? {
+            return Ok(status);
+        }
+
+        // Unlike content, the status is not limited by `req_entities_limit`, so we don't load transactions.
+        let mut storage = self.state.acquire_connection().await?;
+        let nonce_ranges = storage
+            .transactions_web3_dal()
+            .get_mempool_nonce_ranges()
+            .await
+            .map_err(DalError::generalize)?;
+        let initiators: Vec<_> = nonce_ranges.keys().copied().collect();
+        let committed_nonces = storage
+            .storage_web3_dal()
+            .get_nonces_for_addresses(&initiators)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(TxpoolStatus::from_nonce_ranges(
+            &nonce_ranges,
+            &committed_nonces,
+        ))
+    }
+
+    pub async fn content_impl(&self) -> Result<TxpoolContent, Web3Error> {
+        self.pending_transactions(None).await
+    }
+
+    pub async fn content_from_impl(
+        &self,
+        address: Address,
+    ) -> Result<TxpoolContentFrom, Web3Error> {
+        let content = self.pending_transactions(Some(address)).await?;
+        Ok(content.into_account(address))
+    }
+
+    pub async fn inspect_impl(&self) -> Result<TxpoolInspect, Web3Error> {
+        Ok(self.content_impl().await?.inspect())
+    }
+
+    async fn pending_transactions(
+        &self,
+        initiator_address: Option<Address>,
+    ) -> Result<TxpoolContent, Web3Error> {
+        // External nodes don't have a mempool, so the request is proxied to the main node.
+        if let Some(content) = self
+            .state
+            .tx_sink()
+            .lookup_txpool_content(initiator_address)
+            .await?
+        {
+            return Ok(content);
+        }
+
+        let mut storage = self.state.acquire_connection().await?;
+        let limit = self.state.api_config.req_entities_limit;
+        // Request an extra transaction to detect whether the content is truncated.
+        let mut tx_hashes = storage
+            .transactions_web3_dal()
+            .get_mempool_tx_hashes(initiator_address, limit + 1)
+            .await
+            .map_err(DalError::generalize)?;
+        let truncated = tx_hashes.len() > limit;
+        tx_hashes.truncate(limit);
+        let transactions = storage
+            .transactions_web3_dal()
+            .get_transactions(&tx_hashes, self.state.api_config.l2_chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let initiators: HashSet<_> = transactions.iter().filter_map(|tx| tx.from).collect();
+        let initiators: Vec<_> = initiators.into_iter().collect();
+        let committed_nonces = storage
+            .storage_web3_dal()
+            .get_nonces_for_addresses(&initiators)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(TxpoolContent {
+            truncated,
+            ..TxpoolContent::new(transactions, &committed_nonces)
+        })
+    }
+}
@@ -2,14 +2,16 @@ use std::{collections::HashMap, convert::TryInto};
 
 use anyhow::Context as _;
 use zksync_dal::{Connection, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiError;
+use zksync_metadata_calculator::api_server::{TreeApiError, TreeEntryWithProof};
 use zksync_mini_merkle_tree::MiniMerkleTree;
-use zksync_multivm::interface::VmExecutionResultAndLogs;
+use zksync_multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BlockId, BlockNumber, BridgeAddresses, BundleSimulationOptions,
+        BundleSubmissionOptions, DebugCall, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Log,
+        Proof, ProofRequest, ProtocolVersion, SimulatedCallResult, StorageProof, SubmittedBundle,
+        TeeProof, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -17,8 +19,9 @@ use zksync_types::{
     l2::L2Tx,
     l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log},
     tokens::ETHEREUM_ADDRESS,
-    transaction_request::CallRequest,
+    transaction_request::{CallOverrides, CallRequest},
     utils::storage_key_for_standard_token_balance,
+    vm_trace::Call,
     web3::Bytes,
     AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, Transaction,
     L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
@@ -30,10 +33,16 @@ use zksync_web3_decl::{
 };
 
 use crate::{
+    execution_sandbox::BundleTxOutput,
+    tx_sender::SubmitTxError,
     utils::open_readonly_transaction,
     web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, RpcState},
 };
 
+/// Number of L2 blocks after the latest sealed one during which a bundle may be included if its target block
+/// is not specified explicitly.
+const DEFAULT_BUNDLE_TARGET_BLOCKS: u32 = 10;
+
 #[derive(Debug)]
 pub(crate) struct ZksNamespace {
     state: RpcState,
@@ -99,10 +108,146 @@ impl ZksNamespace {
         Ok(self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64, None)
             .await?)
     }
 
+    pub async fn simulate_bundle_impl(
+        &self,
+        requests: Vec<CallRequest>,
+        block_id: Option<BlockId>,
+        options: Option<BundleSimulationOptions>,
+    ) -> Result<Vec<SimulatedCallResult>, Web3Error> {
+        let max_bundle_size = self.state.api_config.max_simulated_bundle_size;
+        if requests.len() > max_bundle_size {
+            return Err(SubmitTxError::TooManyBundleCalls(requests.len(), max_bundle_size).into());
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        if requests.is_empty() {
+            return Ok(vec![]);
+        }
+        let default_gas = self
+            .state
+            .tx_sender
+            .get_default_eth_call_gas(block_args)
+            .await
+            .map_err(Web3Error::InternalError)?;
+
+        // The batch base fee is shared by all calls, so we use the lowest gas price provided in the bundle.
+        let mut enforced_base_fee = None;
+        let mut txs = Vec::with_capacity(requests.len());
+        for mut request in requests {
+            request.gas.get_or_insert(default_gas.into());
+            let call_overrides = request.get_call_overrides()?;
+            enforced_base_fee = enforced_base_fee
+                .into_iter()
+                .chain(call_overrides.enforced_base_fee)
+                .min();
+            txs.push(L2Tx::from_request(
+                request.into(),
+                self.state.api_config.max_tx_size,
+            )?);
+        }
+
+        let options = options.unwrap_or_default();
+        let output = self
+            .state
+            .tx_sender
+            .simulate_bundle(
+                block_args,
+                CallOverrides { enforced_base_fee },
+                txs.clone(),
+                options.state_overrides,
+                options.trace_calls,
+                self.state.api_config.simulate_bundle_timeout,
+            )
+            .await?;
+        let completed_calls = output.txs.len();
+        let mut results: Vec<_> = txs
+            .iter()
+            .zip(output.txs)
+            .map(|(tx, output)| Self::simulated_call_result(tx, output))
+            .collect();
+        if output.timed_out {
+            // The call that was interrupted by the timeout is reported as failed; subsequent calls are omitted.
+            results.push(SimulatedCallResult {
+                status: 0.into(),
+                return_data: vec![].into(),
+                gas_used: 0.into(),
+                logs: vec![],
+                revert_reason: Some(SubmitTxError::BundleTimeout(completed_calls).to_string()),
+                trace: None,
+            });
+        }
+        Ok(results)
+    }
+
+    fn simulated_call_result(tx: &L2Tx, output: BundleTxOutput) -> SimulatedCallResult {
+        let gas_used = output.vm.statistics.gas_used;
+        let (status, return_data, revert_reason) = match output.vm.result {
+            ExecutionResult::Success { output } => (1, output, None),
+            ExecutionResult::Revert { output } => (
+                0,
+                output.encoded_data(),
+                Some(output.to_user_friendly_string()),
+            ),
+            ExecutionResult::Halt { reason } => (0, vec![], Some(reason.to_string())),
+        };
+        let logs = output
+            .vm
+            .logs
+            .events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| {
+                let mut log = Log::from(event);
+                log.transaction_log_index = Some(i.into());
+                log
+            })
+            .collect();
+        let trace = output.call_traces.map(|calls| {
+            let call_output = if status == 1 {
+                return_data.clone()
+            } else {
+                vec![]
+            };
+            let call = Call::new_high_level(
+                tx.common_data.fee.gas_limit.as_u64(),
+                gas_used,
+                tx.execute.value,
+                tx.execute.calldata.clone(),
+                call_output,
+                revert_reason.clone(),
+                calls,
+            );
+            DebugCall::from(call)
+        });
+
+        SimulatedCallResult {
+            status: status.into(),
+            return_data: return_data.into(),
+            gas_used: gas_used.into(),
+            logs,
+            revert_reason,
+            trace,
+        }
+    }
+
     pub fn get_bridgehub_contract_impl(&self) -> Option<Address> {
         self.state.api_config.bridgehub_proxy_addr
     }
@@ -437,6 +582,23 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    pub async fn get_l1_batch_tee_proofs_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Vec<TeeProof>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch_number, &mut storage)
+            .await?;
+
+        Ok(storage
+            .tee_proof_generation_dal()
+            .get_tee_proofs(batch_number)
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
     pub async fn get_bytecode_by_hash_impl(
         &self,
         hash: H256,
@@ -492,10 +654,7 @@ impl ZksNamespace {
             .start_info
             .ensure_not_pruned(l1_batch_number, &mut storage)
             .await?;
-        let hashed_keys = keys
-            .iter()
-            .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
-            .collect();
+        let hashed_keys = Self::hashed_proof_keys(address, &keys);
         let tree_api = self
             .state
             .tree_api
@@ -504,26 +663,92 @@ impl ZksNamespace {
         let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
         let proofs = match proofs_result {
             Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
-            Err(_) => {
-                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
-                    "Unspecified tree API error"
-                )));
+            Err(TreeApiError::NoVersion(err)) if err.missing_version >= err.version_count => {
+                return Ok(None);
             }
+            Err(err) => return Err(Self::map_tree_api_error(err)),
         };
+        Ok(Some(Self::proof_from_tree_entries(address, keys, proofs)))
+    }
+
+    pub async fn get_proofs_for_batches_impl(
+        &self,
+        requests: Vec<ProofRequest>,
+    ) -> Result<Vec<Option<Proof>>, Web3Error> {
+        let max_requests = self.state.api_config.req_entities_limit;
+        if requests.len() > max_requests {
+            return Err(Web3Error::TooManyProofRequests(max_requests));
+        }
+        // The total number of keys is capped by the same limit, so that a handful of requests cannot
+        // make the tree API produce an unbounded number of proofs.
+        let total_keys: usize = requests.iter().map(|request| request.keys.len()).sum();
+        if total_keys > max_requests {
+            return Err(Web3Error::TooManyProofKeys(max_requests));
+        }
+        let tree_api = self
+            .state
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
 
-        let storage_proof = proofs
+        let mut storage = self.state.acquire_connection().await?;
+        for request in &requests {
+            self.state
+                .start_info
+                .ensure_not_pruned(request.l1_batch_number, &mut storage)
+                .await?;
+        }
+        drop(storage);
+        if requests.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Similarly to `zks_getProof`, L1 batches not processed by the tree yet are returned as `None`.
+        let next_l1_batch_number = tree_api
+            .get_info()
+            .await
+            .map_err(Self::map_tree_api_error)?
+            .next_l1_batch_number;
+        let mut proofs = vec![None; requests.len()];
+        let mut tree_requests = vec![];
+        let mut pending_requests = vec![];
+        for (i, request) in requests.into_iter().enumerate() {
+            if request.l1_batch_number < next_l1_batch_number {
+                let hashed_keys = Self::hashed_proof_keys(request.address, &request.keys);
+                tree_requests.push((request.l1_batch_number, hashed_keys));
+                pending_requests.push((i, request));
+            }
+        }
+        if tree_requests.is_empty() {
+            return Ok(proofs);
+        }
+
+        let batches = tree_api
+            .get_proofs_for_batches(tree_requests)
+            .await
+            .map_err(Self::map_tree_api_error)?;
+        for ((i, request), entries) in pending_requests.into_iter().zip(batches) {
+            proofs[i] = Some(Self::proof_from_tree_entries(
+                request.address,
+                request.keys,
+                entries,
+            ));
+        }
+        Ok(proofs)
+    }
+
+    fn hashed_proof_keys(address: Address, keys: &[H256]) -> Vec<U256> {
+        keys.iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
+            .collect()
+    }
+
+    fn proof_from_tree_entries(
+        address: Address,
+        keys: Vec<H256>,
+        entries: Vec<TreeEntryWithProof>,
+    ) -> Proof {
+        let storage_proof = entries
             .into_iter()
             .zip(keys)
             .map(|(proof, key)| StorageProof {
@@ -533,11 +758,32 @@ impl ZksNamespace {
                 index: proof.index,
             })
             .collect();
-
-        Ok(Some(Proof {
+        Proof {
             address,
             storage_proof,
-        }))
+        }
+    }
+
+    fn map_tree_api_error(err: TreeApiError) -> Web3Error {
+        match err {
+            TreeApiError::NotReady(_) => Web3Error::TreeApiUnavailable,
+            TreeApiError::PrunedL1Batch(err) => {
+                Web3Error::PrunedL1Batch(err.earliest_provable_batch)
+            }
+            // The tree is lagging behind or was reverted after the caller checked its state.
+            TreeApiError::NoVersion(err) if err.missing_version >= err.version_count => {
+                Web3Error::TreeApiUnavailable
+            }
+            TreeApiError::NoVersion(err) => Web3Error::InternalError(anyhow::anyhow!(
+                "L1 batch #{} is pruned in Merkle tree, but not in Postgres",
+                err.missing_version
+            )),
+            TreeApiError::Internal(err) => Web3Error::InternalError(err),
+            _ => {
+                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+                Web3Error::InternalError(anyhow::anyhow!("Unspecified tree API error"))
+            }
+        }
     }
 
     pub fn get_base_token_l1_address_impl(&self) -> Result<Address, Web3Error> {
@@ -576,4 +822,71 @@ impl ZksNamespace {
             err.into()
         })
     }
+
+    pub async fn send_private_transaction_impl(&self, tx_bytes: Bytes) -> Result<H256, Web3Error> {
+        let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
+        tx.set_input(tx_bytes.0, hash);
+
+        let submit_result = self.state.tx_sender.submit_private_tx(tx).await;
+        submit_result.map(|_| hash).map_err(|err| {
+            tracing::debug!("Send private transaction error: {err}");
+            API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
+            err.into()
+        })
+    }
+
+    pub async fn send_bundle_impl(
+        &self,
+        txs_bytes: Vec<Bytes>,
+        options: Option<BundleSubmissionOptions>,
+    ) -> Result<SubmittedBundle, Web3Error> {
+        let max_bundle_size = self.state.api_config.max_submitted_bundle_size;
+        if txs_bytes.len() > max_bundle_size {
+            return Err(SubmitTxError::TooManyBundleCalls(txs_bytes.len(), max_bundle_size).into());
+        }
+
+        let mut txs = Vec::with_capacity(txs_bytes.len());
+        for tx_bytes in txs_bytes {
+            let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
+            tx.set_input(tx_bytes.0, hash);
+            txs.push(tx);
+        }
+
+        let mut connection = self.state.acquire_connection().await?;
+        let last_sealed_l2_block = connection
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await
+            .map_err(DalError::generalize)?
+            .unwrap_or_default();
+        drop(connection);
+        let options = options.unwrap_or_default();
+        let target_block = options
+            .target_block
+            .unwrap_or(last_sealed_l2_block + DEFAULT_BUNDLE_TARGET_BLOCKS);
+        if target_block <= last_sealed_l2_block {
+            return Err(SubmitTxError::BundleTargetBlockSealed(target_block.0).into());
+        }
+
+        let tx_hashes = txs.iter().map(L2Tx::hash).collect();
+        let submit_result = self
+            .state
+            .tx_sender
+            .submit_bundle(
+                txs,
+                target_block,
+                self.state.api_config.submit_bundle_timeout,
+            )
+            .await;
+        submit_result
+            .map(|()| SubmittedBundle {
+                tx_hashes,
+                target_block,
+            })
+            .map_err(|err| {
+                tracing::debug!("Send bundle error: {err}");
+                API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
+                err.into()
+            })
+    }
 }
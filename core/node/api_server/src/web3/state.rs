@@ -108,10 +108,15 @@ pub struct InternalApiConfig {
     pub l2_testnet_paymaster_addr: Option<Address>,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    pub max_simulated_bundle_size: usize,
+    pub simulate_bundle_timeout: Duration,
+    pub max_submitted_bundle_size: usize,
+    pub submit_bundle_timeout: Duration,
     pub base_token_address: Option<Address>,
     pub filters_disabled: bool,
     pub dummy_verifier: bool,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
+    pub tee_finalized_tag_enabled: bool,
 }
 
 impl InternalApiConfig {
@@ -159,10 +164,15 @@ impl InternalApiConfig {
             l2_testnet_paymaster_addr: contracts_config.l2_testnet_paymaster_addr,
             req_entities_limit: web3_config.req_entities_limit(),
             fee_history_limit: web3_config.fee_history_limit(),
+            max_simulated_bundle_size: web3_config.max_simulated_bundle_size(),
+            simulate_bundle_timeout: web3_config.simulate_bundle_timeout(),
+            max_submitted_bundle_size: web3_config.max_submitted_bundle_size(),
+            submit_bundle_timeout: web3_config.submit_bundle_timeout(),
             base_token_address: contracts_config.base_token_addr,
             filters_disabled: web3_config.filters_disabled,
             dummy_verifier: genesis_config.dummy_verifier,
             l1_batch_commit_data_generator_mode: genesis_config.l1_batch_commit_data_generator_mode,
+            tee_finalized_tag_enabled: web3_config.tee_finalized_tag_enabled,
         }
     }
 }
@@ -292,12 +302,23 @@ impl RpcState {
             .map_err(|err| err.generalize().into())
     }
 
+    /// Checks that the block ID is supported by the node configuration.
+    fn ensure_block_id_supported(&self, block: api::BlockId) -> Result<(), Web3Error> {
+        if block == api::BlockId::Number(api::BlockNumber::TeeFinalized)
+            && !self.api_config.tee_finalized_tag_enabled
+        {
+            return Err(Web3Error::UnsupportedBlockTag("tee_finalized"));
+        }
+        Ok(())
+    }
+
     /// Resolves the specified block ID to a block number, which is guaranteed to be present in the node storage.
     pub(crate) async fn resolve_block(
         &self,
         connection: &mut Connection<'_, Core>,
         block: api::BlockId,
     ) -> Result<L2BlockNumber, Web3Error> {
+        self.ensure_block_id_supported(block)?;
         self.start_info.ensure_not_pruned(block, connection).await?;
         connection
             .blocks_web3_dal()
@@ -319,6 +340,7 @@ impl RpcState {
         connection: &mut Connection<'_, Core>,
         block: api::BlockId,
     ) -> Result<Option<L2BlockNumber>, Web3Error> {
+        self.ensure_block_id_supported(block)?;
         self.start_info.ensure_not_pruned(block, connection).await?;
         match block {
             api::BlockId::Number(api::BlockNumber::Number(number)) => {
@@ -338,6 +360,7 @@ impl RpcState {
         connection: &mut Connection<'_, Core>,
         block: api::BlockId,
     ) -> Result<BlockArgs, Web3Error> {
+        self.ensure_block_id_supported(block)?;
         BlockArgs::new(connection, block, &self.start_info)
             .await
             .map_err(|err| match err {
@@ -357,6 +380,7 @@ impl RpcState {
 
         let block_number = block_number.unwrap_or(api::BlockNumber::Latest);
         let block_id = api::BlockId::Number(block_number);
+        self.ensure_block_id_supported(block_id)?;
         let mut conn = self.acquire_connection().await?;
         Ok(self.resolve_block(&mut conn, block_id).await.unwrap())
         // ^ `unwrap()` is safe: `resolve_block_id(api::BlockId::Number(_))` can only return `None`
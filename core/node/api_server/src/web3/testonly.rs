@@ -102,6 +102,23 @@ pub async fn spawn_http_server(
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
     stop_receiver: watch::Receiver<bool>,
+) -> ApiServerHandles {
+    spawn_http_server_with_executor(
+        api_config,
+        pool,
+        tx_executor.into(),
+        method_tracer,
+        stop_receiver,
+    )
+    .await
+}
+
+pub(crate) async fn spawn_http_server_with_executor(
+    api_config: InternalApiConfig,
+    pool: ConnectionPool<Core>,
+    tx_executor: TransactionExecutor,
+    method_tracer: Arc<MethodTracer>,
+    stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
     spawn_server(
         ApiTransportLabel::Http,
@@ -127,7 +144,7 @@ pub async fn spawn_ws_server(
         api_config,
         pool,
         websocket_requests_per_minute_limit,
-        MockTransactionExecutor::default(),
+        MockTransactionExecutor::default().into(),
         Arc::default(),
         stop_receiver,
     )
@@ -139,16 +156,16 @@ async fn spawn_server(
     api_config: InternalApiConfig,
     pool: ConnectionPool<Core>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
-    tx_executor: MockTransactionExecutor,
+    tx_executor: TransactionExecutor,
     method_tracer: Arc<MethodTracer>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let (tx_sender, vm_barrier) =
-        create_test_tx_sender(pool.clone(), api_config.l2_chain_id, tx_executor.into()).await;
+        create_test_tx_sender(pool.clone(), api_config.l2_chain_id, tx_executor).await;
     let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
-    namespaces.extend([Namespace::Debug, Namespace::Snapshots]);
+    namespaces.extend([Namespace::Debug, Namespace::Snapshots, Namespace::Txpool]);
 
     let server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
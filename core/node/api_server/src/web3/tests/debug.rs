@@ -1,6 +1,9 @@
 //! Tests for the `debug` Web3 namespace.
 
-use zksync_types::{tx::TransactionExecutionResult, vm_trace::Call, BOOTLOADER_ADDRESS};
+use zksync_multivm::interface::ExecutionResult;
+use zksync_types::{
+    tx::TransactionExecutionResult, vm_trace::Call, BOOTLOADER_ADDRESS, SYSTEM_CONTEXT_ADDRESS,
+};
 use zksync_web3_decl::{
     client::{DynClient, L2},
     namespaces::DebugNamespaceClient,
@@ -204,6 +207,9 @@ impl HttpTest for TraceTransactionTest {
             .trace_transaction(tx_results[0].hash, None)
             .await?
             .context("no transaction traces")?;
+        let api::DebugTraceResult::CallTrace(result) = result else {
+            panic!("Unexpected trace: {result:?}");
+        };
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -218,6 +224,177 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct PrestateTraceTransactionTest;
+
+#[async_trait]
+impl HttpTest for PrestateTraceTransactionTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_tx_responses(|_, block_args| {
+            // The transaction must be replayed on top of the preceding L2 block.
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(0));
+            ExecutionResult::Success { output: vec![] }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [execute_l2_transaction_with_traces(0)];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::CallTracerConfig {
+                only_top_call: false,
+                diff_mode: true,
+            },
+            state_overrides: None,
+        };
+        let result = client
+            .trace_transaction(tx_results[0].hash, Some(options.clone()))
+            .await?
+            .context("no transaction traces")?;
+        // The mock executor doesn't run tracers, so the trace is empty.
+        assert_eq!(
+            result,
+            api::DebugTraceResult::Prestate(api::PrestateTrace::Diff {
+                pre: HashMap::new(),
+                post: HashMap::new(),
+            })
+        );
+
+        let missing_trace = client
+            .trace_transaction(H256::repeat_byte(0xff), Some(options.clone()))
+            .await?;
+        assert!(missing_trace.is_none());
+
+        let error = client
+            .trace_block_by_number(api::BlockNumber::from(1), Some(options))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+            assert!(error.message().contains("prestateTracer"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer() {
+    test_http_server(PrestateTraceTransactionTest).await;
+}
+
+#[derive(Debug)]
+struct PrestateTraceWithRealVmTest;
+
+impl PrestateTraceWithRealVmTest {
+    async fn trace(
+        client: &DynClient<L2>,
+        tx_hash: H256,
+        diff_mode: bool,
+    ) -> anyhow::Result<api::PrestateTrace> {
+        let options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::CallTracerConfig {
+                only_top_call: false,
+                diff_mode,
+            },
+            state_overrides: None,
+        };
+        let result = client
+            .trace_transaction(tx_hash, Some(options))
+            .await?
+            .context("no transaction traces")?;
+        match result {
+            api::DebugTraceResult::Prestate(trace) => Ok(trace),
+            other => panic!("Unexpected trace: {other:?}"),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for PrestateTraceWithRealVmTest {
+    fn uses_real_vm(&self) -> bool {
+        true
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [
+            execute_l2_transaction(create_l2_transaction(100, 800)),
+            execute_l2_transaction(create_l2_transaction(100, 800)),
+        ];
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        drop(storage);
+
+        let api::PrestateTrace::Diff {
+            pre: first_pre,
+            post: first_post,
+        } = Self::trace(client, tx_results[0].hash, true).await?
+        else {
+            panic!("Expected a diff trace");
+        };
+        assert!(!first_post.is_empty());
+
+        // In the non-diff mode, values must be taken before the transaction is executed.
+        let api::PrestateTrace::Prestate(first_prestate) =
+            Self::trace(client, tx_results[0].hash, false).await?
+        else {
+            panic!("Expected a prestate trace");
+        };
+        for (address, account) in &first_pre {
+            let prestate_account = &first_prestate[address];
+            for (slot, value) in &account.storage {
+                assert_eq!(
+                    prestate_account.storage[slot], *value,
+                    "{address:?}/{slot:?}"
+                );
+            }
+        }
+
+        // The second transaction must be traced on top of the state produced by the first one.
+        let api::PrestateTrace::Diff {
+            pre: second_pre, ..
+        } = Self::trace(client, tx_results[1].hash, true).await?
+        else {
+            panic!("Expected a diff trace");
+        };
+        let first_post_context = &first_post[&SYSTEM_CONTEXT_ADDRESS].storage;
+        let second_pre_context = &second_pre[&SYSTEM_CONTEXT_ADDRESS].storage;
+        let mut common_slots = 0;
+        for (slot, value) in second_pre_context {
+            if let Some(first_value) = first_post_context.get(slot) {
+                assert_eq!(value, first_value, "{slot:?}");
+                common_slots += 1;
+            }
+        }
+        assert!(
+            common_slots > 0,
+            "{first_post_context:?} / {second_pre_context:?}"
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_transaction_with_prestate_tracer_in_real_vm() {
+    test_http_server(PrestateTraceWithRealVmTest).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
@@ -30,6 +30,7 @@ use zksync_types::{
     get_nonce_key,
     l2::L2Tx,
     storage::get_code_key,
+    tee_types::TeeType,
     tokens::{TokenInfo, TokenMetadata},
     tx::{
         tx_execution_info::TxExecutionStatus, ExecutionMetrics, IncludedTxLocation,
@@ -56,13 +57,14 @@ use zksync_web3_decl::{
 
 use super::*;
 use crate::{
-    execution_sandbox::testonly::MockTransactionExecutor,
-    web3::testonly::{spawn_http_server, spawn_ws_server},
+    execution_sandbox::{testonly::MockTransactionExecutor, TransactionExecutor},
+    web3::testonly::{spawn_http_server_with_executor, spawn_ws_server},
 };
 
 mod debug;
 mod filters;
 mod snapshots;
+mod txpool;
 mod vm;
 mod ws;
 
@@ -140,6 +142,11 @@ trait HttpTest: Send + Sync {
         MockTransactionExecutor::default()
     }
 
+    /// If set, transactions are executed in a real VM and [`Self::transaction_executor()`] is ignored.
+    fn uses_real_vm(&self) -> bool {
+        false
+    }
+
     fn method_tracer(&self) -> Arc<MethodTracer> {
         Arc::default()
     }
@@ -151,6 +158,11 @@ trait HttpTest: Send + Sync {
     fn filters_disabled(&self) -> bool {
         false
     }
+
+    /// Overrides the `tee_finalized_tag_enabled` configuration parameter for HTTP server startup
+    fn tee_finalized_tag_enabled(&self) -> bool {
+        false
+    }
 }
 
 /// Storage initialization strategy.
@@ -228,10 +240,16 @@ async fn test_http_server(test: impl HttpTest) {
     let genesis = GenesisConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis);
     api_config.filters_disabled = test.filters_disabled();
-    let mut server_handles = spawn_http_server(
+    api_config.tee_finalized_tag_enabled = test.tee_finalized_tag_enabled();
+    let tx_executor = if test.uses_real_vm() {
+        TransactionExecutor::Real
+    } else {
+        test.transaction_executor().into()
+    };
+    let mut server_handles = spawn_http_server_with_executor(
         api_config,
         pool.clone(),
-        test.transaction_executor(),
+        tx_executor,
         test.method_tracer(),
         stop_receiver,
     )
@@ -581,6 +599,128 @@ async fn l1_batch_methods_with_snapshot_recovery() {
     test_http_server(L1BatchMethodsWithSnapshotRecovery).await;
 }
 
+#[derive(Debug)]
+struct TeeProofsTest {
+    tag_enabled: bool,
+}
+
+impl TeeProofsTest {
+    const PUBKEY: &'static [u8] = &[1; 33];
+    const ATTESTATION: &'static [u8] = b"attestation";
+
+    async fn prepare_batches(storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        storage
+            .tee_proof_generation_dal()
+            .save_attestation(Self::PUBKEY, Self::ATTESTATION)
+            .await?;
+        for number in 1..=3 {
+            let l1_batch_number = L1BatchNumber(number);
+            store_l2_block(storage, L2BlockNumber(number), &[]).await?;
+            seal_l1_batch(storage, l1_batch_number).await?;
+            storage
+                .tee_verifier_input_producer_dal()
+                .create_tee_verifier_input_producer_job(l1_batch_number)
+                .await?;
+            storage
+                .tee_proof_generation_dal()
+                .insert_tee_proof_generation_job(l1_batch_number)
+                .await?;
+        }
+        for number in [1, 3] {
+            Self::save_proof(storage, L1BatchNumber(number)).await?;
+        }
+        Ok(())
+    }
+
+    async fn save_proof(
+        storage: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        storage
+            .tee_proof_generation_dal()
+            .save_proof_artifacts_metadata(
+                l1_batch_number,
+                b"signature",
+                Self::PUBKEY,
+                b"proof",
+                TeeType::Sgx,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn tee_finalized_block(client: &DynClient<L2>) -> anyhow::Result<U64> {
+        let block = client
+            .get_block_by_number(api::BlockNumber::TeeFinalized, false)
+            .await?
+            .context("no TEE-finalized block")?;
+        Ok(block.number)
+    }
+}
+
+#[async_trait]
+impl HttpTest for TeeProofsTest {
+    fn tee_finalized_tag_enabled(&self) -> bool {
+        self.tag_enabled
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        Self::prepare_batches(&mut storage).await?;
+
+        let proofs = client.get_l1_batch_tee_proofs(L1BatchNumber(1)).await?;
+        assert_eq!(proofs.len(), 1, "{proofs:?}");
+        let proof = &proofs[0];
+        assert_eq!(proof.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(proof.status, api::TeeProofStatus::Generated);
+        assert_eq!(proof.tee_type, Some(TeeType::Sgx));
+        assert_eq!(proof.pubkey.as_ref().unwrap().0, Self::PUBKEY);
+        assert_eq!(proof.signature.as_ref().unwrap().0, b"signature");
+        assert_eq!(proof.attestation.as_ref().unwrap().0, Self::ATTESTATION);
+        assert!(proof.proved_at.is_some());
+
+        let proofs = client.get_l1_batch_tee_proofs(L1BatchNumber(2)).await?;
+        assert_eq!(proofs.len(), 1, "{proofs:?}");
+        assert_eq!(proofs[0].status, api::TeeProofStatus::ReadyToBeProven);
+        assert_eq!(proofs[0].pubkey, None);
+        assert_eq!(proofs[0].attestation, None);
+        assert_eq!(proofs[0].proved_at, None);
+
+        let proofs = client.get_l1_batch_tee_proofs(L1BatchNumber(4)).await?;
+        assert!(proofs.is_empty(), "{proofs:?}");
+
+        if !self.tag_enabled {
+            let err = Self::tee_finalized_block(client).await.unwrap_err();
+            let err = err.downcast::<ClientError>()?;
+            assert_matches!(
+                err,
+                ClientError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+            );
+            return Ok(());
+        }
+
+        // Batch #3 is proven, but batch #2 isn't, so the TEE-finalized block is the last block in batch #1.
+        assert_eq!(Self::tee_finalized_block(client).await?, 1.into());
+        Self::save_proof(&mut storage, L1BatchNumber(2)).await?;
+        assert_eq!(Self::tee_finalized_block(client).await?, 3.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_tee_proofs() {
+    test_http_server(TeeProofsTest { tag_enabled: true }).await;
+}
+
+#[tokio::test]
+async fn tee_finalized_tag_is_rejected_if_disabled() {
+    test_http_server(TeeProofsTest { tag_enabled: false }).await;
+}
+
 #[derive(Debug)]
 struct StorageAccessWithSnapshotRecovery;
 
@@ -1066,3 +1206,53 @@ impl HttpTest for GenesisConfigTest {
 async fn tracing_genesis_config() {
     test_http_server(GenesisConfigTest).await;
 }
+
+#[derive(Debug)]
+struct ProofRequestLimitsTest;
+
+#[async_trait]
+impl HttpTest for ProofRequestLimitsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let limit = Web3JsonRpcConfig::for_tests().req_entities_limit();
+        let request = api::ProofRequest {
+            address: Address::repeat_byte(1),
+            keys: vec![],
+            l1_batch_number: L1BatchNumber(0),
+        };
+
+        let requests = vec![request.clone(); limit + 1];
+        let err = client.get_proofs(requests).await.unwrap_err();
+        assert_matches!(
+            err,
+            ClientError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+                && err.message().contains("Too many proof requests")
+        );
+
+        let keys_per_request = limit / 2 + 1;
+        let request = api::ProofRequest {
+            keys: (0..keys_per_request)
+                .map(|i| H256::from_low_u64_be(i as u64))
+                .collect(),
+            ..request
+        };
+        let err = client
+            .get_proofs(vec![request.clone(), request])
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            ClientError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+                && err.message().contains("Too many storage keys")
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn proof_request_limits() {
+    test_http_server(ProofRequestLimitsTest).await;
+}
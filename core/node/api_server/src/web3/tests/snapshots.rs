@@ -46,8 +46,10 @@ impl HttpTest for SnapshotBasicsTest {
             .add_snapshot(
                 SnapshotVersion::Version0,
                 L1BatchNumber(1),
+                None,
                 Self::CHUNK_COUNT,
                 "file:///factory_deps",
+                H256::repeat_byte(0xff),
             )
             .await?;
 
@@ -55,7 +57,12 @@ impl HttpTest for SnapshotBasicsTest {
             let path = format!("file:///storage_logs/chunk{chunk_id}");
             storage
                 .snapshots_dal()
-                .add_storage_logs_filepath_for_snapshot(L1BatchNumber(1), chunk_id, &path)
+                .add_storage_logs_filepath_for_snapshot(
+                    L1BatchNumber(1),
+                    chunk_id,
+                    &path,
+                    H256::repeat_byte(chunk_id as u8),
+                )
                 .await?;
         }
 
@@ -69,15 +76,21 @@ impl HttpTest for SnapshotBasicsTest {
         let snapshot_header = client
             .get_snapshot_by_l1_batch_number(L1BatchNumber(1))
             .await?;
-        let snapshot_header = if self.is_complete_snapshot() {
-            snapshot_header.context("no snapshot for L1 batch #1")?
+        let snapshot_manifest = client.get_snapshot_manifest(L1BatchNumber(1)).await?;
+        let (snapshot_header, snapshot_manifest) = if self.is_complete_snapshot() {
+            (
+                snapshot_header.context("no snapshot for L1 batch #1")?,
+                snapshot_manifest.context("no snapshot manifest for L1 batch #1")?,
+            )
         } else {
             assert!(snapshot_header.is_none());
+            assert!(snapshot_manifest.is_none());
             return Ok(());
         };
 
         assert_eq!(snapshot_header.l1_batch_number, L1BatchNumber(1));
         assert_eq!(snapshot_header.l2_block_number, L2BlockNumber(1));
+        assert_eq!(snapshot_header.base_l1_batch_number, None);
         assert_eq!(
             snapshot_header.factory_deps_filepath,
             "file:///factory_deps"
@@ -91,6 +104,22 @@ impl HttpTest for SnapshotBasicsTest {
             assert!(self.chunk_ids.contains(&chunk.chunk_id));
             assert!(chunk.filepath.starts_with("file:///storage_logs/"));
         }
+
+        assert_eq!(snapshot_manifest.version, snapshot_header.version);
+        assert_eq!(snapshot_manifest.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(snapshot_manifest.base_l1_batch_number, None);
+        assert_eq!(
+            snapshot_manifest.factory_deps_digest,
+            H256::repeat_byte(0xff)
+        );
+        let expected_digests: Vec<_> = (0..Self::CHUNK_COUNT)
+            .map(|chunk_id| H256::repeat_byte(chunk_id as u8))
+            .collect();
+        assert_eq!(
+            snapshot_manifest.storage_logs_chunk_digests,
+            expected_digests
+        );
+        assert_eq!(snapshot_manifest.signature, None);
         Ok(())
     }
 }
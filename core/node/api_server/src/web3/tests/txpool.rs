@@ -0,0 +1,65 @@
+//! Tests for the `txpool` Web3 namespace.
+
+use zksync_web3_decl::namespaces::TxpoolNamespaceClient;
+
+use super::*;
+
+#[derive(Debug)]
+struct TxpoolBasicsTest;
+
+#[async_trait]
+impl HttpTest for TxpoolBasicsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let initiator = Address::repeat_byte(1);
+        let mut storage = pool.connection().await?;
+        let mut initiator_tx_hashes = vec![];
+        for nonce in [0, 2] {
+            let mut tx = create_l2_transaction(1, 2);
+            tx.common_data.initiator_address = initiator;
+            tx.common_data.nonce = Nonce(nonce);
+            initiator_tx_hashes.push(tx.hash());
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+                .await?;
+        }
+        let other_tx = create_l2_transaction(1, 2);
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(&other_tx, TransactionExecutionMetrics::default())
+            .await?;
+        drop(storage);
+
+        let status = client.status().await?;
+        assert_eq!(status.pending, 2.into());
+        assert_eq!(status.queued, 1.into());
+
+        let content = client.content().await?;
+        assert_eq!(content.pending[&initiator][&0].hash, initiator_tx_hashes[0]);
+        assert_eq!(content.queued[&initiator][&2].hash, initiator_tx_hashes[1]);
+        let other_initiator = other_tx.initiator_account();
+        assert_eq!(content.pending[&other_initiator][&0].hash, other_tx.hash());
+        assert!(!content.queued.contains_key(&other_initiator));
+        assert!(!content.truncated);
+
+        let content_from = client.content_from(initiator).await?;
+        assert_eq!(content_from.pending.len(), 1);
+        assert_eq!(content_from.queued.len(), 1);
+        let content_from = client.content_from(Address::repeat_byte(0xff)).await?;
+        assert!(content_from.pending.is_empty() && content_from.queued.is_empty());
+
+        let inspect = client.inspect().await?;
+        let summary = &inspect.pending[&initiator][&0];
+        assert!(summary.contains(" wei + "), "{summary}");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn txpool_basics() {
+    test_http_server(TxpoolBasicsTest).await;
+}
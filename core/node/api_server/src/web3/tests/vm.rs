@@ -3,18 +3,25 @@
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use itertools::Itertools;
+use zksync_contracts::{load_contract, read_bytecode};
 use zksync_multivm::{
-    interface::{ExecutionResult, VmRevertReason},
+    interface::{ExecutionResult, Halt, VmRevertReason},
     vm_latest::{VmExecutionLogs, VmExecutionResultAndLogs},
 };
 use zksync_types::{
-    api::{ApiStorageLog, Log},
+    api::{
+        state_override::{OverrideAccount, OverrideState, StateOverride},
+        ApiStorageLog, Log,
+    },
+    ethabi::Token,
     get_intrinsic_constants,
+    storage::get_known_code_key,
     transaction_request::CallRequest,
+    web3::AccessListItem,
     K256PrivateKey, L2ChainId, PackedEthSignature, StorageLogKind, StorageLogWithPreviousValue,
     U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{bytecode::hash_bytecode, u256_to_h256};
 use zksync_web3_decl::namespaces::DebugNamespaceClient;
 
 use super::*;
@@ -63,7 +70,21 @@ impl HttpTest for CallTest {
         client: &DynClient<L2>,
         _pool: &ConnectionPool<Core>,
     ) -> anyhow::Result<()> {
-        let call_result = client.call(Self::call_request(b"pending"), None).await?;
+        let call_result = client
+            .call(Self::call_request(b"pending"), None, None)
+            .await?;
+        assert_eq!(call_result.0, b"output");
+
+        let state_override = StateOverride::new(HashMap::from([(
+            Address::repeat_byte(1),
+            OverrideAccount {
+                balance: Some(U256::from(1) << 100),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let call_result = client
+            .call(Self::call_request(b"pending"), None, Some(state_override))
+            .await?;
         assert_eq!(call_result.0, b"output");
 
         let valid_block_numbers_and_calldata = [
@@ -74,7 +95,7 @@ impl HttpTest for CallTest {
         for (number, calldata) in valid_block_numbers_and_calldata {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(Self::call_request(calldata), Some(number))
+                .call(Self::call_request(calldata), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -82,7 +103,7 @@ impl HttpTest for CallTest {
         let invalid_block_number = api::BlockNumber::from(100);
         let number = api::BlockIdVariant::BlockNumber(invalid_block_number);
         let error = client
-            .call(Self::call_request(b"100"), Some(number))
+            .call(Self::call_request(b"100"), Some(number), None)
             .await
             .unwrap_err();
         if let ClientError::Call(error) = error {
@@ -120,7 +141,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         _pool: &ConnectionPool<Core>,
     ) -> anyhow::Result<()> {
         let call_result = client
-            .call(CallTest::call_request(b"pending"), None)
+            .call(CallTest::call_request(b"pending"), None, None)
             .await?;
         assert_eq!(call_result.0, b"output");
         let pending_block_number = api::BlockIdVariant::BlockNumber(api::BlockNumber::Pending);
@@ -128,6 +149,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
             .call(
                 CallTest::call_request(b"pending"),
                 Some(pending_block_number),
+                None,
             )
             .await?;
         assert_eq!(call_result.0, b"output");
@@ -137,7 +159,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_l2_block);
@@ -147,7 +169,7 @@ impl HttpTest for CallTestAfterSnapshotRecovery {
         for number in first_l2_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number);
             let call_result = client
-                .call(CallTest::call_request(b"first"), Some(number))
+                .call(CallTest::call_request(b"first"), Some(number), None)
                 .await?;
             assert_eq!(call_result.0, b"output");
         }
@@ -389,7 +411,10 @@ async fn send_raw_transaction_with_detailed_output() {
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugTraceResult) {
+        let api::DebugTraceResult::CallTrace(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -442,6 +467,20 @@ impl HttpTest for TraceCallTest {
             Self::assert_debug_call(&call_request, &call_result);
         }
 
+        let prestate_options = api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::CallTracerConfig::default(),
+            state_overrides: None,
+        };
+        let call_result = client
+            .trace_call(call_request.clone(), None, Some(prestate_options))
+            .await?;
+        // The mock executor doesn't run tracers, so the trace is empty.
+        assert_eq!(
+            call_result,
+            api::DebugTraceResult::Prestate(api::PrestateTrace::Prestate(HashMap::new()))
+        );
+
         let invalid_block_number = api::BlockNumber::from(100);
         let error = client
             .trace_call(
@@ -499,7 +538,7 @@ impl HttpTest for TraceCallTestAfterSnapshotRecovery {
         for number in pruned_block_numbers {
             let number = api::BlockIdVariant::BlockNumber(number.into());
             let error = client
-                .call(CallTest::call_request(b"pruned"), Some(number))
+                .call(CallTest::call_request(b"pruned"), Some(number), None)
                 .await
                 .unwrap_err();
             assert_pruned_block_error(&error, first_local_l2_block);
@@ -579,7 +618,7 @@ impl HttpTest for EstimateGasTest {
         for threshold in [10_000, 50_000, 100_000, 1_000_000] {
             self.gas_limit_threshold.store(threshold, Ordering::Relaxed);
             let output = client
-                .estimate_gas(l2_transaction.clone().into(), None)
+                .estimate_gas(l2_transaction.clone().into(), None, None)
                 .await?;
             assert!(
                 output >= U256::from(threshold),
@@ -604,10 +643,15 @@ impl HttpTest for EstimateGasTest {
         let mut call_request = CallRequest::from(l2_transaction);
         call_request.from = Some(SendRawTransactionTest::private_key().address());
         call_request.value = Some(1_000_000.into());
-        client.estimate_gas(call_request.clone(), None).await?;
+        client
+            .estimate_gas(call_request.clone(), None, None)
+            .await?;
 
         call_request.value = Some(U256::max_value());
-        let error = client.estimate_gas(call_request, None).await.unwrap_err();
+        let error = client
+            .estimate_gas(call_request, None, None)
+            .await
+            .unwrap_err();
         if let ClientError::Call(error) = error {
             let error_msg = error.message();
             assert!(
@@ -630,3 +674,448 @@ async fn estimate_gas_basics() {
 async fn estimate_gas_after_snapshot_recovery() {
     test_http_server(EstimateGasTest::new(true)).await;
 }
+
+#[derive(Debug)]
+struct SimulateBundleTest;
+
+impl SimulateBundleTest {
+    fn call_request(data: &[u8]) -> CallRequest {
+        CallRequest {
+            data: Some(data.to_vec().into()),
+            ..CallTest::call_request(b"")
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulateBundleTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, block_args| {
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(1));
+            match tx.execute.calldata() {
+                b"approve" | b"swap" => ExecutionResult::Success {
+                    output: b"output".to_vec(),
+                },
+                b"revert" => ExecutionResult::Revert {
+                    output: VmRevertReason::General {
+                        msg: "swap is not allowed".to_owned(),
+                        data: b"revert data".to_vec(),
+                    },
+                },
+                b"halt" => ExecutionResult::Halt {
+                    reason: Halt::BootloaderOutOfGas,
+                },
+                data => panic!("Unexpected calldata: {data:?}"),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let requests = vec![Self::call_request(b"approve"), Self::call_request(b"swap")];
+        let options = api::BundleSimulationOptions {
+            trace_calls: true,
+            state_overrides: None,
+        };
+        let results = client
+            .simulate_bundle(requests.clone(), None, Some(options))
+            .await?;
+        assert_eq!(results.len(), 2);
+        for (request, result) in requests.iter().zip(&results) {
+            assert_eq!(result.status, 1.into());
+            assert_eq!(result.return_data.0, b"output");
+            assert_eq!(result.revert_reason, None);
+            let trace = result.trace.as_ref().unwrap();
+            assert_eq!(trace.input, *request.data.as_ref().unwrap());
+            assert_eq!(trace.output.0, b"output");
+        }
+
+        // Execution must stop after the halted call, so the last call is never executed.
+        let requests = vec![
+            Self::call_request(b"approve"),
+            Self::call_request(b"revert"),
+            Self::call_request(b"halt"),
+            Self::call_request(b"never"),
+        ];
+        let results = client.simulate_bundle(requests, None, None).await?;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].status, 1.into());
+        assert!(results[0].trace.is_none());
+        assert_eq!(results[1].status, 0.into());
+        assert_eq!(results[1].return_data.0, b"revert data");
+        let revert_reason = results[1].revert_reason.as_ref().unwrap();
+        assert!(
+            revert_reason.contains("swap is not allowed"),
+            "{revert_reason}"
+        );
+        assert_eq!(results[2].status, 0.into());
+        assert!(results[2].return_data.0.is_empty());
+        assert!(results[2].revert_reason.is_some());
+
+        let results = client.simulate_bundle(vec![], None, None).await?;
+        assert!(results.is_empty());
+
+        let requests = vec![Self::call_request(b"approve"); 100];
+        let error = client
+            .simulate_bundle(requests, None, None)
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            assert!(error.message().contains("too many calls"), "{error:?}");
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulate_bundle_basics() {
+    test_http_server(SimulateBundleTest).await;
+}
+
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, _| match tx.execute.calldata() {
+            b"pending" => ExecutionResult::Success {
+                output: b"output".to_vec(),
+            },
+            b"revert" => ExecutionResult::Revert {
+                output: VmRevertReason::General {
+                    msg: "not allowed".to_owned(),
+                    data: vec![],
+                },
+            },
+            data => panic!("Unexpected calldata: {data:?}"),
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let priority_fee = client.max_priority_fee_per_gas().await?;
+        let gas_price = client.gas_price().await?;
+        assert!(priority_fee <= gas_price, "{priority_fee} > {gas_price}");
+
+        // The mock executor doesn't run tracers, which is the same as for legacy VMs.
+        for calldata in [b"pending" as &[_], b"revert"] {
+            let err = client
+                .create_access_list(CallTest::call_request(calldata), None)
+                .await
+                .unwrap_err();
+            if let ClientError::Call(error) = err {
+                assert_eq!(error.code(), ErrorCode::InvalidParams.code());
+                assert!(error.message().contains("legacy VM"), "{error:?}");
+            } else {
+                panic!("Unexpected error: {err:?}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list_basics() {
+    test_http_server(CreateAccessListTest).await;
+}
+
+#[derive(Debug)]
+struct CreateAccessListWithRealVmTest;
+
+impl CreateAccessListWithRealVmTest {
+    const COUNTER_PATH: &'static str =
+        "etc/contracts-test-data/artifacts-zk/contracts/counter/counter.sol/Counter.json";
+    const COUNTER_ADDRESS: Address = Address::repeat_byte(0x42);
+
+    fn call_request(function: &str, args: &[Token]) -> CallRequest {
+        let counter = load_contract(Self::COUNTER_PATH);
+        let calldata = counter
+            .function(function)
+            .unwrap()
+            .encode_input(args)
+            .unwrap();
+        CallRequest {
+            from: Some(Address::repeat_byte(1)),
+            to: Some(Self::COUNTER_ADDRESS),
+            data: Some(calldata.into()),
+            ..CallRequest::default()
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for CreateAccessListWithRealVmTest {
+    fn uses_real_vm(&self) -> bool {
+        true
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        // Deploy the counter contract by writing its code directly to the storage.
+        let bytecode = read_bytecode(Self::COUNTER_PATH);
+        let bytecode_hash = hash_bytecode(&bytecode);
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        let logs = [
+            StorageLog::new_write_log(get_code_key(&Self::COUNTER_ADDRESS), bytecode_hash),
+            StorageLog::new_write_log(get_known_code_key(&bytecode_hash), H256::from_low_u64_be(1)),
+        ];
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await?;
+        storage
+            .factory_deps_dal()
+            .insert_factory_deps(
+                L2BlockNumber(1),
+                &HashMap::from([(bytecode_hash, bytecode)]),
+            )
+            .await?;
+        drop(storage);
+
+        let expected_access_list = vec![AccessListItem {
+            address: Self::COUNTER_ADDRESS,
+            storage_keys: vec![H256::zero()],
+        }];
+        let request = Self::call_request("increment", &[Token::Uint(1.into())]);
+        let result = client.create_access_list(request, None).await?;
+        assert_eq!(result.error, None);
+        assert_eq!(result.access_list, expected_access_list);
+        assert!(result.gas_used > U256::zero());
+
+        // Slots accessed by reverted calls are reported as well.
+        let request = Self::call_request(
+            "incrementWithRevert",
+            &[Token::Uint(1.into()), Token::Bool(true)],
+        );
+        let result = client.create_access_list(request, None).await?;
+        let error = result.error.unwrap();
+        assert!(error.contains("This method always reverts"), "{error}");
+        assert_eq!(result.access_list, expected_access_list);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn create_access_list_with_real_vm() {
+    test_http_server(CreateAccessListWithRealVmTest).await;
+}
+
+#[derive(Debug)]
+struct StateOverrideWithRealVmTest;
+
+impl StateOverrideWithRealVmTest {
+    /// Address without code in the storage; the counter code is only provided via overrides.
+    const UNDEPLOYED_ADDRESS: Address = Address::repeat_byte(0x43);
+
+    fn call_request(to: Address, function: &str, args: &[Token]) -> CallRequest {
+        CallRequest {
+            to: Some(to),
+            ..CreateAccessListWithRealVmTest::call_request(function, args)
+        }
+    }
+
+    fn counter_override(bytecode: &[u8], state: Option<OverrideState>) -> OverrideAccount {
+        OverrideAccount {
+            code: Some(bytecode.to_vec().into()),
+            state,
+            ..OverrideAccount::default()
+        }
+    }
+
+    fn decode_counter_value(output: &[u8]) -> U256 {
+        assert_eq!(output.len(), 32, "{output:?}");
+        U256::from_big_endian(output)
+    }
+}
+
+#[async_trait]
+impl HttpTest for StateOverrideWithRealVmTest {
+    fn uses_real_vm(&self) -> bool {
+        true
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        // Deploy the counter contract with a non-zero value by writing its code and state directly to the storage.
+        let counter_address = CreateAccessListWithRealVmTest::COUNTER_ADDRESS;
+        let bytecode = read_bytecode(CreateAccessListWithRealVmTest::COUNTER_PATH);
+        let bytecode_hash = hash_bytecode(&bytecode);
+        let value_key = StorageKey::new(AccountTreeId::new(counter_address), H256::zero());
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        let logs = [
+            StorageLog::new_write_log(get_code_key(&counter_address), bytecode_hash),
+            StorageLog::new_write_log(get_known_code_key(&bytecode_hash), H256::from_low_u64_be(1)),
+            StorageLog::new_write_log(value_key, H256::from_low_u64_be(7)),
+        ];
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await?;
+        storage
+            .factory_deps_dal()
+            .insert_factory_deps(
+                L2BlockNumber(1),
+                &HashMap::from([(bytecode_hash, bytecode.clone())]),
+            )
+            .await?;
+        drop(storage);
+
+        let get_request = Self::call_request(counter_address, "get", &[]);
+        let output = client.call(get_request.clone(), None, None).await?;
+        assert_eq!(Self::decode_counter_value(&output.0), 7.into());
+
+        // Storage overrides.
+        let state_diff = HashMap::from([(H256::repeat_byte(1), H256::from_low_u64_be(1))]);
+        let state_override = StateOverride::new(HashMap::from([(
+            counter_address,
+            OverrideAccount {
+                state: Some(OverrideState::StateDiff(state_diff)),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let output = client
+            .call(get_request.clone(), None, Some(state_override))
+            .await?;
+        assert_eq!(Self::decode_counter_value(&output.0), 7.into());
+
+        let state_override = StateOverride::new(HashMap::from([(
+            counter_address,
+            OverrideAccount {
+                state: Some(OverrideState::State(HashMap::new())),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let output = client
+            .call(get_request.clone(), None, Some(state_override))
+            .await?;
+        assert_eq!(Self::decode_counter_value(&output.0), 0.into());
+
+        // Code overrides, optionally combined with storage overrides.
+        let undeployed_get_request = Self::call_request(Self::UNDEPLOYED_ADDRESS, "get", &[]);
+        let output = client
+            .call(undeployed_get_request.clone(), None, None)
+            .await?;
+        assert!(output.0.is_empty(), "{output:?}");
+
+        let state_override = StateOverride::new(HashMap::from([(
+            Self::UNDEPLOYED_ADDRESS,
+            Self::counter_override(&bytecode, None),
+        )]));
+        let output = client
+            .call(undeployed_get_request, None, Some(state_override))
+            .await?;
+        assert_eq!(Self::decode_counter_value(&output.0), 0.into());
+
+        let state_diff = HashMap::from([(H256::zero(), H256::from_low_u64_be(42))]);
+        let state_override = StateOverride::new(HashMap::from([(
+            Self::UNDEPLOYED_ADDRESS,
+            Self::counter_override(&bytecode, Some(OverrideState::StateDiff(state_diff))),
+        )]));
+        let increment_request = Self::call_request(
+            Self::UNDEPLOYED_ADDRESS,
+            "incrementWithRevert",
+            &[Token::Uint(5.into()), Token::Bool(false)],
+        );
+        let output = client
+            .call(increment_request, None, Some(state_override))
+            .await?;
+        assert_eq!(Self::decode_counter_value(&output.0), 47.into());
+
+        // Balance overrides. The caller has no balance in the storage, so it cannot transfer value without an override.
+        let mut payable_request = Self::call_request(
+            counter_address,
+            "incrementWithRevertPayable",
+            &[Token::Uint(1.into()), Token::Bool(false)],
+        );
+        payable_request.value = Some(1_000.into());
+        let caller = payable_request.from.unwrap();
+        client
+            .call(payable_request.clone(), None, None)
+            .await
+            .unwrap_err();
+
+        let state_override = StateOverride::new(HashMap::from([(
+            caller,
+            OverrideAccount {
+                balance: Some(U256::from(1) << 64),
+                ..OverrideAccount::default()
+            },
+        )]));
+        let output = client
+            .call(payable_request.clone(), None, Some(state_override.clone()))
+            .await?;
+        assert_eq!(Self::decode_counter_value(&output.0), 8.into());
+
+        // Overrides must be applied when estimating gas as well.
+        let error = client
+            .estimate_gas(payable_request.clone(), None, None)
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            let error_msg = error.message();
+            assert!(
+                error_msg.to_lowercase().contains("insufficient"),
+                "{error_msg}"
+            );
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        let gas = client
+            .estimate_gas(payable_request, None, Some(state_override))
+            .await?;
+        assert!(gas > U256::zero());
+
+        let reverting_request = Self::call_request(
+            Self::UNDEPLOYED_ADDRESS,
+            "incrementWithRevert",
+            &[Token::Uint(1.into()), Token::Bool(true)],
+        );
+        client
+            .estimate_gas(reverting_request.clone(), None, None)
+            .await?;
+        let state_override = StateOverride::new(HashMap::from([(
+            Self::UNDEPLOYED_ADDRESS,
+            Self::counter_override(&bytecode, None),
+        )]));
+        let error = client
+            .estimate_gas(reverting_request, None, Some(state_override))
+            .await
+            .unwrap_err();
+        if let ClientError::Call(error) = error {
+            let error_msg = error.message();
+            assert!(
+                error_msg.contains("This method always reverts"),
+                "{error_msg}"
+            );
+        } else {
+            panic!("Unexpected error: {error:?}");
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn state_override_with_real_vm() {
+    test_http_server(StateOverrideWithRealVmTest).await;
+}
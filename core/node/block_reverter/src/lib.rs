@@ -1,21 +1,22 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{ops::RangeInclusive, path::Path, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use serde::Serialize;
 use tokio::{fs, sync::Semaphore};
+use zksync_anchoring::MintlayerClient;
 use zksync_config::{ContractsConfig, EthConfig};
 use zksync_contracts::hyperchain_contract;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{data_availability_dal::RolledBackAnchoring, ConnectionPool, Core, CoreDal};
 // Public re-export to simplify the API use.
 pub use zksync_eth_client as eth_client;
 use zksync_eth_client::{
     clients::{DynClient, L1},
     BoundEthInterface, CallFunctionArgs, EthInterface, Options,
 };
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::domain::{ZkSyncTree, ZkSyncTreeReader};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
-use zksync_state::RocksdbStorage;
-use zksync_storage::RocksDB;
+use zksync_state::{RocksdbStorage, RocksdbStorageBuilder, StateKeeperColumnFamily};
+use zksync_storage::{RocksDB, RocksDBOptions};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     ethabi::Token,
@@ -24,7 +25,7 @@ use zksync_types::{
         SnapshotStorageLogsStorageKey,
     },
     web3::BlockNumber,
-    Address, L1BatchNumber, L2ChainId, H160, H256, U256,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, H160, H256, U256,
 };
 
 #[cfg(test)]
@@ -81,8 +82,13 @@ pub enum NodeRole {
 /// - State of the Merkle tree
 /// - State of the state keeper cache
 /// - Object store for protocol snapshots
+/// - Records for anchoring L1 batch data on IPFS / Mintlayer (as a part of the Postgres state). Records for rolled back
+///   L1 batches are marked as superseded; if the data (or a deleted snapshot) is already anchored on Mintlayer,
+///   a compensating revocation anchor is recorded together with the rollback and can be issued after it is committed.
 ///
 /// In addition, it can revert the state of the Ethereum contract (if the reverted L1 batches were committed).
+///
+/// Use [`Self::plan_rollback()`] to check what would be rolled back without modifying any state.
 #[derive(Debug)]
 pub struct BlockReverter {
     /// Role affects the interactions with the consensus state.
@@ -94,6 +100,7 @@ pub struct BlockReverter {
     state_keeper_cache_path: Option<String>,
     merkle_tree_path: Option<String>,
     snapshots_object_store: Option<Arc<dyn ObjectStore>>,
+    mintlayer_client: Option<Arc<dyn MintlayerClient>>,
 }
 
 impl BlockReverter {
@@ -106,6 +113,7 @@ impl BlockReverter {
             state_keeper_cache_path: None,
             merkle_tree_path: None,
             snapshots_object_store: None,
+            mintlayer_client: None,
         }
     }
 
@@ -142,8 +150,23 @@ impl BlockReverter {
         self
     }
 
-    /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
-    pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
+    /// Enables issuing a compensating Mintlayer anchor revoking the IPFS data for rolled back L1 batches and deleted
+    /// snapshots that is already anchored on Mintlayer. Only has effect if Postgres is rolled back.
+    ///
+    /// Revocations are recorded in Postgres as a part of the rollback and are sent after it is committed. Revocations
+    /// that failed to be sent (or were recorded while revoking anchors was not enabled) are retried on the next rollback.
+    pub fn enable_revoking_mintlayer_anchors(
+        &mut self,
+        client: Arc<dyn MintlayerClient>,
+    ) -> &mut Self {
+        self.mintlayer_client = Some(client);
+        self
+    }
+
+    async fn check_rollback_allowed(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<()> {
         if !self.allow_rolling_back_executed_batches {
             let mut storage = self.connection_pool.connection().await?;
             let last_executed_l1_batch = storage
@@ -155,6 +178,183 @@ impl BlockReverter {
                 "Attempt to roll back already executed L1 batches; the last executed batch is: {last_executed_l1_batch:?}"
             );
         }
+        Ok(())
+    }
+
+    /// Returns the plan for rolling back to the specified L1 batch, i.e., what [`Self::roll_back()`] would remove
+    /// from each enabled store. Does not modify any state; RocksDB instances are opened in the read-only mode,
+    /// so planning can run alongside a node using them.
+    pub async fn plan_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<RollbackPlan> {
+        self.check_rollback_allowed(last_l1_batch_to_keep).await?;
+
+        let merkle_tree = if let Some(merkle_tree_path) = &self.merkle_tree_path {
+            Some(Self::plan_tree_rollback(last_l1_batch_to_keep, merkle_tree_path).await?)
+        } else {
+            None
+        };
+        let state_keeper_cache = if let Some(path) = &self.state_keeper_cache_path {
+            Some(Self::plan_state_keeper_cache_rollback(last_l1_batch_to_keep, path).await?)
+        } else {
+            None
+        };
+
+        let (postgres, snapshot_objects, mintlayer_revocation) = if self.should_roll_back_postgres {
+            let (plan, snapshots) = self.plan_postgres_rollback(last_l1_batch_to_keep).await?;
+            let snapshot_objects = self.snapshots_object_store.as_ref().map(|_| {
+                snapshots
+                    .iter()
+                    .flat_map(|snapshot| {
+                        let chunk_paths = snapshot.storage_logs_filepaths.iter().flatten();
+                        [&snapshot.factory_deps_filepath]
+                            .into_iter()
+                            .chain(chunk_paths)
+                    })
+                    .cloned()
+                    .collect()
+            });
+            let mintlayer_revocation = self.mintlayer_client.as_ref().and_then(|_| {
+                MintlayerRevocation::new(
+                    last_l1_batch_to_keep,
+                    &plan.anchoring,
+                    &plan.snapshot_ipfs_anchors,
+                )
+            });
+            (Some(plan), snapshot_objects, mintlayer_revocation)
+        } else {
+            (None, None, None)
+        };
+
+        Ok(RollbackPlan {
+            last_l1_batch_to_keep,
+            postgres,
+            merkle_tree,
+            state_keeper_cache,
+            snapshot_objects,
+            mintlayer_revocation,
+        })
+    }
+
+    async fn plan_tree_rollback(
+        last_l1_batch_to_keep: L1BatchNumber,
+        merkle_tree_path: &str,
+    ) -> anyhow::Result<RocksdbRollbackPlan> {
+        let path = Path::new(merkle_tree_path);
+        let merkle_tree_exists = fs::try_exists(path).await.with_context(|| {
+            format!(
+                "cannot check whether Merkle tree path `{}` exists",
+                path.display()
+            )
+        })?;
+        let next_l1_batch_number = if merkle_tree_exists {
+            let path = path.to_path_buf();
+            let next_l1_batch_number = tokio::task::spawn_blocking(move || {
+                let db = RocksDB::with_options_read_only(&path, RocksDBOptions::default())
+                    .context("failed initializing read-only RocksDB for Merkle tree")?;
+                let tree = ZkSyncTreeReader::new(db.into())
+                    .context("failed initializing Merkle tree reader")?;
+                anyhow::Ok(tree.next_l1_batch_number())
+            })
+            .await
+            .context("opening Merkle tree panicked")??;
+            Some(next_l1_batch_number)
+        } else {
+            None
+        };
+        Ok(RocksdbRollbackPlan::new(
+            merkle_tree_path,
+            last_l1_batch_to_keep,
+            next_l1_batch_number,
+        ))
+    }
+
+    async fn plan_state_keeper_cache_rollback(
+        last_l1_batch_to_keep: L1BatchNumber,
+        state_keeper_cache_path: &str,
+    ) -> anyhow::Result<RocksdbRollbackPlan> {
+        let sk_cache_exists = fs::try_exists(state_keeper_cache_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "cannot check whether state keeper cache path `{state_keeper_cache_path}` exists"
+                )
+            })?;
+        anyhow::ensure!(
+            sk_cache_exists,
+            "Path with state keeper cache DB doesn't exist at `{state_keeper_cache_path}`"
+        );
+        let path = Path::new(state_keeper_cache_path).to_path_buf();
+        let db = tokio::task::spawn_blocking(move || {
+            RocksDB::<StateKeeperColumnFamily>::with_options_read_only(
+                &path,
+                RocksDBOptions::default(),
+            )
+        })
+        .await
+        .context("opening state keeper cache panicked")?
+        .context("failed initializing read-only state keeper cache")?;
+        let sk_cache = RocksdbStorageBuilder::from_rocksdb(db);
+        Ok(RocksdbRollbackPlan::new(
+            state_keeper_cache_path,
+            last_l1_batch_to_keep,
+            sk_cache.l1_batch_number().await,
+        ))
+    }
+
+    async fn plan_postgres_rollback(
+        &self,
+        last_l1_batch_to_keep: L1BatchNumber,
+    ) -> anyhow::Result<(PostgresRollbackPlan, Vec<SnapshotMetadata>)> {
+        let mut storage = self.connection_pool.connection().await?;
+        let (_, last_l2_block_to_keep) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(last_l1_batch_to_keep)
+            .await?
+            .with_context(|| {
+                format!("L1 batch #{last_l1_batch_to_keep} doesn't contain L2 blocks")
+            })?;
+        let sealed_l1_batch = storage.blocks_dal().get_sealed_l1_batch_number().await?;
+        let sealed_l2_block = storage.blocks_dal().get_sealed_l2_block_number().await?;
+
+        let l1_batches = sealed_l1_batch
+            .filter(|&number| number > last_l1_batch_to_keep)
+            .map(|number| (last_l1_batch_to_keep + 1)..=number);
+        let l2_blocks = sealed_l2_block
+            .filter(|&number| number > last_l2_block_to_keep)
+            .map(|number| (last_l2_block_to_keep + 1)..=number);
+        let snapshots = storage
+            .snapshots_dal()
+            .get_snapshots_after(last_l1_batch_to_keep)
+            .await?;
+        let anchoring = storage
+            .data_availability_dal()
+            .get_anchoring_records_after(last_l1_batch_to_keep)
+            .await?;
+        let snapshot_ipfs_anchors = storage
+            .snapshots_dal()
+            .get_snapshot_ipfs_anchors_after(last_l1_batch_to_keep)
+            .await?;
+        let plan = PostgresRollbackPlan {
+            l1_batches,
+            l2_blocks,
+            snapshots: snapshots
+                .iter()
+                .map(|snapshot| snapshot.l1_batch_number)
+                .collect(),
+            anchoring,
+            snapshot_ipfs_anchors: snapshot_ipfs_anchors
+                .into_iter()
+                .map(|anchor| anchor.document_cid)
+                .collect(),
+        };
+        Ok((plan, snapshots))
+    }
+
+    /// Rolls back previously enabled DBs (Postgres + RocksDB) and the snapshot object store to a previous state.
+    pub async fn roll_back(&self, last_l1_batch_to_keep: L1BatchNumber) -> anyhow::Result<()> {
+        self.check_rollback_allowed(last_l1_batch_to_keep).await?;
 
         // Tree needs to be rolled back first to keep the state recoverable
         self.roll_back_rocksdb_instances(last_l1_batch_to_keep)
@@ -174,6 +374,12 @@ impl BlockReverter {
             );
         }
 
+        if self.should_roll_back_postgres {
+            if let Some(client) = &self.mintlayer_client {
+                self.send_pending_mintlayer_revocations(client.as_ref())
+                    .await?;
+            }
+        }
         Ok(())
     }
 
@@ -313,6 +519,16 @@ impl BlockReverter {
             .transactions_dal()
             .reset_transactions_state(last_l2_block_to_keep)
             .await?;
+        tracing::info!("Rolling back transaction bundles");
+        // Bundles targeting a kept L2 block can no longer be included atomically, since their transactions
+        // were returned to the mempool by the previous step.
+        transaction
+            .private_txs_dal()
+            .drop_expired_bundles(
+                last_l2_block_to_keep + 1,
+                "dropped: bundle L2 block was reverted",
+            )
+            .await?;
         tracing::info!("Rolling back events");
         transaction
             .events_dal()
@@ -345,11 +561,25 @@ impl BlockReverter {
             .await?;
 
         tracing::info!("Rolling back snapshots");
+        let deleted_snapshot_anchors = transaction
+            .snapshots_dal()
+            .get_snapshot_ipfs_anchors_after(last_l1_batch_to_keep)
+            .await?;
         let deleted_snapshots = transaction
             .snapshots_dal()
             .delete_snapshots_after(last_l1_batch_to_keep)
             .await?;
 
+        tracing::info!("Superseding anchoring records");
+        let rolled_back_anchoring = transaction
+            .data_availability_dal()
+            .get_anchoring_records_after(last_l1_batch_to_keep)
+            .await?;
+        transaction
+            .data_availability_dal()
+            .supersede_anchoring_records(&rolled_back_anchoring)
+            .await?;
+
         // Remove data from main tables (L2 blocks and L1 batches).
         tracing::info!("Rolling back L1 batches");
         transaction
@@ -371,10 +601,65 @@ impl BlockReverter {
             transaction.consensus_dal().fork().await?;
         }
 
+        // The revocation is only recorded here; it's sent after the transaction is committed, so that
+        // an external side effect is never produced for a rollback that didn't happen.
+        let deleted_snapshot_cids: Vec<_> = deleted_snapshot_anchors
+            .into_iter()
+            .map(|anchor| anchor.document_cid)
+            .collect();
+        if let Some(revocation) = MintlayerRevocation::new(
+            last_l1_batch_to_keep,
+            &rolled_back_anchoring,
+            &deleted_snapshot_cids,
+        ) {
+            tracing::info!("Recording Mintlayer revocation anchor: {revocation:?}");
+            if self.mintlayer_client.is_none() {
+                tracing::warn!(
+                    "Rolled back data is anchored on Mintlayer, but revoking anchors is not enabled; \
+                     the revocation will be sent by the next rollback with revoking enabled"
+                );
+            }
+            let payload = serde_json::to_value(&revocation)
+                .context("failed serializing Mintlayer revocation")?;
+            transaction
+                .data_availability_dal()
+                .insert_pending_mintlayer_revocation(&payload)
+                .await?;
+        }
+
         transaction.commit().await?;
         Ok(deleted_snapshots)
     }
 
+    /// Sends all recorded Mintlayer revocations that were not sent yet.
+    async fn send_pending_mintlayer_revocations(
+        &self,
+        client: &dyn MintlayerClient,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.connection().await?;
+        let revocations = storage
+            .data_availability_dal()
+            .get_pending_mintlayer_revocations()
+            .await?;
+        for revocation in revocations {
+            tracing::info!("Sending Mintlayer revocation anchor: {revocation:?}");
+            let data = serde_json::to_vec(&revocation.payload)
+                .context("failed serializing Mintlayer revocation")?;
+            let id = revocation.id;
+            let tx_id = client.deposit_data(&data).await.with_context(|| {
+                format!(
+                    "failed sending Mintlayer revocation #{id}; it's retried by the next rollback"
+                )
+            })?;
+            tracing::info!("Sent Mintlayer revocation anchor in transaction {tx_id}");
+            storage
+                .data_availability_dal()
+                .mark_mintlayer_revocation_sent(revocation.id, &tx_id)
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn delete_snapshot_files(
         object_store: &dyn ObjectStore,
         deleted_snapshots: &[SnapshotMetadata],
@@ -612,6 +897,91 @@ impl BlockReverter {
     }
 }
 
+/// Plan for rolling back the node state returned by [`BlockReverter::plan_rollback()`]. Stores that are not enabled
+/// for rollback are set to `None`.
+#[derive(Debug, Serialize)]
+pub struct RollbackPlan {
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    pub postgres: Option<PostgresRollbackPlan>,
+    pub merkle_tree: Option<RocksdbRollbackPlan>,
+    pub state_keeper_cache: Option<RocksdbRollbackPlan>,
+    /// Paths of snapshot objects to be removed from the object store.
+    pub snapshot_objects: Option<Vec<String>>,
+    /// Compensating anchor to be sent to Mintlayer.
+    pub mintlayer_revocation: Option<MintlayerRevocation>,
+}
+
+/// Part of [`RollbackPlan`] for Postgres.
+#[derive(Debug, Serialize)]
+pub struct PostgresRollbackPlan {
+    /// L1 batches to be removed, together with all associated data.
+    pub l1_batches: Option<RangeInclusive<L1BatchNumber>>,
+    /// L2 blocks to be removed, together with all associated data.
+    pub l2_blocks: Option<RangeInclusive<L2BlockNumber>>,
+    /// L1 batches of snapshots to be removed.
+    pub snapshots: Vec<L1BatchNumber>,
+    /// Anchoring records to be marked as superseded.
+    pub anchoring: RolledBackAnchoring,
+    /// IPFS document CIDs of snapshots to be removed that are anchored on Mintlayer.
+    pub snapshot_ipfs_anchors: Vec<String>,
+}
+
+/// Part of [`RollbackPlan`] for a RocksDB instance (the Merkle tree or the state keeper cache).
+#[derive(Debug, Serialize)]
+pub struct RocksdbRollbackPlan {
+    pub path: String,
+    /// L1 batches to be rolled back. `None` if there's nothing to roll back (e.g., the DB is missing or is behind
+    /// the L1 batch to roll back to).
+    pub l1_batches: Option<RangeInclusive<L1BatchNumber>>,
+}
+
+impl RocksdbRollbackPlan {
+    fn new(
+        path: &str,
+        last_l1_batch_to_keep: L1BatchNumber,
+        next_l1_batch_number: Option<L1BatchNumber>,
+    ) -> Self {
+        let l1_batches = next_l1_batch_number
+            .filter(|&number| number > last_l1_batch_to_keep + 1)
+            .map(|number| (last_l1_batch_to_keep + 1)..=(number - 1));
+        Self {
+            path: path.to_owned(),
+            l1_batches,
+        }
+    }
+}
+
+/// Compensating Mintlayer anchor revoking IPFS data for rolled back L1 batches and deleted snapshots.
+/// Deposited on Mintlayer as JSON.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename = "revocation", rename_all = "camelCase")]
+pub struct MintlayerRevocation {
+    /// Last L1 batch retained after the rollback; data for all later L1 batches is revoked.
+    pub last_l1_batch_to_keep: L1BatchNumber,
+    /// Revoked IPFS hashes previously anchored on Mintlayer.
+    pub revoked_ipfs_hashes: Vec<String>,
+    /// Revoked IPFS document CIDs of deleted snapshots previously anchored on Mintlayer.
+    pub revoked_snapshot_cids: Vec<String>,
+}
+
+impl MintlayerRevocation {
+    fn new(
+        last_l1_batch_to_keep: L1BatchNumber,
+        anchoring: &RolledBackAnchoring,
+        snapshot_cids: &[String],
+    ) -> Option<Self> {
+        let revoked_ipfs_hashes = anchoring.anchored_ipfs_hashes();
+        if revoked_ipfs_hashes.is_empty() && snapshot_cids.is_empty() {
+            return None;
+        }
+        Some(Self {
+            last_l1_batch_to_keep,
+            revoked_ipfs_hashes,
+            revoked_snapshot_cids: snapshot_cids.to_vec(),
+        })
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct SuggestedRevertValues {
     pub last_executed_l1_batch_number: L1BatchNumber,
@@ -1,18 +1,30 @@
 //! Tests for block reverter.
 
-use std::{collections::HashSet, sync::Mutex};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use test_casing::test_casing;
 use tokio::sync::watch;
-use zksync_dal::Connection;
+use zksync_anchoring::MockMintlayerClient;
+use zksync_dal::{
+    data_availability_dal::{
+        OperationStatus, OperationType, PendingIpfsOperation, PendingMintlayerBatch,
+    },
+    Connection,
+};
 use zksync_merkle_tree::TreeInstruction;
 use zksync_object_store::{Bucket, MockObjectStore};
 use zksync_state::ReadStorage;
 use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
-    snapshots::SnapshotVersion,
+    snapshots::{SnapshotIpfsAnchor, SnapshotVersion},
     AccountTreeId, L2BlockNumber, ProtocolVersion, ProtocolVersionId, StorageKey, StorageLog,
 };
 
@@ -213,13 +225,11 @@ async fn create_mock_snapshot(
         .expect("`chunk_ids` cannot be empty")
         + 1;
 
+    let factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![],
+    };
     let factory_deps_key = object_store
-        .put(
-            l1_batch_number,
-            &SnapshotFactoryDependencies {
-                factory_deps: vec![],
-            },
-        )
+        .put(l1_batch_number, &factory_deps)
         .await
         .unwrap();
     storage
@@ -227,8 +237,10 @@ async fn create_mock_snapshot(
         .add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             storage_logs_chunk_count,
             &factory_deps_key,
+            factory_deps.digest(),
         )
         .await
         .unwrap();
@@ -238,18 +250,13 @@ async fn create_mock_snapshot(
             l1_batch_number,
             chunk_id,
         };
-        let key = object_store
-            .put(
-                key,
-                &SnapshotStorageLogsChunk::<H256> {
-                    storage_logs: vec![],
-                },
-            )
-            .await
-            .unwrap();
+        let chunk = SnapshotStorageLogsChunk::<H256> {
+            storage_logs: vec![],
+        };
+        let key = object_store.put(key, &chunk).await.unwrap();
         storage
             .snapshots_dal()
-            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &key)
+            .add_storage_logs_filepath_for_snapshot(l1_batch_number, chunk_id, &key, chunk.digest())
             .await
             .unwrap();
     }
@@ -470,3 +477,330 @@ async fn reverter_handles_incomplete_snapshot() {
         assert_matches!(chunk_result.unwrap_err(), ObjectStoreError::KeyNotFound(_));
     }
 }
+
+#[tokio::test]
+async fn planning_rollback_does_not_modify_state() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let merkle_tree_path = temp_dir.path().join("tree");
+    initialize_merkle_tree(&merkle_tree_path, &storage_logs);
+    let sk_cache_path = temp_dir.path().join("sk_cache");
+    let sk_cache = RocksdbStorage::builder(&sk_cache_path).await.unwrap();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    sk_cache
+        .synchronize(&mut storage, &stop_receiver, None)
+        .await
+        .unwrap();
+
+    let object_store = MockObjectStore::arc();
+    create_mock_snapshot(&mut storage, &*object_store, L1BatchNumber(3), 0..2).await;
+    create_mock_snapshot(&mut storage, &*object_store, L1BatchNumber(7), 0..5).await;
+
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    block_reverter
+        .enable_rolling_back_postgres()
+        .enable_rolling_back_merkle_tree(merkle_tree_path.to_str().unwrap().to_owned())
+        .enable_rolling_back_state_keeper_cache(sk_cache_path.to_str().unwrap().to_owned())
+        .enable_rolling_back_snapshot_objects(object_store.clone());
+    let plan = block_reverter
+        .plan_rollback(L1BatchNumber(5))
+        .await
+        .unwrap();
+
+    let postgres_plan = plan.postgres.unwrap();
+    assert_eq!(
+        postgres_plan.l1_batches,
+        Some(L1BatchNumber(6)..=L1BatchNumber(9))
+    );
+    assert_eq!(
+        postgres_plan.l2_blocks,
+        Some(L2BlockNumber(6)..=L2BlockNumber(9))
+    );
+    assert_eq!(postgres_plan.snapshots, [L1BatchNumber(7)]);
+    assert!(postgres_plan.anchoring.is_empty());
+    let merkle_tree_plan = plan.merkle_tree.unwrap();
+    assert_eq!(
+        merkle_tree_plan.l1_batches,
+        Some(L1BatchNumber(6)..=L1BatchNumber(9))
+    );
+    let sk_cache_plan = plan.state_keeper_cache.unwrap();
+    assert_eq!(
+        sk_cache_plan.l1_batches,
+        Some(L1BatchNumber(6)..=L1BatchNumber(9))
+    );
+    // Factory deps + 5 storage logs chunks
+    assert_eq!(plan.snapshot_objects.unwrap().len(), 6);
+    assert!(plan.mintlayer_revocation.is_none());
+
+    // Check that no state was modified.
+    let last_l1_batch_number = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l1_batch_number, Some(L1BatchNumber(9)));
+    let snapshot = storage
+        .snapshots_dal()
+        .get_snapshot_metadata(L1BatchNumber(7))
+        .await
+        .unwrap();
+    assert!(snapshot.is_some());
+    object_store
+        .get::<SnapshotFactoryDependencies>(L1BatchNumber(7))
+        .await
+        .unwrap();
+    let db = RocksDB::new(&merkle_tree_path).unwrap();
+    let tree = ZkSyncTree::new(db.into()).unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(10));
+}
+
+fn mock_ipfs_operation(
+    l1_batch_range: RangeInclusive<L1BatchNumber>,
+    ipfs_hash: Option<&str>,
+) -> PendingIpfsOperation {
+    PendingIpfsOperation {
+        id: uuid::Uuid::new_v4(),
+        operation_type: OperationType::Commit,
+        data: vec![],
+        attempts: 0,
+        last_attempt: None,
+        created_at: chrono::Utc::now(),
+        status: if ipfs_hash.is_some() {
+            OperationStatus::Completed
+        } else {
+            OperationStatus::Pending
+        },
+        ipfs_hash: ipfs_hash.map(str::to_owned),
+        requires_mintlayer: true,
+        l1_batch_range: Some(l1_batch_range),
+    }
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn rolling_back_anchoring_records(revoke_anchors: bool) {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+
+    let kept_op = mock_ipfs_operation(L1BatchNumber(1)..=L1BatchNumber(3), Some("QmKept"));
+    let anchored_op = mock_ipfs_operation(L1BatchNumber(4)..=L1BatchNumber(7), Some("QmAnchored"));
+    let uploaded_op = mock_ipfs_operation(L1BatchNumber(8)..=L1BatchNumber(8), Some("QmUploaded"));
+    let pending_op = mock_ipfs_operation(L1BatchNumber(9)..=L1BatchNumber(9), None);
+    for op in [&kept_op, &anchored_op, &uploaded_op, &pending_op] {
+        storage
+            .data_availability_dal()
+            .save_pending_operation(op)
+            .await
+            .unwrap();
+    }
+    let anchored_batch = PendingMintlayerBatch {
+        ipfs_hashes: vec!["QmKept".to_owned(), "QmAnchored".to_owned()],
+        status: OperationStatus::Completed,
+        ..PendingMintlayerBatch::new()
+    };
+    let pending_batch = PendingMintlayerBatch {
+        ipfs_hashes: vec!["QmUploaded".to_owned()],
+        ..PendingMintlayerBatch::new()
+    };
+    for batch in [&anchored_batch, &pending_batch] {
+        storage
+            .data_availability_dal()
+            .update_mintlayer_batch(batch)
+            .await
+            .unwrap();
+    }
+    add_anchored_snapshot(&mut storage, L1BatchNumber(7), "bafysnapshot").await;
+
+    let mintlayer = MockMintlayerClient::default();
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    block_reverter.enable_rolling_back_postgres();
+    if revoke_anchors {
+        block_reverter.enable_revoking_mintlayer_anchors(Arc::new(mintlayer.clone()));
+    }
+
+    let plan = block_reverter
+        .plan_rollback(L1BatchNumber(5))
+        .await
+        .unwrap();
+    let postgres_plan = plan.postgres.unwrap();
+    let anchoring_plan = &postgres_plan.anchoring;
+    let planned_op_ids: Vec<_> = anchoring_plan
+        .ipfs_operations
+        .iter()
+        .map(|op| op.id)
+        .collect();
+    assert_eq!(
+        planned_op_ids,
+        [anchored_op.id, uploaded_op.id, pending_op.id]
+    );
+    assert_eq!(anchoring_plan.anchored_ipfs_hashes(), ["QmAnchored"]);
+    assert_eq!(anchoring_plan.pending_mintlayer_batches, [pending_batch.id]);
+    assert_eq!(postgres_plan.snapshot_ipfs_anchors, ["bafysnapshot"]);
+    if revoke_anchors {
+        let revocation = plan.mintlayer_revocation.unwrap();
+        assert_eq!(revocation.revoked_ipfs_hashes, ["QmAnchored"]);
+        assert_eq!(revocation.revoked_snapshot_cids, ["bafysnapshot"]);
+    } else {
+        assert!(plan.mintlayer_revocation.is_none());
+    }
+
+    block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
+
+    let pending_ops = storage
+        .data_availability_dal()
+        .get_pending_ipfs_operations()
+        .await
+        .unwrap();
+    assert!(pending_ops.is_empty(), "{pending_ops:?}");
+    let pending_batches = storage
+        .data_availability_dal()
+        .get_pending_mintlayer_batches()
+        .await
+        .unwrap();
+    assert!(pending_batches.is_empty(), "{pending_batches:?}");
+    let last_anchored_l1_batch = storage
+        .data_availability_dal()
+        .get_last_mintlayer_anchored_l1_batch()
+        .await
+        .unwrap();
+    assert_eq!(last_anchored_l1_batch, Some(L1BatchNumber(3)));
+    let remaining_records = storage
+        .data_availability_dal()
+        .get_anchoring_records_after(L1BatchNumber(5))
+        .await
+        .unwrap();
+    assert!(remaining_records.is_empty(), "{remaining_records:?}");
+
+    let deposits = mintlayer.deposits();
+    let pending_revocations = storage
+        .data_availability_dal()
+        .get_pending_mintlayer_revocations()
+        .await
+        .unwrap();
+    let expected_revocation = serde_json::json!({
+        "type": "revocation",
+        "lastL1BatchToKeep": 5,
+        "revokedIpfsHashes": ["QmAnchored"],
+        "revokedSnapshotCids": ["bafysnapshot"],
+    });
+    if revoke_anchors {
+        assert_eq!(deposits.len(), 1);
+        let deposit = deposits.into_values().next().unwrap();
+        let deposit: serde_json::Value = serde_json::from_slice(&deposit).unwrap();
+        assert_eq!(deposit, expected_revocation);
+        assert!(pending_revocations.is_empty(), "{pending_revocations:?}");
+    } else {
+        assert!(deposits.is_empty());
+        // The revocation is recorded, so that it can be sent later.
+        assert_eq!(pending_revocations.len(), 1);
+        assert_eq!(pending_revocations[0].payload, expected_revocation);
+    }
+}
+
+async fn add_anchored_snapshot(
+    storage: &mut Connection<'_, Core>,
+    l1_batch_number: L1BatchNumber,
+    document_cid: &str,
+) {
+    storage
+        .snapshots_dal()
+        .add_snapshot(
+            SnapshotVersion::Version0,
+            l1_batch_number,
+            None,
+            1,
+            "factory_deps",
+            H256::zero(),
+        )
+        .await
+        .unwrap();
+    let anchor = SnapshotIpfsAnchor {
+        document_cid: document_cid.to_owned(),
+        anchor_tx_id: "0x01".to_owned(),
+    };
+    storage
+        .snapshots_dal()
+        .set_snapshot_ipfs_anchor(l1_batch_number, &anchor)
+        .await
+        .unwrap();
+}
+
+/// Mintlayer client that fails deposits until it's enabled.
+#[derive(Debug, Default)]
+struct FlakyMintlayerClient {
+    inner: MockMintlayerClient,
+    is_available: AtomicBool,
+}
+
+#[async_trait]
+impl MintlayerClient for FlakyMintlayerClient {
+    async fn deposit_data(&self, data: &[u8]) -> anyhow::Result<String> {
+        anyhow::ensure!(
+            self.is_available.load(Ordering::SeqCst),
+            "Mintlayer is unavailable"
+        );
+        self.inner.deposit_data(data).await
+    }
+
+    async fn fetch_deposited_data(&self, tx_id: &str) -> anyhow::Result<Vec<u8>> {
+        self.inner.fetch_deposited_data(tx_id).await
+    }
+}
+
+#[tokio::test]
+async fn mintlayer_revocation_is_sent_after_rollback_is_committed() {
+    let storage_logs = gen_storage_logs();
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    setup_storage(&mut storage, &storage_logs).await;
+    add_anchored_snapshot(&mut storage, L1BatchNumber(7), "bafysnapshot").await;
+
+    let mintlayer = Arc::new(FlakyMintlayerClient::default());
+    let mut block_reverter = BlockReverter::new(NodeRole::External, pool.clone());
+    block_reverter
+        .enable_rolling_back_postgres()
+        .enable_revoking_mintlayer_anchors(mintlayer.clone());
+
+    // Sending the revocation fails, but the rollback itself must be committed.
+    block_reverter
+        .roll_back(L1BatchNumber(5))
+        .await
+        .unwrap_err();
+    let last_l1_batch_number = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .unwrap();
+    assert_eq!(last_l1_batch_number, Some(L1BatchNumber(5)));
+    let pending_revocations = storage
+        .data_availability_dal()
+        .get_pending_mintlayer_revocations()
+        .await
+        .unwrap();
+    assert_eq!(pending_revocations.len(), 1);
+    assert!(mintlayer.inner.deposits().is_empty());
+
+    // Repeating the rollback sends the recorded revocation.
+    mintlayer.is_available.store(true, Ordering::SeqCst);
+    block_reverter.roll_back(L1BatchNumber(5)).await.unwrap();
+    let pending_revocations = storage
+        .data_availability_dal()
+        .get_pending_mintlayer_revocations()
+        .await
+        .unwrap();
+    assert!(pending_revocations.is_empty(), "{pending_revocations:?}");
+    let deposits = mintlayer.inner.deposits();
+    assert_eq!(deposits.len(), 1);
+    let deposit = deposits.into_values().next().unwrap();
+    let deposit: serde_json::Value = serde_json::from_slice(&deposit).unwrap();
+    assert_eq!(
+        deposit["revokedSnapshotCids"],
+        serde_json::json!(["bafysnapshot"])
+    );
+}
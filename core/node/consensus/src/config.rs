@@ -6,11 +6,13 @@ use secrecy::{ExposeSecret as _, Secret};
 use zksync_concurrency::net;
 use zksync_config::{
     configs,
-    configs::consensus::{ConsensusConfig, ConsensusSecrets, Host, NodePublicKey},
+    configs::consensus::{
+        ConsensusConfig, ConsensusSecrets, Host, LeaderSelectionMode, NodePublicKey,
+    },
 };
 use zksync_consensus_crypto::{Text, TextFmt};
 use zksync_consensus_executor as executor;
-use zksync_consensus_roles::{node, validator};
+use zksync_consensus_roles::{attester, node, validator};
 
 fn read_secret_text<T: TextFmt>(text: Option<&Secret<String>>) -> anyhow::Result<Option<T>> {
     text.map(|text| Text::new(text.expose_secret()).decode())
@@ -33,6 +35,7 @@ pub(super) struct GenesisSpec {
     pub(super) chain_id: validator::ChainId,
     pub(super) protocol_version: validator::ProtocolVersion,
     pub(super) validators: validator::Committee,
+    pub(super) attesters: Option<attester::Committee>,
     pub(super) leader_selection: validator::LeaderSelectionMode,
 }
 
@@ -42,6 +45,7 @@ impl GenesisSpec {
             chain_id: g.chain_id,
             protocol_version: g.protocol_version,
             validators: g.validators.clone(),
+            attesters: g.attesters.clone(),
             leader_selection: g.leader_selection.clone(),
         }
     }
@@ -59,13 +63,43 @@ impl GenesisSpec {
             })
             .collect::<anyhow::Result<_>>()
             .context("validators")?;
+        let validators = validator::Committee::new(validators).context("validators")?;
+        let leader: validator::PublicKey = Text::new(&x.leader.0).decode().context("leader")?;
+        anyhow::ensure!(
+            validators.contains(&leader),
+            "leader is not a member of the validator committee"
+        );
+
+        let attesters: Vec<_> = x
+            .attesters
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                Ok(attester::WeightedAttester {
+                    key: Text::new(&v.key.0).decode().context("key").context(i)?,
+                    weight: v.weight,
+                })
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("attesters")?;
+        let attesters = if attesters.is_empty() {
+            None
+        } else {
+            Some(attester::Committee::new(attesters).context("attesters")?)
+        };
+
+        let leader_selection = match x.leader_selection {
+            LeaderSelectionMode::Sticky => validator::LeaderSelectionMode::Sticky(leader),
+            LeaderSelectionMode::RoundRobin => validator::LeaderSelectionMode::RoundRobin,
+            LeaderSelectionMode::Weighted => validator::LeaderSelectionMode::Weighted,
+        };
+
         Ok(Self {
             chain_id: validator::ChainId(x.chain_id.as_u64()),
             protocol_version: validator::ProtocolVersion(x.protocol_version.0),
-            leader_selection: validator::LeaderSelectionMode::Sticky(
-                Text::new(&x.leader.0).decode().context("leader")?,
-            ),
-            validators: validator::Committee::new(validators).context("validators")?,
+            leader_selection,
+            validators,
+            attesters,
         })
     }
 }
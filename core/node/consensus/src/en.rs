@@ -4,7 +4,7 @@ use zksync_consensus_executor as executor;
 use zksync_consensus_roles::validator;
 use zksync_consensus_storage::{BatchStore, BlockStore};
 use zksync_node_sync::{
-    fetcher::FetchedBlock, sync_action::ActionQueueSender, MainNodeClient, SyncState,
+    fetcher::FetchedBlock, sync_action::ActionQueueSender, MainNodeClient, SyncSource, SyncState,
 };
 use zksync_types::L2BlockNumber;
 use zksync_web3_decl::client::{DynClient, L2};
@@ -17,14 +17,21 @@ pub(super) struct EN {
     pub(super) pool: ConnectionPool,
     pub(super) sync_state: SyncState,
     pub(super) client: Box<DynClient<L2>>,
+    /// Source of L2 blocks fetched using JSON RPC. Usually, it's the main node itself.
+    pub(super) source: Box<dyn SyncSource>,
 }
 
 impl EN {
     /// Task running a consensus node for the external node.
-    /// It may be a validator, but it cannot be a leader (cannot propose blocks).
+    /// It may be a validator, but it cannot propose new blocks. With a rotating leader selection mode,
+    /// it leads some of the views, in which it can only repropose blocks it has already received
+    /// from the main node.
     ///
     /// NOTE: Before starting the consensus node if fetches all the blocks
-    /// older than consensus genesis from the main node using json RPC.
+    /// older than consensus genesis from the main node using json RPC,
+    /// unless `cfg.require_certificates` is set. In the latter case, the node
+    /// only accepts certified blocks, so its state has to start at or after
+    /// the first block of consensus genesis.
     pub async fn run(
         self,
         ctx: &ctx::Ctx,
@@ -48,9 +55,32 @@ impl EN {
                 .wrap("new_payload_queue()")?;
             drop(conn);
 
-            // Fetch blocks before the genesis.
-            self.fetch_blocks(ctx, &mut payload_queue, Some(genesis.first_block))
-                .await?;
+            if cfg.require_certificates {
+                // Blocks before the genesis don't have certificates.
+                let next = payload_queue.next();
+                if next < genesis.first_block {
+                    return Err(anyhow::format_err!(
+                        "next block {next:?} precedes consensus genesis (first block {:?}), \
+                         but only certified blocks are allowed",
+                        genesis.first_block
+                    )
+                    .into());
+                }
+                tracing::info!("Only certified blocks will be accepted");
+            } else {
+                // Fetch blocks before the genesis.
+                self.fetch_blocks(ctx, &mut payload_queue, Some(genesis.first_block))
+                    .await?;
+            }
+            // Validator committee may change on a hard fork; the node runs
+            // as a full node if it's not a member of the current committee.
+            let mut validator_key = config::validator_key(&secrets).context("validator_key")?;
+            if let Some(key) = &validator_key {
+                if !genesis.validators.contains(&key.public()) {
+                    tracing::warn!("Validator key is not in the committee; running as a full node");
+                    validator_key = None;
+                }
+            }
             // Monitor the genesis of the main node.
             // If it changes, it means that a hard fork occurred and we need to reset the consensus state.
             s.spawn_bg::<()>(async {
@@ -88,13 +118,11 @@ impl EN {
                 block_store,
                 batch_store,
                 attester: None,
-                validator: config::validator_key(&secrets)
-                    .context("validator_key")?
-                    .map(|key| executor::Validator {
-                        key,
-                        replica_store: Box::new(store.clone()),
-                        payload_manager: Box::new(store.clone()),
-                    }),
+                validator: validator_key.map(|key| executor::Validator {
+                    key,
+                    replica_store: Box::new(store.clone()),
+                    payload_manager: Box::new(store.clone()),
+                }),
             };
             executor.run(ctx).await?;
             Ok(())
@@ -132,19 +160,19 @@ impl EN {
         }
     }
 
-    /// Periodically fetches the head of the main node
+    /// Periodically fetches the head of the sync source
     /// and updates `SyncState` accordingly.
     async fn fetch_state_loop(&self, ctx: &ctx::Ctx) -> ctx::Result<()> {
         const DELAY_INTERVAL: time::Duration = time::Duration::milliseconds(500);
         const RETRY_INTERVAL: time::Duration = time::Duration::seconds(5);
         loop {
-            match ctx.wait(self.client.fetch_l2_block_number()).await? {
+            match ctx.wait(self.source.fetch_l2_block_number()).await? {
                 Ok(head) => {
                     self.sync_state.set_main_node_block(head);
                     ctx.sleep(DELAY_INTERVAL).await?;
                 }
                 Err(err) => {
-                    tracing::warn!("main_node_client.fetch_l2_block_number(): {err}");
+                    tracing::warn!("source.fetch_l2_block_number(): {err}");
                     ctx.sleep(RETRY_INTERVAL).await?;
                 }
             }
@@ -161,18 +189,18 @@ impl EN {
         Ok(zksync_protobuf::serde::deserialize(&genesis.0).context("deserialize(genesis)")?)
     }
 
-    /// Fetches (with retries) the given block from the main node.
+    /// Fetches (with retries) the given block from the sync source.
     async fn fetch_block(&self, ctx: &ctx::Ctx, n: L2BlockNumber) -> ctx::Result<FetchedBlock> {
         const RETRY_INTERVAL: time::Duration = time::Duration::seconds(5);
 
         loop {
-            let res = ctx.wait(self.client.fetch_l2_block(n, true)).await?;
+            let res = ctx.wait(self.source.fetch_l2_block(n)).await?;
             match res {
                 Ok(Some(block)) => return Ok(block.try_into()?),
                 Ok(None) => {}
                 Err(err) if err.is_transient() => {}
                 Err(err) => {
-                    return Err(anyhow::format_err!("client.fetch_l2_block({}): {err}", n).into());
+                    return Err(anyhow::format_err!("source.fetch_l2_block({}): {err}", n).into());
                 }
             }
             ctx.sleep(RETRY_INTERVAL).await?;
@@ -7,7 +7,7 @@
 use zksync_concurrency::ctx;
 use zksync_config::configs::consensus::{ConsensusConfig, ConsensusSecrets};
 use zksync_dal::Core;
-use zksync_node_sync::{sync_action::ActionQueueSender, SyncState};
+use zksync_node_sync::{sync_action::ActionQueueSender, SyncSource, SyncState};
 use zksync_web3_decl::client::{DynClient, L2};
 
 use super::{en, mn, storage::ConnectionPool};
@@ -33,18 +33,24 @@ pub async fn run_main_node(
 /// Runs the consensus node for the external node.
 /// If `cfg` is `None`, it will just fetch blocks from the main node
 /// using JSON RPC, without starting the consensus node.
+/// If `sync_source` is `None`, blocks are fetched from the main node; otherwise, they are fetched
+/// from the specified source (e.g., an L2 block archive).
 pub async fn run_external_node(
     ctx: &ctx::Ctx,
     cfg: Option<(ConsensusConfig, ConsensusSecrets)>,
     pool: zksync_dal::ConnectionPool<Core>,
     sync_state: SyncState,
     main_node_client: Box<DynClient<L2>>,
+    sync_source: Option<Box<dyn SyncSource>>,
     actions: ActionQueueSender,
 ) -> anyhow::Result<()> {
+    let client = main_node_client.for_component("block_fetcher");
+    let source = sync_source.unwrap_or_else(|| Box::new(client.clone()));
     let en = en::EN {
         pool: ConnectionPool(pool),
         sync_state: sync_state.clone(),
-        client: main_node_client.for_component("block_fetcher"),
+        client,
+        source,
     };
     let res = match cfg {
         Some((cfg, secrets)) => en.run(ctx, actions, cfg, secrets).await,
@@ -11,8 +11,14 @@ use crate::{
 };
 
 /// Task running a consensus validator for the main node.
-/// Main node is currently the only leader of the consensus - i.e. it proposes all the
-/// L2 blocks (generated by `Statekeeper`).
+/// Main node is the only node producing L2 blocks (generated by `Statekeeper`), so it proposes
+/// all new blocks. With the sticky leader selection mode, it has to be the leader of all views;
+/// with rotating modes, it only has to be a member of the validator committee, and views led by
+/// other validators can finalize blocks proposed by the main node in earlier views. Either way,
+/// no new blocks are produced while the main node is down.
+///
+/// The node doesn't run an attester: an attester committee in genesis is only recorded,
+/// and L1 batches are not attested.
 pub async fn run_main_node(
     ctx: &ctx::Ctx,
     cfg: ConsensusConfig,
@@ -43,11 +49,23 @@ pub async fn run_main_node(
             .wrap("BlockStore::new()")?;
         s.spawn_bg(runner.run(ctx));
 
-        anyhow::ensure!(
-            block_store.genesis().leader_selection
-                == validator::LeaderSelectionMode::Sticky(validator_key.public()),
-            "unsupported leader selection mode - main node has to be the leader"
-        );
+        let genesis = block_store.genesis();
+        if let validator::LeaderSelectionMode::Sticky(leader) = &genesis.leader_selection {
+            anyhow::ensure!(
+                *leader == validator_key.public(),
+                "unsupported leader selection mode - main node has to be the sticky leader"
+            );
+        } else {
+            anyhow::ensure!(
+                genesis.validators.contains(&validator_key.public()),
+                "main node has to be a member of the validator committee"
+            );
+        }
+        if genesis.attesters.is_some() {
+            tracing::warn!(
+                "Genesis defines an attester committee, but L1 batch attestation is not run yet"
+            );
+        }
 
         // Dummy batch store - we don't gossip batches yet, but we need one anyway.
         let (batch_store, runner) = BatchStore::new(ctx, Box::new(store.clone()))
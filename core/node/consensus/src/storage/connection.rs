@@ -222,7 +222,7 @@ impl<'a> Connection<'a> {
 
             protocol_version: spec.protocol_version,
             validators: spec.validators.clone(),
-            attesters: None,
+            attesters: spec.attesters.clone(),
             leader_selection: spec.leader_selection.clone(),
         }
         .with_hash();
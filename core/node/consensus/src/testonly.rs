@@ -100,8 +100,11 @@ pub(super) fn config(cfg: &network::Config) -> (config::ConsensusConfig, config:
                     weight: 1,
                 }],
                 leader: config::ValidatorPublicKey(key.public().encode()),
+                attesters: vec![],
+                leader_selection: config::LeaderSelectionMode::Sticky,
             }),
             rpc: None,
+            require_certificates: false,
         },
         config::ConsensusSecrets {
             node_key: Some(config::NodeSecretKey(cfg.gossip.key.encode().into())),
@@ -366,6 +369,7 @@ impl StateKeeper {
     ) -> anyhow::Result<()> {
         en::EN {
             pool: self.pool,
+            source: Box::new(client.clone()),
             client,
             sync_state: self.sync_state.clone(),
         }
@@ -381,8 +385,20 @@ impl StateKeeper {
         cfg: &network::Config,
     ) -> anyhow::Result<()> {
         let (cfg, secrets) = config(cfg);
+        self.run_consensus_with(ctx, client, cfg, secrets).await
+    }
+
+    /// Same as `run_consensus`, but with an explicit node config.
+    pub async fn run_consensus_with(
+        self,
+        ctx: &ctx::Ctx,
+        client: Box<DynClient<L2>>,
+        cfg: config::ConsensusConfig,
+        secrets: config::ConsensusSecrets,
+    ) -> anyhow::Result<()> {
         en::EN {
             pool: self.pool,
+            source: Box::new(client.clone()),
             client,
             sync_state: self.sync_state.clone(),
         }
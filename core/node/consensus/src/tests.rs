@@ -3,7 +3,9 @@ use storage::Store;
 use test_casing::{test_casing, Product};
 use tracing::Instrument as _;
 use zksync_concurrency::{ctx, error::Wrap, scope};
-use zksync_config::configs::consensus::{ValidatorPublicKey, WeightedValidator};
+use zksync_config::configs::consensus::{
+    LeaderSelectionMode, ValidatorPublicKey, WeightedValidator,
+};
 use zksync_consensus_crypto::TextFmt as _;
 use zksync_consensus_network::testonly::{new_configs, new_fullnode};
 use zksync_consensus_roles::{
@@ -18,6 +20,11 @@ use crate::{mn::run_main_node, storage::ConnectionPool};
 
 const VERSIONS: [ProtocolVersionId; 2] = [ProtocolVersionId::latest(), ProtocolVersionId::next()];
 const FROM_SNAPSHOT: [bool; 2] = [true, false];
+const LEADER_SELECTION_MODES: [LeaderSelectionMode; 3] = [
+    LeaderSelectionMode::Sticky,
+    LeaderSelectionMode::RoundRobin,
+    LeaderSelectionMode::Weighted,
+];
 
 #[test_casing(2, VERSIONS)]
 #[tokio::test]
@@ -397,6 +404,187 @@ async fn test_en_validators(from_snapshot: bool, version: ProtocolVersionId) {
     .unwrap();
 }
 
+// Test that the validator committee keeps certifying blocks when one of the
+// external node validators goes down, and that the validator catches up after restart.
+// With rotating leader selection modes, the stopped validator leads some of the views.
+#[test_casing(6, Product((LEADER_SELECTION_MODES, VERSIONS)))]
+#[tokio::test]
+async fn test_en_validators_failover(mode: LeaderSelectionMode, version: ProtocolVersionId) {
+    // With 6 validators of equal weight, the committee tolerates 1 faulty replica.
+    const NODES: usize = 6;
+
+    zksync_concurrency::testonly::abort_on_panic();
+    let ctx = &ctx::test_root(&ctx::AffineClock::new(10.));
+    let rng = &mut ctx.rng();
+    let setup = Setup::new(rng, NODES);
+    let cfgs = new_configs(rng, &setup, 1);
+
+    scope::run!(ctx, |ctx, s| async {
+        let main_node_pool = ConnectionPool::test(false, version).await;
+        let (mut main_node, runner) =
+            testonly::StateKeeper::new(ctx, main_node_pool.clone()).await?;
+        s.spawn_bg(async {
+            runner
+                .run(ctx)
+                .instrument(tracing::info_span!("main_node"))
+                .await
+                .context("main_node")
+        });
+        // API server needs at least 1 L1 batch to start.
+        main_node.seal_batch().await;
+        main_node.connect(ctx).await?;
+
+        tracing::info!("Run main node with all nodes being validators.");
+        let (mut cfg, secrets) = testonly::config(&cfgs[0]);
+        let spec = cfg.genesis_spec.as_mut().unwrap();
+        spec.leader_selection = mode;
+        spec.validators = setup
+            .validator_keys
+            .iter()
+            .map(|k| WeightedValidator {
+                key: ValidatorPublicKey(k.public().encode()),
+                weight: 1,
+            })
+            .collect();
+        s.spawn_bg(run_main_node(ctx, cfg, secrets, main_node_pool.clone()));
+
+        tracing::info!("Run external nodes, except for the last one.");
+        for (i, cfg) in cfgs[1..NODES - 1].iter().enumerate() {
+            let i = ctx::NoCopy(i);
+            let pool = ConnectionPool::test(false, version).await;
+            let (ext_node, runner) = testonly::StateKeeper::new(ctx, pool.clone()).await?;
+            s.spawn_bg(async {
+                let i = i;
+                runner
+                    .run(ctx)
+                    .instrument(tracing::info_span!("en", i = *i))
+                    .await
+                    .with_context(|| format!("en{}", *i))
+            });
+            s.spawn_bg(ext_node.run_consensus(ctx, main_node.connect(ctx).await?, cfg));
+        }
+
+        tracing::info!("Run the last external node and stop it.");
+        let last_cfg = &cfgs[NODES - 1];
+        let last_pool = ConnectionPool::test(false, version).await;
+        scope::run!(ctx, |ctx, s| async {
+            let (ext_node, runner) = testonly::StateKeeper::new(ctx, last_pool.clone()).await?;
+            s.spawn_bg(runner.run(ctx));
+            s.spawn_bg(ext_node.run_consensus(ctx, main_node.connect(ctx).await?, last_cfg));
+            main_node.push_random_blocks(rng, 3).await;
+            last_pool
+                .wait_for_certificate(ctx, main_node.last_block())
+                .await?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        tracing::info!("Blocks should still be certified without the stopped validator.");
+        main_node.push_random_blocks(rng, 3).await;
+        main_node_pool
+            .wait_for_certificates_and_verify(ctx, main_node.last_block())
+            .await?;
+
+        tracing::info!("Restart the stopped validator and wait for it to catch up.");
+        let (ext_node, runner) = testonly::StateKeeper::new(ctx, last_pool.clone()).await?;
+        s.spawn_bg(runner.run(ctx));
+        s.spawn_bg(ext_node.run_consensus(ctx, main_node.connect(ctx).await?, last_cfg));
+        main_node.push_random_blocks(rng, 3).await;
+        let want = main_node_pool
+            .wait_for_certificates_and_verify(ctx, main_node.last_block())
+            .await?;
+        let got = last_pool
+            .wait_for_certificates_and_verify(ctx, main_node.last_block())
+            .await?;
+        assert_eq!(want, got);
+        Ok(())
+    })
+    .await
+    .unwrap();
+}
+
+// Test that an external node requiring certificates refuses to start
+// if its state precedes consensus genesis, and works once it has caught up.
+#[test_casing(2, VERSIONS)]
+#[tokio::test]
+async fn test_en_require_certificates(version: ProtocolVersionId) {
+    zksync_concurrency::testonly::abort_on_panic();
+    let ctx = &ctx::test_root(&ctx::AffineClock::new(10.));
+    let rng = &mut ctx.rng();
+    let setup = Setup::new(rng, 1);
+    let validator_cfg = new_configs(rng, &setup, 0)[0].clone();
+    let node_cfg = new_fullnode(rng, &validator_cfg);
+
+    scope::run!(ctx, |ctx, s| async {
+        tracing::info!("Spawn validator.");
+        let validator_pool = ConnectionPool::test(false, version).await;
+        let (mut validator, runner) =
+            testonly::StateKeeper::new(ctx, validator_pool.clone()).await?;
+        s.spawn_bg(runner.run(ctx));
+        tracing::info!("Generate a couple of blocks, before initializing consensus genesis.");
+        validator.push_random_blocks(rng, 5).await;
+        // API server needs at least 1 L1 batch to start.
+        validator.seal_batch().await;
+        let (cfg, secrets) = testonly::config(&validator_cfg);
+        s.spawn_bg(run_main_node(ctx, cfg, secrets, validator_pool.clone()));
+        let client = validator.connect(ctx).await?;
+
+        let node_pool = ConnectionPool::test(false, version).await;
+        let (mut node_cfg, node_secrets) = testonly::config(&node_cfg);
+        node_cfg.require_certificates = true;
+
+        tracing::info!("Node with blocks preceding genesis should refuse to start.");
+        scope::run!(ctx, |ctx, s| async {
+            let (node, runner) = testonly::StateKeeper::new(ctx, node_pool.clone()).await?;
+            s.spawn_bg(runner.run(ctx));
+            let res = node
+                .run_consensus_with(ctx, client.clone(), node_cfg.clone(), node_secrets.clone())
+                .await;
+            assert!(res.is_err(), "{res:?}");
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        tracing::info!("Catch up using the centralized fetcher.");
+        scope::run!(ctx, |ctx, s| async {
+            let (node, runner) = testonly::StateKeeper::new(ctx, node_pool.clone()).await?;
+            s.spawn_bg(runner.run(ctx));
+            s.spawn_bg(node.run_fetcher(ctx, client.clone()));
+            validator.push_random_blocks(rng, 3).await;
+            node_pool
+                .wait_for_payload(ctx, validator.last_block())
+                .await?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        tracing::info!("Node should now sync certified blocks only.");
+        scope::run!(ctx, |ctx, s| async {
+            let (node, runner) = testonly::StateKeeper::new(ctx, node_pool.clone()).await?;
+            s.spawn_bg(runner.run(ctx));
+            s.spawn_bg(node.run_consensus_with(
+                ctx,
+                client.clone(),
+                node_cfg.clone(),
+                node_secrets.clone(),
+            ));
+            validator.push_random_blocks(rng, 3).await;
+            node_pool
+                .wait_for_certificate(ctx, validator.last_block())
+                .await?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        Ok(())
+    })
+    .await
+    .unwrap();
+}
+
 // Test fetcher back filling missing certs.
 #[test_casing(4, Product((FROM_SNAPSHOT,VERSIONS)))]
 #[tokio::test]
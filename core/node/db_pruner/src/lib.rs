@@ -6,17 +6,22 @@ use std::{
 };
 
 use anyhow::Context as _;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
-use zksync_dal::{pruning_dal::PruningInfo, Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    pruning_dal::{HardPruningOptions, HardPruningStats, PruningInfo, RetainedDataClass},
+    Connection, ConnectionPool, Core, CoreDal,
+};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_types::{L1BatchNumber, L2BlockNumber};
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber};
 
+pub use self::prune_conditions::{L1BatchAnchoredOnMintlayerCondition, PruneCondition};
 use self::{
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
         ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
-        NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition, PruneCondition,
+        NextL1BatchHasMetadataCondition, NextL1BatchWasExecutedCondition,
     },
 };
 
@@ -36,6 +41,49 @@ pub struct DbPrunerConfig {
     /// Minimum age of an L1 batch in order for it to be eligible for pruning. Setting this to zero
     /// will effectively disable this pruning criterion.
     pub minimum_l1_batch_age: Duration,
+    /// Retention policy for data that may outlive pruned L1 batches.
+    pub retention: RetentionPolicy,
+    /// If set, L1 batches are only pruned after they are anchored on Mintlayer.
+    pub wait_for_mintlayer_anchoring: bool,
+    /// If set, the pruner doesn't modify the database and only reports what would be pruned.
+    pub dry_run: bool,
+}
+
+/// Retention policy for data classes that may outlive pruned L1 batches. Storage logs and blocks
+/// are always pruned together with their L1 batch.
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    /// Retention period for events. If not set, events are pruned together with their L1 batch.
+    pub events: Option<Duration>,
+    /// Retention period for user L2-to-L1 logs.
+    pub l2_to_l1_logs: Option<Duration>,
+    /// Retention period for call traces.
+    pub call_traces: Option<Duration>,
+    /// Retention period for transaction input, data and execution info.
+    pub transaction_data: Option<Duration>,
+    /// Data related to these addresses is never pruned.
+    pub retained_addresses: Vec<Address>,
+}
+
+impl RetentionPolicy {
+    fn retention_period(&self, class: RetainedDataClass) -> Option<Duration> {
+        match class {
+            RetainedDataClass::Events => self.events,
+            RetainedDataClass::L2ToL1Logs => self.l2_to_l1_logs,
+            RetainedDataClass::CallTraces => self.call_traces,
+            RetainedDataClass::TransactionData => self.transaction_data,
+        }
+    }
+
+    fn hard_pruning_options(&self) -> HardPruningOptions {
+        HardPruningOptions {
+            retained_classes: RetainedDataClass::ALL
+                .into_iter()
+                .filter(|&class| self.retention_period(class).is_some())
+                .collect(),
+            retained_addresses: self.retained_addresses.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -50,6 +98,40 @@ struct DbPrunerHealth {
     last_hard_pruned_l2_block: Option<L2BlockNumber>,
 }
 
+/// Report produced by the pruner in the dry-run mode.
+#[derive(Debug, Serialize)]
+struct DryRunReport {
+    dry_run: bool,
+    last_prunable_l1_batch: L1BatchNumber,
+    last_prunable_l2_block: L2BlockNumber,
+    deleted_l1_batches: u64,
+    deleted_l2_blocks: u64,
+    deleted_storage_logs: u64,
+    deleted_events: u64,
+    deleted_call_traces: u64,
+    deleted_l2_to_l1_logs: u64,
+}
+
+impl DryRunReport {
+    fn new(
+        last_prunable_l1_batch: L1BatchNumber,
+        last_prunable_l2_block: L2BlockNumber,
+        stats: HardPruningStats,
+    ) -> Self {
+        Self {
+            dry_run: true,
+            last_prunable_l1_batch,
+            last_prunable_l2_block,
+            deleted_l1_batches: stats.deleted_l1_batches,
+            deleted_l2_blocks: stats.deleted_l2_blocks,
+            deleted_storage_logs: stats.deleted_storage_logs,
+            deleted_events: stats.deleted_events,
+            deleted_call_traces: stats.deleted_call_traces,
+            deleted_l2_to_l1_logs: stats.deleted_l2_to_l1_logs,
+        }
+    }
+}
+
 impl From<PruningInfo> for DbPrunerHealth {
     fn from(info: PruningInfo) -> Self {
         Self {
@@ -79,6 +161,7 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    hard_pruning_options: HardPruningOptions,
 }
 
 impl DbPruner {
@@ -104,16 +187,29 @@ impl DbPruner {
                 pool: connection_pool.clone(),
             }));
         }
+        if config.wait_for_mintlayer_anchoring {
+            conditions.push(Arc::new(L1BatchAnchoredOnMintlayerCondition::new(
+                connection_pool.clone(),
+            )));
+        }
 
         Self::with_conditions(config, connection_pool, conditions)
     }
 
+    /// Adds an additional condition that must hold for an L1 batch to be pruned.
+    #[must_use]
+    pub fn with_condition(mut self, condition: Arc<dyn PruneCondition>) -> Self {
+        self.prune_conditions.push(condition);
+        self
+    }
+
     fn with_conditions(
         config: DbPrunerConfig,
         connection_pool: ConnectionPool<Core>,
         prune_conditions: Vec<Arc<dyn PruneCondition>>,
     ) -> Self {
         Self {
+            hard_pruning_options: config.retention.hard_pruning_options(),
             config,
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
@@ -244,9 +340,10 @@ impl DbPruner {
 
         let mut dal = transaction.pruning_dal();
         let stats = tokio::select! {
-            result = dal.hard_prune_batches_range(
+            result = dal.hard_prune_batches_range_with_options(
                 last_soft_pruned_l1_batch,
                 last_soft_pruned_l2_block,
+                &self.hard_pruning_options,
             ) => result?,
 
             _ = stop_receiver.changed() => {
@@ -301,6 +398,136 @@ impl DbPruner {
         self.hard_prune(&mut storage, stop_receiver).await
     }
 
+    /// Prunes retained data whose retention period has expired. The first L2 block that may contain not yet pruned data
+    /// is persisted for each data class, so that pruning continues from where it stopped after a restart.
+    async fn prune_expired_data(&self) -> anyhow::Result<()> {
+        let now = Utc::now().timestamp() as u64;
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        for class in RetainedDataClass::ALL {
+            let Some(retention_period) = self.config.retention.retention_period(class) else {
+                continue;
+            };
+            let max_timestamp = now.saturating_sub(retention_period.as_secs());
+            let Some(last_l2_block) = storage
+                .pruning_dal()
+                .get_last_hard_pruned_l2_block_before(max_timestamp)
+                .await?
+            else {
+                continue;
+            };
+
+            let mut transaction = storage.start_transaction().await?;
+            let next_l2_block = transaction
+                .pruning_dal()
+                .get_next_l2_block_with_retained_data(class)
+                .await?;
+            if next_l2_block > last_l2_block {
+                continue;
+            }
+
+            let l2_blocks = next_l2_block..=last_l2_block;
+            let pruned_count = transaction
+                .pruning_dal()
+                .prune_retained_data(
+                    class,
+                    l2_blocks.clone(),
+                    &self.config.retention.retained_addresses,
+                )
+                .await?;
+            transaction
+                .pruning_dal()
+                .set_next_l2_block_with_retained_data(class, last_l2_block + 1)
+                .await?;
+            transaction.commit().await?;
+
+            METRICS.observe_expired_data_pruning(class, pruned_count);
+            tracing::info!(
+                "Pruned {pruned_count} retained entries of class {class:?} in L2 blocks {l2_blocks:?}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Determines the last L1 batch that could be pruned right now and reports what hard pruning
+    /// would delete, estimated using read-only queries. Does nothing if the last prunable
+    /// L1 batch is the same as `last_reported_l1_batch`.
+    async fn run_dry_iteration(
+        &self,
+        last_reported_l1_batch: &mut Option<L1BatchNumber>,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let mut next_l1_batch = pruning_info
+            .last_soft_pruned_l1_batch
+            .unwrap_or(L1BatchNumber(0))
+            + self.config.pruned_batch_chunk_size;
+        let mut last_prunable = None;
+        while self.is_l1_batch_prunable(next_l1_batch).await {
+            let Some((_, last_l2_block)) = storage
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(next_l1_batch)
+                .await?
+            else {
+                break;
+            };
+            last_prunable = Some((next_l1_batch, last_l2_block));
+            next_l1_batch += self.config.pruned_batch_chunk_size;
+        }
+
+        let Some((last_prunable_l1_batch, last_prunable_l2_block)) = last_prunable else {
+            if last_reported_l1_batch.is_none() {
+                self.update_health(pruning_info);
+            }
+            return Ok(());
+        };
+        if *last_reported_l1_batch == Some(last_prunable_l1_batch) {
+            return Ok(());
+        }
+
+        let stats = storage
+            .pruning_dal()
+            .estimate_hard_pruning(
+                last_prunable_l1_batch,
+                last_prunable_l2_block,
+                &self.hard_pruning_options,
+            )
+            .await?;
+
+        let report = DryRunReport::new(last_prunable_l1_batch, last_prunable_l2_block, stats);
+        tracing::info!(
+            "Dry run: pruning L1 batches up to {last_prunable_l1_batch} would result in {report:?}"
+        );
+        let health = Health::from(HealthStatus::Ready).with_details(report);
+        self.health_updater.update(health);
+        *last_reported_l1_batch = Some(last_prunable_l1_batch);
+        Ok(())
+    }
+
+    async fn run_dry(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let next_iteration_delay = self.config.removal_delay / 2;
+        let mut last_reported_l1_batch = None;
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.run_dry_iteration(&mut last_reported_l1_batch).await {
+                tracing::warn!(
+                    "Pruning dry run error, retrying in {next_iteration_delay:?}, error was: {err:?}"
+                );
+                let health = Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+                    "error": err.to_string(),
+                }));
+                self.health_updater.update(health);
+            }
+
+            if tokio::time::timeout(next_iteration_delay, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, shutting down DB pruning dry run");
+        Ok(())
+    }
+
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let next_iteration_delay = self.config.removal_delay / 2;
         tracing::info!(
@@ -311,11 +538,17 @@ impl DbPruner {
                 .map(ToString::to_string)
                 .collect::<Vec<_>>()
         );
+        if self.config.dry_run {
+            return self.run_dry(stop_receiver).await;
+        }
 
         while !*stop_receiver.borrow_and_update() {
             if let Err(err) = self.update_l1_batches_metric().await {
                 tracing::warn!("Error updating DB pruning metrics: {err:?}");
             }
+            if let Err(err) = self.prune_expired_data().await {
+                tracing::warn!("Error pruning expired retained data: {err:?}");
+            }
 
             let should_sleep = match self.run_single_iteration(&mut stop_receiver).await {
                 Err(err) => {
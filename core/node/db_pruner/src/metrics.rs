@@ -3,7 +3,7 @@ use std::time::Duration;
 use vise::{
     Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
 };
-use zksync_dal::pruning_dal::HardPruningStats;
+use zksync_dal::pruning_dal::{HardPruningStats, RetainedDataClass};
 
 use crate::prune_conditions::PruneCondition;
 
@@ -24,6 +24,18 @@ enum PrunedEntityType {
     Event,
     L2ToL1Log,
     CallTrace,
+    TransactionData,
+}
+
+impl From<RetainedDataClass> for PrunedEntityType {
+    fn from(class: RetainedDataClass) -> Self {
+        match class {
+            RetainedDataClass::Events => Self::Event,
+            RetainedDataClass::L2ToL1Logs => Self::L2ToL1Log,
+            RetainedDataClass::CallTraces => Self::CallTrace,
+            RetainedDataClass::TransactionData => Self::TransactionData,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
@@ -56,6 +68,9 @@ pub(super) struct DbPrunerMetrics {
     /// Number of entities deleted during a single hard pruning iteration, grouped by entity type.
     #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
+    /// Number of retained entities pruned after their retention period has expired, grouped by entity type.
+    #[metrics(buckets = ENTITY_COUNT_BUCKETS)]
+    deleted_expired_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
 }
@@ -84,6 +99,10 @@ impl DbPrunerMetrics {
         self.deleted_entities[&PrunedEntityType::CallTrace].observe(deleted_call_traces);
     }
 
+    pub fn observe_expired_data_pruning(&self, class: RetainedDataClass, pruned_count: u64) {
+        self.deleted_expired_entities[&class.into()].observe(pruned_count);
+    }
+
     pub fn observe_condition(&self, condition: &dyn PruneCondition, outcome: ConditionOutcome) {
         let labels = ConditionOutcomeLabels {
             condition: condition.metric_label(),
@@ -5,10 +5,14 @@ use chrono::Utc;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_types::L1BatchNumber;
 
+/// Condition that must hold for an L1 batch to be pruned. All conditions are checked for each pruned chunk
+/// of L1 batches; the batch is pruned only if all conditions return `Ok(true)`.
 #[async_trait]
-pub(crate) trait PruneCondition: fmt::Debug + fmt::Display + Send + Sync + 'static {
+pub trait PruneCondition: fmt::Debug + fmt::Display + Send + Sync + 'static {
+    /// Label used in metrics for this condition.
     fn metric_label(&self) -> &'static str;
 
+    /// Checks whether the specified L1 batch (and all preceding batches) can be pruned.
     async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool>;
 }
 
@@ -171,3 +175,38 @@ impl PruneCondition for ConsistencyCheckerProcessedBatch {
         Ok(l1_batch_number <= last_processed_l1_batch)
     }
 }
+
+/// Checks that the L1 batch was committed in an operation anchored on Mintlayer. Relies on the data persisted
+/// by the Mintlayer anchoring in the Ethereum sender, so it only makes sense on the node performing the anchoring.
+#[derive(Debug)]
+pub struct L1BatchAnchoredOnMintlayerCondition {
+    pool: ConnectionPool<Core>,
+}
+
+impl L1BatchAnchoredOnMintlayerCondition {
+    pub fn new(pool: ConnectionPool<Core>) -> Self {
+        Self { pool }
+    }
+}
+
+impl fmt::Display for L1BatchAnchoredOnMintlayerCondition {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("L1 batch is anchored on Mintlayer")
+    }
+}
+
+#[async_trait]
+impl PruneCondition for L1BatchAnchoredOnMintlayerCondition {
+    fn metric_label(&self) -> &'static str {
+        "l1_batch_anchored_on_mintlayer"
+    }
+
+    async fn is_batch_prunable(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("db_pruner").await?;
+        let last_anchored_l1_batch = storage
+            .data_availability_dal()
+            .get_last_mintlayer_anchored_l1_batch()
+            .await?;
+        Ok(last_anchored_l1_batch.is_some_and(|last| l1_batch_number <= last))
+    }
+}
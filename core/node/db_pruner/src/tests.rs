@@ -1,9 +1,14 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, ops};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use test_log::test;
-use zksync_dal::pruning_dal::PruningInfo;
+use zksync_dal::{
+    data_availability_dal::{
+        OperationStatus, OperationType, PendingIpfsOperation, PendingMintlayerBatch,
+    },
+    pruning_dal::PruningInfo,
+};
 use zksync_db_connection::connection::Connection;
 use zksync_health_check::CheckHealth;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
@@ -12,8 +17,8 @@ use zksync_node_test_utils::{
     l1_batch_metadata_to_commitment_artifacts,
 };
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, block::L2BlockHeader, Address, L2BlockNumber,
-    ProtocolVersion, H256,
+    aggregated_operations::AggregatedActionType, block::L2BlockHeader, tx::IncludedTxLocation,
+    Address, L2BlockNumber, ProtocolVersion, VmEvent, H256,
 };
 
 use super::*;
@@ -79,6 +84,9 @@ async fn is_l1_batch_prunable_works() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 1,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         ConnectionPool::test_pool().await,
         vec![failing_check, other_failing_check],
@@ -152,6 +160,9 @@ async fn hard_pruning_ignores_conditions_checks() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![nothing_prunable_check],
@@ -191,6 +202,9 @@ async fn pruner_catches_up_with_hard_pruning_up_to_soft_pruning_boundary_ignorin
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 5,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -239,6 +253,9 @@ async fn unconstrained_pruner_with_fresh_database() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -289,6 +306,9 @@ async fn pruning_blocked_after_first_chunk() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![first_chunk_prunable_check],
@@ -339,6 +359,9 @@ async fn pruner_is_resistant_to_errors() {
             removal_delay: Duration::ZERO,
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![erroneous_condition],
@@ -508,6 +531,9 @@ async fn pruner_with_real_conditions() {
         removal_delay: Duration::from_millis(10), // non-zero to not have a tight loop in `DbPruner::run()`
         pruned_batch_chunk_size: 1,
         minimum_l1_batch_age: Duration::ZERO,
+        retention: RetentionPolicy::default(),
+        wait_for_mintlayer_anchoring: false,
+        dry_run: false,
     };
     let pruner = DbPruner::new(config, pool.clone());
     let mut health_check = pruner.health_check();
@@ -564,6 +590,9 @@ async fn pruning_iteration_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -593,6 +622,9 @@ async fn pruner_timely_shuts_down() {
             removal_delay: Duration::MAX, // intentionally chosen so that pruning iterations stuck
             pruned_batch_chunk_size: 3,
             minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         },
         pool.clone(),
         vec![], //No checks, so every batch is prunable
@@ -608,3 +640,199 @@ async fn pruner_timely_shuts_down() {
     stop_sender.send_replace(true);
     pruner_handle.await.unwrap().unwrap();
 }
+
+async fn insert_events(
+    conn: &mut Connection<'_, Core>,
+    l2_blocks_count: u32,
+    emitters: &[Address],
+) {
+    let events: Vec<_> = emitters
+        .iter()
+        .enumerate()
+        .map(|(i, &address)| VmEvent {
+            location: (L1BatchNumber(0), i as u32),
+            address,
+            indexed_topics: vec![],
+            value: vec![],
+        })
+        .collect();
+    let location = IncludedTxLocation {
+        tx_hash: H256::repeat_byte(1),
+        tx_index_in_l2_block: 0,
+        tx_initiator_address: Address::repeat_byte(0xff),
+    };
+    for number in 0..l2_blocks_count {
+        conn.events_dal()
+            .save_events(
+                L2BlockNumber(number),
+                &[(location, events.iter().collect())],
+            )
+            .await
+            .unwrap();
+    }
+}
+
+async fn count_events(
+    conn: &mut Connection<'_, Core>,
+    l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+) -> u64 {
+    conn.pruning_dal()
+        .count_retained_data(RetainedDataClass::Events, l2_blocks, &[])
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn retained_data_is_pruned_after_retention_period() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+    let retained_address = Address::repeat_byte(1);
+    insert_events(&mut conn, 20, &[retained_address, Address::repeat_byte(2)]).await;
+
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy {
+                events: Some(Duration::from_secs(3_600)),
+                retained_addresses: vec![retained_address],
+                ..RetentionPolicy::default()
+            },
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
+        },
+        pool.clone(),
+        vec![], //No checks, so every batch is prunable
+    );
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    pruner
+        .run_single_iteration(&mut stop_receiver)
+        .await
+        .unwrap();
+    assert_eq!(
+        conn.pruning_dal()
+            .get_pruning_info()
+            .await
+            .unwrap()
+            .last_hard_pruned_l2_block,
+        Some(L2BlockNumber(7))
+    );
+    assert_eq!(
+        count_events(&mut conn, L2BlockNumber(0)..=L2BlockNumber(7)).await,
+        16
+    );
+
+    // All L2 blocks have zero timestamp, so the retention period has already expired.
+    pruner.prune_expired_data().await.unwrap();
+    assert_eq!(
+        conn.pruning_dal()
+            .get_next_l2_block_with_retained_data(RetainedDataClass::Events)
+            .await
+            .unwrap(),
+        L2BlockNumber(8)
+    );
+    // Only events emitted by the retained address should be left.
+    assert_eq!(
+        count_events(&mut conn, L2BlockNumber(0)..=L2BlockNumber(7)).await,
+        8
+    );
+    assert_eq!(
+        count_events(&mut conn, L2BlockNumber(8)..=L2BlockNumber(19)).await,
+        24
+    );
+}
+
+#[tokio::test]
+async fn dry_run_does_not_modify_database() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+    insert_events(&mut conn, 20, &[Address::repeat_byte(1)]).await;
+
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: true,
+        },
+        pool.clone(),
+        vec![], //No checks, so every batch is prunable
+    );
+    let health_check = pruner.health_check();
+
+    let mut last_reported_l1_batch = None;
+    pruner
+        .run_dry_iteration(&mut last_reported_l1_batch)
+        .await
+        .unwrap();
+    assert_eq!(last_reported_l1_batch, Some(L1BatchNumber(9)));
+
+    let health = health_check.check_health().await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+    let details = health.details().unwrap();
+    assert_eq!(details["dry_run"], true);
+    assert_eq!(details["last_prunable_l2_block"], 19);
+    assert_eq!(details["deleted_l2_blocks"], 20);
+    assert_eq!(details["deleted_events"], 20);
+
+    assert_eq!(
+        conn.pruning_dal().get_pruning_info().await.unwrap(),
+        PruningInfo::default()
+    );
+    assert_eq!(
+        count_events(&mut conn, L2BlockNumber(0)..=L2BlockNumber(19)).await,
+        20
+    );
+}
+
+#[tokio::test]
+async fn mintlayer_anchoring_condition() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let condition = L1BatchAnchoredOnMintlayerCondition::new(pool.clone());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(1)).await.unwrap());
+
+    let ipfs_hash = "QmTest".to_owned();
+    let operation = PendingIpfsOperation {
+        id: uuid::Uuid::new_v4(),
+        operation_type: OperationType::Commit,
+        data: vec![],
+        attempts: 1,
+        last_attempt: None,
+        created_at: chrono::Utc::now(),
+        status: OperationStatus::Completed,
+        ipfs_hash: Some(ipfs_hash.clone()),
+        requires_mintlayer: true,
+        l1_batch_range: Some(L1BatchNumber(1)..=L1BatchNumber(3)),
+    };
+    storage
+        .data_availability_dal()
+        .save_pending_operation(&operation)
+        .await
+        .unwrap();
+    let mut mintlayer_batch = PendingMintlayerBatch {
+        ipfs_hashes: vec![ipfs_hash],
+        ..PendingMintlayerBatch::new()
+    };
+    storage
+        .data_availability_dal()
+        .update_mintlayer_batch(&mintlayer_batch)
+        .await
+        .unwrap();
+    // The Mintlayer batch is not completed yet.
+    assert!(!condition.is_batch_prunable(L1BatchNumber(1)).await.unwrap());
+
+    mintlayer_batch.status = OperationStatus::Completed;
+    storage
+        .data_availability_dal()
+        .update_mintlayer_batch(&mintlayer_batch)
+        .await
+        .unwrap();
+    assert!(condition.is_batch_prunable(L1BatchNumber(3)).await.unwrap());
+    assert!(!condition.is_batch_prunable(L1BatchNumber(4)).await.unwrap());
+}
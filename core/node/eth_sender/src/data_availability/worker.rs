@@ -1,17 +1,17 @@
-use super::{
-    circuit_breaker::CircuitBreaker, error::DataAvailabilityError, metrics::DataAvailabilityMetrics,
-};
-use base64::Engine;
-use s3::Bucket;
-use std::{io::Cursor, sync::Arc, time::Instant};
-use tokio::sync::Mutex;
-use tokio::time::Duration;
+use std::{sync::Arc, time::Instant};
+
+use tokio::{sync::Mutex, time::Duration};
 use uuid::Uuid;
+use zksync_anchoring::{IpfsClient, MintlayerClient};
 use zksync_dal::{
     data_availability_dal::{OperationStatus, PendingIpfsOperation, PendingMintlayerBatch},
     Connection, ConnectionPool, Core, CoreDal,
 };
 
+use super::{
+    circuit_breaker::CircuitBreaker, error::DataAvailabilityError, metrics::DataAvailabilityMetrics,
+};
+
 #[derive(Debug, Clone)]
 pub struct WorkerConfig {
     pub ipfs_retry_base_delay: Duration,
@@ -30,6 +30,8 @@ pub struct DataAvailabilityWorker {
     config: WorkerConfig,
     pool: ConnectionPool<Core>,
     metrics: Arc<DataAvailabilityMetrics>,
+    ipfs: Arc<dyn IpfsClient>,
+    mintlayer: Arc<dyn MintlayerClient>,
     ipfs_circuit_breaker: Mutex<CircuitBreaker>,
     mintlayer_circuit_breaker: Mutex<CircuitBreaker>,
 }
@@ -39,6 +41,8 @@ impl DataAvailabilityWorker {
         config: WorkerConfig,
         pool: ConnectionPool<Core>,
         metrics: Arc<DataAvailabilityMetrics>,
+        ipfs: Arc<dyn IpfsClient>,
+        mintlayer: Arc<dyn MintlayerClient>,
     ) -> Self {
         Self {
             ipfs_circuit_breaker: Mutex::new(CircuitBreaker::new(5, Duration::from_secs(300))),
@@ -46,6 +50,8 @@ impl DataAvailabilityWorker {
             config,
             pool,
             metrics,
+            ipfs,
+            mintlayer,
         }
     }
 
@@ -113,7 +119,7 @@ impl DataAvailabilityWorker {
         let mut delay = self.config.ipfs_retry_base_delay;
 
         while op.attempts < self.config.ipfs_max_attempts {
-            match self.setup_and_upload_to_ipfs(&op.data).await {
+            match self.upload_to_ipfs(&op.data).await {
                 Ok(hash) => return Ok(hash),
                 Err(e) => {
                     op.attempts += 1;
@@ -135,122 +141,15 @@ impl DataAvailabilityWorker {
         ))
     }
 
-    async fn setup_and_upload_to_ipfs(&self, data: &[u8]) -> Result<String, DataAvailabilityError> {
-        let api_key = std::env::var("4EVERLAND_API_KEY")
-            .map_err(|_| DataAvailabilityError::ConfigError("4EVERLAND_API_KEY not set".into()))?;
-        let secret_key = std::env::var("4EVERLAND_SECRET_KEY").map_err(|_| {
-            DataAvailabilityError::ConfigError("4EVERLAND_SECRET_KEY not set".into())
-        })?;
-        let bucket_name = std::env::var("4EVERLAND_BUCKET_NAME").map_err(|_| {
-            DataAvailabilityError::ConfigError("4EVERLAND_BUCKET_NAME not set".into())
-        })?;
-
-        let credentials =
-            s3::creds::Credentials::new(Some(&api_key), Some(&secret_key), None, None, None)
-                .map_err(|e| DataAvailabilityError::ConfigError(e.to_string()))?;
-        let bucket = Bucket::new(
-            &bucket_name,
-            s3::Region::Custom {
-                region: "us-east-1".into(),
-                endpoint: "https://endpoint.4everland.co".into(),
-            },
-            credentials,
-        )
-        .map_err(|e| DataAvailabilityError::ConfigError(e.to_string()))?;
-
+    async fn upload_to_ipfs(&self, data: &[u8]) -> Result<String, DataAvailabilityError> {
         let doc_name = format!("op_{}", Uuid::new_v4());
-        let contents = Cursor::new(data.to_vec());
-
-        self.upload_to_ipfs(&bucket, &doc_name, contents).await
+        self.ipfs
+            .upload(&doc_name, data.to_vec())
+            .await
+            .map_err(|e| DataAvailabilityError::IPFSError(format!("{e:#}")))
     }
 
     pub async fn run(self) {
-        let mintlayer_rpc_url = std::env::var("ML_RPC_URL").expect("ML_RPC_URL not set");
-
-        let mintlayer_rpc_username = std::env::var("ML_RPC_USERNAME");
-        let mintlayer_rpc_password = std::env::var("ML_RPC_PASSWORD");
-        let mnemonic = std::env::var("ML_MNEMONIC");
-
-        let creds = match (mintlayer_rpc_username, mintlayer_rpc_password) {
-            (Ok(username), Ok(password)) => {
-                let creds = base64::engine::general_purpose::STANDARD
-                    .encode(format!("{username}:{password}"));
-                Some(format!("Basic {creds}"))
-            }
-            _ => None,
-        };
-
-        let client = reqwest::Client::new();
-        let headers = {
-            let mut headers = reqwest::header::HeaderMap::new();
-            headers.insert("Content-Type", "application/json".parse().unwrap());
-            if let Some(credentials) = creds {
-                headers.insert("Authorization", credentials.as_str().parse().unwrap());
-            }
-            headers
-        };
-
-        let payload = match mnemonic {
-            Ok(mnemonic) => serde_json::json!({
-                "method": "wallet_create",
-                "params": {
-                    "path": "/home/mintlayer/wallet.dat",
-                    "store_seed_phrase": true,
-                    "mnemonic": mnemonic
-                },
-                "jsonrpc": "2.0",
-                "id": 1,
-            }),
-            Err(_) => serde_json::json!({
-                "method": "wallet_create",
-                "params": {
-                    "path": "/home/mintlayer/wallet.dat",
-                    "store_seed_phrase": true
-                },
-                "jsonrpc": "2.0",
-                "id": 1,
-            }),
-        };
-
-        let _ = client
-            .post(&mintlayer_rpc_url)
-            .headers(headers.clone())
-            .json(&payload)
-            .send()
-            .await;
-
-        let payload = serde_json::json!({
-            "method": "wallet_open",
-            "params": {
-                "path": "/home/mintlayer/wallet.dat",
-            },
-            "jsonrpc": "2.0",
-            "id": 1,
-        });
-
-        let _ = client
-            .post(&mintlayer_rpc_url)
-            .headers(headers.clone())
-            .json(&payload)
-            .send()
-            .await;
-
-        let payload = serde_json::json!({
-            "method": "address_new",
-            "params": {
-                "account": 0,
-            },
-            "jsonrpc": "2.0",
-            "id": 1,
-        });
-
-        let _ = client
-            .post(&mintlayer_rpc_url)
-            .headers(headers)
-            .json(&payload)
-            .send()
-            .await;
-
         let self_arc = Arc::new(self);
 
         let cleanup_task = {
@@ -466,73 +365,15 @@ impl DataAvailabilityWorker {
         &self,
         ipfs_hashes: &[String],
     ) -> Result<String, DataAvailabilityError> {
-        let mintlayer_rpc_url = std::env::var("ML_RPC_URL")
-            .map_err(|_| DataAvailabilityError::ConfigError("ML_RPC_URL not set".into()))?;
-
-        let mintlayer_rpc_username = std::env::var("ML_RPC_USERNAME");
-        let mintlayer_rpc_password = std::env::var("ML_RPC_PASSWORD");
-
-        let creds = match (mintlayer_rpc_username, mintlayer_rpc_password) {
-            (Ok(username), Ok(password)) => {
-                let creds = base64::engine::general_purpose::STANDARD
-                    .encode(format!("{username}:{password}"));
-                Some(format!("Basic {creds}"))
-            }
-            _ => None,
-        };
-
-        let client = reqwest::Client::new();
-        let headers = {
-            let mut headers = reqwest::header::HeaderMap::new();
-            headers.insert("Content-Type", "application/json".parse().unwrap());
-            if let Some(credentials) = creds {
-                headers.insert("Authorization", credentials.as_str().parse().unwrap());
-            }
-            headers
-        };
-        let payload = serde_json::json!({
-            "method": "address_deposit_data",
-            "params": {
-                "data": hex::encode(ipfs_hashes.join(",")),
-                "account": 0,
-                "options": {},
-            },
-            "jsonrpc": "2.0",
-            "id": 1,
-        });
-
-        let response = client
-            .post(&mintlayer_rpc_url)
-            .headers(headers)
-            .json(&payload)
-            .send()
+        let tx_hash = self
+            .mintlayer
+            .deposit_data(ipfs_hashes.join(",").as_bytes())
             .await
-            .map_err(|e| DataAvailabilityError::MintlayerError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(DataAvailabilityError::MintlayerError(format!(
-                "Request failed with status: {}",
-                response.status()
-            )));
-        }
-
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| DataAvailabilityError::MintlayerError(e.to_string()))?;
-        let response_json: serde_json::Value = serde_json::from_str(&response_text)
-            .map_err(|e| DataAvailabilityError::MintlayerError(e.to_string()))?;
-
+            .map_err(|e| DataAvailabilityError::MintlayerError(format!("{e:#}")))?;
         tracing::info!(
-            "add root digest to mintlayer with L1 tx_info: {}",
-            serde_json::to_string(&response_json).unwrap()
+            "Anchored IPFS hashes {ipfs_hashes:?} on Mintlayer in transaction {tx_hash}"
         );
-        match response_json.get("result") {
-            Some(tx_hash) => Ok(tx_hash.as_str().unwrap_or("").to_string()),
-            None => Err(DataAvailabilityError::MintlayerError(
-                "No tx_hash in response".into(),
-            )),
-        }
+        Ok(tx_hash)
     }
 
     async fn queue_mintlayer_batch(
@@ -576,37 +417,4 @@ impl DataAvailabilityWorker {
 
         Ok(())
     }
-
-    async fn upload_to_ipfs(
-        &self,
-        bucket: &Bucket,
-        doc_name: &str,
-        mut contents: Cursor<Vec<u8>>,
-    ) -> Result<String, DataAvailabilityError> {
-        match bucket.put_object_stream(&mut contents, doc_name).await {
-            Ok(response) => {
-                if response.status_code() == 200 {
-                    match bucket.head_object(doc_name).await {
-                        Ok((head, _)) => {
-                            if let Some(metadata) = head.metadata {
-                                if let Some(hash) = metadata.get("ipfs-hash") {
-                                    return Ok(hash.clone());
-                                }
-                            }
-                            Err(DataAvailabilityError::IPFSError(
-                                "Missing IPFS hash in metadata".into(),
-                            ))
-                        }
-                        Err(e) => Err(DataAvailabilityError::IPFSError(e.to_string())),
-                    }
-                } else {
-                    Err(DataAvailabilityError::IPFSError(format!(
-                        "Upload failed with status: {}",
-                        response.status_code()
-                    )))
-                }
-            }
-            Err(e) => Err(DataAvailabilityError::IPFSError(e.to_string())),
-        }
-    }
 }
@@ -3,6 +3,7 @@ use std::{sync::Arc, time::Duration};
 use chrono::Utc;
 use serde_json;
 use tokio::sync::watch;
+use zksync_anchoring::{HttpIpfsClient, HttpMintlayerClient};
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{
@@ -74,7 +75,6 @@ pub struct EthTxAggregator {
     /// address.
     custom_commit_sender_addr: Option<Address>,
     pool: ConnectionPool<Core>,
-    data_availability_worker: Option<DataAvailabilityWorker>,
     metrics: Arc<DataAvailabilityMetrics>,
 }
 
@@ -126,9 +126,34 @@ impl EthTxAggregator {
             batch_size: 6,
         };
 
-        let data_availability_worker =
-            DataAvailabilityWorker::new(worker_config, pool.clone(), metrics.clone());
-        let mut eth_tx_aggregator = Self {
+        let anchoring_clients = HttpIpfsClient::from_env().and_then(|ipfs| {
+            let mintlayer = HttpMintlayerClient::from_env()?;
+            Ok((ipfs, Arc::new(mintlayer)))
+        });
+        match anchoring_clients {
+            Ok((ipfs, mintlayer)) => {
+                let worker = DataAvailabilityWorker::new(
+                    worker_config,
+                    pool.clone(),
+                    metrics.clone(),
+                    Arc::new(ipfs),
+                    mintlayer.clone(),
+                );
+                tokio::spawn(async move {
+                    if let Err(err) = mintlayer.prepare_wallet().await {
+                        tracing::warn!("Failed preparing Mintlayer wallet: {err:#}");
+                    }
+                    worker.run().await;
+                });
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Not starting data availability worker: failed configuring clients: {err:#}"
+                );
+            }
+        }
+
+        Self {
             config,
             aggregator,
             eth_client,
@@ -141,17 +166,8 @@ impl EthTxAggregator {
             rollup_chain_id,
             custom_commit_sender_addr,
             pool,
-            data_availability_worker: Some(data_availability_worker),
             metrics,
-        };
-
-        if let Some(worker) = eth_tx_aggregator.data_availability_worker.take() {
-            tokio::spawn(async move {
-                worker.run().await;
-            });
         }
-
-        eth_tx_aggregator
     }
 
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
@@ -446,6 +462,7 @@ impl EthTxAggregator {
             status: OperationStatus::Pending,
             ipfs_hash: None,
             requires_mintlayer: true,
+            l1_batch_range: Some(aggregated_op.l1_batch_range()),
         };
 
         let mut conn = match self.pool.connection_tagged("eth_tx_aggregator").await {
@@ -35,6 +35,16 @@ struct TreeProofsResponse {
     entries: Vec<TreeEntryWithProof>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeBatchProofsRequest {
+    requests: Vec<TreeProofsRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeBatchProofsResponse {
+    batches: Vec<TreeProofsResponse>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TreeEntryWithProof {
     #[serde(default, skip_serializing_if = "H256::is_zero")]
@@ -77,10 +87,23 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Error returned if proofs are requested for an L1 batch that was pruned from the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error(
+    "L1 batch #{l1_batch_number} was pruned from Merkle tree; the earliest provable L1 batch is #{earliest_provable_batch}"
+)]
+pub struct PrunedL1BatchError {
+    /// Requested L1 batch.
+    pub l1_batch_number: L1BatchNumber,
+    /// Earliest L1 batch retained by the tree, for which proofs can be requested.
+    pub earliest_provable_batch: L1BatchNumber,
+}
+
 /// Server-side tree API error.
 #[derive(Debug)]
 enum TreeApiServerError {
     NoTreeVersion(NoVersionError),
+    PrunedL1Batch(PrunedL1BatchError),
 }
 
 // Contains the same fields as `NoVersionError` and is serializable.
@@ -133,6 +156,15 @@ impl IntoResponse for TreeApiServerError {
                 };
                 (StatusCode::NOT_FOUND, headers, Json(body)).into_response()
             }
+            Self::PrunedL1Batch(err) => {
+                let body = Problem {
+                    r#type: "/errors#l1-batch-pruned",
+                    title: "L1 batch pruned",
+                    detail: err.to_string(),
+                    data: err,
+                };
+                (StatusCode::GONE, headers, Json(body)).into_response()
+            }
         }
     }
 }
@@ -143,6 +175,8 @@ impl IntoResponse for TreeApiServerError {
 pub enum TreeApiError {
     #[error(transparent)]
     NoVersion(NoVersionError),
+    #[error(transparent)]
+    PrunedL1Batch(PrunedL1BatchError),
     #[error("tree API is temporarily unavailable")]
     NotReady(#[source] Option<anyhow::Error>),
     /// Catch-all variant for internal errors.
@@ -163,6 +197,15 @@ impl TreeApiError {
     }
 }
 
+impl From<TreeApiServerError> for TreeApiError {
+    fn from(err: TreeApiServerError) -> Self {
+        match err {
+            TreeApiServerError::NoTreeVersion(err) => Self::NoVersion(err),
+            TreeApiServerError::PrunedL1Batch(err) => Self::PrunedL1Batch(err),
+        }
+    }
+}
+
 /// Client accessing Merkle tree API.
 #[async_trait]
 pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
@@ -175,6 +218,19 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains proofs for multiple L1 batches at once. Proofs are returned in the same order as `requests`.
+    /// If any of the requested L1 batches is missing in the tree, the entire request fails.
+    async fn get_proofs_for_batches(
+        &self,
+        requests: Vec<(L1BatchNumber, Vec<U256>)>,
+    ) -> Result<Vec<Vec<TreeEntryWithProof>>, TreeApiError> {
+        let mut proofs = Vec::with_capacity(requests.len());
+        for (l1_batch_number, hashed_keys) in requests {
+            proofs.push(self.get_proofs(l1_batch_number, hashed_keys).await?);
+        }
+        Ok(proofs)
+    }
 }
 
 /// In-memory client implementation.
@@ -197,7 +253,21 @@ impl TreeApiClient for LazyAsyncTreeReader {
             reader
                 .get_proofs_inner(l1_batch_number, hashed_keys)
                 .await
-                .map_err(TreeApiError::NoVersion)
+                .map_err(Into::into)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
+
+    async fn get_proofs_for_batches(
+        &self,
+        requests: Vec<(L1BatchNumber, Vec<U256>)>,
+    ) -> Result<Vec<Vec<TreeEntryWithProof>>, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .get_proofs_for_batches_inner(requests)
+                .await
+                .map_err(Into::into)
         } else {
             Err(TreeApiError::NotReady(None))
         }
@@ -210,6 +280,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    batch_proofs_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -224,6 +295,37 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            batch_proofs_url: format!("{url_base}/proofs/batch"),
+        }
+    }
+
+    /// Parses a structured error from the response, if there is one.
+    async fn check_problem(response: reqwest::Response) -> Result<reqwest::Response, TreeApiError> {
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
+        if !is_problem {
+            return Ok(response);
+        }
+
+        match response.status() {
+            StatusCode::NOT_FOUND => {
+                // Try to parse `NoVersionError` from the response body.
+                let problem_data: NoVersionErrorData = response
+                    .json()
+                    .await
+                    .context("failed parsing error response")?;
+                Err(TreeApiError::NoVersion(problem_data.into()))
+            }
+            StatusCode::GONE => {
+                let err: PrunedL1BatchError = response
+                    .json()
+                    .await
+                    .context("failed parsing error response")?;
+                Err(TreeApiError::PrunedL1Batch(err))
+            }
+            _ => Ok(response),
         }
     }
 }
@@ -286,19 +388,7 @@ impl TreeApiClient for TreeApiHttpClient {
                 )
             })?;
 
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion(problem_data.into()));
-        }
-
+        let response = Self::check_problem(response).await?;
         let response = response.error_for_status().with_context(|| {
             format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
         })?;
@@ -307,6 +397,48 @@ impl TreeApiClient for TreeApiHttpClient {
         })?;
         Ok(response.entries)
     }
+
+    async fn get_proofs_for_batches(
+        &self,
+        requests: Vec<(L1BatchNumber, Vec<U256>)>,
+    ) -> Result<Vec<Vec<TreeEntryWithProof>>, TreeApiError> {
+        let batch_count = requests.len();
+        let requests = requests
+            .into_iter()
+            .map(|(l1_batch_number, hashed_keys)| TreeProofsRequest {
+                l1_batch_number,
+                hashed_keys,
+            })
+            .collect();
+        let response = self
+            .inner
+            .post(&self.batch_proofs_url)
+            .json(&TreeBatchProofsRequest { requests })
+            .send()
+            .await
+            .map_err(|err| {
+                TreeApiError::for_request(err, format_args!("proofs for {batch_count} L1 batches"))
+            })?;
+
+        let response = Self::check_problem(response).await?;
+        let response = response.error_for_status().with_context(|| {
+            format!("requesting proofs for {batch_count} L1 batches returned non-OK response")
+        })?;
+        let response: TreeBatchProofsResponse = response
+            .json()
+            .await
+            .with_context(|| format!("failed deserializing proofs for {batch_count} L1 batches"))?;
+        anyhow::ensure!(
+            response.batches.len() == batch_count,
+            "unexpected number of L1 batches in proofs response: expected {batch_count}, got {}",
+            response.batches.len()
+        );
+        Ok(response
+            .batches
+            .into_iter()
+            .map(|batch| batch.entries)
+            .collect())
+    }
 }
 
 impl AsyncTreeReader {
@@ -317,18 +449,53 @@ impl AsyncTreeReader {
         Json(info)
     }
 
+    /// Distinguishes between missing and pruned tree versions. For the latter, returns the earliest provable L1 batch.
+    async fn convert_no_version_error(&self, err: NoVersionError) -> TreeApiServerError {
+        if err.missing_version >= err.version_count {
+            return TreeApiServerError::NoTreeVersion(err);
+        }
+        let (Ok(l1_batch_number), Some(earliest_provable_batch)) = (
+            u32::try_from(err.missing_version),
+            self.clone().min_l1_batch_number().await,
+        ) else {
+            return TreeApiServerError::NoTreeVersion(err);
+        };
+        TreeApiServerError::PrunedL1Batch(PrunedL1BatchError {
+            l1_batch_number: L1BatchNumber(l1_batch_number),
+            earliest_provable_batch,
+        })
+    }
+
     async fn get_proofs_inner(
         &self,
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
-    ) -> Result<Vec<TreeEntryWithProof>, NoVersionError> {
-        let proofs = self
+    ) -> Result<Vec<TreeEntryWithProof>, TreeApiServerError> {
+        let proofs = match self
             .clone()
             .entries_with_proofs(l1_batch_number, hashed_keys)
-            .await?;
+            .await
+        {
+            Ok(proofs) => proofs,
+            Err(err) => return Err(self.convert_no_version_error(err).await),
+        };
         Ok(proofs.into_iter().map(TreeEntryWithProof::new).collect())
     }
 
+    async fn get_proofs_for_batches_inner(
+        &self,
+        requests: Vec<(L1BatchNumber, Vec<U256>)>,
+    ) -> Result<Vec<Vec<TreeEntryWithProof>>, TreeApiServerError> {
+        let proofs = match self.clone().entries_with_proofs_for_batches(requests).await {
+            Ok(proofs) => proofs,
+            Err(err) => return Err(self.convert_no_version_error(err).await),
+        };
+        Ok(proofs
+            .into_iter()
+            .map(|batch| batch.into_iter().map(TreeEntryWithProof::new).collect())
+            .collect())
+    }
+
     async fn get_proofs_handler(
         State(this): State<Self>,
         Json(request): Json<TreeProofsRequest>,
@@ -336,13 +503,31 @@ impl AsyncTreeReader {
         let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetProofs].start();
         let entries = this
             .get_proofs_inner(request.l1_batch_number, request.hashed_keys)
-            .await
-            .map_err(TreeApiServerError::NoTreeVersion)?;
+            .await?;
         let response = TreeProofsResponse { entries };
         latency.observe();
         Ok(Json(response))
     }
 
+    async fn get_proofs_for_batches_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeBatchProofsRequest>,
+    ) -> Result<Json<TreeBatchProofsResponse>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetProofsForBatches].start();
+        let requests = request
+            .requests
+            .into_iter()
+            .map(|request| (request.l1_batch_number, request.hashed_keys))
+            .collect();
+        let batches = this.get_proofs_for_batches_inner(requests).await?;
+        let batches = batches
+            .into_iter()
+            .map(|entries| TreeProofsResponse { entries })
+            .collect();
+        latency.observe();
+        Ok(Json(TreeBatchProofsResponse { batches }))
+    }
+
     async fn create_api_server(
         self,
         bind_address: &SocketAddr,
@@ -353,6 +538,10 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route(
+                "/proofs/batch",
+                routing::post(Self::get_proofs_for_batches_handler),
+            )
             .with_state(self);
 
         let listener = tokio::net::TcpListener::bind(bind_address)
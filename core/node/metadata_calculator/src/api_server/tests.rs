@@ -8,10 +8,14 @@ use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpSocket},
 };
+use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{ConnectionPool, Core};
 
 use super::*;
-use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
+use crate::{
+    tests::{extend_db_state, gen_storage_logs, reset_db_state, run_calculator, setup_calculator},
+    GenericAsyncTree, MerkleTreeReplica, MerkleTreeReplicaConfig,
+};
 
 #[tokio::test]
 async fn merkle_tree_api() {
@@ -140,3 +144,196 @@ async fn local_merkle_tree_client() {
     assert_eq!(err.version_count, 6);
     assert_eq!(err.missing_version, 10);
 }
+
+#[tokio::test]
+async fn merkle_tree_api_for_multiple_batches_with_pruning() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    reset_db_state(&pool, 5).await;
+    run_calculator(calculator).await;
+
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    let tree = calculator.create_tree().await.unwrap();
+    let GenericAsyncTree::Ready(mut tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    let reader = tree.reader();
+    let (mut pruner, pruner_handle) = tree.pruner();
+    pruner.set_poll_interval(Duration::from_millis(50));
+    tokio::task::spawn_blocking(|| pruner.run());
+    pruner_handle.set_target_retained_version(3).unwrap();
+    while reader.clone().info().await.min_l1_batch_number < Some(L1BatchNumber(3)) {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    let api_addr = (Ipv4Addr::LOCALHOST, 0).into();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let api_server = reader
+        .clone()
+        .create_api_server(&api_addr, stop_receiver)
+        .await
+        .unwrap();
+    let local_addr = *api_server.local_addr();
+    let api_server_task = tokio::spawn(api_server.run());
+    let api_client = TreeApiHttpClient::new(&format!("http://{local_addr}"));
+
+    let hashed_keys: Vec<_> = gen_storage_logs(20..30, 1)[0]
+        .iter()
+        .map(|log| log.key.hashed_key_u256())
+        .collect();
+    let requests = vec![
+        (L1BatchNumber(3), hashed_keys.clone()),
+        (L1BatchNumber(5), hashed_keys.clone()),
+    ];
+    let batches = api_client.get_proofs_for_batches(requests).await.unwrap();
+    assert_eq!(batches.len(), 2);
+    let single_batch_proofs = api_client
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
+        .await
+        .unwrap();
+    assert_eq!(batches[1].len(), single_batch_proofs.len());
+    for (proof, expected_proof) in batches[1].iter().zip(&single_batch_proofs) {
+        assert_eq!(proof.merkle_path, expected_proof.merkle_path);
+        assert_eq!(proof.value, expected_proof.value);
+    }
+
+    let expected_err = PrunedL1BatchError {
+        l1_batch_number: L1BatchNumber(1),
+        earliest_provable_batch: L1BatchNumber(3),
+    };
+    let err = api_client
+        .get_proofs(L1BatchNumber(1), hashed_keys.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::PrunedL1Batch(err) if err == expected_err);
+    let err = reader
+        .get_proofs_inner(L1BatchNumber(1), vec![])
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiServerError::PrunedL1Batch(err) if err == expected_err);
+
+    let requests = vec![
+        (L1BatchNumber(5), hashed_keys.clone()),
+        (L1BatchNumber(2), hashed_keys),
+    ];
+    let err = api_client
+        .get_proofs_for_batches(requests)
+        .await
+        .unwrap_err();
+    let TreeApiError::PrunedL1Batch(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert_eq!(err.l1_batch_number, L1BatchNumber(2));
+    assert_eq!(err.earliest_provable_batch, L1BatchNumber(3));
+
+    let err = api_client
+        .get_proofs_for_batches(vec![(L1BatchNumber(10), vec![])])
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion(_));
+
+    stop_sender.send_replace(true);
+    api_server_task.await.unwrap().unwrap();
+}
+
+fn replica_config(primary_dir: &TempDir, secondary_dir: &TempDir) -> MerkleTreeReplicaConfig {
+    MerkleTreeReplicaConfig {
+        // `setup_calculator()` places the tree RocksDB in a subdirectory of the specified path.
+        db_path: primary_dir.path().join("new").to_str().unwrap().to_owned(),
+        secondary_db_path: secondary_dir.path().to_str().unwrap().to_owned(),
+        block_cache_capacity: 0,
+        multi_get_chunk_size: 500,
+        node_cache_capacity: 100,
+        catch_up_interval: Duration::from_millis(10),
+    }
+}
+
+#[tokio::test]
+async fn merkle_tree_api_served_by_replica() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let secondary_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    reset_db_state(&pool, 3).await;
+    run_calculator(calculator).await;
+
+    let replica_config = replica_config(&temp_dir, &secondary_dir);
+    let replica = MerkleTreeReplica::new(replica_config, MerkleTreeMode::Full)
+        .await
+        .unwrap();
+    let api_addr = (Ipv4Addr::LOCALHOST, 0).into();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let api_server = replica
+        .tree_reader()
+        .create_api_server(&api_addr, stop_receiver.clone())
+        .await
+        .unwrap();
+    let local_addr = *api_server.local_addr();
+    let api_server_task = tokio::spawn(api_server.run());
+    let replica_task = tokio::spawn(replica.run(stop_receiver));
+    let api_client = TreeApiHttpClient::new(&format!("http://{local_addr}"));
+
+    let tree_info = api_client.get_info().await.unwrap();
+    assert_eq!(tree_info.next_l1_batch_number, L1BatchNumber(4));
+
+    // Extend the tree using the primary instance; the replica should catch up with it.
+    let mut storage = pool.connection().await.unwrap();
+    extend_db_state(&mut storage, gen_storage_logs(100..200, 2)).await;
+    drop(storage);
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    let expected_root_hash = run_calculator(calculator).await;
+
+    let tree_info = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let tree_info = api_client.get_info().await.unwrap();
+            if tree_info.next_l1_batch_number == L1BatchNumber(6) {
+                break tree_info;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("replica hasn't caught up with the primary tree");
+    assert_eq!(tree_info.root_hash, expected_root_hash);
+
+    let hashed_keys: Vec<_> = gen_storage_logs(100..200, 2)[1]
+        .iter()
+        .map(|log| log.key.hashed_key_u256())
+        .collect();
+    let proofs = api_client
+        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .await
+        .unwrap();
+    assert!(proofs.iter().all(|proof| proof.index > 0));
+
+    stop_sender.send_replace(true);
+    api_server_task.await.unwrap().unwrap();
+    replica_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn replica_catch_up_waits_for_in_flight_reads() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let secondary_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone(), true).await;
+    reset_db_state(&pool, 3).await;
+    run_calculator(calculator).await;
+
+    let replica_config = replica_config(&temp_dir, &secondary_dir);
+    let replica = MerkleTreeReplica::new(replica_config, MerkleTreeMode::Full)
+        .await
+        .unwrap();
+    let reader = replica.tree_reader();
+
+    let read_guard = reader.read_guard().await;
+    let catch_up_task = tokio::spawn(reader.clone().catch_up_with_primary());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!catch_up_task.is_finished());
+
+    drop(read_guard);
+    catch_up_task.await.unwrap().unwrap();
+    let tree_info = reader.info().await;
+    assert_eq!(tree_info.next_l1_batch_number, L1BatchNumber(4));
+}
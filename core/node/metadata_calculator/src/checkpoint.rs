@@ -0,0 +1,523 @@
+//! Merkle tree checkpoints: exporting RocksDB checkpoints of the tree to an object store and importing them
+//! during snapshot recovery.
+//!
+//! A checkpoint is stored as a set of objects in the [`Bucket::MerkleTreeCheckpoints`] bucket: one object per
+//! RocksDB file, and a manifest listing these files together with the expected tree state. The manifest is uploaded
+//! last, so that its presence signals that the checkpoint is complete.
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_merkle_tree::{domain::ZkSyncTree, RocksDBWrapper};
+use zksync_object_store::{
+    Bucket, ObjectStore, ObjectStoreError, StoredObject, _reexports::BoxedError,
+};
+use zksync_storage::RocksDB;
+use zksync_types::{snapshots::SnapshotRecoveryStatus, L1BatchNumber, H256};
+
+use crate::{
+    helpers::AsyncTreeReader,
+    metrics::{RecoveryStage, RECOVERY_METRICS},
+    LazyAsyncTreeReader,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CheckpointFileInfo {
+    name: String,
+    size: u64,
+}
+
+/// Manifest of a tree checkpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct TreeCheckpointManifest {
+    l1_batch_number: L1BatchNumber,
+    root_hash: H256,
+    leaf_count: u64,
+    files: Vec<CheckpointFileInfo>,
+}
+
+impl StoredObject for TreeCheckpointManifest {
+    const BUCKET: Bucket = Bucket::MerkleTreeCheckpoints;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("checkpoint_l1_batch_{key}_manifest.json")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        serde_json::to_vec(self).map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        serde_json::from_slice(&bytes).map_err(From::from)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CheckpointFileKey<'a> {
+    l1_batch_number: L1BatchNumber,
+    name: &'a str,
+}
+
+/// Raw contents of a RocksDB file in a checkpoint. Files are not compressed since SST files are already compressed
+/// by RocksDB.
+#[derive(Debug)]
+struct CheckpointFile(Vec<u8>);
+
+impl StoredObject for CheckpointFile {
+    const BUCKET: Bucket = Bucket::MerkleTreeCheckpoints;
+    type Key<'a> = CheckpointFileKey<'a>;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("checkpoint_l1_batch_{}_{}", key.l1_batch_number, key.name)
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        Ok(self.0.clone())
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        Ok(Self(bytes))
+    }
+}
+
+fn temp_checkpoint_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push(".checkpoint");
+    path.into()
+}
+
+/// Checks that a checkpoint file name from a manifest is a plain file name, so that the file cannot be written
+/// outside the checkpoint directory.
+fn is_plain_file_name(name: &str) -> bool {
+    Path::new(name).file_name() == Some(OsStr::new(name)) && name != ".." && name != "."
+}
+
+async fn remove_dir_if_exists(path: &Path) -> anyhow::Result<()> {
+    match tokio::fs::remove_dir_all(path).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("failed removing `{}`", path.display())),
+    }
+}
+
+/// Exports a tree checkpoint for the specified L1 batch to the object store. `db_path` is the path to the tree RocksDB;
+/// the checkpoint is temporarily created in a sibling directory, so that SST files can be hard-linked.
+async fn export_checkpoint(
+    reader: AsyncTreeReader,
+    l1_batch_number: L1BatchNumber,
+    db_path: &Path,
+    store: &dyn ObjectStore,
+) -> anyhow::Result<()> {
+    let checkpoint_path = temp_checkpoint_path(db_path);
+    remove_dir_if_exists(&checkpoint_path).await?;
+    let info = reader
+        .create_checkpoint(l1_batch_number, checkpoint_path.clone())
+        .await?;
+    tracing::info!(
+        "Created tree checkpoint at `{}`: {info:?}",
+        checkpoint_path.display()
+    );
+
+    let mut files = vec![];
+    let mut dir = tokio::fs::read_dir(&checkpoint_path).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        let name = entry
+            .file_name()
+            .into_string()
+            .map_err(|name| anyhow::anyhow!("non-UTF8 RocksDB file name: {name:?}"))?;
+        let contents = tokio::fs::read(entry.path())
+            .await
+            .with_context(|| format!("failed reading checkpoint file `{name}`"))?;
+        let size = contents.len() as u64;
+        let key = CheckpointFileKey {
+            l1_batch_number,
+            name: &name,
+        };
+        store
+            .put(key, &CheckpointFile(contents))
+            .await
+            .with_context(|| format!("failed uploading checkpoint file `{name}`"))?;
+        files.push(CheckpointFileInfo { name, size });
+    }
+
+    let manifest = TreeCheckpointManifest {
+        l1_batch_number,
+        root_hash: info.root_hash,
+        leaf_count: info.leaf_count,
+        files,
+    };
+    store
+        .put(l1_batch_number, &manifest)
+        .await
+        .context("failed uploading checkpoint manifest")?;
+    tracing::info!(
+        "Exported tree checkpoint for L1 batch #{l1_batch_number} ({} files)",
+        manifest.files.len()
+    );
+    remove_dir_if_exists(&checkpoint_path).await
+}
+
+/// Task exporting tree checkpoints for L1 batches with complete storage snapshots. Checkpoints allow to bootstrap
+/// the Merkle tree on external nodes without recovering it from snapshot storage logs.
+#[derive(Debug)]
+pub struct TreeCheckpointExportTask {
+    tree_reader: LazyAsyncTreeReader,
+    pool: ConnectionPool<Core>,
+    store: Arc<dyn ObjectStore>,
+    db_path: PathBuf,
+    poll_interval: Duration,
+}
+
+impl TreeCheckpointExportTask {
+    pub(super) fn new(
+        tree_reader: LazyAsyncTreeReader,
+        pool: ConnectionPool<Core>,
+        store: Arc<dyn ObjectStore>,
+        db_path: PathBuf,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            tree_reader,
+            pool,
+            store,
+            db_path,
+            poll_interval,
+        }
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let Some(reader) = self.tree_reader.wait().await else {
+            tracing::info!("Merkle tree dropped; shutting down tree checkpoint export");
+            return Ok(());
+        };
+
+        let mut last_exported_l1_batch = None;
+        while !*stop_receiver.borrow_and_update() {
+            let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+            let snapshots = storage.snapshots_dal().get_all_complete_snapshots().await?;
+            drop(storage);
+
+            let newest_snapshot = snapshots.snapshots_l1_batch_numbers.first().copied();
+            if let Some(l1_batch_number) = newest_snapshot {
+                if last_exported_l1_batch < Some(l1_batch_number) {
+                    self.export_if_necessary(reader.clone(), l1_batch_number)
+                        .await?;
+                    last_exported_l1_batch = Some(l1_batch_number);
+                }
+            }
+
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, tree checkpoint export is shut down");
+        Ok(())
+    }
+
+    async fn export_if_necessary(
+        &self,
+        reader: AsyncTreeReader,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        match self
+            .store
+            .get::<TreeCheckpointManifest>(l1_batch_number)
+            .await
+        {
+            Ok(_) => {
+                tracing::debug!(
+                    "Tree checkpoint for L1 batch #{l1_batch_number} is already exported"
+                );
+                return Ok(());
+            }
+            Err(ObjectStoreError::KeyNotFound(_)) => { /* continue */ }
+            Err(err) => return Err(err).context("failed checking checkpoint manifest"),
+        }
+
+        let tree_info = reader.clone().info().await;
+        if tree_info.next_l1_batch_number <= l1_batch_number
+            || tree_info.min_l1_batch_number > Some(l1_batch_number)
+        {
+            tracing::warn!(
+                "Cannot export tree checkpoint for L1 batch #{l1_batch_number}: tree doesn't contain it ({tree_info:?})"
+            );
+            return Ok(());
+        }
+        export_checkpoint(reader, l1_batch_number, &self.db_path, &*self.store).await
+    }
+}
+
+/// Imports a tree checkpoint for the snapshot L1 batch into `db_path`, which must not contain a tree.
+/// Returns `Ok(false)` if the checkpoint is not present in the store.
+async fn import_checkpoint(
+    store: &dyn ObjectStore,
+    snapshot_recovery: &SnapshotRecoveryStatus,
+    db_path: &Path,
+) -> anyhow::Result<bool> {
+    let l1_batch_number = snapshot_recovery.l1_batch_number;
+    let manifest = match store.get::<TreeCheckpointManifest>(l1_batch_number).await {
+        Ok(manifest) => manifest,
+        Err(ObjectStoreError::KeyNotFound(_)) => return Ok(false),
+        Err(err) => return Err(err).context("failed getting checkpoint manifest"),
+    };
+    let expected_root_hash = snapshot_recovery.l1_batch_root_hash;
+    anyhow::ensure!(
+        manifest.l1_batch_number == l1_batch_number && manifest.root_hash == expected_root_hash,
+        "checkpoint manifest {manifest:?} doesn't match snapshot recovery status {snapshot_recovery:?}"
+    );
+    for file_info in &manifest.files {
+        anyhow::ensure!(
+            is_plain_file_name(&file_info.name),
+            "invalid checkpoint file name `{}` in manifest",
+            file_info.name
+        );
+    }
+    tracing::info!(
+        "Importing tree checkpoint for L1 batch #{l1_batch_number} ({} files)",
+        manifest.files.len()
+    );
+
+    let checkpoint_path = temp_checkpoint_path(db_path);
+    remove_dir_if_exists(&checkpoint_path).await?;
+    tokio::fs::create_dir_all(&checkpoint_path).await?;
+    for file_info in &manifest.files {
+        let key = CheckpointFileKey {
+            l1_batch_number,
+            name: &file_info.name,
+        };
+        let file: CheckpointFile = store
+            .get(key)
+            .await
+            .with_context(|| format!("failed downloading checkpoint file `{}`", file_info.name))?;
+        anyhow::ensure!(
+            file.0.len() as u64 == file_info.size,
+            "unexpected size of checkpoint file `{}`: expected {}, got {}",
+            file_info.name,
+            file_info.size,
+            file.0.len()
+        );
+        tokio::fs::write(checkpoint_path.join(&file_info.name), file.0).await?;
+    }
+
+    let verified_path = checkpoint_path.clone();
+    tokio::task::spawn_blocking(move || {
+        let db = RocksDB::new(&verified_path).context("failed opening checkpoint RocksDB")?;
+        let tree = ZkSyncTree::new_lightweight(RocksDBWrapper::from(db))?;
+        let next_l1_batch_number = tree.next_l1_batch_number();
+        anyhow::ensure!(
+            next_l1_batch_number == l1_batch_number + 1,
+            "unexpected next L1 batch number in checkpoint: expected {}, got {next_l1_batch_number}",
+            l1_batch_number + 1
+        );
+        let root_hash = tree.root_hash();
+        anyhow::ensure!(
+            root_hash == expected_root_hash,
+            "unexpected checkpoint root hash: expected {expected_root_hash:?}, got {root_hash:?}"
+        );
+        tree.verify_consistency(l1_batch_number)
+            .context("checkpoint is inconsistent")
+    })
+    .await
+    .context("verifying checkpoint panicked")??;
+
+    if let Some(parent) = db_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    remove_dir_if_exists(db_path).await?;
+    tokio::fs::rename(&checkpoint_path, db_path)
+        .await
+        .context("failed moving verified checkpoint")?;
+    Ok(true)
+}
+
+/// Tries to bootstrap the tree at `db_path` from a checkpoint if the tree is empty and there's a snapshot recovery
+/// in Postgres. If the checkpoint cannot be imported, the tree will be recovered from snapshot storage logs as usual.
+pub(super) async fn maybe_import_checkpoint(
+    store: &dyn ObjectStore,
+    pool: &ConnectionPool<Core>,
+    db_path: &Path,
+) -> anyhow::Result<()> {
+    let is_db_empty = match tokio::fs::read_dir(db_path).await {
+        Ok(mut dir) => dir.next_entry().await?.is_none(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => true,
+        Err(err) => return Err(err).context("failed reading Merkle tree directory"),
+    };
+    if !is_db_empty {
+        return Ok(());
+    }
+    let Some(snapshot_recovery) = crate::recovery::get_snapshot_recovery(pool).await? else {
+        return Ok(());
+    };
+
+    let latency = RECOVERY_METRICS.latency[&RecoveryStage::ImportCheckpoint].start();
+    match import_checkpoint(store, &snapshot_recovery, db_path).await {
+        Ok(true) => {
+            let latency = latency.observe();
+            tracing::info!(
+                "Imported tree checkpoint for L1 batch #{} in {latency:?}",
+                snapshot_recovery.l1_batch_number
+            );
+        }
+        Ok(false) => {
+            tracing::info!(
+                "No tree checkpoint for L1 batch #{}; the tree will be recovered from snapshot storage logs",
+                snapshot_recovery.l1_batch_number
+            );
+        }
+        Err(err) => {
+            tracing::warn!(
+                "Failed importing tree checkpoint; the tree will be recovered from snapshot storage logs: {err:#}"
+            );
+            remove_dir_if_exists(&temp_checkpoint_path(db_path)).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+    use zksync_object_store::MockObjectStore;
+    use zksync_types::{L2BlockNumber, ProtocolVersionId};
+
+    use super::*;
+    use crate::{
+        helpers::create_db,
+        tests::{mock_config, reset_db_state},
+        MetadataCalculator,
+    };
+
+    fn mock_snapshot_recovery(
+        l1_batch_number: L1BatchNumber,
+        l1_batch_root_hash: H256,
+    ) -> SnapshotRecoveryStatus {
+        SnapshotRecoveryStatus {
+            l1_batch_number,
+            l1_batch_timestamp: l1_batch_number.0.into(),
+            l1_batch_root_hash,
+            l2_block_number: L2BlockNumber(l1_batch_number.0),
+            l2_block_timestamp: l1_batch_number.0.into(),
+            l2_block_hash: H256::zero(),
+            protocol_version: ProtocolVersionId::latest(),
+            storage_logs_chunks_processed: vec![true],
+        }
+    }
+
+    #[tokio::test]
+    async fn exporting_and_importing_checkpoint() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = mock_config(temp_dir.path());
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let calculator = MetadataCalculator::new(config.clone(), None, pool.clone())
+            .await
+            .unwrap();
+        crate::tests::run_calculator(calculator).await;
+        let root_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(L1BatchNumber(3))
+            .await
+            .unwrap()
+            .expect("no root hash for L1 batch #3");
+
+        let store = MockObjectStore::arc();
+        let db_path = Path::new(&config.db_path).to_owned();
+        let db = create_db(config).await.unwrap();
+        let reader = AsyncTreeReader::new_full(db).unwrap();
+        export_checkpoint(reader, L1BatchNumber(3), &db_path, &*store)
+            .await
+            .unwrap();
+        assert!(!temp_checkpoint_path(&db_path).exists());
+
+        let manifest: TreeCheckpointManifest = store.get(L1BatchNumber(3)).await.unwrap();
+        assert_eq!(manifest.root_hash, root_hash);
+        assert!(!manifest.files.is_empty());
+
+        let import_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let import_path = import_dir.path().join("tree");
+        let snapshot_recovery = mock_snapshot_recovery(L1BatchNumber(4), root_hash);
+        let imported = import_checkpoint(&*store, &snapshot_recovery, &import_path)
+            .await
+            .unwrap();
+        assert!(!imported);
+
+        let snapshot_recovery = mock_snapshot_recovery(L1BatchNumber(3), H256::repeat_byte(1));
+        import_checkpoint(&*store, &snapshot_recovery, &import_path)
+            .await
+            .unwrap_err();
+
+        let snapshot_recovery = mock_snapshot_recovery(L1BatchNumber(3), root_hash);
+        let imported = import_checkpoint(&*store, &snapshot_recovery, &import_path)
+            .await
+            .unwrap();
+        assert!(imported);
+        assert!(!temp_checkpoint_path(&import_path).exists());
+
+        let db = RocksDB::new(&import_path).unwrap();
+        let tree = ZkSyncTree::new_lightweight(RocksDBWrapper::from(db)).unwrap();
+        assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(4));
+        assert_eq!(tree.root_hash(), root_hash);
+    }
+
+    #[tokio::test]
+    async fn importing_checkpoint_with_malicious_file_names() {
+        let store = MockObjectStore::arc();
+        let root_hash = H256::repeat_byte(1);
+        let import_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let import_path = import_dir.path().join("tree");
+        let escaped_path = import_dir.path().join("evil");
+        let snapshot_recovery = mock_snapshot_recovery(L1BatchNumber(3), root_hash);
+
+        let escaped_path_str = escaped_path.to_str().unwrap();
+        for name in ["../evil", "nested/../../evil", escaped_path_str, "..", "."] {
+            let manifest = TreeCheckpointManifest {
+                l1_batch_number: L1BatchNumber(3),
+                root_hash,
+                leaf_count: 1,
+                files: vec![CheckpointFileInfo {
+                    name: name.to_owned(),
+                    size: 4,
+                }],
+            };
+            store.put(L1BatchNumber(3), &manifest).await.unwrap();
+            let key = CheckpointFileKey {
+                l1_batch_number: L1BatchNumber(3),
+                name,
+            };
+            store
+                .put(key, &CheckpointFile(b"evil".to_vec()))
+                .await
+                .unwrap();
+
+            let err = import_checkpoint(&*store, &snapshot_recovery, &import_path)
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("invalid checkpoint file name"),
+                "{err:#}"
+            );
+            assert!(!escaped_path.exists());
+        }
+
+        assert!(is_plain_file_name("000008.sst"));
+        assert!(is_plain_file_name("MANIFEST-000005"));
+    }
+}
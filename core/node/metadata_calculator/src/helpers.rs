@@ -3,7 +3,7 @@
 use std::{
     collections::{BTreeMap, HashSet},
     future::Future,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
@@ -15,12 +15,12 @@ use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 #[cfg(test)]
 use tokio::sync::mpsc;
-use tokio::sync::watch;
+use tokio::sync::{watch, OwnedRwLockReadGuard, RwLock};
 use zksync_config::configs::database::MerkleTreeMode;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_health_check::{CheckHealth, Health, HealthStatus, ReactiveHealthCheck};
 use zksync_merkle_tree::{
-    domain::{TreeMetadata, ZkSyncTree, ZkSyncTreeReader},
+    domain::{TreeCheckpointInfo, TreeMetadata, ZkSyncTree, ZkSyncTreeReader},
     recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
     Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
     TreeEntryWithProof, TreeInstruction,
@@ -145,6 +145,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         multi_get_chunk_size,
         memtable_capacity,
         stalled_writes_timeout,
+        node_cache_capacity,
         ..
     } = config;
 
@@ -152,7 +153,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         "Initializing Merkle tree database at `{path}` (max open files: {max_open_files:?}) with {multi_get_chunk_size} multi-get chunk size, \
          {block_cache_capacity}B block cache (indices & filters included: {include_indices_and_filters_in_block_cache:?}), \
          {memtable_capacity}B memtable capacity, \
-         {stalled_writes_timeout:?} stalled writes timeout",
+         {stalled_writes_timeout:?} stalled writes timeout, {node_cache_capacity} node cache capacity",
         path = path.display()
     );
 
@@ -173,6 +174,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
     }
     let mut db = RocksDBWrapper::from(db);
     db.set_multi_get_chunk_size(multi_get_chunk_size);
+    db.set_node_cache_capacity(node_cache_capacity);
     Ok(db)
 }
 
@@ -222,10 +224,10 @@ impl AsyncTree {
     }
 
     pub fn reader(&self) -> AsyncTreeReader {
-        AsyncTreeReader {
-            inner: self.inner.as_ref().expect(Self::INCONSISTENT_MSG).reader(),
-            mode: self.mode,
-        }
+        AsyncTreeReader::new(
+            self.inner.as_ref().expect(Self::INCONSISTENT_MSG).reader(),
+            self.mode,
+        )
     }
 
     pub fn is_empty(&self) -> bool {
@@ -304,17 +306,54 @@ impl AsyncTree {
 pub struct AsyncTreeReader {
     inner: ZkSyncTreeReader,
     mode: MerkleTreeMode,
+    /// Shared among clones. Read operations hold a read guard, and catching up with the primary RocksDB instance
+    /// holds a write guard, so that catch-up (which may invalidate cached tree nodes) doesn't interleave
+    /// with in-flight reads.
+    catch_up_lock: Arc<RwLock<()>>,
 }
 
 impl AsyncTreeReader {
+    fn new(inner: ZkSyncTreeReader, mode: MerkleTreeMode) -> Self {
+        Self {
+            inner,
+            mode,
+            catch_up_lock: Arc::default(),
+        }
+    }
+
+    /// Creates a reader for a full tree. Used for read replicas that don't have an associated [`AsyncTree`].
+    pub(super) fn new_full(db: RocksDBWrapper) -> anyhow::Result<Self> {
+        Ok(Self::new(ZkSyncTreeReader::new(db)?, MerkleTreeMode::Full))
+    }
+
+    pub(super) async fn read_guard(&self) -> OwnedRwLockReadGuard<()> {
+        self.catch_up_lock.clone().read_owned().await
+    }
+
+    /// Makes changes persisted by the primary RocksDB instance visible to this reader. Should only be called
+    /// for readers backed by a secondary RocksDB instance. Waits for in-flight reads to complete, and blocks
+    /// new reads until catch-up is finished.
+    pub(super) async fn catch_up_with_primary(self) -> anyhow::Result<()> {
+        let guard = self.catch_up_lock.clone().write_owned().await;
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            self.inner.db().try_catch_up_with_primary()
+        })
+        .await
+        .context("catching up with primary RocksDB panicked")?
+    }
+
     fn downgrade(&self) -> WeakAsyncTreeReader {
         WeakAsyncTreeReader {
             db: self.inner.db().clone().into_inner().downgrade(),
             mode: self.mode,
+            catch_up_lock: self.catch_up_lock.clone(),
         }
     }
 
     pub async fn info(self) -> MerkleTreeInfo {
+        // Doesn't hold a read guard: the method doesn't read internal nodes, and it retries if it observes
+        // an inconsistent tree state, which requires catch-up to be able to proceed.
         tokio::task::spawn_blocking(move || {
             loop {
                 let next_l1_batch_number = self.inner.next_l1_batch_number();
@@ -349,12 +388,42 @@ impl AsyncTreeReader {
         .unwrap()
     }
 
+    /// Returns the minimum L1 batch number retained by the tree, i.e., the earliest L1 batch for which proofs can be created.
+    pub async fn min_l1_batch_number(self) -> Option<L1BatchNumber> {
+        let guard = self.read_guard().await;
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            self.inner.min_l1_batch_number()
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Creates a tree checkpoint for the specified L1 batch at `path`.
+    pub(super) async fn create_checkpoint(
+        self,
+        l1_batch_number: L1BatchNumber,
+        path: PathBuf,
+    ) -> anyhow::Result<TreeCheckpointInfo> {
+        let guard = self.read_guard().await;
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            self.inner.create_checkpoint(l1_batch_number, &path)
+        })
+        .await
+        .context("creating tree checkpoint panicked")?
+    }
+
     #[cfg(test)]
     pub async fn verify_consistency(self, l1_batch_number: L1BatchNumber) -> anyhow::Result<()> {
-        tokio::task::spawn_blocking(move || self.inner.verify_consistency(l1_batch_number))
-            .await
-            .context("tree consistency verification panicked")?
-            .map_err(Into::into)
+        let guard = self.read_guard().await;
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            self.inner.verify_consistency(l1_batch_number)
+        })
+        .await
+        .context("tree consistency verification panicked")?
+        .map_err(Into::into)
     }
 
     pub async fn entries_with_proofs(
@@ -362,9 +431,33 @@ impl AsyncTreeReader {
         l1_batch_number: L1BatchNumber,
         keys: Vec<Key>,
     ) -> Result<Vec<TreeEntryWithProof>, NoVersionError> {
-        tokio::task::spawn_blocking(move || self.inner.entries_with_proofs(l1_batch_number, &keys))
-            .await
-            .unwrap()
+        let guard = self.read_guard().await;
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            self.inner.entries_with_proofs(l1_batch_number, &keys)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Batched version of [`Self::entries_with_proofs()`]. Requests are processed in order; processing stops
+    /// on the first missing tree version.
+    pub async fn entries_with_proofs_for_batches(
+        self,
+        requests: Vec<(L1BatchNumber, Vec<Key>)>,
+    ) -> Result<Vec<Vec<TreeEntryWithProof>>, NoVersionError> {
+        let guard = self.read_guard().await;
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            requests
+                .iter()
+                .map(|(l1_batch_number, keys)| {
+                    self.inner.entries_with_proofs(*l1_batch_number, keys)
+                })
+                .collect()
+        })
+        .await
+        .unwrap()
     }
 }
 
@@ -373,6 +466,7 @@ impl AsyncTreeReader {
 struct WeakAsyncTreeReader {
     db: WeakRocksDB<MerkleTreeColumnFamily>,
     mode: MerkleTreeMode,
+    catch_up_lock: Arc<RwLock<()>>,
 }
 
 impl WeakAsyncTreeReader {
@@ -380,6 +474,7 @@ impl WeakAsyncTreeReader {
         Some(AsyncTreeReader {
             inner: ZkSyncTreeReader::new(self.db.upgrade()?.into()).ok()?,
             mode: self.mode,
+            catch_up_lock: self.catch_up_lock.clone(),
         })
     }
 }
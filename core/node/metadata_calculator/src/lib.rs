@@ -3,6 +3,7 @@
 
 use std::{
     num::{NonZeroU32, NonZeroUsize},
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -17,22 +18,26 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
 use zksync_object_store::ObjectStore;
 
+pub use self::{
+    checkpoint::TreeCheckpointExportTask,
+    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
+    pruning::MerkleTreePruningTask,
+    replica::{MerkleTreeReplica, MerkleTreeReplicaConfig},
+};
 use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
     metrics::{ConfigLabels, METRICS},
     pruning::PruningHandles,
     updater::TreeUpdater,
 };
-pub use self::{
-    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
-    pruning::MerkleTreePruningTask,
-};
 
 pub mod api_server;
+mod checkpoint;
 mod helpers;
 mod metrics;
 mod pruning;
 mod recovery;
+mod replica;
 #[cfg(test)]
 pub(crate) mod tests;
 mod updater;
@@ -89,6 +94,8 @@ pub struct MetadataCalculatorConfig {
     pub memtable_capacity: usize,
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     pub stalled_writes_timeout: Duration,
+    /// Capacity of the LRU cache for internal tree nodes (measured in the number of nodes). 0 disables the cache.
+    pub node_cache_capacity: usize,
     /// Whether state keeper writes protective reads when it seals a batch.
     pub sealed_batches_have_protective_reads: bool,
     /// Configuration specific to the Merkle tree recovery.
@@ -112,6 +119,7 @@ impl MetadataCalculatorConfig {
             include_indices_and_filters_in_block_cache: false,
             memtable_capacity: merkle_tree_config.memtable_capacity(),
             stalled_writes_timeout: merkle_tree_config.stalled_writes_timeout(),
+            node_cache_capacity: merkle_tree_config.node_cache_capacity(),
             sealed_batches_have_protective_reads: state_keeper_config
                 .protective_reads_persistence_enabled,
             // The main node isn't supposed to be recovered yet, so this value doesn't matter much
@@ -126,6 +134,7 @@ pub struct MetadataCalculator {
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     pruning_handles_sender: oneshot::Sender<PruningHandles>,
     object_store: Option<Arc<dyn ObjectStore>>,
+    checkpoint_store: Option<Arc<dyn ObjectStore>>,
     pool: ConnectionPool<Core>,
     recovery_pool: ConnectionPool<Core>,
     delayer: Delayer,
@@ -167,6 +176,7 @@ impl MetadataCalculator {
             tree_reader: watch::channel(None).0,
             pruning_handles_sender: oneshot::channel().0,
             object_store,
+            checkpoint_store: None,
             recovery_pool: pool.clone(),
             pool,
             delayer: Delayer::new(config.delay_interval),
@@ -183,6 +193,14 @@ impl MetadataCalculator {
         self
     }
 
+    /// Sets an object store to import tree checkpoints from. If set, an empty tree will be bootstrapped from
+    /// a checkpoint for the snapshot L1 batch (if the checkpoint is present in the store) instead of being recovered
+    /// from snapshot storage logs.
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn ObjectStore>) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> impl CheckHealth {
         MerkleTreeHealthCheck::new(self.health_updater.subscribe(), self.tree_reader())
@@ -202,10 +220,30 @@ impl MetadataCalculator {
         MerkleTreePruningTask::new(pruning_handles, self.pool.clone(), poll_interval)
     }
 
+    /// Returns a task that exports tree checkpoints for L1 batches with storage snapshots to the specified `store`.
+    pub fn checkpoint_export_task(
+        &self,
+        store: Arc<dyn ObjectStore>,
+        poll_interval: Duration,
+    ) -> TreeCheckpointExportTask {
+        TreeCheckpointExportTask::new(
+            self.tree_reader(),
+            self.pool.clone(),
+            store,
+            self.config.db_path.clone().into(),
+            poll_interval,
+        )
+    }
+
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
 
+        if let Some(store) = &self.checkpoint_store {
+            let db_path = Path::new(&self.config.db_path);
+            checkpoint::maybe_import_checkpoint(&**store, &self.pool, db_path).await?;
+        }
+
         let started_at = Instant::now();
         let db = create_db(self.config.clone()).await.with_context(|| {
             format!(
@@ -42,6 +42,7 @@ pub(super) struct ConfigLabels {
     memtable_capacity: usize,
     #[metrics(unit = Unit::Seconds)]
     stalled_writes_timeout: DurationAsSecs,
+    node_cache_capacity: usize,
 }
 
 impl ConfigLabels {
@@ -54,6 +55,7 @@ impl ConfigLabels {
             block_cache_capacity: config.block_cache_capacity,
             memtable_capacity: config.memtable_capacity,
             stalled_writes_timeout: config.stalled_writes_timeout.into(),
+            node_cache_capacity: config.node_cache_capacity,
         }
     }
 }
@@ -181,6 +183,20 @@ impl MetadataCalculatorMetrics {
 #[vise::register]
 pub(super) static METRICS: vise::Global<MetadataCalculatorMetrics> = vise::Global::new();
 
+/// Metrics for Merkle tree read replicas.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "merkle_tree_replica")]
+pub(super) struct TreeReplicaMetrics {
+    /// Latency of catching up with the primary RocksDB instance.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub catch_up_latency: Histogram<Duration>,
+    /// Next L1 batch number to be processed by the tree as observed by the replica.
+    pub next_l1_batch_number: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static REPLICA_METRICS: vise::Global<TreeReplicaMetrics> = vise::Global::new();
+
 impl MetadataCalculator {
     pub(super) fn update_metrics(
         batch_headers: &[L1BatchHeader],
@@ -222,6 +238,7 @@ impl MetadataCalculator {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
 pub(super) enum RecoveryStage {
+    ImportCheckpoint,
     LoadChunkStarts,
     Finalize,
 }
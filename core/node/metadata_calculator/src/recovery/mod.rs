@@ -415,7 +415,7 @@ impl AsyncTreeRecovery {
     }
 }
 
-async fn get_snapshot_recovery(
+pub(super) async fn get_snapshot_recovery(
     pool: &ConnectionPool<Core>,
 ) -> anyhow::Result<Option<SnapshotRecoveryStatus>> {
     let mut storage = pool.connection_tagged("metadata_calculator").await?;
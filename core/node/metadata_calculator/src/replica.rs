@@ -0,0 +1,141 @@
+//! Read-only Merkle tree replica backed by a secondary RocksDB instance.
+
+use std::{path::Path, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_config::configs::database::{MerkleTreeConfig, MerkleTreeMode};
+use zksync_merkle_tree::RocksDBWrapper;
+use zksync_storage::{RocksDB, RocksDBOptions};
+
+use crate::{helpers::AsyncTreeReader, metrics::REPLICA_METRICS};
+
+/// Configuration of a [`MerkleTreeReplica`].
+#[derive(Debug, Clone)]
+pub struct MerkleTreeReplicaConfig {
+    /// Filesystem path to the RocksDB instance written by the primary tree (i.e., by the metadata calculator).
+    pub db_path: String,
+    /// Filesystem path to store secondary instance info. Must be unique for each replica.
+    pub secondary_db_path: String,
+    /// Capacity of the RocksDB block cache in bytes.
+    pub block_cache_capacity: usize,
+    /// Chunk size for multi-get operations.
+    pub multi_get_chunk_size: usize,
+    /// Capacity of the LRU cache for internal tree nodes, measured in the number of nodes.
+    pub node_cache_capacity: usize,
+    /// Interval between catching up with the primary RocksDB instance.
+    pub catch_up_interval: Duration,
+}
+
+impl MerkleTreeReplicaConfig {
+    pub fn new(
+        merkle_tree_config: &MerkleTreeConfig,
+        secondary_db_path: String,
+        catch_up_interval: Duration,
+    ) -> Self {
+        Self {
+            db_path: merkle_tree_config.path.clone(),
+            secondary_db_path,
+            block_cache_capacity: merkle_tree_config.block_cache_size(),
+            multi_get_chunk_size: merkle_tree_config.multi_get_chunk_size,
+            node_cache_capacity: merkle_tree_config.node_cache_capacity(),
+            catch_up_interval,
+        }
+    }
+}
+
+/// Read-only replica of the Merkle tree. The replica opens the tree RocksDB as a secondary instance
+/// and periodically catches up with the primary instance (i.e., the one written by [`MetadataCalculator`]).
+/// It allows serving the tree API from a separate process, so that proof requests don't compete
+/// with tree updates.
+///
+/// The replica must run on the same filesystem as the primary tree; it doesn't require access to Postgres.
+///
+/// [`MetadataCalculator`]: crate::MetadataCalculator
+#[derive(Debug)]
+pub struct MerkleTreeReplica {
+    reader: AsyncTreeReader,
+    catch_up_interval: Duration,
+}
+
+impl MerkleTreeReplica {
+    /// Opens the tree RocksDB in the secondary mode.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors, and returns an error if the tree is not in the full mode.
+    pub async fn new(
+        config: MerkleTreeReplicaConfig,
+        mode: MerkleTreeMode,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            matches!(mode, MerkleTreeMode::Full),
+            "Merkle tree replicas can only serve full trees"
+        );
+        let catch_up_interval = config.catch_up_interval;
+        let db = tokio::task::spawn_blocking(move || create_secondary_db_sync(&config))
+            .await
+            .context("panicked creating secondary Merkle tree RocksDB")??;
+        let reader = AsyncTreeReader::new_full(db)?;
+        Ok(Self {
+            reader,
+            catch_up_interval,
+        })
+    }
+
+    /// Returns a reader for the replicated tree. The reader can be used to run the tree API server.
+    pub fn tree_reader(&self) -> AsyncTreeReader {
+        self.reader.clone()
+    }
+
+    /// Runs the catch-up loop until a stop signal is received.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            let latency = REPLICA_METRICS.catch_up_latency.start();
+            self.reader.clone().catch_up_with_primary().await?;
+            latency.observe();
+
+            let info = self.reader.clone().info().await;
+            tracing::trace!("Caught up with primary Merkle tree: {info:?}");
+            REPLICA_METRICS
+                .next_l1_batch_number
+                .set(info.next_l1_batch_number.0.into());
+
+            if tokio::time::timeout(self.catch_up_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, Merkle tree replica is shutting down");
+        Ok(())
+    }
+}
+
+fn create_secondary_db_sync(config: &MerkleTreeReplicaConfig) -> anyhow::Result<RocksDBWrapper> {
+    let path = Path::new(&config.db_path);
+    let secondary_path = Path::new(&config.secondary_db_path);
+    tracing::info!(
+        "Initializing secondary Merkle tree database at `{}` (primary at `{}`) with {}B block cache, \
+         {} multi-get chunk size, {} node cache capacity",
+        secondary_path.display(),
+        path.display(),
+        config.block_cache_capacity,
+        config.multi_get_chunk_size,
+        config.node_cache_capacity
+    );
+
+    let db = RocksDB::with_options_as_secondary(
+        path,
+        secondary_path,
+        RocksDBOptions {
+            block_cache_capacity: Some(config.block_cache_capacity),
+            ..RocksDBOptions::default()
+        },
+    )?;
+    let mut db = RocksDBWrapper::from(db);
+    db.set_multi_get_chunk_size(config.multi_get_chunk_size);
+    db.set_node_cache_capacity(config.node_cache_capacity);
+    Ok(db)
+}
@@ -57,6 +57,7 @@ pub(super) fn mock_config(db_path: &Path) -> MetadataCalculatorConfig {
         include_indices_and_filters_in_block_cache: false,
         memtable_capacity: 16 << 20,            // 16 MiB
         stalled_writes_timeout: Duration::ZERO, // writes should never be stalled in tests
+        node_cache_capacity: 1_000,
         sealed_batches_have_protective_reads: true,
         recovery: MetadataCalculatorRecoveryConfig::default(),
     }
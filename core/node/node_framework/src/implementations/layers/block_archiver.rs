@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_anchoring::HttpIpfsClient;
+use zksync_config::configs::block_archiver::BlockArchiverConfig;
+use zksync_node_sync::block_archive::BlockArchiveWriter;
+
+use crate::{
+    implementations::resources::{
+        object_store::ObjectStoreResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`BlockArchiveWriter`], which archives L2 blocks to the object store
+/// (and optionally pins them to IPFS), so that external nodes can sync from the archive.
+#[derive(Debug)]
+pub struct BlockArchiverLayer {
+    config: BlockArchiverConfig,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+    pub object_store: ObjectStoreResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    #[context(task)]
+    pub writer: BlockArchiveWriter,
+}
+
+impl BlockArchiverLayer {
+    pub fn new(config: BlockArchiverConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BlockArchiverLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "block_archiver_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get_singleton().await?;
+        let ObjectStoreResource(object_store) = input.object_store;
+        let mut writer = BlockArchiveWriter::new(pool, object_store)
+            .with_poll_interval(self.config.poll_interval());
+        if self.config.upload_to_ipfs {
+            let ipfs = HttpIpfsClient::from_env()
+                .context("failed creating IPFS client")
+                .map_err(WiringError::Internal)?;
+            writer = writer.with_ipfs(Arc::new(ipfs));
+        }
+        Ok(Output { writer })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for BlockArchiveWriter {
+    fn id(&self) -> TaskId {
+        "block_archiver".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -118,6 +118,7 @@ impl Task for ExternalNodeTask {
                 self.pool,
                 self.sync_state,
                 self.main_node_client,
+                None,
                 self.action_queue_sender,
             ));
             let _ = stop_receiver.0.wait_for(|stop| *stop).await?;
@@ -5,10 +5,15 @@ use std::{
 };
 
 use anyhow::Context as _;
-use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
+use zksync_config::{
+    configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode},
+    ObjectStoreConfig,
+};
 use zksync_metadata_calculator::{
     LazyAsyncTreeReader, MerkleTreePruningTask, MetadataCalculator, MetadataCalculatorConfig,
+    TreeCheckpointExportTask,
 };
+use zksync_object_store::ObjectStoreFactory;
 use zksync_storage::RocksDB;
 
 use crate::{
@@ -30,6 +35,8 @@ pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
+    checkpoint_export_interval: Option<Duration>,
+    checkpoint_store_config: Option<ObjectStoreConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -55,6 +62,9 @@ pub struct Output {
     /// Only provided if configuration is provided.
     #[context(task)]
     pub pruning_task: Option<MerkleTreePruningTask>,
+    /// Only provided if checkpoint export is enabled.
+    #[context(task)]
+    pub checkpoint_export_task: Option<TreeCheckpointExportTask>,
     pub rocksdb_shutdown_hook: ShutdownHook,
 }
 
@@ -64,6 +74,8 @@ impl MetadataCalculatorLayer {
             config,
             tree_api_config: None,
             pruning_config: None,
+            checkpoint_export_interval: None,
+            checkpoint_store_config: None,
         }
     }
 
@@ -76,6 +88,18 @@ impl MetadataCalculatorLayer {
         self.pruning_config = Some(pruning_config);
         self
     }
+
+    /// Enables exporting tree checkpoints to the object store. Requires the full tree mode.
+    pub fn with_checkpoint_export(mut self, poll_interval: Duration) -> Self {
+        self.checkpoint_export_interval = Some(poll_interval);
+        self
+    }
+
+    /// Enables importing tree checkpoints from the object store with the specified config during snapshot recovery.
+    pub fn with_checkpoint_store_config(mut self, config: ObjectStoreConfig) -> Self {
+        self.checkpoint_store_config = Some(config);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -106,13 +130,32 @@ impl WiringLayer for MetadataCalculatorLayer {
             }
         };
 
-        let mut metadata_calculator = MetadataCalculator::new(
-            self.config,
-            object_store.map(|store_resource| store_resource.0),
-            main_pool,
-        )
-        .await?
-        .with_recovery_pool(recovery_pool);
+        let object_store = object_store.map(|store_resource| store_resource.0);
+        let mut metadata_calculator =
+            MetadataCalculator::new(self.config, object_store.clone(), main_pool)
+                .await?
+                .with_recovery_pool(recovery_pool);
+        if let Some(store_config) = self.checkpoint_store_config {
+            let store = ObjectStoreFactory::new(store_config)
+                .create_store()
+                .await
+                .map_err(WiringError::internal)?;
+            metadata_calculator = metadata_calculator.with_checkpoint_store(store);
+        }
+
+        let checkpoint_export_task = self
+            .checkpoint_export_interval
+            .map(|poll_interval| {
+                let store = object_store.clone().ok_or_else(|| {
+                    WiringError::Configuration(
+                        "Tree checkpoint export requires full Merkle tree mode".into(),
+                    )
+                })?;
+                Ok::<_, WiringError>(
+                    metadata_calculator.checkpoint_export_task(store, poll_interval),
+                )
+            })
+            .transpose()?;
 
         app_health
             .insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))
@@ -154,6 +197,7 @@ impl WiringLayer for MetadataCalculatorLayer {
             tree_api_client,
             tree_api_task,
             pruning_task,
+            checkpoint_export_task,
             rocksdb_shutdown_hook,
         })
     }
@@ -195,6 +239,17 @@ impl Task for TreeApiTask {
     }
 }
 
+#[async_trait::async_trait]
+impl Task for TreeCheckpointExportTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_checkpoint_export_task".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 #[async_trait::async_trait]
 impl Task for MerkleTreePruningTask {
     fn id(&self) -> TaskId {
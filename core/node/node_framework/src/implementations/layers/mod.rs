@@ -1,6 +1,7 @@
 pub mod base_token_ratio_persister;
 pub mod base_token_ratio_provider;
 pub mod batch_status_updater;
+pub mod block_archiver;
 pub mod circuit_breaker_checker;
 pub mod commitment_generator;
 pub mod consensus;
@@ -25,10 +26,12 @@ pub mod proof_data_handler;
 pub mod pruning;
 pub mod query_eth_client;
 pub mod sigint;
+pub mod signing_eth_client;
 pub mod state_keeper;
 pub mod sync_state_updater;
 pub mod tee_verifier_input_producer;
 pub mod tree_data_fetcher;
+pub mod tx_allowlist;
 pub mod validate_chain_ids;
 pub mod vm_runner;
 pub mod web3_api;
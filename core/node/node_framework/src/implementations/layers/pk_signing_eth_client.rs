@@ -1,9 +1,11 @@
 use anyhow::Context as _;
+use secrecy::ExposeSecret;
 use zksync_config::{
     configs::{wallets, ContractsConfig},
     EthConfig,
 };
 use zksync_eth_client::clients::PKSigningClient;
+use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::L1ChainId;
 
 use crate::{
@@ -20,7 +22,13 @@ pub struct PKSigningEthClientLayer {
     eth_sender_config: EthConfig,
     contracts_config: ContractsConfig,
     l1_chain_id: L1ChainId,
-    wallets: wallets::EthSender,
+    wallets: OperatorWallets,
+}
+
+#[derive(Debug)]
+enum OperatorWallets {
+    Plain(wallets::EthSender),
+    Keystores(wallets::KeystoreEthSender),
 }
 
 #[derive(Debug, FromContext)]
@@ -48,11 +56,41 @@ impl PKSigningEthClientLayer {
             eth_sender_config,
             contracts_config,
             l1_chain_id,
-            wallets,
+            wallets: OperatorWallets::Plain(wallets),
+        }
+    }
+
+    /// Creates a layer with operator keys decrypted from keystores during wiring.
+    pub fn from_keystores(
+        eth_sender_config: EthConfig,
+        contracts_config: ContractsConfig,
+        l1_chain_id: L1ChainId,
+        wallets: wallets::KeystoreEthSender,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            l1_chain_id,
+            wallets: OperatorWallets::Keystores(wallets),
         }
     }
 }
 
+fn load_keystore(wallet: &wallets::KeystoreWallet) -> anyhow::Result<PrivateKeySigner> {
+    let password = wallet.password.read()?;
+    let signer = PrivateKeySigner::from_keystore(&wallet.path, password.expose_secret())
+        .with_context(|| format!("failed loading keystore {}", wallet.path.display()))?;
+    if let Some(address) = wallet.address {
+        anyhow::ensure!(
+            signer.address() == address,
+            "keystore {} contains key for {:?}, expected {address:?}",
+            wallet.path.display(),
+            signer.address()
+        );
+    }
+    Ok(signer)
+}
+
 #[async_trait::async_trait]
 impl WiringLayer for PKSigningEthClientLayer {
     type Input = Input;
@@ -63,7 +101,24 @@ impl WiringLayer for PKSigningEthClientLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
-        let private_key = self.wallets.operator.private_key();
+        let (operator, blob_operator) = match &self.wallets {
+            OperatorWallets::Plain(wallets) => (
+                PrivateKeySigner::new(wallets.operator.private_key().clone()),
+                wallets
+                    .blob_operator
+                    .as_ref()
+                    .map(|wallet| PrivateKeySigner::new(wallet.private_key().clone())),
+            ),
+            OperatorWallets::Keystores(wallets) => (
+                load_keystore(&wallets.operator).context("operator")?,
+                wallets
+                    .blob_operator
+                    .as_ref()
+                    .map(load_keystore)
+                    .transpose()
+                    .context("blob_operator")?,
+            ),
+        };
         let gas_adjuster_config = self
             .eth_sender_config
             .gas_adjuster
@@ -71,8 +126,8 @@ impl WiringLayer for PKSigningEthClientLayer {
             .context("gas_adjuster config is missing")?;
         let EthInterfaceResource(query_client) = input.eth_client;
 
-        let signing_client = PKSigningClient::new_raw(
-            private_key.clone(),
+        let signing_client = PKSigningClient::from_signer(
+            operator,
             self.contracts_config.diamond_proxy_addr,
             gas_adjuster_config.default_priority_fee_per_gas,
             self.l1_chain_id,
@@ -80,10 +135,9 @@ impl WiringLayer for PKSigningEthClientLayer {
         );
         let signing_client = BoundEthInterfaceResource(Box::new(signing_client));
 
-        let signing_client_for_blobs = self.wallets.blob_operator.map(|blob_operator| {
-            let private_key = blob_operator.private_key();
-            let signing_client_for_blobs = PKSigningClient::new_raw(
-                private_key.clone(),
+        let signing_client_for_blobs = blob_operator.map(|blob_operator| {
+            let signing_client_for_blobs = PKSigningClient::from_signer(
+                blob_operator,
                 self.contracts_config.diamond_proxy_addr,
                 gas_adjuster_config.default_priority_fee_per_gas,
                 self.l1_chain_id,
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig, RetentionPolicy};
 
 use crate::{
     implementations::resources::{
@@ -19,6 +19,9 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    retention: RetentionPolicy,
+    wait_for_mintlayer_anchoring: bool,
+    dry_run: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,8 +49,29 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            retention: RetentionPolicy::default(),
+            wait_for_mintlayer_anchoring: false,
+            dry_run: false,
         }
     }
+
+    /// Sets the retention policy for data that may outlive pruned L1 batches.
+    pub fn with_retention_policy(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Makes the pruner wait until L1 batches are anchored on Mintlayer before pruning them.
+    pub fn with_mintlayer_anchoring(mut self, wait_for_mintlayer_anchoring: bool) -> Self {
+        self.wait_for_mintlayer_anchoring = wait_for_mintlayer_anchoring;
+        self
+    }
+
+    /// Makes the pruner only report what would be pruned without modifying the database.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -67,6 +91,9 @@ impl WiringLayer for PruningLayer {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
                 minimum_l1_batch_age: self.minimum_l1_batch_age,
+                retention: self.retention,
+                wait_for_mintlayer_anchoring: self.wait_for_mintlayer_anchoring,
+                dry_run: self.dry_run,
             },
             main_pool,
         );
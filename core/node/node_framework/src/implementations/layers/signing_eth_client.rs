@@ -0,0 +1,103 @@
+use anyhow::Context as _;
+use zksync_config::{
+    configs::{wallets, ContractsConfig},
+    EthConfig,
+};
+use zksync_eth_client::clients::RemoteSigningClient;
+use zksync_types::L1ChainId;
+
+use crate::{
+    implementations::resources::eth_interface::{
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource, EthInterfaceResource,
+    },
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for [`RemoteSigningClient`]. Unlike [`PKSigningEthClientLayer`], operator keys
+/// are not stored by the node; all L1 transactions are signed by a remote signing service.
+///
+/// [`PKSigningEthClientLayer`]: super::pk_signing_eth_client::PKSigningEthClientLayer
+#[derive(Debug)]
+pub struct SigningEthClientLayer {
+    eth_sender_config: EthConfig,
+    contracts_config: ContractsConfig,
+    l1_chain_id: L1ChainId,
+    wallets: wallets::RemoteEthSender,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub eth_client: EthInterfaceResource,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub signing_client: BoundEthInterfaceResource,
+    /// Only provided if the blob operator address is provided to the layer.
+    pub signing_client_for_blobs: Option<BoundEthInterfaceForBlobsResource>,
+}
+
+impl SigningEthClientLayer {
+    pub fn new(
+        eth_sender_config: EthConfig,
+        contracts_config: ContractsConfig,
+        l1_chain_id: L1ChainId,
+        wallets: wallets::RemoteEthSender,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            l1_chain_id,
+            wallets,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SigningEthClientLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "signing_eth_client_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let gas_adjuster_config = self
+            .eth_sender_config
+            .gas_adjuster
+            .as_ref()
+            .context("gas_adjuster config is missing")?;
+        let EthInterfaceResource(query_client) = input.eth_client;
+
+        let signing_client = RemoteSigningClient::new_raw(
+            self.wallets.signer_url.clone(),
+            self.wallets.operator.address(),
+            self.contracts_config.diamond_proxy_addr,
+            gas_adjuster_config.default_priority_fee_per_gas,
+            self.l1_chain_id,
+            query_client.clone(),
+        );
+        let signing_client = BoundEthInterfaceResource(Box::new(signing_client));
+
+        let signing_client_for_blobs = self.wallets.blob_operator.map(|blob_operator| {
+            let signing_client_for_blobs = RemoteSigningClient::new_raw(
+                self.wallets.signer_url,
+                blob_operator.address(),
+                self.contracts_config.diamond_proxy_addr,
+                gas_adjuster_config.default_priority_fee_per_gas,
+                self.l1_chain_id,
+                query_client,
+            );
+            BoundEthInterfaceForBlobsResource(Box::new(signing_client_for_blobs))
+        });
+
+        Ok(Output {
+            signing_client,
+            signing_client_for_blobs,
+        })
+    }
+}
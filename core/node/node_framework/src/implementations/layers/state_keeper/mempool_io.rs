@@ -3,14 +3,16 @@ use zksync_config::configs::{
     chain::{MempoolConfig, StateKeeperConfig},
     wallets,
 };
-use zksync_state_keeper::{MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer};
+use zksync_state_keeper::{
+    seal_criteria::SealCriterion, MempoolFetcher, MempoolGuard, MempoolIO, SequencerSealer,
+};
 use zksync_types::L2ChainId;
 
 use crate::{
     implementations::resources::{
         fee_input::FeeInputResource,
         pools::{MasterPool, PoolResource},
-        state_keeper::{ConditionalSealerResource, StateKeeperIOResource},
+        state_keeper::{ConditionalSealerResource, StateKeeperIOResource, TxAllowlistResource},
     },
     service::StopReceiver,
     task::{Task, TaskId},
@@ -24,6 +26,7 @@ use crate::{
 ///
 /// - `FeeInputResource`
 /// - `PoolResource<MasterPool>`
+/// - `TxAllowlistResource` (required if `StateKeeperConfig::tx_allowlist_enabled` is set)
 ///
 /// ## Adds resources
 ///
@@ -39,6 +42,7 @@ pub struct MempoolIOLayer {
     state_keeper_config: StateKeeperConfig,
     mempool_config: MempoolConfig,
     wallets: wallets::StateKeeper,
+    custom_seal_criteria: Vec<Box<dyn SealCriterion>>,
 }
 
 #[derive(Debug, FromContext)]
@@ -46,6 +50,7 @@ pub struct MempoolIOLayer {
 pub struct Input {
     pub fee_input: FeeInputResource,
     pub master_pool: PoolResource<MasterPool>,
+    pub tx_allowlist: Option<TxAllowlistResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -69,9 +74,46 @@ impl MempoolIOLayer {
             state_keeper_config,
             mempool_config,
             wallets,
+            custom_seal_criteria: Vec::new(),
         }
     }
 
+    /// Registers a custom seal criterion for the conditional sealer, in addition to the built-in criteria.
+    /// The criterion is only enabled if its name is listed in `StateKeeperConfig::custom_seal_criteria`.
+    pub fn with_seal_criterion(mut self, criterion: impl SealCriterion) -> Self {
+        self.custom_seal_criteria.push(Box::new(criterion));
+        self
+    }
+
+    /// Selects registered custom criteria enabled in the config.
+    fn enabled_seal_criteria(&mut self) -> Result<Vec<Box<dyn SealCriterion>>, WiringError> {
+        let enabled_names = &self.state_keeper_config.custom_seal_criteria;
+        if let Some(unknown_name) = enabled_names.iter().find(|name| {
+            !self
+                .custom_seal_criteria
+                .iter()
+                .any(|criterion| criterion.prom_criterion_name() == name.as_str())
+        }) {
+            return Err(WiringError::Configuration(format!(
+                "custom seal criterion `{unknown_name}` is enabled in the config, but not registered"
+            )));
+        }
+
+        let (enabled, disabled): (Vec<_>, Vec<_>) =
+            self.custom_seal_criteria.drain(..).partition(|criterion| {
+                enabled_names
+                    .iter()
+                    .any(|name| name == criterion.prom_criterion_name())
+            });
+        for criterion in &disabled {
+            tracing::info!(
+                "Custom seal criterion `{}` is registered, but not enabled in the config",
+                criterion.prom_criterion_name()
+            );
+        }
+        Ok(enabled)
+    }
+
     async fn build_mempool_guard(
         &self,
         master_pool: &PoolResource<MasterPool>,
@@ -84,7 +126,7 @@ impl MempoolIOLayer {
             .connection()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, self.mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, &self.mempool_config).await;
         mempool.register_metrics();
         Ok(mempool)
     }
@@ -99,7 +141,8 @@ impl WiringLayer for MempoolIOLayer {
         "mempool_io_layer"
     }
 
-    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+    async fn wire(mut self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let custom_seal_criteria = self.enabled_seal_criteria()?;
         let batch_fee_input_provider = input.fee_input.0;
         let master_pool = input.master_pool;
 
@@ -121,7 +164,7 @@ impl WiringLayer for MempoolIOLayer {
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -131,9 +174,18 @@ impl WiringLayer for MempoolIOLayer {
             self.zksync_network_id,
         )
         .await?;
+        if self.state_keeper_config.tx_allowlist_enabled {
+            let TxAllowlistResource(tx_allowlist) = input.tx_allowlist.ok_or_else(|| {
+                WiringError::Configuration(
+                    "Transaction allowlist is enabled, but `TxAllowlistResource` is missing".into(),
+                )
+            })?;
+            io = io.with_tx_allowlist(tx_allowlist);
+        }
 
         // Create sealer.
-        let sealer = SequencerSealer::new(self.state_keeper_config);
+        let sealer = SequencerSealer::new(self.state_keeper_config)
+            .with_custom_criteria(custom_seal_criteria);
 
         Ok(Output {
             state_keeper_io: io.into(),
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use anyhow::Context;
 use zksync_state::{AsyncCatchupTask, ReadStorageFactory};
 use zksync_state_keeper::{
-    seal_criteria::ConditionalSealer, AsyncRocksdbCache, BatchExecutor, OutputHandler,
-    StateKeeperIO, ZkSyncStateKeeper,
+    allowlist::TxAllowlistCache, seal_criteria::ConditionalSealer, AsyncRocksdbCache,
+    BatchExecutor, OutputHandler, StateKeeperIO, ZkSyncStateKeeper,
 };
 use zksync_storage::RocksDB;
 
@@ -21,7 +21,7 @@ use crate::{
         pools::{MasterPool, PoolResource},
         state_keeper::{
             BatchExecutorResource, ConditionalSealerResource, OutputHandlerResource,
-            StateKeeperIOResource,
+            StateKeeperIOResource, TxAllowlistResource,
         },
     },
     service::{ShutdownHook, StopReceiver},
@@ -45,6 +45,7 @@ pub struct Input {
     pub output_handler: OutputHandlerResource,
     pub conditional_sealer: ConditionalSealerResource,
     pub master_pool: PoolResource<MasterPool>,
+    pub tx_allowlist: Option<TxAllowlistResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -106,6 +107,7 @@ impl WiringLayer for StateKeeperLayer {
             output_handler,
             sealer,
             storage_factory: Arc::new(storage_factory),
+            tx_allowlist: input.tx_allowlist.map(|resource| resource.0),
         };
 
         let rocksdb_termination_hook = ShutdownHook::new("rocksdb_terminaton", async {
@@ -129,6 +131,7 @@ pub struct StateKeeperTask {
     output_handler: OutputHandler,
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
+    tx_allowlist: Option<TxAllowlistCache>,
 }
 
 #[async_trait::async_trait]
@@ -138,7 +141,7 @@ impl Task for StateKeeperTask {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        let state_keeper = ZkSyncStateKeeper::new(
+        let mut state_keeper = ZkSyncStateKeeper::new(
             stop_receiver.0,
             self.io,
             self.batch_executor_base,
@@ -146,6 +149,9 @@ impl Task for StateKeeperTask {
             self.sealer,
             self.storage_factory,
         );
+        if let Some(tx_allowlist) = self.tx_allowlist {
+            state_keeper = state_keeper.with_tx_allowlist(tx_allowlist);
+        }
         state_keeper.run().await
     }
 }
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use zksync_state_keeper::allowlist::{TxAllowlistCache, TxAllowlistUpdater};
+
+use crate::{
+    implementations::resources::{
+        pools::{MasterPool, PoolResource},
+        state_keeper::TxAllowlistResource,
+    },
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+/// Wiring layer for the transaction allowlist. If added, both the state keeper and the API server
+/// only accept transactions permitted by the allowlist stored in Postgres.
+///
+/// Must be added before `MempoolIOLayer`, `StateKeeperLayer` and `TxSenderLayer` so that they can pick up
+/// the allowlist.
+///
+/// ## Requests resources
+///
+/// - `PoolResource<MasterPool>`
+///
+/// ## Adds resources
+///
+/// - `TxAllowlistResource`
+///
+/// ## Adds tasks
+///
+/// - `TxAllowlistUpdater`
+#[derive(Debug)]
+pub struct TxAllowlistLayer {
+    reload_interval: Duration,
+}
+
+impl TxAllowlistLayer {
+    pub fn new(reload_interval: Duration) -> Self {
+        Self { reload_interval }
+    }
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+pub struct Input {
+    pub master_pool: PoolResource<MasterPool>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+pub struct Output {
+    pub tx_allowlist: TxAllowlistResource,
+    #[context(task)]
+    pub updater: TxAllowlistUpdater,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for TxAllowlistLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "tx_allowlist_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.master_pool.get_singleton().await?;
+        let cache = TxAllowlistCache::new(&pool)
+            .await
+            .context("failed loading tx allowlist")?;
+        let updater = cache.updater(pool, self.reload_interval);
+        Ok(Output {
+            tx_allowlist: cache.into(),
+            updater,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for TxAllowlistUpdater {
+    fn id(&self) -> TaskId {
+        "tx_allowlist_updater".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
         fee_input::FeeInputResource,
         main_node_client::MainNodeClientResource,
         pools::{PoolResource, ReplicaPool},
-        state_keeper::ConditionalSealerResource,
+        state_keeper::{ConditionalSealerResource, TxAllowlistResource},
         web3_api::{TxSenderResource, TxSinkResource},
     },
     service::StopReceiver,
@@ -42,6 +42,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `TxSinkResource`
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
+/// - `TxAllowlistResource` (required if the allowlist is enabled)
 /// - `FeeInputResource`
 ///
 /// ## Adds resources
@@ -60,6 +61,7 @@ pub struct TxSenderLayer {
     max_vm_concurrency: usize,
     api_contracts: ApiContracts,
     whitelisted_tokens_for_aa_cache: bool,
+    tx_allowlist: bool,
 }
 
 #[derive(Debug, FromContext)]
@@ -70,6 +72,7 @@ pub struct Input {
     pub fee_input: FeeInputResource,
     pub main_node_client: Option<MainNodeClientResource>,
     pub sealer: Option<ConditionalSealerResource>,
+    pub tx_allowlist: Option<TxAllowlistResource>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -97,6 +100,7 @@ impl TxSenderLayer {
             max_vm_concurrency,
             api_contracts,
             whitelisted_tokens_for_aa_cache: false,
+            tx_allowlist: false,
         }
     }
 
@@ -108,6 +112,15 @@ impl TxSenderLayer {
         self.whitelisted_tokens_for_aa_cache = value;
         self
     }
+
+    /// Makes `TxSender` reject transactions not permitted by the transaction allowlist.
+    /// Disabled by default.
+    ///
+    /// Requires `TxAllowlistResource` to be present.
+    pub fn with_tx_allowlist(mut self, value: bool) -> Self {
+        self.tx_allowlist = value;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -153,6 +166,14 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if self.tx_allowlist {
+            let TxAllowlistResource(tx_allowlist) = input.tx_allowlist.ok_or_else(|| {
+                WiringError::Configuration(
+                    "Transaction allowlist is enabled, but `TxAllowlistResource` is missing".into(),
+                )
+            })?;
+            tx_sender = tx_sender.with_tx_allowlist(tx_allowlist);
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         let whitelisted_tokens_for_aa_update_task = if self.whitelisted_tokens_for_aa_cache {
@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use zksync_state_keeper::{
-    seal_criteria::ConditionalSealer, BatchExecutor, OutputHandler, StateKeeperIO,
+    allowlist::TxAllowlistCache, seal_criteria::ConditionalSealer, BatchExecutor, OutputHandler,
+    StateKeeperIO,
 };
 
 use crate::resource::{Resource, Unique};
@@ -75,3 +76,19 @@ where
         Self(Arc::new(sealer))
     }
 }
+
+/// A resource that provides the transaction allowlist shared by the state keeper and the API server.
+#[derive(Debug, Clone)]
+pub struct TxAllowlistResource(pub TxAllowlistCache);
+
+impl Resource for TxAllowlistResource {
+    fn name() -> String {
+        "state_keeper/tx_allowlist".into()
+    }
+}
+
+impl From<TxAllowlistCache> for TxAllowlistResource {
+    fn from(cache: TxAllowlistCache) -> Self {
+        Self(cache)
+    }
+}
@@ -0,0 +1,29 @@
+//! Metrics for L1 batch verifier.
+
+use serde::Serialize;
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics};
+
+/// Kind of check performed by the verifier. Also used in the verifier health details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet, Serialize)]
+#[metrics(label = "check", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub(super) enum CheckKind {
+    L1Commitment,
+    MintlayerAnchor,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_node_batch_verifier")]
+pub(super) struct BatchVerifierMetrics {
+    /// Last L1 batch verified against its L1 commitment.
+    pub last_verified_batch_number: Gauge<u64>,
+    /// Last L1 batch with a verified Mintlayer anchor.
+    pub last_anchored_batch_number: Gauge<u64>,
+    /// Number of detected inconsistencies between main node data and L1 / Mintlayer data.
+    pub inconsistencies: Family<CheckKind, Counter>,
+    /// Number of verified L1 batches that were dropped because the main node has diverged from them.
+    pub dropped_batches: Counter,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<BatchVerifierMetrics> = vise::Global::new();
@@ -0,0 +1,578 @@
+//! Verifier checking L1 batches served by the main node against their L1 commitments. Used by the verify-only
+//! external node mode.
+
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_anchoring::{IpfsClient, MintlayerClient};
+use zksync_dal::{
+    verified_l1_batches_dal::VerifiedL1Batch, ConnectionPool, Core, CoreDal, DalError,
+};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_types::{Address, L1BatchNumber};
+use zksync_web3_decl::{
+    client::{DynClient, L1, L2},
+    error::EnrichedClientError,
+};
+
+use self::{
+    metrics::{CheckKind, METRICS},
+    sources::{
+        AnchoredCommitOperation, BlockCommitEvent, CommitTxStatus, L1CommitClient,
+        VerifierL1Client, VerifierMainNodeClient,
+    },
+};
+
+mod metrics;
+mod sources;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, thiserror::Error)]
+enum BatchVerifierError {
+    #[error("error fetching data: {0}")]
+    Rpc(#[from] EnrichedClientError),
+    #[error("error fetching anchored data: {0:#}")]
+    Anchoring(#[source] anyhow::Error),
+    #[error("internal error: {0:#}")]
+    Internal(#[from] anyhow::Error),
+}
+
+impl From<DalError> for BatchVerifierError {
+    fn from(err: DalError) -> Self {
+        Self::Internal(err.generalize())
+    }
+}
+
+impl BatchVerifierError {
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Rpc(err) => err.is_transient(),
+            // Mintlayer API server and IPFS gateway errors are not classified, so we consider all of them transient.
+            Self::Anchoring(_) => true,
+            Self::Internal(_) => false,
+        }
+    }
+}
+
+type BatchVerifierResult<T> = Result<T, BatchVerifierError>;
+
+/// Inconsistency between data served by the main node and data committed on L1 or anchored on Mintlayer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct Inconsistency {
+    check: CheckKind,
+    l1_batch: L1BatchNumber,
+    reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct BatchVerifierHealth {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_verified_l1_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_anchored_l1_batch: Option<L1BatchNumber>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    inconsistencies: Vec<Inconsistency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<&BatchVerifierHealth> for Health {
+    fn from(health: &BatchVerifierHealth) -> Self {
+        let status = if health.inconsistencies.is_empty() && health.error.is_none() {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        Self::from(status).with_details(health)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum CheckOutcome {
+    Verified(L1BatchNumber),
+    NoProgress,
+    Inconsistency(Inconsistency),
+}
+
+impl CheckOutcome {
+    fn inconsistency(check: CheckKind, l1_batch: L1BatchNumber, reason: String) -> Self {
+        Self::Inconsistency(Inconsistency {
+            check,
+            l1_batch,
+            reason,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct AnchoringClients {
+    mintlayer: Arc<dyn MintlayerClient>,
+    ipfs: Arc<dyn IpfsClient>,
+}
+
+/// Component verifying L1 batches served by the main node without executing them or maintaining the node state.
+///
+/// # Overview
+///
+/// For each L1 batch, the verifier fetches its root hash, metadata and commit transaction from the main node and checks
+/// that the transaction has succeeded in a finalized L1 block and emitted a `BlockCommit` event with the same root hash.
+/// The commitment in the event must match the commitment recomputed from the metadata, i.e. from the tree data
+/// (root hash and last leaf index), meta parameters (bootloader and default AA code hashes) and the auxiliary
+/// output hash. Headers of verified batches (root hash, commitment and commit transaction hash) are persisted
+/// to Postgres; no other data is stored.
+///
+/// Before verifying the next batch, the verifier checks that the main node still serves the last verified batch
+/// with the same root hash, similarly to the reorg detector. If the main node has diverged, headers of diverged
+/// batches are dropped, and these batches are verified again.
+///
+/// Optionally, the verifier checks that commit operations are anchored on Mintlayer: the Mintlayer transaction
+/// returned by the main node must deposit the IPFS hash of the operation, and the operation pinned to IPFS must contain
+/// the same root hash and commitment as committed on L1.
+///
+/// Detected inconsistencies are not fatal; they are logged and reported in the component health.
+#[derive(Debug)]
+pub struct BatchVerifier {
+    main_node_client: Box<dyn VerifierMainNodeClient>,
+    l1_client: Box<dyn VerifierL1Client>,
+    anchoring: Option<AnchoringClients>,
+    pool: ConnectionPool<Core>,
+    first_l1_batch: L1BatchNumber,
+    health: BatchVerifierHealth,
+    health_updater: HealthUpdater,
+    poll_interval: Duration,
+}
+
+impl BatchVerifier {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    /// Creates a verifier connected to the main node and L1.
+    pub fn new(
+        main_node_client: Box<DynClient<L2>>,
+        eth_client: Box<DynClient<L1>>,
+        diamond_proxy_address: Address,
+        pool: ConnectionPool<Core>,
+    ) -> anyhow::Result<Self> {
+        let l1_client = L1CommitClient::new(
+            eth_client.for_component("batch_verifier"),
+            diamond_proxy_address,
+        )?;
+        Ok(Self::from_parts(
+            Box::new(main_node_client.for_component("batch_verifier")),
+            Box::new(l1_client),
+            pool,
+        ))
+    }
+
+    fn from_parts(
+        main_node_client: Box<dyn VerifierMainNodeClient>,
+        l1_client: Box<dyn VerifierL1Client>,
+        pool: ConnectionPool<Core>,
+    ) -> Self {
+        Self {
+            main_node_client,
+            l1_client,
+            anchoring: None,
+            pool,
+            first_l1_batch: L1BatchNumber(1),
+            health: BatchVerifierHealth::default(),
+            health_updater: ReactiveHealthCheck::new("batch_verifier").1,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Sets the first L1 batch to verify if no batches are verified yet. By default, verification starts
+    /// from the first batch after genesis.
+    pub fn with_first_l1_batch(mut self, number: L1BatchNumber) -> Self {
+        self.first_l1_batch = number;
+        self
+    }
+
+    /// Enables checking Mintlayer anchors of L1 batch commitments.
+    pub fn with_anchor_checks(
+        mut self,
+        mintlayer: Arc<dyn MintlayerClient>,
+        ipfs: Arc<dyn IpfsClient>,
+    ) -> Self {
+        self.anchoring = Some(AnchoringClients { mintlayer, ipfs });
+        self
+    }
+
+    /// Returns a health check for this verifier.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn remote_root_hash_matches(&self, batch: &VerifiedL1Batch) -> BatchVerifierResult<bool> {
+        let remote_batch = self.main_node_client.fetch_l1_batch(batch.number).await?;
+        Ok(remote_batch.and_then(|remote| remote.root_hash) == Some(batch.root_hash))
+    }
+
+    async fn get_verified_batch(
+        &self,
+        number: L1BatchNumber,
+    ) -> BatchVerifierResult<VerifiedL1Batch> {
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        let batch = storage
+            .verified_l1_batches_dal()
+            .get_verified_l1_batch(number)
+            .await?
+            .with_context(|| format!("verified L1 batch #{number} disappeared from Postgres"))?;
+        Ok(batch)
+    }
+
+    /// Drops verified batches that diverge from the main node. Returns the number of dropped batches.
+    async fn drop_diverged_batches(&mut self) -> BatchVerifierResult<u64> {
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        let dal = &mut storage.verified_l1_batches_dal();
+        let Some(last_batch) = dal.get_last_verified_l1_batch().await? else {
+            return Ok(0);
+        };
+        let first_number = dal
+            .get_first_verified_l1_batch_number()
+            .await?
+            .context("verified L1 batches disappeared from Postgres")?;
+        drop(storage);
+
+        if self.remote_root_hash_matches(&last_batch).await? {
+            return Ok(0);
+        }
+        tracing::warn!(
+            "Root hash of the last verified L1 batch #{} differs from the one returned by the main node; \
+             looking for the last matching batch",
+            last_batch.number
+        );
+
+        let first_batch = self.get_verified_batch(first_number).await?;
+        let last_retained_number = if self.remote_root_hash_matches(&first_batch).await? {
+            // Invariant: `left` matches the main node, `right` doesn't.
+            let (mut left, mut right) = (first_number, last_batch.number);
+            while left + 1 < right {
+                let middle = L1BatchNumber((left.0 + right.0) / 2);
+                let middle_batch = self.get_verified_batch(middle).await?;
+                if self.remote_root_hash_matches(&middle_batch).await? {
+                    left = middle;
+                } else {
+                    right = middle;
+                }
+            }
+            left
+        } else {
+            let err = anyhow::anyhow!(
+                "Main node has diverged from all verified L1 batches, including the first one (#{first_number})"
+            );
+            L1BatchNumber(first_number.0.checked_sub(1).ok_or(err)?)
+        };
+
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        let dropped_count = storage
+            .verified_l1_batches_dal()
+            .delete_verified_l1_batches_after(last_retained_number)
+            .await?;
+        tracing::warn!(
+            "Dropped {dropped_count} verified L1 batches after #{last_retained_number} that diverge from the main node; \
+             they will be verified again"
+        );
+        METRICS.dropped_batches.inc_by(dropped_count);
+
+        let retained_number =
+            (last_retained_number >= first_number).then_some(last_retained_number);
+        self.health.last_verified_l1_batch = retained_number;
+        self.health.last_anchored_l1_batch =
+            self.health.last_anchored_l1_batch.min(retained_number);
+        Ok(dropped_count)
+    }
+
+    async fn verify_next_batch(&self) -> BatchVerifierResult<CheckOutcome> {
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        let last_verified_batch = storage
+            .verified_l1_batches_dal()
+            .get_last_verified_l1_batch()
+            .await?;
+        drop(storage);
+        let number = last_verified_batch.map_or(self.first_l1_batch, |batch| batch.number + 1);
+
+        let Some(remote_batch) = self.main_node_client.fetch_l1_batch(number).await? else {
+            tracing::debug!("L1 batch #{number} is not present on the main node yet");
+            return Ok(CheckOutcome::NoProgress);
+        };
+        let (Some(root_hash), Some(commit_tx_hash)) =
+            (remote_batch.root_hash, remote_batch.commit_tx_hash)
+        else {
+            tracing::debug!("L1 batch #{number} is not committed on L1 yet");
+            return Ok(CheckOutcome::NoProgress);
+        };
+
+        let check = CheckKind::L1Commitment;
+        let event = match self
+            .l1_client
+            .commit_tx_status(commit_tx_hash, number)
+            .await?
+        {
+            CommitTxStatus::NotIncluded => {
+                tracing::debug!(
+                    "Commit transaction {commit_tx_hash:?} for L1 batch #{number} is not included on L1 yet"
+                );
+                return Ok(CheckOutcome::NoProgress);
+            }
+            CommitTxStatus::NotFinalized => {
+                tracing::debug!(
+                    "Commit transaction {commit_tx_hash:?} for L1 batch #{number} is not finalized on L1 yet"
+                );
+                return Ok(CheckOutcome::NoProgress);
+            }
+            CommitTxStatus::Failed => {
+                let reason = format!("commit transaction {commit_tx_hash:?} has failed on L1");
+                return Ok(CheckOutcome::inconsistency(check, number, reason));
+            }
+            CommitTxStatus::MissingEvent => {
+                let reason = format!(
+                    "commit transaction {commit_tx_hash:?} did not emit a `BlockCommit` event for the batch"
+                );
+                return Ok(CheckOutcome::inconsistency(check, number, reason));
+            }
+            CommitTxStatus::Committed(event) => event,
+        };
+        if event.root_hash != root_hash {
+            let reason = format!(
+                "root hash returned by the main node ({root_hash:?}) differs from the one committed on L1 ({:?})",
+                event.root_hash
+            );
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        }
+
+        let Some(metadata) = self
+            .main_node_client
+            .fetch_l1_batch_metadata(number)
+            .await?
+        else {
+            let reason = "main node doesn't serve metadata for the committed batch".to_owned();
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        };
+        if metadata.root_hash != root_hash {
+            let reason = format!(
+                "main node returned metadata with root hash {:?}, which differs from batch details ({root_hash:?})",
+                metadata.root_hash
+            );
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        }
+        let recomputed_commitment = metadata.recompute_commitment();
+        if recomputed_commitment != event.commitment {
+            let reason = format!(
+                "commitment recomputed from batch metadata returned by the main node ({recomputed_commitment:?}) \
+                 differs from the one committed on L1 ({:?})",
+                event.commitment
+            );
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        }
+
+        let batch = VerifiedL1Batch {
+            number,
+            root_hash,
+            commitment: event.commitment,
+            commit_tx_hash,
+            anchor: None,
+        };
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        storage
+            .verified_l1_batches_dal()
+            .insert_verified_l1_batch(&batch)
+            .await?;
+        tracing::debug!("Verified L1 batch #{number}: {batch:?}");
+        Ok(CheckOutcome::Verified(number))
+    }
+
+    async fn verify_next_anchor(
+        &self,
+        clients: &AnchoringClients,
+    ) -> BatchVerifierResult<CheckOutcome> {
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        let batch = storage
+            .verified_l1_batches_dal()
+            .get_first_unanchored_l1_batch()
+            .await?;
+        drop(storage);
+        let Some(batch) = batch else {
+            return Ok(CheckOutcome::NoProgress);
+        };
+        let number = batch.number;
+
+        let Some(anchor) = self.main_node_client.fetch_l1_batch_anchor(number).await? else {
+            tracing::debug!("L1 batch #{number} is not anchored on Mintlayer yet");
+            return Ok(CheckOutcome::NoProgress);
+        };
+        let check = CheckKind::MintlayerAnchor;
+        let deposited_data = clients
+            .mintlayer
+            .fetch_deposited_data(&anchor.mintlayer_tx_id)
+            .await
+            .map_err(BatchVerifierError::Anchoring)?;
+        // The main node deposits a comma-separated list of IPFS hashes.
+        let deposited_data = String::from_utf8_lossy(&deposited_data);
+        if !deposited_data
+            .split(',')
+            .any(|ipfs_hash| ipfs_hash == anchor.ipfs_hash)
+        {
+            let reason = format!(
+                "Mintlayer transaction {} does not deposit IPFS hash {}",
+                anchor.mintlayer_tx_id, anchor.ipfs_hash
+            );
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        }
+
+        let operation = clients
+            .ipfs
+            .fetch(&anchor.ipfs_hash)
+            .await
+            .map_err(BatchVerifierError::Anchoring)?;
+        let operation: AnchoredCommitOperation = match serde_json::from_slice(&operation) {
+            Ok(operation) => operation,
+            Err(err) => {
+                let reason = format!(
+                    "IPFS object {} is not a commit operation: {err}",
+                    anchor.ipfs_hash
+                );
+                return Ok(CheckOutcome::inconsistency(check, number, reason));
+            }
+        };
+        let (_, anchored_batches, _) = operation.commit;
+        let Some(anchored_batch) = anchored_batches
+            .into_iter()
+            .find(|anchored| anchored.header.number == number)
+        else {
+            let reason = format!(
+                "commit operation in IPFS object {} does not include the batch",
+                anchor.ipfs_hash
+            );
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        };
+        let committed = BlockCommitEvent {
+            root_hash: batch.root_hash,
+            commitment: batch.commitment,
+        };
+        if anchored_batch.metadata != committed {
+            let reason = format!(
+                "anchored root hash and commitment ({:?}) differ from the ones committed on L1 ({committed:?})",
+                anchored_batch.metadata
+            );
+            return Ok(CheckOutcome::inconsistency(check, number, reason));
+        }
+
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        storage
+            .verified_l1_batches_dal()
+            .set_anchor(number, &anchor)
+            .await?;
+        tracing::debug!("Verified Mintlayer anchor for L1 batch #{number}: {anchor:?}");
+        Ok(CheckOutcome::Verified(number))
+    }
+
+    /// Updates health details based on the check outcome. Returns whether the check has made progress.
+    fn process_outcome(&mut self, check: CheckKind, outcome: CheckOutcome) -> bool {
+        match outcome {
+            CheckOutcome::Verified(number) => {
+                self.health.inconsistencies.retain(|inc| inc.check != check);
+                match check {
+                    CheckKind::L1Commitment => {
+                        self.health.last_verified_l1_batch = Some(number);
+                        METRICS.last_verified_batch_number.set(number.0.into());
+                    }
+                    CheckKind::MintlayerAnchor => {
+                        self.health.last_anchored_l1_batch = Some(number);
+                        METRICS.last_anchored_batch_number.set(number.0.into());
+                    }
+                }
+                true
+            }
+            CheckOutcome::NoProgress => {
+                // The inconsistent batch may have been rolled back on the main node.
+                self.health.inconsistencies.retain(|inc| inc.check != check);
+                false
+            }
+            CheckOutcome::Inconsistency(inconsistency) => {
+                if !self.health.inconsistencies.contains(&inconsistency) {
+                    tracing::error!(
+                        "Inconsistency detected for L1 batch #{} during {check:?} check: {}",
+                        inconsistency.l1_batch,
+                        inconsistency.reason
+                    );
+                    METRICS.inconsistencies[&check].inc();
+                    self.health.inconsistencies.retain(|inc| inc.check != check);
+                    self.health.inconsistencies.push(inconsistency);
+                }
+                false
+            }
+        }
+    }
+
+    /// Performs a single verification step. Returns whether any progress was made.
+    async fn step(&mut self) -> BatchVerifierResult<bool> {
+        let dropped_count = self.drop_diverged_batches().await?;
+        let outcome = self.verify_next_batch().await?;
+        let mut progress = self.process_outcome(CheckKind::L1Commitment, outcome);
+
+        let anchor_outcome = match &self.anchoring {
+            Some(clients) => Some(self.verify_next_anchor(clients).await?),
+            None => None,
+        };
+        if let Some(outcome) = anchor_outcome {
+            progress |= self.process_outcome(CheckKind::MintlayerAnchor, outcome);
+        }
+        Ok(progress || dropped_count > 0)
+    }
+
+    fn update_health(&self) {
+        self.health_updater.update((&self.health).into());
+    }
+
+    /// Runs this component until a fatal error occurs or a stop signal is received. Transient errors
+    /// (e.g., no network connection) are handled gracefully by retrying after a delay.
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("batch_verifier").await?;
+        self.health.last_verified_l1_batch = storage
+            .verified_l1_batches_dal()
+            .get_last_verified_l1_batch()
+            .await?
+            .map(|batch| batch.number);
+        drop(storage);
+        tracing::info!(
+            "Starting L1 batch verifier with last verified batch {:?}, Mintlayer anchor checks enabled: {}",
+            self.health.last_verified_l1_batch,
+            self.anchoring.is_some()
+        );
+        self.update_health();
+
+        while !*stop_receiver.borrow_and_update() {
+            let need_to_sleep = match self.step().await {
+                Ok(progress) => {
+                    self.health.error = None;
+                    !progress
+                }
+                Err(err) if err.is_transient() => {
+                    tracing::warn!(
+                        "Transient error in L1 batch verifier, will retry after a delay: {err}"
+                    );
+                    self.health.error = Some(err.to_string());
+                    true
+                }
+                Err(err) => {
+                    tracing::error!("Fatal error in L1 batch verifier: {err}");
+                    return Err(err.into());
+                }
+            };
+            self.update_health();
+
+            if need_to_sleep
+                && tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received; L1 batch verifier is shutting down");
+        Ok(())
+    }
+}
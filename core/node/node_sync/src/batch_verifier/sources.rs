@@ -0,0 +1,201 @@
+//! Data sources used by the L1 batch verifier.
+
+use std::fmt;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use serde::{de::IgnoredAny, Deserialize};
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    api::en,
+    commitment::L1BatchMetadata,
+    web3::{BlockId, BlockNumber},
+    Address, L1BatchNumber, H256,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L1, L2},
+    error::{ClientRpcContext, EnrichedClientResult},
+    namespaces::{EnNamespaceClient, ZksNamespaceClient},
+};
+
+/// Information about an L1 batch returned by the main node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct RemoteL1Batch {
+    pub root_hash: Option<H256>,
+    pub commit_tx_hash: Option<H256>,
+}
+
+/// Main node data necessary for verification.
+#[async_trait]
+pub(super) trait VerifierMainNodeClient: fmt::Debug + Send + Sync + 'static {
+    async fn fetch_l1_batch(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<RemoteL1Batch>>;
+
+    async fn fetch_l1_batch_metadata(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<L1BatchMetadata>>;
+
+    async fn fetch_l1_batch_anchor(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<en::L1BatchAnchor>>;
+}
+
+#[async_trait]
+impl VerifierMainNodeClient for Box<DynClient<L2>> {
+    async fn fetch_l1_batch(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<RemoteL1Batch>> {
+        let details = self
+            .get_l1_batch_details(number)
+            .rpc_context("get_l1_batch_details")
+            .with_arg("number", &number)
+            .await?;
+        Ok(details.map(|details| RemoteL1Batch {
+            root_hash: details.base.root_hash,
+            commit_tx_hash: details.base.commit_tx_hash,
+        }))
+    }
+
+    async fn fetch_l1_batch_metadata(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<L1BatchMetadata>> {
+        self.l1_batch_metadata(number)
+            .rpc_context("l1_batch_metadata")
+            .with_arg("number", &number)
+            .await
+    }
+
+    async fn fetch_l1_batch_anchor(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<en::L1BatchAnchor>> {
+        self.l1_batch_anchor(number)
+            .rpc_context("l1_batch_anchor")
+            .with_arg("number", &number)
+            .await
+    }
+}
+
+/// Data from a `BlockCommit` event emitted by the diamond proxy contract. Also deserializable from `L1BatchMetadata`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub(super) struct BlockCommitEvent {
+    pub root_hash: H256,
+    pub commitment: H256,
+}
+
+/// Status of an L1 batch commit transaction on L1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum CommitTxStatus {
+    /// Transaction is not included into an L1 block (yet).
+    NotIncluded,
+    /// Transaction is included into an L1 block that is not finalized yet.
+    NotFinalized,
+    /// Transaction is included, but has failed.
+    Failed,
+    /// Transaction has succeeded, but didn't emit a `BlockCommit` event for the batch.
+    MissingEvent,
+    Committed(BlockCommitEvent),
+}
+
+/// L1 data necessary for verification.
+#[async_trait]
+pub(super) trait VerifierL1Client: fmt::Debug + Send + Sync + 'static {
+    async fn commit_tx_status(
+        &self,
+        commit_tx_hash: H256,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<CommitTxStatus>;
+}
+
+/// [`VerifierL1Client`] reading `BlockCommit` events from commit transaction receipts. Only transactions
+/// in finalized L1 blocks are considered, so that verified batches cannot be invalidated by an L1 reorg.
+#[derive(Debug)]
+pub(super) struct L1CommitClient {
+    eth_client: Box<DynClient<L1>>,
+    diamond_proxy_address: Address,
+    block_commit_signature: H256,
+}
+
+impl L1CommitClient {
+    pub fn new(
+        eth_client: Box<DynClient<L1>>,
+        diamond_proxy_address: Address,
+    ) -> anyhow::Result<Self> {
+        let block_commit_signature = zksync_contracts::hyperchain_contract()
+            .event("BlockCommit")
+            .context("missing `BlockCommit` event")?
+            .signature();
+        Ok(Self {
+            eth_client,
+            diamond_proxy_address,
+            block_commit_signature,
+        })
+    }
+}
+
+#[async_trait]
+impl VerifierL1Client for L1CommitClient {
+    async fn commit_tx_status(
+        &self,
+        commit_tx_hash: H256,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<CommitTxStatus> {
+        let Some(receipt) = self.eth_client.tx_receipt(commit_tx_hash).await? else {
+            return Ok(CommitTxStatus::NotIncluded);
+        };
+        let Some(block_number) = receipt.block_number else {
+            return Ok(CommitTxStatus::NotIncluded);
+        };
+        let finalized_block = self
+            .eth_client
+            .block(BlockId::Number(BlockNumber::Finalized))
+            .await?;
+        let finalized_block_number = finalized_block.and_then(|block| block.number);
+        if finalized_block_number.map_or(true, |finalized| finalized < block_number) {
+            return Ok(CommitTxStatus::NotFinalized);
+        }
+        if receipt.status != Some(1.into()) {
+            return Ok(CommitTxStatus::Failed);
+        }
+
+        let number_topic = H256::from_low_u64_be(number.0.into());
+        let event = receipt.logs.iter().find(|log| {
+            log.address == self.diamond_proxy_address
+                && log.topics.first() == Some(&self.block_commit_signature)
+                && log.topics.get(1) == Some(&number_topic)
+        });
+        // `BlockCommit` has 3 indexed params: batch number, root hash and commitment.
+        Ok(match event.map(|log| log.topics.as_slice()) {
+            Some([_, _, root_hash, commitment]) => CommitTxStatus::Committed(BlockCommitEvent {
+                root_hash: *root_hash,
+                commitment: *commitment,
+            }),
+            _ => CommitTxStatus::MissingEvent,
+        })
+    }
+}
+
+/// Commit operation pinned to IPFS by the main node. This is a subset of the serialized `AggregatedOperation::Commit`
+/// from the `eth_sender` crate: `(last_committed_batch, batches, pubdata_da)`.
+#[derive(Debug, Deserialize)]
+pub(super) struct AnchoredCommitOperation {
+    #[serde(rename = "Commit")]
+    pub commit: (IgnoredAny, Vec<AnchoredL1Batch>, IgnoredAny),
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct AnchoredL1Batch {
+    pub header: AnchoredL1BatchHeader,
+    pub metadata: BlockCommitEvent,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct AnchoredL1BatchHeader {
+    pub number: L1BatchNumber,
+}
@@ -0,0 +1,513 @@
+use std::{
+    collections::HashMap,
+    ops,
+    sync::{Arc, Mutex},
+};
+
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use test_casing::test_casing;
+use zksync_anchoring::{MockIpfsClient, MockMintlayerClient};
+use zksync_node_test_utils::{create_l1_batch, create_l1_batch_metadata};
+use zksync_types::{
+    api::en,
+    commitment::{L1BatchMetadata, L1BatchWithMetadata},
+    pubdata_da::PubdataDA,
+    H256,
+};
+use zksync_web3_decl::{error::EnrichedClientResult, jsonrpsee::core::ClientError};
+
+use super::{sources::RemoteL1Batch, *};
+
+fn commit_tx_hash(number: u32) -> H256 {
+    H256::from_low_u64_be(0x1000 + u64::from(number))
+}
+
+/// Returns metadata with a consistent commitment for the specified batch.
+fn batch_metadata(number: u32, root_hash: H256) -> L1BatchMetadata {
+    let mut metadata = create_l1_batch_metadata(number);
+    metadata.root_hash = root_hash;
+    metadata.commitment = metadata.recompute_commitment();
+    metadata
+}
+
+#[derive(Debug, Default)]
+struct MockMainNodeState {
+    batches: HashMap<L1BatchNumber, RemoteL1Batch>,
+    metadata: HashMap<L1BatchNumber, L1BatchMetadata>,
+    anchors: HashMap<L1BatchNumber, en::L1BatchAnchor>,
+    transient_error: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockMainNode(Arc<Mutex<MockMainNodeState>>);
+
+impl MockMainNode {
+    fn insert_batch(&self, number: u32, root_hash: H256) {
+        let batch = RemoteL1Batch {
+            root_hash: Some(root_hash),
+            commit_tx_hash: Some(commit_tx_hash(number)),
+        };
+        let mut state = self.0.lock().unwrap();
+        state.batches.insert(L1BatchNumber(number), batch);
+        let metadata = batch_metadata(number, root_hash);
+        state.metadata.insert(L1BatchNumber(number), metadata);
+    }
+
+    fn insert_metadata(&self, number: u32, metadata: L1BatchMetadata) {
+        let mut state = self.0.lock().unwrap();
+        state.metadata.insert(L1BatchNumber(number), metadata);
+    }
+
+    fn insert_anchor(&self, number: u32, anchor: en::L1BatchAnchor) {
+        let mut state = self.0.lock().unwrap();
+        state.anchors.insert(L1BatchNumber(number), anchor);
+    }
+
+    fn set_transient_error(&self) {
+        self.0.lock().unwrap().transient_error = true;
+    }
+
+    fn check_transient_error(&self) -> EnrichedClientResult<()> {
+        if std::mem::take(&mut self.0.lock().unwrap().transient_error) {
+            let err = ClientError::RequestTimeout;
+            return Err(EnrichedClientError::new(err, "mock"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VerifierMainNodeClient for MockMainNode {
+    async fn fetch_l1_batch(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<RemoteL1Batch>> {
+        self.check_transient_error()?;
+        Ok(self.0.lock().unwrap().batches.get(&number).copied())
+    }
+
+    async fn fetch_l1_batch_metadata(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<L1BatchMetadata>> {
+        self.check_transient_error()?;
+        Ok(self.0.lock().unwrap().metadata.get(&number).cloned())
+    }
+
+    async fn fetch_l1_batch_anchor(
+        &self,
+        number: L1BatchNumber,
+    ) -> EnrichedClientResult<Option<en::L1BatchAnchor>> {
+        self.check_transient_error()?;
+        Ok(self.0.lock().unwrap().anchors.get(&number).cloned())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockL1(Arc<Mutex<HashMap<H256, CommitTxStatus>>>);
+
+impl MockL1 {
+    fn commit_batch(&self, number: u32, root_hash: H256) {
+        let event = BlockCommitEvent {
+            root_hash,
+            commitment: batch_metadata(number, root_hash).commitment,
+        };
+        self.set_status(number, CommitTxStatus::Committed(event));
+    }
+
+    fn set_status(&self, number: u32, status: CommitTxStatus) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(commit_tx_hash(number), status);
+    }
+}
+
+#[async_trait]
+impl VerifierL1Client for MockL1 {
+    async fn commit_tx_status(
+        &self,
+        commit_tx_hash: H256,
+        _number: L1BatchNumber,
+    ) -> EnrichedClientResult<CommitTxStatus> {
+        let statuses = self.0.lock().unwrap();
+        Ok(statuses
+            .get(&commit_tx_hash)
+            .copied()
+            .unwrap_or(CommitTxStatus::NotIncluded))
+    }
+}
+
+fn root_hash(number: u32) -> H256 {
+    create_l1_batch_metadata(number).root_hash
+}
+
+/// Creates a main node and L1 with the specified batches committed.
+fn mock_sources(committed_batches: ops::RangeInclusive<u32>) -> (MockMainNode, MockL1) {
+    let main_node = MockMainNode::default();
+    let l1 = MockL1::default();
+    for number in committed_batches {
+        main_node.insert_batch(number, root_hash(number));
+        l1.commit_batch(number, root_hash(number));
+    }
+    (main_node, l1)
+}
+
+async fn verified_batch_numbers(pool: &ConnectionPool<Core>) -> Vec<L1BatchNumber> {
+    let mut storage = pool.connection().await.unwrap();
+    let first = storage
+        .verified_l1_batches_dal()
+        .get_first_verified_l1_batch_number()
+        .await
+        .unwrap();
+    let last = storage
+        .verified_l1_batches_dal()
+        .get_last_verified_l1_batch()
+        .await
+        .unwrap();
+    match (first, last) {
+        (Some(first), Some(last)) => (first.0..=last.number.0).map(L1BatchNumber).collect(),
+        _ => vec![],
+    }
+}
+
+async fn step_until_no_progress(verifier: &mut BatchVerifier) {
+    while verifier.step().await.unwrap() {
+        // Continue stepping
+    }
+}
+
+#[tokio::test]
+async fn verifying_l1_batches() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=2);
+    // Batch #3 is committed by the main node, but the commit transaction is not included on L1 yet.
+    main_node.insert_batch(3, root_hash(3));
+    let mut verifier =
+        BatchVerifier::from_parts(Box::new(main_node), Box::new(l1.clone()), pool.clone());
+
+    step_until_no_progress(&mut verifier).await;
+    assert_eq!(
+        verified_batch_numbers(&pool).await,
+        [L1BatchNumber(1), L1BatchNumber(2)]
+    );
+    assert_eq!(
+        verifier.health.last_verified_l1_batch,
+        Some(L1BatchNumber(2))
+    );
+    assert!(verifier.health.inconsistencies.is_empty());
+
+    // Commit transactions in non-finalized L1 blocks should be ignored.
+    l1.set_status(3, CommitTxStatus::NotFinalized);
+    assert!(!verifier.step().await.unwrap());
+    assert_eq!(
+        verifier.health.last_verified_l1_batch,
+        Some(L1BatchNumber(2))
+    );
+    assert!(verifier.health.inconsistencies.is_empty());
+
+    l1.commit_batch(3, root_hash(3));
+    assert!(verifier.step().await.unwrap());
+    let mut storage = pool.connection().await.unwrap();
+    let batch = storage
+        .verified_l1_batches_dal()
+        .get_last_verified_l1_batch()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        batch,
+        VerifiedL1Batch {
+            number: L1BatchNumber(3),
+            root_hash: root_hash(3),
+            commitment: batch_metadata(3, root_hash(3)).commitment,
+            commit_tx_hash: commit_tx_hash(3),
+            anchor: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn verification_starts_from_specified_batch() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=5);
+    let mut verifier = BatchVerifier::from_parts(Box::new(main_node), Box::new(l1), pool.clone())
+        .with_first_l1_batch(L1BatchNumber(4));
+
+    step_until_no_progress(&mut verifier).await;
+    assert_eq!(
+        verified_batch_numbers(&pool).await,
+        [L1BatchNumber(4), L1BatchNumber(5)]
+    );
+}
+
+#[derive(Debug, Clone, Copy)]
+enum L1Inconsistency {
+    RootHashMismatch,
+    CommitmentMismatch,
+    MissingMetadata,
+    FailedTx,
+    MissingEvent,
+}
+
+impl L1Inconsistency {
+    const ALL: [Self; 5] = [
+        Self::RootHashMismatch,
+        Self::CommitmentMismatch,
+        Self::MissingMetadata,
+        Self::FailedTx,
+        Self::MissingEvent,
+    ];
+}
+
+#[test_casing(5, L1Inconsistency::ALL)]
+#[tokio::test]
+async fn detecting_l1_inconsistency(kind: L1Inconsistency) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=1);
+    main_node.insert_batch(2, root_hash(2));
+    match kind {
+        L1Inconsistency::RootHashMismatch => l1.commit_batch(2, H256::repeat_byte(0xff)),
+        L1Inconsistency::CommitmentMismatch => {
+            l1.commit_batch(2, root_hash(2));
+            let mut metadata = batch_metadata(2, root_hash(2));
+            metadata.aux_data_hash = H256::repeat_byte(0xff);
+            main_node.insert_metadata(2, metadata);
+        }
+        L1Inconsistency::MissingMetadata => {
+            l1.commit_batch(2, root_hash(2));
+            main_node
+                .0
+                .lock()
+                .unwrap()
+                .metadata
+                .remove(&L1BatchNumber(2));
+        }
+        L1Inconsistency::FailedTx => l1.set_status(2, CommitTxStatus::Failed),
+        L1Inconsistency::MissingEvent => l1.set_status(2, CommitTxStatus::MissingEvent),
+    }
+    let mut verifier = BatchVerifier::from_parts(
+        Box::new(main_node.clone()),
+        Box::new(l1.clone()),
+        pool.clone(),
+    );
+
+    step_until_no_progress(&mut verifier).await;
+    assert_eq!(verified_batch_numbers(&pool).await, [L1BatchNumber(1)]);
+    let [inconsistency] = verifier.health.inconsistencies.as_slice() else {
+        panic!("Unexpected health: {:?}", verifier.health);
+    };
+    assert_eq!(inconsistency.check, CheckKind::L1Commitment);
+    assert_eq!(inconsistency.l1_batch, L1BatchNumber(2));
+    let health = Health::from(&verifier.health);
+    assert_matches!(health.status(), HealthStatus::Affected);
+
+    // Repeated checks shouldn't change anything.
+    assert!(!verifier.step().await.unwrap());
+    assert_eq!(verifier.health.inconsistencies.len(), 1);
+
+    // Emulate the inconsistency being fixed.
+    main_node.insert_batch(2, root_hash(2));
+    l1.commit_batch(2, root_hash(2));
+    assert!(verifier.step().await.unwrap());
+    assert_eq!(
+        verified_batch_numbers(&pool).await,
+        [L1BatchNumber(1), L1BatchNumber(2)]
+    );
+    assert!(verifier.health.inconsistencies.is_empty());
+    let health = Health::from(&verifier.health);
+    assert_matches!(health.status(), HealthStatus::Ready);
+}
+
+#[test_casing(2, [false, true])]
+#[tokio::test]
+async fn dropping_diverged_batches(diverge_from_first_batch: bool) {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=5);
+    let mut verifier = BatchVerifier::from_parts(
+        Box::new(main_node.clone()),
+        Box::new(l1.clone()),
+        pool.clone(),
+    );
+    step_until_no_progress(&mut verifier).await;
+    assert_eq!(verified_batch_numbers(&pool).await.len(), 5);
+
+    // Emulate the main node reverting batches and committing new ones instead.
+    let first_diverged_batch = if diverge_from_first_batch { 1 } else { 3 };
+    for number in first_diverged_batch..=5 {
+        let new_root_hash = H256::repeat_byte(number as u8);
+        main_node.insert_batch(number, new_root_hash);
+        l1.commit_batch(number, new_root_hash);
+    }
+
+    let dropped_count = verifier.drop_diverged_batches().await.unwrap();
+    assert_eq!(dropped_count, u64::from(6 - first_diverged_batch));
+    let expected_last_batch =
+        (first_diverged_batch > 1).then(|| L1BatchNumber(first_diverged_batch - 1));
+    assert_eq!(verifier.health.last_verified_l1_batch, expected_last_batch);
+
+    step_until_no_progress(&mut verifier).await;
+    let mut storage = pool.connection().await.unwrap();
+    for number in 1..=5 {
+        let batch = storage
+            .verified_l1_batches_dal()
+            .get_verified_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap()
+            .unwrap();
+        let expected_root_hash = if number >= first_diverged_batch {
+            H256::repeat_byte(number as u8)
+        } else {
+            root_hash(number)
+        };
+        assert_eq!(batch.root_hash, expected_root_hash);
+    }
+}
+
+async fn anchor_batches(
+    main_node: &MockMainNode,
+    mintlayer: &MockMintlayerClient,
+    ipfs: &MockIpfsClient,
+    batches: Vec<L1BatchWithMetadata>,
+) {
+    let ipfs_hash = format!("cid{}", batches[0].header.number);
+    let operation = serde_json::json!({
+        "Commit": [
+            L1BatchWithMetadata {
+                header: create_l1_batch(0),
+                metadata: create_l1_batch_metadata(0),
+                raw_published_factory_deps: vec![],
+            },
+            batches,
+            PubdataDA::Calldata,
+        ],
+    });
+    let operation = serde_json::to_vec(&operation).unwrap();
+    ipfs.insert(ipfs_hash.clone(), operation);
+    let data = format!("other_cid,{ipfs_hash}");
+    let tx_id = mintlayer.deposit_data(data.as_bytes()).await.unwrap();
+
+    for batch in &batches {
+        let anchor = en::L1BatchAnchor {
+            ipfs_hash: ipfs_hash.clone(),
+            mintlayer_tx_id: tx_id.clone(),
+        };
+        main_node.insert_anchor(batch.header.number.0, anchor);
+    }
+}
+
+fn batch_with_metadata(number: u32) -> L1BatchWithMetadata {
+    L1BatchWithMetadata {
+        header: create_l1_batch(number),
+        metadata: batch_metadata(number, root_hash(number)),
+        raw_published_factory_deps: vec![],
+    }
+}
+
+#[tokio::test]
+async fn verifying_mintlayer_anchors() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=3);
+    let mintlayer = MockMintlayerClient::default();
+    let ipfs = MockIpfsClient::default();
+    anchor_batches(
+        &main_node,
+        &mintlayer,
+        &ipfs,
+        vec![batch_with_metadata(1), batch_with_metadata(2)],
+    )
+    .await;
+    let mut verifier =
+        BatchVerifier::from_parts(Box::new(main_node.clone()), Box::new(l1), pool.clone())
+            .with_anchor_checks(Arc::new(mintlayer.clone()), Arc::new(ipfs.clone()));
+
+    step_until_no_progress(&mut verifier).await;
+    assert_eq!(verified_batch_numbers(&pool).await.len(), 3);
+    assert_eq!(
+        verifier.health.last_anchored_l1_batch,
+        Some(L1BatchNumber(2))
+    );
+    let mut storage = pool.connection().await.unwrap();
+    let batch = storage
+        .verified_l1_batches_dal()
+        .get_verified_l1_batch(L1BatchNumber(2))
+        .await
+        .unwrap()
+        .unwrap();
+    let anchor = batch.anchor.unwrap();
+    assert_eq!(anchor.ipfs_hash, "cid1");
+    let first_unanchored_batch = storage
+        .verified_l1_batches_dal()
+        .get_first_unanchored_l1_batch()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first_unanchored_batch.number, L1BatchNumber(3));
+    drop(storage);
+
+    // Transient errors should not be fatal.
+    main_node.set_transient_error();
+    let err = verifier.step().await.unwrap_err();
+    assert!(err.is_transient(), "{err:?}");
+
+    anchor_batches(&main_node, &mintlayer, &ipfs, vec![batch_with_metadata(3)]).await;
+    assert!(verifier.step().await.unwrap());
+    assert_eq!(
+        verifier.health.last_anchored_l1_batch,
+        Some(L1BatchNumber(3))
+    );
+    assert!(verifier.health.inconsistencies.is_empty());
+}
+
+#[tokio::test]
+async fn detecting_mintlayer_anchor_inconsistency() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=1);
+    let mintlayer = MockMintlayerClient::default();
+    let ipfs = MockIpfsClient::default();
+    let mut anchored_batch = batch_with_metadata(1);
+    anchored_batch.metadata.commitment = H256::repeat_byte(0xff);
+    anchor_batches(&main_node, &mintlayer, &ipfs, vec![anchored_batch]).await;
+    let mut verifier = BatchVerifier::from_parts(Box::new(main_node), Box::new(l1), pool.clone())
+        .with_anchor_checks(Arc::new(mintlayer), Arc::new(ipfs));
+
+    step_until_no_progress(&mut verifier).await;
+    assert_eq!(
+        verifier.health.last_verified_l1_batch,
+        Some(L1BatchNumber(1))
+    );
+    assert_eq!(verifier.health.last_anchored_l1_batch, None);
+    let [inconsistency] = verifier.health.inconsistencies.as_slice() else {
+        panic!("Unexpected health: {:?}", verifier.health);
+    };
+    assert_eq!(inconsistency.check, CheckKind::MintlayerAnchor);
+    assert_eq!(inconsistency.l1_batch, L1BatchNumber(1));
+    assert!(
+        inconsistency.reason.contains("commitment"),
+        "{inconsistency:?}"
+    );
+}
+
+#[tokio::test]
+async fn verifier_health() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let (main_node, l1) = mock_sources(1..=2);
+    let verifier = BatchVerifier::from_parts(Box::new(main_node), Box::new(l1), pool);
+    let mut health_check = verifier.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let verifier_task = tokio::spawn(verifier.run(stop_receiver));
+
+    let health = health_check
+        .wait_for(|health| {
+            health
+                .details()
+                .and_then(|details| details.get("last_verified_l1_batch"))
+                .is_some_and(|batch| *batch == 2)
+        })
+        .await;
+    assert_matches!(health.status(), HealthStatus::Ready);
+
+    stop_sender.send_replace(true);
+    verifier_task.await.unwrap().unwrap();
+}
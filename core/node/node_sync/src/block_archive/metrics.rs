@@ -0,0 +1,20 @@
+//! Metrics for the L2 block archive writer.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Gauge, Histogram, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "l2_block_archive")]
+pub(super) struct BlockArchiveMetrics {
+    /// Last L2 block in the archive.
+    pub last_archived_l2_block: Gauge<u64>,
+    /// Latency of archiving a single chunk of L2 blocks.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub chunk_latency: Histogram<Duration>,
+    /// Number of archived chunks dropped because L2 blocks in them were rolled back.
+    pub dropped_chunks: Counter,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<BlockArchiveMetrics> = vise::Global::new();
@@ -0,0 +1,441 @@
+//! Archive of L2 blocks written by the main node, which can be used by external nodes as a sync source instead of
+//! the main node JSON-RPC API.
+//!
+//! The archive consists of chunks of [`L2_BLOCKS_PER_ARCHIVE_CHUNK`] consecutive L2 blocks (with transactions) stored
+//! in the [`Bucket::L2BlockArchive`] object store bucket, and optionally pinned to IPFS. A chunk with index `i`
+//! contains L2 blocks with numbers in `i * L2_BLOCKS_PER_ARCHIVE_CHUNK..(i + 1) * L2_BLOCKS_PER_ARCHIVE_CHUNK`.
+//! Only complete chunks consisting of L2 blocks in sealed L1 batches are archived. Chunks are indexed in Postgres
+//! (this index is exposed via the `en_l2BlockArchiveChunk` JSON-RPC method), and the last archived chunk is
+//! additionally recorded in the object store, so that the object store archive is self-contained.
+//!
+//! Since IPFS is content-addressed, external nodes syncing from IPFS resolve chunk CIDs using the `en_l2BlockArchiveChunk`
+//! method of one or more resolvers (the main node, or other external nodes). External nodes record resolved chunks
+//! in their own index, so that they can act as resolvers for other nodes.
+
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use zksync_anchoring::IpfsClient;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_object_store::{
+    Bucket, ObjectStore, ObjectStoreError, StoredObject, _reexports::BoxedError,
+};
+use zksync_types::{api::en, L2BlockNumber};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::ClientRpcContext,
+    namespaces::EnNamespaceClient,
+};
+
+pub use self::writer::BlockArchiveWriter;
+use crate::sync_source::{SyncSource, SyncSourceError};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+mod writer;
+
+/// Number of L2 blocks in a single archive chunk.
+pub const L2_BLOCKS_PER_ARCHIVE_CHUNK: u32 = 1_000;
+
+/// Chunk of consecutive L2 blocks archived by the main node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L2BlockArchiveChunk {
+    pub blocks: Vec<en::SyncBlock>,
+}
+
+impl StoredObject for L2BlockArchiveChunk {
+    const BUCKET: Bucket = Bucket::L2BlockArchive;
+    type Key<'a> = u32;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("l2_block_archive_chunk_{key}.json.gzip")
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&serde_json::to_vec(self)?)?;
+        encoder.finish().map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        let mut decoder = GzDecoder::new(&bytes[..]);
+        let mut decompressed_bytes = Vec::new();
+        decoder.read_to_end(&mut decompressed_bytes)?;
+        serde_json::from_slice(&decompressed_bytes).map_err(From::from)
+    }
+}
+
+impl L2BlockArchiveChunk {
+    /// Checks that this chunk is non-empty and consists of consecutive L2 blocks belonging to the chunk
+    /// with the specified index.
+    fn validate(&self, index: u32, chunk_size: u32) -> anyhow::Result<()> {
+        let first_block = self.blocks.first().context("chunk is empty")?;
+        let chunk_start = index
+            .checked_mul(chunk_size)
+            .context("chunk index overflow")?;
+        let chunk_end = chunk_start + chunk_size;
+        anyhow::ensure!(
+            (chunk_start..chunk_end).contains(&first_block.number.0),
+            "first L2 block #{} does not belong to chunk #{index}",
+            first_block.number
+        );
+        anyhow::ensure!(
+            first_block.number.0 + self.blocks.len() as u32 <= chunk_end,
+            "chunk #{index} contains too many L2 blocks"
+        );
+        for (i, block) in self.blocks.iter().enumerate() {
+            let expected_number = first_block.number + i as u32;
+            anyhow::ensure!(
+                block.number == expected_number,
+                "unexpected L2 block #{} at position {i}, expected #{expected_number}",
+                block.number
+            );
+            anyhow::ensure!(
+                block.transactions.is_some(),
+                "L2 block #{} has no transactions",
+                block.number
+            );
+        }
+        Ok(())
+    }
+
+    fn get(&self, number: L2BlockNumber) -> Option<&en::SyncBlock> {
+        let first_number = self.blocks.first()?.number;
+        let position = number.0.checked_sub(first_number.0)?;
+        self.blocks.get(position as usize)
+    }
+}
+
+/// Last archived chunk recorded in the object store. Overwritten each time a chunk is archived.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct L2BlockArchiveHead {
+    last_chunk: en::L2BlockArchiveChunkInfo,
+}
+
+impl StoredObject for L2BlockArchiveHead {
+    const BUCKET: Bucket = Bucket::L2BlockArchive;
+    type Key<'a> = ();
+
+    fn encode_key((): Self::Key<'_>) -> String {
+        "l2_block_archive_head.json".to_owned()
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        serde_json::to_vec(self).map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        serde_json::from_slice(&bytes).map_err(From::from)
+    }
+}
+
+/// Chunk fetched from an [`ArchiveStorage`].
+#[derive(Debug)]
+struct StoredChunk {
+    chunk: L2BlockArchiveChunk,
+    /// CID of the chunk if it was fetched from IPFS.
+    ipfs_cid: Option<String>,
+}
+
+impl From<L2BlockArchiveChunk> for StoredChunk {
+    fn from(chunk: L2BlockArchiveChunk) -> Self {
+        Self {
+            chunk,
+            ipfs_cid: None,
+        }
+    }
+}
+
+/// Storage of the L2 block archive.
+#[async_trait]
+trait ArchiveStorage: 'static + Send + Sync + std::fmt::Debug {
+    async fn last_chunk(&self) -> Result<Option<en::L2BlockArchiveChunkInfo>, SyncSourceError>;
+
+    async fn chunk(&self, index: u32) -> Result<Option<StoredChunk>, SyncSourceError>;
+}
+
+#[derive(Debug)]
+struct ObjectStoreArchive(Arc<dyn ObjectStore>);
+
+#[async_trait]
+impl ArchiveStorage for ObjectStoreArchive {
+    async fn last_chunk(&self) -> Result<Option<en::L2BlockArchiveChunkInfo>, SyncSourceError> {
+        match self.0.get::<L2BlockArchiveHead>(()).await {
+            Ok(head) => Ok(Some(head.last_chunk)),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn chunk(&self, index: u32) -> Result<Option<StoredChunk>, SyncSourceError> {
+        match self.0.get::<L2BlockArchiveChunk>(index).await {
+            Ok(chunk) => Ok(Some(chunk.into())),
+            Err(ObjectStoreError::KeyNotFound(_)) => Ok(None),
+            Err(ObjectStoreError::Serialization(err)) => Err(SyncSourceError::InvalidArchive(
+                anyhow::anyhow!(err).context(format!("failed deserializing chunk #{index}")),
+            )),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Archive pinned to IPFS. Since IPFS is content-addressed, chunk CIDs are resolved using the `en_l2BlockArchiveChunk`
+/// method of the resolvers, which are queried in order until one of them knows the CID.
+#[derive(Debug)]
+struct IpfsArchive {
+    resolvers: Vec<Box<DynClient<L2>>>,
+    ipfs: Arc<dyn IpfsClient>,
+}
+
+impl IpfsArchive {
+    async fn chunk_info(
+        resolver: &DynClient<L2>,
+        index: Option<u32>,
+    ) -> Result<Option<en::L2BlockArchiveChunkInfo>, SyncSourceError> {
+        Ok(resolver
+            .l2_block_archive_chunk(index)
+            .rpc_context("l2_block_archive_chunk")
+            .with_arg("index", &index)
+            .await?)
+    }
+
+    /// Resolves the CID of the specified chunk. Returns `Ok(None)` if no resolver knows the CID, and an error
+    /// if all resolvers have failed.
+    async fn resolve_cid(&self, index: u32) -> Result<Option<String>, SyncSourceError> {
+        let mut has_responses = false;
+        let mut last_err = None;
+        for resolver in &self.resolvers {
+            match Self::chunk_info(resolver.as_ref(), Some(index)).await {
+                Ok(Some(en::L2BlockArchiveChunkInfo {
+                    ipfs_cid: Some(cid),
+                    ..
+                })) => return Ok(Some(cid)),
+                Ok(_) => has_responses = true,
+                Err(err) => {
+                    tracing::info!("Failed resolving CID of chunk #{index}: {err}");
+                    last_err = Some(err);
+                }
+            }
+        }
+        match last_err {
+            Some(err) if !has_responses => Err(err),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl ArchiveStorage for IpfsArchive {
+    async fn last_chunk(&self) -> Result<Option<en::L2BlockArchiveChunkInfo>, SyncSourceError> {
+        let mut last_chunk: Option<en::L2BlockArchiveChunkInfo> = None;
+        let mut last_err = None;
+        let mut has_responses = false;
+        for resolver in &self.resolvers {
+            match Self::chunk_info(resolver.as_ref(), None).await {
+                Ok(chunk) => {
+                    has_responses = true;
+                    let chunk = chunk.filter(|chunk| chunk.ipfs_cid.is_some());
+                    let index = chunk.as_ref().map(|chunk| chunk.index);
+                    if index > last_chunk.as_ref().map(|chunk| chunk.index) {
+                        last_chunk = chunk;
+                    }
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+        match last_err {
+            Some(err) if !has_responses => Err(err),
+            _ => Ok(last_chunk),
+        }
+    }
+
+    async fn chunk(&self, index: u32) -> Result<Option<StoredChunk>, SyncSourceError> {
+        let Some(cid) = self.resolve_cid(index).await? else {
+            return Ok(None);
+        };
+        let bytes = self
+            .ipfs
+            .fetch(&cid)
+            .await
+            .with_context(|| format!("failed fetching chunk #{index} with CID {cid}"))
+            .map_err(SyncSourceError::Archive)?;
+        let chunk = <L2BlockArchiveChunk as StoredObject>::deserialize(bytes).map_err(|err| {
+            let err = anyhow::anyhow!(err).context(format!("failed deserializing chunk #{index}"));
+            SyncSourceError::InvalidArchive(err)
+        })?;
+        Ok(Some(StoredChunk {
+            chunk,
+            ipfs_cid: Some(cid),
+        }))
+    }
+}
+
+/// [`SyncSource`] reading L2 blocks from the archive written by the main node.
+#[derive(Debug)]
+pub struct ArchiveSyncSource {
+    storage: Box<dyn ArchiveStorage>,
+    chunk_size: u32,
+    /// Recently fetched chunks. L2 blocks are requested by the fetcher concurrently in the ascending order,
+    /// so caching 2 chunks is enough to not fetch any chunk more than once.
+    cached_chunks: Mutex<VecDeque<(u32, Arc<L2BlockArchiveChunk>)>>,
+    /// Pool used to record chunks fetched from IPFS in the local chunk index.
+    chunk_index_pool: Option<ConnectionPool<Core>>,
+    /// Chunks fetched from IPFS that are not recorded in the local chunk index yet.
+    unrecorded_chunks: std::sync::Mutex<Vec<en::L2BlockArchiveChunkInfo>>,
+}
+
+impl ArchiveSyncSource {
+    const CACHED_CHUNK_COUNT: usize = 2;
+
+    /// Creates a source reading the archive from the specified object store.
+    pub fn object_store(object_store: Arc<dyn ObjectStore>) -> Self {
+        Self::new(Box::new(ObjectStoreArchive(object_store)))
+    }
+
+    /// Creates a source reading the archive from IPFS. Chunk CIDs are resolved using the provided resolvers
+    /// (e.g., the main node and other external nodes), which are queried in order.
+    pub fn ipfs(resolvers: Vec<Box<DynClient<L2>>>, ipfs: Arc<dyn IpfsClient>) -> Self {
+        let resolvers = resolvers
+            .into_iter()
+            .map(|resolver| resolver.for_component("l2_block_archive"))
+            .collect();
+        Self::new(Box::new(IpfsArchive { resolvers, ipfs }))
+    }
+
+    fn new(storage: Box<dyn ArchiveStorage>) -> Self {
+        Self {
+            storage,
+            chunk_size: L2_BLOCKS_PER_ARCHIVE_CHUNK,
+            cached_chunks: Mutex::default(),
+            chunk_index_pool: None,
+            unrecorded_chunks: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Records chunks fetched from IPFS in the local chunk index, so that this node can resolve chunk CIDs
+    /// for other nodes via the `en_l2BlockArchiveChunk` method. A chunk is only recorded once its last L2 block
+    /// is persisted locally with the hash matching the chunk.
+    #[must_use]
+    pub fn with_chunk_index(mut self, pool: ConnectionPool<Core>) -> Self {
+        self.chunk_index_pool = Some(pool);
+        self
+    }
+
+    async fn record_chunks(&self) -> anyhow::Result<()> {
+        let Some(pool) = &self.chunk_index_pool else {
+            return Ok(());
+        };
+        let unrecorded_chunks = self.unrecorded_chunks.lock().unwrap().clone();
+        if unrecorded_chunks.is_empty() {
+            return Ok(());
+        }
+
+        let mut storage = pool.connection_tagged("sync_layer").await?;
+        let mut processed_indices = vec![];
+        for chunk in unrecorded_chunks {
+            let header = storage
+                .blocks_dal()
+                .get_l2_block_header(chunk.last_l2_block)
+                .await?;
+            let Some(header) = header else {
+                continue; // The chunk is not synced yet
+            };
+            if header.hash == chunk.last_l2_block_hash {
+                storage.block_archive_dal().upsert_chunk(&chunk).await?;
+                tracing::debug!("Recorded L2 block archive chunk #{}", chunk.index);
+            } else {
+                tracing::warn!(
+                    "Not recording L2 block archive chunk #{}: hash of its last L2 block differs from the local one",
+                    chunk.index
+                );
+            }
+            processed_indices.push(chunk.index);
+        }
+        self.unrecorded_chunks
+            .lock()
+            .unwrap()
+            .retain(|chunk| !processed_indices.contains(&chunk.index));
+        Ok(())
+    }
+
+    async fn chunk(&self, index: u32) -> Result<Option<Arc<L2BlockArchiveChunk>>, SyncSourceError> {
+        // The lock is held while the chunk is fetched, so that concurrent requests don't fetch the same chunk.
+        let mut cached_chunks = self.cached_chunks.lock().await;
+        if let Some((_, chunk)) = cached_chunks.iter().find(|(i, _)| *i == index) {
+            return Ok(Some(chunk.clone()));
+        }
+
+        let Some(StoredChunk { chunk, ipfs_cid }) = self.storage.chunk(index).await? else {
+            return Ok(None);
+        };
+        chunk
+            .validate(index, self.chunk_size)
+            .with_context(|| format!("chunk #{index} is invalid"))
+            .map_err(SyncSourceError::InvalidArchive)?;
+        tracing::debug!(
+            "Fetched L2 block archive chunk #{index} with {} L2 blocks",
+            chunk.blocks.len()
+        );
+
+        if self.chunk_index_pool.is_some() {
+            let (first_block, last_block) = (chunk.blocks.first(), chunk.blocks.last());
+            if let (Some(first_block), Some(last_block), Some(ipfs_cid)) =
+                (first_block, last_block, ipfs_cid)
+            {
+                if let Some(last_l2_block_hash) = last_block.hash {
+                    let info = en::L2BlockArchiveChunkInfo {
+                        index,
+                        first_l2_block: first_block.number,
+                        last_l2_block: last_block.number,
+                        last_l2_block_hash,
+                        ipfs_cid: Some(ipfs_cid),
+                    };
+                    self.unrecorded_chunks.lock().unwrap().push(info);
+                }
+            }
+            if let Err(err) = self.record_chunks().await {
+                tracing::warn!("Failed recording L2 block archive chunks: {err:#}");
+            }
+        }
+
+        let chunk = Arc::new(chunk);
+        if cached_chunks.len() == Self::CACHED_CHUNK_COUNT {
+            cached_chunks.pop_front();
+        }
+        cached_chunks.push_back((index, chunk.clone()));
+        Ok(Some(chunk))
+    }
+}
+
+#[async_trait]
+impl SyncSource for ArchiveSyncSource {
+    async fn fetch_l2_block_number(&self) -> Result<L2BlockNumber, SyncSourceError> {
+        if let Err(err) = self.record_chunks().await {
+            tracing::warn!("Failed recording L2 block archive chunks: {err:#}");
+        }
+        let last_chunk = self.storage.last_chunk().await?;
+        let last_chunk = last_chunk
+            .context("L2 block archive is empty")
+            .map_err(SyncSourceError::Archive)?;
+        Ok(last_chunk.last_l2_block)
+    }
+
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+    ) -> Result<Option<en::SyncBlock>, SyncSourceError> {
+        let Some(chunk) = self.chunk(number.0 / self.chunk_size).await? else {
+            return Ok(None);
+        };
+        Ok(chunk.get(number).cloned())
+    }
+}
@@ -0,0 +1,407 @@
+//! Tests for the L2 block archive.
+
+use assert_matches::assert_matches;
+use zksync_anchoring::MockIpfsClient;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+use zksync_object_store::MockObjectStore;
+use zksync_types::{block::L2BlockHeader, L1BatchNumber, H256};
+use zksync_web3_decl::client::MockClient;
+
+use super::*;
+use crate::sync_source::FallbackSyncSource;
+
+const TEST_CHUNK_SIZE: u32 = 4;
+
+/// Seals an L1 batch with a single L2 block with the same number.
+async fn seal_l1_batch(storage: &mut Connection<'_, Core>, number: u32, l2_block_hash: H256) {
+    let mut storage = storage.start_transaction().await.unwrap();
+    let l2_block = L2BlockHeader {
+        hash: l2_block_hash,
+        ..create_l2_block(number)
+    };
+    storage
+        .blocks_dal()
+        .insert_l2_block(&l2_block)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(number))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(number))
+        .await
+        .unwrap();
+    storage.commit().await.unwrap();
+}
+
+async fn prepare_storage(pool: &ConnectionPool<Core>, last_l1_batch: u32) {
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    for number in 1..=last_l1_batch {
+        seal_l1_batch(&mut storage, number, H256::from_low_u64_be(number.into())).await;
+    }
+}
+
+fn test_writer(
+    pool: &ConnectionPool<Core>,
+    object_store: &Arc<dyn ObjectStore>,
+) -> BlockArchiveWriter {
+    let mut writer = BlockArchiveWriter::new(pool.clone(), object_store.clone());
+    writer.chunk_size = TEST_CHUNK_SIZE;
+    writer
+}
+
+fn test_source(object_store: Arc<dyn ObjectStore>) -> ArchiveSyncSource {
+    let mut source = ArchiveSyncSource::object_store(object_store);
+    source.chunk_size = TEST_CHUNK_SIZE;
+    source
+}
+
+/// `SyncBlock` doesn't implement `PartialEq`, so blocks are compared by their JSON representation.
+#[track_caller]
+fn assert_blocks_eq(block: &en::SyncBlock, expected: &en::SyncBlock) {
+    assert_eq!(
+        serde_json::to_value(block).unwrap(),
+        serde_json::to_value(expected).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn archiving_l2_blocks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 9).await;
+    let object_store = MockObjectStore::arc();
+    let writer = test_writer(&pool, &object_store);
+
+    assert!(writer.step().await.unwrap());
+    assert!(writer.step().await.unwrap());
+    // The next chunk is not complete yet.
+    assert!(!writer.step().await.unwrap());
+
+    let mut storage = pool.connection().await.unwrap();
+    let last_chunk = storage
+        .block_archive_dal()
+        .get_last_chunk()
+        .await
+        .unwrap()
+        .expect("no archived chunks");
+    assert_eq!(last_chunk.index, 1);
+    assert_eq!(last_chunk.first_l2_block, L2BlockNumber(4));
+    assert_eq!(last_chunk.last_l2_block, L2BlockNumber(7));
+    assert_eq!(last_chunk.last_l2_block_hash, H256::from_low_u64_be(7));
+    assert_eq!(last_chunk.ipfs_cid, None);
+
+    let chunk: L2BlockArchiveChunk = object_store.get(0).await.unwrap();
+    let numbers: Vec<_> = chunk.blocks.iter().map(|block| block.number.0).collect();
+    assert_eq!(numbers, [0, 1, 2, 3]);
+    let head: L2BlockArchiveHead = object_store.get(()).await.unwrap();
+    assert_eq!(head.last_chunk, last_chunk);
+
+    seal_l1_batch(&mut storage, 10, H256::from_low_u64_be(10)).await;
+    seal_l1_batch(&mut storage, 11, H256::from_low_u64_be(11)).await;
+    assert!(writer.step().await.unwrap());
+    let head: L2BlockArchiveHead = object_store.get(()).await.unwrap();
+    assert_eq!(head.last_chunk.index, 2);
+    assert_eq!(head.last_chunk.last_l2_block, L2BlockNumber(11));
+}
+
+#[tokio::test]
+async fn syncing_from_object_store_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 9).await;
+    let object_store = MockObjectStore::arc();
+    let source = test_source(object_store.clone());
+    let err = source.fetch_l2_block_number().await.unwrap_err();
+    assert_matches!(err, SyncSourceError::Archive(_));
+    assert!(source
+        .fetch_l2_block(L2BlockNumber(0))
+        .await
+        .unwrap()
+        .is_none());
+
+    let writer = test_writer(&pool, &object_store);
+    while writer.step().await.unwrap() {}
+
+    assert_eq!(
+        source.fetch_l2_block_number().await.unwrap(),
+        L2BlockNumber(7)
+    );
+    let mut storage = pool.connection().await.unwrap();
+    let expected_blocks = storage
+        .sync_dal()
+        .sync_blocks_with_transactions(L2BlockNumber(0)..L2BlockNumber(8))
+        .await
+        .unwrap();
+    for expected_block in expected_blocks {
+        let block = source.fetch_l2_block(expected_block.number).await.unwrap();
+        assert_blocks_eq(&block.unwrap(), &expected_block);
+    }
+    assert!(source
+        .fetch_l2_block(L2BlockNumber(8))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn dropping_rolled_back_chunks() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 9).await;
+    let object_store = MockObjectStore::arc();
+    let writer = test_writer(&pool, &object_store);
+    while writer.step().await.unwrap() {}
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .blocks_dal()
+        .delete_l2_blocks(L2BlockNumber(5))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .delete_l1_batches(L1BatchNumber(5))
+        .await
+        .unwrap();
+
+    // The writer should drop the last chunk, since L2 blocks in it were rolled back.
+    assert!(writer.step().await.unwrap());
+    let last_chunk = storage.block_archive_dal().get_last_chunk().await.unwrap();
+    assert_eq!(last_chunk.unwrap().index, 0);
+    let head: L2BlockArchiveHead = object_store.get(()).await.unwrap();
+    assert_eq!(head.last_chunk.index, 0);
+    assert!(!writer.step().await.unwrap());
+
+    for number in 6..=9 {
+        seal_l1_batch(&mut storage, number, H256::repeat_byte(number as u8)).await;
+    }
+    assert!(writer.step().await.unwrap());
+    let head: L2BlockArchiveHead = object_store.get(()).await.unwrap();
+    assert_eq!(head.last_chunk.index, 1);
+    assert_eq!(head.last_chunk.last_l2_block_hash, H256::repeat_byte(7));
+    let chunk: L2BlockArchiveChunk = object_store.get(1).await.unwrap();
+    assert_eq!(chunk.blocks[3].hash, Some(H256::repeat_byte(7)));
+
+    // Roll back all chunks.
+    storage
+        .blocks_dal()
+        .delete_l2_blocks(L2BlockNumber(0))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .delete_l1_batches(L1BatchNumber(0))
+        .await
+        .unwrap();
+    assert!(writer.step().await.unwrap());
+    assert!(writer.step().await.unwrap());
+    let last_chunk = storage.block_archive_dal().get_last_chunk().await.unwrap();
+    assert_eq!(last_chunk, None);
+    let err = object_store
+        .get::<L2BlockArchiveHead>(())
+        .await
+        .unwrap_err();
+    assert_matches!(err, ObjectStoreError::KeyNotFound(_));
+}
+
+#[tokio::test]
+async fn syncing_from_ipfs_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 9).await;
+    let object_store = MockObjectStore::arc();
+    let ipfs = Arc::new(MockIpfsClient::default());
+    let writer = test_writer(&pool, &object_store).with_ipfs(ipfs.clone());
+    while writer.step().await.unwrap() {}
+    assert_eq!(ipfs.len(), 2);
+
+    let mut storage = pool.connection().await.unwrap();
+    let mut chunks = vec![];
+    for index in 0..2 {
+        let chunk = storage.block_archive_dal().get_chunk(index).await.unwrap();
+        let chunk = chunk.unwrap();
+        assert!(chunk.ipfs_cid.is_some());
+        chunks.push(chunk);
+    }
+    let main_node_client = MockClient::builder(L2::default())
+        .method("en_l2BlockArchiveChunk", move |index: Option<u32>| {
+            Ok(match index {
+                Some(index) => chunks.get(index as usize).cloned(),
+                None => chunks.last().cloned(),
+            })
+        })
+        .build();
+    let mut source = ArchiveSyncSource::ipfs(vec![Box::new(main_node_client)], ipfs);
+    source.chunk_size = TEST_CHUNK_SIZE;
+
+    assert_eq!(
+        source.fetch_l2_block_number().await.unwrap(),
+        L2BlockNumber(7)
+    );
+    let expected_block = storage
+        .sync_dal()
+        .sync_block(L2BlockNumber(5), true)
+        .await
+        .unwrap()
+        .unwrap();
+    let block = source.fetch_l2_block(L2BlockNumber(5)).await.unwrap();
+    assert_blocks_eq(&block.unwrap(), &expected_block);
+    assert!(source
+        .fetch_l2_block(L2BlockNumber(9))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn resolving_ipfs_cids_via_multiple_resolvers() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 9).await;
+    let object_store = MockObjectStore::arc();
+    let ipfs = Arc::new(MockIpfsClient::default());
+    let writer = test_writer(&pool, &object_store).with_ipfs(ipfs.clone());
+    while writer.step().await.unwrap() {}
+
+    let mut storage = pool.connection().await.unwrap();
+    let mut chunks = vec![];
+    for index in 0..2 {
+        let chunk = storage.block_archive_dal().get_chunk(index).await.unwrap();
+        chunks.push(chunk.unwrap());
+    }
+    // The first resolver is down, and the second one only knows about chunk #0.
+    let failing_resolver = MockClient::builder(L2::default()).build();
+    let lagging_chunks = chunks[..1].to_vec();
+    let lagging_resolver = MockClient::builder(L2::default())
+        .method("en_l2BlockArchiveChunk", move |index: Option<u32>| {
+            Ok(match index {
+                Some(index) => lagging_chunks.get(index as usize).cloned(),
+                None => lagging_chunks.last().cloned(),
+            })
+        })
+        .build();
+    let main_node_client = MockClient::builder(L2::default())
+        .method("en_l2BlockArchiveChunk", move |index: Option<u32>| {
+            Ok(match index {
+                Some(index) => chunks.get(index as usize).cloned(),
+                None => chunks.last().cloned(),
+            })
+        })
+        .build();
+    let resolvers: Vec<Box<DynClient<L2>>> = vec![
+        Box::new(failing_resolver.clone()),
+        Box::new(lagging_resolver),
+        Box::new(main_node_client),
+    ];
+
+    // The syncing node has the same L2 blocks as the main node, so fetched chunks should be recorded.
+    let en_pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&en_pool, 6).await;
+    let mut source =
+        ArchiveSyncSource::ipfs(resolvers, ipfs.clone()).with_chunk_index(en_pool.clone());
+    source.chunk_size = TEST_CHUNK_SIZE;
+
+    assert_eq!(
+        source.fetch_l2_block_number().await.unwrap(),
+        L2BlockNumber(7)
+    );
+    let block = source.fetch_l2_block(L2BlockNumber(2)).await.unwrap();
+    assert_eq!(block.unwrap().number, L2BlockNumber(2));
+    let block = source.fetch_l2_block(L2BlockNumber(6)).await.unwrap();
+    assert_eq!(block.unwrap().number, L2BlockNumber(6));
+
+    let mut en_storage = en_pool.connection().await.unwrap();
+    let recorded_chunk = en_storage.block_archive_dal().get_chunk(0).await.unwrap();
+    let expected_chunk = storage.block_archive_dal().get_chunk(0).await.unwrap();
+    assert_eq!(recorded_chunk, expected_chunk);
+    // The last L2 block in chunk #1 is not synced yet.
+    let recorded_chunk = en_storage.block_archive_dal().get_chunk(1).await.unwrap();
+    assert_eq!(recorded_chunk, None);
+
+    seal_l1_batch(&mut en_storage, 7, H256::from_low_u64_be(7)).await;
+    source.fetch_l2_block_number().await.unwrap();
+    let recorded_chunk = en_storage.block_archive_dal().get_chunk(1).await.unwrap();
+    let expected_chunk = storage.block_archive_dal().get_chunk(1).await.unwrap();
+    assert_eq!(recorded_chunk, expected_chunk);
+
+    // If all resolvers fail, the error is propagated.
+    let mut source = ArchiveSyncSource::ipfs(vec![Box::new(failing_resolver)], ipfs);
+    source.chunk_size = TEST_CHUNK_SIZE;
+    let err = source.fetch_l2_block(L2BlockNumber(2)).await.unwrap_err();
+    assert_matches!(err, SyncSourceError::Rpc(_));
+}
+
+#[tokio::test]
+async fn falling_back_from_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 5).await;
+    let object_store = MockObjectStore::arc();
+    let writer = test_writer(&pool, &object_store);
+    while writer.step().await.unwrap() {}
+
+    // The primary archive only contains chunk #0.
+    let primary_store = MockObjectStore::arc();
+    let chunk: L2BlockArchiveChunk = object_store.get(0).await.unwrap();
+    primary_store.put(0, &chunk).await.unwrap();
+    let head: L2BlockArchiveHead = object_store.get(()).await.unwrap();
+    primary_store.put((), &head).await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    for number in 6..=9 {
+        seal_l1_batch(&mut storage, number, H256::from_low_u64_be(number.into())).await;
+    }
+    while writer.step().await.unwrap() {}
+
+    let source = FallbackSyncSource::new(
+        test_source(primary_store.clone()),
+        test_source(object_store.clone()),
+    );
+    // The fallback source is ahead of the primary one.
+    assert_eq!(
+        source.fetch_l2_block_number().await.unwrap(),
+        L2BlockNumber(7)
+    );
+    let block = source.fetch_l2_block(L2BlockNumber(5)).await.unwrap();
+    assert_eq!(block.unwrap().number, L2BlockNumber(5));
+
+    // Invalid chunks in the primary archive are not fatal.
+    let mut chunk: L2BlockArchiveChunk = object_store.get(1).await.unwrap();
+    chunk.blocks.remove(1);
+    primary_store.put(1, &chunk).await.unwrap();
+    let source = FallbackSyncSource::new(test_source(primary_store), test_source(object_store));
+    let block = source.fetch_l2_block(L2BlockNumber(5)).await.unwrap();
+    assert_eq!(block.unwrap().number, L2BlockNumber(5));
+    let block = source.fetch_l2_block(L2BlockNumber(6)).await.unwrap();
+    assert_eq!(block.unwrap().number, L2BlockNumber(6));
+}
+
+#[tokio::test]
+async fn invalid_archive_chunk_is_rejected() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    prepare_storage(&pool, 9).await;
+    let object_store = MockObjectStore::arc();
+    let writer = test_writer(&pool, &object_store);
+    while writer.step().await.unwrap() {}
+
+    // Replace chunk #1 with a chunk missing an L2 block.
+    let mut chunk: L2BlockArchiveChunk = object_store.get(1).await.unwrap();
+    chunk.blocks.remove(1);
+    object_store.put(1, &chunk).await.unwrap();
+
+    let source = test_source(object_store.clone());
+    source.fetch_l2_block(L2BlockNumber(3)).await.unwrap();
+    let err = source.fetch_l2_block(L2BlockNumber(5)).await.unwrap_err();
+    assert_matches!(err, SyncSourceError::InvalidArchive(_));
+    assert!(!err.is_transient());
+
+    // Chunk #0 belongs to a different range of L2 blocks.
+    let chunk: L2BlockArchiveChunk = object_store.get(0).await.unwrap();
+    object_store.put(1, &chunk).await.unwrap();
+    let source = test_source(object_store);
+    let err = source.fetch_l2_block(L2BlockNumber(5)).await.unwrap_err();
+    assert_matches!(err, SyncSourceError::InvalidArchive(_));
+}
@@ -0,0 +1,231 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_anchoring::IpfsClient;
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_object_store::{ObjectStore, StoredObject};
+use zksync_types::{api::en, L2BlockNumber};
+
+use super::{
+    metrics::METRICS, L2BlockArchiveChunk, L2BlockArchiveHead, L2_BLOCKS_PER_ARCHIVE_CHUNK,
+};
+
+/// Component running on the main node that archives chunks of L2 blocks in sealed L1 batches to the object store
+/// (and optionally pins them to IPFS), so that they can be used by external nodes as a sync source.
+#[derive(Debug)]
+pub struct BlockArchiveWriter {
+    pool: ConnectionPool<Core>,
+    object_store: Arc<dyn ObjectStore>,
+    ipfs: Option<Arc<dyn IpfsClient>>,
+    pub(super) chunk_size: u32,
+    poll_interval: Duration,
+}
+
+impl BlockArchiveWriter {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub fn new(pool: ConnectionPool<Core>, object_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            pool,
+            object_store,
+            ipfs: None,
+            chunk_size: L2_BLOCKS_PER_ARCHIVE_CHUNK,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Enables pinning archived chunks to IPFS.
+    #[must_use]
+    pub fn with_ipfs(mut self, ipfs: Arc<dyn IpfsClient>) -> Self {
+        self.ipfs = Some(ipfs);
+        self
+    }
+
+    #[must_use]
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Checks that the last archived chunk is still consistent with Postgres, and drops it if it's not
+    /// (e.g., because L2 blocks in it were rolled back). Returns `true` if the chunk was dropped.
+    async fn drop_reverted_chunk(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_chunk: &en::L2BlockArchiveChunkInfo,
+    ) -> anyhow::Result<bool> {
+        let header = storage
+            .blocks_dal()
+            .get_l2_block_header(last_chunk.last_l2_block)
+            .await?;
+        if header.is_some_and(|header| header.hash == last_chunk.last_l2_block_hash) {
+            return Ok(false);
+        }
+
+        tracing::warn!(
+            "Last L2 block #{} in archive chunk #{} was rolled back; dropping the chunk",
+            last_chunk.last_l2_block,
+            last_chunk.index
+        );
+        let mut transaction = storage.start_transaction().await?;
+        let dropped_count = transaction
+            .block_archive_dal()
+            .delete_chunks_starting_from(last_chunk.index)
+            .await?;
+        let new_last_chunk = transaction.block_archive_dal().get_last_chunk().await?;
+        // Update the head before committing, so that the object store never refers to a dropped chunk
+        // after the Postgres index is updated.
+        if let Some(last_chunk) = new_last_chunk {
+            self.object_store
+                .put((), &L2BlockArchiveHead { last_chunk })
+                .await?;
+        } else {
+            self.object_store.remove::<L2BlockArchiveHead>(()).await?;
+        }
+        transaction.commit().await?;
+        METRICS.dropped_chunks.inc_by(dropped_count);
+        Ok(true)
+    }
+
+    /// Returns the index and the L2 block range of the next chunk to archive, or `None` if it's not ready yet.
+    async fn next_chunk(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        last_chunk: Option<&en::L2BlockArchiveChunkInfo>,
+    ) -> anyhow::Result<Option<(u32, L2BlockNumber, L2BlockNumber)>> {
+        let (index, first_l2_block) = if let Some(last_chunk) = last_chunk {
+            let index = last_chunk.index + 1;
+            (index, L2BlockNumber(index * self.chunk_size))
+        } else {
+            // The first chunk may be incomplete if the node was recovered from a snapshot.
+            let Some(earliest_l1_batch) =
+                storage.blocks_dal().get_earliest_l1_batch_number().await?
+            else {
+                return Ok(None);
+            };
+            let (first_l2_block, _) = storage
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(earliest_l1_batch)
+                .await?
+                .with_context(|| format!("L1 batch #{earliest_l1_batch} has no L2 blocks"))?;
+            (first_l2_block.0 / self.chunk_size, first_l2_block)
+        };
+        let last_l2_block = L2BlockNumber((index + 1) * self.chunk_size - 1);
+
+        let Some(sealed_l1_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(None);
+        };
+        let (_, last_sealed_l2_block) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(sealed_l1_batch)
+            .await?
+            .with_context(|| format!("L1 batch #{sealed_l1_batch} has no L2 blocks"))?;
+        Ok((last_l2_block <= last_sealed_l2_block).then_some((
+            index,
+            first_l2_block,
+            last_l2_block,
+        )))
+    }
+
+    /// Archives the next chunk if possible. Returns `true` if the archive was changed.
+    pub(super) async fn step(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("block_archive_writer").await?;
+        let last_chunk = storage.block_archive_dal().get_last_chunk().await?;
+        if let Some(last_chunk) = &last_chunk {
+            if self.drop_reverted_chunk(&mut storage, last_chunk).await? {
+                return Ok(true);
+            }
+        }
+
+        let Some((index, first_l2_block, last_l2_block)) =
+            self.next_chunk(&mut storage, last_chunk.as_ref()).await?
+        else {
+            return Ok(false);
+        };
+        let started_at = tokio::time::Instant::now();
+        let blocks = storage
+            .sync_dal()
+            .sync_blocks_with_transactions(first_l2_block..last_l2_block + 1)
+            .await?;
+        drop(storage);
+
+        let chunk = L2BlockArchiveChunk { blocks };
+        chunk
+            .validate(index, self.chunk_size)
+            .with_context(|| format!("L2 blocks for chunk #{index} are inconsistent"))?;
+        let last_l2_block_hash = chunk
+            .blocks
+            .last()
+            .unwrap()
+            .hash
+            .with_context(|| format!("L2 block #{last_l2_block} has no hash"))?;
+
+        self.object_store
+            .put(index, &chunk)
+            .await
+            .with_context(|| format!("failed putting chunk #{index} to object store"))?;
+        let ipfs_cid = if let Some(ipfs) = &self.ipfs {
+            let data = StoredObject::serialize(&chunk)
+                .map_err(|err| anyhow::anyhow!(err))
+                .context("failed serializing chunk")?;
+            let name = L2BlockArchiveChunk::encode_key(index);
+            match ipfs.upload(&name, data).await {
+                Ok(cid) => Some(cid),
+                Err(err) => {
+                    // Don't record the chunk without a CID; it will be re-uploaded on the next iteration.
+                    tracing::warn!("Failed pinning chunk #{index} to IPFS: {err:#}");
+                    return Ok(false);
+                }
+            }
+        } else {
+            None
+        };
+
+        let chunk_info = en::L2BlockArchiveChunkInfo {
+            index,
+            first_l2_block,
+            last_l2_block,
+            last_l2_block_hash,
+            ipfs_cid,
+        };
+        let mut storage = self.pool.connection_tagged("block_archive_writer").await?;
+        storage
+            .block_archive_dal()
+            .insert_chunk(&chunk_info)
+            .await?;
+        self.object_store
+            .put(
+                (),
+                &L2BlockArchiveHead {
+                    last_chunk: chunk_info,
+                },
+            )
+            .await?;
+
+        tracing::info!("Archived chunk #{index} with L2 blocks {first_l2_block}..={last_l2_block}");
+        METRICS.chunk_latency.observe(started_at.elapsed());
+        METRICS.last_archived_l2_block.set(last_l2_block.0.into());
+        Ok(true)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting L2 block archive writer with chunk size {}, IPFS pinning enabled: {}",
+            self.chunk_size,
+            self.ipfs.is_some()
+        );
+        while !*stop_receiver.borrow_and_update() {
+            let progress = self.step().await?;
+            if !progress
+                && tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received; L2 block archive writer is shutting down");
+        Ok(())
+    }
+}
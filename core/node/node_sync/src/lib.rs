@@ -1,10 +1,13 @@
 pub mod batch_status_updater;
+pub mod batch_verifier;
+pub mod block_archive;
 mod client;
 pub mod external_io;
 pub mod fetcher;
 pub mod genesis;
 mod metrics;
 pub mod sync_action;
+pub mod sync_source;
 mod sync_state;
 pub mod testonly;
 #[cfg(test)]
@@ -16,6 +19,7 @@ pub use self::{
     client::{MainNodeClient, MainNodeHealthCheck},
     external_io::ExternalIO,
     sync_action::{ActionQueue, ActionQueueSender},
+    sync_source::{FallbackSyncSource, SyncSource, SyncSourceError},
     sync_state::SyncState,
 };
 
@@ -0,0 +1,154 @@
+//! Sources of L2 blocks for the external node.
+
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use zksync_object_store::ObjectStoreError;
+use zksync_types::{api::en, L2BlockNumber};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::EnrichedClientError,
+};
+
+use crate::MainNodeClient;
+
+/// Errors that can occur when fetching L2 blocks from a [`SyncSource`].
+#[derive(Debug, thiserror::Error)]
+pub enum SyncSourceError {
+    #[error("error fetching data from main node: {0}")]
+    Rpc(#[from] EnrichedClientError),
+    #[error("error fetching L2 block archive from object store: {0}")]
+    ObjectStore(#[from] ObjectStoreError),
+    #[error("error accessing L2 block archive: {0:#}")]
+    Archive(#[source] anyhow::Error),
+    #[error("invalid L2 block archive: {0:#}")]
+    InvalidArchive(#[source] anyhow::Error),
+}
+
+impl SyncSourceError {
+    /// Checks whether the error is transient, i.e., the failed operation can be retried.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Rpc(err) => err.is_transient(),
+            Self::ObjectStore(err) => err.is_transient(),
+            Self::Archive(_) => true,
+            Self::InvalidArchive(_) => false,
+        }
+    }
+}
+
+/// Source of L2 blocks synced by the external node, such as the main node JSON-RPC API or an archive of L2 blocks
+/// written by the main node.
+///
+/// Sources are not trusted; the external node checks hashes of the synced L2 blocks in the same way regardless
+/// of the source.
+#[async_trait]
+pub trait SyncSource: 'static + Send + Sync + fmt::Debug {
+    /// Returns the number of the last L2 block available from this source.
+    async fn fetch_l2_block_number(&self) -> Result<L2BlockNumber, SyncSourceError>;
+
+    /// Fetches an L2 block together with its transactions. Returns `Ok(None)` if the block is not available (yet).
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+    ) -> Result<Option<en::SyncBlock>, SyncSourceError>;
+}
+
+#[async_trait]
+impl SyncSource for Box<DynClient<L2>> {
+    async fn fetch_l2_block_number(&self) -> Result<L2BlockNumber, SyncSourceError> {
+        Ok(<Self as MainNodeClient>::fetch_l2_block_number(self).await?)
+    }
+
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+    ) -> Result<Option<en::SyncBlock>, SyncSourceError> {
+        Ok(<Self as MainNodeClient>::fetch_l2_block(self, number, true).await?)
+    }
+}
+
+/// [`SyncSource`] fetching L2 blocks from the primary source (e.g., an L2 block archive) and falling back
+/// to another source (e.g., the main node JSON-RPC API) for blocks that are not available from the primary source,
+/// or if the primary source fails. Both transient and non-transient errors of the primary source are not propagated;
+/// after an error, the primary source is not queried for [`Self::PRIMARY_RETRY_INTERVAL`].
+#[derive(Debug)]
+pub struct FallbackSyncSource<P, F> {
+    primary: P,
+    fallback: F,
+    primary_disabled_until: Mutex<Option<Instant>>,
+}
+
+impl<P: SyncSource, F: SyncSource> FallbackSyncSource<P, F> {
+    const PRIMARY_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self {
+            primary,
+            fallback,
+            primary_disabled_until: Mutex::new(None),
+        }
+    }
+
+    fn is_primary_enabled(&self) -> bool {
+        let disabled_until = *self.primary_disabled_until.lock().unwrap();
+        disabled_until.map_or(true, |until| Instant::now() >= until)
+    }
+
+    fn disable_primary(&self, err: &SyncSourceError) {
+        tracing::warn!(
+            "Primary sync source failed, falling back for {:?}: {err}",
+            Self::PRIMARY_RETRY_INTERVAL
+        );
+        *self.primary_disabled_until.lock().unwrap() =
+            Some(Instant::now() + Self::PRIMARY_RETRY_INTERVAL);
+    }
+}
+
+#[async_trait]
+impl<P: SyncSource, F: SyncSource> SyncSource for FallbackSyncSource<P, F> {
+    async fn fetch_l2_block_number(&self) -> Result<L2BlockNumber, SyncSourceError> {
+        let primary_head = if self.is_primary_enabled() {
+            match self.primary.fetch_l2_block_number().await {
+                Ok(head) => Some(head),
+                Err(err) => {
+                    self.disable_primary(&err);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        // The fallback source may be ahead of the primary one (e.g., the archive only contains complete chunks).
+        match (primary_head, self.fallback.fetch_l2_block_number().await) {
+            (Some(primary_head), Ok(fallback_head)) => Ok(primary_head.max(fallback_head)),
+            (Some(primary_head), Err(err)) => {
+                tracing::info!(
+                    "Fallback sync source failed fetching head, using primary source: {err}"
+                );
+                Ok(primary_head)
+            }
+            (None, res) => res,
+        }
+    }
+
+    async fn fetch_l2_block(
+        &self,
+        number: L2BlockNumber,
+    ) -> Result<Option<en::SyncBlock>, SyncSourceError> {
+        if self.is_primary_enabled() {
+            match self.primary.fetch_l2_block(number).await {
+                Ok(Some(block)) => return Ok(Some(block)),
+                Ok(None) => {
+                    tracing::trace!("L2 block #{number} is not available from primary sync source");
+                }
+                Err(err) => self.disable_primary(&err),
+            }
+        }
+        self.fallback.fetch_l2_block(number).await
+    }
+}
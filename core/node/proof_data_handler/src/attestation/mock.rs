@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use super::{AttestationError, AttestationVerifier, QuoteReport};
+
+/// Mock [`AttestationVerifier`] accepting a fixed set of test vectors, i.e. quotes
+/// with pre-defined verification results. All other quotes are rejected.
+#[derive(Debug, Default)]
+pub struct MockAttestationVerifier {
+    test_vectors: HashMap<Vec<u8>, QuoteReport>,
+}
+
+impl MockAttestationVerifier {
+    /// Adds a test vector: `quote` will be verified as having the specified report.
+    pub fn with_test_vector(mut self, quote: impl Into<Vec<u8>>, report: QuoteReport) -> Self {
+        self.test_vectors.insert(quote.into(), report);
+        self
+    }
+}
+
+impl AttestationVerifier for MockAttestationVerifier {
+    fn verify(&self, quote: &[u8]) -> Result<QuoteReport, AttestationError> {
+        self.test_vectors
+            .get(quote)
+            .cloned()
+            .ok_or_else(|| AttestationError::InvalidQuote("not a known test vector".to_owned()))
+    }
+}
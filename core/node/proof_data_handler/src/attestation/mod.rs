@@ -0,0 +1,146 @@
+//! Verification of TEE attestation quotes submitted by `zksync_tee_prover`.
+//!
+//! An attestation is accepted if:
+//!
+//! - the quote is authentic, as checked by an [`AttestationVerifier`],
+//! - the enclave that produced the quote is in the [`EnclaveRegistry`], and
+//! - the quote is bound to the public key that will be used to sign TEE proofs.
+
+use std::{collections::HashSet, fmt, sync::Arc};
+
+use zksync_config::configs::ProofDataHandlerConfig;
+use zksync_types::H256;
+
+pub use self::{mock::MockAttestationVerifier, sgx::DcapQuoteVerifier};
+
+mod mock;
+mod sgx;
+#[cfg(test)]
+mod tests;
+
+/// Information about an enclave extracted from a verified attestation quote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteReport {
+    /// Hash of the enclave code and initial data (MRENCLAVE).
+    pub mr_enclave: H256,
+    /// Hash of the enclave signing key (MRSIGNER).
+    pub mr_signer: H256,
+    pub isv_prod_id: u16,
+    pub isv_svn: u16,
+    /// Whether the enclave runs in debug mode; such enclaves don't provide any confidentiality
+    /// or integrity guarantees.
+    pub debug: bool,
+    /// Data supplied by the enclave when generating the quote.
+    pub report_data: [u8; 64],
+}
+
+/// Errors that can occur during attestation verification.
+#[derive(Debug, thiserror::Error)]
+pub enum AttestationError {
+    #[error("malformed quote: {0}")]
+    MalformedQuote(String),
+    #[error("invalid quote: {0}")]
+    InvalidQuote(String),
+    #[error("enclave is running in debug mode")]
+    DebugEnclave,
+    #[error("unknown enclave (MRENCLAVE {mr_enclave:?}, MRSIGNER {mr_signer:?})")]
+    UnknownEnclave { mr_enclave: H256, mr_signer: H256 },
+    #[error("quote report data doesn't match the public key")]
+    PubkeyMismatch,
+    #[error("no attestation registered for the public key")]
+    MissingAttestation,
+}
+
+/// Verifier of TEE attestation quotes.
+///
+/// The verifier is only responsible for checking quote authenticity; checking that the enclave
+/// is trusted is done separately using [`EnclaveRegistry`].
+pub trait AttestationVerifier: 'static + fmt::Debug + Send + Sync {
+    /// Verifies the quote and returns information about the attested enclave.
+    fn verify(&self, quote: &[u8]) -> Result<QuoteReport, AttestationError>;
+}
+
+/// Registry of enclaves trusted to produce TEE proofs.
+#[derive(Debug, Clone, Default)]
+pub struct EnclaveRegistry {
+    mr_enclaves: HashSet<H256>,
+    mr_signers: HashSet<H256>,
+}
+
+impl EnclaveRegistry {
+    pub fn new(
+        mr_enclaves: impl IntoIterator<Item = H256>,
+        mr_signers: impl IntoIterator<Item = H256>,
+    ) -> Self {
+        Self {
+            mr_enclaves: mr_enclaves.into_iter().collect(),
+            mr_signers: mr_signers.into_iter().collect(),
+        }
+    }
+
+    pub fn from_config(config: &ProofDataHandlerConfig) -> Self {
+        Self::new(
+            config.tee_allowed_mr_enclaves.iter().copied(),
+            config.tee_allowed_mr_signers.iter().copied(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mr_enclaves.is_empty() && self.mr_signers.is_empty()
+    }
+
+    /// Checks that the enclave is trusted, i.e. either its MRENCLAVE or its MRSIGNER is registered.
+    pub fn check(&self, report: &QuoteReport) -> Result<(), AttestationError> {
+        if report.debug {
+            return Err(AttestationError::DebugEnclave);
+        }
+        if self.mr_enclaves.contains(&report.mr_enclave)
+            || self.mr_signers.contains(&report.mr_signer)
+        {
+            Ok(())
+        } else {
+            Err(AttestationError::UnknownEnclave {
+                mr_enclave: report.mr_enclave,
+                mr_signer: report.mr_signer,
+            })
+        }
+    }
+}
+
+/// Checks attestations against an [`AttestationVerifier`] and an [`EnclaveRegistry`].
+#[derive(Debug, Clone)]
+pub(crate) struct AttestationChecker {
+    verifier: Arc<dyn AttestationVerifier>,
+    registry: EnclaveRegistry,
+}
+
+impl AttestationChecker {
+    pub(crate) fn new(verifier: Arc<dyn AttestationVerifier>, registry: EnclaveRegistry) -> Self {
+        if registry.is_empty() {
+            tracing::warn!(
+                "No trusted TEE enclaves are configured; all TEE attestations will be rejected"
+            );
+        }
+        Self { verifier, registry }
+    }
+
+    /// Checks that `quote` is produced by a trusted enclave and attests `pubkey`. The public key
+    /// must be placed at the start of the report data, with the remaining bytes zeroed.
+    pub(crate) fn check(
+        &self,
+        pubkey: &[u8],
+        quote: &[u8],
+    ) -> Result<QuoteReport, AttestationError> {
+        let report = self.verifier.verify(quote)?;
+        self.registry.check(&report)?;
+
+        if pubkey.is_empty() || pubkey.len() > report.report_data.len() {
+            return Err(AttestationError::PubkeyMismatch);
+        }
+        let (key_part, padding) = report.report_data.split_at(pubkey.len());
+        if key_part != pubkey || padding.iter().any(|&byte| byte != 0) {
+            return Err(AttestationError::PubkeyMismatch);
+        }
+        Ok(report)
+    }
+}
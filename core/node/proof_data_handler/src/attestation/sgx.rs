@@ -0,0 +1,305 @@
+//! Verification of Intel SGX DCAP quotes (version 3, with ECDSA P-256 attestation keys).
+
+use std::{fs, time::SystemTime};
+
+use anyhow::Context as _;
+use p256::ecdsa::{signature::Verifier as _, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use x509_cert::{
+    der::{DecodePem as _, Encode as _},
+    Certificate,
+};
+use zksync_config::configs::ProofDataHandlerConfig;
+use zksync_types::H256;
+
+use super::{AttestationError, AttestationVerifier, QuoteReport};
+
+const QUOTE_VERSION: u16 = 3;
+const ATTESTATION_KEY_TYPE_ECDSA_P256: u16 = 2;
+const TEE_TYPE_SGX: u32 = 0;
+
+const HEADER_LEN: usize = 48;
+pub(super) const REPORT_BODY_LEN: usize = 384;
+const SIGNATURE_LEN: usize = 64;
+const ATTESTATION_KEY_LEN: usize = 64;
+const CERT_DATA_TYPE_PCK_CERT_CHAIN: u16 = 5;
+
+/// Debug flag in the enclave attributes.
+const ATTRIBUTES_DEBUG: u8 = 0x02;
+
+/// Intel SGX Root CA certificate (<https://certificates.trustedservices.intel.com/Intel_SGX_Provisioning_Certification_RootCA.pem>),
+/// which is used as the trusted root unless another root is configured.
+pub(super) const INTEL_SGX_ROOT_CA_PEM: &[u8] = include_bytes!("intel_sgx_root_ca.pem");
+/// MRSIGNER of the Intel quoting enclave, as specified by the QE identity published by Intel.
+pub(super) const INTEL_QE_MR_SIGNER: H256 = H256([
+    0x8c, 0x4f, 0x57, 0x75, 0xd7, 0x96, 0x50, 0x3e, 0x96, 0x13, 0x7f, 0x77, 0xc6, 0x8a, 0x82, 0x9a,
+    0x00, 0x56, 0xac, 0x8d, 0xed, 0x70, 0x14, 0x0b, 0x08, 0x1b, 0x09, 0x44, 0x90, 0xc5, 0x7b, 0xff,
+]);
+/// ISVPRODID of the Intel quoting enclave (QE3).
+pub(super) const INTEL_QE_ISV_PROD_ID: u16 = 1;
+
+/// Sequential reader of little-endian quote fields.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, field: &str) -> Result<&'a [u8], AttestationError> {
+        if self.0.len() < len {
+            return Err(AttestationError::MalformedQuote(format!(
+                "{field} is truncated"
+            )));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn read_u16(&mut self, field: &str) -> Result<u16, AttestationError> {
+        let bytes = self.take(2, field)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self, field: &str) -> Result<u32, AttestationError> {
+        let bytes = self.take(4, field)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+/// Parsed SGX DCAP quote. Only the fields necessary for verification are retained.
+#[derive(Debug)]
+pub(super) struct SgxQuote<'a> {
+    /// Quote header and enclave report body, i.e. data signed by the attestation key.
+    signed_data: &'a [u8],
+    report_body: &'a [u8],
+    signature: &'a [u8],
+    attestation_key: &'a [u8],
+    qe_report: &'a [u8],
+    qe_report_signature: &'a [u8],
+    qe_auth_data: &'a [u8],
+    pck_cert_chain: &'a [u8],
+}
+
+impl<'a> SgxQuote<'a> {
+    pub(super) fn parse(quote: &'a [u8]) -> Result<Self, AttestationError> {
+        let mut reader = Reader(quote);
+        let version = reader.read_u16("version")?;
+        if version != QUOTE_VERSION {
+            return Err(AttestationError::MalformedQuote(format!(
+                "unsupported quote version {version}"
+            )));
+        }
+        let attestation_key_type = reader.read_u16("attestation key type")?;
+        if attestation_key_type != ATTESTATION_KEY_TYPE_ECDSA_P256 {
+            return Err(AttestationError::MalformedQuote(format!(
+                "unsupported attestation key type {attestation_key_type}"
+            )));
+        }
+        let tee_type = reader.read_u32("TEE type")?;
+        if tee_type != TEE_TYPE_SGX {
+            return Err(AttestationError::MalformedQuote(format!(
+                "unsupported TEE type {tee_type:#x}"
+            )));
+        }
+        reader.take(HEADER_LEN - 8, "header")?;
+        let report_body = reader.take(REPORT_BODY_LEN, "report body")?;
+        let signed_data = &quote[..HEADER_LEN + REPORT_BODY_LEN];
+
+        let signature_data_len = reader.read_u32("signature data length")? as usize;
+        let mut reader = Reader(reader.take(signature_data_len, "signature data")?);
+        let signature = reader.take(SIGNATURE_LEN, "quote signature")?;
+        let attestation_key = reader.take(ATTESTATION_KEY_LEN, "attestation key")?;
+        let qe_report = reader.take(REPORT_BODY_LEN, "QE report")?;
+        let qe_report_signature = reader.take(SIGNATURE_LEN, "QE report signature")?;
+        let qe_auth_data_len = reader.read_u16("QE authentication data length")? as usize;
+        let qe_auth_data = reader.take(qe_auth_data_len, "QE authentication data")?;
+        let cert_data_type = reader.read_u16("certification data type")?;
+        if cert_data_type != CERT_DATA_TYPE_PCK_CERT_CHAIN {
+            return Err(AttestationError::MalformedQuote(format!(
+                "unsupported certification data type {cert_data_type}"
+            )));
+        }
+        let cert_data_len = reader.read_u32("certification data length")? as usize;
+        let pck_cert_chain = reader.take(cert_data_len, "certification data")?;
+
+        Ok(Self {
+            signed_data,
+            report_body,
+            signature,
+            attestation_key,
+            qe_report,
+            qe_report_signature,
+            qe_auth_data,
+            pck_cert_chain,
+        })
+    }
+
+    pub(super) fn report(&self) -> QuoteReport {
+        report_from_body(self.report_body)
+    }
+
+    /// Verifies the quote signature made by the attestation key, and that the attestation key
+    /// is certified by the quoting enclave (QE) whose report is signed by the PCK key.
+    /// The QE must match the Intel QE identity.
+    pub(super) fn verify_signatures(&self, pck_key: &VerifyingKey) -> Result<(), AttestationError> {
+        let mut sec1_key = Vec::with_capacity(ATTESTATION_KEY_LEN + 1);
+        sec1_key.push(0x04);
+        sec1_key.extend_from_slice(self.attestation_key);
+        let attestation_key = VerifyingKey::from_sec1_bytes(&sec1_key)
+            .map_err(|_| AttestationError::InvalidQuote("invalid attestation key".to_owned()))?;
+        verify_signature(&attestation_key, self.signed_data, self.signature)
+            .map_err(|_| AttestationError::InvalidQuote("invalid quote signature".to_owned()))?;
+
+        let qe_report = report_from_body(self.qe_report);
+        if qe_report.mr_signer != INTEL_QE_MR_SIGNER
+            || qe_report.isv_prod_id != INTEL_QE_ISV_PROD_ID
+            || qe_report.debug
+        {
+            return Err(AttestationError::InvalidQuote(
+                "QE report doesn't match the Intel QE identity".to_owned(),
+            ));
+        }
+        let qe_report_data = qe_report.report_data;
+        let expected_hash = Sha256::new()
+            .chain_update(self.attestation_key)
+            .chain_update(self.qe_auth_data)
+            .finalize();
+        let (hash, padding) = qe_report_data.split_at(32);
+        if hash != expected_hash.as_slice() || padding.iter().any(|&byte| byte != 0) {
+            return Err(AttestationError::InvalidQuote(
+                "QE report doesn't certify the attestation key".to_owned(),
+            ));
+        }
+        verify_signature(pck_key, self.qe_report, self.qe_report_signature)
+            .map_err(|_| AttestationError::InvalidQuote("invalid QE report signature".to_owned()))
+    }
+}
+
+fn report_from_body(body: &[u8]) -> QuoteReport {
+    assert_eq!(body.len(), REPORT_BODY_LEN);
+    QuoteReport {
+        mr_enclave: H256::from_slice(&body[64..96]),
+        mr_signer: H256::from_slice(&body[128..160]),
+        isv_prod_id: u16::from_le_bytes([body[256], body[257]]),
+        isv_svn: u16::from_le_bytes([body[258], body[259]]),
+        debug: body[48] & ATTRIBUTES_DEBUG != 0,
+        report_data: body[320..384].try_into().unwrap(),
+    }
+}
+
+/// Verifies a raw (`r || s`) ECDSA signature over SHA-256 digest of the message.
+fn verify_signature(key: &VerifyingKey, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+    let signature = Signature::from_slice(signature)?;
+    key.verify(message, &signature)?;
+    Ok(())
+}
+
+fn verify_certificate(cert: &Certificate, issuer_key: &VerifyingKey) -> anyhow::Result<()> {
+    let tbs_certificate = cert.tbs_certificate.to_der()?;
+    let signature = Signature::from_der(cert.signature.raw_bytes())?;
+    issuer_key.verify(&tbs_certificate, &signature)?;
+    Ok(())
+}
+
+fn is_certificate_valid_at(cert: &Certificate, now: SystemTime) -> bool {
+    let validity = &cert.tbs_certificate.validity;
+    let since_epoch = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    validity.not_before.to_unix_duration() <= since_epoch
+        && since_epoch <= validity.not_after.to_unix_duration()
+}
+
+fn certificate_key(cert: &Certificate) -> anyhow::Result<VerifyingKey> {
+    let key = cert
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .raw_bytes();
+    Ok(VerifyingKey::from_sec1_bytes(key)?)
+}
+
+/// Verifies the PCK certificate chain (leaf first, root last) and returns the PCK public key.
+pub(super) fn verify_pck_chain(
+    chain_pem: &[u8],
+    trusted_root: &[u8],
+    now: SystemTime,
+) -> Result<VerifyingKey, AttestationError> {
+    let invalid = |msg: &str| AttestationError::InvalidQuote(msg.to_owned());
+
+    // The chain may be NUL-terminated.
+    let chain_pem = chain_pem
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(&[][..], |last| &chain_pem[..=last]);
+    let chain = Certificate::load_pem_chain(chain_pem)
+        .map_err(|_| invalid("malformed PCK certificate chain"))?;
+    let (root, _) = chain
+        .split_last()
+        .ok_or_else(|| invalid("empty PCK certificate chain"))?;
+
+    for (cert, issuer) in chain.iter().zip(chain.iter().skip(1).chain([root])) {
+        let issuer_key =
+            certificate_key(issuer).map_err(|_| invalid("invalid PCK chain certificate key"))?;
+        verify_certificate(cert, &issuer_key)
+            .map_err(|_| invalid("invalid PCK chain certificate signature"))?;
+        if !is_certificate_valid_at(cert, now) {
+            return Err(invalid("PCK chain certificate is expired or not yet valid"));
+        }
+    }
+    let root = root
+        .to_der()
+        .map_err(|_| invalid("malformed PCK chain root certificate"))?;
+    if root != trusted_root {
+        return Err(invalid(
+            "PCK certificate chain doesn't lead to the trusted root",
+        ));
+    }
+    certificate_key(&chain[0]).map_err(|_| invalid("invalid PCK certificate key"))
+}
+
+/// [`AttestationVerifier`] for SGX DCAP quotes.
+///
+/// The verifier checks the quote signature, the quoting enclave (QE) report and identity, and
+/// the PCK certificate chain embedded into the quote. The chain must lead to the trusted root
+/// certificate (by default, the Intel SGX Root CA), and all its certificates must be valid
+/// at the time of verification. Revocation and TCB status of the platform are not checked, since
+/// this requires collateral (CRLs and TCB info) from the Intel provisioning certification service.
+#[derive(Debug)]
+pub struct DcapQuoteVerifier {
+    /// DER encoding of the trusted root certificate.
+    trusted_root: Vec<u8>,
+}
+
+impl DcapQuoteVerifier {
+    /// Creates a verifier requiring PCK certificate chains to lead to the specified PEM root.
+    pub fn with_trusted_root_pem(pem: &[u8]) -> anyhow::Result<Self> {
+        let root = Certificate::from_pem(pem).context("failed parsing root certificate")?;
+        Ok(Self {
+            trusted_root: root.to_der()?,
+        })
+    }
+
+    /// Creates a verifier requiring PCK certificate chains to lead to the Intel SGX Root CA.
+    pub fn with_intel_root() -> Self {
+        Self::with_trusted_root_pem(INTEL_SGX_ROOT_CA_PEM)
+            .expect("embedded Intel SGX Root CA is invalid")
+    }
+
+    pub fn from_config(config: &ProofDataHandlerConfig) -> anyhow::Result<Self> {
+        if let Some(path) = &config.tee_sgx_root_ca_path {
+            let pem = fs::read(path).with_context(|| format!("failed reading {path}"))?;
+            Self::with_trusted_root_pem(&pem)
+        } else {
+            Ok(Self::with_intel_root())
+        }
+    }
+}
+
+impl AttestationVerifier for DcapQuoteVerifier {
+    fn verify(&self, quote: &[u8]) -> Result<QuoteReport, AttestationError> {
+        let quote = SgxQuote::parse(quote)?;
+        let pck_key =
+            verify_pck_chain(quote.pck_cert_chain, &self.trusted_root, SystemTime::now())?;
+        quote.verify_signatures(&pck_key)?;
+        Ok(quote.report())
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use assert_matches::assert_matches;
+use p256::ecdsa::{signature::Signer as _, Signature, SigningKey};
+use sha2::{Digest, Sha256};
+use x509_cert::{
+    der::{DecodePem as _, Encode as _},
+    Certificate,
+};
+use zksync_types::H256;
+
+use super::{
+    sgx::{self, SgxQuote},
+    *,
+};
+
+const MR_ENCLAVE: H256 = H256::repeat_byte(0xee);
+const MR_SIGNER: H256 = H256::repeat_byte(0x55);
+
+fn report(pubkey: &[u8]) -> QuoteReport {
+    let mut report_data = [0_u8; 64];
+    report_data[..pubkey.len()].copy_from_slice(pubkey);
+    QuoteReport {
+        mr_enclave: MR_ENCLAVE,
+        mr_signer: MR_SIGNER,
+        isv_prod_id: 1,
+        isv_svn: 2,
+        debug: false,
+        report_data,
+    }
+}
+
+fn report_body(report: &QuoteReport) -> Vec<u8> {
+    let mut body = vec![0_u8; sgx::REPORT_BODY_LEN];
+    if report.debug {
+        body[48] = 0x02;
+    }
+    body[64..96].copy_from_slice(report.mr_enclave.as_bytes());
+    body[128..160].copy_from_slice(report.mr_signer.as_bytes());
+    body[256..258].copy_from_slice(&report.isv_prod_id.to_le_bytes());
+    body[258..260].copy_from_slice(&report.isv_svn.to_le_bytes());
+    body[320..384].copy_from_slice(&report.report_data);
+    body
+}
+
+fn sign(key: &SigningKey, message: &[u8]) -> Vec<u8> {
+    let signature: Signature = key.sign(message);
+    signature.to_bytes().to_vec()
+}
+
+fn qe_report(report_data: [u8; 64]) -> QuoteReport {
+    QuoteReport {
+        mr_enclave: H256::repeat_byte(0x0e),
+        mr_signer: sgx::INTEL_QE_MR_SIGNER,
+        isv_prod_id: sgx::INTEL_QE_ISV_PROD_ID,
+        isv_svn: 8,
+        debug: false,
+        report_data,
+    }
+}
+
+/// Builds a synthetic SGX quote signed by the specified attestation and PCK keys.
+fn build_quote(
+    report: &QuoteReport,
+    attestation_key: &SigningKey,
+    pck_key: &SigningKey,
+) -> Vec<u8> {
+    build_quote_with_qe(report, attestation_key, pck_key, qe_report)
+}
+
+fn build_quote_with_qe(
+    report: &QuoteReport,
+    attestation_key: &SigningKey,
+    pck_key: &SigningKey,
+    qe_report: impl FnOnce([u8; 64]) -> QuoteReport,
+) -> Vec<u8> {
+    let mut quote = vec![];
+    quote.extend_from_slice(&3_u16.to_le_bytes()); // version
+    quote.extend_from_slice(&2_u16.to_le_bytes()); // attestation key type
+    quote.extend_from_slice(&0_u32.to_le_bytes()); // TEE type
+    quote.extend_from_slice(&[0; 40]);
+    quote.extend_from_slice(&report_body(report));
+
+    let attestation_pubkey = attestation_key.verifying_key().to_encoded_point(false);
+    let attestation_pubkey = &attestation_pubkey.as_bytes()[1..];
+    let qe_auth_data = b"auth data";
+    let mut qe_report_data = [0_u8; 64];
+    let hash = Sha256::new()
+        .chain_update(attestation_pubkey)
+        .chain_update(qe_auth_data)
+        .finalize();
+    qe_report_data[..32].copy_from_slice(&hash);
+    let qe_report = report_body(&qe_report(qe_report_data));
+    let cert_data = b"-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n\0";
+
+    let mut signature_data = sign(attestation_key, &quote);
+    signature_data.extend_from_slice(attestation_pubkey);
+    signature_data.extend_from_slice(&qe_report);
+    signature_data.extend_from_slice(&sign(pck_key, &qe_report));
+    signature_data.extend_from_slice(&(qe_auth_data.len() as u16).to_le_bytes());
+    signature_data.extend_from_slice(qe_auth_data);
+    signature_data.extend_from_slice(&5_u16.to_le_bytes());
+    signature_data.extend_from_slice(&(cert_data.len() as u32).to_le_bytes());
+    signature_data.extend_from_slice(cert_data);
+
+    quote.extend_from_slice(&(signature_data.len() as u32).to_le_bytes());
+    quote.extend_from_slice(&signature_data);
+    quote
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_slice(&[seed; 32]).unwrap()
+}
+
+#[test]
+fn parsing_and_verifying_sgx_quote() {
+    let report = report(&[1; 33]);
+    let attestation_key = signing_key(1);
+    let pck_key = signing_key(2);
+    let quote = build_quote(&report, &attestation_key, &pck_key);
+
+    let parsed = SgxQuote::parse(&quote).unwrap();
+    assert_eq!(parsed.report(), report);
+    parsed.verify_signatures(pck_key.verifying_key()).unwrap();
+
+    let err = parsed
+        .verify_signatures(signing_key(3).verifying_key())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::InvalidQuote(msg) if msg.contains("QE report"));
+}
+
+#[test]
+fn rejecting_tampered_sgx_quote() {
+    let report = report(&[1; 33]);
+    let pck_key = signing_key(2);
+    let mut quote = build_quote(&report, &signing_key(1), &pck_key);
+    // Change MRENCLAVE in the report body.
+    quote[48 + 64] ^= 1;
+
+    let parsed = SgxQuote::parse(&quote).unwrap();
+    let err = parsed
+        .verify_signatures(pck_key.verifying_key())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::InvalidQuote(msg) if msg.contains("quote signature"));
+}
+
+#[test]
+fn rejecting_sgx_quote_from_non_intel_qe() {
+    let pck_key = signing_key(2);
+    let quote = build_quote_with_qe(&report(&[1; 33]), &signing_key(1), &pck_key, |data| {
+        QuoteReport {
+            mr_signer: H256::repeat_byte(0x42),
+            ..qe_report(data)
+        }
+    });
+    let parsed = SgxQuote::parse(&quote).unwrap();
+    let err = parsed
+        .verify_signatures(pck_key.verifying_key())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::InvalidQuote(msg) if msg.contains("Intel QE identity"));
+
+    let quote = build_quote_with_qe(&report(&[1; 33]), &signing_key(1), &pck_key, |data| {
+        QuoteReport {
+            debug: true,
+            ..qe_report(data)
+        }
+    });
+    let parsed = SgxQuote::parse(&quote).unwrap();
+    let err = parsed
+        .verify_signatures(pck_key.verifying_key())
+        .unwrap_err();
+    assert_matches!(err, AttestationError::InvalidQuote(msg) if msg.contains("Intel QE identity"));
+}
+
+#[test]
+fn verifying_pck_chain() {
+    let trusted_root = Certificate::from_pem(sgx::INTEL_SGX_ROOT_CA_PEM)
+        .unwrap()
+        .to_der()
+        .unwrap();
+    // The self-signed root is the shortest valid chain.
+    let now = SystemTime::now();
+    sgx::verify_pck_chain(sgx::INTEL_SGX_ROOT_CA_PEM, &trusted_root, now).unwrap();
+
+    let before_issuance = SystemTime::UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+    let err = sgx::verify_pck_chain(sgx::INTEL_SGX_ROOT_CA_PEM, &trusted_root, before_issuance)
+        .unwrap_err();
+    assert_matches!(err, AttestationError::InvalidQuote(msg) if msg.contains("not yet valid"));
+
+    let err = sgx::verify_pck_chain(sgx::INTEL_SGX_ROOT_CA_PEM, b"other root", now).unwrap_err();
+    assert_matches!(err, AttestationError::InvalidQuote(msg) if msg.contains("trusted root"));
+}
+
+#[test]
+fn rejecting_malformed_sgx_quote() {
+    let quote = build_quote(&report(&[1; 33]), &signing_key(1), &signing_key(2));
+    let err = SgxQuote::parse(&quote[..quote.len() - 1]).unwrap_err();
+    assert_matches!(err, AttestationError::MalformedQuote(_));
+
+    let mut quote = quote;
+    quote[0] = 4; // version
+    let err = SgxQuote::parse(&quote).unwrap_err();
+    assert_matches!(err, AttestationError::MalformedQuote(msg) if msg.contains("version"));
+
+    let err = DcapQuoteVerifier::with_intel_root()
+        .verify(b"garbage")
+        .unwrap_err();
+    assert_matches!(err, AttestationError::MalformedQuote(_));
+}
+
+#[test]
+fn checking_attestations() {
+    let pubkey = [1; 33];
+    let verifier = MockAttestationVerifier::default()
+        .with_test_vector(b"quote".to_vec(), report(&pubkey))
+        .with_test_vector(
+            b"debug_quote".to_vec(),
+            QuoteReport {
+                debug: true,
+                ..report(&pubkey)
+            },
+        );
+    let verifier = Arc::new(verifier);
+
+    let checker = AttestationChecker::new(verifier.clone(), EnclaveRegistry::new([MR_ENCLAVE], []));
+    checker.check(&pubkey, b"quote").unwrap();
+    assert_matches!(
+        checker.check(&pubkey, b"debug_quote").unwrap_err(),
+        AttestationError::DebugEnclave
+    );
+    assert_matches!(
+        checker.check(&pubkey, b"unknown_quote").unwrap_err(),
+        AttestationError::InvalidQuote(_)
+    );
+    assert_matches!(
+        checker.check(&[2; 33], b"quote").unwrap_err(),
+        AttestationError::PubkeyMismatch
+    );
+    assert_matches!(
+        checker.check(&[1; 32], b"quote").unwrap_err(),
+        AttestationError::PubkeyMismatch
+    );
+
+    let checker = AttestationChecker::new(verifier.clone(), EnclaveRegistry::new([], [MR_SIGNER]));
+    checker.check(&pubkey, b"quote").unwrap();
+
+    let checker = AttestationChecker::new(
+        verifier,
+        EnclaveRegistry::new([H256::repeat_byte(1)], [H256::repeat_byte(2)]),
+    );
+    assert_matches!(
+        checker.check(&pubkey, b"quote").unwrap_err(),
+        AttestationError::UnknownEnclave { mr_enclave, mr_signer }
+            if mr_enclave == MR_ENCLAVE && mr_signer == MR_SIGNER
+    );
+}
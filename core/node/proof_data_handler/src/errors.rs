@@ -4,10 +4,16 @@ use axum::{
 };
 use zksync_dal::DalError;
 use zksync_object_store::ObjectStoreError;
+use zksync_types::L1BatchNumber;
+
+use crate::attestation::AttestationError;
 
 pub(crate) enum RequestProcessorError {
     ObjectStore(ObjectStoreError),
     Dal(DalError),
+    TeeAttestation(AttestationError),
+    InvalidTeeProof(String),
+    MissingRootHash(L1BatchNumber),
 }
 
 impl IntoResponse for RequestProcessorError {
@@ -32,6 +38,21 @@ impl IntoResponse for RequestProcessorError {
                     ),
                 }
             }
+            RequestProcessorError::TeeAttestation(err) => {
+                tracing::warn!("Rejected TEE attestation: {err}");
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid TEE attestation: {err}"),
+                )
+            }
+            RequestProcessorError::InvalidTeeProof(err) => {
+                tracing::warn!("Rejected TEE proof: {err}");
+                (StatusCode::BAD_REQUEST, format!("Invalid TEE proof: {err}"))
+            }
+            RequestProcessorError::MissingRootHash(l1_batch_number) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Root hash of L1 batch #{l1_batch_number} is not computed yet"),
+            ),
         };
         (status_code, message).into_response()
     }
@@ -1,6 +1,7 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
+use attestation::{AttestationChecker, AttestationVerifier, DcapQuoteVerifier, EnclaveRegistry};
 use axum::{extract::Path, routing::post, Json, Router};
 use request_processor::RequestProcessor;
 use tee_request_processor::TeeRequestProcessor;
@@ -17,6 +18,7 @@ use zksync_types::commitment::L1BatchCommitmentMode;
 #[cfg(test)]
 mod tests;
 
+pub mod attestation;
 mod errors;
 mod request_processor;
 mod tee_request_processor;
@@ -30,7 +32,17 @@ pub async fn run_server(
 ) -> anyhow::Result<()> {
     let bind_address = SocketAddr::from(([0, 0, 0, 0], config.http_port));
     tracing::debug!("Starting proof data handler server on {bind_address}");
-    let app = create_proof_processing_router(blob_store, connection_pool, config, commitment_mode);
+    let attestation_verifier = Arc::new(
+        DcapQuoteVerifier::from_config(&config)
+            .context("failed initializing TEE quote verifier")?,
+    );
+    let app = create_proof_processing_router(
+        blob_store,
+        connection_pool,
+        config,
+        commitment_mode,
+        attestation_verifier,
+    );
 
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
@@ -53,6 +65,7 @@ fn create_proof_processing_router(
     connection_pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
     commitment_mode: L1BatchCommitmentMode,
+    attestation_verifier: Arc<dyn AttestationVerifier>,
 ) -> Router {
     let get_proof_gen_processor = RequestProcessor::new(
         blob_store.clone(),
@@ -86,8 +99,14 @@ fn create_proof_processing_router(
         );
 
     if config.tee_support {
-        let get_tee_proof_gen_processor =
-            TeeRequestProcessor::new(blob_store, connection_pool, config.clone());
+        let attestation_checker =
+            AttestationChecker::new(attestation_verifier, EnclaveRegistry::from_config(&config));
+        let get_tee_proof_gen_processor = TeeRequestProcessor::new(
+            blob_store,
+            connection_pool,
+            config.clone(),
+            attestation_checker,
+        );
         let submit_tee_proof_processor = get_tee_proof_gen_processor.clone();
         let register_tee_attestation_processor = get_tee_proof_gen_processor.clone();
 
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use axum::{extract::Path, Json};
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SECP256K1};
 use zksync_config::configs::ProofDataHandlerConfig;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_object_store::ObjectStore;
@@ -11,15 +12,19 @@ use zksync_prover_interface::{
     },
     inputs::TeeVerifierInput,
 };
-use zksync_types::L1BatchNumber;
+use zksync_types::{L1BatchNumber, H256};
 
-use crate::errors::RequestProcessorError;
+use crate::{
+    attestation::{AttestationChecker, AttestationError},
+    errors::RequestProcessorError,
+};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool<Core>,
     config: ProofDataHandlerConfig,
+    attestation_checker: AttestationChecker,
 }
 
 impl TeeRequestProcessor {
@@ -27,11 +32,13 @@ impl TeeRequestProcessor {
         blob_store: Arc<dyn ObjectStore>,
         pool: ConnectionPool<Core>,
         config: ProofDataHandlerConfig,
+        attestation_checker: AttestationChecker,
     ) -> Self {
         Self {
             blob_store,
             pool,
             config,
+            attestation_checker,
         }
     }
 
@@ -79,22 +86,46 @@ impl TeeRequestProcessor {
             .connection()
             .await
             .map_err(RequestProcessorError::Dal)?;
-        let mut dal = connection.tee_proof_generation_dal();
 
         tracing::info!(
             "Received proof {:?} for block number: {:?}",
             proof,
             l1_batch_number
         );
-        dal.save_proof_artifacts_metadata(
-            l1_batch_number,
-            &proof.0.signature,
-            &proof.0.pubkey,
-            &proof.0.proof,
-            proof.0.tee_type,
-        )
-        .await
-        .map_err(RequestProcessorError::Dal)?;
+        // The enclave registry may have changed since the attestation was registered,
+        // so the attestation is re-checked for each proof.
+        let attestation = connection
+            .tee_proof_generation_dal()
+            .get_attestation(&proof.0.pubkey)
+            .await
+            .map_err(RequestProcessorError::Dal)?
+            .ok_or(RequestProcessorError::TeeAttestation(
+                AttestationError::MissingAttestation,
+            ))?;
+        self.attestation_checker
+            .check(&proof.0.pubkey, &attestation)
+            .map_err(RequestProcessorError::TeeAttestation)?;
+
+        let root_hash = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Dal)?
+            .ok_or(RequestProcessorError::MissingRootHash(l1_batch_number))?;
+        verify_proof_signature(&proof, root_hash)
+            .map_err(RequestProcessorError::InvalidTeeProof)?;
+
+        connection
+            .tee_proof_generation_dal()
+            .save_proof_artifacts_metadata(
+                l1_batch_number,
+                &proof.0.signature,
+                &proof.0.pubkey,
+                &proof.0.proof,
+                proof.0.tee_type,
+            )
+            .await
+            .map_err(RequestProcessorError::Dal)?;
 
         Ok(Json(SubmitProofResponse::Success))
     }
@@ -105,6 +136,16 @@ impl TeeRequestProcessor {
     ) -> Result<Json<RegisterTeeAttestationResponse>, RequestProcessorError> {
         tracing::info!("Received attestation: {:?}", payload);
 
+        let report = self
+            .attestation_checker
+            .check(&payload.pubkey, &payload.attestation)
+            .map_err(RequestProcessorError::TeeAttestation)?;
+        tracing::info!(
+            "Verified attestation of enclave with MRENCLAVE {:?}, MRSIGNER {:?}",
+            report.mr_enclave,
+            report.mr_signer
+        );
+
         let mut connection = self
             .pool
             .connection()
@@ -119,3 +160,21 @@ impl TeeRequestProcessor {
         Ok(Json(RegisterTeeAttestationResponse::Success))
     }
 }
+
+/// Checks that the proof is the root hash of the L1 batch signed by the attested key
+/// (as done by `zksync_tee_prover`).
+fn verify_proof_signature(proof: &SubmitTeeProofRequest, root_hash: H256) -> Result<(), String> {
+    if proof.0.proof != root_hash.as_bytes() {
+        return Err(format!(
+            "proof doesn't match the L1 batch root hash {root_hash:?}"
+        ));
+    }
+    let pubkey = PublicKey::from_slice(&proof.0.pubkey)
+        .map_err(|err| format!("invalid public key: {err}"))?;
+    let signature = Signature::from_compact(&proof.0.signature)
+        .map_err(|err| format!("invalid signature: {err}"))?;
+    let message = Message::from_slice(root_hash.as_bytes()).expect("root hash has 32 bytes");
+    SECP256K1
+        .verify_ecdsa(&message, &signature, &pubkey)
+        .map_err(|_| "signature doesn't match the L1 batch root hash".to_owned())
+}
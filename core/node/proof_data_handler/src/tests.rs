@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 use axum::{
     body::Body,
@@ -6,21 +6,94 @@ use axum::{
     response::Response,
     Router,
 };
+use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
 use serde_json::json;
 use tower::ServiceExt;
 use zksync_basic_types::U256;
 use zksync_config::configs::ProofDataHandlerConfig;
-use zksync_contracts::{BaseSystemContracts, SystemContractCode};
+use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes, SystemContractCode};
 use zksync_dal::{ConnectionPool, CoreDal};
 use zksync_multivm::interface::{L1BatchEnv, L2BlockEnv, SystemEnv, TxExecutionMode};
 use zksync_object_store::MockObjectStore;
 use zksync_prover_interface::{
-    api::SubmitTeeProofRequest,
+    api::{RegisterTeeAttestationRequest, SubmitTeeProofRequest},
     inputs::{TeeVerifierInput, V1TeeVerifierInput, WitnessInputMerklePaths},
 };
-use zksync_types::{commitment::L1BatchCommitmentMode, L1BatchNumber, H256};
+use zksync_types::{
+    block::{L1BatchHeader, L1BatchTreeData},
+    commitment::L1BatchCommitmentMode,
+    L1BatchNumber, ProtocolVersion, ProtocolVersionId, H256,
+};
+
+use crate::{
+    attestation::{MockAttestationVerifier, QuoteReport},
+    create_proof_processing_router,
+};
+
+const TEE_ATTESTATION: [u8; 5] = [15, 16, 17, 18, 19];
+const UNKNOWN_TEE_ATTESTATION: [u8; 5] = [20, 21, 22, 23, 24];
+const MR_ENCLAVE: H256 = H256::repeat_byte(0xee);
+const ROOT_HASH: H256 = H256::repeat_byte(0x12);
 
-use crate::create_proof_processing_router;
+fn tee_signing_key() -> SecretKey {
+    SecretKey::from_slice(&[7; 32]).unwrap()
+}
+
+fn tee_pubkey() -> Vec<u8> {
+    PublicKey::from_secret_key(SECP256K1, &tee_signing_key())
+        .serialize()
+        .to_vec()
+}
+
+// TEE proof for the specified root hash signed in the same way as by `zksync_tee_prover`.
+fn tee_proof_request(root_hash: H256) -> SubmitTeeProofRequest {
+    let message = Message::from_slice(root_hash.as_bytes()).unwrap();
+    let signature = SECP256K1.sign_ecdsa(&message, &tee_signing_key());
+    serde_json::from_value(json!({
+        "signature": signature.serialize_compact().to_vec(),
+        "pubkey": tee_pubkey(),
+        "proof": root_hash.as_bytes(),
+        "tee_type": "Sgx",
+    }))
+    .unwrap()
+}
+
+fn test_config() -> ProofDataHandlerConfig {
+    ProofDataHandlerConfig {
+        http_port: 1337,
+        proof_generation_timeout_in_secs: 10,
+        tee_support: true,
+        tee_allowed_mr_enclaves: vec![MR_ENCLAVE],
+        tee_allowed_mr_signers: vec![],
+        tee_sgx_root_ca_path: None,
+    }
+}
+
+// Mock attestation verifier accepting `TEE_ATTESTATION` (produced by a trusted enclave)
+// and `UNKNOWN_TEE_ATTESTATION` (produced by an unknown enclave) for `tee_pubkey()`.
+fn mock_attestation_verifier() -> Arc<MockAttestationVerifier> {
+    let pubkey = tee_pubkey();
+    let mut report_data = [0; 64];
+    report_data[..pubkey.len()].copy_from_slice(&pubkey);
+    let report = QuoteReport {
+        mr_enclave: MR_ENCLAVE,
+        mr_signer: H256::repeat_byte(0x55),
+        isv_prod_id: 0,
+        isv_svn: 0,
+        debug: false,
+        report_data,
+    };
+    let verifier = MockAttestationVerifier::default()
+        .with_test_vector(TEE_ATTESTATION, report.clone())
+        .with_test_vector(
+            UNKNOWN_TEE_ATTESTATION,
+            QuoteReport {
+                mr_enclave: H256::repeat_byte(0x01),
+                ..report
+            },
+        );
+    Arc::new(verifier)
+}
 
 // Test the /tee/proof_inputs endpoint by:
 // 1. Mocking an object store with a single batch blob containing TEE verifier input
@@ -87,12 +160,9 @@ async fn request_tee_proof_inputs() {
     let app = create_proof_processing_router(
         blob_store,
         db_conn_pool,
-        ProofDataHandlerConfig {
-            http_port: 1337,
-            proof_generation_timeout_in_secs: 10,
-            tee_support: true,
-        },
+        test_config(),
         L1BatchCommitmentMode::Rollup,
+        mock_attestation_verifier(),
     );
     let req_body = Body::from(serde_json::to_vec(&json!({})).unwrap());
     let response = app
@@ -130,38 +200,27 @@ async fn submit_tee_proof() {
 
     // send a request to the /tee/submit_proofs endpoint, using a mocked TEE proof
 
-    let tee_proof_request_str = r#"{
-        "signature": [ 0, 1, 2, 3, 4 ],
-        "pubkey": [ 5, 6, 7, 8, 9 ],
-        "proof": [ 10, 11, 12, 13, 14 ],
-        "tee_type": "Sgx"
-    }"#;
-    let tee_proof_request =
-        serde_json::from_str::<SubmitTeeProofRequest>(tee_proof_request_str).unwrap();
+    let tee_proof_request = tee_proof_request(ROOT_HASH);
     let uri = format!("/tee/submit_proofs/{}", batch_number.0);
     let app = create_proof_processing_router(
         blob_store,
         db_conn_pool.clone(),
-        ProofDataHandlerConfig {
-            http_port: 1337,
-            proof_generation_timeout_in_secs: 10,
-            tee_support: true,
-        },
+        test_config(),
         L1BatchCommitmentMode::Rollup,
+        mock_attestation_verifier(),
     );
 
     // this should fail because we haven't saved the attestation for the pubkey yet
 
     let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
-    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
     // save the attestation for the pubkey
 
-    let attestation = [15, 16, 17, 18, 19];
     let mut proof_dal = db_conn_pool.connection().await.unwrap();
     proof_dal
         .tee_proof_generation_dal()
-        .save_attestation(&tee_proof_request.0.pubkey, &attestation)
+        .save_attestation(&tee_proof_request.0.pubkey, &TEE_ATTESTATION)
         .await
         .expect("Failed to save attestation");
 
@@ -182,6 +241,133 @@ async fn submit_tee_proof() {
     assert!(oldest_batch_number.is_none());
 }
 
+// Test that TEE proofs signed by a key attested by an unknown enclave are rejected
+#[tokio::test]
+async fn submit_tee_proof_from_unknown_enclave() {
+    let blob_store = MockObjectStore::arc();
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let batch_number = L1BatchNumber::from(1);
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number, "mocked_object_path").await;
+
+    // the attestation was registered, but the enclave is not (or is no longer) trusted
+
+    let mut proof_dal = db_conn_pool.connection().await.unwrap();
+    proof_dal
+        .tee_proof_generation_dal()
+        .save_attestation(&tee_pubkey(), &UNKNOWN_TEE_ATTESTATION)
+        .await
+        .expect("Failed to save attestation");
+
+    let tee_proof_request = tee_proof_request(ROOT_HASH);
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+    let app = create_proof_processing_router(
+        blob_store,
+        db_conn_pool.clone(),
+        test_config(),
+        L1BatchCommitmentMode::Rollup,
+        mock_attestation_verifier(),
+    );
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // the batch should still be awaiting proof
+
+    let oldest_batch_number = proof_dal
+        .tee_proof_generation_dal()
+        .get_oldest_unpicked_batch()
+        .await
+        .unwrap();
+    assert_eq!(oldest_batch_number, Some(batch_number));
+}
+
+// Test that TEE proofs not matching the L1 batch root hash or having an invalid signature are rejected
+#[tokio::test]
+async fn submit_invalid_tee_proof() {
+    let blob_store = MockObjectStore::arc();
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let batch_number = L1BatchNumber::from(1);
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number, "mocked_object_path").await;
+
+    let mut proof_dal = db_conn_pool.connection().await.unwrap();
+    proof_dal
+        .tee_proof_generation_dal()
+        .save_attestation(&tee_pubkey(), &TEE_ATTESTATION)
+        .await
+        .expect("Failed to save attestation");
+
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+    let app = create_proof_processing_router(
+        blob_store,
+        db_conn_pool.clone(),
+        test_config(),
+        L1BatchCommitmentMode::Rollup,
+        mock_attestation_verifier(),
+    );
+
+    // a validly signed proof for another root hash
+    let wrong_root_hash_request = tee_proof_request(H256::repeat_byte(0x21));
+    // a proof for the correct root hash with a signature over another root hash
+    let mut wrong_signature_request = tee_proof_request(ROOT_HASH);
+    wrong_signature_request.0.signature = wrong_root_hash_request.0.signature.clone();
+    // a proof with a malformed signature
+    let mut malformed_signature_request = tee_proof_request(ROOT_HASH);
+    malformed_signature_request.0.signature = vec![0, 1, 2, 3, 4];
+
+    for request in [
+        wrong_root_hash_request,
+        wrong_signature_request,
+        malformed_signature_request,
+    ] {
+        let response = send_submit_tee_proof_request(&app, &uri, &request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // the batch should still be awaiting proof
+
+    let oldest_batch_number = proof_dal
+        .tee_proof_generation_dal()
+        .get_oldest_unpicked_batch()
+        .await
+        .unwrap();
+    assert_eq!(oldest_batch_number, Some(batch_number));
+}
+
+// Test /tee/register_attestation endpoint: only attestations from trusted enclaves are saved
+#[tokio::test]
+async fn register_tee_attestation() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        test_config(),
+        L1BatchCommitmentMode::Rollup,
+        mock_attestation_verifier(),
+    );
+
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    for (attestation, expected_status) in [
+        (UNKNOWN_TEE_ATTESTATION, StatusCode::BAD_REQUEST),
+        ([0; 5], StatusCode::BAD_REQUEST),
+        (TEE_ATTESTATION, StatusCode::OK),
+    ] {
+        let request = RegisterTeeAttestationRequest {
+            attestation: attestation.to_vec(),
+            pubkey: tee_pubkey(),
+        };
+        let response = send_register_tee_attestation_request(&app, &request).await;
+        assert_eq!(response.status(), expected_status);
+
+        let saved_attestation = conn
+            .tee_proof_generation_dal()
+            .get_attestation(&tee_pubkey())
+            .await
+            .unwrap();
+        let expected_attestation =
+            (expected_status == StatusCode::OK).then(|| attestation.to_vec());
+        assert_eq!(saved_attestation, expected_attestation);
+    }
+}
+
 // Mock SQL db with information about the status of the TEE proof generation
 async fn mock_tee_batch_status(
     db_conn_pool: ConnectionPool<zksync_dal::Core>,
@@ -205,6 +391,35 @@ async fn mock_tee_batch_status(
         .await
         .expect("Failed to create tee_verifier_input_producer_job");
 
+    // mock the L1 batch with the root hash computed by the Merkle tree
+
+    let mut storage = db_conn_pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let header = L1BatchHeader::new(
+        batch_number,
+        0,
+        BaseSystemContractsHashes::default(),
+        ProtocolVersionId::default(),
+    );
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    let tree_data = L1BatchTreeData {
+        hash: ROOT_HASH,
+        rollup_last_leaf_index: 1,
+    };
+    storage
+        .blocks_dal()
+        .save_l1_batch_tree_data(batch_number, &tree_data)
+        .await
+        .unwrap();
+
     // pretend that the TEE verifier input blob file was fetched successfully
 
     input_producer_dal
@@ -247,3 +462,21 @@ async fn send_submit_tee_proof_request(
         .await
         .unwrap()
 }
+
+async fn send_register_tee_attestation_request(
+    app: &Router,
+    request: &RegisterTeeAttestationRequest,
+) -> Response {
+    let req_body = Body::from(serde_json::to_vec(request).unwrap());
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/tee/register_attestation")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(req_body)
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
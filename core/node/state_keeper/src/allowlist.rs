@@ -0,0 +1,288 @@
+//! Transaction allowlist used by permissioned deployments.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{
+    tx_allowlist_dal::TxAllowlistPermission, Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_types::{
+    event::DEPLOY_EVENT_SIGNATURE, Address, Transaction, VmEvent, CONTRACT_DEPLOYER_ADDRESS,
+};
+use zksync_utils::h256_to_account_address;
+
+/// Reason why a transaction is not permitted by the [`TxAllowlist`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TxAllowlistError {
+    #[error("address {0:?} is not allowed to deploy contracts")]
+    DeployerNotAllowed(Address),
+    #[error("neither sender {sender:?} nor contract {contract:?} is allowlisted")]
+    NotAllowlisted { sender: Address, contract: Address },
+}
+
+/// Set of addresses permitted to interact with the sequencer. An L2 transaction is accepted if either its sender
+/// or its recipient contract is allowlisted. Direct contract deployments additionally require the sender
+/// to have the deployer permission, regardless of other permissions. L1 transactions are never restricted.
+///
+/// Deployments are also checked after execution (see [`Self::check_deployments()`]): every contract deployed
+/// by the transaction must be deployed by an address with the deployer permission. Thus, deploying
+/// via a factory contract or a smart account is rejected unless the factory / account itself has the deployer
+/// permission.
+///
+/// # Limitations
+///
+/// Calls made during execution other than deployments are not inspected, i.e. an allowlisted sender (or a call
+/// to an allowlisted contract) can still call non-allowlisted contracts indirectly.
+#[derive(Debug, Default)]
+pub struct TxAllowlist {
+    senders: HashSet<Address>,
+    contracts: HashSet<Address>,
+    deployers: HashSet<Address>,
+}
+
+impl TxAllowlist {
+    /// Loads the allowlist from Postgres.
+    pub async fn load(storage: &mut Connection<'_, Core>) -> anyhow::Result<Self> {
+        let entries = storage
+            .tx_allowlist_dal()
+            .get_entries()
+            .await
+            .context("failed loading tx allowlist")?;
+        Ok(entries.into_iter().collect())
+    }
+
+    /// Checks whether a transaction from `sender` to `contract` is permitted.
+    pub fn check(&self, sender: Address, contract: Address) -> Result<(), TxAllowlistError> {
+        if contract == CONTRACT_DEPLOYER_ADDRESS {
+            return if self.deployers.contains(&sender) {
+                Ok(())
+            } else {
+                Err(TxAllowlistError::DeployerNotAllowed(sender))
+            };
+        }
+        if self.senders.contains(&sender) || self.contracts.contains(&contract) {
+            Ok(())
+        } else {
+            Err(TxAllowlistError::NotAllowlisted { sender, contract })
+        }
+    }
+
+    /// Checks whether the transaction is permitted.
+    pub fn check_tx(&self, tx: &Transaction) -> Result<(), TxAllowlistError> {
+        if tx.is_l1() {
+            return Ok(());
+        }
+        self.check(tx.initiator_account(), tx.recipient_account())
+    }
+
+    /// Checks deployments performed during transaction execution based on the `ContractDeployed` events
+    /// emitted by the contract deployer. The deploying address in these events is the immediate caller
+    /// of the contract deployer, e.g. a factory contract rather than the transaction sender.
+    pub fn check_deployments(&self, events: &[VmEvent]) -> Result<(), TxAllowlistError> {
+        let deployers = events
+            .iter()
+            .filter(|event| {
+                event.address == CONTRACT_DEPLOYER_ADDRESS
+                    && event.indexed_topics.len() == 4
+                    && event.indexed_topics[0] == *DEPLOY_EVENT_SIGNATURE
+            })
+            .map(|event| h256_to_account_address(&event.indexed_topics[1]));
+        for deployer in deployers {
+            if !self.deployers.contains(&deployer) {
+                return Err(TxAllowlistError::DeployerNotAllowed(deployer));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<(Address, TxAllowlistPermission)> for TxAllowlist {
+    fn from_iter<I: IntoIterator<Item = (Address, TxAllowlistPermission)>>(iter: I) -> Self {
+        let mut this = Self::default();
+        for (address, permission) in iter {
+            let set = match permission {
+                TxAllowlistPermission::Sender => &mut this.senders,
+                TxAllowlistPermission::Contract => &mut this.contracts,
+                TxAllowlistPermission::Deployer => &mut this.deployers,
+            };
+            set.insert(address);
+        }
+        this
+    }
+}
+
+/// Shared [`TxAllowlist`] that is periodically reloaded from Postgres by [`TxAllowlistUpdater`],
+/// so that allowlist changes are applied without a restart.
+#[derive(Debug, Clone, Default)]
+pub struct TxAllowlistCache(Arc<RwLock<Arc<TxAllowlist>>>);
+
+impl TxAllowlistCache {
+    /// Creates a cache with the allowlist loaded from Postgres.
+    pub async fn new(pool: &ConnectionPool<Core>) -> anyhow::Result<Self> {
+        let mut storage = pool.connection_tagged("tx_allowlist").await?;
+        let allowlist = TxAllowlist::load(&mut storage).await?;
+        Ok(Self(Arc::new(RwLock::new(Arc::new(allowlist)))))
+    }
+
+    /// Returns the current allowlist snapshot.
+    pub fn get(&self) -> Arc<TxAllowlist> {
+        self.0.read().expect("allowlist lock is poisoned").clone()
+    }
+
+    fn set(&self, allowlist: TxAllowlist) {
+        *self.0.write().expect("allowlist lock is poisoned") = Arc::new(allowlist);
+    }
+
+    /// Returns a task that periodically reloads the allowlist.
+    pub fn updater(
+        &self,
+        pool: ConnectionPool<Core>,
+        reload_interval: Duration,
+    ) -> TxAllowlistUpdater {
+        TxAllowlistUpdater {
+            cache: self.clone(),
+            pool,
+            reload_interval,
+        }
+    }
+}
+
+impl From<TxAllowlist> for TxAllowlistCache {
+    fn from(allowlist: TxAllowlist) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(allowlist))))
+    }
+}
+
+/// Task periodically reloading a [`TxAllowlistCache`].
+#[derive(Debug)]
+pub struct TxAllowlistUpdater {
+    cache: TxAllowlistCache,
+    pool: ConnectionPool<Core>,
+    reload_interval: Duration,
+}
+
+impl TxAllowlistUpdater {
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            let mut storage = self.pool.connection_tagged("tx_allowlist").await?;
+            match TxAllowlist::load(&mut storage).await {
+                Ok(allowlist) => self.cache.set(allowlist),
+                Err(err) => tracing::warn!("Failed reloading tx allowlist: {err:#}"),
+            }
+            drop(storage);
+
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.reload_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, tx allowlist updater is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testonly::test_batch_executor::deploy_event;
+
+    #[test]
+    fn checking_allowlist() {
+        let sender = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        let deployer = Address::repeat_byte(3);
+        let other = Address::repeat_byte(0xff);
+        let allowlist: TxAllowlist = [
+            (sender, TxAllowlistPermission::Sender),
+            (contract, TxAllowlistPermission::Contract),
+            (deployer, TxAllowlistPermission::Deployer),
+        ]
+        .into_iter()
+        .collect();
+
+        allowlist.check(sender, other).unwrap();
+        allowlist.check(other, contract).unwrap();
+        allowlist
+            .check(deployer, CONTRACT_DEPLOYER_ADDRESS)
+            .unwrap();
+        assert_eq!(
+            allowlist.check(other, other).unwrap_err(),
+            TxAllowlistError::NotAllowlisted {
+                sender: other,
+                contract: other,
+            }
+        );
+        // Sender permission doesn't allow deployments.
+        assert_eq!(
+            allowlist
+                .check(sender, CONTRACT_DEPLOYER_ADDRESS)
+                .unwrap_err(),
+            TxAllowlistError::DeployerNotAllowed(sender)
+        );
+        // Deployer permission doesn't allow calling arbitrary contracts.
+        allowlist.check(deployer, other).unwrap_err();
+    }
+
+    #[test]
+    fn checking_deployments() {
+        let deployer = Address::repeat_byte(3);
+        let factory = Address::repeat_byte(4);
+        let allowlist: TxAllowlist = [
+            (deployer, TxAllowlistPermission::Deployer),
+            (factory, TxAllowlistPermission::Contract),
+        ]
+        .into_iter()
+        .collect();
+
+        allowlist.check_deployments(&[]).unwrap();
+        allowlist
+            .check_deployments(&[deploy_event(deployer)])
+            .unwrap();
+        // A call to the factory is permitted, but deploying through it is not.
+        allowlist
+            .check(Address::repeat_byte(0xff), factory)
+            .unwrap();
+        assert_eq!(
+            allowlist
+                .check_deployments(&[deploy_event(deployer), deploy_event(factory)])
+                .unwrap_err(),
+            TxAllowlistError::DeployerNotAllowed(factory)
+        );
+
+        let mut unrelated_event = deploy_event(factory);
+        unrelated_event.address = factory;
+        allowlist.check_deployments(&[unrelated_event]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reloading_allowlist() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let sender = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        let cache = TxAllowlistCache::new(&pool).await.unwrap();
+        cache.get().check(sender, contract).unwrap_err();
+
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .tx_allowlist_dal()
+            .add_entry(sender, TxAllowlistPermission::Sender)
+            .await
+            .unwrap();
+        drop(storage);
+
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let updater = cache.updater(pool, Duration::from_millis(10));
+        let updater_handle = tokio::spawn(updater.run(stop_receiver));
+        while cache.get().check(sender, contract).is_err() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        stop_sender.send_replace(true);
+        updater_handle.await.unwrap().unwrap();
+    }
+}
@@ -66,6 +66,7 @@ impl BatchExecutor for MainBatchExecutor {
             save_call_traces: self.save_call_traces,
             optional_bytecode_compression: self.optional_bytecode_compression,
             commands: commands_receiver,
+            bundle_tx_count: None,
         };
 
         let stop_receiver = stop_receiver.clone();
@@ -97,6 +98,9 @@ struct CommandReceiver {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
     commands: mpsc::Receiver<Command>,
+    /// Number of transactions executed since the start of the latest bundle, or `None` if no bundle was started.
+    /// Each executed transaction leaves a VM snapshot on top of the snapshot taken at the bundle start.
+    bundle_tx_count: Option<usize>,
 }
 
 impl CommandReceiver {
@@ -126,6 +130,18 @@ impl CommandReceiver {
                         break;
                     }
                 }
+                Command::StartBundle(resp) => {
+                    self.start_bundle(&mut vm);
+                    if resp.send(()).is_err() {
+                        break;
+                    }
+                }
+                Command::RollbackBundle(resp) => {
+                    self.rollback_bundle(&mut vm);
+                    if resp.send(()).is_err() {
+                        break;
+                    }
+                }
                 Command::StartNextL2Block(l2_block_env, resp) => {
                     self.start_next_l2_block(l2_block_env, &mut vm);
                     if resp.send(()).is_err() {
@@ -163,12 +179,15 @@ impl CommandReceiver {
     }
 
     fn execute_tx<S: WriteStorage>(
-        &self,
+        &mut self,
         tx: &Transaction,
         vm: &mut VmInstance<S, HistoryEnabled>,
     ) -> TxExecutionResult {
         // Save pre-`execute_next_tx` VM snapshot.
         vm.make_snapshot();
+        if let Some(count) = &mut self.bundle_tx_count {
+            *count += 1;
+        }
 
         // Execute the transaction.
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::Execution].start();
@@ -207,6 +226,26 @@ impl CommandReceiver {
         latency.observe();
     }
 
+    fn start_bundle<S: WriteStorage>(&mut self, vm: &mut VmInstance<S, HistoryEnabled>) {
+        vm.make_snapshot();
+        self.bundle_tx_count = Some(0);
+    }
+
+    fn rollback_bundle<S: WriteStorage>(&mut self, vm: &mut VmInstance<S, HistoryEnabled>) {
+        let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::TxRollback].start();
+        let tx_count = self
+            .bundle_tx_count
+            .take()
+            .expect("bundle rollback requested without starting a bundle");
+        // Snapshots of the bundled transactions are discarded without restoring them; the VM state is restored
+        // only once, from the snapshot taken at the bundle start.
+        for _ in 0..tx_count {
+            vm.pop_snapshot_no_rollback();
+        }
+        vm.rollback_to_the_latest_snapshot();
+        latency.observe();
+    }
+
     fn start_next_l2_block<S: WriteStorage>(
         &self,
         l2_block_env: L2BlockEnv,
@@ -207,6 +207,51 @@ impl BatchExecutorHandle {
         Ok(())
     }
 
+    /// Saves the VM state before executing a transaction bundle, so that the entire bundle can be rolled back
+    /// using [`Self::rollback_bundle()`].
+    pub async fn start_bundle(&mut self) -> anyhow::Result<()> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let send_failed = self
+            .commands
+            .send(Command::StartBundle(response_sender))
+            .await
+            .is_err();
+        if send_failed {
+            return Err(self.handle.wait_for_error().await);
+        }
+
+        let latency = EXECUTOR_METRICS.batch_executor_command_response_time
+            [&ExecutorCommand::StartBundle]
+            .start();
+        if response_receiver.await.is_err() {
+            return Err(self.handle.wait_for_error().await);
+        }
+        latency.observe();
+        Ok(())
+    }
+
+    /// Rolls back the VM state to the start of the latest bundle, discarding all transactions executed since.
+    pub async fn rollback_bundle(&mut self) -> anyhow::Result<()> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let send_failed = self
+            .commands
+            .send(Command::RollbackBundle(response_sender))
+            .await
+            .is_err();
+        if send_failed {
+            return Err(self.handle.wait_for_error().await);
+        }
+
+        let latency = EXECUTOR_METRICS.batch_executor_command_response_time
+            [&ExecutorCommand::RollbackBundle]
+            .start();
+        if response_receiver.await.is_err() {
+            return Err(self.handle.wait_for_error().await);
+        }
+        latency.observe();
+        Ok(())
+    }
+
     pub async fn finish_batch(mut self) -> anyhow::Result<FinishedL1Batch> {
         let (response_sender, response_receiver) = oneshot::channel();
         let send_failed = self
@@ -263,6 +308,8 @@ pub(super) enum Command {
     ExecuteTx(Box<Transaction>, oneshot::Sender<TxExecutionResult>),
     StartNextL2Block(L2BlockEnv, oneshot::Sender<()>),
     RollbackLastTx(oneshot::Sender<()>),
+    StartBundle(oneshot::Sender<()>),
+    RollbackBundle(oneshot::Sender<()>),
     FinishBatch(oneshot::Sender<FinishedL1Batch>),
     FinishBatchWithCache(oneshot::Sender<(FinishedL1Batch, StorageViewCache)>),
 }
@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -9,7 +9,7 @@ use anyhow::Context as _;
 use async_trait::async_trait;
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::BaseSystemContracts;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{private_txs_dal::TxBundle, ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
 use zksync_multivm::{interface::Halt, utils::derive_base_fee_and_gas_per_pubdata};
 use zksync_node_fee_model::BatchFeeModelInputProvider;
@@ -22,15 +22,17 @@ use zksync_utils::time::millis_since_epoch;
 use zksync_vm_utils::storage::L1BatchParamsProvider;
 
 use crate::{
+    allowlist::TxAllowlistCache,
     io::{
         common::{load_pending_batch, poll_iters, IoCursor},
         seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
-        L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO,
+        BundleTxPosition, L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO,
     },
     mempool_actor::l2_tx_filter,
     metrics::{L2BlockSealReason, AGGREGATION_METRICS, KEEPER_METRICS},
     seal_criteria::{
-        IoSealCriteria, L2BlockMaxPayloadSizeSealer, TimeoutSealer, UnexecutableReason,
+        IoSealCriteria, L2BlockCountSealer, L2BlockMaxPayloadSizeSealer, TimeoutSealer,
+        UnexecutableReason,
     },
     updates::UpdatesManager,
     MempoolGuard,
@@ -46,6 +48,7 @@ pub struct MempoolIO {
     pool: ConnectionPool<Core>,
     timeout_sealer: TimeoutSealer,
     l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer,
+    l2_block_count_sealer: L2BlockCountSealer,
     filter: L2TxFilter,
     l1_batch_params_provider: L1BatchParamsProvider,
     fee_account: Address,
@@ -55,15 +58,53 @@ pub struct MempoolIO {
     // Used to keep track of gas prices to set accepted price per pubdata byte in blocks.
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     chain_id: L2ChainId,
+    tx_allowlist: Option<TxAllowlistCache>,
+    /// Transaction bundles that can be included into the current L2 block, ordered by submission.
+    pending_bundles: VecDeque<TxBundle>,
+    /// Bundle currently being executed by the state keeper.
+    active_bundle: Option<ActiveBundle>,
+    /// IDs of bundles included into L2 blocks that may not be persisted yet.
+    included_bundle_ids: HashSet<u64>,
+}
+
+/// Bundle currently being executed by the state keeper.
+#[derive(Debug)]
+struct ActiveBundle {
+    bundle: TxBundle,
+    /// Number of bundled transactions returned from [`MempoolIO::wait_for_next_tx()`].
+    served_count: usize,
+}
+
+impl ActiveBundle {
+    fn is_finished(&self) -> bool {
+        self.served_count == self.bundle.transactions.len()
+    }
+
+    fn contains(&self, tx_hash: H256) -> bool {
+        self.bundle
+            .transactions
+            .iter()
+            .any(|tx| tx.hash() == tx_hash)
+    }
 }
 
 impl IoSealCriteria for MempoolIO {
     fn should_seal_l1_batch_unconditionally(&mut self, manager: &UpdatesManager) -> bool {
+        // Bundles must be included atomically, so we never seal in the middle of a bundle.
+        if self.is_executing_bundle() {
+            return false;
+        }
         self.timeout_sealer
             .should_seal_l1_batch_unconditionally(manager)
+            || self
+                .l2_block_count_sealer
+                .should_seal_l1_batch_unconditionally(manager)
     }
 
     fn should_seal_l2_block(&mut self, manager: &UpdatesManager) -> bool {
+        if self.is_executing_bundle() {
+            return false;
+        }
         if self.timeout_sealer.should_seal_l2_block(manager) {
             AGGREGATION_METRICS.l2_block_reason_inc(&L2BlockSealReason::Timeout);
             return true;
@@ -193,8 +234,9 @@ impl StateKeeperIO for MempoolIO {
             )
             .await
             .context("failed creating L2 transaction filter")?;
+            self.load_pending_bundles(cursor.next_l2_block).await?;
 
-            if !self.mempool.has_next(&self.filter) {
+            if !self.mempool.has_next(&self.filter) && self.pending_bundles.is_empty() {
                 tokio::time::sleep(self.delay_interval).await;
                 continue;
             }
@@ -229,6 +271,7 @@ impl StateKeeperIO for MempoolIO {
         let Ok(timestamp) = timeout_result else {
             return Ok(None);
         };
+        self.load_pending_bundles(cursor.next_l2_block).await?;
 
         Ok(Some(L2BlockParams {
             timestamp,
@@ -241,6 +284,11 @@ impl StateKeeperIO for MempoolIO {
         &mut self,
         max_wait: Duration,
     ) -> anyhow::Result<Option<Transaction>> {
+        // Bundled transactions take precedence over the mempool.
+        if let Some(tx) = self.next_bundle_tx().await? {
+            return Ok(Some(tx));
+        }
+
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
@@ -260,6 +308,18 @@ impl StateKeeperIO for MempoolIO {
                         .await?;
                     continue;
                 }
+                // Transactions are checked against the allowlist on the API level as well, but the allowlist
+                // may have changed since the transaction was accepted.
+                if let Some(tx_allowlist) = &self.tx_allowlist {
+                    if let Err(err) = tx_allowlist.get().check_tx(&tx) {
+                        tracing::warn!(
+                            "Found tx not permitted by allowlist in state keeper, hash: {:?}: {err}",
+                            tx.hash()
+                        );
+                        self.reject(&tx, UnexecutableReason::NotAllowlisted).await?;
+                        continue;
+                    }
+                }
                 return Ok(Some(tx));
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -269,7 +329,21 @@ impl StateKeeperIO for MempoolIO {
         Ok(None)
     }
 
+    fn bundle_position(&self) -> Option<BundleTxPosition> {
+        let active_bundle = self.active_bundle.as_ref()?;
+        Some(BundleTxPosition {
+            index: active_bundle.served_count - 1,
+            len: active_bundle.bundle.transactions.len(),
+        })
+    }
+
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()> {
+        if let Some(active_bundle) = self.take_active_bundle(tx.hash()) {
+            // Bundled transactions are not in the mempool; instead, the entire bundle will be retried.
+            self.pending_bundles.push_front(active_bundle.bundle);
+            return Ok(());
+        }
+
         // Reset nonces in the mempool.
         self.mempool.rollback(&tx);
         // Insert the transaction back.
@@ -287,6 +361,21 @@ impl StateKeeperIO for MempoolIO {
             "L1 transactions should not be rejected: {reason}"
         );
 
+        if let Some(active_bundle) = self.take_active_bundle(rejected.hash()) {
+            let bundle_id = active_bundle.bundle.id;
+            KEEPER_METRICS.inc_rejected_txs(reason.as_metric_label());
+            tracing::warn!(
+                "Bundle #{bundle_id} is rejected because of transaction {}: {reason}",
+                rejected.hash()
+            );
+            let mut storage = self.pool.connection_tagged("state_keeper").await?;
+            storage
+                .private_txs_dal()
+                .reject_bundle(bundle_id, &format!("rejected: {reason}"))
+                .await?;
+            return Ok(());
+        }
+
         // Reset the nonces in the mempool, but don't insert the transaction back.
         self.mempool.rollback(rejected);
 
@@ -436,6 +525,7 @@ impl MempoolIO {
             pool,
             timeout_sealer: TimeoutSealer::new(config),
             l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer::new(config),
+            l2_block_count_sealer: L2BlockCountSealer::new(config),
             filter: L2TxFilter::default(),
             // ^ Will be initialized properly on the first newly opened batch
             l1_batch_params_provider,
@@ -445,8 +535,129 @@ impl MempoolIO {
             delay_interval,
             batch_fee_input_provider,
             chain_id,
+            tx_allowlist: None,
+            pending_bundles: VecDeque::new(),
+            active_bundle: None,
+            included_bundle_ids: HashSet::new(),
         })
     }
+
+    fn is_executing_bundle(&self) -> bool {
+        self.active_bundle
+            .as_ref()
+            .is_some_and(|bundle| !bundle.is_finished())
+    }
+
+    /// Removes the active bundle if it contains the specified transaction.
+    fn take_active_bundle(&mut self, tx_hash: H256) -> Option<ActiveBundle> {
+        if self.active_bundle.as_ref()?.contains(tx_hash) {
+            self.active_bundle.take()
+        } else {
+            None
+        }
+    }
+
+    /// Clears the active bundle once all its transactions are returned to the state keeper. Since the state keeper
+    /// calls `rollback()` / `reject()` for a failed bundle before requesting the next transaction or sealing
+    /// the L2 block, a finished bundle remaining active at this point is included into the current L2 block.
+    fn finish_active_bundle(&mut self) {
+        if let Some(active_bundle) = self.active_bundle.take() {
+            if active_bundle.is_finished() {
+                self.included_bundle_ids.insert(active_bundle.bundle.id);
+            } else {
+                self.active_bundle = Some(active_bundle);
+            }
+        }
+    }
+
+    /// Loads bundles that can be included into the L2 block with the specified number, dropping expired bundles.
+    async fn load_pending_bundles(&mut self, l2_block_number: L2BlockNumber) -> anyhow::Result<()> {
+        self.finish_active_bundle();
+        anyhow::ensure!(
+            self.active_bundle.is_none(),
+            "L2 block is started in the middle of a transaction bundle"
+        );
+
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        let dropped_count = storage
+            .private_txs_dal()
+            .drop_expired_bundles(
+                l2_block_number,
+                "dropped: bundle target L2 block has passed",
+            )
+            .await?;
+        if dropped_count > 0 {
+            tracing::info!(
+                "Dropped {dropped_count} transactions in expired bundles before L2 block #{l2_block_number}"
+            );
+        }
+        let bundles = storage
+            .private_txs_dal()
+            .get_pending_bundles(l2_block_number)
+            .await?;
+        drop(storage);
+
+        // Included bundles may be returned from storage until the corresponding L2 blocks are persisted.
+        // Once a bundle is not returned, it's safe to forget about it.
+        self.included_bundle_ids
+            .retain(|id| bundles.iter().any(|bundle| bundle.id == *id));
+        self.pending_bundles = bundles
+            .into_iter()
+            .filter(|bundle| !self.included_bundle_ids.contains(&bundle.id))
+            .collect();
+        Ok(())
+    }
+
+    /// Returns the next bundled transaction, starting a new bundle if necessary.
+    async fn next_bundle_tx(&mut self) -> anyhow::Result<Option<Transaction>> {
+        self.finish_active_bundle();
+        if self.active_bundle.is_none() {
+            let Some(bundle) = self.pending_bundles.pop_front() else {
+                return Ok(None);
+            };
+            if let Some(reason) = self.check_bundle(&bundle) {
+                // No bundled transactions were executed yet, so it's safe to reject the bundle right away.
+                KEEPER_METRICS.inc_rejected_txs(reason.as_metric_label());
+                tracing::warn!("Bundle #{} is rejected: {reason}", bundle.id);
+                let mut storage = self.pool.connection_tagged("state_keeper").await?;
+                storage
+                    .private_txs_dal()
+                    .reject_bundle(bundle.id, &format!("rejected: {reason}"))
+                    .await?;
+                return Ok(None);
+            }
+            self.active_bundle = Some(ActiveBundle {
+                bundle,
+                served_count: 0,
+            });
+        }
+
+        let active_bundle = self.active_bundle.as_mut().unwrap();
+        let tx = active_bundle.bundle.transactions[active_bundle.served_count].clone();
+        active_bundle.served_count += 1;
+        Ok(Some(tx))
+    }
+
+    /// Performs the same checks for bundled transactions as for transactions from the mempool.
+    fn check_bundle(&self, bundle: &TxBundle) -> Option<UnexecutableReason> {
+        for tx in &bundle.transactions {
+            if tx.gas_limit() > self.max_allowed_tx_gas_limit {
+                return Some(UnexecutableReason::Halt(Halt::TooBigGasLimit));
+            }
+            if let Some(tx_allowlist) = &self.tx_allowlist {
+                if tx_allowlist.get().check_tx(tx).is_err() {
+                    return Some(UnexecutableReason::NotAllowlisted);
+                }
+            }
+        }
+        None
+    }
+
+    /// Enables allowlist mode: only L2 transactions permitted by the allowlist will be executed.
+    pub fn with_tx_allowlist(mut self, tx_allowlist: TxAllowlistCache) -> Self {
+        self.tx_allowlist = Some(tx_allowlist);
+        self
+    }
 }
 
 /// Getters required for testing the MempoolIO.
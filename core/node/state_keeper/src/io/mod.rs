@@ -97,6 +97,26 @@ impl L1BatchParams {
     }
 }
 
+/// Position of a transaction in a bundle, i.e. a sequence of transactions that must be included
+/// atomically and in order into a single L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleTxPosition {
+    /// Zero-based index of the transaction in the bundle.
+    pub index: usize,
+    /// Total number of transactions in the bundle.
+    pub len: usize,
+}
+
+impl BundleTxPosition {
+    pub fn is_first(&self) -> bool {
+        self.index == 0
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.index + 1 == self.len
+    }
+}
+
 /// Provides the interactive layer for the state keeper:
 /// it's used to receive volatile parameters (such as batch parameters) and sequence transactions
 /// providing L2 block and L1 batch boundaries for them.
@@ -133,6 +153,13 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
     /// Returns `None` if no transaction became available until the timeout.
     async fn wait_for_next_tx(&mut self, max_wait: Duration)
         -> anyhow::Result<Option<Transaction>>;
+    /// Returns the position of the transaction last returned by [`Self::wait_for_next_tx()`] in a bundle,
+    /// or `None` if the transaction is not bundled. If any transaction in a bundle cannot be included,
+    /// the state keeper rolls back the entire bundle and calls [`Self::rollback()`] or [`Self::reject()`]
+    /// for the failed transaction only.
+    fn bundle_position(&self) -> Option<BundleTxPosition> {
+        None
+    }
     /// Marks the transaction as "not executed", so it can be retrieved from the IO again.
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()>;
     /// Marks the transaction as "rejected", e.g. one that is not correct and can't be executed.
@@ -5,7 +5,7 @@ use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
 use zksync_multivm::utils::derive_base_fee_and_gas_per_pubdata;
-use zksync_node_test_utils::prepare_recovery_snapshot;
+use zksync_node_test_utils::{create_l2_transaction, prepare_recovery_snapshot};
 use zksync_types::{
     block::{BlockGasCount, L2BlockHasher},
     commitment::L1BatchCommitmentMode,
@@ -19,8 +19,9 @@ use zksync_utils::time::seconds_since_epoch;
 
 use self::tester::Tester;
 use crate::{
-    io::{seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, StateKeeperIO},
+    io::{seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, BundleTxPosition, StateKeeperIO},
     mempool_actor::l2_tx_filter,
+    seal_criteria::UnexecutableReason,
     testonly::BASE_SYSTEM_CONTRACTS,
     tests::{create_execution_result, create_transaction, Query},
     updates::{L2BlockSealCommand, L2BlockUpdates, UpdatesManager},
@@ -551,3 +552,79 @@ async fn different_timestamp_for_l2_blocks_in_same_batch(commitment_mode: L1Batc
         .expect("no new L2 block params");
     assert!(l2_block_params.timestamp > current_timestamp);
 }
+
+#[tokio::test]
+async fn serving_tx_bundles() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+
+    let txs: Vec<_> = (0..2).map(|_| create_l2_transaction(10, 100)).collect();
+    let mut storage = connection_pool.connection().await.unwrap();
+    for tx in &txs {
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+            .await
+            .unwrap();
+    }
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.hash()).collect();
+    storage
+        .private_txs_dal()
+        .insert_bundle(&tx_hashes, L2BlockNumber(100))
+        .await
+        .unwrap();
+    drop(storage);
+
+    let (mut mempool, _) = tester.create_test_mempool_io(connection_pool.clone()).await;
+    let (io_cursor, _) = mempool.initialize().await.unwrap();
+    mempool
+        .wait_for_new_l2_block_params(&io_cursor, Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("no new L2 block params");
+
+    for (i, tx_hash) in tx_hashes.iter().enumerate() {
+        let tx = mempool
+            .wait_for_next_tx(Duration::from_secs(1))
+            .await
+            .unwrap()
+            .expect("no bundled tx");
+        assert_eq!(tx.hash(), *tx_hash);
+        assert_eq!(
+            mempool.bundle_position(),
+            Some(BundleTxPosition { index: i, len: 2 })
+        );
+    }
+
+    // Rolling back a bundled transaction should re-queue the entire bundle.
+    let tx = txs[1].clone().into();
+    mempool.rollback(tx).await.unwrap();
+    let tx = mempool
+        .wait_for_next_tx(Duration::from_secs(1))
+        .await
+        .unwrap()
+        .expect("no bundled tx");
+    assert_eq!(tx.hash(), tx_hashes[0]);
+    assert!(mempool.bundle_position().unwrap().is_first());
+
+    // Rejecting a bundled transaction should reject the entire bundle.
+    mempool
+        .reject(&tx, UnexecutableReason::BundleTxReverted)
+        .await
+        .unwrap();
+    let next_tx = mempool
+        .wait_for_next_tx(Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert!(next_tx.is_none());
+    assert_eq!(mempool.bundle_position(), None);
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    let bundles = storage
+        .private_txs_dal()
+        .get_pending_bundles(io_cursor.next_l2_block)
+        .await
+        .unwrap();
+    assert!(bundles.is_empty());
+}
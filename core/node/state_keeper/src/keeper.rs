@@ -6,21 +6,25 @@ use std::{
 
 use anyhow::Context as _;
 use tokio::sync::watch;
-use zksync_multivm::interface::{Halt, L1BatchEnv, SystemEnv};
+use zksync_multivm::interface::{Halt, L1BatchEnv, SystemEnv, VmExecutionResultAndLogs};
 use zksync_state::ReadStorageFactory;
 use zksync_types::{
     block::L2BlockExecutionData, l2::TransactionType, protocol_upgrade::ProtocolUpgradeTx,
     protocol_version::ProtocolVersionId, storage_writes_deduplicator::StorageWritesDeduplicator,
-    utils::display_timestamp, L1BatchNumber, Transaction,
+    utils::display_timestamp, ExecuteTransactionCommon, L1BatchNumber, Transaction,
 };
 
 use super::{
+    allowlist::TxAllowlistCache,
     batch_executor::{BatchExecutor, BatchExecutorHandle, TxExecutionResult},
-    io::{IoCursor, L1BatchParams, L2BlockParams, OutputHandler, PendingBatchData, StateKeeperIO},
+    io::{
+        BundleTxPosition, IoCursor, L1BatchParams, L2BlockParams, OutputHandler, PendingBatchData,
+        StateKeeperIO,
+    },
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
     seal_criteria::{ConditionalSealer, SealData, SealResolution},
     types::ExecutionMetricsForCriteria,
-    updates::UpdatesManager,
+    updates::{UpdatesCheckpoint, UpdatesManager},
     utils::gas_count_from_writes,
 };
 use crate::seal_criteria::UnexecutableReason;
@@ -47,6 +51,59 @@ impl Error {
     }
 }
 
+/// State saved before executing the first transaction in a bundle.
+#[derive(Debug)]
+struct BundleStart {
+    checkpoint: UpdatesCheckpoint,
+    /// Whether the bundle is executed at the very start of the L1 batch.
+    is_first_in_batch: bool,
+}
+
+/// Action taken if a bundled transaction cannot be included.
+#[derive(Debug)]
+enum BundleFailure {
+    /// The bundle may be included into a later L1 batch.
+    Retry,
+    /// The bundle cannot be included.
+    Reject(UnexecutableReason),
+}
+
+impl BundleFailure {
+    /// Checks whether the entire bundle must be rolled back after executing a transaction at `position`.
+    fn new(
+        bundle_start: &BundleStart,
+        position: BundleTxPosition,
+        seal_resolution: &SealResolution,
+        exec_result: &TxExecutionResult,
+    ) -> Option<Self> {
+        // If a bundle doesn't fit into an empty L1 batch, it never will.
+        let retry_or_reject = || {
+            if bundle_start.is_first_in_batch {
+                Self::Reject(UnexecutableReason::BundleTooLarge)
+            } else {
+                Self::Retry
+            }
+        };
+
+        match seal_resolution {
+            SealResolution::Unexecutable(reason) => Some(Self::Reject(reason.clone())),
+            SealResolution::ExcludeAndSeal => Some(retry_or_reject()),
+            SealResolution::IncludeAndSeal if !position.is_last() => Some(retry_or_reject()),
+            SealResolution::NoSeal | SealResolution::IncludeAndSeal => {
+                let TxExecutionResult::Success { tx_result, .. } = exec_result else {
+                    unreachable!(
+                        "Tx inclusion seal resolution must be a result of a successful tx execution",
+                    );
+                };
+                tx_result
+                    .result
+                    .is_failed()
+                    .then_some(Self::Reject(UnexecutableReason::BundleTxReverted))
+            }
+        }
+    }
+}
+
 /// State keeper represents a logic layer of L1 batch / L2 block processing flow.
 /// It's responsible for taking all the data from the `StateKeeperIO`, feeding it into `BatchExecutor` objects
 /// and calling `SealManager` to decide whether an L2 block or L1 batch should be sealed.
@@ -64,6 +121,7 @@ pub struct ZkSyncStateKeeper {
     batch_executor_base: Box<dyn BatchExecutor>,
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
+    tx_allowlist: Option<TxAllowlistCache>,
 }
 
 impl ZkSyncStateKeeper {
@@ -82,9 +140,17 @@ impl ZkSyncStateKeeper {
             output_handler,
             sealer,
             storage_factory,
+            tx_allowlist: None,
         }
     }
 
+    /// Enables allowlist mode: L2 transactions deploying contracts via addresses without the deployer permission
+    /// (e.g., via factory contracts) are rejected after execution.
+    pub fn with_tx_allowlist(mut self, tx_allowlist: TxAllowlistCache) -> Self {
+        self.tx_allowlist = Some(tx_allowlist);
+        self
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         match self.run_inner().await {
             Ok(_) => unreachable!(),
@@ -492,6 +558,7 @@ impl ZkSyncStateKeeper {
                 .await?;
         }
 
+        let mut bundle_start = None;
         while !self.is_canceled() {
             let full_latency = KEEPER_METRICS.process_l1_batch_loop_iteration.start();
 
@@ -541,11 +608,58 @@ impl ZkSyncStateKeeper {
             };
             waiting_latency.observe();
 
+            let bundle_position = self.io.bundle_position();
+            if bundle_position.is_some_and(|position| position.is_first()) {
+                batch_executor
+                    .start_bundle()
+                    .await
+                    .context("failed starting bundle in batch executor")?;
+                bundle_start = Some(BundleStart {
+                    checkpoint: updates_manager.checkpoint(),
+                    is_first_in_batch: updates_manager.pending_executed_transactions_len() == 0,
+                });
+            }
+
             let tx_hash = tx.hash();
             let (seal_resolution, exec_result) = self
                 .process_one_tx(batch_executor, updates_manager, tx.clone())
                 .await?;
 
+            if let Some(position) = bundle_position {
+                let start = bundle_start
+                    .as_ref()
+                    .context("bundled transaction is executed before the bundle start")?;
+                if let Some(failure) =
+                    BundleFailure::new(start, position, &seal_resolution, &exec_result)
+                {
+                    let start = bundle_start.take().unwrap();
+                    let should_seal = seal_resolution.should_seal() && !start.is_first_in_batch;
+                    self.rollback_bundle(
+                        batch_executor,
+                        updates_manager,
+                        start,
+                        position,
+                        tx,
+                        failure,
+                    )
+                    .await?;
+
+                    full_latency.observe();
+                    if should_seal {
+                        tracing::debug!(
+                            "L1 batch #{} should be sealed with resolution {seal_resolution:?} after rolling back \
+                             bundle with transaction {tx_hash}",
+                            updates_manager.l1_batch.number
+                        );
+                        return Ok(());
+                    }
+                    continue;
+                }
+                if position.is_last() {
+                    bundle_start = None;
+                }
+            }
+
             let latency = KEEPER_METRICS.match_seal_resolution.start();
             match &seal_resolution {
                 SealResolution::NoSeal | SealResolution::IncludeAndSeal => {
@@ -667,6 +781,40 @@ impl ZkSyncStateKeeper {
         }
     }
 
+    /// Rolls back all executed transactions in a bundle, the last of which is `tx`.
+    async fn rollback_bundle(
+        &mut self,
+        batch_executor: &mut BatchExecutorHandle,
+        updates_manager: &mut UpdatesManager,
+        bundle_start: BundleStart,
+        position: BundleTxPosition,
+        tx: Transaction,
+        failure: BundleFailure,
+    ) -> anyhow::Result<()> {
+        let tx_hash = tx.hash();
+        tracing::info!(
+            "Rolling back {} transaction(s) in bundle with transaction {tx_hash:?}: {failure:?}",
+            position.index + 1
+        );
+        batch_executor.rollback_bundle().await.with_context(|| {
+            format!("failed rolling back bundle with transaction {tx_hash:?} in batch executor")
+        })?;
+        updates_manager.restore(bundle_start.checkpoint);
+
+        match failure {
+            BundleFailure::Retry => self
+                .io
+                .rollback(tx)
+                .await
+                .with_context(|| format!("failed rolling back transaction {tx_hash:?} in I/O")),
+            BundleFailure::Reject(reason) => self
+                .io
+                .reject(&tx, reason)
+                .await
+                .with_context(|| format!("cannot reject transaction {tx_hash:?}")),
+        }
+    }
+
     /// Executes one transaction in the batch executor, and then decides whether the batch should be sealed.
     /// Batch may be sealed because of one of the following reasons:
     /// 1. The VM entered an incorrect state (e.g. out of gas). In that case, we must revert the transaction and seal
@@ -727,6 +875,11 @@ impl ZkSyncStateKeeper {
             TxExecutionResult::RejectedByVm { reason } => {
                 UnexecutableReason::Halt(reason.clone()).into()
             }
+            TxExecutionResult::Success { tx_result, .. }
+                if !self.are_deployments_allowed(&tx, tx_result) =>
+            {
+                UnexecutableReason::NotAllowlisted.into()
+            }
             TxExecutionResult::Success {
                 tx_result,
                 tx_metrics,
@@ -781,6 +934,7 @@ impl ZkSyncStateKeeper {
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    l2_block_count: 1,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -792,6 +946,7 @@ impl ZkSyncStateKeeper {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    l2_block_count: updates_manager.pending_l2_blocks_count(),
                 };
 
                 self.sealer.should_seal_l1_batch(
@@ -807,4 +962,29 @@ impl ZkSyncStateKeeper {
         latency.observe();
         Ok((resolution, exec_result))
     }
+
+    /// Checks contracts deployed by an L2 transaction against the allowlist (if any). Deployments
+    /// aren't visible before execution if they are performed by a factory contract or a smart account.
+    fn are_deployments_allowed(
+        &self,
+        tx: &Transaction,
+        tx_result: &VmExecutionResultAndLogs,
+    ) -> bool {
+        let Some(tx_allowlist) = &self.tx_allowlist else {
+            return true;
+        };
+        if !matches!(tx.common_data, ExecuteTransactionCommon::L2(_)) {
+            return true;
+        }
+        match tx_allowlist.get().check_deployments(&tx_result.logs.events) {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::info!(
+                    "Transaction {:?} is not permitted by allowlist: {err}",
+                    tx.hash()
+                );
+                false
+            }
+        }
+    }
 }
@@ -25,6 +25,7 @@ pub use self::{
     updates::UpdatesManager,
 };
 
+pub mod allowlist;
 mod batch_executor;
 pub mod io;
 mod keeper;
@@ -83,6 +83,14 @@ impl MempoolFetcher {
             let latency = KEEPER_METRICS.mempool_sync.start();
             let mut storage = self.pool.connection_tagged("state_keeper").await?;
             let mempool_info = self.mempool.get_mempool_info();
+            if !mempool_info.evicted_transactions.is_empty() {
+                let removed_txs = storage
+                    .transactions_dal()
+                    .remove_evicted_txs(&mempool_info.evicted_transactions)
+                    .await
+                    .context("failed removing evicted transactions")?;
+                tracing::debug!("Number of evicted txs removed from storage: {removed_txs}");
+            }
             let protocol_version = storage
                 .blocks_dal()
                 .pending_protocol_version()
@@ -176,6 +184,9 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        max_pending_txs_per_account: None,
+        nonce_gap_ttl_sec: None,
+        evict_low_fee_txs: false,
     };
 
     #[tokio::test]
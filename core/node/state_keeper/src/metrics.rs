@@ -6,8 +6,8 @@ use std::{
 };
 
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LatencyObserver,
-    Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    LatencyObserver, Metrics,
 };
 use zksync_mempool::MempoolStore;
 use zksync_multivm::interface::{VmExecutionResultAndLogs, VmRevertReason};
@@ -104,6 +104,10 @@ pub struct StateKeeperMetrics {
     /// The time it takes to wait for new L2 block parameters
     #[metrics(buckets = Buckets::LATENCIES)]
     pub wait_for_l2_block_params: Histogram<Duration>,
+    /// Number of L2 transactions evicted from the mempool, grouped by the eviction reason.
+    /// Updated from the mempool stats on each metrics scrape.
+    #[metrics(labels = ["reason"])]
+    mempool_evicted_txs: LabeledFamily<&'static str, Counter>,
 }
 
 fn vm_revert_reason_as_metric_label(reason: &VmRevertReason) -> &'static str {
@@ -164,6 +168,8 @@ impl StateKeeperGauges {
         #[vise::register]
         static COLLECTOR: vise::Collector<Option<StateKeeperGauges>> = vise::Collector::new();
 
+        // Mempool stats contain total eviction counts; counters are incremented by deltas since the last scrape.
+        let reported_evictions = Mutex::new([0_u64; 3]);
         let res = COLLECTOR.before_scrape(move || {
             pool_ref.upgrade().map(|pool| {
                 let stats = pool.lock().expect("failed to acquire mempool lock").stats();
@@ -175,6 +181,22 @@ impl StateKeeperGauges {
                 gauges
                     .l2_priority_queue_size
                     .set(stats.l2_priority_queue_size);
+
+                let evictions = [
+                    ("account_limit", stats.account_limit_evictions),
+                    ("nonce_gap", stats.nonce_gap_evictions),
+                    ("low_fee", stats.low_fee_evictions),
+                ];
+                let mut reported_evictions = reported_evictions
+                    .lock()
+                    .expect("failed to acquire evictions lock");
+                for ((reason, total), reported) in
+                    evictions.into_iter().zip(&mut *reported_evictions)
+                {
+                    KEEPER_METRICS.mempool_evicted_txs[&reason]
+                        .inc_by(total.saturating_sub(*reported));
+                    *reported = total;
+                }
                 gauges
             })
         });
@@ -443,6 +465,8 @@ pub(super) enum ExecutorCommand {
     #[metrics(name = "start_next_miniblock")]
     StartNextL2Block,
     RollbackLastTx,
+    StartBundle,
+    RollbackBundle,
     FinishBatch,
     FinishBatchWithCache,
 }
@@ -121,6 +121,15 @@ impl SequencerSealer {
         Self { config, sealers }
     }
 
+    /// Registers additional seal criteria. They are checked after the built-in ones.
+    pub fn with_custom_criteria(
+        mut self,
+        criteria: impl IntoIterator<Item = Box<dyn SealCriterion>>,
+    ) -> Self {
+        self.sealers.extend(criteria);
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn with_sealers(
         config: StateKeeperConfig,
@@ -130,7 +139,7 @@ impl SequencerSealer {
     }
 
     fn default_sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
-        vec![
+        let mut sealers: Vec<Box<dyn SealCriterion>> = vec![
             Box::new(criteria::SlotsCriterion),
             Box::new(criteria::GasCriterion),
             Box::new(criteria::PubDataBytesCriterion {
@@ -139,7 +148,13 @@ impl SequencerSealer {
             Box::new(criteria::CircuitsCriterion),
             Box::new(criteria::TxEncodingSizeCriterion),
             Box::new(criteria::GasForBatchTipCriterion),
-        ]
+        ];
+        if let Some(max_payload_bytes) = config.max_anchoring_payload_bytes {
+            sealers.push(Box::new(criteria::AnchoringPayloadSizeCriterion {
+                max_payload_bytes,
+            }));
+        }
+        sealers
     }
 }
 
@@ -0,0 +1,121 @@
+use zksync_types::ProtocolVersionId;
+
+use crate::seal_criteria::{
+    SealCriterion, SealData, SealResolution, StateKeeperConfig, UnexecutableReason,
+};
+
+/// Bounds the size of the payload anchored to IPFS / Mintlayer for an L1 batch. The anchored payload
+/// is dominated by the batch pubdata, so the payload size is estimated as the pubdata size of the batch.
+#[derive(Debug)]
+pub struct AnchoringPayloadSizeCriterion {
+    pub max_payload_bytes: u64,
+}
+
+impl SealCriterion for AnchoringPayloadSizeCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let max_payload_bytes = self.max_payload_bytes as usize;
+        let reject_bound =
+            (max_payload_bytes as f64 * config.reject_tx_at_eth_params_percentage).round();
+        let include_and_seal_bound =
+            (max_payload_bytes as f64 * config.close_block_at_eth_params_percentage).round();
+
+        let block_size =
+            block_data.execution_metrics.size() + block_data.writes_metrics.size(protocol_version);
+        // Mirrors pubdata size estimation in `PubDataBytesCriterion`.
+        let tx_size = if tx_data.execution_metrics.pubdata_published == 0 {
+            tx_data.execution_metrics.size() + tx_data.writes_metrics.size(protocol_version)
+        } else {
+            tx_data.execution_metrics.pubdata_published as usize
+        };
+        if tx_size > reject_bound as usize {
+            UnexecutableReason::AnchoringPayloadLimit.into()
+        } else if block_size > max_payload_bytes {
+            SealResolution::ExcludeAndSeal
+        } else if block_size > include_and_seal_bound as usize {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "anchoring_payload_size"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::tx::ExecutionMetrics;
+
+    use super::*;
+
+    fn seal_data(pubdata_size: usize) -> SealData {
+        SealData {
+            execution_metrics: ExecutionMetrics {
+                l2_l1_long_messages: pubdata_size,
+                ..ExecutionMetrics::default()
+            },
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn seal_criterion() {
+        let config = StateKeeperConfig {
+            reject_tx_at_eth_params_percentage: 0.9,
+            close_block_at_eth_params_percentage: 0.5,
+            ..Default::default()
+        };
+        let criterion = AnchoringPayloadSizeCriterion {
+            max_payload_bytes: 1_000,
+        };
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            0,
+            &seal_data(100),
+            &seal_data(100),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            0,
+            &seal_data(600),
+            &seal_data(100),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            0,
+            &seal_data(1_001),
+            &seal_data(100),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            0,
+            &seal_data(1_001),
+            &seal_data(901),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, UnexecutableReason::AnchoringPayloadLimit.into());
+    }
+}
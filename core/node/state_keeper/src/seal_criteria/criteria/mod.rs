@@ -1,3 +1,4 @@
+mod anchoring_payload;
 mod gas;
 mod gas_for_batch_tip;
 mod geometry_seal_criteria;
@@ -5,6 +6,7 @@ mod pubdata_bytes;
 mod slots;
 mod tx_encoding_size;
 
+pub use self::anchoring_payload::AnchoringPayloadSizeCriterion;
 pub(crate) use self::{
     gas::GasCriterion, gas_for_batch_tip::GasForBatchTipCriterion,
     geometry_seal_criteria::CircuitsCriterion, pubdata_bytes::PubDataBytesCriterion,
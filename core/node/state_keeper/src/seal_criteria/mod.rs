@@ -26,7 +26,10 @@ use zksync_utils::time::millis_since;
 mod conditional_sealer;
 pub(super) mod criteria;
 
-pub use self::conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer};
+pub use self::{
+    conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer},
+    criteria::AnchoringPayloadSizeCriterion,
+};
 use super::{
     metrics::AGGREGATION_METRICS,
     updates::UpdatesManager,
@@ -69,6 +72,10 @@ pub enum UnexecutableReason {
     OutOfGasForBatchTip,
     BootloaderOutOfGas,
     NotEnoughGasProvided,
+    AnchoringPayloadLimit,
+    NotAllowlisted,
+    BundleTxReverted,
+    BundleTooLarge,
 }
 
 impl UnexecutableReason {
@@ -83,6 +90,10 @@ impl UnexecutableReason {
             UnexecutableReason::OutOfGasForBatchTip => "OutOfGasForBatchTip",
             UnexecutableReason::BootloaderOutOfGas => "BootloaderOutOfGas",
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
+            UnexecutableReason::AnchoringPayloadLimit => "AnchoringPayloadLimit",
+            UnexecutableReason::NotAllowlisted => "NotAllowlisted",
+            UnexecutableReason::BundleTxReverted => "BundleTxReverted",
+            UnexecutableReason::BundleTooLarge => "BundleTooLarge",
         }
     }
 }
@@ -107,6 +118,14 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::OutOfGasForBatchTip => write!(f, "Out of gas for batch tip"),
             UnexecutableReason::BootloaderOutOfGas => write!(f, "Bootloader out of gas"),
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
+            UnexecutableReason::AnchoringPayloadLimit => {
+                write!(f, "Anchoring payload size limit reached")
+            }
+            UnexecutableReason::NotAllowlisted => {
+                write!(f, "Transaction is not permitted by the allowlist")
+            }
+            UnexecutableReason::BundleTxReverted => write!(f, "Bundled transaction reverted"),
+            UnexecutableReason::BundleTooLarge => write!(f, "Bundle doesn't fit into an L1 batch"),
         }
     }
 }
@@ -164,6 +183,7 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    pub(super) l2_block_count: usize,
 }
 
 impl SealData {
@@ -184,11 +204,47 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics,
             gas_remaining: tx_metrics.gas_remaining,
+            l2_block_count: 1,
         }
     }
+
+    /// Execution metrics accumulated by the transaction or block.
+    pub fn execution_metrics(&self) -> &ExecutionMetrics {
+        &self.execution_metrics
+    }
+
+    /// L1 gas required to submit the transaction or block.
+    pub fn gas_count(&self) -> &BlockGasCount {
+        &self.gas_count
+    }
+
+    /// Total bootloader encoding size of transactions.
+    pub fn cumulative_size(&self) -> usize {
+        self.cumulative_size
+    }
+
+    /// Deduplicated storage writes metrics.
+    pub fn writes_metrics(&self) -> &DeduplicatedWritesMetrics {
+        &self.writes_metrics
+    }
+
+    /// Gas remaining after executing the transaction.
+    pub fn gas_remaining(&self) -> u32 {
+        self.gas_remaining
+    }
+
+    /// Number of L2 blocks spanned by the data, including the currently open L2 block.
+    pub fn l2_block_count(&self) -> usize {
+        self.l2_block_count
+    }
 }
 
-pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
+/// Deterministic criterion used by [`SequencerSealer`] to decide whether an L1 batch should be sealed
+/// after executing a transaction. Besides the built-in criteria, custom implementations can be registered
+/// using [`SequencerSealer::with_custom_criteria()`].
+pub trait SealCriterion: fmt::Debug + Send + Sync + 'static {
+    /// Returns the seal resolution for a batch with the specified `block_data` after executing
+    /// a transaction with `tx_data`.
     fn should_seal(
         &self,
         config: &StateKeeperConfig,
@@ -199,6 +255,7 @@ pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
         protocol_version: ProtocolVersionId,
     ) -> SealResolution;
 
+    /// Returns the name of the criterion used in metrics and logs.
     // We need self here only for rust restrictions for creating an object from trait
     // https://doc.rust-lang.org/reference/items/traits.html#object-safety
     fn prom_criterion_name(&self) -> &'static str;
@@ -276,12 +333,53 @@ impl L2BlockMaxPayloadSizeSealer {
     }
 }
 
+/// Seals an L1 batch once it contains the maximum allowed number of sealed L2 blocks. The sealer is checked
+/// at L2 block boundaries before a new L2 block gets filled, so that the last allowed L2 block can be filled
+/// completely, and L2 blocks sealed without executing transactions afterwards (e.g., by timeout) cannot make
+/// the batch exceed the limit.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct L2BlockCountSealer {
+    max_l2_blocks_per_batch: Option<u32>,
+}
+
+impl L2BlockCountSealer {
+    pub fn new(config: &StateKeeperConfig) -> Self {
+        Self {
+            max_l2_blocks_per_batch: config.max_l2_blocks_per_batch,
+        }
+    }
+
+    pub fn should_seal_l1_batch_unconditionally(&self, manager: &UpdatesManager) -> bool {
+        const RULE_NAME: &str = "l2_block_count";
+
+        let Some(max_l2_blocks_per_batch) = self.max_l2_blocks_per_batch else {
+            return false;
+        };
+        if manager.pending_executed_transactions_len() == 0 {
+            return false;
+        }
+        let should_seal = manager.l1_batch.l2_blocks_count >= max_l2_blocks_per_batch as usize;
+        if should_seal {
+            AGGREGATION_METRICS.l1_batch_reason_inc_criterion(RULE_NAME);
+            tracing::debug!(
+                "Decided to seal L1 batch using rule `{RULE_NAME}`; L1 batch contains {} L2 blocks, \
+                 max allowed: {max_l2_blocks_per_batch}",
+                manager.l1_batch.l2_blocks_count
+            );
+        }
+        should_seal
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_utils::time::seconds_since_epoch;
 
     use super::*;
-    use crate::tests::{create_execution_result, create_transaction, create_updates_manager};
+    use crate::{
+        io::L2BlockParams,
+        tests::{create_execution_result, create_transaction, create_updates_manager},
+    };
 
     fn apply_tx_to_manager(tx: Transaction, manager: &mut UpdatesManager) {
         manager.extend_from_executed_transaction(
@@ -349,4 +447,34 @@ mod tests {
             "L2 block with payload encoding size equal or greater than max payload size should be sealed"
         );
     }
+
+    #[test]
+    fn l2_block_count_sealer() {
+        let sealer = L2BlockCountSealer {
+            max_l2_blocks_per_batch: Some(2),
+        };
+        let mut manager = create_updates_manager();
+        assert!(!sealer.should_seal_l1_batch_unconditionally(&manager));
+
+        for i in 0..2 {
+            apply_tx_to_manager(create_transaction(10, 100), &mut manager);
+            assert!(
+                !sealer.should_seal_l1_batch_unconditionally(&manager),
+                "L1 batch with {i} sealed L2 blocks shouldn't be sealed"
+            );
+            manager.push_l2_block(L2BlockParams {
+                timestamp: manager.l2_block.timestamp + 1,
+                virtual_blocks: 1,
+            });
+        }
+        assert!(
+            sealer.should_seal_l1_batch_unconditionally(&manager),
+            "L1 batch with max number of sealed L2 blocks should be sealed"
+        );
+
+        let sealer = L2BlockCountSealer {
+            max_l2_blocks_per_batch: None,
+        };
+        assert!(!sealer.should_seal_l1_batch_unconditionally(&manager));
+    }
 }
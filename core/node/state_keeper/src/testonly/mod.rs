@@ -104,6 +104,8 @@ impl BatchExecutor for MockBatchExecutor {
                     Command::ExecuteTx(_, resp) => resp.send(successful_exec()).unwrap(),
                     Command::StartNextL2Block(_, resp) => resp.send(()).unwrap(),
                     Command::RollbackLastTx(_) => panic!("unexpected rollback"),
+                    Command::StartBundle(resp) => resp.send(()).unwrap(),
+                    Command::RollbackBundle(_) => panic!("unexpected bundle rollback"),
                     Command::FinishBatch(resp) => {
                         // Blanket result, it doesn't really matter.
                         resp.send(default_vm_batch_result()).unwrap();
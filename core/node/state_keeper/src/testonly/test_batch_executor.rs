@@ -22,13 +22,18 @@ use zksync_multivm::{
 use zksync_node_test_utils::create_l2_transaction;
 use zksync_state::{PgOrRocksdbStorage, ReadStorageFactory};
 use zksync_types::{
-    fee_model::BatchFeeInput, protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber,
-    L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256,
+    event::DEPLOY_EVENT_SIGNATURE, fee_model::BatchFeeInput, protocol_upgrade::ProtocolUpgradeTx,
+    Address, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, VmEvent,
+    CONTRACT_DEPLOYER_ADDRESS, H256,
 };
+use zksync_utils::address_to_h256;
 
 use crate::{
+    allowlist::TxAllowlistCache,
     batch_executor::{BatchExecutor, BatchExecutorHandle, Command, TxExecutionResult},
-    io::{IoCursor, L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO},
+    io::{
+        BundleTxPosition, IoCursor, L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO,
+    },
     seal_criteria::{IoSealCriteria, SequencerSealer, UnexecutableReason},
     testonly::{
         default_vm_batch_result, storage_view_cache, successful_exec, BASE_SYSTEM_CONTRACTS,
@@ -57,6 +62,7 @@ pub(crate) struct TestScenario {
     pending_batch: Option<PendingBatchData>,
     l1_batch_seal_fn: Box<SealFn>,
     l2_block_seal_fn: Box<SealFn>,
+    tx_allowlist: Option<TxAllowlistCache>,
 }
 
 type SealFn = dyn FnMut(&UpdatesManager) -> bool + Send + Sync;
@@ -78,9 +84,16 @@ impl TestScenario {
             pending_batch: None,
             l1_batch_seal_fn: Box::new(|_| false),
             l2_block_seal_fn: Box::new(|_| false),
+            tx_allowlist: None,
         }
     }
 
+    /// Enables allowlist mode for the state keeper.
+    pub(crate) fn with_tx_allowlist(mut self, tx_allowlist: TxAllowlistCache) -> Self {
+        self.tx_allowlist = Some(tx_allowlist);
+        self
+    }
+
     /// Adds a pending batch data that would be fed into the state keeper.
     /// Note that during processing pending batch, state keeper do *not* call `seal_l2_block` method on the IO (since
     /// it only recovers the temporary state).
@@ -117,6 +130,19 @@ impl TestScenario {
         self
     }
 
+    /// Same as [`Self::next_tx()`], but the transaction is returned as a part of a bundle at the specified position.
+    pub(crate) fn next_bundle_tx(
+        mut self,
+        description: &'static str,
+        tx: Transaction,
+        result: TxExecutionResult,
+        position: BundleTxPosition,
+    ) -> Self {
+        self.actions
+            .push_back(ScenarioItem::BundleTx(description, tx, result, position));
+        self
+    }
+
     /// Expect the state keeper to rollback the transaction (i.e. return to the mempool).
     pub(crate) fn tx_rollback(mut self, description: &'static str, tx: Transaction) -> Self {
         self.actions
@@ -195,13 +221,14 @@ impl TestScenario {
 
     /// Launches the test.
     /// Provided `SealManager` is expected to be externally configured to adhere the written scenario logic.
-    pub(crate) async fn run(self, sealer: SequencerSealer) {
+    pub(crate) async fn run(mut self, sealer: SequencerSealer) {
         assert!(!self.actions.is_empty(), "Test scenario can't be empty");
 
         let batch_executor_base = TestBatchExecutorBuilder::new(&self);
+        let tx_allowlist = self.tx_allowlist.take();
         let (stop_sender, stop_receiver) = watch::channel(false);
         let (io, output_handler) = TestIO::new(stop_sender, self);
-        let state_keeper = ZkSyncStateKeeper::new(
+        let mut state_keeper = ZkSyncStateKeeper::new(
             stop_receiver,
             Box::new(io),
             Box::new(batch_executor_base),
@@ -209,6 +236,9 @@ impl TestScenario {
             Arc::new(sealer),
             Arc::new(MockReadStorageFactory),
         );
+        if let Some(tx_allowlist) = tx_allowlist {
+            state_keeper = state_keeper.with_tx_allowlist(tx_allowlist);
+        }
         let sk_thread = tokio::spawn(state_keeper.run());
 
         // We must assume that *theoretically* state keeper may ignore the stop signal from IO once scenario is
@@ -270,6 +300,31 @@ pub(crate) fn successful_exec_with_metrics(
     }
 }
 
+/// Creates a `ContractDeployed` event emitted by the contract deployer when called by `deployer`.
+pub(crate) fn deploy_event(deployer: Address) -> VmEvent {
+    VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: CONTRACT_DEPLOYER_ADDRESS,
+        indexed_topics: vec![
+            *DEPLOY_EVENT_SIGNATURE,
+            address_to_h256(&deployer),
+            H256::repeat_byte(0xbc),
+            address_to_h256(&Address::repeat_byte(0xcc)),
+        ],
+        value: vec![],
+    }
+}
+
+/// Creates a `TxExecutionResult` object denoting a successful tx execution deploying a contract via `deployer`.
+pub(crate) fn successful_deploy_exec(deployer: Address) -> TxExecutionResult {
+    let mut exec_result = successful_exec();
+    let TxExecutionResult::Success { tx_result, .. } = &mut exec_result else {
+        unreachable!();
+    };
+    tx_result.logs.events.push(deploy_event(deployer));
+    exec_result
+}
+
 /// Creates a `TxExecutionResult` object denoting a tx that was rejected.
 pub(crate) fn rejected_exec() -> TxExecutionResult {
     TxExecutionResult::RejectedByVm {
@@ -284,6 +339,12 @@ enum ScenarioItem {
     /// Increments protocol version in IO state.
     IncrementProtocolVersion(&'static str),
     Tx(&'static str, Transaction, TxExecutionResult),
+    BundleTx(
+        &'static str,
+        Transaction,
+        TxExecutionResult,
+        BundleTxPosition,
+    ),
     Rollback(&'static str, Transaction),
     Reject(&'static str, Transaction, UnexecutableReason),
     L2BlockSeal(
@@ -313,6 +374,13 @@ impl fmt::Debug for ScenarioItem {
                 .field(tx)
                 .field(result)
                 .finish(),
+            Self::BundleTx(descr, tx, result, position) => formatter
+                .debug_tuple("BundleTx")
+                .field(descr)
+                .field(tx)
+                .field(result)
+                .field(position)
+                .finish(),
             Self::Rollback(descr, tx) => formatter
                 .debug_tuple("Rollback")
                 .field(descr)
@@ -343,6 +411,8 @@ pub struct TestBatchExecutorBuilder {
     txs: ExpectedTransactions,
     /// Set of transactions that would be rolled back at least once.
     rollback_set: HashSet<H256>,
+    /// Set of bundled transactions. These must be rolled back together with the entire bundle.
+    bundled_txs: HashSet<H256>,
 }
 
 impl TestBatchExecutorBuilder {
@@ -350,6 +420,7 @@ impl TestBatchExecutorBuilder {
         let mut txs = VecDeque::new();
         let mut batch_txs = HashMap::new();
         let mut rollback_set = HashSet::new();
+        let mut bundled_txs = HashSet::new();
 
         // Insert data about the pending batch, if it exists.
         // All the txs from the pending batch must succeed.
@@ -366,7 +437,10 @@ impl TestBatchExecutorBuilder {
         // Go through scenario and collect per-batch transactions and the overall rollback set.
         for item in &scenario.actions {
             match item {
-                ScenarioItem::Tx(_, tx, result) => {
+                ScenarioItem::Tx(_, tx, result) | ScenarioItem::BundleTx(_, tx, result, _) => {
+                    if matches!(item, ScenarioItem::BundleTx(..)) {
+                        bundled_txs.insert(tx.hash());
+                    }
                     batch_txs
                         .entry(tx.hash())
                         .and_modify(|txs: &mut VecDeque<TxExecutionResult>| {
@@ -397,7 +471,11 @@ impl TestBatchExecutorBuilder {
         // for the initialization of the "next-to-last" batch.
         txs.push_back(HashMap::default());
 
-        Self { txs, rollback_set }
+        Self {
+            txs,
+            rollback_set,
+            bundled_txs,
+        }
     }
 
     /// Adds successful transactions to be executed in a single L1 batch.
@@ -425,6 +503,7 @@ impl BatchExecutor for TestBatchExecutorBuilder {
             commands_receiver,
             self.txs.pop_front().unwrap(),
             self.rollback_set.clone(),
+            self.bundled_txs.clone(),
         );
         let handle = tokio::task::spawn_blocking(move || {
             executor.run();
@@ -442,8 +521,12 @@ pub(super) struct TestBatchExecutor {
     txs: HashMap<H256, VecDeque<TxExecutionResult>>,
     /// Set of transactions that are expected to be rolled back.
     rollback_set: HashSet<H256>,
+    /// Set of bundled transactions, which must not be rolled back individually.
+    bundled_txs: HashSet<H256>,
     /// Last executed tx hash.
     last_tx: H256,
+    /// Whether a bundle was started and can be rolled back.
+    bundle_started: bool,
 }
 
 impl TestBatchExecutor {
@@ -451,12 +534,15 @@ impl TestBatchExecutor {
         commands: mpsc::Receiver<Command>,
         txs: HashMap<H256, VecDeque<TxExecutionResult>>,
         rollback_set: HashSet<H256>,
+        bundled_txs: HashSet<H256>,
     ) -> Self {
         Self {
             commands,
             txs,
             rollback_set,
+            bundled_txs,
             last_tx: H256::default(), // We don't expect rollbacks until the first tx is executed.
+            bundle_started: false,
         }
     }
 
@@ -485,6 +571,11 @@ impl TestBatchExecutor {
                     // This is an additional safety check: IO would check that every rollback is included in the
                     // test scenario, but here we want to additionally check that each such request goes to the
                     // the batch executor as well.
+                    assert!(
+                        !self.bundled_txs.contains(&self.last_tx),
+                        "Received a request to rollback a bundled tx {:?} individually",
+                        self.last_tx
+                    );
                     if !self.rollback_set.contains(&self.last_tx) {
                         // Request to rollback an unexpected tx.
                         panic!(
@@ -496,6 +587,18 @@ impl TestBatchExecutor {
                     // It's OK to not update `last_executed_tx`, since state keeper never should rollback more than 1
                     // tx in a row, and it's going to cause a panic anyway.
                 }
+                Command::StartBundle(resp) => {
+                    self.bundle_started = true;
+                    resp.send(()).unwrap();
+                }
+                Command::RollbackBundle(resp) => {
+                    assert!(
+                        self.bundle_started,
+                        "Received a request to rollback a bundle that wasn't started"
+                    );
+                    self.bundle_started = false;
+                    resp.send(()).unwrap();
+                }
                 Command::FinishBatch(resp) => {
                     // Blanket result, it doesn't really matter.
                     resp.send(default_vm_batch_result()).unwrap();
@@ -571,6 +674,8 @@ pub(crate) struct TestIO {
     /// Internal flag that is being set if scenario was configured to return `None` to all the transaction
     /// requests until some other action happens.
     skipping_txs: bool,
+    /// Position of the last returned transaction in a bundle.
+    bundle_position: Option<BundleTxPosition>,
     protocol_version: ProtocolVersionId,
     previous_batch_protocol_version: ProtocolVersionId,
     protocol_upgrade_txs: HashMap<ProtocolVersionId, ProtocolUpgradeTx>,
@@ -618,6 +723,7 @@ impl TestIO {
             l2_block_number,
             fee_account: FEE_ACCOUNT,
             skipping_txs: false,
+            bundle_position: None,
             protocol_version: ProtocolVersionId::latest(),
             previous_batch_protocol_version: ProtocolVersionId::latest(),
             protocol_upgrade_txs: HashMap::default(),
@@ -747,12 +853,19 @@ impl StateKeeperIO for TestIO {
         }
 
         // We shouldn't, process normally.
-        let ScenarioItem::Tx(_, tx, _) = action else {
-            panic!("Unexpected action: {:?}", action);
+        let (tx, bundle_position) = match action {
+            ScenarioItem::Tx(_, tx, _) => (tx, None),
+            ScenarioItem::BundleTx(_, tx, _, position) => (tx, Some(position)),
+            _ => panic!("Unexpected action: {:?}", action),
         };
+        self.bundle_position = bundle_position;
         Ok(Some(tx))
     }
 
+    fn bundle_position(&self) -> Option<BundleTxPosition> {
+        self.bundle_position
+    }
+
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()> {
         let action = self.pop_next_item("rollback");
         let ScenarioItem::Rollback(_, expected_tx) = action else {
@@ -827,6 +940,8 @@ impl BatchExecutor for MockBatchExecutor {
                     Command::ExecuteTx(_, resp) => resp.send(successful_exec()).unwrap(),
                     Command::StartNextL2Block(_, resp) => resp.send(()).unwrap(),
                     Command::RollbackLastTx(_) => panic!("unexpected rollback"),
+                    Command::StartBundle(resp) => resp.send(()).unwrap(),
+                    Command::RollbackBundle(_) => panic!("unexpected bundle rollback"),
                     Command::FinishBatch(resp) => {
                         // Blanket result, it doesn't really matter.
                         resp.send(default_vm_batch_result()).unwrap();
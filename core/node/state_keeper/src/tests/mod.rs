@@ -8,6 +8,7 @@ use std::{
 
 use tokio::sync::watch;
 use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_dal::tx_allowlist_dal::TxAllowlistPermission;
 use zksync_multivm::{
     interface::{
         ExecutionResult, Halt, L1BatchEnv, L2BlockEnv, Refunds, SystemEnv, TxExecutionMode,
@@ -28,8 +29,9 @@ use zksync_types::{
 use zksync_utils::u256_to_h256;
 
 use crate::{
+    allowlist::{TxAllowlist, TxAllowlistCache},
     batch_executor::TxExecutionResult,
-    io::PendingBatchData,
+    io::{BundleTxPosition, PendingBatchData},
     keeper::POLL_WAIT_DURATION,
     seal_criteria::{
         criteria::{GasCriterion, SlotsCriterion},
@@ -38,8 +40,9 @@ use crate::{
     testonly::{
         successful_exec,
         test_batch_executor::{
-            random_tx, random_upgrade_tx, rejected_exec, successful_exec_with_metrics,
-            MockReadStorageFactory, TestBatchExecutorBuilder, TestIO, TestScenario, FEE_ACCOUNT,
+            random_tx, random_upgrade_tx, rejected_exec, successful_deploy_exec,
+            successful_exec_with_metrics, MockReadStorageFactory, TestBatchExecutorBuilder, TestIO,
+            TestScenario, FEE_ACCOUNT,
         },
         BASE_SYSTEM_CONTRACTS,
     },
@@ -332,6 +335,117 @@ async fn rejected_tx() {
         .await;
 }
 
+#[tokio::test]
+async fn rejecting_factory_deployment_in_allowlist_mode() {
+    let config = StateKeeperConfig {
+        transaction_slots: 2,
+        ..StateKeeperConfig::default()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let deployer = Address::repeat_byte(3);
+    let factory = Address::repeat_byte(4);
+    let tx_allowlist: TxAllowlist = [
+        (deployer, TxAllowlistPermission::Deployer),
+        (factory, TxAllowlistPermission::Contract),
+    ]
+    .into_iter()
+    .collect();
+
+    let factory_tx = random_tx(1);
+    TestScenario::new()
+        .with_tx_allowlist(TxAllowlistCache::from(tx_allowlist))
+        .seal_l2_block_when(|updates| updates.l2_block.executed_transactions.len() == 1)
+        .next_tx(
+            "Deployment via factory",
+            factory_tx.clone(),
+            successful_deploy_exec(factory),
+        )
+        .tx_rejected(
+            "Deployment via factory is rejected",
+            factory_tx,
+            UnexecutableReason::NotAllowlisted,
+        )
+        .next_tx(
+            "Deployment by deployer",
+            random_tx(2),
+            successful_deploy_exec(deployer),
+        )
+        .l2_block_sealed("L2 block with deployment")
+        .next_tx("Successful tx", random_tx(3), successful_exec())
+        .l2_block_sealed("Second L2 block")
+        .batch_sealed("Batch with 2 successful txs")
+        .run(sealer)
+        .await;
+}
+
+#[tokio::test]
+async fn bundle_is_rolled_back_if_last_tx_fails() {
+    let config = StateKeeperConfig {
+        transaction_slots: 10,
+        ..StateKeeperConfig::default()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let first_bundle_tx = random_tx(1);
+    let second_bundle_tx = random_tx(2);
+    let last_bundle_tx = random_tx(3);
+    let regular_tx = random_tx(4);
+    let regular_tx_hash = regular_tx.hash();
+    let position = |index| BundleTxPosition { index, len: 3 };
+    TestScenario::new()
+        .seal_l2_block_when(move |updates| {
+            updates
+                .l2_block
+                .executed_transactions
+                .iter()
+                .any(|tx| tx.hash == regular_tx_hash)
+        })
+        .seal_l1_batch_when(move |updates| {
+            updates
+                .l1_batch
+                .executed_transactions
+                .iter()
+                .any(|tx| tx.hash == regular_tx_hash)
+        })
+        .next_bundle_tx(
+            "First bundle tx",
+            first_bundle_tx,
+            successful_exec(),
+            position(0),
+        )
+        .next_bundle_tx(
+            "Second bundle tx",
+            second_bundle_tx,
+            successful_exec(),
+            position(1),
+        )
+        .next_bundle_tx(
+            "Last bundle tx",
+            last_bundle_tx.clone(),
+            rejected_exec(),
+            position(2),
+        )
+        .tx_rejected(
+            "Bundle got rejected because of the last tx",
+            last_bundle_tx,
+            UnexecutableReason::Halt(Halt::InnerTxError),
+        )
+        .next_tx("Regular tx", regular_tx, successful_exec())
+        .l2_block_sealed_with("L2 block without bundled txs", move |updates| {
+            let tx_hashes: Vec<_> = updates
+                .l2_block
+                .executed_transactions
+                .iter()
+                .map(|tx| tx.hash)
+                .collect();
+            assert_eq!(tx_hashes, [regular_tx_hash]);
+        })
+        .batch_sealed("Batch with the regular tx")
+        .run(sealer)
+        .await;
+}
+
 #[tokio::test]
 async fn bootloader_tip_out_of_gas_flow() {
     let config = StateKeeperConfig {
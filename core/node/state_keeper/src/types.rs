@@ -3,8 +3,9 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{Connection, Core, CoreDal};
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolLimits, MempoolStore};
 use zksync_multivm::interface::VmExecutionResultAndLogs;
 use zksync_types::{
     block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
@@ -19,12 +20,21 @@ use super::{
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
 impl MempoolGuard {
-    pub async fn from_storage(storage_processor: &mut Connection<'_, Core>, capacity: u64) -> Self {
+    pub async fn from_storage(
+        storage_processor: &mut Connection<'_, Core>,
+        config: &MempoolConfig,
+    ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(next_priority_id, capacity)
+        let limits = MempoolLimits {
+            max_pending_txs_per_account: config.max_pending_txs_per_account,
+            nonce_gap_ttl: config.nonce_gap_ttl(),
+            evict_low_fee_txs: config.evict_low_fee_txs,
+        };
+        let store = MempoolStore::new(next_priority_id, config.capacity).with_limits(limits);
+        Self(Arc::new(Mutex::new(store)))
     }
 
     pub(super) fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
@@ -194,6 +194,36 @@ impl UpdatesManager {
     pub(crate) fn pending_txs_encoding_size(&self) -> usize {
         self.l1_batch.txs_encoding_size + self.l2_block.txs_encoding_size
     }
+
+    /// Returns the number of L2 blocks in the pending L1 batch, including the currently open L2 block.
+    pub(crate) fn pending_l2_blocks_count(&self) -> usize {
+        self.l1_batch.l2_blocks_count + 1
+    }
+
+    /// Creates a checkpoint of the pending L2 block state. Used to discard partially executed transaction bundles.
+    pub(crate) fn checkpoint(&self) -> UpdatesCheckpoint {
+        UpdatesCheckpoint {
+            l2_block: self.l2_block.clone(),
+            storage_writes_deduplicator: self.storage_writes_deduplicator.clone(),
+        }
+    }
+
+    /// Restores the state from the checkpoint. The checkpoint must be created in the same L2 block.
+    pub(crate) fn restore(&mut self, checkpoint: UpdatesCheckpoint) {
+        assert_eq!(
+            checkpoint.l2_block.number, self.l2_block.number,
+            "Cannot restore a checkpoint from another L2 block"
+        );
+        self.l2_block = checkpoint.l2_block;
+        self.storage_writes_deduplicator = checkpoint.storage_writes_deduplicator;
+    }
+}
+
+/// Checkpoint of the [`UpdatesManager`] state created by [`UpdatesManager::checkpoint()`].
+#[derive(Debug)]
+pub(crate) struct UpdatesCheckpoint {
+    l2_block: L2BlockUpdates,
+    storage_writes_deduplicator: StorageWritesDeduplicator,
 }
 
 /// Command to seal an L2 block containing all necessary data for it.
@@ -256,4 +286,36 @@ mod tests {
         assert_eq!(updates_manager.l2_block.executed_transactions.len(), 0);
         assert_eq!(updates_manager.l1_batch.executed_transactions.len(), 1);
     }
+
+    #[test]
+    fn restoring_checkpoint() {
+        let mut updates_manager = create_updates_manager();
+        let tx = create_transaction(10, 100);
+        updates_manager.extend_from_executed_transaction(
+            tx,
+            create_execution_result([]),
+            vec![],
+            new_block_gas_count(),
+            ExecutionMetrics::default(),
+            vec![],
+        );
+        let checkpoint = updates_manager.checkpoint();
+
+        for _ in 0..2 {
+            let tx = create_transaction(10, 100);
+            updates_manager.extend_from_executed_transaction(
+                tx,
+                create_execution_result([]),
+                vec![],
+                new_block_gas_count(),
+                ExecutionMetrics::default(),
+                vec![],
+            );
+        }
+        assert_eq!(updates_manager.pending_executed_transactions_len(), 3);
+
+        updates_manager.restore(checkpoint);
+        assert_eq!(updates_manager.pending_executed_transactions_len(), 1);
+        assert_eq!(updates_manager.l2_block.executed_transactions.len(), 1);
+    }
 }
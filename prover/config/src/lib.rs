@@ -14,8 +14,8 @@ use zksync_config::{
         ObjectStoreConfig, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
         ProtectiveReadsWriterConfig,
     },
-    ApiConfig, ContractVerifierConfig, DBConfig, EthConfig, EthWatchConfig, GasAdjusterConfig,
-    PostgresConfig, SnapshotsCreatorConfig,
+    ApiConfig, BlockArchiverConfig, ContractVerifierConfig, DBConfig, EthConfig, EthWatchConfig,
+    GasAdjusterConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
 use zksync_core_leftovers::temp_config_store::{decode_yaml_repr, TempConfigStore};
 use zksync_env_config::FromEnv;
@@ -54,6 +54,7 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         basic_witness_input_producer_config: BasicWitnessInputProducerConfig::from_env().ok(),
         core_object_store: ObjectStoreConfig::from_env().ok(),
         base_token_adjuster_config: BaseTokenAdjusterConfig::from_env().ok(),
+        block_archiver_config: BlockArchiverConfig::from_env().ok(),
         commitment_generator: None,
         pruning: None,
         snapshot_recovery: None,
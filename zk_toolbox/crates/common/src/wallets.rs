@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Wallet {
     pub address: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub private_key: Option<H256>,
 }
 
@@ -4,6 +4,8 @@ use types::ChainId;
 pub(crate) const CONFIG_NAME: &str = "ZkStack.yaml";
 /// Name of the wallets file
 pub(crate) const WALLETS_FILE: &str = "wallets.yaml";
+/// Directory (relative to the configs directory) with encrypted operator keystores
+pub(crate) const KEYSTORES_DIR: &str = "keystores";
 /// Name of the secrets config file
 pub(crate) const SECRETS_FILE: &str = "secrets.yaml";
 /// Name of the general config file
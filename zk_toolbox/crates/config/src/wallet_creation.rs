@@ -6,7 +6,7 @@ use types::WalletCreation;
 use xshell::Shell;
 
 use crate::{
-    consts::{BASE_PATH, TEST_CONFIG_PATH},
+    consts::{BASE_PATH, KEYSTORES_DIR, TEST_CONFIG_PATH},
     traits::{ReadConfig, SaveConfigWithBasePath},
     EthMnemonicConfig, WalletsConfig,
 };
@@ -18,8 +18,9 @@ pub fn create_wallets(
     id: u32,
     wallet_creation: WalletCreation,
     initial_wallet_path: Option<PathBuf>,
+    keystore_password_env: Option<&str>,
 ) -> anyhow::Result<()> {
-    let wallets = match wallet_creation {
+    let mut wallets = match wallet_creation {
         WalletCreation::Random => {
             let rng = &mut thread_rng();
             WalletsConfig::random(rng)
@@ -34,6 +35,10 @@ pub fn create_wallets(
             WalletsConfig::read(shell, path)?
         }
     };
+    if let Some(password_env) = keystore_password_env {
+        let keystores_dir = shell.current_dir().join(base_path).join(KEYSTORES_DIR);
+        wallets.encrypt_operator_keys(&keystores_dir, password_env)?;
+    }
 
     wallets.save_with_base_path(shell, base_path)?;
     Ok(())
@@ -58,5 +63,6 @@ pub fn create_localhost_wallets(
         blob_operator: Wallet::from_mnemonic(&eth_mnemonic.test_mnemonic, &base_path, 2)?,
         fee_account: Wallet::from_mnemonic(&eth_mnemonic.test_mnemonic, &base_path, 3)?,
         governor: Wallet::from_mnemonic(&eth_mnemonic.test_mnemonic, &base_path, 4)?,
+        keystores: None,
     })
 }
@@ -1,6 +1,12 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
 use common::wallets::Wallet;
-use ethers::types::H256;
-use rand::Rng;
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::{Address, H256},
+};
+use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -15,6 +21,23 @@ pub struct WalletsConfig {
     pub blob_operator: Wallet,
     pub fee_account: Wallet,
     pub governor: Wallet,
+    /// Encrypted keystores with operator keys. If set, the server reads operator keys from them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystores: Option<KeystoresConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoresConfig {
+    pub operator: KeystoreWallet,
+    pub blob_operator: KeystoreWallet,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreWallet {
+    pub address: Address,
+    pub path: PathBuf,
+    /// Env variable with the keystore password.
+    pub password_env: String,
 }
 
 impl WalletsConfig {
@@ -26,6 +49,7 @@ impl WalletsConfig {
             blob_operator: Wallet::random(rng),
             fee_account: Wallet::random(rng),
             governor: Wallet::random(rng),
+            keystores: None,
         }
     }
 
@@ -37,8 +61,70 @@ impl WalletsConfig {
             blob_operator: Wallet::empty(),
             fee_account: Wallet::empty(),
             governor: Wallet::empty(),
+            keystores: None,
         }
     }
+
+    /// Moves operator private keys into encrypted keystores in `dir`. The password is read
+    /// from the `password_env` env variable; the server reads it from the same variable.
+    pub fn encrypt_operator_keys(&mut self, dir: &Path, password_env: &str) -> anyhow::Result<()> {
+        let password = std::env::var(password_env).with_context(|| {
+            format!("Keystore password env variable `{password_env}` is not set")
+        })?;
+        std::fs::create_dir_all(dir)?;
+
+        let encrypt = |wallet: &mut Wallet, name: &str| -> anyhow::Result<KeystoreWallet> {
+            let private_key = wallet
+                .private_key
+                .take()
+                .with_context(|| format!("Private key for {name} is missing"))?;
+            let file_name = format!("{name}.json");
+            let (local_wallet, _) = LocalWallet::encrypt_keystore(
+                dir,
+                &mut thread_rng(),
+                private_key.as_bytes(),
+                &password,
+                Some(&file_name),
+            )?;
+            anyhow::ensure!(
+                local_wallet.address() == wallet.address,
+                "Private key for {name} doesn't correspond to its address"
+            );
+            Ok(KeystoreWallet {
+                address: wallet.address,
+                path: dir.join(file_name),
+                password_env: password_env.to_owned(),
+            })
+        };
+
+        self.keystores = Some(KeystoresConfig {
+            operator: encrypt(&mut self.operator, "operator")?,
+            blob_operator: encrypt(&mut self.blob_operator, "blob_operator")?,
+        });
+        Ok(())
+    }
+
+    /// Returns the operator wallet with its private key, decrypting it from the keystore
+    /// if operator keys are encrypted.
+    pub fn operator_wallet(&self) -> anyhow::Result<Wallet> {
+        let Some(keystores) = &self.keystores else {
+            return Ok(self.operator.clone());
+        };
+        let keystore = &keystores.operator;
+        let password = std::env::var(&keystore.password_env).with_context(|| {
+            format!(
+                "Keystore password env variable `{}` is not set",
+                keystore.password_env
+            )
+        })?;
+        let local_wallet = LocalWallet::decrypt_keystore(&keystore.path, password)
+            .with_context(|| format!("Failed decrypting keystore {:?}", keystore.path))?;
+        Ok(Wallet {
+            address: keystore.address,
+            private_key: Some(H256::from_slice(&local_wallet.signer().to_bytes())),
+        })
+    }
+
     pub fn deployer_private_key(&self) -> Option<H256> {
         self.deployer.as_ref().and_then(|wallet| wallet.private_key)
     }
@@ -15,11 +15,12 @@ use crate::{
         MSG_BASE_TOKEN_PRICE_DENOMINATOR_HELP, MSG_BASE_TOKEN_PRICE_DENOMINATOR_PROMPT,
         MSG_BASE_TOKEN_PRICE_NOMINATOR_HELP, MSG_BASE_TOKEN_PRICE_NOMINATOR_PROMPT,
         MSG_BASE_TOKEN_SELECTION_PROMPT, MSG_CHAIN_ID_PROMPT, MSG_CHAIN_NAME_PROMPT,
-        MSG_L1_BATCH_COMMIT_DATA_GENERATOR_MODE_PROMPT, MSG_L1_COMMIT_DATA_GENERATOR_MODE_HELP,
-        MSG_NUMBER_VALIDATOR_GREATHER_THAN_ZERO_ERR, MSG_NUMBER_VALIDATOR_NOT_ZERO_ERR,
-        MSG_PROVER_MODE_HELP, MSG_PROVER_VERSION_PROMPT, MSG_SET_AS_DEFAULT_HELP,
-        MSG_SET_AS_DEFAULT_PROMPT, MSG_WALLET_CREATION_HELP, MSG_WALLET_CREATION_PROMPT,
-        MSG_WALLET_PATH_HELP, MSG_WALLET_PATH_INVALID_ERR, MSG_WALLET_PATH_PROMPT,
+        MSG_KEYSTORE_PASSWORD_ENV_HELP, MSG_L1_BATCH_COMMIT_DATA_GENERATOR_MODE_PROMPT,
+        MSG_L1_COMMIT_DATA_GENERATOR_MODE_HELP, MSG_NUMBER_VALIDATOR_GREATHER_THAN_ZERO_ERR,
+        MSG_NUMBER_VALIDATOR_NOT_ZERO_ERR, MSG_PROVER_MODE_HELP, MSG_PROVER_VERSION_PROMPT,
+        MSG_SET_AS_DEFAULT_HELP, MSG_SET_AS_DEFAULT_PROMPT, MSG_WALLET_CREATION_HELP,
+        MSG_WALLET_CREATION_PROMPT, MSG_WALLET_PATH_HELP, MSG_WALLET_PATH_INVALID_ERR,
+        MSG_WALLET_PATH_PROMPT,
     },
 };
 
@@ -35,6 +36,8 @@ pub struct ChainCreateArgs {
     pub wallet_creation: Option<WalletCreation>,
     #[clap(long, help = MSG_WALLET_PATH_HELP)]
     pub wallet_path: Option<PathBuf>,
+    #[clap(long, help = MSG_KEYSTORE_PASSWORD_ENV_HELP)]
+    pub keystore_password_env: Option<String>,
     #[clap(long, help = MSG_L1_COMMIT_DATA_GENERATOR_MODE_HELP)]
     pub l1_batch_commit_data_generator_mode: Option<L1BatchCommitDataGeneratorMode>,
     #[clap(long, help = MSG_BASE_TOKEN_ADDRESS_HELP)]
@@ -141,6 +144,7 @@ impl ChainCreateArgs {
             wallet_creation,
             l1_batch_commit_data_generator_mode,
             wallet_path,
+            keystore_password_env: self.keystore_password_env,
             base_token,
             set_as_default,
         }
@@ -155,6 +159,7 @@ pub struct ChainCreateArgsFinal {
     pub wallet_creation: WalletCreation,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitDataGeneratorMode,
     pub wallet_path: Option<PathBuf>,
+    pub keystore_password_env: Option<String>,
     pub base_token: BaseToken,
     pub set_as_default: bool,
 }
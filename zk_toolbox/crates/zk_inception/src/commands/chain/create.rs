@@ -82,6 +82,7 @@ pub(crate) fn create_chain_inner(
         chain_id,
         args.wallet_creation,
         args.wallet_path,
+        args.keystore_password_env.as_deref(),
     )?;
 
     chain_config.save_with_base_path(shell, chain_path)?;
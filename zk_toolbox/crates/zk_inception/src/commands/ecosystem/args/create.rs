@@ -68,6 +68,7 @@ impl EcosystemCreateArgs {
             link_to_code,
             wallet_creation: chain.wallet_creation,
             wallet_path: chain.wallet_path.clone(),
+            keystore_password_env: chain.keystore_password_env.clone(),
             chain_args: chain,
             start_containers,
         }
@@ -81,6 +82,7 @@ pub struct EcosystemCreateArgsFinal {
     pub link_to_code: String,
     pub wallet_creation: WalletCreation,
     pub wallet_path: Option<PathBuf>,
+    pub keystore_password_env: Option<String>,
     pub chain_args: ChainCreateArgsFinal,
     pub start_containers: bool,
 }
@@ -93,6 +93,7 @@ fn create(args: EcosystemCreateArgs, shell: &Shell) -> anyhow::Result<()> {
         0,
         args.wallet_creation,
         args.wallet_path,
+        args.keystore_password_env.as_deref(),
     )?;
     ecosystem_config.save_with_base_path(shell, ".")?;
     spinner.finish();
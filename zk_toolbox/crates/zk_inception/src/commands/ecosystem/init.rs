@@ -147,7 +147,7 @@ pub async fn distribute_eth(
             addresses.push(deployer.address)
         }
         common::ethereum::distribute_eth(
-            wallets.operator,
+            wallets.operator_wallet()?,
             addresses,
             l1_rpc_url,
             ecosystem_config.l1_network.chain_id(),
@@ -89,6 +89,8 @@ pub(super) fn msg_chain_doesnt_exist_err(chain_name: &str, chains: &Vec<String>)
 pub(super) const MSG_PROVER_MODE_HELP: &str = "Prover options";
 pub(super) const MSG_WALLET_CREATION_HELP: &str = "Wallet options";
 pub(super) const MSG_WALLET_PATH_HELP: &str = "Wallet path";
+pub(super) const MSG_KEYSTORE_PASSWORD_ENV_HELP: &str =
+    "Store operator keys in encrypted keystores, with the password taken from this env variable";
 pub(super) const MSG_L1_COMMIT_DATA_GENERATOR_MODE_HELP: &str = "Commit data generation mode";
 pub(super) const MSG_BASE_TOKEN_ADDRESS_HELP: &str = "Base token address";
 pub(super) const MSG_BASE_TOKEN_PRICE_NOMINATOR_HELP: &str = "Base token nominator";